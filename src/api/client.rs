@@ -191,9 +191,21 @@ impl ApiContext {
 
     /// Make a GET request
     pub fn get(&self, endpoint: &str) -> Result<minreq::Response, GitAiError> {
+        self.get_with_headers(endpoint, &[])
+    }
+
+    /// Make a GET request with extra headers (e.g. `If-None-Match` for conditional requests)
+    pub fn get_with_headers(
+        &self,
+        endpoint: &str,
+        headers: &[(&str, &str)],
+    ) -> Result<minreq::Response, GitAiError> {
         let url = self.build_url(endpoint)?;
 
         let mut request = Self::http_get(&url);
+        for (name, value) in headers {
+            request = request.with_header(*name, *value);
+        }
 
         // Add authentication header if token is present
         if let Some(token) = &self.auth_token {
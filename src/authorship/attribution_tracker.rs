@@ -3,7 +3,9 @@
 //! This library maintains attribution ranges as files are edited, preserving
 //! authorship information even through moves, edits, and whitespace changes.
 
-use crate::authorship::imara_diff_utils::{ByteDiff, ByteDiffOp, DiffOp, capture_diff_slices};
+use crate::authorship::imara_diff_utils::{
+    ByteDiff, ByteDiffOp, DiffAlgorithm, DiffOp, capture_diff_slices, capture_diff_slices_with,
+};
 use crate::authorship::move_detection::{DeletedLine, InsertedLine, detect_moves};
 use crate::authorship::working_log::CheckpointKind;
 use crate::error::GitAiError;
@@ -270,12 +272,15 @@ struct DiffComputation {
 /// Configuration for the attribution tracker
 pub struct AttributionConfig {
     move_lines_threshold: usize,
+    /// Line-level algorithm used to match old and new content.
+    diff_algorithm: DiffAlgorithm,
 }
 
 impl Default for AttributionConfig {
     fn default() -> Self {
         AttributionConfig {
             move_lines_threshold: 3,
+            diff_algorithm: DiffAlgorithm::default(),
         }
     }
 }
@@ -286,10 +291,14 @@ pub struct AttributionTracker {
 }
 
 impl AttributionTracker {
-    /// Create a new attribution tracker with default configuration
+    /// Create a new attribution tracker with default configuration and the user's
+    /// configured diff algorithm
     pub fn new() -> Self {
         AttributionTracker {
-            config: AttributionConfig::default(),
+            config: AttributionConfig {
+                diff_algorithm: crate::config::Config::get().diff_algorithm(),
+                ..AttributionConfig::default()
+            },
         }
     }
 
@@ -323,10 +332,15 @@ impl AttributionTracker {
             .map(|line| &new_content[line.start..line.end])
             .collect();
 
-        let line_ops = capture_diff_slices(&old_line_slices, &new_line_slices);
+        let line_ops = capture_diff_slices_with(
+            &old_line_slices,
+            &new_line_slices,
+            self.config.diff_algorithm,
+        );
         let line_ops_len = line_ops.len();
         debug_log(&format!(
-            "[BENCHMARK] capture_diff_slices produced {} ops in {:?}",
+            "[BENCHMARK] capture_diff_slices ({}) produced {} ops in {:?}",
+            self.config.diff_algorithm.as_str(),
             line_ops_len,
             capture_start.elapsed()
         ));
@@ -2395,6 +2409,7 @@ mod tests {
         let tracker = AttributionTracker::with_config(AttributionConfig {
             // Test with a one-line threshold
             move_lines_threshold: 1,
+            ..AttributionConfig::default()
         });
         let old = "fn helper() { println!(\"helper\"); }\nfn main() { println!(\"main\"); }\n";
         let new = "fn main() { println!(\"main\"); }\nfn helper() { println!(\"helper\"); }\n";
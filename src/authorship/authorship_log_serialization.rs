@@ -27,6 +27,40 @@ pub struct AuthorshipMetadata {
     pub git_ai_version: Option<String>,
     pub base_commit_sha: String,
     pub prompts: BTreeMap<String, PromptRecord>,
+    /// Whole-file AI-generated assets (file path -> prompt hash) that have no line attestations
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub assets: BTreeMap<String, String>,
+    /// Lines AI tools deleted in this commit (file path -> prompt hash -> line ranges in the
+    /// first parent's version of the file). Deleted lines not listed here were removed by the
+    /// human author
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub deletions: BTreeMap<String, BTreeMap<String, String>>,
+    /// Lines written by both the human and AI tools (file path -> line -> prompt hash ->
+    /// percent of the line's non-whitespace characters). The human wrote the remainder
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub line_shares: BTreeMap<String, BTreeMap<u32, BTreeMap<String, u8>>>,
+    /// AI lines handed over to the human after the fact (file path -> transfers). The prompt
+    /// record stays in `prompts` so the original AI authorship remains on record
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub reassignments: BTreeMap<String, Vec<Reassignment>>,
+}
+
+/// Lines whose attestation moved from an AI prompt to the human
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Reassignment {
+    /// Prompt hash the lines were attested to
+    pub prompt: String,
+    /// Line ranges in this commit's version of the file
+    pub lines: String,
+    /// Who the lines now belong to (always "human" for now)
+    pub to: String,
+    /// Who made the transfer
+    pub by: String,
+    /// "manual" for `git-ai reassign`, "rewrite" when the commit found the human had rewritten
+    /// more than `reassign_threshold` percent of the line
+    pub reason: String,
+    /// Unix timestamp (seconds) of the transfer
+    pub timestamp: u64,
 }
 
 impl AuthorshipMetadata {
@@ -36,6 +70,10 @@ impl AuthorshipMetadata {
             git_ai_version: Some(GIT_AI_VERSION.to_string()),
             base_commit_sha: String::new(),
             prompts: BTreeMap::new(),
+            assets: BTreeMap::new(),
+            deletions: BTreeMap::new(),
+            line_shares: BTreeMap::new(),
+            reassignments: BTreeMap::new(),
         }
     }
 }
@@ -504,7 +542,7 @@ impl Default for AuthorshipLog {
 
 /// Format line ranges as comma-separated values with ranges as "start-end"
 /// Sorts ranges first: Single ranges by their value, Range ones by their lowest bound
-fn format_line_ranges(ranges: &[LineRange]) -> String {
+pub(crate) fn format_line_ranges(ranges: &[LineRange]) -> String {
     let mut sorted_ranges = ranges.to_vec();
     sorted_ranges.sort_by(|a, b| {
         let a_start = match a {
@@ -530,7 +568,7 @@ fn format_line_ranges(ranges: &[LineRange]) -> String {
 
 /// Parse line ranges from a string like "1,2,19-222"
 /// No spaces are expected in the format
-fn parse_line_ranges(input: &str) -> Result<Vec<LineRange>, Box<dyn std::error::Error>> {
+pub(crate) fn parse_line_ranges(input: &str) -> Result<Vec<LineRange>, Box<dyn std::error::Error>> {
     let mut ranges = Vec::new();
 
     for part in input.split(',') {
@@ -0,0 +1,348 @@
+//! Deletion provenance: which AI tool removed lines, not only who added them.
+//!
+//! AI checkpoints keep the content of the lines they delete. At commit time those lines are
+//! matched against the lines the commit removed from its first parent, and the matches are
+//! written to the authorship log's `deletions` metadata (file -> prompt hash -> line ranges
+//! in the parent). Removed lines with no matching AI deletion were deleted by the human.
+
+use crate::authorship::authorship_log::{LineRange, PromptRecord};
+use crate::authorship::authorship_log_serialization::{
+    AuthorshipLog, format_line_ranges, generate_short_hash, parse_line_ranges,
+};
+use crate::authorship::imara_diff_utils::{self, unchanged_line_map};
+use crate::authorship::post_commit::prompt_record_for_checkpoint;
+use crate::authorship::virtual_attribution::get_file_content_at_commit;
+use crate::authorship::working_log::{Checkpoint, CheckpointKind};
+use crate::error::GitAiError;
+use crate::git::diff_tree_to_tree::DiffStatus;
+use crate::git::refs::get_authorship;
+use crate::git::repository::Repository;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+/// A line a commit removed from its first parent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeletedLine {
+    /// Path in the parent commit
+    pub file: String,
+    /// 1-based line number in the parent's version of the file
+    pub line: u32,
+    pub content: String,
+    /// Prompt that deleted the line, or None when the human author did
+    pub prompt_hash: Option<String>,
+}
+
+/// Record the removed lines of `commit_sha` that an AI checkpoint deleted.
+pub fn attach_ai_deletions(
+    repo: &Repository,
+    parent_sha: &str,
+    commit_sha: &str,
+    checkpoints: &[Checkpoint],
+    human_author: &str,
+    authorship_log: &mut AuthorshipLog,
+) -> Result<(), GitAiError> {
+    // file -> deleted content -> checkpoints that deleted it, oldest first
+    let mut deleted_by: HashMap<&str, HashMap<&str, VecDeque<usize>>> = HashMap::new();
+    for (idx, checkpoint) in checkpoints.iter().enumerate() {
+        if checkpoint.kind == CheckpointKind::Human || checkpoint.agent_id.is_none() {
+            continue;
+        }
+        for entry in &checkpoint.entries {
+            for line in &entry.deleted_lines {
+                deleted_by
+                    .entry(entry.file.as_str())
+                    .or_default()
+                    .entry(line.as_str())
+                    .or_default()
+                    .push_back(idx);
+            }
+        }
+    }
+    if deleted_by.is_empty() {
+        return Ok(());
+    }
+
+    for (old_path, new_path) in changed_file_pairs(repo, parent_sha, commit_sha, None)? {
+        let Some(file_deletions) =
+            deleted_by.get_mut(new_path.as_deref().unwrap_or(old_path.as_str()))
+        else {
+            continue;
+        };
+
+        let mut lines_by_checkpoint: BTreeMap<usize, Vec<u32>> = BTreeMap::new();
+        for (line, content) in
+            removed_lines(repo, parent_sha, commit_sha, &old_path, new_path.as_deref())?
+        {
+            if let Some(idx) = file_deletions
+                .get_mut(content.as_str())
+                .and_then(VecDeque::pop_front)
+            {
+                lines_by_checkpoint.entry(idx).or_default().push(line);
+            }
+        }
+
+        for (idx, lines) in lines_by_checkpoint {
+            let checkpoint = &checkpoints[idx];
+            let Some(agent_id) = checkpoint.agent_id.as_ref() else {
+                continue;
+            };
+            let prompt_hash = generate_short_hash(&agent_id.id, &agent_id.tool);
+            authorship_log
+                .metadata
+                .prompts
+                .entry(prompt_hash.clone())
+                .or_insert_with(|| {
+                    prompt_record_for_checkpoint(checkpoint, agent_id, human_author)
+                });
+
+            add_deleted_lines(authorship_log, &old_path, prompt_hash, lines);
+        }
+    }
+
+    Ok(())
+}
+
+/// Carry the AI deletions `source_log` records for `source_commit` over to `target_log`, the
+/// log of `target_commit` that rewrites it (rebase, amend, cherry-pick or squash).
+///
+/// Deleted lines are numbered in the first parent, so each one is followed from the source's
+/// parent to the target's and kept only if the target commit still removes it. Lines the
+/// target already credits to a prompt keep that prompt.
+pub fn carry_ai_deletions(
+    repo: &Repository,
+    source_commit: &str,
+    source_log: &AuthorshipLog,
+    target_commit: &str,
+    target_log: &mut AuthorshipLog,
+) -> Result<(), GitAiError> {
+    if source_log.metadata.deletions.is_empty() {
+        return Ok(());
+    }
+    let (Ok(source_parent), Ok(target_parent)) = (
+        repo.find_commit(source_commit.to_string())?.parent(0),
+        repo.find_commit(target_commit.to_string())?.parent(0),
+    ) else {
+        return Ok(());
+    };
+    let (source_parent, target_parent) = (source_parent.id(), target_parent.id());
+
+    for (old_path, new_path) in changed_file_pairs(repo, &target_parent, target_commit, None)? {
+        let Some(by_prompt) = source_log.metadata.deletions.get(&old_path) else {
+            continue;
+        };
+        let removed: HashSet<u32> = removed_lines(
+            repo,
+            &target_parent,
+            target_commit,
+            &old_path,
+            new_path.as_deref(),
+        )?
+        .into_iter()
+        .map(|(line, _)| line)
+        .collect();
+        let line_map = if source_parent == target_parent {
+            None
+        } else {
+            Some(unchanged_line_map(
+                &get_file_content_at_commit(repo, &source_parent, &old_path)?,
+                &get_file_content_at_commit(repo, &target_parent, &old_path)?,
+            ))
+        };
+        let claimed: HashSet<u32> = target_log
+            .metadata
+            .deletions
+            .get(&old_path)
+            .into_iter()
+            .flat_map(|by_prompt| by_prompt.values())
+            .flat_map(|ranges| parse_line_ranges(ranges).unwrap_or_default())
+            .flat_map(|range| range.expand())
+            .collect();
+
+        for (hash, ranges) in by_prompt {
+            let lines: Vec<u32> = parse_line_ranges(ranges)
+                .unwrap_or_default()
+                .iter()
+                .flat_map(LineRange::expand)
+                .filter_map(|line| match &line_map {
+                    Some(map) => map.get(&line).copied(),
+                    None => Some(line),
+                })
+                .filter(|line| removed.contains(line) && !claimed.contains(line))
+                .collect();
+            if lines.is_empty() {
+                continue;
+            }
+            if let Some(record) = source_log.metadata.prompts.get(hash) {
+                target_log
+                    .metadata
+                    .prompts
+                    .entry(hash.clone())
+                    .or_insert_with(|| record.clone());
+            }
+            add_deleted_lines(target_log, &old_path, hash.clone(), lines);
+        }
+    }
+    Ok(())
+}
+
+/// Credit `lines` (in the parent's version of `file`) to `prompt_hash` in the log's
+/// deletions, merging with the lines it already has.
+fn add_deleted_lines(
+    authorship_log: &mut AuthorshipLog,
+    file: &str,
+    prompt_hash: String,
+    lines: Vec<u32>,
+) {
+    let by_prompt = authorship_log
+        .metadata
+        .deletions
+        .entry(file.to_string())
+        .or_default();
+    let mut all_lines: Vec<u32> = by_prompt
+        .get(&prompt_hash)
+        .and_then(|ranges| parse_line_ranges(ranges).ok())
+        .unwrap_or_default()
+        .iter()
+        .flat_map(LineRange::expand)
+        .collect();
+    all_lines.extend(lines);
+    all_lines.sort_unstable();
+    all_lines.dedup();
+    by_prompt.insert(
+        prompt_hash,
+        format_line_ranges(&LineRange::compress_lines(&all_lines)),
+    );
+}
+
+/// The lines a commit removed from its first parent.
+#[derive(Debug, Clone)]
+pub struct CommitDeletions {
+    pub commit_sha: String,
+    /// Author of the commit, credited with deletions no AI tool claimed
+    pub author: String,
+    /// Prompts referenced by `lines`
+    pub prompts: BTreeMap<String, PromptRecord>,
+    pub lines: Vec<DeletedLine>,
+}
+
+impl CommitDeletions {
+    /// Who deleted `line`: `tool (model)` for AI deletions, otherwise the commit author.
+    pub fn deleted_by(&self, line: &DeletedLine) -> String {
+        match line
+            .prompt_hash
+            .as_ref()
+            .and_then(|hash| self.prompts.get(hash))
+        {
+            Some(prompt) => format!("{} ({})", prompt.agent_id.tool, prompt.agent_id.model),
+            None => self.author.clone(),
+        }
+    }
+}
+
+/// Every line `commit_sha` removed from its first parent (limited to `pathspecs`), with the
+/// prompt that deleted it.
+///
+/// Root commits have nothing to delete.
+pub fn commit_deletions(
+    repo: &Repository,
+    commit_sha: &str,
+    pathspecs: Option<&HashSet<String>>,
+) -> Result<CommitDeletions, GitAiError> {
+    let commit = repo.find_commit(commit_sha.to_string())?;
+    let mut deletions = CommitDeletions {
+        commit_sha: commit_sha.to_string(),
+        author: commit.author()?.name().unwrap_or("unknown").to_string(),
+        prompts: BTreeMap::new(),
+        lines: Vec::new(),
+    };
+    let Ok(parent) = commit.parent(0) else {
+        return Ok(deletions);
+    };
+    let parent_sha = parent.id();
+
+    // file -> line -> prompt hash
+    let mut ai_deleted: HashMap<String, HashMap<u32, String>> = HashMap::new();
+    if let Some(log) = get_authorship(repo, commit_sha) {
+        for (file, by_prompt) in log.metadata.deletions {
+            let lines = ai_deleted.entry(file).or_default();
+            for (hash, ranges) in by_prompt {
+                if let Some(prompt) = log.metadata.prompts.get(&hash) {
+                    deletions.prompts.insert(hash.clone(), prompt.clone());
+                }
+                for range in parse_line_ranges(&ranges).unwrap_or_default() {
+                    for line in range.expand() {
+                        lines.insert(line, hash.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    for (old_path, new_path) in changed_file_pairs(repo, &parent_sha, commit_sha, pathspecs)? {
+        let prompts = ai_deleted.get(&old_path);
+        for (line, content) in removed_lines(
+            repo,
+            &parent_sha,
+            commit_sha,
+            &old_path,
+            new_path.as_deref(),
+        )? {
+            deletions.lines.push(DeletedLine {
+                file: old_path.clone(),
+                line,
+                content,
+                prompt_hash: prompts.and_then(|p| p.get(&line)).cloned(),
+            });
+        }
+    }
+    Ok(deletions)
+}
+
+/// (path in parent, path in commit) for files that can have lost lines. Deleted files have
+/// no path in the commit.
+fn changed_file_pairs(
+    repo: &Repository,
+    parent_sha: &str,
+    commit_sha: &str,
+    pathspecs: Option<&HashSet<String>>,
+) -> Result<Vec<(String, Option<String>)>, GitAiError> {
+    let parent_tree = repo.find_commit(parent_sha.to_string())?.tree()?;
+    let commit_tree = repo.find_commit(commit_sha.to_string())?.tree()?;
+    let diff = repo.diff_tree_to_tree(Some(&parent_tree), Some(&commit_tree), None, pathspecs)?;
+
+    let mut pairs = Vec::new();
+    for delta in diff.deltas() {
+        let old_path = delta
+            .old_file()
+            .path()
+            .map(|p| p.to_string_lossy().to_string());
+        let new_path = delta
+            .new_file()
+            .path()
+            .map(|p| p.to_string_lossy().to_string());
+        match (delta.status(), old_path) {
+            (DiffStatus::Modified | DiffStatus::Renamed, Some(old_path)) => {
+                pairs.push((old_path, new_path))
+            }
+            (DiffStatus::Deleted, Some(old_path)) => pairs.push((old_path, None)),
+            _ => {}
+        }
+    }
+    Ok(pairs)
+}
+
+/// (line number in the parent, content) of each line removed from `old_path`.
+fn removed_lines(
+    repo: &Repository,
+    parent_sha: &str,
+    commit_sha: &str,
+    old_path: &str,
+    new_path: Option<&str>,
+) -> Result<Vec<(u32, String)>, GitAiError> {
+    let old_content = get_file_content_at_commit(repo, parent_sha, old_path)?;
+    let new_content = match new_path {
+        Some(path) => get_file_content_at_commit(repo, commit_sha, path)?,
+        None => String::new(),
+    };
+
+    Ok(imara_diff_utils::removed_lines(&old_content, &new_content))
+}
@@ -1,12 +1,16 @@
 use std::collections::{BTreeMap, HashMap};
 
 use crate::authorship::ignore::{build_ignore_matcher, should_ignore_file_with_matcher};
+use crate::authorship::line_filter::LineFilter;
+use crate::authorship::model_names::tool_model_key;
 use crate::commands::blame::GitAiBlameOptions;
 use crate::error::GitAiError;
 use crate::git::repository::Repository;
 
 #[derive(Debug, Default)]
 pub struct DiffAiAcceptedStats {
+    /// Added lines that passed the line filter
+    pub counted_lines: u32,
     pub total_ai_accepted: u32,
     pub per_tool_model: BTreeMap<String, u32>,
     pub per_prompt: BTreeMap<String, u32>,
@@ -18,6 +22,8 @@ pub fn diff_ai_accepted_stats(
     to_ref: &str,
     oldest_commit: Option<&str>,
     ignore_patterns: &[String],
+    line_filter: &LineFilter,
+    raw_models: bool,
 ) -> Result<DiffAiAcceptedStats, GitAiError> {
     let added_lines_by_file = repo.diff_added_lines(from_ref, to_ref, None)?;
     let ignore_matcher = build_ignore_matcher(ignore_patterns);
@@ -35,6 +41,8 @@ pub fn diff_ai_accepted_stats(
 
         lines.sort_unstable();
         lines.dedup();
+        line_filter.retain_committed_lines(repo, to_ref, &file_path, &mut lines);
+        stats.counted_lines += lines.len() as u32;
         let line_ranges = lines_to_ranges(&lines);
 
         if line_ranges.is_empty() {
@@ -59,7 +67,8 @@ pub fn diff_ai_accepted_stats(
 
         let mut prompt_tool_map: HashMap<String, String> = HashMap::new();
         for (hash, record) in &prompt_records {
-            let tool_model = format!("{}::{}", record.agent_id.tool, record.agent_id.model);
+            let tool_model =
+                tool_model_key(&record.agent_id.tool, &record.agent_id.model, raw_models);
             prompt_tool_map.insert(hash.clone(), tool_model);
         }
 
@@ -180,6 +189,7 @@ mod tests {
     #[test]
     fn test_diff_ai_accepted_stats_debug() {
         let stats = DiffAiAcceptedStats {
+            counted_lines: 12,
             total_ai_accepted: 10,
             per_tool_model: BTreeMap::new(),
             per_prompt: BTreeMap::new(),
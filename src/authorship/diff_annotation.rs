@@ -0,0 +1,403 @@
+//! Annotates a commit's unified diff with the authorship of every added line, taken from
+//! the commit's authorship log. Used by `git-ai log` and `git-ai show --patch`.
+
+use crate::authorship::authorship_log::{LineRange, PromptRecord};
+use crate::authorship::authorship_log_serialization::AuthorshipLog;
+use crate::error::GitAiError;
+use crate::git::refs::get_authorship;
+use crate::git::repository::{Repository, exec_git};
+use std::collections::{BTreeMap, HashMap};
+
+/// Which prompt (if any) wrote each line of each file in a commit.
+#[derive(Debug, Default)]
+pub struct LineAuthorshipIndex {
+    files: HashMap<String, Vec<(LineRange, String)>>,
+    prompts: BTreeMap<String, PromptRecord>,
+    has_log: bool,
+}
+
+impl LineAuthorshipIndex {
+    pub fn from_authorship_log(log: &AuthorshipLog) -> Self {
+        let mut files: HashMap<String, Vec<(LineRange, String)>> = HashMap::new();
+        for attestation in &log.attestations {
+            let ranges = files.entry(attestation.file_path.clone()).or_default();
+            for entry in &attestation.entries {
+                for range in &entry.line_ranges {
+                    ranges.push((range.clone(), entry.hash.clone()));
+                }
+            }
+        }
+        LineAuthorshipIndex {
+            files,
+            prompts: log.metadata.prompts.clone(),
+            has_log: true,
+        }
+    }
+
+    /// The prompt that wrote `line` (1-indexed, in the commit's version of `file`).
+    pub fn prompt_for_line(&self, file: &str, line: u32) -> Option<(&str, &PromptRecord)> {
+        let hash = self.hash_for_line(file, line)?;
+        self.prompts.get(hash).map(|record| (hash, record))
+    }
+
+    /// The prompt hash attested for `line`, whether or not the log has its prompt record.
+    pub fn hash_for_line(&self, file: &str, line: u32) -> Option<&str> {
+        self.files
+            .get(file)?
+            .iter()
+            .find(|(range, _)| range.contains(line))
+            .map(|(_, hash)| hash.as_str())
+    }
+
+    /// Whether the commit has an authorship log at all.
+    pub fn has_log(&self) -> bool {
+        self.has_log
+    }
+}
+
+/// Per-commit totals over the added lines of an annotated diff.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct DiffAnnotationSummary {
+    pub added_lines: u32,
+    pub ai_lines: u32,
+    /// AI lines per (tool, model)
+    pub ai_lines_by_agent: BTreeMap<(String, String), u32>,
+    pub has_log: bool,
+}
+
+impl DiffAnnotationSummary {
+    pub fn ai_percent(&self) -> u32 {
+        if self.added_lines == 0 {
+            return 0;
+        }
+        ((self.ai_lines as f64 / self.added_lines as f64) * 100.0).round() as u32
+    }
+
+    /// e.g. "62% AI (claude-sonnet-4 via cursor)"
+    pub fn summary_line(&self) -> String {
+        if !self.has_log {
+            return "No AI authorship data".to_string();
+        }
+        if self.ai_lines == 0 {
+            return "0% AI".to_string();
+        }
+
+        let mut agents: Vec<(&(String, String), &u32)> = self.ai_lines_by_agent.iter().collect();
+        agents.sort_by(|a, b| b.1.cmp(a.1).then_with(|| a.0.cmp(b.0)));
+        let agents: Vec<String> = agents
+            .into_iter()
+            .map(|((tool, model), _)| agent_label(tool, model))
+            .collect();
+        format!("{}% AI ({})", self.ai_percent(), agents.join(", "))
+    }
+}
+
+/// e.g. "claude-sonnet-4 via cursor", or just the tool when the model is unknown.
+pub fn agent_label(tool: &str, model: &str) -> String {
+    if model.is_empty() || model == "unknown" {
+        tool.to_string()
+    } else {
+        format!("{} via {}", model, tool)
+    }
+}
+
+/// Render `diff_text` (unified diff, no color) with a marker after every added line:
+/// `🤖tool (model)` for AI lines and `👤human_label` for the rest.
+pub fn annotate_diff(
+    diff_text: &str,
+    index: &LineAuthorshipIndex,
+    human_label: &str,
+    use_color: bool,
+) -> (String, DiffAnnotationSummary) {
+    let mut summary = DiffAnnotationSummary {
+        has_log: index.has_log(),
+        ..Default::default()
+    };
+    let mut output = String::new();
+
+    walk_diff(diff_text, |line, kind| match kind {
+        DiffLine::Header => push_line(&mut output, line, HEADER, use_color),
+        DiffLine::Hunk => push_line(&mut output, line, HUNK, use_color),
+        DiffLine::Added { file, line_num } => {
+            summary.added_lines += 1;
+            let marker = match index.prompt_for_line(file, line_num) {
+                Some((_, record)) => {
+                    summary.ai_lines += 1;
+                    *summary
+                        .ai_lines_by_agent
+                        .entry((record.agent_id.tool.clone(), record.agent_id.model.clone()))
+                        .or_insert(0) += 1;
+                    ai_marker(record)
+                }
+                None => format!("👤{}", human_label),
+            };
+            if use_color {
+                output.push_str(&format!(
+                    "\x1b[32m{}\x1b[0m  \x1b[2m{}\x1b[0m\n",
+                    line, marker
+                ));
+            } else {
+                output.push_str(&format!("{}  {}\n", line, marker));
+            }
+        }
+        DiffLine::Removed => push_line(&mut output, line, DELETION, use_color),
+        DiffLine::Context => push_line(&mut output, line, None, use_color),
+        DiffLine::Other { in_hunk } => {
+            let color = if in_hunk { None } else { HEADER };
+            push_line(&mut output, line, color, use_color);
+        }
+    });
+
+    (output, summary)
+}
+
+const HEADER: Option<&str> = Some("\x1b[1m");
+const HUNK: Option<&str> = Some("\x1b[36m");
+const DELETION: Option<&str> = Some("\x1b[31m");
+
+fn push_line(output: &mut String, line: &str, color: Option<&str>, use_color: bool) {
+    match color {
+        Some(color) if use_color => output.push_str(&format!("{}{}\x1b[0m\n", color, line)),
+        _ => {
+            output.push_str(line);
+            output.push('\n');
+        }
+    }
+}
+
+fn ai_marker(record: &PromptRecord) -> String {
+    let model = &record.agent_id.model;
+    if model.is_empty() || model == "unknown" {
+        format!("🤖{}", record.agent_id.tool)
+    } else {
+        format!("🤖{} ({})", record.agent_id.tool, model)
+    }
+}
+
+/// Line numbers (in the new version) of the lines each file gains in a unified diff.
+pub fn added_lines_by_file(diff_text: &str) -> BTreeMap<String, Vec<u32>> {
+    let mut added: BTreeMap<String, Vec<u32>> = BTreeMap::new();
+    walk_diff(diff_text, |_, kind| {
+        if let DiffLine::Added { file, line_num } = kind {
+            added.entry(file.to_string()).or_default().push(line_num);
+        }
+    });
+    added
+}
+
+/// What a line of a unified diff is, as seen by [`walk_diff`].
+enum DiffLine<'a> {
+    /// `diff --git` and `+++` lines
+    Header,
+    Hunk,
+    /// An added line, numbered in the new version of `file`
+    Added {
+        file: &'a str,
+        line_num: u32,
+    },
+    Removed,
+    Context,
+    /// Extended headers, "\ No newline at end of file", binary markers
+    Other {
+        in_hunk: bool,
+    },
+}
+
+/// Call `visit` with every line of `diff_text` (unified diff, no color) and its kind,
+/// tracking the current file and new-version line numbers.
+fn walk_diff(diff_text: &str, mut visit: impl FnMut(&str, DiffLine<'_>)) {
+    let mut current_file = String::new();
+    let mut new_line_num = 0u32;
+    let mut in_hunk = false;
+
+    for line in diff_text.lines() {
+        if line.starts_with("diff --git") {
+            in_hunk = false;
+            current_file.clear();
+            visit(line, DiffLine::Header);
+        } else if !in_hunk && let Some(path) = line.strip_prefix("+++ ") {
+            let path = crate::utils::unescape_git_path(path.trim_end());
+            current_file = path.strip_prefix("b/").unwrap_or(&path).to_string();
+            visit(line, DiffLine::Header);
+        } else if let Some(new_start) = parse_hunk_new_start(line) {
+            in_hunk = true;
+            new_line_num = new_start;
+            visit(line, DiffLine::Hunk);
+        } else if in_hunk && line.starts_with('+') {
+            visit(
+                line,
+                DiffLine::Added {
+                    file: &current_file,
+                    line_num: new_line_num,
+                },
+            );
+            new_line_num += 1;
+        } else if in_hunk && line.starts_with('-') {
+            visit(line, DiffLine::Removed);
+        } else if in_hunk && line.starts_with(' ') {
+            new_line_num += 1;
+            visit(line, DiffLine::Context);
+        } else {
+            visit(line, DiffLine::Other { in_hunk });
+        }
+    }
+}
+
+/// Parse the new-file start line out of `@@ -a,b +c,d @@`.
+fn parse_hunk_new_start(line: &str) -> Option<u32> {
+    let rest = line.strip_prefix("@@ ")?;
+    let new_part = rest.split_whitespace().find(|part| part.starts_with('+'))?;
+    new_part[1..].split(',').next()?.parse().ok()
+}
+
+/// Render one commit the way `git show` does (header, message, patch), with a summary line
+/// of its AI share after the message and every added line annotated.
+pub fn annotated_commit(
+    repo: &Repository,
+    commit_sha: &str,
+    pathspecs: &[String],
+    use_color: bool,
+) -> Result<String, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.extend(
+        ["show", "-s", "--no-color", "--format=medium", commit_sha]
+            .iter()
+            .map(|s| s.to_string()),
+    );
+    let header = String::from_utf8_lossy(&exec_git(&args)?.stdout).to_string();
+
+    let mut args = repo.global_args_for_exec();
+    args.extend(
+        ["show", "-s", "--format=%an", commit_sha]
+            .iter()
+            .map(|s| s.to_string()),
+    );
+    let author_name = String::from_utf8_lossy(&exec_git(&args)?.stdout)
+        .trim()
+        .to_string();
+
+    let (annotated, summary) =
+        annotate_commit_diff(repo, commit_sha, &author_name, pathspecs, use_color)?;
+
+    let mut output = header.trim_end().to_string();
+    output.push_str("\n\n    ");
+    if use_color {
+        output.push_str(&format!("\x1b[1;35m{}\x1b[0m", summary.summary_line()));
+    } else {
+        output.push_str(&summary.summary_line());
+    }
+    output.push('\n');
+    if !annotated.is_empty() {
+        output.push('\n');
+        output.push_str(&annotated);
+    }
+    Ok(output)
+}
+
+/// AI share of the lines a commit adds, without rendering its diff. Used by
+/// `git-ai log --graph` and `git-ai log --format json`.
+pub fn commit_annotation_summary(
+    repo: &Repository,
+    commit_sha: &str,
+    pathspecs: &[String],
+) -> Result<DiffAnnotationSummary, GitAiError> {
+    annotate_commit_diff(repo, commit_sha, "", pathspecs, false).map(|(_, summary)| summary)
+}
+
+fn annotate_commit_diff(
+    repo: &Repository,
+    commit_sha: &str,
+    human_label: &str,
+    pathspecs: &[String],
+    use_color: bool,
+) -> Result<(String, DiffAnnotationSummary), GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.extend(
+        [
+            "diff-tree",
+            "-p",
+            "-M",
+            "--root",
+            "--no-color",
+            "--no-commit-id",
+            commit_sha,
+        ]
+        .iter()
+        .map(|s| s.to_string()),
+    );
+    if !pathspecs.is_empty() {
+        args.push("--".to_string());
+        args.extend(pathspecs.iter().cloned());
+    }
+    let diff_text = String::from_utf8_lossy(&exec_git(&args)?.stdout).to_string();
+
+    let index = get_authorship(repo, commit_sha)
+        .map(|log| LineAuthorshipIndex::from_authorship_log(&log))
+        .unwrap_or_default();
+    Ok(annotate_diff(&diff_text, &index, human_label, use_color))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authorship::authorship_log_serialization::{AttestationEntry, FileAttestation};
+    use crate::authorship::working_log::AgentId;
+
+    fn prompt(tool: &str, model: &str) -> PromptRecord {
+        PromptRecord {
+            agent_id: AgentId {
+                tool: tool.to_string(),
+                id: "session".to_string(),
+                model: model.to_string(),
+            },
+            human_author: None,
+            messages: Vec::new(),
+            total_additions: 0,
+            total_deletions: 0,
+            accepted_lines: 0,
+            overriden_lines: 0,
+            messages_url: None,
+        }
+    }
+
+    #[test]
+    fn test_annotate_diff_marks_added_lines_and_summarizes() {
+        let mut log = AuthorshipLog::new();
+        let mut attestation = FileAttestation::new("src/app.rs".to_string());
+        attestation.add_entry(AttestationEntry::new(
+            "abc1234".to_string(),
+            vec![LineRange::Range(2, 3)],
+        ));
+        log.attestations.push(attestation);
+        log.metadata
+            .prompts
+            .insert("abc1234".to_string(), prompt("cursor", "claude-sonnet-4"));
+        let index = LineAuthorshipIndex::from_authorship_log(&log);
+
+        let diff = "diff --git a/src/app.rs b/src/app.rs\n\
+                    --- a/src/app.rs\n\
+                    +++ b/src/app.rs\n\
+                    @@ -1,2 +1,4 @@\n\
+                    \x20fn main() {\n\
+                    +    let a = 1;\n\
+                    +    let b = 2;\n\
+                    -    old();\n\
+                    +    new();\n\
+                    \x20}\n";
+        let (output, summary) = annotate_diff(diff, &index, "Alice", false);
+
+        assert!(output.contains("+    let a = 1;  🤖cursor (claude-sonnet-4)"));
+        assert!(output.contains("+    let b = 2;  🤖cursor (claude-sonnet-4)"));
+        assert!(output.contains("+    new();  👤Alice"));
+        assert!(output.contains("-    old();\n"));
+        assert_eq!(summary.added_lines, 3);
+        assert_eq!(summary.ai_lines, 2);
+        assert_eq!(
+            summary.summary_line(),
+            "67% AI (claude-sonnet-4 via cursor)"
+        );
+
+        let (_, no_log) = annotate_diff(diff, &LineAuthorshipIndex::default(), "Alice", false);
+        assert_eq!(no_log.summary_line(), "No AI authorship data");
+    }
+}
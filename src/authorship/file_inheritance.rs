@@ -0,0 +1,449 @@
+//! Carry AI attribution across files when a commit moves code between them.
+//!
+//! git blame follows whole-file renames on its own, but lines moved into a *new* file
+//! (a split, or a block extracted from a file that stays behind) or folded into another
+//! file (several files merged into one) are blamed to the commit that moved them. Without
+//! help, that commit's authorship log records them as human-written. This module matches
+//! those lines back to the sources they were removed from and re-attests them with the
+//! source line's prompt.
+
+use crate::authorship::authorship_log::{LineRange, PromptRecord};
+use crate::authorship::authorship_log_serialization::{AttestationEntry, AuthorshipLog};
+use crate::authorship::imara_diff_utils::{inserted_line_numbers, removed_line_numbers};
+use crate::authorship::virtual_attribution::get_file_content_at_commit;
+use crate::commands::blame::GitAiBlameOptions;
+use crate::error::GitAiError;
+use crate::git::diff_tree_to_tree::DiffStatus;
+use crate::git::repository::Repository;
+use crate::utils::debug_log;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Minimum fraction of a destination's new distinctive lines that must come from the
+/// sources (together, for merges) before attribution is inherited.
+const MOVE_SIMILARITY_THRESHOLD: f64 = 0.5;
+
+/// Lines with fewer alphanumeric characters (`}`, `pass`, `else {`) occur everywhere, so
+/// they neither count as moved nor inherit attribution.
+const MIN_DISTINCTIVE_CHARS: usize = 6;
+
+/// Bail out on sweeping commits: each candidate source costs a blame at the parent.
+const MAX_MOVE_SOURCES: usize = 50;
+
+/// Lines removed from a source file in this commit.
+struct MovedSource {
+    path: String,
+    /// Path of the file in the commit when it was renamed
+    renamed_to: Option<String>,
+    /// Trimmed content of every removed distinctive line, used for similarity.
+    removed: HashSet<String>,
+    /// Line numbers (at the parent) of the removed distinctive lines.
+    removed_line_numbers: Vec<u32>,
+    /// Trimmed content of removed AI lines -> prompt hash, filled in by blaming the
+    /// source once a destination matched it.
+    ai_lines: Option<HashMap<String, String>>,
+}
+
+/// A file that gained lines matching removed lines of some sources.
+struct MoveDestination {
+    path: String,
+    /// Line number and trimmed content of the distinctive lines this commit introduced.
+    candidate_lines: Vec<(u32, String)>,
+    /// Indices into the sources whose removed lines the candidates resemble.
+    matched: Vec<usize>,
+}
+
+impl MovedSource {
+    /// Whether `path` is this source file, under its old or new name.
+    fn is_file(&self, path: &str) -> bool {
+        self.path == path || self.renamed_to.as_deref() == Some(path)
+    }
+}
+
+/// Attest lines that `commit_sha` moved between files, using the prompt that wrote them at
+/// `parent_sha`.
+///
+/// Handles splits (one source, several new files) and merges (several sources folded into
+/// one new or existing file). Lines the working log already attributed are left alone.
+pub fn inherit_moved_file_attributions(
+    repo: &Repository,
+    parent_sha: &str,
+    commit_sha: &str,
+    authorship_log: &mut AuthorshipLog,
+) -> Result<(), GitAiError> {
+    let parent_tree = repo.find_commit(parent_sha.to_string())?.tree()?;
+    let commit_tree = repo.find_commit(commit_sha.to_string())?.tree()?;
+    let diff = repo.diff_tree_to_tree(Some(&parent_tree), Some(&commit_tree), None, None)?;
+
+    let mut source_paths: Vec<(String, Option<String>)> = Vec::new();
+    let mut destination_paths: Vec<(String, bool)> = Vec::new();
+    for delta in diff.deltas() {
+        let old_path = delta
+            .old_file()
+            .path()
+            .map(|p| p.to_string_lossy().to_string());
+        let new_path = delta
+            .new_file()
+            .path()
+            .map(|p| p.to_string_lossy().to_string());
+        match delta.status() {
+            DiffStatus::Added => destination_paths.extend(new_path.map(|p| (p, true))),
+            DiffStatus::Deleted => source_paths.extend(old_path.map(|p| (p, None))),
+            DiffStatus::Modified => {
+                // A modified file can both lose lines (split) and absorb them (merge)
+                source_paths.extend(new_path.clone().map(|p| (p, None)));
+                destination_paths.extend(new_path.map(|p| (p, false)));
+            }
+            // blame already follows the renamed file itself; its old content can still
+            // have been split into other files
+            DiffStatus::Renamed => source_paths.extend(old_path.map(|p| (p, new_path))),
+            _ => {}
+        }
+    }
+
+    if source_paths.is_empty() || destination_paths.is_empty() {
+        return Ok(());
+    }
+    if source_paths.len() > MAX_MOVE_SOURCES {
+        debug_log(&format!(
+            "Skipping moved-line inheritance for {}: {} candidate sources",
+            commit_sha,
+            source_paths.len()
+        ));
+        return Ok(());
+    }
+
+    let mut sources = Vec::new();
+    for (path, renamed_to) in source_paths {
+        if let Some(source) = load_moved_source(repo, parent_sha, commit_sha, path, renamed_to)? {
+            sources.push(source);
+        }
+    }
+    if sources.is_empty() {
+        return Ok(());
+    }
+
+    // Match destinations to sources by content first; only matched sources get blamed
+    let mut moves: Vec<MoveDestination> = Vec::new();
+    for (destination, is_new) in destination_paths {
+        let content = get_file_content_at_commit(repo, commit_sha, &destination)?;
+        let lines: Vec<&str> = content.lines().collect();
+        // Only lines this commit introduced can have been moved in
+        let candidates: Vec<u32> = if is_new {
+            (1..=lines.len() as u32).collect()
+        } else {
+            let old_content = get_file_content_at_commit(repo, parent_sha, &destination)?;
+            inserted_line_numbers(&old_content, &content)
+        };
+        let candidate_lines: Vec<(u32, &str)> = candidates
+            .into_iter()
+            .filter_map(|n| lines.get(n as usize - 1).map(|line| (n, line.trim())))
+            .filter(|(_, line)| is_distinctive(line))
+            .collect();
+
+        // A file can't be a move destination for its own lines
+        let other_sources: Vec<(usize, &MovedSource)> = sources
+            .iter()
+            .enumerate()
+            .filter(|(_, source)| !source.is_file(&destination))
+            .collect();
+        let matched = matching_sources(&candidate_lines, &other_sources);
+        if matched.is_empty() {
+            continue;
+        }
+        let candidate_lines = candidate_lines
+            .into_iter()
+            .map(|(n, line)| (n, line.to_string()))
+            .collect();
+        moves.push(MoveDestination {
+            path: destination,
+            candidate_lines,
+            matched,
+        });
+    }
+    if moves.is_empty() {
+        return Ok(());
+    }
+
+    let mut prompts: HashMap<String, PromptRecord> = HashMap::new();
+    for MoveDestination {
+        path: destination,
+        candidate_lines,
+        matched,
+    } in moves
+    {
+        for &index in &matched {
+            if sources[index].ai_lines.is_none() {
+                let ai_lines =
+                    blame_removed_ai_lines(repo, parent_sha, &sources[index], &mut prompts)?;
+                sources[index].ai_lines = Some(ai_lines);
+            }
+        }
+        let matched: Vec<&MovedSource> = matched.iter().map(|&index| &sources[index]).collect();
+
+        let already_attested = attested_lines(authorship_log, &destination);
+        let mut by_hash: BTreeMap<String, Vec<u32>> = BTreeMap::new();
+        for (line_number, text) in &candidate_lines {
+            if already_attested.contains(line_number) {
+                continue;
+            }
+            if let Some(hash) = matched
+                .iter()
+                .filter_map(|source| source.ai_lines.as_ref())
+                .find_map(|ai_lines| ai_lines.get(text))
+            {
+                by_hash.entry(hash.clone()).or_default().push(*line_number);
+            }
+        }
+        if by_hash.is_empty() {
+            continue;
+        }
+
+        debug_log(&format!(
+            "Inheriting AI attribution for {} from {}",
+            destination,
+            matched
+                .iter()
+                .map(|source| source.path.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+        for (hash, lines) in by_hash {
+            if let Some(record) = prompts.get(&hash) {
+                authorship_log
+                    .metadata
+                    .prompts
+                    .entry(hash.clone())
+                    .or_insert_with(|| record.clone());
+            }
+            authorship_log
+                .get_or_create_file(&destination)
+                .add_entry(AttestationEntry::new(
+                    hash,
+                    LineRange::compress_lines(&lines),
+                ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Whether `line` (trimmed) is specific enough to tell where it was moved from.
+fn is_distinctive(line: &str) -> bool {
+    line.chars().filter(|c| c.is_alphanumeric()).count() >= MIN_DISTINCTIVE_CHARS
+}
+
+/// The distinctive lines `commit_sha` removed from `path`, or `None` when it removed none.
+fn load_moved_source(
+    repo: &Repository,
+    parent_sha: &str,
+    commit_sha: &str,
+    path: String,
+    renamed_to: Option<String>,
+) -> Result<Option<MovedSource>, GitAiError> {
+    let old_content = get_file_content_at_commit(repo, parent_sha, &path)?;
+    let new_content = match &renamed_to {
+        Some(new_path) => get_file_content_at_commit(repo, commit_sha, new_path)?,
+        None => get_file_content_at_commit(repo, commit_sha, &path)?,
+    };
+
+    let old_lines: Vec<&str> = old_content.lines().collect();
+    let mut removed = HashSet::new();
+    let mut distinctive_line_numbers = Vec::new();
+    for line_number in removed_line_numbers(&old_content, &new_content) {
+        let Some(text) = old_lines.get(line_number as usize - 1).map(|l| l.trim()) else {
+            continue;
+        };
+        if is_distinctive(text) {
+            removed.insert(text.to_string());
+            distinctive_line_numbers.push(line_number);
+        }
+    }
+    if distinctive_line_numbers.is_empty() {
+        return Ok(None);
+    }
+
+    Ok(Some(MovedSource {
+        path,
+        renamed_to,
+        removed,
+        removed_line_numbers: distinctive_line_numbers,
+        ai_lines: None,
+    }))
+}
+
+/// Trimmed content -> prompt hash of the removed lines of `source` that were AI-attributed
+/// at `parent_sha`, collecting their prompt records into `prompts`.
+fn blame_removed_ai_lines(
+    repo: &Repository,
+    parent_sha: &str,
+    source: &MovedSource,
+    prompts: &mut HashMap<String, PromptRecord>,
+) -> Result<HashMap<String, String>, GitAiError> {
+    let blame_options = GitAiBlameOptions {
+        no_output: true,
+        return_human_authors_as_human: true,
+        use_prompt_hashes_as_names: true,
+        newest_commit: Some(parent_sha.to_string()),
+        ..Default::default()
+    };
+    let Ok((line_authors, line_prompts)) = repo.blame(&source.path, &blame_options) else {
+        return Ok(HashMap::new());
+    };
+
+    let old_content = get_file_content_at_commit(repo, parent_sha, &source.path)?;
+    let old_lines: Vec<&str> = old_content.lines().collect();
+    let mut ai_lines = HashMap::new();
+    for &line_number in &source.removed_line_numbers {
+        let Some(text) = old_lines.get(line_number as usize - 1).map(|l| l.trim()) else {
+            continue;
+        };
+        if let Some(author) = line_authors.get(&line_number)
+            && line_prompts.contains_key(author)
+        {
+            ai_lines
+                .entry(text.to_string())
+                .or_insert_with(|| author.clone());
+        }
+    }
+
+    for (hash, record) in line_prompts {
+        prompts.entry(hash).or_insert(record);
+    }
+    Ok(ai_lines)
+}
+
+/// Indices of the sources that lines of `lines` were moved from, most contributing first,
+/// or none when too few of the lines came from elsewhere to call it a split or merge.
+fn matching_sources(lines: &[(u32, &str)], sources: &[(usize, &MovedSource)]) -> Vec<usize> {
+    if lines.is_empty() {
+        return Vec::new();
+    }
+
+    let moved = lines
+        .iter()
+        .filter(|(_, line)| {
+            sources
+                .iter()
+                .any(|(_, source)| source.removed.contains(*line))
+        })
+        .count();
+    if (moved as f64 / lines.len() as f64) < MOVE_SIMILARITY_THRESHOLD {
+        return Vec::new();
+    }
+
+    let mut contributing: Vec<(usize, usize)> = sources
+        .iter()
+        .map(|(index, source)| {
+            let matched = lines
+                .iter()
+                .filter(|(_, line)| source.removed.contains(*line))
+                .count();
+            (*index, matched)
+        })
+        .filter(|(_, matched)| *matched > 0)
+        .collect();
+    contributing.sort_by_key(|(_, matched)| std::cmp::Reverse(*matched));
+    contributing.into_iter().map(|(index, _)| index).collect()
+}
+
+fn attested_lines(authorship_log: &AuthorshipLog, file: &str) -> HashSet<u32> {
+    authorship_log
+        .attestations
+        .iter()
+        .filter(|attestation| attestation.file_path == file)
+        .flat_map(|attestation| attestation.entries.iter())
+        .flat_map(|entry| entry.line_ranges.iter())
+        .flat_map(|range| range.expand())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn source(path: &str, removed: &[&str]) -> MovedSource {
+        MovedSource {
+            path: path.to_string(),
+            renamed_to: None,
+            removed: removed.iter().map(|l| l.to_string()).collect(),
+            removed_line_numbers: Vec::new(),
+            ai_lines: None,
+        }
+    }
+
+    fn numbered<'a>(lines: &[&'a str]) -> Vec<(u32, &'a str)> {
+        lines
+            .iter()
+            .enumerate()
+            .map(|(i, line)| (i as u32 + 1, *line))
+            .collect()
+    }
+
+    fn indexed(sources: &[MovedSource]) -> Vec<(usize, &MovedSource)> {
+        sources.iter().enumerate().collect()
+    }
+
+    #[test]
+    fn matching_sources_requires_majority_of_lines() {
+        let sources = [
+            source("big.py", &["def alpha():", "return 'alpha'"]),
+            source("other.py", &["def beta():"]),
+        ];
+        let sources = indexed(&sources);
+
+        let split = matching_sources(&numbered(&["def alpha():", "return 'alpha'"]), &sources);
+        assert_eq!(split, vec![0]);
+
+        assert!(
+            matching_sources(
+                &numbered(&["def alpha():", "first = 1", "second = 2"]),
+                &sources
+            )
+            .is_empty()
+        );
+        assert!(matching_sources(&[], &sources).is_empty());
+    }
+
+    #[test]
+    fn matching_sources_combines_merged_files() {
+        let sources = [
+            source("first.py", &["def first():", "return 'first'"]),
+            source(
+                "second.py",
+                &["def second():", "return 'second'", "print(second)"],
+            ),
+        ];
+
+        // Neither source alone is a majority of the merged file, together they are
+        let merged = matching_sources(
+            &numbered(&[
+                "def first():",
+                "return 'first'",
+                "def second():",
+                "return 'second'",
+                "print(second)",
+                "counter = 1",
+            ]),
+            &indexed(&sources),
+        );
+        assert_eq!(merged, vec![1, 0]);
+    }
+
+    #[test]
+    fn short_lines_are_not_distinctive() {
+        for line in ["}", "pass", "else {", "break;", "i += 1", "// ====="] {
+            assert!(!is_distinctive(line), "{}", line);
+        }
+        assert!(is_distinctive("def parse(value):"));
+        assert!(is_distinctive("return value"));
+    }
+
+    #[test]
+    fn moved_source_matches_its_renamed_path() {
+        let mut renamed = source("old.py", &["def alpha():"]);
+        renamed.renamed_to = Some("new.py".to_string());
+        assert!(renamed.is_file("old.py"));
+        assert!(renamed.is_file("new.py"));
+        assert!(!renamed.is_file("other.py"));
+    }
+}
@@ -0,0 +1,301 @@
+//! Graft attribution from an old history onto a recreated one.
+//!
+//! Teams that recreate history (a repository split with a new root, a filter-repo rewrite,
+//! `git replace` grafts) end up with commits that carry none of the old authorship notes. File
+//! contents survive such rewrites unchanged, so a blob in the new history that also appears
+//! in the old one has the same lines, and each of those lines keeps the attribution blame
+//! gives it at the old commit. Every new commit without a note gets one covering the lines it
+//! added to files whose blob matches, whatever path the file has now.
+
+use crate::authorship::authorship_log::LineRange;
+use crate::authorship::authorship_log_serialization::{AttestationEntry, AuthorshipLog};
+use crate::commands::blame::GitAiBlameOptions;
+use crate::error::GitAiError;
+use crate::git::refs::{commits_with_authorship_notes, notes_add};
+use crate::git::repository::{Repository, exec_git};
+use crate::utils::debug_log;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+const EMPTY_TREE_HASH: &str = "4b825dc642cb6eb9a060e54bf8d69288fbee4904";
+const NULL_OID: &str = "0000000000000000000000000000000000000000";
+
+/// A file in a new commit whose blob was found in the old history.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraftedFile {
+    pub path: String,
+    /// Commit and path the blob was blamed at
+    pub old_commit: String,
+    pub old_path: String,
+    pub ai_lines: u32,
+}
+
+/// A new commit that received a grafted authorship note.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraftedCommit {
+    pub commit: String,
+    pub files: Vec<GraftedFile>,
+}
+
+impl GraftedCommit {
+    pub fn ai_lines(&self) -> u32 {
+        self.files.iter().map(|file| file.ai_lines).sum()
+    }
+}
+
+/// Graft attribution from the history of `from` onto the commits of `onto` that are not in
+/// it and have no authorship note. Returns the commits that gained AI attributions; with
+/// `dry_run` nothing is written.
+pub fn graft_attribution(
+    repo: &Repository,
+    from: &str,
+    onto: &str,
+    dry_run: bool,
+) -> Result<Vec<GraftedCommit>, GitAiError> {
+    let from = repo.revparse_single(from)?.id();
+    let onto = repo.revparse_single(onto)?.id();
+    let old_blobs = blobs_in_history(repo, &from)?;
+
+    let mut args = repo.global_args_for_exec();
+    args.extend(["rev-list", "--reverse", &onto, "--not", &from].map(str::to_string));
+    let candidates: Vec<String> = String::from_utf8(exec_git(&args)?.stdout)?
+        .lines()
+        .map(str::to_string)
+        .collect();
+    let noted = commits_with_authorship_notes(repo, &candidates)?;
+
+    let mut blame_cache: HashMap<(String, String), AuthorshipLog> = HashMap::new();
+    let mut grafted = Vec::new();
+    for commit in candidates.iter().filter(|commit| !noted.contains(*commit)) {
+        let matches: Vec<(String, &(String, String))> = changed_blobs(repo, commit)?
+            .into_iter()
+            .filter_map(|(path, blob)| old_blobs.get(&blob).map(|old| (path, old)))
+            .collect();
+        if matches.is_empty() {
+            continue;
+        }
+
+        let parent = repo
+            .find_commit(commit.clone())?
+            .parents()
+            .next()
+            .map(|parent| parent.id())
+            .unwrap_or_else(|| EMPTY_TREE_HASH.to_string());
+        let pathspecs: HashSet<String> = matches.iter().map(|(path, _)| path.clone()).collect();
+        let added = repo.diff_added_lines(&parent, commit, Some(&pathspecs))?;
+
+        let mut log = AuthorshipLog::new();
+        log.metadata.base_commit_sha = commit.clone();
+        let mut files = Vec::new();
+        for (path, (old_commit, old_path)) in matches {
+            let Some(added_lines) = added.get(&path) else {
+                continue;
+            };
+            let key = (old_commit.clone(), old_path.clone());
+            if !blame_cache.contains_key(&key) {
+                blame_cache.insert(key.clone(), blame_at(repo, old_commit, old_path)?);
+            }
+            let old_log = &blame_cache[&key];
+            let Some(attestation) = old_log.attestations.first() else {
+                continue;
+            };
+
+            let mut ai_lines = 0u32;
+            for entry in &attestation.entries {
+                let lines: Vec<u32> = added_lines
+                    .iter()
+                    .copied()
+                    .filter(|line| entry.line_ranges.iter().any(|range| range.contains(*line)))
+                    .collect();
+                if lines.is_empty() {
+                    continue;
+                }
+                ai_lines += lines.len() as u32;
+                if let Some(prompt) = old_log.metadata.prompts.get(&entry.hash) {
+                    log.metadata
+                        .prompts
+                        .insert(entry.hash.clone(), prompt.clone());
+                }
+                log.get_or_create_file(&path)
+                    .add_entry(AttestationEntry::new(
+                        entry.hash.clone(),
+                        LineRange::compress_lines(&lines),
+                    ));
+            }
+            if ai_lines > 0 {
+                files.push(GraftedFile {
+                    path,
+                    old_commit: old_commit.clone(),
+                    old_path: old_path.clone(),
+                    ai_lines,
+                });
+            }
+        }
+        if files.is_empty() {
+            continue;
+        }
+
+        if !dry_run {
+            let note = log.serialize_to_string().map_err(|_| {
+                GitAiError::Generic("Failed to serialize authorship log".to_string())
+            })?;
+            notes_add(repo, commit, &note)?;
+        }
+        debug_log(&format!(
+            "Grafted attribution for {} files onto {}",
+            files.len(),
+            commit
+        ));
+        grafted.push(GraftedCommit {
+            commit: commit.clone(),
+            files,
+        });
+    }
+    Ok(grafted)
+}
+
+/// Every blob written by a commit reachable from `tip`, mapped to the newest commit and path
+/// that wrote it.
+fn blobs_in_history(
+    repo: &Repository,
+    tip: &str,
+) -> Result<HashMap<String, (String, String)>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.extend(
+        [
+            "log",
+            "--raw",
+            "--no-renames",
+            "--no-abbrev",
+            "-z",
+            "--format=%x00commit %H",
+            tip,
+        ]
+        .map(str::to_string),
+    );
+    let output = String::from_utf8(exec_git(&args)?.stdout)?;
+
+    let mut blobs = HashMap::new();
+    let mut commit = String::new();
+    let mut fields = output.split('\0').filter(|field| !field.is_empty());
+    while let Some(field) = fields.next() {
+        // The commit header and the first raw entry after it share a field
+        for segment in field.split('\n') {
+            if let Some(sha) = segment.strip_prefix("commit ") {
+                commit = sha.trim().to_string();
+            } else if segment.starts_with(':') {
+                let Some(path) = fields.next() else {
+                    return Ok(blobs);
+                };
+                if let Some(blob) = raw_new_blob(segment) {
+                    blobs
+                        .entry(blob)
+                        .or_insert_with(|| (commit.clone(), path.to_string()));
+                }
+            }
+        }
+    }
+    Ok(blobs)
+}
+
+/// Paths and new blobs of the files `commit` added or modified relative to its first parent.
+fn changed_blobs(repo: &Repository, commit: &str) -> Result<Vec<(String, String)>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.extend(
+        [
+            "diff-tree",
+            "-r",
+            "--root",
+            "--raw",
+            "--no-renames",
+            "--no-abbrev",
+            "--no-commit-id",
+            "-z",
+            commit,
+        ]
+        .map(str::to_string),
+    );
+    let output = String::from_utf8(exec_git(&args)?.stdout)?;
+
+    let mut changed = Vec::new();
+    let mut fields = output.split('\0').filter(|field| !field.is_empty());
+    while let Some(field) = fields.next() {
+        if !field.starts_with(':') {
+            continue;
+        }
+        let Some(path) = fields.next() else {
+            break;
+        };
+        if let Some(blob) = raw_new_blob(field) {
+            changed.push((path.to_string(), blob));
+        }
+    }
+    Ok(changed)
+}
+
+/// New blob of a `--raw` diff line (`:<mode> <mode> <old> <new> <status>`), unless the file
+/// was deleted or isn't a regular file.
+fn raw_new_blob(raw: &str) -> Option<String> {
+    let parts: Vec<&str> = raw.trim_start_matches(':').split_whitespace().collect();
+    let (new_mode, new_blob) = (parts.get(1)?, parts.get(3)?);
+    if *new_blob == NULL_OID || !new_mode.starts_with("100") {
+        return None;
+    }
+    Some(new_blob.to_string())
+}
+
+/// AI attribution of every line of `path` at `commit`, as a single-file authorship log.
+fn blame_at(repo: &Repository, commit: &str, path: &str) -> Result<AuthorshipLog, GitAiError> {
+    let options = GitAiBlameOptions {
+        newest_commit: Some(commit.to_string()),
+        use_prompt_hashes_as_names: true,
+        return_human_authors_as_human: true,
+        no_output: true,
+        ..Default::default()
+    };
+    let (line_authors, prompts) = repo.blame(path, &options)?;
+
+    let mut lines_by_prompt: BTreeMap<&str, Vec<u32>> = BTreeMap::new();
+    for (line, author) in &line_authors {
+        if prompts.contains_key(author) {
+            lines_by_prompt.entry(author).or_default().push(*line);
+        }
+    }
+
+    let mut log = AuthorshipLog::new();
+    for (hash, mut lines) in lines_by_prompt {
+        lines.sort_unstable();
+        log.get_or_create_file(path)
+            .add_entry(AttestationEntry::new(
+                hash.to_string(),
+                LineRange::compress_lines(&lines),
+            ));
+        if let Some(prompt) = prompts.get(hash) {
+            log.metadata
+                .prompts
+                .insert(hash.to_string(), prompt.clone());
+        }
+    }
+    Ok(log)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_raw_new_blob_skips_deletions_and_non_files() {
+        let blob = "a".repeat(40);
+        assert_eq!(
+            raw_new_blob(&format!(":100644 100644 {} {} M", NULL_OID, blob)),
+            Some(blob.clone())
+        );
+        assert_eq!(
+            raw_new_blob(&format!(":100644 000000 {} {} D", blob, NULL_OID)),
+            None
+        );
+        assert_eq!(
+            raw_new_blob(&format!(":000000 160000 {} {} A", NULL_OID, blob)),
+            None
+        );
+    }
+}
@@ -0,0 +1,166 @@
+//! Attribution categories for commit identities.
+//!
+//! CI bots (dependabot, release bots) and code generators commit changes no human wrote, and
+//! those commits carry no authorship note, so stats would count every line as human. The
+//! `identity_categories` config maps identity patterns to a category:
+//!
+//! ```json
+//! "identity_categories": {
+//!   "dependabot*": "bot",
+//!   "release-bot@example.com": "bot",
+//!   "*codegen*": "generator",
+//!   "copilot-swe-agent*": "ai"
+//! }
+//! ```
+//!
+//! Patterns are case-insensitive globs matched against the identity's name, its email, and
+//! `Name <email>`; the longest matching pattern wins. The commit author is classified, so a
+//! bot's commit merged by a human stays the bot's, and a human's commit that a bot rebased or
+//! merged (GitHub's web-flow, `github-actions[bot]`) stays the human's. The committer is only
+//! tried when it has the author's email, in case a rule matches the name it commits under.
+
+use crate::config::Config;
+use crate::git::repository::Commit;
+use glob::{MatchOptions, Pattern};
+use std::collections::BTreeMap;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IdentityCategory {
+    /// Automation that commits on its own (dependency and release bots)
+    Bot,
+    /// Deterministic code generators
+    Generator,
+    /// AI agents committing under their own identity
+    Ai,
+    Human,
+}
+
+impl IdentityCategory {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            IdentityCategory::Bot => "bot",
+            IdentityCategory::Generator => "generator",
+            IdentityCategory::Ai => "ai",
+            IdentityCategory::Human => "human",
+        }
+    }
+}
+
+impl std::str::FromStr for IdentityCategory {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.trim().to_ascii_lowercase().as_str() {
+            "bot" => Ok(IdentityCategory::Bot),
+            "generator" | "generated" => Ok(IdentityCategory::Generator),
+            "ai" => Ok(IdentityCategory::Ai),
+            "human" => Ok(IdentityCategory::Human),
+            other => Err(format!("invalid identity category: '{}'", other)),
+        }
+    }
+}
+
+/// The identity a commit's lines belong to when a rule classifies it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClassifiedIdentity {
+    pub name: String,
+    pub category: IdentityCategory,
+}
+
+/// Category of `name <email>` under `rules` (pattern -> category), if any rule matches.
+pub fn classify_identity(
+    rules: &BTreeMap<String, String>,
+    name: &str,
+    email: &str,
+) -> Option<IdentityCategory> {
+    let options = MatchOptions {
+        case_sensitive: false,
+        require_literal_separator: false,
+        require_literal_leading_dot: false,
+    };
+    let full = format!("{} <{}>", name, email);
+    rules
+        .iter()
+        .filter_map(|(pattern, category)| {
+            let glob = Pattern::new(pattern).ok()?;
+            let matches = [name, email, full.as_str()]
+                .iter()
+                .any(|candidate| !candidate.is_empty() && glob.matches_with(candidate, options));
+            if !matches {
+                return None;
+            }
+            Some((pattern.len(), category.parse::<IdentityCategory>().ok()?))
+        })
+        .max_by_key(|(specificity, _)| *specificity)
+        .map(|(_, category)| category)
+}
+
+/// Classify a commit by its author, falling back to its committer when that is the same
+/// identity, using the configured `identity_categories`.
+pub fn classify_commit(commit: &Commit<'_>) -> Option<ClassifiedIdentity> {
+    let rules = Config::get().identity_categories();
+    if rules.is_empty() {
+        return None;
+    }
+    let author = commit.author().ok();
+    let committer = commit.committer().ok().filter(|committer| {
+        author.as_ref().is_some_and(|author| {
+            let email = author.email().unwrap_or_default();
+            !email.is_empty() && email.eq_ignore_ascii_case(committer.email().unwrap_or_default())
+        })
+    });
+    [author, committer]
+        .into_iter()
+        .flatten()
+        .find_map(|signature| {
+            let name = signature.name().unwrap_or_default().to_string();
+            let email = signature.email().unwrap_or_default().to_string();
+            classify_identity(rules, &name, &email).map(|category| ClassifiedIdentity {
+                name: if name.is_empty() { email } else { name },
+                category,
+            })
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_classify_identity_matches_name_email_or_both() {
+        let rules = BTreeMap::from([
+            ("dependabot*".to_string(), "bot".to_string()),
+            ("*@codegen.example.com".to_string(), "generator".to_string()),
+            ("Release Bot <*>".to_string(), "bot".to_string()),
+            ("*".to_string(), "human".to_string()),
+        ]);
+
+        assert_eq!(
+            classify_identity(&rules, "dependabot[bot]", "bot@users.noreply.github.com"),
+            Some(IdentityCategory::Bot)
+        );
+        assert_eq!(
+            classify_identity(&rules, "Schema Sync", "sync@CODEGEN.example.com"),
+            Some(IdentityCategory::Generator)
+        );
+        assert_eq!(
+            classify_identity(&rules, "release bot", "ci@example.com"),
+            Some(IdentityCategory::Bot)
+        );
+        // The catch-all is the least specific pattern
+        assert_eq!(
+            classify_identity(&rules, "Test User", "test@example.com"),
+            Some(IdentityCategory::Human)
+        );
+    }
+
+    #[test]
+    fn test_classify_identity_ignores_unknown_categories() {
+        let rules = BTreeMap::from([("*".to_string(), "robot".to_string())]);
+        assert_eq!(
+            classify_identity(&rules, "Test User", "test@example.com"),
+            None
+        );
+        assert_eq!("Generator".parse(), Ok(IdentityCategory::Generator));
+    }
+}
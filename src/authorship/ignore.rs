@@ -26,6 +26,8 @@ const DEFAULT_IGNORE_PATTERNS: &[&str] = &[
     "**/*.snap.new",
 ];
 
+const LFS_POINTER_VERSION_PREFIX: &str = "version https://git-lfs.github.com/spec/v1";
+
 #[derive(Clone, Debug)]
 enum CompiledPattern {
     Glob(Pattern),
@@ -88,6 +90,53 @@ pub fn should_ignore_file(path: &str, patterns: &[String]) -> bool {
 }
 
 pub fn load_linguist_generated_patterns_from_root_gitattributes(repo: &Repository) -> Vec<String> {
+    load_root_gitattributes_patterns(repo, |attr| {
+        if attr == "linguist-generated" {
+            return Some(true);
+        }
+        if attr == "-linguist-generated" || attr == "!linguist-generated" {
+            return Some(false);
+        }
+        if let Some(value) = attr.strip_prefix("linguist-generated=") {
+            if value.eq_ignore_ascii_case("true") || value == "1" {
+                return Some(true);
+            } else if value.eq_ignore_ascii_case("false") || value == "0" {
+                return Some(false);
+            }
+        }
+        None
+    })
+}
+
+/// Patterns for files stored in Git LFS (`filter=lfs` in the root .gitattributes).
+///
+/// LFS files are never attributed line-by-line: the blob in history is a pointer, and the
+/// smudged working copy can be gigabytes of binary data we must not read during checkpoints.
+pub fn load_lfs_patterns_from_root_gitattributes(repo: &Repository) -> Vec<String> {
+    load_root_gitattributes_patterns(repo, |attr| {
+        if attr == "filter=lfs" {
+            return Some(true);
+        }
+        if attr == "-filter" || attr == "!filter" || attr.starts_with("filter=") {
+            return Some(false);
+        }
+        None
+    })
+}
+
+/// Returns true if the content is a Git LFS pointer file rather than real file content.
+pub fn is_lfs_pointer(content: &[u8]) -> bool {
+    // Pointer files are tiny; the spec caps them well below 1KB.
+    content.len() < 1024 && content.starts_with(LFS_POINTER_VERSION_PREFIX.as_bytes())
+}
+
+/// Collect path patterns from the root .gitattributes whose final attribute state is "set".
+/// `classify` maps a single attribute token to Some(true)/Some(false) when it sets/unsets
+/// the attribute of interest, or None when the token is unrelated.
+fn load_root_gitattributes_patterns<F>(repo: &Repository, classify: F) -> Vec<String>
+where
+    F: Fn(&str) -> Option<bool>,
+{
     let Some(contents) = load_root_gitattributes_contents(repo) else {
         return Vec::new();
     };
@@ -112,20 +161,8 @@ pub fn load_linguist_generated_patterns_from_root_gitattributes(repo: &Repositor
         let mut state: Option<bool> = None;
 
         for attr in &tokens[1..] {
-            if attr == "linguist-generated" {
-                state = Some(true);
-                continue;
-            }
-            if attr == "-linguist-generated" || attr == "!linguist-generated" {
-                state = Some(false);
-                continue;
-            }
-            if let Some(value) = attr.strip_prefix("linguist-generated=") {
-                if value.eq_ignore_ascii_case("true") || value == "1" {
-                    state = Some(true);
-                } else if value.eq_ignore_ascii_case("false") || value == "0" {
-                    state = Some(false);
-                }
+            if let Some(value) = classify(attr) {
+                state = Some(value);
             }
         }
 
@@ -159,6 +196,7 @@ pub fn effective_ignore_patterns(
     patterns.extend(load_linguist_generated_patterns_from_root_gitattributes(
         repo,
     ));
+    patterns.extend(load_lfs_patterns_from_root_gitattributes(repo));
     patterns.extend(extra_patterns.iter().cloned());
     patterns.extend(user_patterns.iter().cloned());
     dedupe_patterns(patterns)
@@ -431,4 +469,44 @@ generated/** linguist-generated=true
         let patterns = load_linguist_generated_patterns_from_root_gitattributes(&bare_repo);
         assert!(patterns.is_empty());
     }
+
+    #[test]
+    fn loads_lfs_patterns_and_adds_them_to_effective_patterns() {
+        let tmp_repo = TmpRepo::new().expect("tmp repo");
+        tmp_repo
+            .write_file(
+                ".gitattributes",
+                "\
+*.psd filter=lfs diff=lfs merge=lfs -text
+models/** filter=lfs diff=lfs merge=lfs -text
+models/small.bin -filter
+*.rs text
+",
+                true,
+            )
+            .expect("write attrs");
+        tmp_repo
+            .commit_with_message("add gitattributes")
+            .expect("commit");
+
+        let patterns = load_lfs_patterns_from_root_gitattributes(tmp_repo.gitai_repo());
+        assert_eq!(patterns, vec!["*.psd".to_string(), "models/**".to_string()]);
+
+        let matcher =
+            build_ignore_matcher(&effective_ignore_patterns(tmp_repo.gitai_repo(), &[], &[]));
+        assert!(should_ignore_file_with_matcher("art/cover.psd", &matcher));
+        assert!(should_ignore_file_with_matcher(
+            "models/weights.bin",
+            &matcher
+        ));
+        assert!(!should_ignore_file_with_matcher("src/main.rs", &matcher));
+    }
+
+    #[test]
+    fn detects_lfs_pointer_content() {
+        let pointer = b"version https://git-lfs.github.com/spec/v1\noid sha256:4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393\nsize 12345\n";
+        assert!(is_lfs_pointer(pointer));
+        assert!(!is_lfs_pointer(b"fn main() {}\n"));
+        assert!(!is_lfs_pointer(&[0u8; 4096]));
+    }
 }
@@ -3,8 +3,228 @@
 //! imara-diff matches git's diff output more closely than `similar`, which is important
 //! for accurate line attribution tracking.
 
-use imara_diff::{Algorithm, Diff, InternedInput, TokenSource};
+use imara_diff::{Algorithm, Diff, Hunk, InternedInput, Token, TokenSource};
+use std::collections::HashMap;
 use std::hash::Hash;
+use std::ops::Range;
+
+/// Line diff algorithm used when matching old and new file versions for attribution.
+///
+/// Configured through the `diff_algorithm` config key. Histogram is the default: it keeps
+/// unique lines anchored, so in files with repeated blocks (match arms, test cases,
+/// generated boilerplate) unchanged lines stay matched where Myers slides hunks across the
+/// repeats and reports them as deleted and re-added (see `tests/diff_algorithm_benchmark.rs`).
+/// Myers matches `git diff`'s default output. Patience anchors on lines that occur exactly
+/// once on both sides and falls back to Myers between anchors.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DiffAlgorithm {
+    Myers,
+    #[default]
+    Histogram,
+    Patience,
+}
+
+impl DiffAlgorithm {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DiffAlgorithm::Myers => "myers",
+            DiffAlgorithm::Histogram => "histogram",
+            DiffAlgorithm::Patience => "patience",
+        }
+    }
+
+    /// The imara-diff algorithm to run, or `None` for patience, which imara-diff lacks.
+    fn imara_algorithm(&self) -> Option<Algorithm> {
+        match self {
+            DiffAlgorithm::Myers => Some(Algorithm::Myers),
+            DiffAlgorithm::Histogram => Some(Algorithm::Histogram),
+            DiffAlgorithm::Patience => None,
+        }
+    }
+}
+
+impl std::str::FromStr for DiffAlgorithm {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.trim().to_ascii_lowercase().as_str() {
+            "myers" => Ok(DiffAlgorithm::Myers),
+            "histogram" | "default" => Ok(DiffAlgorithm::Histogram),
+            "patience" => Ok(DiffAlgorithm::Patience),
+            other => Err(format!("invalid diff algorithm: '{}'", other)),
+        }
+    }
+}
+
+// ============================================================================
+// Patience diff
+// ============================================================================
+
+/// Hunks of a patience diff of `input`.
+fn patience_hunks<T>(input: &InternedInput<T>) -> Vec<Hunk> {
+    let mut removed = vec![false; input.before.len()];
+    let mut added = vec![false; input.after.len()];
+    patience_diff(
+        &input.before,
+        &input.after,
+        0..input.before.len(),
+        0..input.after.len(),
+        input.interner.num_tokens(),
+        &mut removed,
+        &mut added,
+    );
+
+    let mut hunks = Vec::new();
+    let (mut old_idx, mut new_idx) = (0, 0);
+    while old_idx < removed.len() || new_idx < added.len() {
+        let old_start = old_idx;
+        let new_start = new_idx;
+        while old_idx < removed.len() && removed[old_idx] {
+            old_idx += 1;
+        }
+        while new_idx < added.len() && added[new_idx] {
+            new_idx += 1;
+        }
+        if old_idx == old_start && new_idx == new_start {
+            // Both sides are on an unchanged token
+            old_idx += 1;
+            new_idx += 1;
+            continue;
+        }
+        hunks.push(Hunk {
+            before: old_start as u32..old_idx as u32,
+            after: new_start as u32..new_idx as u32,
+        });
+    }
+    hunks
+}
+
+/// Mark the tokens of `old_range` removed and of `new_range` added unless patience matches
+/// them: tokens occurring exactly once on both sides anchor the diff (keeping the longest
+/// run of anchors that appear in the same order), and the gaps between anchors are diffed
+/// the same way. Gaps without unique tokens fall back to Myers.
+fn patience_diff(
+    before: &[Token],
+    after: &[Token],
+    mut old_range: Range<usize>,
+    mut new_range: Range<usize>,
+    num_tokens: u32,
+    removed: &mut [bool],
+    added: &mut [bool],
+) {
+    while !old_range.is_empty()
+        && !new_range.is_empty()
+        && before[old_range.start] == after[new_range.start]
+    {
+        old_range.start += 1;
+        new_range.start += 1;
+    }
+    while !old_range.is_empty()
+        && !new_range.is_empty()
+        && before[old_range.end - 1] == after[new_range.end - 1]
+    {
+        old_range.end -= 1;
+        new_range.end -= 1;
+    }
+    if old_range.is_empty() || new_range.is_empty() {
+        removed[old_range].fill(true);
+        added[new_range].fill(true);
+        return;
+    }
+
+    let anchors = unique_common_anchors(before, after, old_range.clone(), new_range.clone());
+    if anchors.is_empty() {
+        let mut diff = Diff::default();
+        diff.compute_with(
+            Algorithm::Myers,
+            &before[old_range.clone()],
+            &after[new_range.clone()],
+            num_tokens,
+        );
+        for idx in old_range.clone() {
+            removed[idx] = diff.is_removed((idx - old_range.start) as u32);
+        }
+        for idx in new_range.clone() {
+            added[idx] = diff.is_added((idx - new_range.start) as u32);
+        }
+        return;
+    }
+
+    let (mut old_start, mut new_start) = (old_range.start, new_range.start);
+    for (old_anchor, new_anchor) in anchors {
+        patience_diff(
+            before,
+            after,
+            old_start..old_anchor,
+            new_start..new_anchor,
+            num_tokens,
+            removed,
+            added,
+        );
+        old_start = old_anchor + 1;
+        new_start = new_anchor + 1;
+    }
+    patience_diff(
+        before,
+        after,
+        old_start..old_range.end,
+        new_start..new_range.end,
+        num_tokens,
+        removed,
+        added,
+    );
+}
+
+/// Positions `(old, new)` of tokens that occur once in each range, reduced to the longest
+/// sequence that is increasing on both sides.
+fn unique_common_anchors(
+    before: &[Token],
+    after: &[Token],
+    old_range: Range<usize>,
+    new_range: Range<usize>,
+) -> Vec<(usize, usize)> {
+    // token -> (count in old, position in old, count in new, position in new)
+    let mut occurrences: HashMap<Token, (u32, usize, u32, usize)> = HashMap::new();
+    for idx in old_range.clone() {
+        let entry = occurrences.entry(before[idx]).or_default();
+        entry.0 += 1;
+        entry.1 = idx;
+    }
+    for idx in new_range {
+        if let Some(entry) = occurrences.get_mut(&after[idx]) {
+            entry.2 += 1;
+            entry.3 = idx;
+        }
+    }
+    let candidates: Vec<(usize, usize)> = old_range
+        .filter_map(|idx| match occurrences.get(&before[idx]) {
+            Some(&(1, _, 1, new_idx)) => Some((idx, new_idx)),
+            _ => None,
+        })
+        .collect();
+
+    // Patience sorting: longest increasing subsequence of new positions
+    let mut pile_tops: Vec<usize> = Vec::new();
+    let mut predecessors: Vec<Option<usize>> = vec![None; candidates.len()];
+    for (i, &(_, new_idx)) in candidates.iter().enumerate() {
+        let pile = pile_tops.partition_point(|&top| candidates[top].1 < new_idx);
+        predecessors[i] = pile.checked_sub(1).map(|prev| pile_tops[prev]);
+        if pile == pile_tops.len() {
+            pile_tops.push(i);
+        } else {
+            pile_tops[pile] = i;
+        }
+    }
+
+    let mut anchors = Vec::new();
+    let mut next = pile_tops.last().copied();
+    while let Some(i) = next {
+        anchors.push(candidates[i]);
+        next = predecessors[i];
+    }
+    anchors.reverse();
+    anchors
+}
 
 // ============================================================================
 // Byte-level diff types (replacing diff_match_patch_rs)
@@ -130,9 +350,21 @@ impl<'a, T: Clone + Hash + Eq> TokenSource for SliceTokenSource<'a, T> {
 /// # Returns
 /// A vector of `DiffOp` representing the changes between old and new.
 pub fn capture_diff_slices<T: Hash + Eq + Clone>(old: &[T], new: &[T]) -> Vec<DiffOp> {
+    capture_diff_slices_with(old, new, DiffAlgorithm::Myers)
+}
+
+/// Same as [`capture_diff_slices`], using the given diff algorithm.
+pub fn capture_diff_slices_with<T: Hash + Eq + Clone>(
+    old: &[T],
+    new: &[T],
+    algorithm: DiffAlgorithm,
+) -> Vec<DiffOp> {
     let input = InternedInput::new(SliceTokenSource::new(old), SliceTokenSource::new(new));
-    let diff = Diff::compute(Algorithm::Myers, &input);
-    hunks_to_diff_ops(&diff, old.len(), new.len())
+    let hunks = match algorithm.imara_algorithm() {
+        Some(imara_algorithm) => Diff::compute(imara_algorithm, &input).hunks().collect(),
+        None => patience_hunks(&input),
+    };
+    hunks_to_diff_ops(&hunks, old.len(), new.len())
 }
 
 /// Represents a change in a line-based diff.
@@ -176,19 +408,121 @@ impl<'a> LineChange<'a> {
 /// # Returns
 /// A vector of `LineChange` representing each line's change status.
 pub fn compute_line_changes<'a>(old: &'a str, new: &'a str) -> Vec<LineChange<'a>> {
+    compute_line_changes_with(old, new, DiffAlgorithm::Myers)
+}
+
+/// 1-based line numbers of `old` mapped to where the same line sits in `new`, for every
+/// line the diff leaves unchanged.
+pub fn unchanged_line_map(old: &str, new: &str) -> HashMap<u32, u32> {
+    let mut map = HashMap::new();
+    let (mut old_line, mut new_line) = (0u32, 0u32);
+    for change in compute_line_changes(old, new) {
+        match change.tag() {
+            LineChangeTag::Equal => {
+                old_line += 1;
+                new_line += 1;
+                map.insert(old_line, new_line);
+            }
+            LineChangeTag::Delete => old_line += 1,
+            LineChangeTag::Insert => new_line += 1,
+        }
+    }
+    map
+}
+
+/// 1-based line numbers in `new` that were inserted relative to `old`.
+pub fn inserted_line_numbers(old: &str, new: &str) -> Vec<u32> {
+    let mut inserted = Vec::new();
+    let mut new_line = 0u32;
+    for change in compute_line_changes(old, new) {
+        match change.tag() {
+            LineChangeTag::Equal => new_line += 1,
+            LineChangeTag::Insert => {
+                new_line += 1;
+                inserted.push(new_line);
+            }
+            LineChangeTag::Delete => {}
+        }
+    }
+    inserted
+}
+
+/// 1-based line numbers in `old` that were removed in `new`.
+pub fn removed_line_numbers(old: &str, new: &str) -> Vec<u32> {
+    let mut removed = Vec::new();
+    let mut old_line = 0u32;
+    for change in compute_line_changes(old, new) {
+        match change.tag() {
+            LineChangeTag::Equal => old_line += 1,
+            LineChangeTag::Delete => {
+                old_line += 1;
+                removed.push(old_line);
+            }
+            LineChangeTag::Insert => {}
+        }
+    }
+    removed
+}
+
+/// (1-based line number in `old`, content without terminator) of each line removed in `new`.
+///
+/// A line that only gained or lost its terminator (e.g. the last line once something is
+/// appended after it) shows up as deleted and re-inserted, and is not counted.
+pub fn removed_lines(old: &str, new: &str) -> Vec<(u32, String)> {
+    removed_lines_with(old, new, DiffAlgorithm::Myers)
+}
+
+/// Same as [`removed_lines`], using the given diff algorithm.
+pub fn removed_lines_with(old: &str, new: &str, algorithm: DiffAlgorithm) -> Vec<(u32, String)> {
+    let mut removed = Vec::new();
+    let mut inserted: HashMap<String, usize> = HashMap::new();
+    let mut old_line = 0u32;
+    for change in compute_line_changes_with(old, new, algorithm) {
+        let content = change.value().trim_end_matches(['\r', '\n']).to_string();
+        match change.tag() {
+            LineChangeTag::Equal => old_line += 1,
+            LineChangeTag::Delete => {
+                old_line += 1;
+                removed.push((old_line, content));
+            }
+            LineChangeTag::Insert => *inserted.entry(content).or_default() += 1,
+        }
+    }
+    removed.retain(|(_, content)| match inserted.get_mut(content) {
+        Some(count) if *count > 0 => {
+            *count -= 1;
+            false
+        }
+        _ => true,
+    });
+    removed
+}
+
+/// Same as [`compute_line_changes`], using the given diff algorithm.
+pub fn compute_line_changes_with<'a>(
+    old: &'a str,
+    new: &'a str,
+    algorithm: DiffAlgorithm,
+) -> Vec<LineChange<'a>> {
     let old_lines: Vec<&str> = split_lines_with_terminators(old);
     let new_lines: Vec<&str> = split_lines_with_terminators(new);
 
     // Use imara_diff with &str which implements TokenSource (tokenizes by lines)
     let input = InternedInput::new(old, new);
-    let mut diff = Diff::compute(Algorithm::Myers, &input);
-    diff.postprocess_lines(&input);
+    let hunks: Vec<Hunk> = match algorithm.imara_algorithm() {
+        Some(imara_algorithm) => {
+            let mut diff = Diff::compute(imara_algorithm, &input);
+            diff.postprocess_lines(&input);
+            diff.hunks().collect()
+        }
+        None => patience_hunks(&input),
+    };
 
     let mut changes = Vec::new();
     let mut old_idx: usize = 0;
     let mut new_idx: usize = 0;
 
-    for hunk in diff.hunks() {
+    for hunk in &hunks {
         let hunk_old_start = hunk.before.start as usize;
         let hunk_old_end = hunk.before.end as usize;
         let hunk_new_start = hunk.after.start as usize;
@@ -265,12 +599,12 @@ fn split_lines_with_terminators(s: &str) -> Vec<&str> {
 }
 
 /// Converts imara-diff hunks to a vector of DiffOp.
-fn hunks_to_diff_ops(diff: &Diff, old_len: usize, _new_len: usize) -> Vec<DiffOp> {
+fn hunks_to_diff_ops(hunks: &[Hunk], old_len: usize, _new_len: usize) -> Vec<DiffOp> {
     let mut ops = Vec::new();
     let mut old_idx: usize = 0;
     let mut new_idx: usize = 0;
 
-    for hunk in diff.hunks() {
+    for hunk in hunks {
         let hunk_old_start = hunk.before.start as usize;
         let hunk_old_end = hunk.before.end as usize;
         let hunk_new_start = hunk.after.start as usize;
@@ -485,4 +819,84 @@ mod tests {
         let lines_trailing = split_lines_with_terminators(s_trailing);
         assert_eq!(lines_trailing, vec!["line1\n", "line2\n"]);
     }
+
+    #[test]
+    fn test_diff_algorithm_parsing_and_consistency() {
+        assert_eq!("Histogram".parse(), Ok(DiffAlgorithm::Histogram));
+        assert_eq!("patience".parse(), Ok(DiffAlgorithm::Patience));
+        assert_eq!("myers".parse(), Ok(DiffAlgorithm::Myers));
+        assert!("minimal".parse::<DiffAlgorithm>().is_err());
+
+        // Repeated blocks: every algorithm must still account for each old and new line once.
+        let old = "fn a() {\n    x();\n}\nfn b() {\n    x();\n}\n";
+        let new = "fn a() {\n    x();\n}\n\nfn c() {\n    x();\n}\n\nfn b() {\n    x();\n}\n";
+        for algorithm in [
+            DiffAlgorithm::Myers,
+            DiffAlgorithm::Histogram,
+            DiffAlgorithm::Patience,
+        ] {
+            let changes = compute_line_changes_with(old, new, algorithm);
+            let old_count = changes
+                .iter()
+                .filter(|c| *c.tag() != LineChangeTag::Insert)
+                .count();
+            let new_count = changes
+                .iter()
+                .filter(|c| *c.tag() != LineChangeTag::Delete)
+                .count();
+            assert_eq!(old_count, old.lines().count(), "{:?}", algorithm);
+            assert_eq!(new_count, new.lines().count(), "{:?}", algorithm);
+        }
+    }
+
+    #[test]
+    fn test_unchanged_line_map_follows_shifted_lines() {
+        let map = unchanged_line_map("a\nb\nc\n", "x\na\nc\n");
+        assert_eq!(map.get(&1), Some(&2));
+        assert_eq!(map.get(&2), None);
+        assert_eq!(map.get(&3), Some(&3));
+    }
+
+    #[test]
+    fn test_inserted_and_removed_line_numbers_skip_unchanged_lines() {
+        assert_eq!(inserted_line_numbers("a\nb\n", "a\nx\nb\ny\n"), vec![2, 4]);
+        assert_eq!(removed_line_numbers("a\nx\nb\ny\n", "a\nb\n"), vec![2, 4]);
+    }
+
+    #[test]
+    fn test_removed_lines_ignores_terminator_only_changes() {
+        assert_eq!(
+            removed_lines("a\nb\nc", "a\nc\nd\n"),
+            vec![(2, "b".to_string())]
+        );
+    }
+
+    #[test]
+    fn test_patience_keeps_unique_lines_matched() {
+        let old = "fn a() {\n    x();\n}\nfn b() {\n    x();\n}\n";
+        let new = "fn b() {\n    x();\n}\nfn a() {\n    x();\n    y();\n}\n";
+        let changes = compute_line_changes_with(old, new, DiffAlgorithm::Patience);
+
+        let rebuilt_old: String = changes
+            .iter()
+            .filter(|c| *c.tag() != LineChangeTag::Insert)
+            .map(|c| c.value())
+            .collect();
+        let rebuilt_new: String = changes
+            .iter()
+            .filter(|c| *c.tag() != LineChangeTag::Delete)
+            .map(|c| c.value())
+            .collect();
+        assert_eq!(rebuilt_old, old);
+        assert_eq!(rebuilt_new, new);
+
+        // Each signature occurs once, so one of the swapped functions stays anchored
+        assert!(changes.iter().any(|c| *c.tag() == LineChangeTag::Equal
+            && matches!(c.value(), "fn a() {\n" | "fn b() {\n")));
+        assert!(
+            changes
+                .iter()
+                .any(|c| *c.tag() == LineChangeTag::Insert && c.value() == "    y();\n")
+        );
+    }
 }
@@ -208,11 +208,13 @@ impl PromptDbRecord {
     }
 
     /// Count total messages in transcript
+    #[cfg_attr(not(feature = "tui"), allow(dead_code))]
     pub fn message_count(&self) -> usize {
         self.messages.messages.len()
     }
 
     /// Format relative time ("1 day ago", "5 days ago", etc.)
+    #[cfg_attr(not(feature = "tui"), allow(dead_code))]
     pub fn relative_time(&self) -> String {
         let now = std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
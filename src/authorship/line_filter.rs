@@ -0,0 +1,252 @@
+use crate::git::repository::Repository;
+use crate::utils::debug_log;
+use std::path::Path;
+
+/// Which added lines count towards AI/human totals in `git-ai stats`.
+///
+/// The default counts every line. Filters are applied to the committed content of each
+/// file, so that braces, blank separators and comment banners don't dominate the headline.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LineFilter {
+    pub exclude_blank: bool,
+    /// Skip lines that are entirely comment, using comment syntax inferred from the file name.
+    pub exclude_comments: bool,
+    /// Skip lines with fewer than this many characters once surrounding whitespace is trimmed.
+    pub min_length: usize,
+}
+
+impl LineFilter {
+    pub fn is_active(&self) -> bool {
+        self.exclude_blank || self.exclude_comments || self.min_length > 0
+    }
+
+    /// Keep only the (1-indexed) line numbers of `content` that pass the filter.
+    pub fn retain_counted_lines(&self, path: &str, content: &str, lines: &mut Vec<u32>) {
+        if !self.is_active() {
+            return;
+        }
+        let source_lines: Vec<&str> = content.lines().collect();
+        let comment_lines = if self.exclude_comments {
+            comment_syntax_for_path(path)
+                .map(|syntax| classify_comment_lines(&syntax, &source_lines))
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        lines.retain(|line_number| {
+            let index = (*line_number as usize).wrapping_sub(1);
+            let Some(line) = source_lines.get(index) else {
+                return false;
+            };
+            let trimmed = line.trim();
+            if self.exclude_blank && trimmed.is_empty() {
+                return false;
+            }
+            if trimmed.chars().count() < self.min_length {
+                return false;
+            }
+            !comment_lines.get(index).copied().unwrap_or(false)
+        });
+    }
+
+    /// Like `retain_counted_lines`, for `path` as committed in `commit_sha`. Lines of a file
+    /// that can't be read are kept.
+    pub fn retain_committed_lines(
+        &self,
+        repo: &Repository,
+        commit_sha: &str,
+        path: &str,
+        lines: &mut Vec<u32>,
+    ) {
+        if !self.is_active() {
+            return;
+        }
+        match repo.get_file_content(path, commit_sha) {
+            Ok(content) => {
+                self.retain_counted_lines(path, &String::from_utf8_lossy(&content), lines)
+            }
+            Err(e) => debug_log(&format!(
+                "Stats line filter could not read {} at {}: {}",
+                path, commit_sha, e
+            )),
+        }
+    }
+}
+
+struct CommentSyntax {
+    line: &'static [&'static str],
+    block: Option<(&'static str, &'static str)>,
+}
+
+const C_STYLE: CommentSyntax = CommentSyntax {
+    line: &["//"],
+    block: Some(("/*", "*/")),
+};
+const HASH: CommentSyntax = CommentSyntax {
+    line: &["#"],
+    block: None,
+};
+
+fn comment_syntax_for_path(path: &str) -> Option<CommentSyntax> {
+    let path = Path::new(path);
+    let file_name = path.file_name()?.to_str()?;
+    match file_name {
+        "Dockerfile" | "Makefile" | "CMakeLists.txt" | "Gemfile" | "Rakefile" => {
+            return Some(HASH);
+        }
+        _ => {}
+    }
+
+    let extension = path.extension()?.to_str()?.to_ascii_lowercase();
+    let syntax = match extension.as_str() {
+        "rs" | "c" | "h" | "cc" | "cpp" | "cxx" | "hpp" | "hh" | "m" | "mm" | "cs" | "java"
+        | "kt" | "kts" | "scala" | "go" | "js" | "jsx" | "mjs" | "cjs" | "ts" | "tsx" | "swift"
+        | "dart" | "groovy" | "gradle" | "scss" | "less" | "proto" | "sol" | "zig" => C_STYLE,
+        "css" => CommentSyntax {
+            line: &[],
+            block: Some(("/*", "*/")),
+        },
+        "php" => CommentSyntax {
+            line: &["//", "#"],
+            block: Some(("/*", "*/")),
+        },
+        "py" | "pyi" => CommentSyntax {
+            line: &["#"],
+            block: Some(("\"\"\"", "\"\"\"")),
+        },
+        "rb" | "sh" | "bash" | "zsh" | "fish" | "pl" | "pm" | "r" | "yaml" | "yml" | "toml"
+        | "ps1" | "cmake" | "mk" | "ex" | "exs" | "nim" | "cfg" | "conf" => HASH,
+        "tf" | "hcl" => CommentSyntax {
+            line: &["#", "//"],
+            block: Some(("/*", "*/")),
+        },
+        "sql" => CommentSyntax {
+            line: &["--"],
+            block: Some(("/*", "*/")),
+        },
+        "lua" => CommentSyntax {
+            line: &["--"],
+            block: Some(("--[[", "]]")),
+        },
+        "hs" | "elm" => CommentSyntax {
+            line: &["--"],
+            block: Some(("{-", "-}")),
+        },
+        "clj" | "cljs" | "cljc" | "el" | "lisp" | "scm" | "asm" | "s" => CommentSyntax {
+            line: &[";"],
+            block: None,
+        },
+        "ini" => CommentSyntax {
+            line: &[";", "#"],
+            block: None,
+        },
+        "erl" | "hrl" | "tex" => CommentSyntax {
+            line: &["%"],
+            block: None,
+        },
+        "html" | "htm" | "xml" | "svg" | "vue" | "svelte" | "md" | "markdown" => CommentSyntax {
+            line: &[],
+            block: Some(("<!--", "-->")),
+        },
+        _ => return None,
+    };
+    Some(syntax)
+}
+
+/// Mark lines that contain only comment text.
+///
+/// This is a line-oriented heuristic: it tracks block comments across lines but does not
+/// tokenize string literals, so a comment marker inside a string after code can be misread.
+fn classify_comment_lines(syntax: &CommentSyntax, lines: &[&str]) -> Vec<bool> {
+    let mut in_block = false;
+    let mut result = Vec::with_capacity(lines.len());
+
+    for line in lines {
+        let mut rest = line.trim();
+        let has_text = !rest.is_empty();
+        let mut only_comment = true;
+
+        loop {
+            if in_block {
+                let (_, close) = syntax.block.expect("in_block implies block syntax");
+                match rest.find(close) {
+                    Some(end) => {
+                        rest = rest[end + close.len()..].trim_start();
+                        in_block = false;
+                    }
+                    None => break,
+                }
+            }
+            if rest.is_empty() {
+                break;
+            }
+            // Block openers first: Lua's `--[[` also starts with its line marker.
+            if let Some((open, _)) = syntax.block
+                && rest.starts_with(open)
+            {
+                rest = &rest[open.len()..];
+                in_block = true;
+                continue;
+            }
+            if syntax.line.iter().any(|marker| rest.starts_with(marker)) {
+                break;
+            }
+
+            only_comment = false;
+            // Code followed by a block comment that stays open onto the next line.
+            if let Some((open, close)) = syntax.block
+                && let Some(start) = rest.find(open)
+                && !rest[start + open.len()..].contains(close)
+            {
+                in_block = true;
+            }
+            break;
+        }
+
+        result.push(has_text && only_comment);
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counted(filter: LineFilter, path: &str, content: &str) -> Vec<u32> {
+        let mut lines: Vec<u32> = (1..=content.lines().count() as u32).collect();
+        filter.retain_counted_lines(path, content, &mut lines);
+        lines
+    }
+
+    #[test]
+    fn test_comment_filter_is_language_aware() {
+        let filter = LineFilter {
+            exclude_comments: true,
+            ..Default::default()
+        };
+
+        let rust = "// header\nfn main() {\n    /* start\n     * middle\n     */\n    let x = 1; // trailing\n}\n";
+        assert_eq!(counted(filter, "src/main.rs", rust), vec![2, 6, 7]);
+
+        let python =
+            "# comment\ndef f():\n    \"\"\"Docstring\n    more\n    \"\"\"\n    return 1\n";
+        assert_eq!(counted(filter, "lib.py", python), vec![2, 6]);
+
+        // `#` is not a comment in Rust, and unknown extensions keep everything.
+        assert_eq!(counted(filter, "src/lib.rs", "#[test]\n"), vec![1]);
+        assert_eq!(counted(filter, "notes.txt", "// not code\n"), vec![1]);
+    }
+
+    #[test]
+    fn test_blank_and_min_length_filters() {
+        let filter = LineFilter {
+            exclude_blank: true,
+            min_length: 3,
+            ..Default::default()
+        };
+        let content = "fn a() {\n\n    }\n    call();\n  \n";
+        assert_eq!(counted(filter, "a.rs", content), vec![1, 4]);
+        assert!(!LineFilter::default().is_active());
+    }
+}
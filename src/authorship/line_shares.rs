@@ -0,0 +1,327 @@
+//! Split attribution for lines a human and AI tools wrote together.
+//!
+//! Line attestations give each line a single author: whoever edited it last. When a human
+//! wrote part of a line and an AI tool the rest (a skeleton the agent filled in, then tweaked
+//! by hand), the character-level attributions in the working log still know the split. At
+//! commit time the split of every such line is written to the authorship log's `line_shares`
+//! metadata (file path -> line -> prompt hash -> percent of the line's non-whitespace
+//! characters); the human holds the remainder. Blame picks the author of a shared line with
+//! the configured [`DominantAuthor`] rule.
+
+use crate::authorship::attribution_tracker::Attribution;
+use crate::authorship::authorship_log_serialization::AuthorshipLog;
+use crate::authorship::imara_diff_utils::unchanged_line_map;
+use crate::authorship::virtual_attribution::get_file_content_at_commit;
+use crate::authorship::working_log::{Checkpoint, CheckpointKind, WorkingLogEntry};
+use crate::error::GitAiError;
+use crate::git::repo_storage::PersistedWorkingLog;
+use crate::git::repository::Repository;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+
+const EMPTY_TREE_HASH: &str = "4b825dc642cb6eb9a060e54bf8d69288fbee4904";
+
+/// Which co-author blame shows for a line written by both a human and AI.
+///
+/// Configured through the `blame_dominant_author` config key or `blame --dominant-author`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum DominantAuthor {
+    /// Whoever edited the line last, as recorded in the line attestations
+    #[default]
+    Latest,
+    /// AI when AI tools wrote more than half of the line, human when they wrote less
+    Majority,
+    /// AI whenever an AI tool wrote any part of the line
+    AnyAi,
+}
+
+impl DominantAuthor {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DominantAuthor::Latest => "latest",
+            DominantAuthor::Majority => "majority",
+            DominantAuthor::AnyAi => "any-ai",
+        }
+    }
+
+    /// The prompt that owns a shared line under this rule, or `None` for the human.
+    ///
+    /// `recorded` is the prompt the line attestation names (the latest editor).
+    pub fn pick<'a>(
+        &self,
+        recorded: Option<&'a str>,
+        shares: &'a BTreeMap<String, u8>,
+    ) -> Option<&'a str> {
+        let top = shares
+            .iter()
+            .max_by_key(|(hash, share)| (**share, std::cmp::Reverse(hash.as_str())))
+            .map(|(hash, _)| hash.as_str());
+        let ai_total: u32 = shares.values().map(|share| *share as u32).sum();
+        match self {
+            DominantAuthor::Latest => recorded,
+            DominantAuthor::Majority if ai_total > 50 => recorded.or(top),
+            DominantAuthor::Majority if ai_total < 50 => None,
+            DominantAuthor::Majority => recorded,
+            DominantAuthor::AnyAi => recorded.or(top),
+        }
+    }
+}
+
+impl std::str::FromStr for DominantAuthor {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        match input.trim().to_ascii_lowercase().as_str() {
+            "latest" | "default" => Ok(DominantAuthor::Latest),
+            "majority" => Ok(DominantAuthor::Majority),
+            "any-ai" | "any_ai" | "ai" => Ok(DominantAuthor::AnyAi),
+            other => Err(format!("invalid dominant author rule: '{}'", other)),
+        }
+    }
+}
+
+/// AI share of every line written by both the human and AI (1-based line -> author id ->
+/// percent of the line's non-whitespace characters). Each character belongs to the most
+/// recent attribution covering it; characters no attribution covers count as human.
+pub fn line_ai_shares(
+    attributions: &[Attribution],
+    content: &str,
+) -> BTreeMap<u32, BTreeMap<String, u8>> {
+    let human = CheckpointKind::Human.to_str();
+    let mut sorted: Vec<&Attribution> = attributions
+        .iter()
+        .filter(|attr| attr.start < attr.end)
+        .collect();
+    sorted.sort_by_key(|attr| (attr.start, attr.end));
+
+    let mut result = BTreeMap::new();
+    let mut next = 0usize;
+    let mut active: Vec<&Attribution> = Vec::new();
+    let mut line_start = 0usize;
+    for (idx, line) in content.split_inclusive('\n').enumerate() {
+        let line_end = line_start + line.len();
+        while next < sorted.len() && sorted[next].start < line_end {
+            active.push(sorted[next]);
+            next += 1;
+        }
+        active.retain(|attr| attr.end > line_start);
+
+        let mut counts: HashMap<&str, u32> = HashMap::new();
+        let mut total = 0u32;
+        for (offset, ch) in line.char_indices() {
+            if ch.is_whitespace() {
+                continue;
+            }
+            let pos = line_start + offset;
+            let owner = active
+                .iter()
+                .filter(|attr| attr.start <= pos && pos < attr.end)
+                .max_by_key(|attr| attr.ts)
+                .map(|attr| attr.author_id.as_str())
+                .unwrap_or(human.as_str());
+            *counts.entry(owner).or_insert(0) += 1;
+            total += 1;
+        }
+
+        let human_count = counts.remove(human.as_str()).unwrap_or(0);
+        if human_count > 0 && !counts.is_empty() {
+            let shares = counts
+                .into_iter()
+                .map(|(author, count)| {
+                    let percent = (count * 100 + total / 2) / total;
+                    (author.to_string(), percent.clamp(1, 99) as u8)
+                })
+                .collect();
+            result.insert(idx as u32 + 1, shares);
+        }
+        line_start = line_end;
+    }
+    result
+}
+
+/// Record the human/AI split of the shared lines `commit_sha` added.
+///
+/// The split comes from the character attributions of the last checkpoint of each file. Its
+/// line numbers can differ from the commit's (edits after the checkpoint, unstaged hunks), so
+/// shared lines are matched to the committed lines by content.
+pub fn attach_line_shares(
+    repo: &Repository,
+    parent_sha: &str,
+    commit_sha: &str,
+    working_log: &PersistedWorkingLog,
+    checkpoints: &[Checkpoint],
+    authorship_log: &mut AuthorshipLog,
+) -> Result<(), GitAiError> {
+    let mut latest: HashMap<&str, &WorkingLogEntry> = HashMap::new();
+    for checkpoint in checkpoints {
+        for entry in &checkpoint.entries {
+            if !entry.attributions.is_empty() {
+                latest.insert(entry.file.as_str(), entry);
+            }
+        }
+    }
+    if latest.is_empty() {
+        return Ok(());
+    }
+    let pathspecs: HashSet<String> = latest.keys().map(|file| file.to_string()).collect();
+    let from = if parent_sha == "initial" {
+        EMPTY_TREE_HASH
+    } else {
+        parent_sha
+    };
+
+    for (file, added) in repo.diff_added_lines(from, commit_sha, Some(&pathspecs))? {
+        let Some(entry) = latest.get(file.as_str()) else {
+            continue;
+        };
+        let Ok(checkpoint_content) = working_log.get_file_version(&entry.blob_sha) else {
+            continue;
+        };
+        let shares = line_ai_shares(&entry.attributions, &checkpoint_content);
+        if shares.is_empty() {
+            continue;
+        }
+
+        let checkpoint_lines: Vec<&str> = checkpoint_content.lines().collect();
+        let mut by_content: HashMap<&str, VecDeque<&BTreeMap<String, u8>>> = HashMap::new();
+        for (line, share) in &shares {
+            if let Some(content) = checkpoint_lines.get(*line as usize - 1) {
+                by_content.entry(content).or_default().push_back(share);
+            }
+        }
+
+        let committed = get_file_content_at_commit(repo, commit_sha, &file)?;
+        let committed_lines: Vec<&str> = committed.lines().collect();
+        let mut file_shares: BTreeMap<u32, BTreeMap<String, u8>> = BTreeMap::new();
+        for line in added {
+            let Some(share) = committed_lines
+                .get(line as usize - 1)
+                .and_then(|content| by_content.get_mut(content))
+                .and_then(VecDeque::pop_front)
+            else {
+                continue;
+            };
+            let share: BTreeMap<String, u8> = share
+                .iter()
+                .filter(|(hash, _)| authorship_log.metadata.prompts.contains_key(*hash))
+                .map(|(hash, percent)| (hash.clone(), *percent))
+                .collect();
+            if !share.is_empty() {
+                file_shares.insert(line, share);
+            }
+        }
+        if !file_shares.is_empty() {
+            authorship_log
+                .metadata
+                .line_shares
+                .insert(file, file_shares);
+        }
+    }
+    Ok(())
+}
+
+/// Carry the shared lines `source_log` records for `source_commit` over to `target_log`, the
+/// log of `target_commit` that rewrites it (rebase, amend, cherry-pick or squash).
+///
+/// Shared lines are numbered in the commit itself, so each one is followed from the source's
+/// content to the target's and dropped if the target changed it. Lines the target already
+/// records a split for keep theirs.
+pub fn carry_line_shares(
+    repo: &Repository,
+    source_commit: &str,
+    source_log: &AuthorshipLog,
+    target_commit: &str,
+    target_log: &mut AuthorshipLog,
+) -> Result<(), GitAiError> {
+    for (file, shares) in &source_log.metadata.line_shares {
+        let target_content = get_file_content_at_commit(repo, target_commit, file)?;
+        if target_content.is_empty() {
+            continue;
+        }
+        let line_map = unchanged_line_map(
+            &get_file_content_at_commit(repo, source_commit, file)?,
+            &target_content,
+        );
+        for (line, share) in shares {
+            let Some(target_line) = line_map.get(line) else {
+                continue;
+            };
+            let file_shares = target_log
+                .metadata
+                .line_shares
+                .entry(file.clone())
+                .or_default();
+            if file_shares.contains_key(target_line) {
+                continue;
+            }
+            file_shares.insert(*target_line, share.clone());
+            for hash in share.keys() {
+                if let Some(record) = source_log.metadata.prompts.get(hash) {
+                    target_log
+                        .metadata
+                        .prompts
+                        .entry(hash.clone())
+                        .or_insert_with(|| record.clone());
+                }
+            }
+        }
+        if target_log
+            .metadata
+            .line_shares
+            .get(file)
+            .is_some_and(BTreeMap::is_empty)
+        {
+            target_log.metadata.line_shares.remove(file);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn attr(start: usize, end: usize, author: &str, ts: u128) -> Attribution {
+        Attribution::new(start, end, author.to_string(), ts)
+    }
+
+    #[test]
+    fn test_line_ai_shares_splits_mixed_lines() {
+        // "fn a() {" by the human, "body();" by the AI, a human tweak of "body" to "main"
+        let content = "fn a() {\n    body();\n}\nlet x = ai_value;\n";
+        let attributions = vec![
+            attr(0, 9, "human", 1),
+            attr(9, 21, "abc1234", 2),
+            attr(13, 17, "human", 3),
+            attr(21, 23, "human", 1),
+            attr(31, 40, "abc1234", 2),
+        ];
+        let shares = line_ai_shares(&attributions, content);
+
+        // Line 2: "body();" is 7 characters, 4 rewritten by the human
+        assert_eq!(shares.get(&2).unwrap().get("abc1234"), Some(&43));
+        // Line 4: "let x =" is uncovered (human), "ai_value;" is AI
+        assert_eq!(shares.get(&4).unwrap().get("abc1234"), Some(&64));
+        // Lines written by one author only carry no split
+        assert!(!shares.contains_key(&1));
+        assert!(!shares.contains_key(&3));
+    }
+
+    #[test]
+    fn test_dominant_author_rules() {
+        let minority = BTreeMap::from([("abc1234".to_string(), 30u8)]);
+        let majority = BTreeMap::from([("abc1234".to_string(), 70u8)]);
+
+        assert_eq!(DominantAuthor::Latest.pick(None, &majority), None);
+        assert_eq!(
+            DominantAuthor::Majority.pick(None, &majority),
+            Some("abc1234")
+        );
+        assert_eq!(
+            DominantAuthor::Majority.pick(Some("abc1234"), &minority),
+            None
+        );
+        assert_eq!(DominantAuthor::AnyAi.pick(None, &minority), Some("abc1234"));
+        assert_eq!("ANY-AI".parse(), Ok(DominantAuthor::AnyAi));
+        assert!("loudest".parse::<DominantAuthor>().is_err());
+    }
+}
@@ -0,0 +1,163 @@
+//! Attribution for merge commits, including the hunks written to resolve conflicts.
+//!
+//! Lines a merge commit takes from either parent keep the attribution they have in that
+//! parent's history, since blame follows them there. Only lines that appear in neither parent
+//! (conflict resolutions and other edits made during the merge) belong to the merge commit
+//! itself. The working log sees more than that: a checkpoint taken while resolving diffs
+//! against the first parent, so every line brought in from the other side looks new. The
+//! merge commit's log is cut down to the resolution lines, which stay AI where a checkpoint
+//! during the resolution attributed them and are human otherwise.
+
+use crate::authorship::authorship_log::LineRange;
+use crate::authorship::authorship_log_serialization::{
+    AuthorshipLog, format_line_ranges, parse_line_ranges,
+};
+use crate::authorship::imara_diff_utils::{inserted_line_numbers, removed_line_numbers};
+use crate::authorship::virtual_attribution::get_file_content_at_commit;
+use crate::error::GitAiError;
+use crate::git::repository::Repository;
+use crate::utils::debug_log;
+use std::collections::HashSet;
+
+/// Restrict the authorship log of merge commit `commit_sha` to the lines it resolved.
+///
+/// Does nothing for commits with a single parent.
+pub fn restrict_to_resolution_lines(
+    repo: &Repository,
+    commit_sha: &str,
+    authorship_log: &mut AuthorshipLog,
+) -> Result<(), GitAiError> {
+    let commit = repo.find_commit(commit_sha.to_string())?;
+    let parents: Vec<String> = commit.parents().map(|parent| parent.id()).collect();
+    if parents.len() < 2 {
+        return Ok(());
+    }
+
+    let prompts_before = referenced_prompts(authorship_log);
+
+    for attestation in &mut authorship_log.attestations {
+        let resolution = resolution_lines(repo, commit_sha, &parents, &attestation.file_path)?;
+
+        for entry in &mut attestation.entries {
+            let from_parents: Vec<u32> = entry
+                .line_ranges
+                .iter()
+                .flat_map(LineRange::expand)
+                .filter(|line| !resolution.contains(line))
+                .collect();
+            if !from_parents.is_empty() {
+                entry.remove_line_ranges(&LineRange::compress_lines(&from_parents));
+            }
+        }
+        attestation
+            .entries
+            .retain(|entry| !entry.line_ranges.is_empty());
+        if !resolution.is_empty() {
+            debug_log(&format!(
+                "Merge commit {} resolved {} lines in {}",
+                commit_sha,
+                resolution.len(),
+                attestation.file_path
+            ));
+        }
+    }
+    authorship_log
+        .attestations
+        .retain(|attestation| !attestation.entries.is_empty());
+
+    // Lines deleted from the first parent only count when the other parent still had them;
+    // otherwise the deletion was merged in, not made while resolving
+    let first_parent = &parents[0];
+    for (file, by_prompt) in &mut authorship_log.metadata.deletions {
+        let first_content = get_file_content_at_commit(repo, first_parent, file)?;
+        let mut merged_in: HashSet<u32> = HashSet::new();
+        for parent in &parents[1..] {
+            let parent_content = get_file_content_at_commit(repo, parent, file)?;
+            merged_in.extend(removed_line_numbers(&first_content, &parent_content));
+        }
+        if merged_in.is_empty() {
+            continue;
+        }
+        for ranges in by_prompt.values_mut() {
+            let kept: Vec<u32> = parse_line_ranges(ranges)
+                .unwrap_or_default()
+                .iter()
+                .flat_map(LineRange::expand)
+                .filter(|line| !merged_in.contains(line))
+                .collect();
+            *ranges = format_line_ranges(&LineRange::compress_lines(&kept));
+        }
+        by_prompt.retain(|_, ranges| !ranges.is_empty());
+    }
+    authorship_log
+        .metadata
+        .deletions
+        .retain(|_, by_prompt| !by_prompt.is_empty());
+
+    // Split lines only keep their shares where the merge wrote them
+    for (file, lines) in &mut authorship_log.metadata.line_shares {
+        let resolution = resolution_lines(repo, commit_sha, &parents, file)?;
+        lines.retain(|line, _| resolution.contains(line));
+    }
+    authorship_log
+        .metadata
+        .line_shares
+        .retain(|_, lines| !lines.is_empty());
+
+    // Drop prompts whose only lines came from the other side of the merge
+    let prompts_after = referenced_prompts(authorship_log);
+    authorship_log
+        .metadata
+        .prompts
+        .retain(|hash, _| !prompts_before.contains(hash) || prompts_after.contains(hash));
+
+    Ok(())
+}
+
+fn referenced_prompts(authorship_log: &AuthorshipLog) -> HashSet<String> {
+    let mut hashes: HashSet<String> = authorship_log
+        .attestations
+        .iter()
+        .flat_map(|attestation| attestation.entries.iter())
+        .map(|entry| entry.hash.clone())
+        .collect();
+    hashes.extend(
+        authorship_log
+            .metadata
+            .deletions
+            .values()
+            .flat_map(|by_prompt| by_prompt.keys().cloned()),
+    );
+    hashes.extend(
+        authorship_log
+            .metadata
+            .line_shares
+            .values()
+            .flat_map(|lines| lines.values())
+            .flat_map(|shares| shares.keys().cloned()),
+    );
+    hashes.extend(authorship_log.metadata.assets.values().cloned());
+    hashes
+}
+
+/// Lines of `file` in merge commit `commit_sha` that are in none of its parents.
+fn resolution_lines(
+    repo: &Repository,
+    commit_sha: &str,
+    parents: &[String],
+    file: &str,
+) -> Result<HashSet<u32>, GitAiError> {
+    let content = get_file_content_at_commit(repo, commit_sha, file)?;
+    let mut resolution: Option<HashSet<u32>> = None;
+    for parent in parents {
+        let parent_content = get_file_content_at_commit(repo, parent, file)?;
+        let inserted: HashSet<u32> = inserted_line_numbers(&parent_content, &content)
+            .into_iter()
+            .collect();
+        resolution = Some(match resolution {
+            Some(lines) => lines.intersection(&inserted).copied().collect(),
+            None => inserted,
+        });
+    }
+    Ok(resolution.unwrap_or_default())
+}
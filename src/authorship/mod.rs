@@ -1,18 +1,33 @@
 pub mod attribution_tracker;
 pub mod authorship_log;
 pub mod authorship_log_serialization;
+pub mod deletions;
 pub mod diff_ai_accepted;
+pub mod diff_annotation;
+pub mod file_inheritance;
+pub mod graft;
+pub mod identity;
 pub mod ignore;
 pub mod imara_diff_utils;
 pub mod internal_db;
+pub mod line_filter;
+pub mod line_shares;
+pub mod merge_resolution;
+pub mod model_names;
 pub mod move_detection;
+pub mod note_merge;
+pub mod policy;
 pub mod post_commit;
 pub mod pre_commit;
 pub mod prompt_utils;
+pub mod provenance;
 pub mod range_authorship;
+pub mod reassign;
 pub mod rebase_authorship;
 pub mod secrets;
+pub mod squash_backfill;
 pub mod stats;
+pub mod stats_report;
 pub mod transcript;
 pub mod virtual_attribution;
 pub mod working_log;
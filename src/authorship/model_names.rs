@@ -0,0 +1,126 @@
+//! Canonical model names, so stats group versions of one model together.
+//!
+//! Agents record models as free-form strings ("gpt-4o", "GPT-4o", "gpt-4o-2024-08-06",
+//! "openai/gpt-4o"). Notes keep the raw string; grouping normalizes it when stats are read:
+//! lowercase, drop any provider prefix and release date suffix, then map known spellings
+//! through the built-in table. `model_aliases` in the config maps further names (raw or
+//! normalized, case-insensitive) to a canonical name of the user's choosing.
+
+use crate::config::Config;
+use regex::Regex;
+use std::collections::BTreeMap;
+use std::sync::LazyLock;
+
+/// Known alternate spellings -> canonical name, matched after the suffix rules.
+const BUILTIN_ALIASES: &[(&str, &str)] = &[
+    ("gpt4", "gpt-4"),
+    ("gpt4o", "gpt-4o"),
+    ("gpt-4-o", "gpt-4o"),
+    ("gpt4o-mini", "gpt-4o-mini"),
+    ("chatgpt-4o", "gpt-4o"),
+    ("claude-3.5-sonnet", "claude-3-5-sonnet"),
+    ("claude-3.5-haiku", "claude-3-5-haiku"),
+    ("claude-3.7-sonnet", "claude-3-7-sonnet"),
+    ("claude-sonnet-3.5", "claude-3-5-sonnet"),
+    ("claude-sonnet-3.7", "claude-3-7-sonnet"),
+];
+
+/// Release dates appended to model names: `-2024-08-06`, `-20240806`, `@20240806`.
+static DATE_SUFFIX: LazyLock<Regex> =
+    LazyLock::new(|| Regex::new(r"[-@](\d{4}-\d{2}-\d{2}|\d{8})$").unwrap());
+
+/// Canonical name for `model`, using the configured `model_aliases`.
+pub fn normalize_model(model: &str) -> String {
+    normalize_model_with(model, Config::get().model_aliases())
+}
+
+/// Canonical name for `model` with an explicit alias table.
+pub fn normalize_model_with(model: &str, aliases: &BTreeMap<String, String>) -> String {
+    let trimmed = model.trim();
+    if let Some(alias) = lookup_alias(aliases, trimmed) {
+        return alias;
+    }
+
+    let mut name = trimmed
+        .to_lowercase()
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join("-");
+    if let Some((_, last)) = name.rsplit_once('/') {
+        name = last.to_string();
+    }
+    name = DATE_SUFFIX.replace(&name, "").into_owned();
+    if let Some(stripped) = name.strip_suffix("-latest") {
+        name = stripped.to_string();
+    }
+    if let Some((_, canonical)) = BUILTIN_ALIASES.iter().find(|(from, _)| *from == name) {
+        name = canonical.to_string();
+    }
+
+    lookup_alias(aliases, &name).unwrap_or(name)
+}
+
+/// Stats grouping key for a tool and model: `tool::canonical-model`, or the model exactly as
+/// recorded when `raw_models` is set (`--raw-models`).
+pub fn tool_model_key(tool: &str, model: &str, raw_models: bool) -> String {
+    if raw_models {
+        return format!("{}::{}", tool, model);
+    }
+    format!("{}::{}", tool, normalize_model(model))
+}
+
+fn lookup_alias(aliases: &BTreeMap<String, String>, model: &str) -> Option<String> {
+    aliases
+        .iter()
+        .find(|(from, _)| from.eq_ignore_ascii_case(model))
+        .map(|(_, to)| to.clone())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn normalize(model: &str) -> String {
+        normalize_model_with(model, &BTreeMap::new())
+    }
+
+    #[test]
+    fn test_versions_of_one_model_share_a_name() {
+        for model in [
+            "gpt-4o",
+            "GPT-4o",
+            "gpt-4o-2024-08-06",
+            "openai/gpt-4o",
+            "gpt-4o-latest",
+            "gpt4o",
+        ] {
+            assert_eq!(normalize(model), "gpt-4o", "{}", model);
+        }
+        assert_eq!(normalize("claude-3-5-sonnet-20241022"), "claude-3-5-sonnet");
+        assert_eq!(normalize("claude-3.5-sonnet"), "claude-3-5-sonnet");
+        assert_eq!(normalize("claude-opus-4@20250514"), "claude-opus-4");
+    }
+
+    #[test]
+    fn test_distinct_models_stay_distinct() {
+        assert_eq!(normalize("gpt-4o-mini"), "gpt-4o-mini");
+        assert_eq!(normalize("gpt-4"), "gpt-4");
+        assert_eq!(normalize("unknown"), "unknown");
+    }
+
+    #[test]
+    fn test_configured_aliases_match_raw_and_normalized_names() {
+        let aliases = BTreeMap::from([
+            ("Internal-Proxy-Model".to_string(), "gpt-4o".to_string()),
+            ("gpt-4o-mini".to_string(), "gpt-4o".to_string()),
+        ]);
+        assert_eq!(
+            normalize_model_with("internal-proxy-model", &aliases),
+            "gpt-4o"
+        );
+        assert_eq!(
+            normalize_model_with("gpt-4o-mini-2024-07-18", &aliases),
+            "gpt-4o"
+        );
+    }
+}
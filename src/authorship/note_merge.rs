@@ -0,0 +1,282 @@
+//! Combine two authorship notes attached to the same commit.
+//!
+//! A clone that works offline for a while can come back to find others have noted the same
+//! commits differently: a backfill or `git-ai reassign` on one side, new attribution on the
+//! other. `git notes merge -s ours` keeps one side whole and drops the other; here the notes
+//! are combined instead. A line keeps its attribution when the two sides agree on the prompt
+//! or only one side attests it, lines either side reassigned to the human stay human, and
+//! prompts and metadata are unioned. Lines the two sides attribute to different prompts are
+//! conflicts, and the notes are not merged.
+
+use crate::authorship::authorship_log::LineRange;
+use crate::authorship::authorship_log_serialization::{
+    AttestationEntry, AuthorshipLog, format_line_ranges, parse_line_ranges,
+};
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+
+/// Lines of one file the two notes attribute to different prompts.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LineConflict {
+    pub file: String,
+    /// Line ranges in the commit's version of the file, e.g. "2-3,7"
+    pub lines: String,
+    pub local_prompt: String,
+    pub local_tool: String,
+    pub remote_prompt: String,
+    pub remote_tool: String,
+}
+
+/// Merge the `remote` note of a commit into the `local` one.
+pub fn merge_notes(
+    local: &AuthorshipLog,
+    remote: &AuthorshipLog,
+) -> Result<AuthorshipLog, Vec<LineConflict>> {
+    let local_lines = attested_lines(local);
+    let remote_lines = attested_lines(remote);
+    let reassigned = reassigned_lines(local)
+        .union(&reassigned_lines(remote))
+        .cloned()
+        .collect::<HashSet<_>>();
+
+    let mut files: Vec<&String> = local
+        .attestations
+        .iter()
+        .map(|attestation| &attestation.file_path)
+        .collect();
+    for attestation in &remote.attestations {
+        if !files.contains(&&attestation.file_path) {
+            files.push(&attestation.file_path);
+        }
+    }
+
+    let empty = BTreeMap::new();
+    let mut conflicts: BTreeMap<(String, String, String), Vec<u32>> = BTreeMap::new();
+    let mut merged_files: Vec<(String, BTreeMap<u32, String>)> = Vec::new();
+    for file in files {
+        let ours = local_lines.get(file.as_str()).unwrap_or(&empty);
+        let theirs = remote_lines.get(file.as_str()).unwrap_or(&empty);
+        let mut merged = BTreeMap::new();
+        for (line, prompt) in ours.iter().chain(theirs.iter()) {
+            if reassigned.contains(&(file.clone(), *line, prompt.clone())) {
+                continue;
+            }
+            match (ours.get(line), theirs.get(line)) {
+                (Some(local_prompt), Some(remote_prompt)) if local_prompt != remote_prompt => {
+                    conflicts
+                        .entry((file.clone(), local_prompt.clone(), remote_prompt.clone()))
+                        .or_default()
+                        .push(*line);
+                }
+                _ => {
+                    merged.insert(*line, prompt.clone());
+                }
+            }
+        }
+        merged_files.push((file.clone(), merged));
+    }
+
+    if !conflicts.is_empty() {
+        let tool = |log: &AuthorshipLog, prompt: &str| {
+            log.metadata
+                .prompts
+                .get(prompt)
+                .map(|record| record.agent_id.tool.clone())
+                .unwrap_or_else(|| "unknown".to_string())
+        };
+        return Err(conflicts
+            .into_iter()
+            .map(|((file, local_prompt, remote_prompt), mut lines)| {
+                lines.sort_unstable();
+                lines.dedup();
+                LineConflict {
+                    file,
+                    lines: format_line_ranges(&LineRange::compress_lines(&lines)),
+                    local_tool: tool(local, &local_prompt),
+                    remote_tool: tool(remote, &remote_prompt),
+                    local_prompt,
+                    remote_prompt,
+                }
+            })
+            .collect());
+    }
+
+    let mut merged = AuthorshipLog::new();
+    merged.metadata = local.metadata.clone();
+    for (file, lines) in merged_files {
+        // Entries keep the order prompts first appear in
+        let mut by_prompt: Vec<(String, Vec<u32>)> = Vec::new();
+        for (line, prompt) in lines {
+            match by_prompt.iter_mut().find(|(hash, _)| *hash == prompt) {
+                Some((_, prompt_lines)) => prompt_lines.push(line),
+                None => by_prompt.push((prompt, vec![line])),
+            }
+        }
+        if by_prompt.is_empty() {
+            continue;
+        }
+        let attestation = merged.get_or_create_file(&file);
+        for (prompt, prompt_lines) in by_prompt {
+            attestation.add_entry(AttestationEntry::new(
+                prompt,
+                LineRange::compress_lines(&prompt_lines),
+            ));
+        }
+    }
+
+    let metadata = &mut merged.metadata;
+    for (hash, record) in &remote.metadata.prompts {
+        // The side that refetched more of the transcript wins
+        let keep_local = metadata
+            .prompts
+            .get(hash)
+            .is_some_and(|local| local.messages.len() >= record.messages.len());
+        if !keep_local {
+            metadata.prompts.insert(hash.clone(), record.clone());
+        }
+    }
+    for (file, prompt) in &remote.metadata.assets {
+        metadata
+            .assets
+            .entry(file.clone())
+            .or_insert_with(|| prompt.clone());
+    }
+    for (file, prompts) in &remote.metadata.deletions {
+        let deletions = metadata.deletions.entry(file.clone()).or_default();
+        for (prompt, lines) in prompts {
+            deletions
+                .entry(prompt.clone())
+                .or_insert_with(|| lines.clone());
+        }
+    }
+    for (file, lines) in &remote.metadata.line_shares {
+        let shares = metadata.line_shares.entry(file.clone()).or_default();
+        for (line, split) in lines {
+            shares.entry(*line).or_insert_with(|| split.clone());
+        }
+    }
+    for (file, records) in &remote.metadata.reassignments {
+        let merged_records = metadata.reassignments.entry(file.clone()).or_default();
+        for record in records {
+            if !merged_records.contains(record) {
+                merged_records.push(record.clone());
+            }
+        }
+    }
+
+    Ok(merged)
+}
+
+/// File -> line -> prompt hash.
+fn attested_lines(log: &AuthorshipLog) -> BTreeMap<&str, BTreeMap<u32, String>> {
+    let mut files: BTreeMap<&str, BTreeMap<u32, String>> = BTreeMap::new();
+    for attestation in &log.attestations {
+        let lines = files.entry(attestation.file_path.as_str()).or_default();
+        for entry in &attestation.entries {
+            for line in entry.line_ranges.iter().flat_map(LineRange::expand) {
+                lines.insert(line, entry.hash.clone());
+            }
+        }
+    }
+    files
+}
+
+/// (file, line, prompt) the note handed over to the human.
+fn reassigned_lines(log: &AuthorshipLog) -> BTreeSet<(String, u32, String)> {
+    let mut lines = BTreeSet::new();
+    for (file, records) in &log.metadata.reassignments {
+        for record in records {
+            let ranges = parse_line_ranges(&record.lines).unwrap_or_default();
+            for line in ranges.iter().flat_map(LineRange::expand) {
+                lines.insert((file.clone(), line, record.prompt.clone()));
+            }
+        }
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authorship::authorship_log_serialization::Reassignment;
+
+    fn note(file: &str, entries: &[(&str, LineRange)]) -> AuthorshipLog {
+        let mut log = AuthorshipLog::new();
+        let attestation = log.get_or_create_file(file);
+        for (prompt, range) in entries {
+            attestation.add_entry(AttestationEntry::new(
+                prompt.to_string(),
+                vec![range.clone()],
+            ));
+        }
+        log
+    }
+
+    fn lines_of(log: &AuthorshipLog, file: &str) -> BTreeMap<u32, String> {
+        attested_lines(log).remove(file).unwrap_or_default()
+    }
+
+    #[test]
+    fn test_merge_unions_lines_and_keeps_reassignments() {
+        let mut local = note("src/app.rs", &[("aaaa", LineRange::Range(1, 3))]);
+        // Local handed line 3 to the human while offline
+        local.attestations[0].entries[0].line_ranges = vec![LineRange::Range(1, 2)];
+        local.metadata.reassignments.insert(
+            "src/app.rs".to_string(),
+            vec![Reassignment {
+                prompt: "aaaa".to_string(),
+                lines: "3".to_string(),
+                to: "human".to_string(),
+                by: "Test User".to_string(),
+                reason: "manual".to_string(),
+                timestamp: 0,
+            }],
+        );
+        let mut remote = note(
+            "src/app.rs",
+            &[
+                ("aaaa", LineRange::Range(1, 3)),
+                ("bbbb", LineRange::Single(5)),
+            ],
+        );
+        remote
+            .get_or_create_file("src/lib.rs")
+            .add_entry(AttestationEntry::new(
+                "bbbb".to_string(),
+                vec![LineRange::Single(1)],
+            ));
+
+        let merged = merge_notes(&local, &remote).unwrap();
+        assert_eq!(
+            lines_of(&merged, "src/app.rs"),
+            BTreeMap::from([
+                (1, "aaaa".to_string()),
+                (2, "aaaa".to_string()),
+                (5, "bbbb".to_string()),
+            ])
+        );
+        assert_eq!(
+            lines_of(&merged, "src/lib.rs"),
+            BTreeMap::from([(1, "bbbb".to_string())])
+        );
+        assert_eq!(merged.metadata.reassignments["src/app.rs"].len(), 1);
+    }
+
+    #[test]
+    fn test_lines_attributed_to_different_prompts_conflict() {
+        let local = note("src/app.rs", &[("aaaa", LineRange::Range(1, 4))]);
+        let remote = note(
+            "src/app.rs",
+            &[
+                ("aaaa", LineRange::Single(1)),
+                ("bbbb", LineRange::Range(2, 3)),
+            ],
+        );
+
+        let conflicts = merge_notes(&local, &remote).unwrap_err();
+        assert_eq!(conflicts.len(), 1);
+        assert_eq!(conflicts[0].file, "src/app.rs");
+        assert_eq!(conflicts[0].lines, "2-3");
+        assert_eq!(conflicts[0].local_prompt, "aaaa");
+        assert_eq!(conflicts[0].remote_prompt, "bbbb");
+    }
+}
@@ -0,0 +1,537 @@
+//! Repository policy on AI authorship, read from `.git-ai/policy.toml` in the repo root:
+//!
+//! ```toml
+//! [[rule]]
+//! name = "crypto-needs-humans"
+//! paths = ["src/crypto/**"]
+//! max_ai_percent = 80        # of the lines added in matching files, AI-written and
+//!                            # not edited by a human
+//! severity = "block"         # or "warn" (default)
+//! on = ["push"]              # "commit" and/or "push" (default: both)
+//!
+//! [[rule]]
+//! name = "migration-prompts"
+//! paths = ["migrations/**"]
+//! require_prompt_records = true
+//! ```
+//!
+//! Rules are checked by the commit and push pre-command hooks and by `git-ai policy check`.
+//! `--no-verify` or `GIT_AI_SKIP_POLICY=1` skips them in the hooks. A policy file that doesn't
+//! parse blocks the hooks and fails the check, so a typo can't switch the rules off.
+
+use crate::authorship::authorship_log_serialization::generate_short_hash;
+use crate::authorship::diff_annotation::{LineAuthorshipIndex, added_lines_by_file};
+use crate::authorship::ignore::build_ignore_matcher;
+use crate::authorship::line_shares::line_ai_shares;
+use crate::authorship::working_log::CheckpointKind;
+use crate::commands::blame::latest_working_log_entry;
+use crate::error::GitAiError;
+use crate::git::refs::get_authorship;
+use crate::git::repository::{Repository, exec_git};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+pub const POLICY_FILE: &str = ".git-ai/policy.toml";
+pub const SKIP_POLICY_ENV: &str = "GIT_AI_SKIP_POLICY";
+
+const EMPTY_TREE_HASH: &str = "4b825dc642cb6eb9a060e54bf8d69288fbee4904";
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Policy {
+    #[serde(default, rename = "rule")]
+    pub rules: Vec<PolicyRule>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct PolicyRule {
+    pub name: String,
+    /// Glob patterns of the files the rule applies to
+    pub paths: Vec<String>,
+    /// Maximum share (0-100) of added lines that may be AI-authored without a human edit
+    #[serde(default)]
+    pub max_ai_percent: Option<f64>,
+    /// Every AI-authored line must resolve to a prompt record
+    #[serde(default)]
+    pub require_prompt_records: bool,
+    #[serde(default)]
+    pub severity: PolicySeverity,
+    /// Hooks the rule runs in; empty means all of them
+    #[serde(default)]
+    pub on: Vec<PolicyStage>,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PolicySeverity {
+    #[default]
+    Warn,
+    Block,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PolicyStage {
+    Commit,
+    Push,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PolicyViolation {
+    pub rule: String,
+    pub severity: PolicySeverity,
+    pub message: String,
+}
+
+/// What a `git commit` takes its content from. Only a plain commit commits the index as
+/// staged; `-a` and pathspecs pick files up from the working tree when git runs.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommitScope {
+    Index,
+    /// `-a`/`--all`: every tracked file as it is in the working tree
+    AllTracked,
+    /// Pathspecs: those files from the working tree, plus the rest of the index with
+    /// `-i`/`--include` (the default, `-o`/`--only`, commits just the pathspecs)
+    Paths {
+        pathspecs: Vec<String>,
+        include_index: bool,
+    },
+    /// `--only` without pathspecs (`--amend --only`, `--fixup=reword:`): the tree stays as it
+    /// is and nothing staged is committed
+    Unchanged,
+}
+
+/// Authorship of the lines a change adds, per file.
+#[derive(Debug, Clone, Default)]
+pub struct ChangeAuthorship {
+    pub files: BTreeMap<String, FileChangeAuthorship>,
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FileChangeAuthorship {
+    pub added_lines: u32,
+    pub ai_lines: u32,
+    /// AI lines whose prompt hash has no prompt record
+    pub ai_lines_without_prompt: u32,
+    /// AI lines a human also wrote part of
+    pub human_edited_ai_lines: u32,
+}
+
+impl Policy {
+    pub fn parse(content: &str) -> Result<Policy, GitAiError> {
+        toml::from_str(content)
+            .map_err(|e| GitAiError::Generic(format!("Invalid {}: {}", POLICY_FILE, e)))
+    }
+
+    /// The repository's policy, or `None` when it has no policy file.
+    pub fn load(repo: &Repository) -> Result<Option<Policy>, GitAiError> {
+        let path = repo.workdir()?.join(POLICY_FILE);
+        if !path.exists() {
+            return Ok(None);
+        }
+        Policy::parse(&std::fs::read_to_string(path)?).map(Some)
+    }
+
+    /// Check `change` against every rule that runs at `stage` (all rules when `None`).
+    pub fn evaluate(
+        &self,
+        stage: Option<PolicyStage>,
+        change: &ChangeAuthorship,
+    ) -> Vec<PolicyViolation> {
+        let mut violations = Vec::new();
+        for rule in &self.rules {
+            if let Some(stage) = stage
+                && !rule.on.is_empty()
+                && !rule.on.contains(&stage)
+            {
+                continue;
+            }
+
+            let matcher = build_ignore_matcher(&rule.paths);
+            let mut totals = FileChangeAuthorship::default();
+            for (file, authorship) in &change.files {
+                if matcher.is_ignored(file) {
+                    totals.added_lines += authorship.added_lines;
+                    totals.ai_lines += authorship.ai_lines;
+                    totals.ai_lines_without_prompt += authorship.ai_lines_without_prompt;
+                    totals.human_edited_ai_lines += authorship.human_edited_ai_lines;
+                }
+            }
+            if totals.added_lines == 0 {
+                continue;
+            }
+
+            let paths = rule.paths.join(", ");
+            if let Some(max) = rule.max_ai_percent {
+                let unedited = totals.ai_lines - totals.human_edited_ai_lines;
+                let percent = unedited as f64 * 100.0 / totals.added_lines as f64;
+                if percent > max {
+                    violations.push(PolicyViolation {
+                        rule: rule.name.clone(),
+                        severity: rule.severity,
+                        message: format!(
+                            "{:.0}% of the {} lines added in {} are AI-authored without a human edit (limit {}%)",
+                            percent, totals.added_lines, paths, max
+                        ),
+                    });
+                }
+            }
+            if rule.require_prompt_records && totals.ai_lines_without_prompt > 0 {
+                violations.push(PolicyViolation {
+                    rule: rule.name.clone(),
+                    severity: rule.severity,
+                    message: format!(
+                        "{} AI-authored lines added in {} have no prompt record",
+                        totals.ai_lines_without_prompt, paths
+                    ),
+                });
+            }
+        }
+        violations
+    }
+}
+
+impl ChangeAuthorship {
+    /// Lines added by `commits`, attributed from each commit's authorship log.
+    pub fn from_commits(repo: &Repository, commits: &[String]) -> Result<Self, GitAiError> {
+        let mut change = ChangeAuthorship::default();
+        for commit in commits {
+            let mut args = repo.global_args_for_exec();
+            args.extend(
+                [
+                    "diff-tree",
+                    "-p",
+                    "-U0",
+                    "-M",
+                    "--root",
+                    "--no-color",
+                    "--no-commit-id",
+                    commit.as_str(),
+                ]
+                .iter()
+                .map(|s| s.to_string()),
+            );
+            let diff_text = String::from_utf8_lossy(&exec_git(&args)?.stdout).to_string();
+
+            let log = get_authorship(repo, commit);
+            let index = log
+                .as_ref()
+                .map(LineAuthorshipIndex::from_authorship_log)
+                .unwrap_or_default();
+            for (file, lines) in added_lines_by_file(&diff_text) {
+                let shared = log
+                    .as_ref()
+                    .and_then(|log| log.metadata.line_shares.get(&file));
+                let entry = change.files.entry(file.clone()).or_default();
+                for line in lines {
+                    entry.added_lines += 1;
+                    if index.hash_for_line(&file, line).is_none() {
+                        continue;
+                    }
+                    entry.ai_lines += 1;
+                    if index.prompt_for_line(&file, line).is_none() {
+                        entry.ai_lines_without_prompt += 1;
+                    }
+                    if shared.is_some_and(|shared| shared.contains_key(&line)) {
+                        entry.human_edited_ai_lines += 1;
+                    }
+                }
+            }
+        }
+        Ok(change)
+    }
+
+    /// Lines a `git commit` with `scope` is about to add, attributed from the working log.
+    pub fn from_commit_scope(repo: &Repository, scope: &CommitScope) -> Result<Self, GitAiError> {
+        let base = repo
+            .head()
+            .ok()
+            .and_then(|head| head.target().ok())
+            .unwrap_or_else(|| EMPTY_TREE_HASH.to_string());
+        let mut change = ChangeAuthorship::default();
+        match scope {
+            CommitScope::Index => change.add_diff(repo, &["--cached"], &[], false)?,
+            CommitScope::AllTracked => change.add_diff(repo, &[&base], &[], true)?,
+            CommitScope::Paths {
+                pathspecs,
+                include_index,
+            } => {
+                change.add_diff(repo, &[&base], pathspecs, true)?;
+                if *include_index {
+                    change.add_diff(repo, &["--cached"], &[], false)?;
+                }
+            }
+            CommitScope::Unchanged => {}
+        }
+        Ok(change)
+    }
+
+    /// Add the lines of `git diff <diff_args> -- <pathspecs>` for files not counted yet,
+    /// reading each file's new content from the working tree or the index.
+    fn add_diff(
+        &mut self,
+        repo: &Repository,
+        diff_args: &[&str],
+        pathspecs: &[String],
+        from_worktree: bool,
+    ) -> Result<(), GitAiError> {
+        let mut args = repo.global_args_for_exec();
+        args.extend(["diff", "-U0", "-M", "--no-color"].map(str::to_string));
+        args.extend(diff_args.iter().map(|arg| arg.to_string()));
+        if !pathspecs.is_empty() {
+            args.push("--".to_string());
+            args.extend(pathspecs.iter().cloned());
+        }
+        let diff_text = String::from_utf8_lossy(&exec_git(&args)?.stdout).to_string();
+        let workdir = repo.workdir()?;
+
+        for (file, lines) in added_lines_by_file(&diff_text) {
+            if self.files.contains_key(&file) {
+                continue;
+            }
+            let content = if from_worktree {
+                std::fs::read_to_string(workdir.join(&file)).unwrap_or_default()
+            } else {
+                let mut args = repo.global_args_for_exec();
+                args.push("show".to_string());
+                args.push(format!(":{}", file));
+                exec_git(&args)
+                    .map(|output| String::from_utf8_lossy(&output.stdout).to_string())
+                    .unwrap_or_default()
+            };
+            let authorship = WorkingLogAuthorship::read(repo, &file, &content);
+
+            let entry = self.files.entry(file).or_default();
+            for line in lines {
+                entry.added_lines += 1;
+                let Some(hash) = authorship.ai_lines.get(&line) else {
+                    continue;
+                };
+                entry.ai_lines += 1;
+                if !authorship.prompts.contains(hash) {
+                    entry.ai_lines_without_prompt += 1;
+                }
+                if authorship.shared_lines.contains(&line) {
+                    entry.human_edited_ai_lines += 1;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// AI authorship of a file's uncommitted lines, from its latest working log entry.
+#[derive(Default)]
+struct WorkingLogAuthorship {
+    /// Line -> prompt hash of every AI line
+    ai_lines: HashMap<u32, String>,
+    /// Prompt hashes the commit will have prompt records for
+    prompts: HashSet<String>,
+    /// Lines a human and AI both wrote part of
+    shared_lines: HashSet<u32>,
+}
+
+impl WorkingLogAuthorship {
+    /// Empty unless the latest checkpoint of `file` matches `content`, since its line
+    /// numbers are meaningless otherwise.
+    fn read(repo: &Repository, file: &str, content: &str) -> Self {
+        let Some((checkpoints, entry)) = latest_working_log_entry(repo, file, content) else {
+            return WorkingLogAuthorship::default();
+        };
+
+        let mut prompts: HashSet<String> = checkpoints
+            .iter()
+            .filter_map(|checkpoint| checkpoint.agent_id.as_ref())
+            .map(|agent_id| generate_short_hash(&agent_id.id, &agent_id.tool))
+            .collect();
+        if let Ok(head) = repo.head().and_then(|head| head.target()) {
+            prompts.extend(
+                repo.storage
+                    .working_log_for_base_commit(&head)
+                    .read_initial_attributions()
+                    .prompts
+                    .into_keys(),
+            );
+        }
+
+        let human = CheckpointKind::Human.to_str();
+        let mut ai_lines = HashMap::new();
+        for attr in &entry.line_attributions {
+            if attr.author_id == human {
+                continue;
+            }
+            for line in attr.start_line..=attr.end_line {
+                ai_lines.insert(line, attr.author_id.clone());
+            }
+        }
+
+        WorkingLogAuthorship {
+            ai_lines,
+            prompts,
+            shared_lines: line_ai_shares(&entry.attributions, content)
+                .into_keys()
+                .collect(),
+        }
+    }
+}
+
+pub fn print_violations(violations: &[PolicyViolation]) {
+    for violation in violations {
+        let label = match violation.severity {
+            PolicySeverity::Warn => "warning",
+            PolicySeverity::Block => "error",
+        };
+        eprintln!(
+            "git-ai policy {} [{}]: {}",
+            label, violation.rule, violation.message
+        );
+    }
+}
+
+pub fn has_blocking(violations: &[PolicyViolation]) -> bool {
+    violations
+        .iter()
+        .any(|violation| violation.severity == PolicySeverity::Block)
+}
+
+fn policy_skipped(command_args: &[String], stage: PolicyStage) -> bool {
+    if std::env::var(SKIP_POLICY_ENV).is_ok_and(|value| value == "1" || value == "true") {
+        return true;
+    }
+    command_args
+        .iter()
+        .any(|arg| arg == "--no-verify" || (stage == PolicyStage::Commit && arg == "-n"))
+}
+
+/// Evaluate the policy in a pre-command hook. Returns false when the command must be
+/// blocked; violations are printed either way.
+pub fn enforce(
+    repo: &Repository,
+    stage: PolicyStage,
+    command_args: &[String],
+    change: impl FnOnce() -> Result<ChangeAuthorship, GitAiError>,
+) -> bool {
+    if policy_skipped(command_args, stage) {
+        return true;
+    }
+    let policy = match Policy::load(repo) {
+        Ok(Some(policy)) => policy,
+        Ok(None) => return true,
+        Err(e) => {
+            // A policy we can't read could hold blocking rules
+            eprintln!("git-ai policy error: {}", e);
+            eprintln!(
+                "Blocked by {}. Use --no-verify or {}=1 to bypass.",
+                POLICY_FILE, SKIP_POLICY_ENV
+            );
+            return false;
+        }
+    };
+    let change = match change() {
+        Ok(change) => change,
+        Err(e) => {
+            // A change we can't attribute could hide a violation of a blocking rule
+            let blocking = policy.rules.iter().any(|rule| {
+                rule.severity == PolicySeverity::Block
+                    && (rule.on.is_empty() || rule.on.contains(&stage))
+            });
+            if !blocking {
+                crate::utils::debug_log(&format!("Failed to evaluate policy: {}", e));
+                return true;
+            }
+            eprintln!("git-ai policy error: could not check the change: {}", e);
+            eprintln!(
+                "Blocked by {}. Use --no-verify or {}=1 to bypass.",
+                POLICY_FILE, SKIP_POLICY_ENV
+            );
+            return false;
+        }
+    };
+
+    let violations = policy.evaluate(Some(stage), &change);
+    print_violations(&violations);
+    if has_blocking(&violations) {
+        eprintln!(
+            "Blocked by {}. Use --no-verify or {}=1 to bypass.",
+            POLICY_FILE, SKIP_POLICY_ENV
+        );
+        return false;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn change(files: &[(&str, u32, u32, u32, u32)]) -> ChangeAuthorship {
+        ChangeAuthorship {
+            files: files
+                .iter()
+                .map(|(file, added, ai, without_prompt, human_edited)| {
+                    (
+                        file.to_string(),
+                        FileChangeAuthorship {
+                            added_lines: *added,
+                            ai_lines: *ai,
+                            ai_lines_without_prompt: *without_prompt,
+                            human_edited_ai_lines: *human_edited,
+                        },
+                    )
+                })
+                .collect(),
+        }
+    }
+
+    #[test]
+    fn test_policy_evaluate_thresholds_and_stages() {
+        let policy = Policy::parse(
+            r#"
+[[rule]]
+name = "crypto"
+paths = ["src/crypto/**"]
+max_ai_percent = 80
+severity = "block"
+on = ["push"]
+
+[[rule]]
+name = "migrations"
+paths = ["migrations/**"]
+require_prompt_records = true
+"#,
+        )
+        .unwrap();
+
+        let change = change(&[
+            ("src/crypto/aes.rs", 10, 9, 0, 0),
+            ("src/other.rs", 100, 0, 0, 0),
+            ("migrations/001.sql", 4, 2, 1, 0),
+        ]);
+
+        let violations = policy.evaluate(Some(PolicyStage::Push), &change);
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].rule, "crypto");
+        assert!(violations[0].message.starts_with("90% of the 10 lines"));
+        assert!(has_blocking(&violations));
+        assert_eq!(violations[1].severity, PolicySeverity::Warn);
+
+        // The crypto rule only runs on push
+        let violations = policy.evaluate(Some(PolicyStage::Commit), &change);
+        assert_eq!(violations.len(), 1);
+        assert!(!has_blocking(&violations));
+
+        // Under the limit
+        let ok = self::change(&[("src/crypto/aes.rs", 10, 8, 0, 0)]);
+        assert!(policy.evaluate(None, &ok).is_empty());
+
+        // Lines a human edited don't count towards the limit
+        let edited = self::change(&[("src/crypto/aes.rs", 10, 9, 0, 2)]);
+        assert!(policy.evaluate(None, &edited).is_empty());
+    }
+
+    #[test]
+    fn test_policy_parse_rejects_unknown_severity() {
+        assert!(
+            Policy::parse("[[rule]]\nname = \"x\"\npaths = []\nseverity = \"panic\"\n").is_err()
+        );
+    }
+}
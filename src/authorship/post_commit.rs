@@ -1,19 +1,26 @@
 use crate::api::{ApiClient, ApiContext};
-use crate::authorship::authorship_log_serialization::AuthorshipLog;
+use crate::authorship::authorship_log::PromptRecord;
+use crate::authorship::authorship_log_serialization::{AuthorshipLog, generate_short_hash};
+use crate::authorship::deletions::attach_ai_deletions;
+use crate::authorship::file_inheritance::inherit_moved_file_attributions;
 use crate::authorship::ignore::{
     build_ignore_matcher, effective_ignore_patterns, should_ignore_file_with_matcher,
 };
+use crate::authorship::line_shares::attach_line_shares;
+use crate::authorship::merge_resolution::restrict_to_resolution_lines;
 use crate::authorship::prompt_utils::{PromptUpdateResult, update_prompt_from_tool};
+use crate::authorship::reassign::reassign_rewritten_lines;
 use crate::authorship::secrets::{redact_secrets_from_prompts, strip_prompt_messages};
 use crate::authorship::stats::{stats_for_commit_stats, write_stats_to_terminal};
 use crate::authorship::virtual_attribution::VirtualAttributions;
-use crate::authorship::working_log::{Checkpoint, CheckpointKind, WorkingLogEntry};
+use crate::authorship::working_log::{AgentId, Checkpoint, CheckpointKind, WorkingLogEntry};
 use crate::config::{Config, PromptStorageMode};
 use crate::error::GitAiError;
+use crate::git::attribution_events::AttributionEvent;
 use crate::git::refs::notes_add;
 use crate::git::repository::Repository;
 use crate::utils::debug_log;
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::io::IsTerminal;
 
 /// Skip expensive post-commit stats when this threshold is exceeded.
@@ -92,6 +99,37 @@ pub fn post_commit(
         );
     }
 
+    let stale_files = find_stale_checkpoint_files(&parent_working_log, &working_log);
+    if !stale_files.is_empty() {
+        let exclude = Config::get().exclude_stale_checkpoints();
+        if !supress_output {
+            eprintln!(
+                "Warning: AI checkpoints for {} are more than {}h older than the files, whose AI lines are gone; they may be stale{}",
+                stale_files.join(", "),
+                Config::get().stale_checkpoint_hours(),
+                if exclude {
+                    "; excluding them from attribution"
+                } else {
+                    ""
+                }
+            );
+        }
+        if exclude {
+            // Only the AI's stale claims go; human checkpoints of the file still count
+            for checkpoint in &mut parent_working_log {
+                if checkpoint.kind == CheckpointKind::Human {
+                    continue;
+                }
+                checkpoint
+                    .entries
+                    .retain(|entry| !stale_files.contains(&entry.file));
+            }
+            parent_working_log.retain(|checkpoint| {
+                !checkpoint.entries.is_empty() || !checkpoint.whole_file_assets.is_empty()
+            });
+        }
+    }
+
     working_log.write_all_checkpoints(&parent_working_log)?;
 
     // Create VirtualAttributions from working log (fast path - no blame)
@@ -134,6 +172,78 @@ pub fn post_commit(
 
     authorship_log.metadata.base_commit_sha = commit_sha.clone();
 
+    // Lines moved between files (splits and merges) keep the AI attribution they had at the parent
+    if parent_sha != "initial"
+        && let Err(e) =
+            inherit_moved_file_attributions(repo, &parent_sha, &commit_sha, &mut authorship_log)
+    {
+        debug_log(&format!(
+            "[Warning] Failed to inherit moved file attributions: {}",
+            e
+        ));
+    }
+
+    // Record which AI tool deleted lines this commit removed from the parent
+    if parent_sha != "initial"
+        && let Err(e) = attach_ai_deletions(
+            repo,
+            &parent_sha,
+            &commit_sha,
+            &parent_working_log,
+            &human_author,
+            &mut authorship_log,
+        )
+    {
+        debug_log(&format!("[Warning] Failed to record AI deletions: {}", e));
+    }
+
+    // Lines the human and AI wrote together keep the split between them
+    if Config::get().get_feature_flags().char_level_attribution
+        && let Err(e) = attach_line_shares(
+            repo,
+            &parent_sha,
+            &commit_sha,
+            &working_log,
+            &parent_working_log,
+            &mut authorship_log,
+        )
+    {
+        debug_log(&format!("[Warning] Failed to record line shares: {}", e));
+    }
+
+    // Lines the human rewrote past the configured threshold become theirs, keeping a record of
+    // the AI prompt they came from
+    if let Some(threshold) = Config::get().reassign_threshold() {
+        let reassigned = reassign_rewritten_lines(&mut authorship_log, threshold, &human_author);
+        if reassigned > 0 {
+            debug_log(&format!(
+                "Reassigned {} rewritten AI lines to {}",
+                reassigned, human_author
+            ));
+        }
+    }
+
+    // A merge commit only owns the lines written while resolving it; everything else keeps the
+    // attribution it has on the side it came from
+    if parent_sha != "initial"
+        && let Err(e) = restrict_to_resolution_lines(repo, &commit_sha, &mut authorship_log)
+    {
+        debug_log(&format!(
+            "[Warning] Failed to attribute merge resolution: {}",
+            e
+        ));
+    }
+
+    // Whole-file assets carry no line attestations, so map each committed asset straight to the
+    // prompt that produced it. Assets left out of this commit carry over to the next working log.
+    let carried_over_assets = attach_whole_file_assets(
+        repo,
+        &commit_sha,
+        &parent_working_log,
+        &human_author,
+        &mut authorship_log,
+    )?;
+
     // Handle prompts based on effective prompt storage mode for this repository
     // The effective mode considers include/exclude lists and fallback settings
     let effective_storage = Config::get().effective_prompt_storage(&Some(repo.clone()));
@@ -195,6 +305,16 @@ pub fn post_commit(
         .map_err(|_| GitAiError::Generic("Failed to serialize authorship log".to_string()))?;
 
     notes_add(repo, &commit_sha, &authorship_json)?;
+    repo.storage
+        .record_attribution_event(AttributionEvent::commit_attributed(
+            &commit_sha,
+            authorship_log
+                .attestations
+                .iter()
+                .map(|a| a.file_path.clone())
+                .collect(),
+            authorship_log.metadata.prompts.len(),
+        ));
 
     // Compute stats once (needed for both metrics and terminal output), unless preflight
     // estimate predicts this would be too expensive for the commit hook path.
@@ -259,6 +379,13 @@ pub fn post_commit(
             .write_initial_attributions(initial_attributions.files, initial_attributions.prompts)?;
     }
 
+    if !carried_over_assets.is_empty() {
+        let new_working_log = repo_storage.working_log_for_base_commit(&commit_sha);
+        for checkpoint in &carried_over_assets {
+            new_working_log.append_checkpoint(checkpoint)?;
+        }
+    }
+
     // // Clean up old working log
     repo_storage.delete_working_log_for_base_commit(&parent_sha)?;
 
@@ -291,6 +418,90 @@ pub fn post_commit(
     Ok((commit_sha.to_string(), authorship_log))
 }
 
+/// Record committed whole-file assets in the authorship log (asset path -> prompt hash).
+///
+/// The latest AI checkpoint to claim an asset wins. Returns checkpoints holding the assets
+/// that were not part of this commit so they can be carried over to the next working log.
+fn attach_whole_file_assets(
+    repo: &Repository,
+    commit_sha: &str,
+    checkpoints: &[Checkpoint],
+    human_author: &str,
+    authorship_log: &mut AuthorshipLog,
+) -> Result<Vec<Checkpoint>, GitAiError> {
+    let mut latest_by_asset: BTreeMap<String, usize> = BTreeMap::new();
+    for (idx, checkpoint) in checkpoints.iter().enumerate() {
+        if checkpoint.kind == CheckpointKind::Human || checkpoint.agent_id.is_none() {
+            continue;
+        }
+        for asset in &checkpoint.whole_file_assets {
+            latest_by_asset.insert(asset.clone(), idx);
+        }
+    }
+
+    if latest_by_asset.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let asset_paths: HashSet<String> = latest_by_asset.keys().cloned().collect();
+    let committed_files = repo.list_commit_files(commit_sha, Some(&asset_paths))?;
+
+    let mut uncommitted_by_checkpoint: BTreeMap<usize, Vec<String>> = BTreeMap::new();
+    for (asset, idx) in latest_by_asset {
+        if !committed_files.contains(&asset) {
+            uncommitted_by_checkpoint
+                .entry(idx)
+                .or_default()
+                .push(asset);
+            continue;
+        }
+
+        let checkpoint = &checkpoints[idx];
+        let Some(agent_id) = checkpoint.agent_id.as_ref() else {
+            continue;
+        };
+        let prompt_hash = generate_short_hash(&agent_id.id, &agent_id.tool);
+        authorship_log
+            .metadata
+            .prompts
+            .entry(prompt_hash.clone())
+            .or_insert_with(|| prompt_record_for_checkpoint(checkpoint, agent_id, human_author));
+        authorship_log.metadata.assets.insert(asset, prompt_hash);
+    }
+
+    Ok(uncommitted_by_checkpoint
+        .into_iter()
+        .map(|(idx, assets)| {
+            let mut checkpoint = checkpoints[idx].clone();
+            checkpoint.entries = Vec::new();
+            checkpoint.whole_file_assets = assets;
+            checkpoint
+        })
+        .collect())
+}
+
+/// Prompt record for an AI checkpoint whose prompt has no line attestations in the commit.
+pub(crate) fn prompt_record_for_checkpoint(
+    checkpoint: &Checkpoint,
+    agent_id: &AgentId,
+    human_author: &str,
+) -> PromptRecord {
+    PromptRecord {
+        agent_id: agent_id.clone(),
+        human_author: Some(human_author.to_string()),
+        messages: checkpoint
+            .transcript
+            .as_ref()
+            .map(|t| t.messages().to_vec())
+            .unwrap_or_default(),
+        total_additions: 0,
+        total_deletions: 0,
+        accepted_lines: 0,
+        overriden_lines: 0,
+        messages_url: None,
+    }
+}
+
 #[derive(Debug, Clone)]
 enum StatsSkipReason {
     MergeCommit,
@@ -416,6 +627,77 @@ fn update_prompts_to_latest(checkpoints: &mut [Checkpoint]) -> Result<(), GitAiE
     Ok(())
 }
 
+/// Files whose latest AI checkpoint looks left over from an earlier editing session: the file
+/// on disk was modified more than `stale_checkpoint_hours` after that checkpoint was taken, and
+/// none of the lines the AI had written by then are still in it (the session was reverted).
+fn find_stale_checkpoint_files(
+    checkpoints: &[Checkpoint],
+    working_log: &crate::git::repo_storage::PersistedWorkingLog,
+) -> Vec<String> {
+    let threshold_secs = Config::get().stale_checkpoint_hours().saturating_mul(3600);
+    if threshold_secs == 0 {
+        return Vec::new();
+    }
+
+    let mut latest_ai: BTreeMap<&str, (u64, &WorkingLogEntry)> = BTreeMap::new();
+    for checkpoint in checkpoints {
+        if checkpoint.kind == CheckpointKind::Human {
+            continue;
+        }
+        for entry in &checkpoint.entries {
+            let latest = latest_ai
+                .entry(entry.file.as_str())
+                .or_insert((checkpoint.timestamp, entry));
+            if checkpoint.timestamp >= latest.0 {
+                *latest = (checkpoint.timestamp, entry);
+            }
+        }
+    }
+
+    let human = CheckpointKind::Human.to_str();
+    let mut stale = Vec::new();
+    for (file, (timestamp, entry)) in latest_ai {
+        let modified = std::fs::metadata(working_log.to_repo_absolute_path(file))
+            .and_then(|metadata| metadata.modified())
+            .ok()
+            .and_then(|modified| modified.duration_since(std::time::UNIX_EPOCH).ok())
+            .map(|since_epoch| since_epoch.as_secs());
+        if modified.is_none_or(|modified| modified <= timestamp.saturating_add(threshold_secs)) {
+            continue;
+        }
+
+        // A touched file (checkout, formatter) gets a new mtime without losing the AI's work
+        let (Ok(checkpointed), Ok(current)) = (
+            working_log.get_file_version(&entry.blob_sha),
+            working_log.read_current_file_content(file),
+        ) else {
+            continue;
+        };
+        let checkpointed_lines: Vec<&str> = checkpointed.lines().collect();
+        let current_lines: HashSet<&str> = current.lines().map(str::trim).collect();
+        let ai_lines_remain = entry
+            .line_attributions
+            .iter()
+            .filter(|attr| attr.author_id != human)
+            .flat_map(|attr| attr.start_line..=attr.end_line)
+            .filter_map(|line| checkpointed_lines.get(line as usize - 1))
+            .map(|line| line.trim())
+            .any(|line| !line.is_empty() && current_lines.contains(line));
+        if ai_lines_remain {
+            continue;
+        }
+
+        debug_log(&format!(
+            "Checkpoint for {} at {} is stale (file modified at {}, none of its AI lines remain)",
+            file,
+            timestamp,
+            modified.unwrap_or_default()
+        ));
+        stale.push(file.to_string());
+    }
+    stale
+}
+
 /// Batch upsert all prompts from checkpoints to the internal database.
 /// For each unique agent_id (tool:id), only the LAST checkpoint is inserted.
 /// This mirrors the deduplication logic in update_prompts_to_latest().
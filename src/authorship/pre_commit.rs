@@ -1,8 +1,23 @@
 use crate::authorship::working_log::CheckpointKind;
+use crate::commands::sandbox::Sandbox;
 use crate::error::GitAiError;
 use crate::git::repository::Repository;
 
 pub fn pre_commit(repo: &Repository, default_author: String) -> Result<(), GitAiError> {
+    // In an agent sandbox, everything not yet checkpointed was written by the agent.
+    if let Some(sandbox) = Sandbox::load(repo) {
+        crate::commands::checkpoint::run(
+            repo,
+            &default_author,
+            CheckpointKind::AiAgent,
+            false,
+            false,
+            true,
+            Some(sandbox.agent_run_result()),
+            false,
+        )?;
+    }
+
     // Run checkpoint as human editor.
     let result: Result<(usize, usize, usize), GitAiError> = crate::commands::checkpoint::run(
         repo,
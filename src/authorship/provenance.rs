@@ -0,0 +1,211 @@
+//! `git describe`-style provenance for a single line.
+//!
+//! The provenance string is one line of space-separated `key=value` fields in a fixed order,
+//! so build systems can embed it in generated headers or SBOM fields and diff it:
+//!
+//! ```text
+//! git-ai-provenance/1 file=src/app.rs:42 tag=v1.2.0+3 commit=<sha> by=ai:cursor model=claude-sonnet-4 checkpoint=1a2b3c4d5e6f7a8b
+//! git-ai-provenance/1 file=README.md:1 tag=none commit=<sha> by=human:jane@example.com model=none checkpoint=none
+//! ```
+//!
+//! `tag` is the nearest tag reachable from the commit plus the number of commits since it,
+//! `commit` is the commit that introduced the line (`uncommitted` for lines only in the
+//! working tree), `checkpoint` is the id of the AI session that wrote it. Spaces and `%` in
+//! values are percent-encoded.
+
+use crate::authorship::authorship_log::PromptRecord;
+use crate::commands::blame::{
+    GitAiBlameOptions, is_uncommitted_sha, working_log_line_attributions,
+};
+use crate::commands::show_prompt::prompt_for_line;
+use crate::error::GitAiError;
+use crate::git::repository::{Repository, exec_git};
+
+pub const PROVENANCE_VERSION: &str = "git-ai-provenance/1";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LineAuthor {
+    Ai {
+        tool: String,
+        model: String,
+        checkpoint: String,
+    },
+    /// Email of the human author
+    Human(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Provenance {
+    pub file: String,
+    pub line: u32,
+    /// Nearest tag and the number of commits since it
+    pub tag: Option<(String, u32)>,
+    /// None for uncommitted lines
+    pub commit: Option<String>,
+    pub author: LineAuthor,
+}
+
+impl Provenance {
+    pub fn to_line(&self) -> String {
+        let tag = match &self.tag {
+            Some((tag, distance)) => format!("{}+{}", tag, distance),
+            None => "none".to_string(),
+        };
+        let (by, model, checkpoint) = match &self.author {
+            LineAuthor::Ai {
+                tool,
+                model,
+                checkpoint,
+            } => {
+                let model = if model.is_empty() { "none" } else { model };
+                (format!("ai:{}", tool), model, checkpoint.as_str())
+            }
+            LineAuthor::Human(email) => (format!("human:{}", email), "none", "none"),
+        };
+        let fields = [
+            ("file", format!("{}:{}", self.file, self.line)),
+            ("tag", tag),
+            (
+                "commit",
+                self.commit
+                    .clone()
+                    .unwrap_or_else(|| "uncommitted".to_string()),
+            ),
+            ("by", by),
+            ("model", model.to_string()),
+            ("checkpoint", checkpoint.to_string()),
+        ];
+
+        let mut output = PROVENANCE_VERSION.to_string();
+        for (key, value) in fields {
+            output.push(' ');
+            output.push_str(key);
+            output.push('=');
+            output.push_str(&encode_value(&value));
+        }
+        output
+    }
+}
+
+fn encode_value(value: &str) -> String {
+    value.replace('%', "%25").replace(' ', "%20")
+}
+
+/// Provenance of `line` (1-based, current line number) of `file`, which is absolute or
+/// relative to the repository root.
+pub fn line_provenance(repo: &Repository, file: &str, line: u32) -> Result<Provenance, GitAiError> {
+    let file = repo.repo_relative_path(file)?;
+    let hunk = repo
+        .blame_hunks(&file, line, line, &GitAiBlameOptions::default())?
+        .into_iter()
+        .next()
+        .ok_or_else(|| GitAiError::Generic(format!("{} has no line {}", file, line)))?;
+
+    if is_uncommitted_sha(&hunk.commit_sha) {
+        let head = repo.head().and_then(|head| head.target()).ok();
+        let content = std::fs::read_to_string(repo.workdir()?.join(&file))?;
+        let pending =
+            working_log_line_attributions(repo, &file, &content).and_then(|(lines, prompts)| {
+                let prompt_id = lines.get(&line)?.clone();
+                let record = prompts.get(&prompt_id)?.clone();
+                Some((prompt_id, record))
+            });
+        let author = match pending {
+            Some((prompt_id, record)) => ai_author(&prompt_id, &record),
+            None => LineAuthor::Human(
+                repo.config_get_str("user.email")
+                    .ok()
+                    .flatten()
+                    .unwrap_or_default(),
+            ),
+        };
+        return Ok(Provenance {
+            file,
+            line,
+            tag: head.and_then(|head| nearest_tag(repo, &head)),
+            commit: None,
+            author,
+        });
+    }
+
+    let orig_file = hunk.orig_file_path.as_deref().unwrap_or(&file);
+    let orig_line = hunk.orig_range.0 + (line - hunk.range.0);
+    let author = match prompt_for_line(repo, &hunk.commit_sha, orig_file, orig_line) {
+        Ok(found) => ai_author(&found.prompt_id, &found.prompt),
+        Err(_) => LineAuthor::Human(hunk.author_email.clone()),
+    };
+
+    Ok(Provenance {
+        file,
+        line,
+        tag: nearest_tag(repo, &hunk.commit_sha),
+        commit: Some(hunk.commit_sha),
+        author,
+    })
+}
+
+fn ai_author(prompt_id: &str, record: &PromptRecord) -> LineAuthor {
+    LineAuthor::Ai {
+        tool: record.agent_id.tool.clone(),
+        model: record.agent_id.model.clone(),
+        checkpoint: prompt_id.to_string(),
+    }
+}
+
+/// Nearest tag reachable from `commit` and the commits since it, from `git describe`.
+fn nearest_tag(repo: &Repository, commit: &str) -> Option<(String, u32)> {
+    let mut args = repo.global_args_for_exec();
+    args.extend(
+        ["describe", "--tags", "--long", commit]
+            .iter()
+            .map(|s| s.to_string()),
+    );
+    let output = exec_git(&args).ok()?;
+    parse_describe(String::from_utf8_lossy(&output.stdout).trim())
+}
+
+/// `<tag>-<distance>-g<sha>`; the tag itself may contain dashes.
+fn parse_describe(described: &str) -> Option<(String, u32)> {
+    let mut parts = described.rsplitn(3, '-');
+    let _sha = parts.next()?;
+    let distance = parts.next()?.parse().ok()?;
+    let tag = parts.next()?;
+    Some((tag.to_string(), distance))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_describe_keeps_dashes_in_tags() {
+        assert_eq!(
+            parse_describe("release-2024-01-3-gabc1234"),
+            Some(("release-2024-01".to_string(), 3))
+        );
+        assert_eq!(
+            parse_describe("v1.0.0-0-gabc1234"),
+            Some(("v1.0.0".to_string(), 0))
+        );
+        assert_eq!(parse_describe("abc1234"), None);
+    }
+
+    #[test]
+    fn test_provenance_line_encodes_values() {
+        let provenance = Provenance {
+            file: "docs/my notes.md".to_string(),
+            line: 3,
+            tag: None,
+            commit: None,
+            author: LineAuthor::Ai {
+                tool: "cursor".to_string(),
+                model: String::new(),
+                checkpoint: "1a2b3c4d".to_string(),
+            },
+        };
+        assert_eq!(
+            provenance.to_line(),
+            "git-ai-provenance/1 file=docs/my%20notes.md:3 tag=none commit=uncommitted by=ai:cursor model=none checkpoint=1a2b3c4d"
+        );
+    }
+}
@@ -4,11 +4,18 @@ use std::collections::HashSet;
 use serde::Deserialize;
 use serde::Serialize;
 
+use crate::authorship::authorship_log::LineRange;
 use crate::authorship::diff_ai_accepted::diff_ai_accepted_stats;
+use crate::authorship::identity::{ClassifiedIdentity, classify_commit};
 use crate::authorship::ignore::{build_ignore_matcher, should_ignore_file_with_matcher};
-use crate::authorship::stats::{CommitStats, stats_for_commit_stats, stats_from_authorship_log};
+use crate::authorship::line_filter::LineFilter;
+use crate::authorship::stats::{
+    CommitStats, credit_identity_lines, stats_for_commit_stats_with_filter,
+    stats_from_authorship_log,
+};
+use crate::commands::blame::GitAiBlameOptions;
 use crate::error::GitAiError;
-use crate::git::refs::{CommitAuthorship, get_commits_with_notes_from_list};
+use crate::git::refs::{CommitAuthorship, get_authorship, get_commits_with_notes_from_list};
 use crate::git::repository::{CommitRange, Repository};
 use crate::utils::debug_log;
 
@@ -44,6 +51,8 @@ pub fn range_authorship(
     commit_range: CommitRange,
     pre_fetch_contents: bool,
     ignore_patterns: &[String],
+    line_filter: &LineFilter,
+    raw_models: bool,
 ) -> Result<RangeAuthorshipStats, GitAiError> {
     commit_range.is_valid()?;
 
@@ -114,8 +123,13 @@ pub fn range_authorship(
     let commit_authorship = get_commits_with_notes_from_list(repository, &commit_shas)?;
 
     // Calculate range stats - now just pass start, end, and commits
-    let range_stats =
-        calculate_range_stats_direct(repository, commit_range_clone, ignore_patterns)?;
+    let range_stats = calculate_range_stats_direct(
+        repository,
+        commit_range_clone,
+        ignore_patterns,
+        line_filter,
+        raw_models,
+    )?;
 
     Ok(RangeAuthorshipStats {
         authorship_stats: RangeAuthorshipStatsData {
@@ -205,6 +219,10 @@ fn create_authorship_log_for_range(
                     ),
                     base_commit_sha: end_sha.to_string(),
                     prompts: std::collections::BTreeMap::new(),
+                    assets: std::collections::BTreeMap::new(),
+                    deletions: std::collections::BTreeMap::new(),
+                    line_shares: std::collections::BTreeMap::new(),
+                    reassignments: std::collections::BTreeMap::new(),
                 },
             },
         );
@@ -391,23 +409,43 @@ fn get_git_diff_stats_for_range(
 
 /// Calculate AI vs human line contributions for a commit range
 /// Uses VirtualAttributions approach to create an in-memory squash
+/// Only added lines that pass `line_filter` in the end commit are counted
 fn calculate_range_stats_direct(
     repo: &Repository,
     commit_range: CommitRange,
     ignore_patterns: &[String],
+    line_filter: &LineFilter,
+    raw_models: bool,
 ) -> Result<CommitStats, GitAiError> {
     let start_sha = commit_range.start_oid.clone();
     let end_sha = commit_range.end_oid.clone();
     // Special case: single commit range (start == end)
     if start_sha == end_sha {
-        return stats_for_commit_stats(repo, &end_sha, ignore_patterns);
+        return stats_for_commit_stats_with_filter(
+            repo,
+            &end_sha,
+            ignore_patterns,
+            line_filter,
+            raw_models,
+        );
     }
 
     // Step 1: Get git diff stats between start and end
-    let (git_diff_added_lines, git_diff_deleted_lines) =
+    let (mut git_diff_added_lines, git_diff_deleted_lines) =
         get_git_diff_stats_for_range(repo, &start_sha, &end_sha, ignore_patterns)?;
 
-    let diff_ai_stats = diff_ai_accepted_stats(repo, &start_sha, &end_sha, None, ignore_patterns)?;
+    let diff_ai_stats = diff_ai_accepted_stats(
+        repo,
+        &start_sha,
+        &end_sha,
+        None,
+        ignore_patterns,
+        line_filter,
+        raw_models,
+    )?;
+    if line_filter.is_active() {
+        git_diff_added_lines = diff_ai_stats.counted_lines;
+    }
 
     // Step 2: Create in-memory authorship log for the range, filtered to only commits in the range
     let commit_shas = commit_range.clone().all_commits();
@@ -415,17 +453,101 @@ fn calculate_range_stats_direct(
         create_authorship_log_for_range(repo, &start_sha, &end_sha, &commit_shas, ignore_patterns)?;
 
     // Step 3: Calculate stats from the authorship log
-    let stats = stats_from_authorship_log(
+    let mut stats = stats_from_authorship_log(
         Some(&authorship_log),
         git_diff_added_lines,
         git_diff_deleted_lines,
         diff_ai_stats.total_ai_accepted,
         &diff_ai_stats.per_tool_model,
+        raw_models,
     );
 
+    // Step 4: Lines bots, generators or AI identities committed are not the human's
+    apply_identity_categories(
+        repo,
+        &start_sha,
+        &end_sha,
+        &commit_shas,
+        ignore_patterns,
+        line_filter,
+        &mut stats,
+    )?;
+
     Ok(stats)
 }
 
+/// Move the human lines of the range that commits by classified identities added to their
+/// category. Each added line is blamed to the commit that wrote it; lines that commit's note
+/// attests to AI are already counted as AI.
+fn apply_identity_categories(
+    repo: &Repository,
+    start_sha: &str,
+    end_sha: &str,
+    commit_shas: &[String],
+    ignore_patterns: &[String],
+    line_filter: &LineFilter,
+    stats: &mut CommitStats,
+) -> Result<(), GitAiError> {
+    let mut classified: HashMap<String, ClassifiedIdentity> = HashMap::new();
+    for sha in commit_shas {
+        if let Some(identity) = classify_commit(&repo.find_commit(sha.clone())?) {
+            classified.insert(sha.clone(), identity);
+        }
+    }
+    if classified.is_empty() {
+        return Ok(());
+    }
+
+    let ignore_matcher = build_ignore_matcher(ignore_patterns);
+    let options = GitAiBlameOptions {
+        newest_commit: Some(end_sha.to_string()),
+        ..Default::default()
+    };
+    let mut notes = HashMap::new();
+    let mut lines_by_commit: HashMap<String, u32> = HashMap::new();
+    for (file_path, mut lines) in repo.diff_added_lines(start_sha, end_sha, None)? {
+        if should_ignore_file_with_matcher(&file_path, &ignore_matcher) {
+            continue;
+        }
+        lines.sort_unstable();
+        lines.dedup();
+        line_filter.retain_committed_lines(repo, end_sha, &file_path, &mut lines);
+        for range in LineRange::compress_lines(&lines) {
+            let (start, end) = match range {
+                LineRange::Single(line) => (line, line),
+                LineRange::Range(start, end) => (start, end),
+            };
+            for hunk in repo.blame_hunks(&file_path, start, end, &options)? {
+                if !classified.contains_key(&hunk.commit_sha) {
+                    continue;
+                }
+                let note = notes
+                    .entry(hunk.commit_sha.clone())
+                    .or_insert_with(|| get_authorship(repo, &hunk.commit_sha));
+                let path = hunk.orig_file_path.as_deref().unwrap_or(&file_path);
+                let attested = note
+                    .as_ref()
+                    .and_then(|log| log.attestations.iter().find(|a| a.file_path == path));
+                let human_lines = (hunk.orig_range.0..=hunk.orig_range.1)
+                    .filter(|line| {
+                        !attested.is_some_and(|attestation| {
+                            attestation.entries.iter().any(|entry| {
+                                entry.line_ranges.iter().any(|range| range.contains(*line))
+                            })
+                        })
+                    })
+                    .count() as u32;
+                *lines_by_commit.entry(hunk.commit_sha).or_insert(0) += human_lines;
+            }
+        }
+    }
+
+    for (sha, lines) in lines_by_commit {
+        credit_identity_lines(stats, &classified[&sha], lines);
+    }
+    Ok(())
+}
+
 pub fn print_range_authorship_stats(stats: &RangeAuthorshipStats) {
     println!("\n");
 
@@ -509,7 +631,14 @@ mod tests {
             "package-lock.json".to_string(),
             "yarn.lock".to_string(),
         ];
-        let stats = range_authorship(commit_range, false, &lockfile_patterns).unwrap();
+        let stats = range_authorship(
+            commit_range,
+            false,
+            &lockfile_patterns,
+            &LineFilter::default(),
+            false,
+        )
+        .unwrap();
 
         // Verify stats
         assert_eq!(stats.authorship_stats.total_commits, 1);
@@ -553,7 +682,14 @@ mod tests {
             "package-lock.json".to_string(),
             "yarn.lock".to_string(),
         ];
-        let stats = range_authorship(commit_range, false, &lockfile_patterns).unwrap();
+        let stats = range_authorship(
+            commit_range,
+            false,
+            &lockfile_patterns,
+            &LineFilter::default(),
+            false,
+        )
+        .unwrap();
 
         // Verify stats - should include all commits from beginning
         assert_eq!(stats.authorship_stats.total_commits, 2);
@@ -597,7 +733,14 @@ mod tests {
             "package-lock.json".to_string(),
             "yarn.lock".to_string(),
         ];
-        let stats = range_authorship(commit_range, false, &lockfile_patterns).unwrap();
+        let stats = range_authorship(
+            commit_range,
+            false,
+            &lockfile_patterns,
+            &LineFilter::default(),
+            false,
+        )
+        .unwrap();
 
         // For single commit, should use stats_for_commit_stats
         assert_eq!(stats.authorship_stats.total_commits, 1);
@@ -654,7 +797,14 @@ mod tests {
             "package-lock.json".to_string(),
             "yarn.lock".to_string(),
         ];
-        let stats = range_authorship(commit_range, false, &lockfile_patterns).unwrap();
+        let stats = range_authorship(
+            commit_range,
+            false,
+            &lockfile_patterns,
+            &LineFilter::default(),
+            false,
+        )
+        .unwrap();
 
         // Verify stats
         assert_eq!(stats.authorship_stats.total_commits, 3);
@@ -692,7 +842,14 @@ mod tests {
             "package-lock.json".to_string(),
             "yarn.lock".to_string(),
         ];
-        let stats = range_authorship(commit_range, false, &lockfile_patterns).unwrap();
+        let stats = range_authorship(
+            commit_range,
+            false,
+            &lockfile_patterns,
+            &LineFilter::default(),
+            false,
+        )
+        .unwrap();
 
         // Should have 1 commit but no diffs since start == end
         assert_eq!(stats.authorship_stats.total_commits, 1);
@@ -731,7 +888,14 @@ mod tests {
             "package-lock.json".to_string(),
             "yarn.lock".to_string(),
         ];
-        let stats = range_authorship(commit_range, false, &lockfile_patterns).unwrap();
+        let stats = range_authorship(
+            commit_range,
+            false,
+            &lockfile_patterns,
+            &LineFilter::default(),
+            false,
+        )
+        .unwrap();
 
         // Verify all files are included
         assert_eq!(stats.authorship_stats.total_commits, 1);
@@ -791,7 +955,14 @@ mod tests {
             "package-lock.json".to_string(),
             "yarn.lock".to_string(),
         ];
-        let stats = range_authorship(commit_range, false, &lockfile_patterns).unwrap();
+        let stats = range_authorship(
+            commit_range,
+            false,
+            &lockfile_patterns,
+            &LineFilter::default(),
+            false,
+        )
+        .unwrap();
 
         // Verify lockfile is excluded: only 2 lines added (from main.rs), not 1000+ from lockfile
         assert_eq!(stats.authorship_stats.total_commits, 1);
@@ -862,7 +1033,14 @@ mod tests {
             "package-lock.json".to_string(),
             "yarn.lock".to_string(),
         ];
-        let stats = range_authorship(commit_range, false, &lockfile_patterns).unwrap();
+        let stats = range_authorship(
+            commit_range,
+            false,
+            &lockfile_patterns,
+            &LineFilter::default(),
+            false,
+        )
+        .unwrap();
 
         // Key assertion: git_diff should only count lib.rs changes (3 lines), not package-lock.json (3000 lines)
         assert_eq!(stats.authorship_stats.total_commits, 2);
@@ -927,7 +1105,14 @@ mod tests {
             "poetry.lock".to_string(),
             "go.sum".to_string(),
         ];
-        let stats = range_authorship(commit_range, false, &lockfile_patterns).unwrap();
+        let stats = range_authorship(
+            commit_range,
+            false,
+            &lockfile_patterns,
+            &LineFilter::default(),
+            false,
+        )
+        .unwrap();
 
         // Verify: only the 1 README line is counted, all lockfiles excluded (2000 lines ignored)
         assert_eq!(stats.authorship_stats.total_commits, 1);
@@ -983,7 +1168,14 @@ mod tests {
             "package-lock.json".to_string(),
             "yarn.lock".to_string(),
         ];
-        let stats = range_authorship(commit_range, false, &lockfile_patterns).unwrap();
+        let stats = range_authorship(
+            commit_range,
+            false,
+            &lockfile_patterns,
+            &LineFilter::default(),
+            false,
+        )
+        .unwrap();
 
         // Verify: no lines counted since only lockfiles changed
         assert_eq!(stats.authorship_stats.total_commits, 1);
@@ -1424,7 +1616,14 @@ mod tests {
             "*lock.json".to_string(), // Matches package-lock.json
             "*.generated.*".to_string(),
         ];
-        let stats = range_authorship(commit_range, false, &glob_patterns).unwrap();
+        let stats = range_authorship(
+            commit_range,
+            false,
+            &glob_patterns,
+            &LineFilter::default(),
+            false,
+        )
+        .unwrap();
 
         // Should only count the 1 line in main.rs, ignoring 1700 lines in lockfiles and generated files
         assert_eq!(stats.range_stats.git_diff_added_lines, 1);
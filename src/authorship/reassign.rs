@@ -0,0 +1,333 @@
+//! Hand AI-attributed lines over to the human.
+//!
+//! When a human substantially rewrites lines an AI tool wrote, the line attestations can still
+//! name the tool: the rewrite reused the line, or the edit landed after the checkpoint.
+//! Reassigning removes the lines from the AI attestation of the commit that introduced them,
+//! so blame and stats count them as human, and records the transfer in the note's
+//! `reassignments` metadata. The prompt record stays, so the original AI authorship is kept
+//! as history rather than overwritten.
+//!
+//! `git-ai reassign` does this by hand for a line range. With the `reassign_threshold` config
+//! set, each commit also reassigns the shared lines whose human share exceeds the threshold.
+
+use crate::authorship::authorship_log::LineRange;
+use crate::authorship::authorship_log_serialization::{
+    AuthorshipLog, Reassignment, format_line_ranges, parse_line_ranges,
+};
+use crate::authorship::imara_diff_utils::unchanged_line_map;
+use crate::authorship::virtual_attribution::get_file_content_at_commit;
+use crate::commands::blame::{GitAiBlameOptions, is_uncommitted_sha};
+use crate::error::GitAiError;
+use crate::git::refs::{get_reference_as_authorship_log_v3, notes_add};
+use crate::git::repository::Repository;
+use std::collections::BTreeMap;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+pub const REASON_MANUAL: &str = "manual";
+pub const REASON_REWRITE: &str = "rewrite";
+
+/// Lines of one commit's note that were reassigned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReassignedCommit {
+    pub commit: String,
+    /// Path the lines are attested under in that commit
+    pub path: String,
+    pub lines: u32,
+}
+
+/// Reassign the AI lines of `file` in `ranges` (1-based, inclusive, current line numbers) to
+/// the human, editing the notes of the commits that introduced them. Lines that are not
+/// committed or not attributed to AI are left alone.
+pub fn reassign_lines(
+    repo: &Repository,
+    file: &str,
+    ranges: &[(u32, u32)],
+    by: &str,
+) -> Result<Vec<ReassignedCommit>, GitAiError> {
+    // Commit -> attested path -> lines in that commit's version of the file
+    let mut by_commit: BTreeMap<String, BTreeMap<String, Vec<u32>>> = BTreeMap::new();
+    for (start, end) in ranges {
+        let hunks = repo.blame_hunks(file, *start, *end, &GitAiBlameOptions::default())?;
+        for hunk in hunks {
+            if is_uncommitted_sha(&hunk.commit_sha) {
+                continue;
+            }
+            let path = hunk
+                .orig_file_path
+                .clone()
+                .unwrap_or_else(|| file.to_string());
+            let lines = by_commit
+                .entry(hunk.commit_sha.clone())
+                .or_default()
+                .entry(path)
+                .or_default();
+            for offset in 0..=(hunk.range.1 - hunk.range.0) {
+                let current = hunk.range.0 + offset;
+                if current >= *start && current <= *end {
+                    lines.push(hunk.orig_range.0 + offset);
+                }
+            }
+        }
+    }
+
+    let timestamp = now();
+    let mut reassigned = Vec::new();
+    for (commit, paths) in by_commit {
+        let Ok(mut log) = get_reference_as_authorship_log_v3(repo, &commit) else {
+            continue;
+        };
+        let mut changed = false;
+        for (path, mut lines) in paths {
+            lines.sort_unstable();
+            lines.dedup();
+            let count = transfer_to_human(&mut log, &path, &lines, by, REASON_MANUAL, timestamp);
+            if count > 0 {
+                changed = true;
+                reassigned.push(ReassignedCommit {
+                    commit: commit.clone(),
+                    path,
+                    lines: count,
+                });
+            }
+        }
+        if changed {
+            let note = log.serialize_to_string().map_err(|_| {
+                GitAiError::Generic("Failed to serialize authorship log".to_string())
+            })?;
+            notes_add(repo, &commit, &note)?;
+        }
+    }
+    Ok(reassigned)
+}
+
+/// Reassign the AI lines of a new authorship log whose human share (from `line_shares`) is
+/// above `threshold` percent. Returns the number of lines reassigned.
+pub fn reassign_rewritten_lines(log: &mut AuthorshipLog, threshold: u32, by: &str) -> u32 {
+    let rewritten: Vec<(String, Vec<u32>)> = log
+        .metadata
+        .line_shares
+        .iter()
+        .map(|(path, lines)| {
+            let lines = lines
+                .iter()
+                .filter(|(_, shares)| {
+                    let ai_total: u32 = shares.values().map(|share| *share as u32).sum();
+                    100u32.saturating_sub(ai_total) > threshold
+                })
+                .map(|(line, _)| *line)
+                .collect();
+            (path.clone(), lines)
+        })
+        .collect();
+
+    let timestamp = now();
+    rewritten
+        .into_iter()
+        .map(|(path, lines)| transfer_to_human(log, &path, &lines, by, REASON_REWRITE, timestamp))
+        .sum()
+}
+
+/// Move `lines` of `path` from their AI attestations to the human and record the transfer.
+/// Returns the number of lines that were attested to AI.
+fn transfer_to_human(
+    log: &mut AuthorshipLog,
+    path: &str,
+    lines: &[u32],
+    by: &str,
+    reason: &str,
+    timestamp: u64,
+) -> u32 {
+    let Some(attestation) = log
+        .attestations
+        .iter_mut()
+        .find(|attestation| attestation.file_path == path)
+    else {
+        return 0;
+    };
+
+    let mut transfers = Vec::new();
+    for entry in &mut attestation.entries {
+        let taken: Vec<u32> = lines
+            .iter()
+            .copied()
+            .filter(|line| entry.line_ranges.iter().any(|range| range.contains(*line)))
+            .collect();
+        if taken.is_empty() {
+            continue;
+        }
+        let taken_ranges = LineRange::compress_lines(&taken);
+        entry.remove_line_ranges(&taken_ranges);
+        transfers.push((entry.hash.clone(), taken.len() as u32, taken_ranges));
+    }
+    attestation
+        .entries
+        .retain(|entry| !entry.line_ranges.is_empty());
+    log.attestations
+        .retain(|attestation| !attestation.entries.is_empty());
+    if transfers.is_empty() {
+        return 0;
+    }
+
+    // The split no longer matters once the human owns the line, and a dominant-author rule
+    // must not hand it back to the tool
+    if let Some(shares) = log.metadata.line_shares.get_mut(path) {
+        for line in lines {
+            shares.remove(line);
+        }
+        if shares.is_empty() {
+            log.metadata.line_shares.remove(path);
+        }
+    }
+
+    let records = log
+        .metadata
+        .reassignments
+        .entry(path.to_string())
+        .or_default();
+    let mut count = 0;
+    for (prompt, lines, ranges) in transfers {
+        count += lines;
+        records.push(Reassignment {
+            prompt,
+            lines: format_line_ranges(&ranges),
+            to: "human".to_string(),
+            by: by.to_string(),
+            reason: reason.to_string(),
+            timestamp,
+        });
+    }
+    count
+}
+
+/// Carry the reassignments `source_log` records for `source_commit` over to `target_log`, the
+/// log of `target_commit` that rewrites it (rebase, amend, cherry-pick or squash), so the
+/// transfer stays on record with the prompt it took the lines from.
+///
+/// Reassigned lines are numbered in the commit itself, so each one is followed from the
+/// source's content to the target's; a record is dropped once the target changed all its lines.
+pub fn carry_reassignments(
+    repo: &Repository,
+    source_commit: &str,
+    source_log: &AuthorshipLog,
+    target_commit: &str,
+    target_log: &mut AuthorshipLog,
+) -> Result<(), GitAiError> {
+    for (file, records) in &source_log.metadata.reassignments {
+        let target_content = get_file_content_at_commit(repo, target_commit, file)?;
+        if target_content.is_empty() {
+            continue;
+        }
+        let line_map = unchanged_line_map(
+            &get_file_content_at_commit(repo, source_commit, file)?,
+            &target_content,
+        );
+        for record in records {
+            let lines: Vec<u32> = parse_line_ranges(&record.lines)
+                .unwrap_or_default()
+                .iter()
+                .flat_map(LineRange::expand)
+                .filter_map(|line| line_map.get(&line).copied())
+                .collect();
+            if lines.is_empty() {
+                continue;
+            }
+            let carried = Reassignment {
+                lines: format_line_ranges(&LineRange::compress_lines(&lines)),
+                ..record.clone()
+            };
+            let target_records = target_log
+                .metadata
+                .reassignments
+                .entry(file.clone())
+                .or_default();
+            if target_records.contains(&carried) {
+                continue;
+            }
+            target_records.push(carried);
+            if let Some(prompt) = source_log.metadata.prompts.get(&record.prompt) {
+                target_log
+                    .metadata
+                    .prompts
+                    .entry(record.prompt.clone())
+                    .or_insert_with(|| prompt.clone());
+            }
+        }
+        if target_log
+            .metadata
+            .reassignments
+            .get(file)
+            .is_some_and(Vec::is_empty)
+        {
+            target_log.metadata.reassignments.remove(file);
+        }
+    }
+    Ok(())
+}
+
+fn now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::authorship::authorship_log_serialization::AttestationEntry;
+
+    fn log_with_ai_lines() -> AuthorshipLog {
+        let mut log = AuthorshipLog::new();
+        log.get_or_create_file("src/app.rs")
+            .add_entry(AttestationEntry::new(
+                "abc1234".to_string(),
+                vec![LineRange::Range(2, 5)],
+            ));
+        log.metadata.line_shares.insert(
+            "src/app.rs".to_string(),
+            BTreeMap::from([
+                (3, BTreeMap::from([("abc1234".to_string(), 20u8)])),
+                (4, BTreeMap::from([("abc1234".to_string(), 60u8)])),
+            ]),
+        );
+        log
+    }
+
+    #[test]
+    fn test_rewritten_lines_above_threshold_move_to_human() {
+        let mut log = log_with_ai_lines();
+        assert_eq!(reassign_rewritten_lines(&mut log, 50, "Test User"), 1);
+
+        let entry = &log.attestations[0].entries[0];
+        let remaining: Vec<u32> = entry
+            .line_ranges
+            .iter()
+            .flat_map(LineRange::expand)
+            .collect();
+        assert_eq!(remaining, vec![2, 4, 5]);
+        let records = &log.metadata.reassignments["src/app.rs"];
+        assert_eq!(records.len(), 1);
+        assert_eq!(records[0].prompt, "abc1234");
+        assert_eq!(records[0].lines, "3");
+        assert_eq!(records[0].reason, REASON_REWRITE);
+        assert!(!log.metadata.line_shares["src/app.rs"].contains_key(&3));
+        assert!(log.metadata.line_shares["src/app.rs"].contains_key(&4));
+    }
+
+    #[test]
+    fn test_transfer_drops_emptied_attestations() {
+        let mut log = log_with_ai_lines();
+        let count = transfer_to_human(
+            &mut log,
+            "src/app.rs",
+            &[1, 2, 3, 4, 5],
+            "Test User",
+            REASON_MANUAL,
+            0,
+        );
+        assert_eq!(count, 4);
+        assert!(log.attestations.is_empty());
+        assert!(log.metadata.line_shares.is_empty());
+        assert_eq!(log.metadata.reassignments["src/app.rs"][0].lines, "2-5");
+    }
+}
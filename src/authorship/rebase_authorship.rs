@@ -1,5 +1,8 @@
 use crate::authorship::authorship_log_serialization::AuthorshipLog;
+use crate::authorship::deletions::carry_ai_deletions;
+use crate::authorship::line_shares::carry_line_shares;
 use crate::authorship::post_commit;
+use crate::authorship::reassign::carry_reassignments;
 use crate::error::GitAiError;
 use crate::git::authorship_traversal::{
     commits_have_authorship_notes, load_ai_touched_files_for_commits,
@@ -85,11 +88,12 @@ pub fn rewrite_authorship_if_needed(
             ));
         }
         RewriteLogEvent::RebaseComplete { rebase_complete } => {
-            rewrite_authorship_after_rebase_v2(
+            rewrite_authorship_after_rebase_with_rewrites(
                 repo,
                 &rebase_complete.original_head,
                 &rebase_complete.original_commits,
                 &rebase_complete.new_commits,
+                &rebase_complete.rewritten,
                 &commit_author,
             )?;
 
@@ -285,16 +289,33 @@ pub fn rewrite_authorship_after_squash_or_rebase(
     merge_commit_sha: &str,
     _suppress_output: bool,
 ) -> Result<(), GitAiError> {
-    use crate::authorship::virtual_attribution::{
-        VirtualAttributions, merge_attributions_favoring_first,
-    };
-
     // Step 1: Get target branch head (first parent on merge_ref)
     // This is more correct than just parent(0) in cases with complex back-and-forth merge history
     let merge_commit = repo.find_commit(merge_commit_sha.to_string())?;
     let target_branch_head = merge_commit.parent_on_refname(merge_ref)?;
     let target_branch_head_sha = target_branch_head.id().to_string();
 
+    rewrite_authorship_for_squash_commit(
+        repo,
+        source_head_sha,
+        &target_branch_head_sha,
+        merge_commit_sha,
+    )
+}
+
+/// Write the authorship log for `merge_commit_sha`, a squash (or rebase) of the source branch
+/// ending at `source_head_sha` onto `target_branch_head_sha`.
+pub fn rewrite_authorship_for_squash_commit(
+    repo: &Repository,
+    source_head_sha: &str,
+    target_branch_head_sha: &str,
+    merge_commit_sha: &str,
+) -> Result<(), GitAiError> {
+    use crate::authorship::virtual_attribution::{
+        VirtualAttributions, merge_attributions_favoring_first,
+    };
+    let target_branch_head_sha = target_branch_head_sha.to_string();
+
     debug_log(&format!(
         "Rewriting authorship for squash/rebase merge: {} -> {}",
         source_head_sha, merge_commit_sha
@@ -390,6 +411,8 @@ pub fn rewrite_authorship_after_squash_or_rebase(
     // Step 6: Convert to AuthorshipLog (everything is committed in CI merge)
     let mut authorship_log = merged_va.to_authorship_log()?;
     authorship_log.metadata.base_commit_sha = merge_commit_sha.to_string();
+    // rev-list order, newest first
+    carry_line_metadata(repo, &source_commits, merge_commit_sha, &mut authorship_log);
 
     // Preserve accumulated totals from source commits (squash/rebase should not drop session totals).
     let mut summed_totals: HashMap<String, (u32, u32)> = HashMap::new();
@@ -436,6 +459,27 @@ pub fn rewrite_authorship_after_rebase_v2(
     original_head: &str,
     original_commits: &[String],
     new_commits: &[String],
+    human_author: &str,
+) -> Result<(), GitAiError> {
+    rewrite_authorship_after_rebase_with_rewrites(
+        repo,
+        original_head,
+        original_commits,
+        new_commits,
+        &[],
+        human_author,
+    )
+}
+
+/// Like [`rewrite_authorship_after_rebase_v2`], but with the exact original -> new commit
+/// mapping (`rewritten`) when it is known. Several originals may map to one new commit
+/// (squash/fixup); their notes are merged instead of pairing commits by position.
+pub fn rewrite_authorship_after_rebase_with_rewrites(
+    repo: &Repository,
+    original_head: &str,
+    original_commits: &[String],
+    new_commits: &[String],
+    rewritten: &[(String, String)],
     _human_author: &str,
 ) -> Result<(), GitAiError> {
     // Handle edge case: no commits to process
@@ -473,30 +517,72 @@ pub fn rewrite_authorship_after_rebase_v2(
     ));
     let commits_to_process_lookup: HashSet<&str> =
         commits_to_process.iter().map(String::as_str).collect();
-    let commit_pairs_to_process: Vec<(String, String)> = original_commits
-        .iter()
-        .zip(new_commits.iter())
-        .filter(|(_original_commit, new_commit)| {
-            commits_to_process_lookup.contains(new_commit.as_str())
-        })
-        .map(|(original_commit, new_commit)| (original_commit.clone(), new_commit.clone()))
-        .collect();
-    let original_commits_for_processing: Vec<String> = commit_pairs_to_process
-        .iter()
-        .map(|(original_commit, _new_commit)| original_commit.clone())
-        .collect();
-
+    let commit_pairs_to_process: Vec<(String, String)> = if rewritten.is_empty() {
+        original_commits
+            .iter()
+            .zip(new_commits.iter())
+            .filter(|(_original_commit, new_commit)| {
+                commits_to_process_lookup.contains(new_commit.as_str())
+            })
+            .map(|(original_commit, new_commit)| (original_commit.clone(), new_commit.clone()))
+            .collect()
+    } else {
+        rewritten
+            .iter()
+            .filter(|(_original_commit, new_commit)| {
+                commits_to_process_lookup.contains(new_commit.as_str())
+            })
+            .cloned()
+            .collect()
+    };
+    let mut sources_by_new_commit: HashMap<&str, Vec<String>> = HashMap::new();
+    for (original_commit, new_commit) in &commit_pairs_to_process {
+        sources_by_new_commit
+            .entry(new_commit.as_str())
+            .or_default()
+            .push(original_commit.clone());
+    }
+    let has_many_to_one_rewrites = sources_by_new_commit
+        .values()
+        .any(|sources| sources.len() > 1);
     // Step 1: Extract pathspecs from all original commits and narrow to AI-touched files.
     let pathspecs = get_pathspecs_from_commits(repo, original_commits)?;
     let pathspecs = filter_pathspecs_to_ai_touched_files(repo, original_commits, &pathspecs)?;
 
     if pathspecs.is_empty() {
         // No AI-touched files were rewritten. Preserve metadata-only / prompt-only notes by remapping
-        // existing source notes to their corresponding rebased commits.
-        let original_note_contents =
-            load_note_contents_for_commits(repo, &original_commits_for_processing)?;
-        let remapped_count =
-            remap_notes_for_commit_pairs(repo, &commit_pairs_to_process, &original_note_contents)?;
+        // existing source notes to their corresponding rebased commits. Squashed commits get the
+        // prompts of every commit folded into them.
+        let mut remapped_count = 0;
+        let mut one_to_one_pairs = Vec::new();
+        let mut merged_note_entries = Vec::new();
+        for (original_commit, new_commit) in &commit_pairs_to_process {
+            let sources = &sources_by_new_commit[new_commit.as_str()];
+            if sources.len() == 1 {
+                one_to_one_pairs.push((original_commit.clone(), new_commit.clone()));
+            } else if sources.first() == Some(original_commit)
+                && let Some(merged_log) =
+                    build_metadata_only_authorship_log_from_source_notes(repo, sources, new_commit)?
+            {
+                let serialized = merged_log.serialize_to_string().map_err(|_| {
+                    GitAiError::Generic("Failed to serialize authorship log".to_string())
+                })?;
+                merged_note_entries.push((new_commit.clone(), serialized));
+            }
+        }
+        if !merged_note_entries.is_empty() {
+            remapped_count += merged_note_entries.len();
+            crate::git::refs::notes_add_batch(repo, &merged_note_entries)?;
+        }
+        let original_note_contents = load_note_contents_for_commits(
+            repo,
+            &one_to_one_pairs
+                .iter()
+                .map(|(original_commit, _new_commit)| original_commit.clone())
+                .collect::<Vec<_>>(),
+        )?;
+        remapped_count +=
+            remap_notes_for_commit_pairs(repo, &one_to_one_pairs, &original_note_contents)?;
         if remapped_count > 0 {
             debug_log(&format!(
                 "Remapped {} metadata-only authorship notes for rebase commits",
@@ -516,13 +602,25 @@ pub fn rewrite_authorship_after_rebase_v2(
         new_commits.len()
     ));
 
-    if try_fast_path_rebase_note_remap(
-        repo,
-        original_commits,
-        new_commits,
-        &commits_to_process_lookup,
-        &pathspecs,
-    )? {
+    let fast_path_remapped = if rewritten.is_empty() {
+        try_fast_path_rebase_note_remap(
+            repo,
+            original_commits,
+            new_commits,
+            &commits_to_process_lookup,
+            &pathspecs,
+        )?
+    } else {
+        // Every rebased commit must come from exactly one original for a blob-level remap.
+        !has_many_to_one_rewrites
+            && sources_by_new_commit.len() == commits_to_process.len()
+            && try_fast_path_rebase_note_remap_for_pairs(
+                repo,
+                commit_pairs_to_process.clone(),
+                &pathspecs,
+            )?
+    };
+    if fast_path_remapped {
         return Ok(());
     }
 
@@ -621,7 +719,14 @@ pub fn rewrite_authorship_after_rebase_v2(
     );
     let rebase_ts = current_va.timestamp();
 
-    let commit_tree_pairs = build_first_parent_tree_pairs(repo, &commits_to_process)?;
+    let mut commit_tree_pairs = build_first_parent_tree_pairs(repo, &commits_to_process)?;
+    // With reordered commits the first rebased commit can lack files the original head has,
+    // so diff it against the state being transformed rather than against its parent.
+    if !rewritten.is_empty()
+        && let Some((_, parent_tree, _)) = commit_tree_pairs.first_mut()
+    {
+        *parent_tree = repo.find_commit(original_head.to_string())?.tree()?.id();
+    }
     let mut changed_contents_by_commit = collect_changed_file_contents_for_commit_pairs(
         repo,
         &commit_tree_pairs,
@@ -714,6 +819,13 @@ pub fn rewrite_authorship_after_rebase_v2(
 
         current_authorship_log.metadata.base_commit_sha = new_commit.clone();
         current_authorship_log.metadata.prompts = flatten_prompts_for_metadata(&current_prompts);
+        current_authorship_log.metadata.deletions.clear();
+        current_authorship_log.metadata.line_shares.clear();
+        current_authorship_log.metadata.reassignments.clear();
+        if let Some(sources) = sources_by_new_commit.get(new_commit.as_str()) {
+            let newest_first: Vec<String> = sources.iter().rev().cloned().collect();
+            carry_line_metadata(repo, &newest_first, new_commit, &mut current_authorship_log);
+        }
 
         let computed_note_has_payload = !current_authorship_log.attestations.is_empty()
             || !current_authorship_log.metadata.prompts.is_empty();
@@ -922,6 +1034,14 @@ pub fn rewrite_authorship_after_cherry_pick(
         });
 
         authorship_log.metadata.base_commit_sha = new_commit.clone();
+        if let Some((source_commit, _)) = commit_pairs.get(idx) {
+            carry_line_metadata(
+                repo,
+                std::slice::from_ref(source_commit),
+                new_commit,
+                &mut authorship_log,
+            );
+        }
 
         // Save computed note when it has payload; otherwise preserve original metadata-only notes.
         let computed_note_has_payload =
@@ -1050,7 +1170,7 @@ fn collect_changed_file_contents_from_diff(
     Ok((changed_files, file_contents))
 }
 
-fn batch_read_blob_contents(
+pub(crate) fn batch_read_blob_contents(
     repo: &Repository,
     blob_oids: &[String],
 ) -> Result<HashMap<String, String>, GitAiError> {
@@ -1497,6 +1617,12 @@ pub fn rewrite_authorship_after_commit_amend(
 
     // Update base commit SHA
     authorship_log.metadata.base_commit_sha = amended_commit.to_string();
+    carry_line_metadata(
+        repo,
+        &[original_commit.to_string()],
+        amended_commit,
+        &mut authorship_log,
+    );
 
     // Save authorship log
     let authorship_json = authorship_log
@@ -1518,6 +1644,47 @@ pub fn rewrite_authorship_after_commit_amend(
     Ok(authorship_log)
 }
 
+/// Carry the metadata keyed by line number (AI deletions, shared lines, reassignments) from the
+/// notes of `sources`, newest first, to `target_log` of `target_commit`, which rewrites them.
+/// The rewritten attestations are rebuilt from blame, but this metadata only exists in the
+/// notes.
+fn carry_line_metadata(
+    repo: &Repository,
+    sources: &[String],
+    target_commit: &str,
+    target_log: &mut AuthorshipLog,
+) {
+    for source_commit in sources {
+        let Ok(source_log) = get_reference_as_authorship_log_v3(repo, source_commit) else {
+            continue;
+        };
+        if let Err(e) =
+            carry_ai_deletions(repo, source_commit, &source_log, target_commit, target_log)
+        {
+            debug_log(&format!(
+                "[Warning] Failed to carry AI deletions from {} to {}: {}",
+                source_commit, target_commit, e
+            ));
+        }
+        if let Err(e) =
+            carry_line_shares(repo, source_commit, &source_log, target_commit, target_log)
+        {
+            debug_log(&format!(
+                "[Warning] Failed to carry shared lines from {} to {}: {}",
+                source_commit, target_commit, e
+            ));
+        }
+        if let Err(e) =
+            carry_reassignments(repo, source_commit, &source_log, target_commit, target_log)
+        {
+            debug_log(&format!(
+                "[Warning] Failed to carry reassignments from {} to {}: {}",
+                source_commit, target_commit, e
+            ));
+        }
+    }
+}
+
 pub fn walk_commits_to_base(
     repository: &Repository,
     head: &str,
@@ -2003,6 +2170,37 @@ fn try_fast_path_rebase_note_remap(
         .map(|(original_commit, new_commit)| (original_commit.clone(), new_commit.clone()))
         .collect();
 
+    try_fast_path_rebase_note_remap_for_pairs_since(
+        repo,
+        commits_to_remap,
+        tracked_paths,
+        fast_path_start,
+    )
+}
+
+/// Fast-path remap for an explicit list of one-to-one (original, new) commit pairs.
+fn try_fast_path_rebase_note_remap_for_pairs(
+    repo: &Repository,
+    commits_to_remap: Vec<(String, String)>,
+    tracked_paths: &[String],
+) -> Result<bool, GitAiError> {
+    if tracked_paths.is_empty() {
+        return Ok(false);
+    }
+    try_fast_path_rebase_note_remap_for_pairs_since(
+        repo,
+        commits_to_remap,
+        tracked_paths,
+        std::time::Instant::now(),
+    )
+}
+
+fn try_fast_path_rebase_note_remap_for_pairs_since(
+    repo: &Repository,
+    commits_to_remap: Vec<(String, String)>,
+    tracked_paths: &[String],
+    fast_path_start: std::time::Instant,
+) -> Result<bool, GitAiError> {
     if commits_to_remap.is_empty() {
         return Ok(false);
     }
@@ -0,0 +1,234 @@
+//! Recover authorship for squash merges made outside git-ai (e.g. GitHub's "Squash and merge").
+//!
+//! A squash commit has no authorship note of its own, but its patch is the source branch's
+//! net diff. Matching the two by `git patch-id --stable` identifies which branch a squash
+//! commit came from, and the branch's per-commit notes are then merged into a note for the
+//! squash commit.
+
+use crate::authorship::rebase_authorship::rewrite_authorship_for_squash_commit;
+use crate::error::GitAiError;
+use crate::git::refs::commits_with_authorship_notes;
+use crate::git::repository::{Repository, exec_git, exec_git_stdin};
+use crate::utils::debug_log;
+use std::collections::HashMap;
+
+/// How far back along the target branch to look for a squash commit.
+pub const MAX_SQUASH_CANDIDATES: usize = 200;
+
+/// Local branches checked for squash merges after a fetch or pull, most recent first.
+const MAX_AUTO_BACKFILL_BRANCHES: usize = 20;
+
+/// Commits behind the target tip checked per branch after a fetch or pull.
+const MAX_AUTO_BACKFILL_CANDIDATES: usize = 50;
+
+/// A source branch (or range) whose commits were squashed into a single commit.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SquashSource {
+    /// Commit the branch forked from
+    pub base: String,
+    /// Tip of the branch
+    pub head: String,
+}
+
+/// A squash commit found for a source.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SquashMatch {
+    pub squash_commit: String,
+    pub source_head: String,
+}
+
+impl SquashSource {
+    /// Resolve `<branch>` (forked from `onto`) or `<base>..<head>`.
+    pub fn resolve(repo: &Repository, from: &str, onto: &str) -> Result<Self, GitAiError> {
+        if let Some((base, head)) = from.split_once("..") {
+            let head = if head.is_empty() { "HEAD" } else { head };
+            return Ok(SquashSource {
+                base: repo.revparse_single(base)?.id(),
+                head: repo.revparse_single(head)?.id(),
+            });
+        }
+
+        let head = repo.revparse_single(from)?.id();
+        let onto = repo.revparse_single(onto)?.id();
+        let base = repo.merge_base(head.clone(), onto)?;
+        Ok(SquashSource { base, head })
+    }
+
+    /// Commits on the source, newest first.
+    pub fn commits(&self, repo: &Repository) -> Result<Vec<String>, GitAiError> {
+        rev_list(repo, &[format!("{}..{}", self.base, self.head)])
+    }
+}
+
+/// Find the commit on `onto` that squashes `source`, skipping commits that already have a
+/// note.
+pub fn find_squash_commit(
+    repo: &Repository,
+    source: &SquashSource,
+    onto: &str,
+    max_candidates: usize,
+) -> Result<Option<SquashMatch>, GitAiError> {
+    let mut patch_ids = HashMap::new();
+    find_squash_commit_cached(repo, source, onto, max_candidates, &mut patch_ids)
+}
+
+fn find_squash_commit_cached(
+    repo: &Repository,
+    source: &SquashSource,
+    onto: &str,
+    max_candidates: usize,
+    patch_ids: &mut HashMap<String, Option<String>>,
+) -> Result<Option<SquashMatch>, GitAiError> {
+    if source.base == source.head {
+        return Ok(None);
+    }
+    let Some(source_patch_id) = patch_id(repo, &source.base, &source.head)? else {
+        return Ok(None);
+    };
+
+    let candidates = rev_list(
+        repo,
+        &[
+            "--no-merges".to_string(),
+            format!("--max-count={}", max_candidates),
+            format!("{}..{}", source.head, onto),
+        ],
+    )?;
+    let noted = commits_with_authorship_notes(repo, &candidates)?;
+
+    for candidate in candidates {
+        if noted.contains(&candidate) {
+            continue;
+        }
+        let candidate_patch_id = match patch_ids.get(&candidate) {
+            Some(cached) => cached.clone(),
+            None => {
+                // Root commits have no parent to diff against
+                let computed = patch_id(repo, &format!("{}^", candidate), &candidate)
+                    .ok()
+                    .flatten();
+                patch_ids.insert(candidate.clone(), computed.clone());
+                computed
+            }
+        };
+        if candidate_patch_id.as_deref() == Some(source_patch_id.as_str()) {
+            return Ok(Some(SquashMatch {
+                squash_commit: candidate,
+                source_head: source.head.clone(),
+            }));
+        }
+    }
+    Ok(None)
+}
+
+/// Write the merged authorship log of `source`'s commits onto the matched squash commit.
+pub fn backfill_squash_commit(repo: &Repository, found: &SquashMatch) -> Result<(), GitAiError> {
+    let parent = repo
+        .find_commit(found.squash_commit.clone())?
+        .parent(0)?
+        .id();
+    rewrite_authorship_for_squash_commit(repo, &found.source_head, &parent, &found.squash_commit)
+}
+
+/// After a fetch or pull, look for local branches that were squash-merged into
+/// `target` (usually `<remote>/HEAD`) and backfill their squash commits.
+///
+/// Only branches whose own commits carry authorship notes are considered.
+pub fn backfill_squash_merges_into(
+    repo: &Repository,
+    target: &str,
+) -> Result<Vec<SquashMatch>, GitAiError> {
+    let target = repo.revparse_single(target)?.id();
+    let mut args = repo.global_args_for_exec();
+    args.extend(
+        [
+            "for-each-ref",
+            "--sort=-committerdate",
+            format!("--count={}", MAX_AUTO_BACKFILL_BRANCHES).as_str(),
+            "--format=%(objectname)",
+            "refs/heads/",
+        ]
+        .iter()
+        .map(|s| s.to_string()),
+    );
+    let output = exec_git(&args)?;
+    let branch_tips: Vec<String> = String::from_utf8(output.stdout)?
+        .lines()
+        .map(str::to_string)
+        .collect();
+
+    let mut patch_ids = HashMap::new();
+    let mut backfilled = Vec::new();
+    for head in branch_tips {
+        if head == target {
+            continue;
+        }
+        let Ok(base) = repo.merge_base(head.clone(), target.clone()) else {
+            continue;
+        };
+        // Fully merged branches were merged normally (or fast-forwarded), not squashed
+        if base == head {
+            continue;
+        }
+        let source = SquashSource { base, head };
+        if commits_with_authorship_notes(repo, &source.commits(repo)?)?.is_empty() {
+            continue;
+        }
+
+        if let Some(found) = find_squash_commit_cached(
+            repo,
+            &source,
+            &target,
+            MAX_AUTO_BACKFILL_CANDIDATES,
+            &mut patch_ids,
+        )? {
+            debug_log(&format!(
+                "Backfilling squash commit {} from branch tip {}",
+                found.squash_commit, found.source_head
+            ));
+            backfill_squash_commit(repo, &found)?;
+            backfilled.push(found);
+        }
+    }
+    Ok(backfilled)
+}
+
+/// `git patch-id --stable` of the diff between two commits, or None for an empty diff.
+fn patch_id(repo: &Repository, from: &str, to: &str) -> Result<Option<String>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.extend(
+        [
+            "diff",
+            "--no-color",
+            "--no-ext-diff",
+            "--full-index",
+            from,
+            to,
+        ]
+        .iter()
+        .map(|s| s.to_string()),
+    );
+    let diff = exec_git(&args)?.stdout;
+    if diff.is_empty() {
+        return Ok(None);
+    }
+
+    let mut args = repo.global_args_for_exec();
+    args.extend(["patch-id", "--stable"].iter().map(|s| s.to_string()));
+    let output = exec_git_stdin(&args, &diff)?;
+    Ok(String::from_utf8(output.stdout)?
+        .split_whitespace()
+        .next()
+        .map(str::to_string))
+}
+
+fn rev_list(repo: &Repository, extra: &[String]) -> Result<Vec<String>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.push("rev-list".to_string());
+    args.extend(extra.iter().cloned());
+    let output = exec_git(&args)?;
+    Ok(String::from_utf8(output.stdout)?
+        .lines()
+        .map(str::to_string)
+        .collect())
+}
@@ -1,5 +1,8 @@
 use crate::authorship::authorship_log::LineRange;
+use crate::authorship::identity::{ClassifiedIdentity, IdentityCategory, classify_commit};
 use crate::authorship::ignore::{build_ignore_matcher, should_ignore_file_with_matcher};
+use crate::authorship::line_filter::LineFilter;
+use crate::authorship::model_names::tool_model_key;
 use crate::authorship::transcript::Message;
 use crate::error::GitAiError;
 use crate::git::refs::get_authorship;
@@ -44,6 +47,13 @@ pub struct CommitStats {
     pub git_diff_deleted_lines: u32,
     #[serde(default)]
     pub git_diff_added_lines: u32,
+    /// Category of the commit's identity when an `identity_categories` rule matched it
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identity_category: Option<String>,
+    #[serde(default)]
+    pub bot_additions: u32, // Number of lines committed by a bot or code generator identity
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ai_generated_assets: Vec<String>, // Files attributed to AI as a whole (binary assets)
     #[serde(default)]
     pub tool_model_breakdown: BTreeMap<String, ToolModelHeadlineStats>,
 }
@@ -53,6 +63,8 @@ pub fn stats_command(
     commit_sha: Option<&str>,
     json: bool,
     ignore_patterns: &[String],
+    line_filter: &LineFilter,
+    raw_models: bool,
 ) -> Result<(), GitAiError> {
     let (target, refname) = if let Some(sha) = commit_sha {
         // Validate that the commit exists using revparse_single
@@ -80,7 +92,13 @@ pub fn stats_command(
         target, refname
     ));
 
-    let stats = stats_for_commit_stats(repo, &target, ignore_patterns)?;
+    let stats = stats_for_commit_stats_with_filter(
+        repo,
+        &target,
+        ignore_patterns,
+        line_filter,
+        raw_models,
+    )?;
 
     if json {
         let json_str = serde_json::to_string(&stats)?;
@@ -292,6 +310,28 @@ pub fn write_stats_to_terminal(stats: &CommitStats, print: bool) -> String {
             println!("{}", ai_acceptance_str);
         }
     }
+
+    for asset in &stats.ai_generated_assets {
+        let asset_line = format!("     \x1b[90mAI-generated asset: {}\x1b[0m", asset);
+        output.push_str(&asset_line);
+        output.push('\n');
+        if print {
+            println!("{}", asset_line);
+        }
+    }
+
+    if stats.bot_additions > 0 {
+        let bot_line = format!(
+            "     \x1b[90m{} lines committed by a {} identity\x1b[0m",
+            stats.bot_additions,
+            stats.identity_category.as_deref().unwrap_or("bot")
+        );
+        output.push_str(&bot_line);
+        output.push('\n');
+        if print {
+            println!("{}", bot_line);
+        }
+    }
     output
 }
 
@@ -439,6 +479,9 @@ pub fn write_stats_to_markdown(stats: &CommitStats) -> String {
             model_name, model_stats.ai_accepted, model_stats.total_ai_additions
         ));
     }
+    for asset in &stats.ai_generated_assets {
+        output.push_str(&format!("- AI-generated asset: `{}`\n", asset));
+    }
 
     output.push_str("\n</details>");
 
@@ -453,6 +496,7 @@ pub fn stats_from_authorship_log(
     git_diff_deleted_lines: u32,
     ai_accepted: u32,
     ai_accepted_by_tool: &BTreeMap<String, u32>,
+    raw_models: bool,
 ) -> CommitStats {
     let mut commit_stats = CommitStats {
         human_additions: 0,
@@ -463,20 +507,26 @@ pub fn stats_from_authorship_log(
         total_ai_deletions: 0,
         time_waiting_for_ai: 0,
         tool_model_breakdown: BTreeMap::new(),
+        ai_generated_assets: Vec::new(),
+        identity_category: None,
+        bot_additions: 0,
         git_diff_deleted_lines,
         git_diff_added_lines,
     };
 
     // Process authorship log if present
     if let Some(log) = authorship_log {
+        commit_stats.ai_generated_assets = log.metadata.assets.keys().cloned().collect();
+
         for prompt_record in log.metadata.prompts.values() {
             commit_stats.total_ai_additions += prompt_record.total_additions;
             commit_stats.total_ai_deletions += prompt_record.total_deletions;
             commit_stats.mixed_additions += prompt_record.overriden_lines;
 
-            let key = format!(
-                "{}::{}",
-                prompt_record.agent_id.tool, prompt_record.agent_id.model
+            let key = tool_model_key(
+                &prompt_record.agent_id.tool,
+                &prompt_record.agent_id.model,
+                raw_models,
             );
             let tool_stats = commit_stats.tool_model_breakdown.entry(key).or_default();
             tool_stats.total_ai_additions += prompt_record.total_additions;
@@ -532,13 +582,34 @@ pub fn stats_for_commit_stats(
     repo: &Repository,
     commit_sha: &str,
     ignore_patterns: &[String],
+) -> Result<CommitStats, GitAiError> {
+    stats_for_commit_stats_with_filter(
+        repo,
+        commit_sha,
+        ignore_patterns,
+        &LineFilter::default(),
+        false,
+    )
+}
+
+/// Like `stats_for_commit_stats`, but only counts added lines that pass `line_filter`, and
+/// keys the model breakdown by the recorded model names when `raw_models` is set.
+///
+/// Filtering needs the lines each file added, which merge commits don't track, so an active
+/// filter is an error for them.
+pub fn stats_for_commit_stats_with_filter(
+    repo: &Repository,
+    commit_sha: &str,
+    ignore_patterns: &[String],
+    line_filter: &LineFilter,
+    raw_models: bool,
 ) -> Result<CommitStats, GitAiError> {
     let commit_obj = repo.revparse_single(commit_sha)?.peel_to_commit()?;
 
     // Step 1: get the diff between this commit and its parent ON refname (if more than one parent)
     // If initial than everything is additions
     // We want the count here git shows +111 -55
-    let (git_diff_added_lines, git_diff_deleted_lines) =
+    let (mut git_diff_added_lines, git_diff_deleted_lines) =
         get_git_diff_stats(repo, commit_sha, ignore_patterns)?;
 
     // Step 2: get the authorship log for this commit
@@ -548,6 +619,12 @@ pub fn stats_for_commit_stats(
     // This keeps accepted stats scoped to the target commit while avoiding expensive blame traversal.
     let parent_count = commit_obj.parent_count()?;
     let is_merge_commit = parent_count > 1;
+    if is_merge_commit && line_filter.is_active() {
+        return Err(GitAiError::Generic(format!(
+            "{} is a merge commit; --exclude-blank, --exclude-comments and --min-line-length don't apply to merges",
+            commit_sha
+        )));
+    }
     let mut added_lines_by_file: HashMap<String, Vec<u32>> = if is_merge_commit {
         HashMap::new()
     } else {
@@ -566,27 +643,75 @@ pub fn stats_for_commit_stats(
         lines.dedup();
     }
 
+    // Optional normalization: drop blank/comment/short lines from both sides of the split.
+    if line_filter.is_active() {
+        for (file_path, lines) in added_lines_by_file.iter_mut() {
+            line_filter.retain_committed_lines(repo, commit_sha, file_path, lines);
+        }
+        git_diff_added_lines = added_lines_by_file
+            .values()
+            .map(|lines| lines.len() as u32)
+            .sum();
+    }
+
     // Step 4: derive accepted lines directly from note attestations for lines added in this commit.
     let (ai_accepted, ai_accepted_by_tool) = accepted_lines_from_attestations(
         authorship_log.as_ref(),
         &added_lines_by_file,
         is_merge_commit,
+        raw_models,
     );
 
     // Step 5: Calculate stats from authorship log
-    Ok(stats_from_authorship_log(
+    let mut stats = stats_from_authorship_log(
         authorship_log.as_ref(),
         git_diff_added_lines,
         git_diff_deleted_lines,
         ai_accepted,
         &ai_accepted_by_tool,
-    ))
+        raw_models,
+    );
+
+    // Step 6: Lines a bot, generator or AI identity committed are not the human's
+    if let Some(identity) = classify_commit(&commit_obj) {
+        apply_identity_category(&mut stats, &identity);
+    }
+    Ok(stats)
+}
+
+/// Move the lines `stats` counts as human to the category of the identity that committed them.
+pub fn apply_identity_category(stats: &mut CommitStats, identity: &ClassifiedIdentity) {
+    stats.identity_category = Some(identity.category.as_str().to_string());
+    let unattributed = stats.human_additions;
+    credit_identity_lines(stats, identity, unattributed);
+}
+
+/// Move `lines` of the lines `stats` counts as human to the category of `identity`, which
+/// committed them.
+pub fn credit_identity_lines(stats: &mut CommitStats, identity: &ClassifiedIdentity, lines: u32) {
+    let lines = lines.min(stats.human_additions);
+    match identity.category {
+        IdentityCategory::Human => return,
+        IdentityCategory::Bot | IdentityCategory::Generator => stats.bot_additions += lines,
+        IdentityCategory::Ai => {
+            stats.ai_accepted += lines;
+            stats.ai_additions += lines;
+            let tool_stats = stats
+                .tool_model_breakdown
+                .entry(tool_model_key(&identity.name, "unknown", false))
+                .or_default();
+            tool_stats.ai_accepted += lines;
+            tool_stats.ai_additions += lines;
+        }
+    }
+    stats.human_additions -= lines;
 }
 
 fn accepted_lines_from_attestations(
     authorship_log: Option<&crate::authorship::authorship_log_serialization::AuthorshipLog>,
     added_lines_by_file: &HashMap<String, Vec<u32>>,
     is_merge_commit: bool,
+    raw_models: bool,
 ) -> (u32, BTreeMap<String, u32>) {
     if is_merge_commit {
         return (0, BTreeMap::new());
@@ -618,9 +743,10 @@ fn accepted_lines_from_attestations(
             total_ai_accepted += accepted;
 
             if let Some(prompt_record) = log.metadata.prompts.get(&entry.hash) {
-                let tool_model = format!(
-                    "{}::{}",
-                    prompt_record.agent_id.tool, prompt_record.agent_id.model
+                let tool_model = tool_model_key(
+                    &prompt_record.agent_id.tool,
+                    &prompt_record.agent_id.model,
+                    raw_models,
                 );
                 *per_tool_model.entry(tool_model).or_insert(0) += accepted;
             }
@@ -630,7 +756,7 @@ fn accepted_lines_from_attestations(
     (total_ai_accepted, per_tool_model)
 }
 
-fn line_range_overlap_len(range: &LineRange, added_lines: &[u32]) -> u32 {
+pub(crate) fn line_range_overlap_len(range: &LineRange, added_lines: &[u32]) -> u32 {
     match range {
         LineRange::Single(line) => u32::from(added_lines.binary_search(line).is_ok()),
         LineRange::Range(start, end) => {
@@ -776,6 +902,9 @@ mod tests {
             total_ai_additions: 100,
             total_ai_deletions: 0,
             tool_model_breakdown: BTreeMap::new(),
+            ai_generated_assets: Vec::new(),
+            identity_category: None,
+            bot_additions: 0,
         };
 
         let mixed_output = write_stats_to_terminal(&stats, true);
@@ -793,6 +922,9 @@ mod tests {
             total_ai_additions: 100,
             total_ai_deletions: 0,
             tool_model_breakdown: BTreeMap::new(),
+            ai_generated_assets: Vec::new(),
+            identity_category: None,
+            bot_additions: 0,
         };
 
         let ai_only_output = write_stats_to_terminal(&ai_stats, true);
@@ -810,6 +942,9 @@ mod tests {
             total_ai_additions: 0,
             total_ai_deletions: 0,
             tool_model_breakdown: BTreeMap::new(),
+            ai_generated_assets: Vec::new(),
+            identity_category: None,
+            bot_additions: 0,
         };
 
         let human_only_output = write_stats_to_terminal(&human_stats, true);
@@ -827,6 +962,9 @@ mod tests {
             total_ai_additions: 100,
             total_ai_deletions: 0,
             tool_model_breakdown: BTreeMap::new(),
+            ai_generated_assets: Vec::new(),
+            identity_category: None,
+            bot_additions: 0,
         };
 
         let minimal_human_output = write_stats_to_terminal(&minimal_human_stats, true);
@@ -844,6 +982,9 @@ mod tests {
             total_ai_additions: 0,
             total_ai_deletions: 0,
             tool_model_breakdown: BTreeMap::new(),
+            ai_generated_assets: Vec::new(),
+            identity_category: None,
+            bot_additions: 0,
         };
 
         let deletion_only_output = write_stats_to_terminal(&deletion_only_stats, true);
@@ -864,6 +1005,9 @@ mod tests {
             total_ai_additions: 100,
             total_ai_deletions: 0,
             tool_model_breakdown: BTreeMap::new(),
+            ai_generated_assets: Vec::new(),
+            identity_category: None,
+            bot_additions: 0,
         };
 
         let mixed_output = write_stats_to_markdown(&stats);
@@ -881,6 +1025,9 @@ mod tests {
             total_ai_additions: 100,
             total_ai_deletions: 0,
             tool_model_breakdown: BTreeMap::new(),
+            ai_generated_assets: Vec::new(),
+            identity_category: None,
+            bot_additions: 0,
         };
 
         let ai_only_output = write_stats_to_markdown(&ai_stats);
@@ -898,6 +1045,9 @@ mod tests {
             total_ai_additions: 0,
             total_ai_deletions: 0,
             tool_model_breakdown: BTreeMap::new(),
+            ai_generated_assets: Vec::new(),
+            identity_category: None,
+            bot_additions: 0,
         };
 
         let human_only_output = write_stats_to_markdown(&human_stats);
@@ -915,6 +1065,9 @@ mod tests {
             total_ai_additions: 100,
             total_ai_deletions: 0,
             tool_model_breakdown: BTreeMap::new(),
+            ai_generated_assets: Vec::new(),
+            identity_category: None,
+            bot_additions: 0,
         };
 
         let minimal_human_output = write_stats_to_markdown(&minimal_human_stats);
@@ -932,6 +1085,9 @@ mod tests {
             total_ai_additions: 0,
             total_ai_deletions: 0,
             tool_model_breakdown: BTreeMap::new(),
+            ai_generated_assets: Vec::new(),
+            identity_category: None,
+            bot_additions: 0,
         };
 
         let deletion_only_output = write_stats_to_markdown(&deletion_only_stats);
@@ -1281,7 +1437,8 @@ mod tests {
     #[test]
     fn test_accepted_lines_no_authorship_log() {
         let added_lines: HashMap<String, Vec<u32>> = HashMap::new();
-        let (accepted, per_tool) = accepted_lines_from_attestations(None, &added_lines, false);
+        let (accepted, per_tool) =
+            accepted_lines_from_attestations(None, &added_lines, false, false);
         assert_eq!(accepted, 0);
         assert!(per_tool.is_empty());
     }
@@ -1327,7 +1484,8 @@ mod tests {
         let mut added_lines: HashMap<String, Vec<u32>> = HashMap::new();
         added_lines.insert("foo.rs".to_string(), vec![1, 2, 3]);
 
-        let (accepted, per_tool) = accepted_lines_from_attestations(Some(&log), &added_lines, true);
+        let (accepted, per_tool) =
+            accepted_lines_from_attestations(Some(&log), &added_lines, true, false);
         assert_eq!(accepted, 0);
         assert!(per_tool.is_empty());
     }
@@ -1374,7 +1532,7 @@ mod tests {
         added_lines.insert("bar.rs".to_string(), vec![1, 2, 3]);
 
         let (accepted, per_tool) =
-            accepted_lines_from_attestations(Some(&log), &added_lines, false);
+            accepted_lines_from_attestations(Some(&log), &added_lines, false, false);
         assert_eq!(accepted, 0);
         assert!(per_tool.is_empty());
     }
@@ -1420,7 +1578,7 @@ mod tests {
         added_lines.insert("foo.rs".to_string(), vec![1, 2, 3]);
 
         let (accepted, per_tool) =
-            accepted_lines_from_attestations(Some(&log), &added_lines, false);
+            accepted_lines_from_attestations(Some(&log), &added_lines, false, false);
         assert_eq!(accepted, 3);
 
         // Verify per-tool breakdown contains the right key
@@ -1662,6 +1820,8 @@ mod tests {
             Some("0000000000000000000000000000000000000000"),
             false,
             &[],
+            &LineFilter::default(),
+            false,
         );
         assert!(result.is_err());
     }
@@ -1679,7 +1839,14 @@ mod tests {
         let head_sha = tmp_repo.get_head_commit_sha().unwrap();
 
         // Should succeed with json output
-        let result = stats_command(tmp_repo.gitai_repo(), Some(&head_sha), true, &[]);
+        let result = stats_command(
+            tmp_repo.gitai_repo(),
+            Some(&head_sha),
+            true,
+            &[],
+            &LineFilter::default(),
+            false,
+        );
         assert!(result.is_ok());
     }
 
@@ -1694,7 +1861,14 @@ mod tests {
         tmp_repo.commit_with_message("Commit").unwrap();
 
         // No SHA provided should default to HEAD
-        let result = stats_command(tmp_repo.gitai_repo(), None, false, &[]);
+        let result = stats_command(
+            tmp_repo.gitai_repo(),
+            None,
+            false,
+            &[],
+            &LineFilter::default(),
+            false,
+        );
         assert!(result.is_ok());
     }
 
@@ -1730,7 +1904,7 @@ mod tests {
 
     #[test]
     fn test_stats_from_authorship_log_no_log() {
-        let stats = stats_from_authorship_log(None, 10, 5, 3, &BTreeMap::new());
+        let stats = stats_from_authorship_log(None, 10, 5, 3, &BTreeMap::new(), false);
 
         assert_eq!(stats.git_diff_added_lines, 10);
         assert_eq!(stats.git_diff_deleted_lines, 5);
@@ -1774,7 +1948,7 @@ mod tests {
         );
 
         // Only 10 lines added, 5 accepted by AI
-        let stats = stats_from_authorship_log(Some(&log), 10, 0, 5, &BTreeMap::new());
+        let stats = stats_from_authorship_log(Some(&log), 10, 0, 5, &BTreeMap::new(), false);
 
         // Mixed should be capped to max possible: 10 - 5 = 5
         assert_eq!(stats.mixed_additions, 5);
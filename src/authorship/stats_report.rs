@@ -0,0 +1,450 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use serde::Serialize;
+
+use crate::authorship::authorship_log_serialization::AuthorshipLog;
+use crate::authorship::ignore::{build_ignore_matcher, should_ignore_file_with_matcher};
+use crate::authorship::line_filter::LineFilter;
+use crate::authorship::model_names::tool_model_key;
+use crate::authorship::rebase_authorship::batch_read_blob_contents;
+use crate::authorship::stats::line_range_overlap_len;
+use crate::error::GitAiError;
+use crate::git::refs::note_blob_oids_for_commits;
+use crate::git::repository::{Repository, exec_git};
+
+/// Row key used for human-written lines when grouping by model.
+const HUMAN_GROUP_KEY: &str = "human";
+
+const EMPTY_TREE_HASH: &str = "4b825dc642cb6eb9a060e54bf8d69288fbee4904";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum StatsGroupBy {
+    File,
+    Author,
+    Model,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct StatsReportOptions {
+    /// Revision or range passed to `git log` (defaults to HEAD).
+    pub rev_range: Option<String>,
+    /// Passed through to `git log --since`.
+    pub since: Option<String>,
+    /// Passed through to `git log --author`.
+    pub author: Option<String>,
+    pub group_by: Option<StatsGroupBy>,
+    /// Key model rows by the model names as recorded instead of their canonical names.
+    pub raw_models: bool,
+    /// Only count added lines that pass this filter.
+    pub line_filter: LineFilter,
+}
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize)]
+pub struct StatsReportRow {
+    pub key: String,
+    pub commits: usize,
+    pub ai_lines: u64,
+    pub human_lines: u64,
+    pub total_lines: u64,
+}
+
+impl StatsReportRow {
+    fn new(key: &str) -> Self {
+        StatsReportRow {
+            key: key.to_string(),
+            ..Default::default()
+        }
+    }
+
+    pub fn ai_percent(&self) -> f64 {
+        if self.total_lines == 0 {
+            0.0
+        } else {
+            self.ai_lines as f64 * 100.0 / self.total_lines as f64
+        }
+    }
+}
+
+/// Aggregated AI vs human line counts across a set of commits.
+#[derive(Debug, Clone, Serialize)]
+pub struct StatsReport {
+    pub rev_range: String,
+    pub total_commits: usize,
+    pub commits_with_authorship: usize,
+    pub group_by: Option<StatsGroupBy>,
+    pub totals: StatsReportRow,
+    pub rows: Vec<StatsReportRow>,
+}
+
+struct LoggedCommit {
+    sha: String,
+    author: String,
+    /// (path, added lines) from `--numstat`; binary files are skipped.
+    added: Vec<(String, u64)>,
+}
+
+/// Build a report from the authorship notes of every non-merge commit selected by `options`.
+///
+/// Added lines come from each commit's diff against its first parent, and the AI share of
+/// those lines comes from the commit's note, so code that was later deleted still counts.
+/// With an active line filter, only the added lines that pass it are counted, on both sides.
+pub fn build_stats_report(
+    repo: &Repository,
+    options: &StatsReportOptions,
+    ignore_patterns: &[String],
+) -> Result<StatsReport, GitAiError> {
+    let rev_range = options
+        .rev_range
+        .clone()
+        .unwrap_or_else(|| "HEAD".to_string());
+    let commits = log_commits(repo, &rev_range, options)?;
+
+    let shas: Vec<String> = commits.iter().map(|c| c.sha.clone()).collect();
+    let logs = load_authorship_logs(repo, &shas)?;
+    let ignore_matcher = build_ignore_matcher(ignore_patterns);
+
+    let mut totals = StatsReportRow::new("total");
+    let mut rows: BTreeMap<String, StatsReportRow> = BTreeMap::new();
+
+    for commit in &commits {
+        let log = logs.get(&commit.sha);
+        let mut touched_rows: HashSet<String> = HashSet::new();
+        let mut commit_counted = false;
+        let counted_lines = if options.line_filter.is_active() {
+            Some(counted_added_lines(
+                repo,
+                &commit.sha,
+                &options.line_filter,
+            )?)
+        } else {
+            None
+        };
+
+        for (path, numstat_added) in &commit.added {
+            let counted = counted_lines
+                .as_ref()
+                .map(|lines| lines.get(path).map(Vec::as_slice).unwrap_or_default());
+            let added = counted.map_or(*numstat_added, |lines| lines.len() as u64);
+            if added == 0 || should_ignore_file_with_matcher(path, &ignore_matcher) {
+                continue;
+            }
+
+            let ai_by_model = log
+                .map(|log| ai_lines_by_model(log, path, counted, options.raw_models))
+                .unwrap_or_default();
+            // Notes can over-attribute when a file is rewritten wholesale; never exceed the diff.
+            let mut remaining = added;
+            let mut ai_rows: Vec<(String, u64)> = Vec::new();
+            for (model, lines) in ai_by_model {
+                let lines = lines.min(remaining);
+                remaining -= lines;
+                if lines > 0 {
+                    ai_rows.push((model, lines));
+                }
+            }
+            let ai_lines = added - remaining;
+            let human_lines = remaining;
+
+            totals.ai_lines += ai_lines;
+            totals.human_lines += human_lines;
+            totals.total_lines += added;
+            commit_counted = true;
+
+            let mut bump = |key: &str, ai: u64, human: u64| {
+                let row = rows
+                    .entry(key.to_string())
+                    .or_insert_with(|| StatsReportRow::new(key));
+                row.ai_lines += ai;
+                row.human_lines += human;
+                row.total_lines += ai + human;
+                if touched_rows.insert(key.to_string()) {
+                    row.commits += 1;
+                }
+            };
+
+            match options.group_by {
+                Some(StatsGroupBy::File) => bump(path, ai_lines, human_lines),
+                Some(StatsGroupBy::Author) => bump(&commit.author, ai_lines, human_lines),
+                Some(StatsGroupBy::Model) => {
+                    for (model, lines) in &ai_rows {
+                        bump(model, *lines, 0);
+                    }
+                    if human_lines > 0 {
+                        bump(HUMAN_GROUP_KEY, 0, human_lines);
+                    }
+                }
+                None => {}
+            }
+        }
+
+        if commit_counted {
+            totals.commits += 1;
+        }
+    }
+
+    let mut rows: Vec<StatsReportRow> = rows.into_values().collect();
+    rows.sort_by(|a, b| b.total_lines.cmp(&a.total_lines).then(a.key.cmp(&b.key)));
+
+    Ok(StatsReport {
+        rev_range,
+        total_commits: commits.len(),
+        commits_with_authorship: logs.len(),
+        group_by: options.group_by,
+        totals,
+        rows,
+    })
+}
+
+/// Added lines of `commit_sha` against its first parent that pass `line_filter`, by file.
+fn counted_added_lines(
+    repo: &Repository,
+    commit_sha: &str,
+    line_filter: &LineFilter,
+) -> Result<HashMap<String, Vec<u32>>, GitAiError> {
+    let commit = repo.find_commit(commit_sha.to_string())?;
+    let from_ref = if commit.parent_count()? == 0 {
+        EMPTY_TREE_HASH.to_string()
+    } else {
+        commit.parent(0)?.id()
+    };
+    let mut added_lines = repo.diff_added_lines(&from_ref, commit_sha, None)?;
+    for (path, lines) in added_lines.iter_mut() {
+        lines.sort_unstable();
+        lines.dedup();
+        line_filter.retain_committed_lines(repo, commit_sha, path, lines);
+    }
+    Ok(added_lines)
+}
+
+/// Count AI-attributed lines in `path`, keyed by `tool::model` of the owning prompt. When
+/// `counted` is given, only those (sorted) lines count.
+fn ai_lines_by_model(
+    log: &AuthorshipLog,
+    path: &str,
+    counted: Option<&[u32]>,
+    raw_models: bool,
+) -> BTreeMap<String, u64> {
+    let mut result = BTreeMap::new();
+    let Some(attestation) = log.attestations.iter().find(|a| a.file_path == path) else {
+        return result;
+    };
+    for entry in &attestation.entries {
+        let Some(prompt) = log.metadata.prompts.get(&entry.hash) else {
+            continue;
+        };
+        let lines: u64 = entry
+            .line_ranges
+            .iter()
+            .map(|range| match counted {
+                Some(counted) => line_range_overlap_len(range, counted) as u64,
+                None => range.expand().len() as u64,
+            })
+            .sum();
+        *result
+            .entry(tool_model_key(
+                &prompt.agent_id.tool,
+                &prompt.agent_id.model,
+                raw_models,
+            ))
+            .or_insert(0) += lines;
+    }
+    result
+}
+
+fn log_commits(
+    repo: &Repository,
+    rev_range: &str,
+    options: &StatsReportOptions,
+) -> Result<Vec<LoggedCommit>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.push("log".to_string());
+    args.push("--no-merges".to_string());
+    args.push("--no-renames".to_string());
+    args.push("--numstat".to_string());
+    args.push("--format=%x00%H%x00%an".to_string());
+    if let Some(since) = &options.since {
+        args.push(format!("--since={}", since));
+    }
+    if let Some(author) = &options.author {
+        args.push(format!("--author={}", author));
+    }
+    args.push(rev_range.to_string());
+    args.push("--".to_string());
+
+    let output = exec_git(&args)?;
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    Ok(parse_log_numstat(&stdout))
+}
+
+fn parse_log_numstat(stdout: &str) -> Vec<LoggedCommit> {
+    let mut commits: Vec<LoggedCommit> = Vec::new();
+    for line in stdout.lines() {
+        if let Some(header) = line.strip_prefix('\0') {
+            let mut parts = header.splitn(2, '\0');
+            let sha = parts.next().unwrap_or_default().to_string();
+            let author = parts.next().unwrap_or_default().to_string();
+            commits.push(LoggedCommit {
+                sha,
+                author,
+                added: Vec::new(),
+            });
+            continue;
+        }
+
+        let mut parts = line.splitn(3, '\t');
+        let (Some(added), Some(_deleted), Some(path)) = (parts.next(), parts.next(), parts.next())
+        else {
+            continue;
+        };
+        // Binary files report "-" for both counts.
+        if let (Ok(added), Some(commit)) = (added.parse::<u64>(), commits.last_mut()) {
+            commit.added.push((path.to_string(), added));
+        }
+    }
+    commits
+}
+
+fn load_authorship_logs(
+    repo: &Repository,
+    shas: &[String],
+) -> Result<BTreeMap<String, AuthorshipLog>, GitAiError> {
+    let note_oids = note_blob_oids_for_commits(repo, shas)?;
+    let unique_oids: Vec<String> = note_oids
+        .values()
+        .cloned()
+        .collect::<HashSet<_>>()
+        .into_iter()
+        .collect();
+    let blobs = batch_read_blob_contents(repo, &unique_oids)?;
+
+    let mut logs = BTreeMap::new();
+    for (sha, oid) in note_oids {
+        if let Some(content) = blobs.get(&oid)
+            && let Ok(log) = AuthorshipLog::deserialize_from_string(content)
+        {
+            logs.insert(sha, log);
+        }
+    }
+    Ok(logs)
+}
+
+pub fn print_stats_report(report: &StatsReport) {
+    let key_header = match report.group_by {
+        Some(StatsGroupBy::File) => "File",
+        Some(StatsGroupBy::Author) => "Author",
+        Some(StatsGroupBy::Model) => "Model",
+        None => "",
+    };
+
+    let rows: Vec<&StatsReportRow> = if report.group_by.is_some() {
+        report.rows.iter().chain([&report.totals]).collect()
+    } else {
+        vec![&report.totals]
+    };
+
+    let key_width = rows
+        .iter()
+        .map(|r| r.key.chars().count())
+        .chain([key_header.chars().count(), "total".len()])
+        .max()
+        .unwrap_or(5);
+
+    println!(
+        "{:<key_width$}  {:>7}  {:>9}  {:>9}  {:>9}  {:>6}",
+        key_header, "Commits", "AI", "Human", "Total", "AI %"
+    );
+    for (index, row) in rows.iter().enumerate() {
+        if report.group_by.is_some() && index == rows.len() - 1 {
+            println!("{}", "-".repeat(key_width + 52));
+        }
+        println!(
+            "{:<key_width$}  {:>7}  {:>9}  {:>9}  {:>9}  {:>5.1}%",
+            row.key,
+            row.commits,
+            row.ai_lines,
+            row.human_lines,
+            row.total_lines,
+            row.ai_percent()
+        );
+    }
+
+    let without = report.total_commits - report.commits_with_authorship;
+    if without > 0 {
+        println!(
+            "\n{} of {} commits in {} have no authorship log; their lines count as human.",
+            without, report.total_commits, report.rev_range
+        );
+    }
+}
+
+pub fn stats_report_to_csv(report: &StatsReport) -> String {
+    let mut out = String::from("group,commits,ai_lines,human_lines,total_lines,ai_percent\n");
+    for row in report.rows.iter().chain([&report.totals]) {
+        out.push_str(&format!(
+            "{},{},{},{},{},{:.1}\n",
+            csv_field(&row.key),
+            row.commits,
+            row.ai_lines,
+            row.human_lines,
+            row.total_lines,
+            row.ai_percent()
+        ));
+    }
+    out
+}
+
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_log_numstat_skips_binary_files() {
+        let stdout =
+            "\0aaa\0Alice\n\n3\t1\tsrc/lib.rs\n-\t-\tlogo.png\n\0bbb\0Bob\n\n2\t0\tREADME.md\n";
+        let commits = parse_log_numstat(stdout);
+        assert_eq!(commits.len(), 2);
+        assert_eq!(commits[0].author, "Alice");
+        assert_eq!(commits[0].added, vec![("src/lib.rs".to_string(), 3)]);
+        assert_eq!(commits[1].sha, "bbb");
+        assert_eq!(commits[1].added, vec![("README.md".to_string(), 2)]);
+    }
+
+    #[test]
+    fn test_csv_quotes_keys_with_commas() {
+        let report = StatsReport {
+            rev_range: "HEAD".to_string(),
+            total_commits: 1,
+            commits_with_authorship: 1,
+            group_by: Some(StatsGroupBy::Author),
+            totals: StatsReportRow {
+                key: "total".to_string(),
+                commits: 1,
+                ai_lines: 1,
+                human_lines: 3,
+                total_lines: 4,
+            },
+            rows: vec![StatsReportRow {
+                key: "Doe, Jane".to_string(),
+                commits: 1,
+                ai_lines: 1,
+                human_lines: 3,
+                total_lines: 4,
+            }],
+        };
+        assert_eq!(
+            stats_report_to_csv(&report),
+            "group,commits,ai_lines,human_lines,total_lines,ai_percent\n\
+             \"Doe, Jane\",1,1,3,4,25.0\n\
+             total,1,1,3,4,25.0\n"
+        );
+    }
+}
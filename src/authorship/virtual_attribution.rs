@@ -5,11 +5,66 @@ use crate::authorship::authorship_log::{LineRange, PromptRecord};
 use crate::authorship::working_log::CheckpointKind;
 use crate::commands::blame::{GitAiBlameOptions, OLDEST_AI_BLAME_DATE};
 use crate::error::GitAiError;
+use crate::git::attribution_cache::AttributionCache;
+use crate::git::repo_storage::PersistedWorkingLog;
 use crate::git::repository::Repository;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
 use std::time::{SystemTime, UNIX_EPOCH};
 
+const WORKING_LOG_CACHE_NAMESPACE: &str = "working_log";
+
+/// Attribution tables derived from a working log, reusable while neither the working log
+/// nor the contents of the files they describe have changed.
+#[derive(Serialize, Deserialize)]
+struct CachedWorkingLogAttributions {
+    attributions: HashMap<String, (Vec<Attribution>, Vec<LineAttribution>)>,
+    prompts: BTreeMap<String, BTreeMap<String, PromptRecord>>,
+    /// File path -> sha256 of the workdir content the attributions were computed against.
+    content_hashes: HashMap<String, String>,
+    ts: u128,
+}
+
+impl CachedWorkingLogAttributions {
+    fn from_virtual_attributions(va: &VirtualAttributions) -> Self {
+        CachedWorkingLogAttributions {
+            attributions: va.attributions.clone(),
+            prompts: va.prompts.clone(),
+            content_hashes: va
+                .file_contents
+                .iter()
+                .map(|(path, content)| (path.clone(), content_sha256(content)))
+                .collect(),
+            ts: va.ts,
+        }
+    }
+
+    /// Re-read the workdir files; `None` if any of them changed since the entry was cached.
+    fn current_file_contents(&self, repo: &Repository) -> Option<HashMap<String, String>> {
+        let workdir = repo.workdir().ok()?;
+        let mut file_contents = HashMap::new();
+        for (path, hash) in &self.content_hashes {
+            let abs_path = workdir.join(path);
+            let content = if abs_path.exists() {
+                std::fs::read_to_string(&abs_path).unwrap_or_default()
+            } else {
+                String::new()
+            };
+            if content_sha256(&content) != *hash {
+                return None;
+            }
+            file_contents.insert(path.clone(), content);
+        }
+        Some(file_contents)
+    }
+}
+
+fn content_sha256(content: &str) -> String {
+    format!("{:x}", Sha256::digest(content.as_bytes()))
+}
+
 pub struct VirtualAttributions {
     repo: Repository,
     base_commit: String,
@@ -298,6 +353,43 @@ impl VirtualAttributions {
         human_author: Option<String>,
     ) -> Result<Self, GitAiError> {
         let working_log = repo.storage.working_log_for_base_commit(&base_commit);
+        let cache = repo.storage.attribution_cache();
+        let cache_key = AttributionCache::key(&[
+            &base_commit,
+            &working_log.fingerprint(),
+            &format!("{:?}", human_author),
+        ]);
+
+        if let Some(cached) =
+            cache.get::<CachedWorkingLogAttributions>(WORKING_LOG_CACHE_NAMESPACE, &cache_key)
+            && let Some(file_contents) = cached.current_file_contents(&repo)
+        {
+            return Ok(VirtualAttributions {
+                repo,
+                base_commit,
+                attributions: cached.attributions,
+                file_contents,
+                prompts: cached.prompts,
+                ts: cached.ts,
+                blame_start_commit: None,
+            });
+        }
+
+        let va = Self::from_working_log_entries(repo, base_commit, human_author, &working_log)?;
+        cache.put(
+            WORKING_LOG_CACHE_NAMESPACE,
+            &cache_key,
+            &CachedWorkingLogAttributions::from_virtual_attributions(&va),
+        );
+        Ok(va)
+    }
+
+    fn from_working_log_entries(
+        repo: Repository,
+        base_commit: String,
+        human_author: Option<String>,
+        working_log: &PersistedWorkingLog,
+    ) -> Result<Self, GitAiError> {
         let initial_attributions = working_log.read_initial_attributions();
         let checkpoints = working_log.read_all_checkpoints().unwrap_or_default();
 
@@ -1330,7 +1422,9 @@ pub fn merge_attributions_favoring_first(
     use crate::authorship::attribution_tracker::AttributionTracker;
 
     let tracker = AttributionTracker::new();
-    let ts = primary.ts;
+    // Attributions created while transforming either side must not sort before the other
+    // side's, even if the two were captured on machines with skewed clocks
+    let ts = primary.ts.max(secondary.ts);
     let repo = primary.repo.clone();
     let base_commit = primary.base_commit.clone();
 
@@ -1523,12 +1617,14 @@ pub fn restore_stashed_va(
         }
     };
 
-    // Write INITIAL attributions to working log for new HEAD
+    // Write INITIAL attributions to working log for new HEAD, keeping the INITIAL
+    // attributions of files the stash didn't touch
     if !initial_attributions.files.is_empty() || !initial_attributions.prompts.is_empty() {
         let working_log = repository.storage.working_log_for_base_commit(new_head);
-        if let Err(e) = working_log
-            .write_initial_attributions(initial_attributions.files, initial_attributions.prompts)
-        {
+        let mut initial = working_log.read_initial_attributions();
+        initial.files.extend(initial_attributions.files);
+        initial.prompts.extend(initial_attributions.prompts);
+        if let Err(e) = working_log.write_initial_attributions(initial.files, initial.prompts) {
             debug_log(&format!("Failed to write INITIAL attributions: {}", e));
             return;
         }
@@ -1728,7 +1824,7 @@ fn compute_attributions_for_file(
     }
 }
 
-fn get_file_content_at_commit(
+pub(crate) fn get_file_content_at_commit(
     repo: &Repository,
     commit_sha: &str,
     file_path: &str,
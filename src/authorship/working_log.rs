@@ -2,6 +2,7 @@ use crate::authorship::attribution_tracker::{Attribution, LineAttribution};
 use crate::authorship::authorship_log_serialization::GIT_AI_VERSION;
 use crate::authorship::transcript::AiTranscript;
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 use std::collections::HashMap;
 use std::fmt;
 use std::time::{SystemTime, UNIX_EPOCH};
@@ -20,6 +21,10 @@ pub struct WorkingLogEntry {
     pub attributions: Vec<Attribution>,
     #[serde(default)]
     pub line_attributions: Vec<LineAttribution>,
+    /// Content of the lines an AI checkpoint removed from the file, so the commit can record
+    /// which tool deleted them
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub deleted_lines: Vec<String>,
 }
 
 impl WorkingLogEntry {
@@ -35,6 +40,7 @@ impl WorkingLogEntry {
             blob_sha,
             attributions,
             line_attributions,
+            deleted_lines: Vec::new(),
         }
     }
 }
@@ -118,6 +124,15 @@ pub struct Checkpoint {
     pub api_version: String,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub git_ai_version: Option<String>,
+    /// Files attributed as a whole to this checkpoint's agent (e.g. generated binary assets),
+    /// where line-level attribution is meaningless.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub whole_file_assets: Vec<String>,
+    /// Logical clock for the working log: one more than the highest sequence in the log
+    /// when this checkpoint was appended. Orders checkpoints from machines whose clocks
+    /// disagree (0 for checkpoints written before sequences existed).
+    #[serde(default)]
+    pub sequence: u64,
 }
 
 impl Checkpoint {
@@ -144,8 +159,60 @@ impl Checkpoint {
             line_stats: CheckpointLineStats::default(),
             api_version: CHECKPOINT_API_VERSION.to_string(),
             git_ai_version: Some(GIT_AI_VERSION.to_string()),
+            whole_file_assets: Vec::new(),
+            sequence: 0,
         }
     }
+
+    /// Hybrid ordering: logical sequence first, wall-clock timestamp to break ties between
+    /// checkpoints appended concurrently. Legacy checkpoints (sequence 0) compare equal to each
+    /// other, since their timestamps may be skewed.
+    pub fn cmp_order(&self, other: &Checkpoint) -> Ordering {
+        self.sequence.cmp(&other.sequence).then_with(|| {
+            if self.sequence == 0 {
+                Ordering::Equal
+            } else {
+                self.timestamp.cmp(&other.timestamp)
+            }
+        })
+    }
+}
+
+/// Sequence number for a checkpoint appended after `checkpoints`.
+pub fn next_checkpoint_sequence(checkpoints: &[Checkpoint]) -> u64 {
+    checkpoints
+        .iter()
+        .map(|checkpoint| checkpoint.sequence)
+        .max()
+        .unwrap_or(0)
+        + 1
+}
+
+/// Order checkpoints oldest to newest by their hybrid ordering. The sort is stable, so
+/// legacy checkpoints without sequences keep their order in the log.
+pub fn sort_checkpoints(checkpoints: &mut [Checkpoint]) {
+    checkpoints.sort_by(Checkpoint::cmp_order);
+}
+
+/// Timestamp (ms since epoch) for the attributions of a checkpoint appended after
+/// `checkpoints`. Overlapping attributions resolve to the latest timestamp, so this stays
+/// ahead of every earlier attribution even when the local clock is behind the clock that
+/// wrote them.
+pub fn next_attribution_ts(checkpoints: &[Checkpoint]) -> u128 {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    let latest = checkpoints
+        .iter()
+        .flat_map(|checkpoint| checkpoint.entries.iter())
+        .flat_map(|entry| entry.attributions.iter())
+        .map(|attribution| attribution.ts)
+        .max();
+    match latest {
+        Some(latest) => now.max(latest + 1),
+        None => now,
+    }
 }
 
 #[cfg(test)]
@@ -305,4 +372,64 @@ mod tests {
         assert_eq!(deserialized_agent.tool, "cursor");
         assert_eq!(deserialized_agent.id, "session-abc123");
     }
+
+    fn checkpoint_at(sequence: u64, timestamp: u64, author: &str) -> Checkpoint {
+        let mut checkpoint = Checkpoint::new(
+            CheckpointKind::AiAgent,
+            "".to_string(),
+            author.to_string(),
+            Vec::new(),
+        );
+        checkpoint.sequence = sequence;
+        checkpoint.timestamp = timestamp;
+        checkpoint
+    }
+
+    #[test]
+    fn test_sort_checkpoints_prefers_sequence_over_skewed_timestamps() {
+        // The laptop's clock is an hour behind the devbox, but its checkpoint came later
+        let mut checkpoints = vec![
+            checkpoint_at(2, 1_000, "laptop"),
+            checkpoint_at(1, 4_600, "devbox"),
+            checkpoint_at(2, 900, "laptop-concurrent"),
+        ];
+        sort_checkpoints(&mut checkpoints);
+        let authors: Vec<&str> = checkpoints.iter().map(|c| c.author.as_str()).collect();
+        assert_eq!(authors, vec!["devbox", "laptop-concurrent", "laptop"]);
+        assert_eq!(next_checkpoint_sequence(&checkpoints), 3);
+        assert_eq!(next_checkpoint_sequence(&[]), 1);
+    }
+
+    #[test]
+    fn test_sort_checkpoints_keeps_legacy_order() {
+        // Legacy entries were appended in log order, whatever their clocks said
+        let mut checkpoints = vec![
+            checkpoint_at(0, 4_600, "first"),
+            checkpoint_at(0, 1_000, "second"),
+            checkpoint_at(0, 2_000, "third"),
+        ];
+        sort_checkpoints(&mut checkpoints);
+        let authors: Vec<&str> = checkpoints.iter().map(|c| c.author.as_str()).collect();
+        assert_eq!(authors, vec!["first", "second", "third"]);
+    }
+
+    #[test]
+    fn test_next_attribution_ts_stays_ahead_of_future_clock() {
+        let future_ts = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_millis()
+            + 3_600_000;
+        let entry = WorkingLogEntry::new(
+            "src/lib.rs".to_string(),
+            "sha".to_string(),
+            vec![Attribution::new(0, 10, "ai".to_string(), future_ts)],
+            Vec::new(),
+        );
+        let mut checkpoint = checkpoint_at(1, 0, "devbox");
+        checkpoint.entries.push(entry);
+
+        assert_eq!(next_attribution_ts(&[checkpoint]), future_ts + 1);
+        assert!(next_attribution_ts(&[]) < future_ts);
+    }
 }
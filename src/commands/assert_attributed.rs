@@ -0,0 +1,101 @@
+use crate::commands::hooks::rebase_hooks::read_rebase_rewritten_list;
+use crate::git::find_repository;
+use crate::git::refs::get_reference_as_authorship_log_v3;
+use crate::git::repository::Repository;
+
+/// `git-ai assert-attributed [<rev>...] [--quiet]`
+///
+/// Exits non-zero unless every revision (default `HEAD`) carries a valid authorship log. Meant
+/// for `git rebase --exec 'git-ai assert-attributed HEAD'` loops: while a rebase is running,
+/// git-ai only rewrites notes once it finishes, so a rebased commit without a note yet passes
+/// when the commit it was rewritten from has a valid log.
+pub fn handle_assert_attributed(args: &[String]) {
+    let quiet = args.iter().any(|arg| arg == "-q" || arg == "--quiet");
+    let mut revs: Vec<&str> = Vec::new();
+    for arg in args {
+        match arg.as_str() {
+            "-q" | "--quiet" => {}
+            flag if flag.starts_with('-') => {
+                eprintln!("Error: unknown assert-attributed option: {}", flag);
+                std::process::exit(2);
+            }
+            rev => revs.push(rev),
+        }
+    }
+    if revs.is_empty() {
+        revs.push("HEAD");
+    }
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Failed to find repository: {}", e);
+            std::process::exit(2);
+        }
+    };
+
+    let mut failed = false;
+    for rev in revs {
+        let sha = match repo.revparse_single(rev) {
+            Ok(object) => object.id(),
+            Err(e) => {
+                eprintln!("Error: cannot resolve {}: {}", rev, e);
+                std::process::exit(2);
+            }
+        };
+        let short = &sha[..sha.len().min(8)];
+        match check_attributed(&repo, &sha) {
+            Ok(None) => {
+                if !quiet {
+                    println!("{} {} has a valid authorship log", rev, short);
+                }
+            }
+            Ok(Some(original)) => {
+                if !quiet {
+                    println!(
+                        "{} {} is being rebased from {}, which has a valid authorship log",
+                        rev,
+                        short,
+                        &original[..original.len().min(8)]
+                    );
+                }
+            }
+            Err(reason) => {
+                eprintln!("{} {}: {}", rev, short, reason);
+                failed = true;
+            }
+        }
+    }
+
+    if failed {
+        std::process::exit(1);
+    }
+}
+
+/// `Ok(None)` when `sha` has a valid log of its own, `Ok(Some(original))` when it is a commit
+/// of the rebase in progress whose original commits all have valid logs.
+fn check_attributed(repo: &Repository, sha: &str) -> Result<Option<String>, String> {
+    let own_error = match get_reference_as_authorship_log_v3(repo, sha) {
+        Ok(_) => return Ok(None),
+        Err(e) => e.to_string(),
+    };
+
+    let originals: Vec<String> = read_rebase_rewritten_list(repo)
+        .into_iter()
+        .filter(|(_, new)| new == sha)
+        .map(|(old, _)| old)
+        .collect();
+    if originals.is_empty() {
+        return Err(own_error);
+    }
+    for original in &originals {
+        if let Err(e) = get_reference_as_authorship_log_v3(repo, original) {
+            return Err(format!(
+                "rebased from {}, which has no valid authorship log ({})",
+                &original[..original.len().min(8)],
+                e
+            ));
+        }
+    }
+    Ok(originals.into_iter().last())
+}
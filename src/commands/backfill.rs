@@ -0,0 +1,86 @@
+use crate::authorship::squash_backfill::{
+    MAX_SQUASH_CANDIDATES, SquashSource, backfill_squash_commit, find_squash_commit,
+};
+use crate::git::find_repository;
+
+/// `git-ai backfill --from <branch-or-range> [--onto <rev>] [--dry-run]`: find the commit on
+/// `--onto` (default HEAD) that squash-merged the source and give it an authorship note built
+/// from the source commits' notes.
+pub fn handle_backfill(args: &[String]) {
+    let mut from: Option<String> = None;
+    let mut onto = "HEAD".to_string();
+    let mut dry_run = false;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--from" | "--onto" => {
+                let Some(value) = args.get(i + 1) else {
+                    eprintln!("Error: {} requires a value", args[i]);
+                    std::process::exit(1);
+                };
+                if args[i] == "--from" {
+                    from = Some(value.clone());
+                } else {
+                    onto = value.clone();
+                }
+                i += 2;
+            }
+            "--dry-run" => {
+                dry_run = true;
+                i += 1;
+            }
+            arg => {
+                eprintln!("Unknown backfill argument: {}", arg);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let Some(from) = from else {
+        eprintln!("Usage: git-ai backfill --from <branch-or-range> [--onto <rev>] [--dry-run]");
+        std::process::exit(1);
+    };
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Failed to find repository: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let found = SquashSource::resolve(&repo, &from, &onto)
+        .and_then(|source| find_squash_commit(&repo, &source, &onto, MAX_SQUASH_CANDIDATES));
+    let found = match found {
+        Ok(Some(found)) => found,
+        Ok(None) => {
+            eprintln!(
+                "No commit on {} without an authorship note matches the squashed changes of {}",
+                onto, from
+            );
+            std::process::exit(1);
+        }
+        Err(e) => {
+            eprintln!("Backfill failed: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    if dry_run {
+        println!(
+            "Would backfill {} from {}",
+            found.squash_commit, found.source_head
+        );
+        return;
+    }
+
+    if let Err(e) = backfill_squash_commit(&repo, &found) {
+        eprintln!("Backfill failed: {}", e);
+        std::process::exit(1);
+    }
+    println!(
+        "Backfilled authorship for {} from {}",
+        found.squash_commit, found.source_head
+    );
+}
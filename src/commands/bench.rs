@@ -0,0 +1,359 @@
+use crate::commands::blame::BLAME_CACHE_NAMESPACE;
+use crate::config::Config;
+use crate::error::GitAiError;
+use crate::git::attribution_cache::{ATTRIBUTION_CACHE_DIR_ENV, AttributionCache};
+use crate::git::find_repository;
+use crate::git::repository::{Repository, exec_git};
+use serde_json::json;
+use std::io::Read;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+const DEFAULT_SAMPLE_FILES: usize = 20;
+const DEFAULT_RUNS: usize = 3;
+/// Bytes sniffed for NUL to skip binary files
+const BINARY_SNIFF_BYTES: usize = 8000;
+
+/// `git-ai bench <benchmark>`
+pub fn handle_bench(args: &[String]) {
+    match args.first().map(String::as_str) {
+        Some("blame") => handle_bench_blame(&args[1..]),
+        _ => {
+            eprintln!(
+                "Usage: git-ai bench blame [--against-git] [--files <n>] [--runs <n>] [--budget <percent>] [--json]"
+            );
+            std::process::exit(1);
+        }
+    }
+}
+
+struct BenchBlameOptions {
+    against_git: bool,
+    files: usize,
+    runs: usize,
+    budget: Option<u32>,
+    json: bool,
+}
+
+/// `git-ai bench blame`: time `git-ai blame` (with a cold, scratch blame cache, so the
+/// repository's cache is left alone) on an evenly spaced sample of the files tracked at HEAD. With `--against-git`, also time plain `git blame` on
+/// the same files and report git-ai's overhead; the run fails when the p95 overhead exceeds
+/// `--budget` or the `blame_overhead_budget` config.
+fn handle_bench_blame(args: &[String]) {
+    let mut options = BenchBlameOptions {
+        against_git: false,
+        files: DEFAULT_SAMPLE_FILES,
+        runs: DEFAULT_RUNS,
+        budget: None,
+        json: false,
+    };
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--against-git" => options.against_git = true,
+            "--json" => options.json = true,
+            "--files" | "--runs" | "--budget" => {
+                let Some(value) = args
+                    .get(i + 1)
+                    .and_then(|value| value.trim_end_matches('%').parse::<usize>().ok())
+                else {
+                    eprintln!("Error: {} requires a whole number", args[i]);
+                    std::process::exit(1);
+                };
+                match args[i].as_str() {
+                    "--files" => options.files = value.max(1),
+                    "--runs" => options.runs = value.max(1),
+                    _ => options.budget = Some(value as u32),
+                }
+                i += 1;
+            }
+            arg => {
+                eprintln!("Unknown bench blame argument: {}", arg);
+                std::process::exit(1);
+            }
+        }
+        i += 1;
+    }
+    if options.budget.is_some() && !options.against_git {
+        eprintln!("Error: --budget needs --against-git to measure overhead against");
+        std::process::exit(1);
+    }
+    let budget = options
+        .budget
+        .or_else(|| Config::get().blame_overhead_budget());
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Failed to find repository: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let report = match bench_blame(&repo, &options) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("Blame benchmark failed: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let p95_overhead = report
+        .overhead_percent
+        .as_ref()
+        .map(|overhead| percentile(overhead, 95.0));
+    let within_budget = match (p95_overhead, budget) {
+        (Some(overhead), Some(budget)) => Some(overhead <= budget as f64),
+        _ => None,
+    };
+
+    if options.json {
+        let summary = |values: &[f64]| {
+            json!({
+                "p50": percentile(values, 50.0),
+                "p90": percentile(values, 90.0),
+                "p95": percentile(values, 95.0),
+                "max": percentile(values, 100.0),
+            })
+        };
+        let output = json!({
+            "files": report.files.len(),
+            "runs": options.runs,
+            "git_ai_ms": summary(&report.git_ai_ms),
+            "git_ms": report.git_ms.as_deref().map(summary),
+            "overhead_percent": report.overhead_percent.as_deref().map(summary),
+            "budget_percent": budget,
+            "within_budget": within_budget,
+        });
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&output).unwrap_or_default()
+        );
+    } else {
+        print_report(&report, options.runs);
+        if let (Some(overhead), Some(budget)) = (p95_overhead, budget) {
+            let verdict = if overhead <= budget as f64 {
+                "within"
+            } else {
+                "over"
+            };
+            println!(
+                "p95 overhead {:.0}% is {} the {}% budget",
+                overhead, verdict, budget
+            );
+        }
+    }
+
+    if within_budget == Some(false) {
+        std::process::exit(1);
+    }
+}
+
+struct BlameBenchReport {
+    files: Vec<String>,
+    /// Median duration per file, sorted
+    git_ai_ms: Vec<f64>,
+    git_ms: Option<Vec<f64>>,
+    /// Per-file overhead of git-ai over git, sorted
+    overhead_percent: Option<Vec<f64>>,
+}
+
+fn bench_blame(
+    repo: &Repository,
+    options: &BenchBlameOptions,
+) -> Result<BlameBenchReport, GitAiError> {
+    let workdir = repo.workdir()?;
+    let files = sample_files(repo, &workdir, options.files)?;
+    if files.is_empty() {
+        return Err(GitAiError::Generic(
+            "no text files tracked at HEAD to blame".to_string(),
+        ));
+    }
+
+    let git_ai = std::env::current_exe()?;
+    let git = Config::get().git_cmd().to_string();
+    let cache_dir = std::env::temp_dir().join(format!("git-ai-bench-cache-{}", std::process::id()));
+    let result = time_files(&files, &workdir, &git_ai, &git, &cache_dir, options);
+    let _ = std::fs::remove_dir_all(&cache_dir);
+    let (git_ai_ms, git_ms, overhead) = result?;
+
+    let sorted = |mut values: Vec<f64>| {
+        values.sort_by(f64::total_cmp);
+        values
+    };
+    Ok(BlameBenchReport {
+        files,
+        git_ai_ms: sorted(git_ai_ms),
+        git_ms: options.against_git.then(|| sorted(git_ms)),
+        overhead_percent: options.against_git.then(|| sorted(overhead)),
+    })
+}
+
+/// Median git-ai (and git) blame time of each file, and git-ai's overhead, in file order.
+#[allow(clippy::type_complexity)]
+fn time_files(
+    files: &[String],
+    workdir: &Path,
+    git_ai: &Path,
+    git: &str,
+    cache_dir: &Path,
+    options: &BenchBlameOptions,
+) -> Result<(Vec<f64>, Vec<f64>, Vec<f64>), GitAiError> {
+    let cache = AttributionCache::new(cache_dir.to_path_buf());
+
+    let mut git_ai_ms = Vec::new();
+    let mut git_ms = Vec::new();
+    let mut overhead = Vec::new();
+    for file in files {
+        let mut git_ai_runs = Vec::new();
+        let mut git_runs = Vec::new();
+        for _ in 0..options.runs {
+            cache.clear_namespace(BLAME_CACHE_NAMESPACE)?;
+            git_ai_runs.push(time_command(
+                git_ai_command(git_ai)
+                    .env(ATTRIBUTION_CACHE_DIR_ENV, cache_dir)
+                    .arg("blame")
+                    .arg(file),
+                workdir,
+            )?);
+            if options.against_git {
+                git_runs.push(time_command(
+                    Command::new(git).arg("blame").arg(file),
+                    workdir,
+                )?);
+            }
+        }
+        let ai = median_ms(git_ai_runs);
+        git_ai_ms.push(ai);
+        if options.against_git {
+            let plain = median_ms(git_runs);
+            git_ms.push(plain);
+            overhead.push((ai - plain) / plain.max(0.001) * 100.0);
+        }
+    }
+    Ok((git_ai_ms, git_ms, overhead))
+}
+
+/// Up to `count` text files tracked at HEAD, evenly spaced through the sorted file list so
+/// repeated runs blame the same files. Each pick is the first text file in its slot of the
+/// list, so only the files tried are read.
+fn sample_files(
+    repo: &Repository,
+    workdir: &Path,
+    count: usize,
+) -> Result<Vec<String>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.extend(["ls-tree", "-r", "-z", "--name-only", "HEAD"].map(str::to_string));
+    let output = String::from_utf8(exec_git(&args)?.stdout)?;
+    let mut paths: Vec<&str> = output.split('\0').filter(|path| !path.is_empty()).collect();
+    paths.sort_unstable();
+
+    let slots = count.min(paths.len());
+    Ok((0..slots)
+        .filter_map(|i| {
+            let slot = &paths[i * paths.len() / slots..(i + 1) * paths.len() / slots];
+            slot.iter().find(|path| is_text_file(&workdir.join(path)))
+        })
+        .map(|path| path.to_string())
+        .collect())
+}
+
+fn is_text_file(path: &Path) -> bool {
+    let Ok(file) = std::fs::File::open(path) else {
+        return false;
+    };
+    let mut head = Vec::with_capacity(BINARY_SNIFF_BYTES);
+    match file.take(BINARY_SNIFF_BYTES as u64).read_to_end(&mut head) {
+        Ok(_) => !head.is_empty() && !head.contains(&0),
+        Err(_) => false,
+    }
+}
+
+/// This binary, invoked as `git-ai` whatever name it was installed under.
+fn git_ai_command(exe: &Path) -> Command {
+    #[allow(unused_mut)]
+    let mut command = Command::new(exe);
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.arg0("git-ai");
+    }
+    command
+}
+
+fn time_command(command: &mut Command, workdir: &Path) -> Result<Duration, GitAiError> {
+    let start = Instant::now();
+    let status = command
+        .current_dir(workdir)
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .status()?;
+    let elapsed = start.elapsed();
+    if !status.success() {
+        return Err(GitAiError::Generic(format!(
+            "{:?} exited with {}",
+            command, status
+        )));
+    }
+    Ok(elapsed)
+}
+
+fn median_ms(mut runs: Vec<Duration>) -> f64 {
+    runs.sort();
+    runs[runs.len() / 2].as_secs_f64() * 1000.0
+}
+
+/// Nearest-rank percentile of sorted values.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    if sorted.is_empty() {
+        return 0.0;
+    }
+    let rank = ((p / 100.0) * sorted.len() as f64).ceil() as usize;
+    sorted[rank.clamp(1, sorted.len()) - 1]
+}
+
+fn print_report(report: &BlameBenchReport, runs: usize) {
+    println!(
+        "Blamed {} files, median of {} run(s) each",
+        report.files.len(),
+        runs
+    );
+    println!(
+        "{:<10} {:>9} {:>9} {:>9} {:>9}",
+        "", "p50", "p90", "p95", "max"
+    );
+    let row = |label: &str, values: &[f64], unit: &str| {
+        println!(
+            "{:<10} {:>9} {:>9} {:>9} {:>9}",
+            label,
+            format!("{:.0}{}", percentile(values, 50.0), unit),
+            format!("{:.0}{}", percentile(values, 90.0), unit),
+            format!("{:.0}{}", percentile(values, 95.0), unit),
+            format!("{:.0}{}", percentile(values, 100.0), unit),
+        );
+    };
+    row("git-ai", &report.git_ai_ms, "ms");
+    if let Some(git_ms) = &report.git_ms {
+        row("git", git_ms, "ms");
+    }
+    if let Some(overhead) = &report.overhead_percent {
+        row("overhead", overhead, "%");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_percentile_nearest_rank() {
+        let values: Vec<f64> = (1..=20).map(f64::from).collect();
+        assert_eq!(percentile(&values, 50.0), 10.0);
+        assert_eq!(percentile(&values, 95.0), 19.0);
+        assert_eq!(percentile(&values, 100.0), 20.0);
+        assert_eq!(percentile(&[7.0], 95.0), 7.0);
+        assert_eq!(percentile(&[], 95.0), 0.0);
+    }
+}
@@ -1,17 +1,24 @@
 use crate::auth::CredentialStore;
-use crate::authorship::authorship_log::PromptRecord;
-use crate::authorship::authorship_log_serialization::AuthorshipLog;
+use crate::authorship::authorship_log::{Author, PromptRecord};
+use crate::authorship::authorship_log_serialization::{AuthorshipLog, generate_short_hash};
+use crate::authorship::deletions::commit_deletions;
+use crate::authorship::imara_diff_utils::{LineChangeTag, compute_line_changes};
+use crate::authorship::line_shares::DominantAuthor;
 use crate::authorship::prompt_utils::enrich_prompt_messages;
-use crate::authorship::working_log::CheckpointKind;
+use crate::authorship::provenance::line_provenance;
+use crate::authorship::working_log::{Checkpoint, CheckpointKind, WorkingLogEntry};
 use crate::error::GitAiError;
-use crate::git::refs::get_reference_as_authorship_log_v3;
+use crate::git::attribution_cache::AttributionCache;
+use crate::git::refs::{AI_AUTHORSHIP_NOTES_REF, get_reference_as_authorship_log_v3};
 use crate::git::repository::Repository;
 use crate::git::repository::{exec_git, exec_git_stdin};
 #[cfg(windows)]
 use crate::utils::normalize_to_posix;
+use crate::utils::unescape_git_path;
 use chrono::{DateTime, FixedOffset, TimeZone, Utc};
-use serde::Serialize;
-use std::collections::HashMap;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{HashMap, HashSet};
 use std::fs;
 use std::io::{self, IsTerminal, Write};
 use std::sync::LazyLock;
@@ -24,7 +31,7 @@ pub static OLDEST_AI_BLAME_DATE: LazyLock<DateTime<FixedOffset>> = LazyLock::new
         .unwrap()
 });
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BlameHunk {
     /// Line range [start, end] (inclusive) - current line numbers in the file
     pub range: (u32, u32),
@@ -55,6 +62,87 @@ pub struct BlameHunk {
     pub committer_tz: String,
     /// Whether this is a boundary commit
     pub is_boundary: bool,
+    /// Path of the file in the introducing commit, when git followed the lines there from
+    /// another file (a rename, or `-M`/`-C` move and copy detection)
+    #[serde(default)]
+    pub orig_file_path: Option<String>,
+}
+
+/// Structured per-line output formats (`--format=json|jsonl`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlameLineFormat {
+    /// A single JSON array of line records
+    Json,
+    /// One JSON line record per line of output
+    Jsonl,
+}
+
+/// Which lines the blame output is restricted to (`--only-ai`, `--only-human`, `--tool`,
+/// `--model`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BlameLineFilter {
+    /// AI lines, optionally only those of one tool (exact name) or model (substring), both
+    /// compared case-insensitively
+    Ai {
+        tool: Option<String>,
+        model: Option<String>,
+    },
+    /// Lines without an AI prompt behind them
+    Human,
+}
+
+impl BlameLineFilter {
+    /// Whether a line written by `prompt` (None for human lines) passes the filter.
+    pub fn matches(&self, prompt: Option<&PromptRecord>) -> bool {
+        match (self, prompt) {
+            (BlameLineFilter::Human, prompt) => prompt.is_none(),
+            (BlameLineFilter::Ai { .. }, None) => false,
+            (BlameLineFilter::Ai { tool, model }, Some(prompt)) => {
+                tool.as_ref()
+                    .is_none_or(|tool| prompt.agent_id.tool.eq_ignore_ascii_case(tool))
+                    && model.as_ref().is_none_or(|model| {
+                        prompt
+                            .agent_id
+                            .model
+                            .to_lowercase()
+                            .contains(&model.to_lowercase())
+                    })
+            }
+        }
+    }
+}
+
+/// When to emit ANSI colors in the default blame output (`--color=always|never|auto`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum BlameColorMode {
+    Always,
+    Never,
+    /// Color only when stdout is a terminal (and `NO_COLOR` is unset)
+    #[default]
+    Auto,
+}
+
+impl BlameColorMode {
+    fn from_arg(value: &str) -> Option<Self> {
+        match value {
+            "always" => Some(BlameColorMode::Always),
+            "never" => Some(BlameColorMode::Never),
+            "auto" => Some(BlameColorMode::Auto),
+            _ => None,
+        }
+    }
+
+    fn enabled(self) -> bool {
+        match self {
+            BlameColorMode::Always => true,
+            BlameColorMode::Never => false,
+            BlameColorMode::Auto => {
+                io::stdout().is_terminal()
+                    && std::env::var_os("NO_COLOR").is_none()
+                    && std::env::var("TERM").map(|t| t != "dumb").unwrap_or(true)
+            }
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -99,6 +187,7 @@ pub struct GitAiBlameOptions {
     // Color options
     pub color_lines: bool,
     pub color_by_age: bool,
+    pub color: BlameColorMode,
 
     // Progress options
     pub progress: bool,
@@ -136,16 +225,43 @@ pub struct GitAiBlameOptions {
     // JSON output format
     pub json: bool,
 
+    // Structured per-line output format (--format=json|jsonl)
+    pub format: Option<BlameLineFormat>,
+
     // Mark lines from commits without authorship logs as "Unknown"
     pub mark_unknown: bool,
 
     // Show prompt hashes inline and dump prompts when piped
     pub show_prompt: bool,
 
+    // Show the full prompt id next to AI authors (--show-prompt-id)
+    pub show_prompt_id: bool,
+
+    // Copy provenance of the single blamed line to the clipboard (--copy-provenance)
+    pub copy_provenance: bool,
+
+    // List lines deleted from the file, and who deleted them, after the blame (--show-deleted)
+    pub show_deleted: bool,
+
+    // --compat strict: stdout comes from git blame untouched, AI data goes to a sidecar file
+    pub compat_strict: bool,
+
+    // Sidecar path for --compat strict (defaults to .git/ai/blame/<file>.json)
+    pub sidecar_path: Option<String>,
+
     // Split hunks when lines have different AI human authors
     // When true, a single git blame hunk may be split into multiple hunks
     // if different lines were authored by different humans working with AI
     pub split_hunks_by_ai_author: bool,
+
+    // Only output lines matching this filter (--only-ai, --only-human, --tool, --model)
+    pub line_filter: Option<BlameLineFilter>,
+
+    // Show lines excluded by line_filter dimmed instead of folding them into `...` markers
+    pub dim_filtered: bool,
+
+    // Who owns lines a human and AI wrote together (--dominant-author, blame_dominant_author)
+    pub dominant_author: DominantAuthor,
 }
 
 impl Default for GitAiBlameOptions {
@@ -176,6 +292,7 @@ impl Default for GitAiBlameOptions {
             no_ignore_revs_file: false,
             color_lines: false,
             color_by_age: false,
+            color: BlameColorMode::Auto,
             progress: false,
             date_format: None,
             contents_file: None,
@@ -188,20 +305,27 @@ impl Default for GitAiBlameOptions {
             no_output: false,
             ignore_whitespace: false,
             json: false,
+            format: None,
             mark_unknown: false,
             show_prompt: false,
+            show_prompt_id: false,
+            copy_provenance: false,
+            show_deleted: false,
+            compat_strict: false,
+            sidecar_path: None,
             split_hunks_by_ai_author: true,
+            line_filter: None,
+            dim_filtered: false,
+            dominant_author: DominantAuthor::Latest,
         }
     }
 }
 
 impl Repository {
-    #[allow(clippy::type_complexity)]
-    pub fn blame(
-        &self,
-        file_path: &str,
-        options: &GitAiBlameOptions,
-    ) -> Result<(HashMap<u32, String>, HashMap<String, PromptRecord>), GitAiError> {
+    /// `file_path` relative to the repository root, the form authorship logs store paths in.
+    /// Absolute paths (callers resolve paths relative to the current directory first) are made
+    /// relative to the root; relative ones are taken as root-relative already.
+    pub fn repo_relative_path(&self, file_path: &str) -> Result<String, GitAiError> {
         // Use repo root for file system operations
         let repo_root = self.workdir().map_err(|e| {
             GitAiError::Generic(format!("Repository has no working directory: {}", e))
@@ -262,6 +386,17 @@ impl Repository {
                 .to_string()
         };
 
+        Ok(relative_file_path)
+    }
+
+    #[allow(clippy::type_complexity)]
+    pub fn blame(
+        &self,
+        file_path: &str,
+        options: &GitAiBlameOptions,
+    ) -> Result<(HashMap<u32, String>, HashMap<String, PromptRecord>), GitAiError> {
+        let relative_file_path = self.repo_relative_path(file_path)?;
+
         // For JSON output, default to HEAD to exclude uncommitted changes
         // and use prompt hashes as names so we can correlate with prompt_records
         let options = if options.json {
@@ -271,7 +406,12 @@ impl Repository {
             }
             opts.use_prompt_hashes_as_names = true;
             opts
-        } else if options.show_prompt {
+        } else if options.show_prompt
+            || options.show_prompt_id
+            || options.format.is_some()
+            || options.copy_provenance
+            || options.compat_strict
+        {
             let mut opts = options.clone();
             opts.use_prompt_hashes_as_names = true;
             opts
@@ -279,6 +419,18 @@ impl Repository {
             options.clone()
         };
 
+        // Filters need the prompt behind each AI line, so blame by prompt hash and switch back
+        // to tool names once the lines are filtered
+        let names_from_hashes =
+            options.line_filter.is_some() && !options.use_prompt_hashes_as_names;
+        let options = if names_from_hashes {
+            let mut opts = options;
+            opts.use_prompt_hashes_as_names = true;
+            opts
+        } else {
+            options
+        };
+
         // Read file content from one of:
         // 1. Provided contents_data (from --contents flag)
         // 2. A specific commit
@@ -317,7 +469,7 @@ impl Repository {
             }
         } else {
             // Read from working directory (existing behavior)
-            let abs_file_path = repo_root.join(&relative_file_path);
+            let abs_file_path = self.workdir()?.join(&relative_file_path);
 
             if !abs_file_path.exists() {
                 return Err(GitAiError::Generic(format!(
@@ -351,27 +503,164 @@ impl Repository {
             }
         }
 
-        // Step 1: Get Git's native blame for all ranges
-        let mut all_blame_hunks = Vec::new();
-        for (start_line, end_line) in &line_ranges {
-            let hunks = self.blame_hunks(&relative_file_path, *start_line, *end_line, &options)?;
-            all_blame_hunks.extend(hunks);
+        // Reuse a previous result when the content, history and notes are all unchanged
+        let cache = self.storage.attribution_cache();
+        let cache_key = blame_cache_key(
+            self,
+            &relative_file_path,
+            &file_content,
+            &line_ranges,
+            &options,
+        );
+        let cached = cache_key
+            .as_ref()
+            .and_then(|key| cache.get::<CachedBlame>(BLAME_CACHE_NAMESPACE, key))
+            .and_then(CachedBlame::into_parts);
+
+        let (
+            all_blame_hunks,
+            mut line_authors,
+            mut prompt_records,
+            authorship_logs,
+            prompt_commits,
+        ) = if let Some(parts) = cached {
+            parts
+        } else {
+            // Step 1: Get Git's native blame for all ranges
+            let mut all_blame_hunks = Vec::new();
+            for (start_line, end_line) in &line_ranges {
+                let hunks =
+                    self.blame_hunks(&relative_file_path, *start_line, *end_line, &options)?;
+                all_blame_hunks.extend(hunks);
+            }
+
+            // Step 2: Overlay AI authorship information
+            let (line_authors, prompt_records, authorship_logs, prompt_commits) =
+                overlay_ai_authorship(self, &all_blame_hunks, &relative_file_path, &options)?;
+
+            if let Some(key) = &cache_key {
+                cache.put(
+                    BLAME_CACHE_NAMESPACE,
+                    key,
+                    &CachedBlame {
+                        hunks: all_blame_hunks.clone(),
+                        line_authors: line_authors.clone(),
+                        prompt_records: prompt_records.clone(),
+                        authorship_logs: authorship_logs
+                            .iter()
+                            .filter_map(|log| log.serialize_to_string().ok())
+                            .collect(),
+                        prompt_commits: prompt_commits.clone(),
+                    },
+                );
+            }
+
+            (
+                all_blame_hunks,
+                line_authors,
+                prompt_records,
+                authorship_logs,
+                prompt_commits,
+            )
+        };
+
+        // Uncommitted buffer lines that match the file on disk keep their working log
+        // attribution, so an unsaved edit doesn't erase AI authorship of the rest of the file
+        if options.contents_data.is_some() && !options.json {
+            overlay_buffer_working_log(
+                self,
+                &relative_file_path,
+                &file_content,
+                &all_blame_hunks,
+                &mut line_authors,
+                &mut prompt_records,
+                &options,
+            );
         }
 
-        // Step 2: Overlay AI authorship information
-        let (line_authors, prompt_records, authorship_logs, prompt_commits) =
-            overlay_ai_authorship(self, &all_blame_hunks, &relative_file_path, &options)?;
+        // Lines the filter rejects are dropped from the machine-readable formats, and folded
+        // into gap markers (or dimmed) in the default one
+        let mut filtered_out: HashSet<u32> = HashSet::new();
+        let mut matched_ranges: Vec<(u32, u32)> = Vec::new();
+        if let Some(filter) = &options.line_filter {
+            for (start_line, end_line) in &line_ranges {
+                for line_num in *start_line..=*end_line {
+                    let prompt = line_authors
+                        .get(&line_num)
+                        .and_then(|author| prompt_records.get(author));
+                    if !filter.matches(prompt) {
+                        filtered_out.insert(line_num);
+                    } else if let Some(last) = matched_ranges.last_mut()
+                        && last.1 + 1 == line_num
+                    {
+                        last.1 = line_num;
+                    } else {
+                        matched_ranges.push((line_num, line_num));
+                    }
+                }
+            }
+        } else {
+            matched_ranges = line_ranges.clone();
+        }
+        if names_from_hashes {
+            for author in line_authors.values_mut() {
+                if let Some(prompt) = prompt_records.get(author) {
+                    *author = prompt.agent_id.tool.clone();
+                }
+            }
+        }
+        let matched_authors: HashMap<u32, String> = line_authors
+            .iter()
+            .filter(|(line_num, _)| !filtered_out.contains(line_num))
+            .map(|(line_num, author)| (*line_num, author.clone()))
+            .collect();
 
         if options.no_output {
-            return Ok((line_authors, prompt_records));
+            return Ok((matched_authors, prompt_records));
         }
 
         // Output based on format
-        if options.json {
-            output_json_format(
+        if options.compat_strict {
+            let records = build_line_records(
                 self,
+                &all_blame_hunks,
                 &line_authors,
                 &prompt_records,
+                &relative_file_path,
+                &file_content,
+                &line_ranges,
+            );
+            write_compat_sidecar(self, &relative_file_path, &records, &options)?;
+        } else if options.copy_provenance {
+            let records = build_line_records(
+                self,
+                &all_blame_hunks,
+                &line_authors,
+                &prompt_records,
+                &relative_file_path,
+                &file_content,
+                &line_ranges,
+            );
+            let record = records.first().ok_or_else(|| {
+                GitAiError::Generic("No blame information for the requested line".to_string())
+            })?;
+            output_line_provenance(self, &relative_file_path, record.line)?;
+        } else if let Some(format) = options.format {
+            output_line_records_format(
+                self,
+                &all_blame_hunks,
+                &line_authors,
+                &prompt_records,
+                &relative_file_path,
+                &file_content,
+                &matched_ranges,
+                format,
+            )?;
+        } else if options.json {
+            output_json_format(
+                self,
+                &matched_authors,
+                &prompt_records,
                 &authorship_logs,
                 &prompt_commits,
                 &relative_file_path,
@@ -382,7 +671,7 @@ impl Repository {
                 &line_authors,
                 &relative_file_path,
                 &lines,
-                &line_ranges,
+                &matched_ranges,
                 &options,
             )?;
         } else if options.incremental {
@@ -391,7 +680,7 @@ impl Repository {
                 &line_authors,
                 &relative_file_path,
                 &lines,
-                &line_ranges,
+                &matched_ranges,
                 &options,
             )?;
         } else {
@@ -402,11 +691,15 @@ impl Repository {
                 &relative_file_path,
                 &lines,
                 &line_ranges,
+                &filtered_out,
                 &options,
             )?;
+            if options.show_deleted {
+                output_deleted_lines(self, &relative_file_path, &options)?;
+            }
         }
 
-        Ok((line_authors, prompt_records))
+        Ok((matched_authors, prompt_records))
     }
 
     pub fn blame_hunks(
@@ -426,6 +719,11 @@ impl Repository {
             args.push("-w".to_string());
         }
 
+        // Don't treat root commits as boundaries
+        if options.show_root {
+            args.push("--root".to_string());
+        }
+
         // Respect ignore options in use
         for rev in &options.ignore_revs {
             args.push("--ignore-rev".to_string());
@@ -436,6 +734,18 @@ impl Repository {
             args.push(file.clone());
         }
 
+        // Follow moved (-M) and copied (-C) lines, within and across files
+        let threshold = options
+            .move_threshold
+            .map(|t| t.to_string())
+            .unwrap_or_default();
+        if options.detect_moves {
+            args.push(format!("-M{}", threshold));
+        }
+        for _ in 0..options.detect_copies {
+            args.push(format!("-C{}", threshold));
+        }
+
         // Limit to specified range
         args.push("-L".to_string());
         args.push(format!("{},{}", start_line, end_line));
@@ -497,6 +807,7 @@ impl Repository {
             committer_time: i64,
             committer_tz: String,
             boundary: bool,
+            filename: String,
         }
 
         let mut hunks: Vec<BlameHunk> = Vec::new();
@@ -566,6 +877,10 @@ impl Repository {
                 cur_meta.boundary = true;
                 continue;
             }
+            if let Some(rest) = line.strip_prefix("filename ") {
+                cur_meta.filename = unescape_git_path(rest);
+                continue;
+            }
 
             // Header line: either 4 fields (new hunk) or 3 fields (continuation)
             let mut parts = line.split_whitespace();
@@ -625,6 +940,9 @@ impl Repository {
                         committer_time: cur_meta.committer_time,
                         committer_tz: cur_meta.committer_tz.clone(),
                         is_boundary: cur_meta.boundary,
+                        orig_file_path: (!cur_meta.filename.is_empty()
+                            && cur_meta.filename != file_path)
+                            .then(|| cur_meta.filename.clone()),
                     });
                 }
 
@@ -694,15 +1012,44 @@ impl Repository {
                 committer_time: cur_meta.committer_time,
                 committer_tz: cur_meta.committer_tz.clone(),
                 is_boundary: cur_meta.boundary,
+                orig_file_path: (!cur_meta.filename.is_empty() && cur_meta.filename != file_path)
+                    .then(|| cur_meta.filename.clone()),
             });
         }
 
+        // git reports lines that only exist in a --contents buffer as "External file";
+        // they are unsaved edits by whoever is running the editor
+        if options.contents_data.is_some() {
+            self.assign_buffer_hunks_to_current_user(&mut hunks);
+        }
+
         // Post-process hunks to populate ai_human_author from authorship logs
         let hunks = self.populate_ai_human_authors(hunks, file_path, options)?;
 
         Ok(hunks)
     }
 
+    /// Replace git's placeholder identity on uncommitted `--contents` hunks with the
+    /// configured git user. Left untouched when no identity is configured.
+    fn assign_buffer_hunks_to_current_user(&self, hunks: &mut [BlameHunk]) {
+        let name = self.config_get_str("user.name").ok().flatten();
+        let email = self.config_get_str("user.email").ok().flatten();
+        let (Some(name), email) = (name, email) else {
+            return;
+        };
+        for hunk in hunks
+            .iter_mut()
+            .filter(|hunk| is_uncommitted_sha(&hunk.commit_sha))
+        {
+            hunk.original_author = name.clone();
+            hunk.committer = name.clone();
+            if let Some(email) = &email {
+                hunk.author_email = email.clone();
+                hunk.committer_email = email.clone();
+            }
+        }
+    }
+
     /// Post-process blame hunks to populate ai_human_author from authorship logs.
     /// For each hunk, looks up the authorship log for its commit and finds the human_author
     /// from the prompt record that covers lines in the hunk.
@@ -744,7 +1091,7 @@ impl Repository {
                     let human_author = if let Some((_author, _prompt_hash, Some(prompt_record))) =
                         authorship_log.get_line_attribution(
                             self,
-                            file_path,
+                            hunk.orig_file_path.as_deref().unwrap_or(file_path),
                             orig_line_num,
                             &mut foreign_prompts_cache,
                         ) {
@@ -808,6 +1155,169 @@ impl Repository {
     }
 }
 
+pub(crate) const BLAME_CACHE_NAMESPACE: &str = "blame";
+
+/// A blame result as stored in the attribution cache.
+#[derive(Serialize, Deserialize)]
+struct CachedBlame {
+    hunks: Vec<BlameHunk>,
+    line_authors: HashMap<u32, String>,
+    prompt_records: HashMap<String, PromptRecord>,
+    /// Serialized authorship logs (only needed by `--json` output).
+    authorship_logs: Vec<String>,
+    prompt_commits: HashMap<String, Vec<String>>,
+}
+
+impl CachedBlame {
+    #[allow(clippy::type_complexity)]
+    fn into_parts(
+        self,
+    ) -> Option<(
+        Vec<BlameHunk>,
+        HashMap<u32, String>,
+        HashMap<String, PromptRecord>,
+        Vec<AuthorshipLog>,
+        HashMap<String, Vec<String>>,
+    )> {
+        let authorship_logs = self
+            .authorship_logs
+            .iter()
+            .map(|content| AuthorshipLog::deserialize_from_string(content).ok())
+            .collect::<Option<Vec<_>>>()?;
+        Some((
+            self.hunks,
+            self.line_authors,
+            self.prompt_records,
+            authorship_logs,
+            self.prompt_commits,
+        ))
+    }
+}
+
+/// Key for a cached blame: the blamed content, the commits it is blamed against, the
+/// authorship notes version (tip of the local notes ref, which notes fetched from a custom
+/// `git-ai.notesRef` are merged into), the config `git blame` reads on its own (the
+/// `blame.ignoreRevsFile` list and the mailmap) and every option that changes the attribution.
+/// Returns `None` when the history can't be pinned down (e.g. unborn HEAD).
+fn blame_cache_key(
+    repo: &Repository,
+    file_path: &str,
+    file_content: &str,
+    line_ranges: &[(u32, u32)],
+    options: &GitAiBlameOptions,
+) -> Option<String> {
+    let newest = options.newest_commit.as_deref().unwrap_or("HEAD");
+    let newest_sha = repo.revparse_single(newest).ok()?.id();
+    let oldest_sha = match &options.oldest_commit {
+        Some(oldest) => repo.revparse_single(oldest).ok()?.id(),
+        None => String::new(),
+    };
+    let notes_tip = repo
+        .revparse_single(AI_AUTHORSHIP_NOTES_REF)
+        .map(|obj| obj.id())
+        .unwrap_or_default();
+    let ignore_revs_file = options
+        .ignore_revs_file
+        .as_ref()
+        .map(|path| file_digest(std::path::Path::new(path)))
+        .unwrap_or_default();
+    let git_blame_config = git_blame_config_digest(repo);
+    let content_hash = format!("{:x}", Sha256::digest(file_content.as_bytes()));
+    // Uncommitted --contents lines are attributed to the configured user
+    let buffer_identity = if options.contents_data.is_some() {
+        format!(
+            "{:?}",
+            (
+                repo.config_get_str("user.name").ok().flatten(),
+                repo.config_get_str("user.email").ok().flatten(),
+            )
+        )
+    } else {
+        String::new()
+    };
+    let attribution_options = format!(
+        "{:?}",
+        (
+            line_ranges,
+            options.oldest_date,
+            options.contents_data.is_some(),
+            (
+                options.ignore_whitespace,
+                options.show_root,
+                &options.ignore_revs,
+                options.no_ignore_revs_file,
+                options.first_parent,
+                &options.encoding,
+            ),
+            options.use_prompt_hashes_as_names,
+            options.return_human_authors_as_human,
+            options.mark_unknown,
+            options.split_hunks_by_ai_author,
+            options.dominant_author,
+            (
+                options.detect_moves,
+                options.detect_copies,
+                options.move_threshold,
+            ),
+        )
+    );
+
+    Some(AttributionCache::key(&[
+        file_path,
+        &content_hash,
+        &newest_sha,
+        &oldest_sha,
+        &notes_tip,
+        &ignore_revs_file,
+        &git_blame_config,
+        &buffer_identity,
+        &attribution_options,
+    ]))
+}
+
+/// Hash of the file at `path`, or an empty string when it can't be read.
+fn file_digest(path: &std::path::Path) -> String {
+    fs::read(path)
+        .map(|content| format!("{:x}", Sha256::digest(&content)))
+        .unwrap_or_default()
+}
+
+/// The config `git blame` applies without being asked: the `blame.ignoreRevsFile` list (path
+/// and contents) and the mailmap from `.mailmap`, `mailmap.file` and `mailmap.blob`.
+fn git_blame_config_digest(repo: &Repository) -> String {
+    let workdir = repo.workdir().ok();
+    let config_file = |key: &str| {
+        let path = repo
+            .config_get_str(key)
+            .ok()
+            .flatten()
+            .filter(|path| !path.is_empty())?;
+        let path = match &workdir {
+            Some(workdir) if std::path::Path::new(&path).is_relative() => workdir.join(path),
+            _ => std::path::PathBuf::from(path),
+        };
+        Some(format!("{}:{}", path.display(), file_digest(&path)))
+    };
+    let mailmap_blob = repo
+        .config_get_str("mailmap.blob")
+        .ok()
+        .flatten()
+        .and_then(|rev| repo.revparse_single(&rev).ok())
+        .map(|obj| obj.id());
+
+    format!(
+        "{:?}",
+        (
+            config_file("blame.ignoreRevsFile"),
+            workdir
+                .as_ref()
+                .map(|workdir| file_digest(&workdir.join(".mailmap"))),
+            config_file("mailmap.file"),
+            mailmap_blob,
+        )
+    )
+}
+
 #[allow(clippy::type_complexity)]
 fn overlay_ai_authorship(
     repo: &Repository,
@@ -853,12 +1363,38 @@ fn overlay_ai_authorship(
                 let current_line_num = hunk.range.0 + i;
                 let orig_line_num = hunk.orig_range.0 + i;
 
-                if let Some((author, prompt_hash, prompt)) = authorship_log.get_line_attribution(
+                // Lines followed across a rename, move or copy are attested under the
+                // path they had in the introducing commit
+                let attested_path = hunk.orig_file_path.as_deref().unwrap_or(file_path);
+                let mut attribution = authorship_log.get_line_attribution(
                     repo,
-                    file_path,
+                    attested_path,
                     orig_line_num,
                     &mut foreign_prompts_cache,
-                ) {
+                );
+                if let Some(shares) = authorship_log
+                    .metadata
+                    .line_shares
+                    .get(attested_path)
+                    .and_then(|lines| lines.get(&orig_line_num))
+                {
+                    let recorded = attribution
+                        .as_ref()
+                        .and_then(|(_, hash, _)| hash.as_deref());
+                    let picked = options.dominant_author.pick(recorded, shares);
+                    if picked != recorded {
+                        attribution = picked.and_then(|hash| {
+                            let prompt = authorship_log.metadata.prompts.get(hash)?;
+                            let author = Author {
+                                username: prompt.agent_id.tool.clone(),
+                                email: String::new(),
+                            };
+                            Some((author, Some(hash.to_string()), Some(prompt.clone())))
+                        });
+                    }
+                }
+
+                if let Some((author, prompt_hash, prompt)) = attribution {
                     // If this line is AI-assisted, display the tool name; otherwise the human username
                     if let Some(prompt_record) = prompt {
                         let prompt_hash = prompt_hash.unwrap();
@@ -1105,6 +1641,403 @@ fn output_json_format(
     Ok(())
 }
 
+/// Where the AI attribution for a blamed line came from
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+enum BlameLineSource {
+    /// The authorship note attached to the commit that introduced the line
+    AuthorshipNote,
+    /// The working log of uncommitted checkpoints
+    WorkingLog,
+}
+
+/// Sidecar written by `--compat strict`: the AI data that strict mode keeps out of stdout.
+#[derive(Debug, Serialize)]
+struct CompatSidecar<'a> {
+    version: u32,
+    file: &'a str,
+    lines: &'a [BlameLineRecord],
+}
+
+/// Default sidecar location for `--compat strict`, inside the repository's git-ai storage.
+pub fn default_sidecar_path(repo: &Repository, file_path: &str) -> std::path::PathBuf {
+    repo.storage
+        .repo_path
+        .join("ai")
+        .join("blame")
+        .join(format!("{}.json", file_path))
+}
+
+fn write_compat_sidecar(
+    repo: &Repository,
+    file_path: &str,
+    records: &[BlameLineRecord],
+    options: &GitAiBlameOptions,
+) -> Result<(), GitAiError> {
+    let path = match &options.sidecar_path {
+        Some(path) => std::path::PathBuf::from(path),
+        None => default_sidecar_path(repo, file_path),
+    };
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    let sidecar = CompatSidecar {
+        version: 1,
+        file: file_path,
+        lines: records,
+    };
+    let json = serde_json::to_string_pretty(&sidecar)
+        .map_err(|e| GitAiError::Generic(format!("Failed to serialize blame sidecar: {}", e)))?;
+    fs::write(&path, json)?;
+    Ok(())
+}
+
+/// One line of `--format=json|jsonl` blame output
+#[derive(Debug, Serialize)]
+struct BlameLineRecord {
+    line: u32,
+    commit_sha: String,
+    human_author: String,
+    ai_tool: Option<String>,
+    ai_model: Option<String>,
+    prompt_id: Option<String>,
+    session_id: Option<String>,
+    source: Option<BlameLineSource>,
+    /// Percent of the line written by AI (0 or 100 unless a human and AI shared it)
+    ai_share: u8,
+    content: String,
+}
+
+/// The checkpoints of the working log on HEAD and the latest entry among them for
+/// `file_path`, if that entry was checkpointed with exactly `file_content`.
+pub(crate) fn latest_working_log_entry(
+    repo: &Repository,
+    file_path: &str,
+    file_content: &str,
+) -> Option<(Vec<Checkpoint>, WorkingLogEntry)> {
+    let base_commit = repo
+        .head()
+        .and_then(|head| head.target())
+        .unwrap_or_else(|_| "initial".to_string());
+    let checkpoints = repo
+        .storage
+        .working_log_for_base_commit(&base_commit)
+        .read_all_checkpoints()
+        .ok()?;
+
+    let entry = checkpoints
+        .iter()
+        .rev()
+        .find_map(|checkpoint| checkpoint.entries.iter().find(|e| e.file == file_path))?
+        .clone();
+    let content_sha = format!("{:x}", Sha256::digest(file_content.as_bytes()));
+    if entry.blob_sha != content_sha {
+        return None;
+    }
+    Some((checkpoints, entry))
+}
+
+/// Attributions for uncommitted lines, read from the latest checkpoint of `file_path`.
+/// Only used when the checkpointed content still matches the blamed content, since the
+/// checkpoint's line numbers are meaningless otherwise.
+#[allow(clippy::type_complexity)]
+pub(crate) fn working_log_line_attributions(
+    repo: &Repository,
+    file_path: &str,
+    file_content: &str,
+) -> Option<(HashMap<u32, String>, HashMap<String, PromptRecord>)> {
+    let (checkpoints, entry) = latest_working_log_entry(repo, file_path, file_content)?;
+
+    let mut prompts: HashMap<String, PromptRecord> = HashMap::new();
+    for checkpoint in &checkpoints {
+        if let Some(agent_id) = &checkpoint.agent_id {
+            prompts.insert(
+                generate_short_hash(&agent_id.id, &agent_id.tool),
+                PromptRecord {
+                    agent_id: agent_id.clone(),
+                    human_author: Some(checkpoint.author.clone()),
+                    messages: Vec::new(),
+                    total_additions: 0,
+                    total_deletions: 0,
+                    accepted_lines: 0,
+                    overriden_lines: 0,
+                    messages_url: None,
+                },
+            );
+        }
+    }
+
+    let mut lines: HashMap<u32, String> = HashMap::new();
+    for attr in &entry.line_attributions {
+        if !prompts.contains_key(&attr.author_id) {
+            continue;
+        }
+        for line in attr.start_line..=attr.end_line {
+            lines.insert(line, attr.author_id.clone());
+        }
+    }
+
+    Some((lines, prompts))
+}
+
+/// Attribute the uncommitted lines of a `--contents` buffer from the working log.
+///
+/// The working log describes the file as last checkpointed on disk, so buffer lines are
+/// mapped onto the on-disk file first: lines unchanged from disk take the AI attribution
+/// recorded for them, and lines that only exist in the buffer stay with the current user.
+fn overlay_buffer_working_log(
+    repo: &Repository,
+    file_path: &str,
+    buffer_content: &str,
+    hunks: &[BlameHunk],
+    line_authors: &mut HashMap<u32, String>,
+    prompt_records: &mut HashMap<String, PromptRecord>,
+    options: &GitAiBlameOptions,
+) {
+    let uncommitted_lines: Vec<u32> = hunks
+        .iter()
+        .filter(|hunk| is_uncommitted_sha(&hunk.commit_sha))
+        .flat_map(|hunk| hunk.range.0..=hunk.range.1)
+        .collect();
+    if uncommitted_lines.is_empty() {
+        return;
+    }
+
+    let Some(disk_content) = repo
+        .workdir()
+        .ok()
+        .and_then(|workdir| fs::read(workdir.join(file_path)).ok())
+        .map(|bytes| String::from_utf8_lossy(&bytes).into_owned())
+    else {
+        return;
+    };
+    let Some((working_log_lines, working_log_prompts)) =
+        working_log_line_attributions(repo, file_path, &disk_content)
+    else {
+        return;
+    };
+
+    // Map each buffer line to the on-disk line it is unchanged from
+    let mut buffer_to_disk: HashMap<u32, u32> = HashMap::new();
+    let (mut disk_line, mut buffer_line) = (1u32, 1u32);
+    for change in compute_line_changes(&disk_content, buffer_content) {
+        match change.tag() {
+            LineChangeTag::Equal => {
+                buffer_to_disk.insert(buffer_line, disk_line);
+                disk_line += 1;
+                buffer_line += 1;
+            }
+            LineChangeTag::Delete => disk_line += 1,
+            LineChangeTag::Insert => buffer_line += 1,
+        }
+    }
+
+    for line in uncommitted_lines {
+        let Some(prompt_hash) = buffer_to_disk
+            .get(&line)
+            .and_then(|disk_line| working_log_lines.get(disk_line))
+        else {
+            continue;
+        };
+        let Some(record) = working_log_prompts.get(prompt_hash) else {
+            continue;
+        };
+        let author = if options.use_prompt_hashes_as_names {
+            prompt_hash.clone()
+        } else {
+            record.agent_id.tool.clone()
+        };
+        line_authors.insert(line, author);
+        prompt_records.insert(prompt_hash.clone(), record.clone());
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+fn output_line_records_format(
+    repo: &Repository,
+    hunks: &[BlameHunk],
+    line_authors: &HashMap<u32, String>,
+    prompt_records: &HashMap<String, PromptRecord>,
+    file_path: &str,
+    file_content: &str,
+    line_ranges: &[(u32, u32)],
+    format: BlameLineFormat,
+) -> Result<(), GitAiError> {
+    let records = build_line_records(
+        repo,
+        hunks,
+        line_authors,
+        prompt_records,
+        file_path,
+        file_content,
+        line_ranges,
+    );
+
+    let stdout = io::stdout();
+    let mut handle = stdout.lock();
+    match format {
+        BlameLineFormat::Json => {
+            let json_str = serde_json::to_string_pretty(&records).map_err(|e| {
+                GitAiError::Generic(format!("Failed to serialize JSON output: {}", e))
+            })?;
+            writeln!(handle, "{}", json_str)?;
+        }
+        BlameLineFormat::Jsonl => {
+            for record in &records {
+                let json_str = serde_json::to_string(record).map_err(|e| {
+                    GitAiError::Generic(format!("Failed to serialize JSON output: {}", e))
+                })?;
+                writeln!(handle, "{}", json_str)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Resolve each blamed line to its commit, human author and (if any) AI prompt.
+fn build_line_records(
+    repo: &Repository,
+    hunks: &[BlameHunk],
+    line_authors: &HashMap<u32, String>,
+    prompt_records: &HashMap<String, PromptRecord>,
+    file_path: &str,
+    file_content: &str,
+    line_ranges: &[(u32, u32)],
+) -> Vec<BlameLineRecord> {
+    let lines: Vec<&str> = file_content.lines().collect();
+
+    let mut line_to_hunk: HashMap<u32, &BlameHunk> = HashMap::new();
+    for hunk in hunks {
+        for line_num in hunk.range.0..=hunk.range.1 {
+            line_to_hunk.insert(line_num, hunk);
+        }
+    }
+
+    let has_uncommitted_lines = hunks.iter().any(|h| is_uncommitted_sha(&h.commit_sha));
+    let (working_log_lines, working_log_prompts) = if has_uncommitted_lines {
+        working_log_line_attributions(repo, file_path, file_content).unwrap_or_default()
+    } else {
+        (HashMap::new(), HashMap::new())
+    };
+
+    let mut note_cache: HashMap<String, Option<AuthorshipLog>> = HashMap::new();
+    let mut records = Vec::new();
+    for (start_line, end_line) in line_ranges {
+        for line_num in *start_line..=*end_line {
+            let Some(hunk) = line_to_hunk.get(&line_num) else {
+                continue;
+            };
+            let content = lines
+                .get((line_num - 1) as usize)
+                .map(|l| l.to_string())
+                .unwrap_or_default();
+
+            let (prompt, source, shares) = if is_uncommitted_sha(&hunk.commit_sha) {
+                let prompt = working_log_lines.get(&line_num).and_then(|hash| {
+                    working_log_prompts
+                        .get(hash)
+                        .map(|record| (hash.clone(), record))
+                });
+                let source = prompt.as_ref().map(|_| BlameLineSource::WorkingLog);
+                (prompt, source, None)
+            } else {
+                let note = note_cache
+                    .entry(hunk.commit_sha.clone())
+                    .or_insert_with(|| {
+                        get_reference_as_authorship_log_v3(repo, &hunk.commit_sha).ok()
+                    });
+                let prompt = line_authors.get(&line_num).and_then(|hash| {
+                    prompt_records
+                        .get(hash)
+                        .map(|record| (hash.clone(), record))
+                });
+                let source = note.is_some().then_some(BlameLineSource::AuthorshipNote);
+                let orig_line = hunk.orig_range.0 + (line_num - hunk.range.0);
+                let shares = note.as_ref().and_then(|log| {
+                    log.metadata
+                        .line_shares
+                        .get(hunk.orig_file_path.as_deref().unwrap_or(file_path))
+                        .and_then(|lines| lines.get(&orig_line))
+                        .map(|shares| shares.values().map(|share| *share as u32).sum::<u32>())
+                });
+                (prompt, source, shares)
+            };
+            let ai_share = match (shares, &prompt) {
+                (Some(total), _) => total.min(100) as u8,
+                (None, Some(_)) => 100,
+                (None, None) => 0,
+            };
+
+            let human_author = prompt
+                .as_ref()
+                .and_then(|(_, record)| record.human_author.clone())
+                .unwrap_or_else(|| hunk.original_author.clone());
+
+            records.push(BlameLineRecord {
+                line: line_num,
+                commit_sha: hunk.commit_sha.clone(),
+                human_author,
+                ai_tool: prompt.as_ref().map(|(_, r)| r.agent_id.tool.clone()),
+                ai_model: prompt.as_ref().map(|(_, r)| r.agent_id.model.clone()),
+                prompt_id: prompt.as_ref().map(|(hash, _)| hash.clone()),
+                session_id: prompt.as_ref().map(|(_, r)| r.agent_id.id.clone()),
+                source,
+                ai_share,
+                content,
+            });
+        }
+    }
+    records
+}
+
+/// Copy the provenance string of `line` (commit, author or tool, model, prompt) and its
+/// permalink for `--copy-provenance` to the clipboard, and print it as well.
+fn output_line_provenance(repo: &Repository, file_path: &str, line: u32) -> Result<(), GitAiError> {
+    let text = format_line_provenance(repo, file_path, line)?;
+    println!("{}", text);
+    match crate::utils::copy_to_clipboard(&text) {
+        Ok(()) => eprintln!("Copied provenance to clipboard"),
+        Err(e) => eprintln!("Warning: could not copy to clipboard: {}", e),
+    }
+    Ok(())
+}
+
+fn format_line_provenance(
+    repo: &Repository,
+    file_path: &str,
+    line: u32,
+) -> Result<String, GitAiError> {
+    let provenance = line_provenance(repo, file_path, line)?;
+    let mut text = provenance.to_line();
+    if let Some(commit) = &provenance.commit
+        && let Some(remote_url) = default_remote_url(repo)
+    {
+        text.push('\n');
+        text.push_str(&crate::repo_url::line_permalink(
+            &remote_url,
+            commit,
+            file_path,
+            line,
+        ));
+    }
+    Ok(text)
+}
+
+/// Canonical HTTPS URL of the default remote, if there is one.
+fn default_remote_url(repo: &Repository) -> Option<String> {
+    let remote_name = repo.get_default_remote().ok().flatten()?;
+    let (_, url) = repo
+        .remotes_with_urls()
+        .ok()?
+        .into_iter()
+        .find(|(name, _)| name == &remote_name)?;
+    crate::repo_url::normalize_repo_url(&url).ok()
+}
+
+pub(crate) fn is_uncommitted_sha(sha: &str) -> bool {
+    sha.chars().all(|c| c == '0')
+}
+
 fn output_porcelain_format(
     repo: &Repository,
     _line_authors: &HashMap<u32, String>,
@@ -1149,7 +2082,8 @@ fn output_porcelain_format(
                 let committer_time = hunk.committer_time;
                 let committer_tz = &hunk.committer_tz;
                 let boundary = hunk.is_boundary;
-                let filename = file_path;
+                let filename = hunk.orig_file_path.as_deref().unwrap_or(file_path);
+                let orig_line_num = hunk.orig_range.0 + (line_num - hunk.range.0);
 
                 // Retrieve the commit summary directly from the commit object
                 let commit = repo.find_commit(commit_sha.clone())?;
@@ -1162,14 +2096,14 @@ fn output_porcelain_format(
                         println!(
                             "{} {} {} {}",
                             commit_sha,
-                            line_num,
+                            orig_line_num,
                             line_num,
                             hunk.range.1 - hunk.range.0 + 1
                         );
                         last_hunk_id = Some(hunk_id);
                     } else {
                         // Subsequent lines: 3-field header
-                        println!("{} {} {}", commit_sha, line_num, line_num);
+                        println!("{} {} {}", commit_sha, orig_line_num, line_num);
                     }
                     println!("author {}", author_name);
                     println!("author-mail <{}>", author_email);
@@ -1191,7 +2125,7 @@ fn output_porcelain_format(
                         println!(
                             "{} {} {} {}",
                             commit_sha,
-                            line_num,
+                            orig_line_num,
                             line_num,
                             hunk.range.1 - hunk.range.0 + 1
                         );
@@ -1212,7 +2146,7 @@ fn output_porcelain_format(
                         last_hunk_id = Some(hunk_id);
                     } else {
                         // For subsequent lines, print only the header and content (no metadata block)
-                        println!("{} {} {}", commit_sha, line_num, line_num);
+                        println!("{} {} {}", commit_sha, orig_line_num, line_num);
                         println!("\t{}", line_content);
                     }
                 }
@@ -1283,7 +2217,10 @@ fn output_incremental_format(
                     if hunk.is_boundary {
                         println!("boundary");
                     }
-                    println!("filename {}", file_path);
+                    println!(
+                        "filename {}",
+                        hunk.orig_file_path.as_deref().unwrap_or(file_path)
+                    );
                     last_hunk_id = Some(hunk_id);
                 }
                 // For incremental, no content lines (no \tLine)
@@ -1309,6 +2246,51 @@ fn output_incremental_format(
     Ok(())
 }
 
+/// List the lines deleted from `file_path` in its history (newest commit first) with the
+/// human or AI tool that deleted each one.
+fn output_deleted_lines(
+    repo: &Repository,
+    file_path: &str,
+    options: &GitAiBlameOptions,
+) -> Result<(), GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.push("log".to_string());
+    args.push("--format=%H".to_string());
+    args.push(
+        options
+            .newest_commit
+            .clone()
+            .unwrap_or_else(|| "HEAD".to_string()),
+    );
+    args.push("--".to_string());
+    args.push(file_path.to_string());
+    let commits = String::from_utf8(exec_git(&args)?.stdout)?;
+
+    let pathspecs: HashSet<String> = HashSet::from([file_path.to_string()]);
+    let mut output = String::new();
+    for sha in commits.lines().filter(|l| !l.is_empty()) {
+        let deletions = commit_deletions(repo, sha, Some(&pathspecs))?;
+        for line in &deletions.lines {
+            output.push_str(&format!(
+                "{} ({} {:>5}) - {}\n",
+                &sha[..sha.len().min(8)],
+                deletions.deleted_by(line),
+                line.line,
+                line.content
+            ));
+        }
+    }
+
+    if output.is_empty() {
+        println!("\nNo deleted lines");
+    } else {
+        println!("\nDeleted lines:");
+        print!("{}", output);
+    }
+    Ok(())
+}
+
+#[allow(clippy::too_many_arguments)]
 fn output_default_format(
     repo: &Repository,
     line_authors: &HashMap<u32, String>,
@@ -1316,6 +2298,7 @@ fn output_default_format(
     file_path: &str,
     lines: &[&str],
     line_ranges: &[(u32, u32)],
+    filtered_out: &HashSet<u32>,
     options: &GitAiBlameOptions,
 ) -> Result<(), GitAiError> {
     let mut output = String::new();
@@ -1335,35 +2318,114 @@ fn output_default_format(
         }
     }
 
-    // Calculate the maximum line number width for proper padding
-    let max_line_num = lines.len() as u32;
+    // Match git's column widths: line numbers are as wide as the largest line shown,
+    // and original line numbers (-n) as wide as the largest original line shown
+    let max_line_num = line_ranges.iter().map(|(_, end)| *end).max().unwrap_or(0);
     let line_num_width = max_line_num.to_string().len();
+    let max_orig_line_num = line_to_hunk
+        .iter()
+        .map(|(line_num, hunk)| hunk.orig_range.0 + (line_num - hunk.range.0))
+        .max()
+        .unwrap_or(0);
+    let orig_line_num_width = max_orig_line_num.to_string().len();
+
+    // Like git, show the filename column whenever a line was followed in from another file,
+    // padded to the longest name shown
+    let show_name = options.show_name
+        || line_to_hunk
+            .values()
+            .any(|hunk| hunk.orig_file_path.is_some());
+    let name_width = line_to_hunk
+        .values()
+        .map(|hunk| {
+            hunk.orig_file_path
+                .as_deref()
+                .unwrap_or(file_path)
+                .chars()
+                .count()
+        })
+        .max()
+        .unwrap_or(0);
+
+    // Match git's hash width: one more than the abbreviation so boundary commits
+    // can be prefixed with '^' without shifting the columns
+    let hash_len = if options.long_rev {
+        40
+    } else if let Some(abbrev) = options.abbrev {
+        if abbrev == 0 || abbrev >= 40 {
+            40
+        } else {
+            abbrev as usize + 1
+        }
+    } else {
+        let shas: Vec<&str> = line_to_hunk
+            .values()
+            .map(|hunk| hunk.commit_sha.as_str())
+            .collect();
+        auto_abbrev_len(repo, &shas) + 1
+    };
 
-    // Calculate the maximum author name width for proper padding
-    let mut max_author_width = 0;
-    for (start_line, end_line) in line_ranges {
-        let h = repo.blame_hunks(file_path, *start_line, *end_line, &no_split_options)?;
-        for hunk in h {
-            let author = line_authors
-                .get(&hunk.range.0)
-                .unwrap_or(&hunk.original_author);
-            let author_display = if options.suppress_author {
-                "".to_string()
-            } else if options.show_prompt && prompt_records.contains_key(author) {
-                let prompt = &prompt_records[author];
-                let short_hash = &author[..7.min(author.len())];
-                format!("{} [{}]", prompt.agent_id.tool, short_hash)
-            } else if options.show_email {
-                format!("{} <{}>", author, &hunk.author_email)
+    let author_display_for = |author: &String, hunk: &BlameHunk| -> String {
+        if options.suppress_author {
+            "".to_string()
+        } else if options.show_prompt_id && prompt_records.contains_key(author) {
+            format!("{} [{}]", prompt_records[author].agent_id.tool, author)
+        } else if options.show_prompt && prompt_records.contains_key(author) {
+            let prompt = &prompt_records[author];
+            let short_hash = &author[..7.min(author.len())];
+            format!("{} [{}]", prompt.agent_id.tool, short_hash)
+        } else if options.show_email {
+            if *author == hunk.original_author {
+                format!("<{}>", &hunk.author_email)
             } else {
-                author.to_string()
-            };
-            max_author_width = max_author_width.max(author_display.len());
+                format!("{} <{}>", author, &hunk.author_email)
+            }
+        } else {
+            author.to_string()
         }
+    };
+
+    // Calculate the maximum author name width for proper padding (in characters, like git)
+    let mut max_author_width = 0;
+    for (line_num, hunk) in &line_to_hunk {
+        if filtered_out.contains(line_num) && !options.dim_filtered {
+            continue;
+        }
+        let author = line_authors.get(line_num).unwrap_or(&hunk.original_author);
+        max_author_width = max_author_width.max(author_display_for(author, hunk).chars().count());
     }
 
+    let colorize = options.color.enabled();
+    let theme = crate::config::Config::get().blame_theme();
+    // Filtered-out lines are dimmed as a whole, so their columns stay unstyled
+    let dim_filtered = options.dim_filtered && colorize;
+    let paint = |spec: &str, text: String, colorize: bool| -> String {
+        if colorize {
+            crate::config::BlameTheme::paint(spec, &text)
+        } else {
+            text
+        }
+    };
+
+    // Runs of filtered-out lines collapse to a single gap marker, unless nothing matched
+    let any_matched = line_ranges
+        .iter()
+        .any(|(start, end)| (*start..=*end).any(|line_num| !filtered_out.contains(&line_num)));
+    let mut in_gap = false;
+
     for (start_line, end_line) in line_ranges {
         for line_num in *start_line..=*end_line {
+            let filtered = filtered_out.contains(&line_num);
+            if filtered && !dim_filtered {
+                if any_matched && !in_gap {
+                    output.push_str("...\n");
+                }
+                in_gap = true;
+                continue;
+            }
+            in_gap = false;
+            let line_colorize = colorize && !filtered;
+
             let line_index = (line_num - 1) as usize;
             let line_content = if line_index < lines.len() {
                 lines[line_index]
@@ -1372,113 +2434,73 @@ fn output_default_format(
             };
 
             if let Some(hunk) = line_to_hunk.get(&line_num) {
-                // Determine hash length - match git blame default (7 chars)
-                let hash_len = if options.long_rev {
-                    40 // Full hash for long revision
-                } else if let Some(abbrev) = options.abbrev {
-                    abbrev as usize
-                } else {
-                    7 // Default 7 chars
-                };
-                let sha = if hash_len < hunk.commit_sha.len() {
-                    &hunk.commit_sha[..hash_len]
-                } else {
-                    &hunk.commit_sha
-                };
-
-                // Add boundary marker if this is a boundary commit
-                let boundary_marker = if hunk.is_boundary && options.blank_boundary {
-                    "^"
-                } else {
-                    ""
-                };
+                // Format the hash column exactly like git: boundary commits either get a '^'
+                // in place of the last hash character, or are blanked out entirely with -b
+                let sha_len = hash_len.min(hunk.commit_sha.len());
                 let full_sha = if hunk.is_boundary && options.blank_boundary {
-                    format!("{}{}", boundary_marker, "        ") // Empty hash for boundary
+                    " ".repeat(sha_len)
+                } else if hunk.is_boundary {
+                    format!("^{}", &hunk.commit_sha[..sha_len.saturating_sub(1)])
                 } else {
-                    format!("{}{}", boundary_marker, sha)
+                    hunk.commit_sha[..sha_len].to_string()
                 };
-
-                // Get the author for this line (AI authorship or original)
-                let author = line_authors.get(&line_num).unwrap_or(&hunk.original_author);
-
-                // Format date according to options
-                let date_str = format_blame_date(hunk.author_time, &hunk.author_tz, options);
-
-                // Handle different output formats based on flags
-                let author_display = if options.suppress_author {
-                    "".to_string()
-                } else if options.show_prompt && prompt_records.contains_key(author) {
-                    let prompt = &prompt_records[author];
-                    let short_hash = &author[..7.min(author.len())];
-                    format!("{} [{}]", prompt.agent_id.tool, short_hash)
-                } else if options.show_email {
-                    format!("{} <{}>", author, &hunk.author_email)
+                let sha_spec = if hunk.is_boundary {
+                    &theme.boundary
                 } else {
-                    author.to_string()
+                    &theme.commit
                 };
+                let mut line_out = paint(sha_spec, full_sha, line_colorize);
 
-                // Pad author name to consistent width
-                let padded_author = if max_author_width > 0 {
-                    format!("{:<width$}", author_display, width = max_author_width)
-                } else {
-                    author_display
-                };
+                if show_name {
+                    line_out.push_str(&format!(
+                        " {:<width$}",
+                        hunk.orig_file_path.as_deref().unwrap_or(file_path),
+                        width = name_width
+                    ));
+                }
 
-                let _filename_display = if options.show_name {
-                    format!("{} ", file_path)
-                } else {
-                    "".to_string()
-                };
+                if options.show_number {
+                    let orig_line_num = hunk.orig_range.0 + (line_num - hunk.range.0);
+                    line_out.push_str(&format!(
+                        " {:>width$}",
+                        orig_line_num,
+                        width = orig_line_num_width
+                    ));
+                }
 
-                let _number_display = if options.show_number {
-                    format!("{} ", line_num)
-                } else {
-                    "".to_string()
-                };
+                if !options.suppress_author {
+                    // Get the author for this line (AI authorship or original)
+                    let author = line_authors.get(&line_num).unwrap_or(&hunk.original_author);
+                    let author_spec =
+                        if prompt_records.contains_key(author) || *author != hunk.original_author {
+                            &theme.ai_author
+                        } else {
+                            &theme.human_author
+                        };
+                    let padded_author = format!(
+                        "{:<width$}",
+                        author_display_for(author, hunk),
+                        width = max_author_width
+                    );
+                    let date_str = format_blame_date(hunk.author_time, &hunk.author_tz, options);
+                    line_out.push_str(&format!(
+                        " ({} {}",
+                        paint(author_spec, padded_author, line_colorize),
+                        paint(&theme.date, format!("{:>10}", date_str), line_colorize)
+                    ));
+                }
 
-                // Format exactly like git blame: sha (author date line) code
-                if options.suppress_author {
-                    // Suppress author format: sha line_number) code
-                    output.push_str(&format!("{} {}) {}\n", full_sha, line_num, line_content));
-                } else {
-                    // Normal format: sha (author date line) code
-                    if options.show_name {
-                        // Show filename format: sha filename (author date line) code
-                        output.push_str(&format!(
-                            "{} {} ({} {} {:>width$}) {}\n",
-                            full_sha,
-                            file_path,
-                            padded_author,
-                            date_str,
-                            line_num,
-                            line_content,
-                            width = line_num_width
-                        ));
-                    } else if options.show_number {
-                        // Show number format: sha line_number (author date line) code (matches git's -n output)
-                        output.push_str(&format!(
-                            "{} {} ({} {} {:>width$}) {}\n",
-                            full_sha,
-                            line_num,
-                            padded_author,
-                            date_str,
-                            line_num,
-                            line_content,
-                            width = line_num_width
-                        ));
-                    } else {
-                        // Normal format: sha (author date line) code
-                        output.push_str(&format!(
-                            "{} ({} {} {:>width$}) {}\n",
-                            full_sha,
-                            padded_author,
-                            date_str,
-                            line_num,
-                            line_content,
-                            width = line_num_width
-                        ));
-                    }
+                let line_num_str = format!("{:>width$}", line_num, width = line_num_width);
+                line_out.push_str(&format!(
+                    " {}) ",
+                    paint(&theme.line_number, line_num_str, line_colorize)
+                ));
+                line_out.push_str(line_content);
+                if filtered {
+                    line_out = paint("dim", line_out, true);
                 }
+                line_out.push('\n');
+                output.push_str(&line_out);
             } else {
                 // Fallback for lines without blame info
                 output.push_str(&format!(
@@ -1541,11 +2563,13 @@ fn output_default_format(
     if pager == "cat" || pager.is_empty() {
         print!("{}", output);
     } else if io::stdout().is_terminal() {
-        // Try to use the specified pager
-        match std::process::Command::new(&pager)
-            .stdin(std::process::Stdio::piped())
-            .spawn()
-        {
+        // Try to use the specified pager. Like git, default LESS to FRX so that
+        // colored output is rendered rather than shown as raw escape codes.
+        let mut pager_cmd = std::process::Command::new(&pager);
+        if std::env::var_os("LESS").is_none() {
+            pager_cmd.env("LESS", "FRX");
+        }
+        match pager_cmd.stdin(std::process::Stdio::piped()).spawn() {
             Ok(mut child) => {
                 if let Some(stdin) = child.stdin.as_mut() {
                     if stdin.write_all(output.as_bytes()).is_ok() {
@@ -1572,6 +2596,11 @@ fn output_default_format(
 }
 
 fn format_blame_date(author_time: i64, author_tz: &str, options: &GitAiBlameOptions) -> String {
+    // -t prints the raw epoch and timezone, like git, whatever the date format
+    if options.raw_timestamp {
+        return format!("{} {}", author_time, author_tz);
+    }
+
     let dt = DateTime::from_timestamp(author_time, 0)
         .unwrap_or_else(|| DateTime::from_timestamp(0, 0).unwrap());
 
@@ -1602,8 +2631,58 @@ fn format_blame_date(author_time: i64, author_tz: &str, options: &GitAiBlameOpti
     }
 }
 
+/// Length of the shortest unambiguous abbreviation covering all `shas`, as git blame
+/// computes it (respects `core.abbrev`, minimum 7).
+fn auto_abbrev_len(repo: &Repository, shas: &[&str]) -> usize {
+    let mut unique: Vec<&str> = shas
+        .iter()
+        .copied()
+        .filter(|sha| !is_uncommitted_sha(sha))
+        .collect();
+    unique.sort_unstable();
+    unique.dedup();
+    if unique.is_empty() {
+        return 7;
+    }
+
+    let mut args = repo.global_args_for_exec();
+    args.push("rev-parse".to_string());
+    args.push("--short".to_string());
+    args.extend(unique.iter().map(|sha| sha.to_string()));
+
+    match exec_git(&args) {
+        Ok(output) => String::from_utf8_lossy(&output.stdout)
+            .lines()
+            .map(|line| line.trim().len())
+            .max()
+            .unwrap_or(7)
+            .max(7),
+        Err(_) => 7,
+    }
+}
+
+/// Flags only git-ai blame understands; git blame rejects them.
+const GIT_AI_ONLY_BLAME_FLAGS: &[&str] = &[
+    "--json",
+    "--format",
+    "--mark-unknown",
+    "--show-prompt",
+    "--show-prompt-id",
+    "--show-deleted",
+    "--only-ai",
+    "--only-human",
+    "--tool",
+    "--model",
+    "--dim-filtered",
+    "--dominant-author",
+    "--copy-provenance",
+];
+
 pub fn parse_blame_args(args: &[String]) -> Result<(String, GitAiBlameOptions), GitAiError> {
-    let mut options = GitAiBlameOptions::default();
+    let mut options = GitAiBlameOptions {
+        dominant_author: crate::config::Config::get().blame_dominant_author(),
+        ..Default::default()
+    };
     let mut file_path = None;
     let mut i = 0;
 
@@ -1685,6 +2764,17 @@ pub fn parse_blame_args(args: &[String]) -> Result<(String, GitAiBlameOptions),
                 }
                 i += 2;
             }
+            arg if arg.starts_with("--abbrev=") => {
+                match arg["--abbrev=".len()..].parse::<u32>() {
+                    Ok(n) => options.abbrev = Some(n),
+                    Err(_) => {
+                        return Err(GitAiError::Generic(
+                            "Invalid number for --abbrev".to_string(),
+                        ));
+                    }
+                }
+                i += 1;
+            }
 
             // Boundary options
             "-b" => {
@@ -1724,6 +2814,19 @@ pub fn parse_blame_args(args: &[String]) -> Result<(String, GitAiBlameOptions),
                 }
             }
 
+            arg if (arg.starts_with("-M") || arg.starts_with("-C"))
+                && arg.len() > 2
+                && arg[2..].chars().all(|c| c.is_ascii_digit()) =>
+            {
+                if arg.starts_with("-M") {
+                    options.detect_moves = true;
+                } else {
+                    options.detect_copies = (options.detect_copies + 1).min(3);
+                }
+                options.move_threshold = arg[2..].parse::<u32>().ok();
+                i += 1;
+            }
+
             // Ignore options
             "--ignore-rev" => {
                 if i + 1 >= args.len() {
@@ -1758,6 +2861,24 @@ pub fn parse_blame_args(args: &[String]) -> Result<(String, GitAiBlameOptions),
                 options.color_by_age = true;
                 i += 1;
             }
+            "--color" => {
+                options.color = BlameColorMode::Always;
+                i += 1;
+            }
+            "--no-color" => {
+                options.color = BlameColorMode::Never;
+                i += 1;
+            }
+            arg if arg.starts_with("--color=") => {
+                let value = arg.strip_prefix("--color=").unwrap_or_default();
+                options.color = BlameColorMode::from_arg(value).ok_or_else(|| {
+                    GitAiError::Generic(format!(
+                        "Invalid --color value '{}'. Expected always, never, or auto",
+                        value
+                    ))
+                })?;
+                i += 1;
+            }
 
             // Progress options
             "--progress" => {
@@ -1853,6 +2974,68 @@ pub fn parse_blame_args(args: &[String]) -> Result<(String, GitAiBlameOptions),
                 i += 1;
             }
 
+            arg if arg == "--format" || arg.starts_with("--format=") => {
+                let value = if let Some(value) = arg.strip_prefix("--format=") {
+                    i += 1;
+                    value.to_string()
+                } else {
+                    if i + 1 >= args.len() {
+                        return Err(GitAiError::Generic(
+                            "Missing argument for --format".to_string(),
+                        ));
+                    }
+                    i += 2;
+                    args[i - 1].clone()
+                };
+                options.format = Some(match value.as_str() {
+                    "json" => BlameLineFormat::Json,
+                    "jsonl" => BlameLineFormat::Jsonl,
+                    other => {
+                        return Err(GitAiError::Generic(format!(
+                            "Invalid value for --format: {} (expected json or jsonl)",
+                            other
+                        )));
+                    }
+                });
+            }
+
+            // Byte-for-byte git blame output, AI data in a sidecar file
+            arg if arg == "--compat" || arg.starts_with("--compat=") => {
+                let value = if let Some(value) = arg.strip_prefix("--compat=") {
+                    i += 1;
+                    value.to_string()
+                } else {
+                    if i + 1 >= args.len() {
+                        return Err(GitAiError::Generic(
+                            "Missing argument for --compat".to_string(),
+                        ));
+                    }
+                    i += 2;
+                    args[i - 1].clone()
+                };
+                if value != "strict" {
+                    return Err(GitAiError::Generic(format!(
+                        "Invalid value for --compat: {} (expected strict)",
+                        value
+                    )));
+                }
+                options.compat_strict = true;
+            }
+            arg if arg == "--sidecar" || arg.starts_with("--sidecar=") => {
+                if let Some(value) = arg.strip_prefix("--sidecar=") {
+                    options.sidecar_path = Some(value.to_string());
+                    i += 1;
+                } else {
+                    if i + 1 >= args.len() {
+                        return Err(GitAiError::Generic(
+                            "Missing argument for --sidecar".to_string(),
+                        ));
+                    }
+                    options.sidecar_path = Some(args[i + 1].clone());
+                    i += 2;
+                }
+            }
+
             // Mark unknown authorship
             "--mark-unknown" => {
                 options.mark_unknown = true;
@@ -1864,6 +3047,111 @@ pub fn parse_blame_args(args: &[String]) -> Result<(String, GitAiBlameOptions),
                 options.show_prompt = true;
                 i += 1;
             }
+            "--show-prompt-id" => {
+                options.show_prompt_id = true;
+                i += 1;
+            }
+            "--show-deleted" => {
+                options.show_deleted = true;
+                i += 1;
+            }
+
+            // Restrict output to AI or human lines
+            "--only-ai" => {
+                restrict_to_ai_lines(&mut options, None, None)?;
+                i += 1;
+            }
+            "--only-human" => {
+                if matches!(options.line_filter, Some(BlameLineFilter::Ai { .. })) {
+                    return Err(GitAiError::Generic(
+                        "--only-human cannot be combined with --only-ai, --tool or --model"
+                            .to_string(),
+                    ));
+                }
+                options.line_filter = Some(BlameLineFilter::Human);
+                i += 1;
+            }
+            arg if arg == "--tool"
+                || arg.starts_with("--tool=")
+                || arg == "--model"
+                || arg.starts_with("--model=") =>
+            {
+                let (flag, value) = match arg.split_once('=') {
+                    Some((flag, value)) => {
+                        i += 1;
+                        (flag, value.to_string())
+                    }
+                    None => {
+                        if i + 1 >= args.len() {
+                            return Err(GitAiError::Generic(format!(
+                                "Missing argument for {}",
+                                arg
+                            )));
+                        }
+                        i += 2;
+                        (arg, args[i - 1].clone())
+                    }
+                };
+                if flag == "--tool" {
+                    restrict_to_ai_lines(&mut options, Some(value), None)?;
+                } else {
+                    restrict_to_ai_lines(&mut options, None, Some(value))?;
+                }
+            }
+            "--dim-filtered" => {
+                options.dim_filtered = true;
+                i += 1;
+            }
+            arg if arg == "--dominant-author" || arg.starts_with("--dominant-author=") => {
+                let value = if let Some(value) = arg.strip_prefix("--dominant-author=") {
+                    i += 1;
+                    value.to_string()
+                } else {
+                    if i + 1 >= args.len() {
+                        return Err(GitAiError::Generic(
+                            "Missing argument for --dominant-author".to_string(),
+                        ));
+                    }
+                    i += 2;
+                    args[i - 1].clone()
+                };
+                options.dominant_author = value.parse::<DominantAuthor>().map_err(|_| {
+                    GitAiError::Generic(format!(
+                        "Invalid value for --dominant-author: {} (expected latest, majority or any-ai)",
+                        value
+                    ))
+                })?;
+            }
+
+            // Copy provenance for <file>:<line> to the clipboard
+            "--copy-provenance" => {
+                if i + 1 >= args.len() {
+                    return Err(GitAiError::Generic(
+                        "Missing argument for --copy-provenance (expected <file>:<line>)"
+                            .to_string(),
+                    ));
+                }
+                let target = &args[i + 1];
+                let (path, line) = target
+                    .rsplit_once(':')
+                    .and_then(|(path, line)| Some((path, line.parse::<u32>().ok()?)))
+                    .filter(|(path, line)| !path.is_empty() && *line > 0)
+                    .ok_or_else(|| {
+                        GitAiError::Generic(format!(
+                            "Invalid --copy-provenance target '{}' (expected <file>:<line>)",
+                            target
+                        ))
+                    })?;
+                if file_path.is_some() {
+                    return Err(GitAiError::Generic(
+                        "Multiple file paths specified".to_string(),
+                    ));
+                }
+                file_path = Some(path.to_string());
+                options.line_ranges = vec![(line, line)];
+                options.copy_provenance = true;
+                i += 2;
+            }
 
             // File path (non-option argument)
             arg if !arg.starts_with('-') => {
@@ -1887,9 +3175,44 @@ pub fn parse_blame_args(args: &[String]) -> Result<(String, GitAiBlameOptions),
     let file_path =
         file_path.ok_or_else(|| GitAiError::Generic("No file path specified".to_string()))?;
 
+    // Strict mode hands the arguments to git blame, which doesn't know git-ai's own flags
+    if options.compat_strict
+        && let Some(flag) = args.iter().find_map(|arg| {
+            let flag = arg.split_once('=').map_or(arg.as_str(), |(flag, _)| flag);
+            GIT_AI_ONLY_BLAME_FLAGS.contains(&flag).then_some(flag)
+        })
+    {
+        return Err(GitAiError::Generic(format!(
+            "{} is a git-ai option and can't be used with --compat strict, which runs git blame",
+            flag
+        )));
+    }
+
     Ok((file_path, options))
 }
 
+/// Narrow `options` to AI lines, keeping any tool or model restriction already given.
+fn restrict_to_ai_lines(
+    options: &mut GitAiBlameOptions,
+    tool: Option<String>,
+    model: Option<String>,
+) -> Result<(), GitAiError> {
+    let (current_tool, current_model) = match options.line_filter.take() {
+        None => (None, None),
+        Some(BlameLineFilter::Ai { tool, model }) => (tool, model),
+        Some(BlameLineFilter::Human) => {
+            return Err(GitAiError::Generic(
+                "--only-human cannot be combined with --only-ai, --tool or --model".to_string(),
+            ));
+        }
+    };
+    options.line_filter = Some(BlameLineFilter::Ai {
+        tool: tool.or(current_tool),
+        model: model.or(current_model),
+    });
+    Ok(())
+}
+
 fn parse_line_range(range_str: &str) -> Option<(u32, u32)> {
     if let Some(dash_pos) = range_str.find(',') {
         let start_str = &range_str[..dash_pos];
@@ -4,16 +4,22 @@ use crate::authorship::attribution_tracker::{
 use crate::authorship::authorship_log::PromptRecord;
 use crate::authorship::authorship_log_serialization::generate_short_hash;
 use crate::authorship::ignore::{
-    IgnoreMatcher, build_ignore_matcher, effective_ignore_patterns, should_ignore_file_with_matcher,
+    IgnoreMatcher, build_ignore_matcher, effective_ignore_patterns, is_lfs_pointer,
+    load_lfs_patterns_from_root_gitattributes, should_ignore_file_with_matcher,
+};
+use crate::authorship::imara_diff_utils::{
+    LineChangeTag, compute_line_changes_with, removed_lines_with,
 };
-use crate::authorship::imara_diff_utils::{LineChangeTag, compute_line_changes};
 use crate::authorship::working_log::CheckpointKind;
-use crate::authorship::working_log::{Checkpoint, WorkingLogEntry};
+use crate::authorship::working_log::{
+    Checkpoint, WorkingLogEntry, next_attribution_ts, next_checkpoint_sequence,
+};
 use crate::commands::blame::{GitAiBlameOptions, OLDEST_AI_BLAME_DATE};
 use crate::commands::checkpoint_agent::agent_presets::AgentRunResult;
 use crate::config::Config;
 use crate::error::GitAiError;
-use crate::git::repo_storage::{PersistedWorkingLog, RepoStorage};
+use crate::git::attribution_events::AttributionEvent;
+use crate::git::repo_storage::PersistedWorkingLog;
 use crate::git::repository::Repository;
 use crate::git::status::{EntryKind, StatusCode};
 use crate::utils::{debug_log, normalize_to_posix};
@@ -21,7 +27,9 @@ use futures::stream::{self, StreamExt};
 use sha2::{Digest, Sha256};
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
-use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use std::time::Instant;
+#[cfg(not(any(test, feature = "test-support")))]
+use std::time::{SystemTime, UNIX_EPOCH};
 
 /// Per-file line statistics (in-memory only, not persisted)
 #[derive(Debug, Clone, Default)]
@@ -142,12 +150,17 @@ pub fn run(
         ));
     }
 
+    if repo.is_read_only() {
+        debug_log("Repository is read-only (ai.readOnly), skipping checkpoint");
+        return Ok((0, 0, 0));
+    }
+
     let ignore_patterns = effective_ignore_patterns(repo, &[], &[]);
     let ignore_matcher = build_ignore_matcher(&ignore_patterns);
 
     // Initialize the new storage system
     let storage_start = Instant::now();
-    let repo_storage = RepoStorage::for_repo_path(repo.path(), &repo.workdir()?);
+    let repo_storage = &repo.storage;
     let mut working_log = repo_storage.working_log_for_base_commit(&base_commit);
     debug_log(&format!(
         "[BENCHMARK] Storage initialization took {:?}",
@@ -184,12 +197,6 @@ pub fn run(
         working_log.set_dirty_files(Some(dirty_files));
     }
 
-    // Get the current timestamp in milliseconds since the Unix epoch
-    let ts = SystemTime::now()
-        .duration_since(UNIX_EPOCH)
-        .unwrap_or_default()
-        .as_millis();
-
     // Extract edited filepaths from agent_run_result if available
     // For human checkpoints, use will_edit_filepaths to narrow git status scope
     // For AI checkpoints, use edited_filepaths
@@ -289,6 +296,10 @@ pub fn run(
         read_checkpoints_start.elapsed()
     ));
 
+    // Timestamp in milliseconds since the Unix epoch, kept ahead of earlier checkpoints'
+    // attributions in case they were written on a machine with a faster clock
+    let ts = next_attribution_ts(&checkpoints);
+
     if show_working_log {
         if checkpoints.is_empty() {
             eprintln!("No working log entries found.");
@@ -395,6 +406,8 @@ pub fn run(
             entries.clone(),
         );
 
+        checkpoint.sequence = next_checkpoint_sequence(&checkpoints);
+
         // Aggregate line stats from in-memory stats (computed during entry creation)
         checkpoint.line_stats = compute_line_stats(&file_stats)?;
 
@@ -441,6 +454,17 @@ pub fn run(
             "[BENCHMARK] Appending checkpoint to working log took {:?}",
             append_start.elapsed()
         ));
+        repo_storage.record_attribution_event(AttributionEvent::checkpoint_recorded(
+            &base_commit,
+            &checkpoint.kind.to_str(),
+            checkpoint.agent_id.as_ref().map(|a| a.tool.clone()),
+            checkpoint
+                .entries
+                .iter()
+                .map(|e| e.file.clone())
+                .chain(checkpoint.whole_file_assets.iter().cloned())
+                .collect(),
+        ));
         checkpoints.push(checkpoint.clone());
 
         // Build common attributes once (reused for all events)
@@ -531,6 +555,97 @@ pub fn run(
     Ok((entries.len(), files.len(), checkpoints.len()))
 }
 
+/// Attribute binary and Git LFS files edited by an agent as whole-file AI-generated assets.
+///
+/// Line-level attribution is meaningless for images, models and other binary assets, so
+/// instead of diffing them this appends an entry-less checkpoint whose `whole_file_assets`
+/// attributes each file as a whole to the agent. Text files are left to the regular
+/// line-level checkpoint. Returns the number of assets recorded.
+pub fn record_whole_file_assets(
+    repo: &Repository,
+    author: &str,
+    agent_run_result: &AgentRunResult,
+) -> Result<usize, GitAiError> {
+    if agent_run_result.checkpoint_kind == CheckpointKind::Human {
+        return Ok(0);
+    }
+    let Some(edited_filepaths) = agent_run_result.edited_filepaths.as_ref() else {
+        return Ok(0);
+    };
+
+    let base_commit = match repo.head() {
+        Ok(head) => match head.target() {
+            Ok(oid) => oid,
+            Err(_) => "initial".to_string(),
+        },
+        Err(_) => "initial".to_string(),
+    };
+
+    let repo_workdir = repo.workdir()?;
+    let repo_storage = &repo.storage;
+    let working_log = repo_storage.working_log_for_base_commit(&base_commit);
+    let ignore_matcher = build_ignore_matcher(&effective_ignore_patterns(repo, &[], &[]));
+    let lfs_matcher = build_ignore_matcher(&load_lfs_patterns_from_root_gitattributes(repo));
+
+    let mut assets: Vec<String> = Vec::new();
+    let mut hasher = Sha256::new();
+    for path in edited_filepaths {
+        let path_buf = if std::path::Path::new(path).is_absolute() {
+            std::path::PathBuf::from(path)
+        } else {
+            repo_workdir.join(path)
+        };
+        if !repo.path_is_in_workdir(&path_buf) || !path_buf.is_file() {
+            continue;
+        }
+
+        let relative_path =
+            normalize_to_posix(&working_log.to_repo_relative_path(&path_buf.to_string_lossy()));
+        if assets.contains(&relative_path) {
+            continue;
+        }
+        // LFS-tracked files are assets by definition; never read their (possibly huge) content.
+        let is_lfs_tracked = should_ignore_file_with_matcher(&relative_path, &lfs_matcher);
+        if !is_lfs_tracked
+            && (should_ignore_file_with_matcher(&relative_path, &ignore_matcher)
+                || is_text_file(&working_log, &relative_path))
+        {
+            continue;
+        }
+
+        // Fingerprint by path and size so large assets are never read into memory
+        let size = std::fs::metadata(&path_buf)?.len();
+        hasher.update(relative_path.as_bytes());
+        hasher.update(size.to_le_bytes());
+        assets.push(relative_path);
+    }
+
+    if assets.is_empty() {
+        return Ok(0);
+    }
+    assets.sort();
+
+    let mut checkpoint = Checkpoint::new(
+        agent_run_result.checkpoint_kind,
+        format!("{:x}", hasher.finalize()),
+        author.to_string(),
+        Vec::new(),
+    );
+    checkpoint.transcript = Some(agent_run_result.transcript.clone().unwrap_or_default());
+    checkpoint.agent_id = Some(agent_run_result.agent_id.clone());
+    checkpoint.agent_metadata = agent_run_result.agent_metadata.clone();
+    checkpoint.whole_file_assets = assets;
+
+    working_log.append_checkpoint(&checkpoint)?;
+    debug_log(&format!(
+        "Recorded {} whole-file asset(s): {:?}",
+        checkpoint.whole_file_assets.len(),
+        checkpoint.whole_file_assets
+    ));
+
+    Ok(checkpoint.whole_file_assets.len())
+}
+
 // Gets tracked changes AND
 fn get_status_of_files(
     repo: &Repository,
@@ -1324,12 +1439,21 @@ fn make_entry_for_file(
         stats_start.elapsed()
     ));
 
-    let entry = WorkingLogEntry::new(
+    let mut entry = WorkingLogEntry::new(
         file_path.to_string(),
         blob_sha.to_string(),
         new_attributions,
         line_attributions,
     );
+    // Lines nobody claims at commit time were deleted by the human, so only AI deletions
+    // need recording
+    if author_id != CheckpointKind::Human.to_str() {
+        entry.deleted_lines =
+            removed_lines_with(previous_content, content, Config::get().diff_algorithm())
+                .into_iter()
+                .map(|(_, content)| content)
+                .collect();
+    }
 
     Ok((entry, line_stats))
 }
@@ -1338,8 +1462,12 @@ fn make_entry_for_file(
 fn compute_file_line_stats(previous_content: &str, current_content: &str) -> FileLineStats {
     let mut stats = FileLineStats::default();
 
-    // Use imara_diff to count line changes (matches git's diff algorithm)
-    let changes = compute_line_changes(previous_content, current_content);
+    // Use imara_diff to count line changes with the same algorithm attribution uses
+    let changes = compute_line_changes_with(
+        previous_content,
+        current_content,
+        Config::get().diff_algorithm(),
+    );
     for change in changes {
         match change.tag() {
             LineChangeTag::Insert => {
@@ -1407,7 +1535,7 @@ fn is_text_file(working_log: &PersistedWorkingLog, path: &str) -> bool {
 
     working_log
         .read_current_file_content(&normalized_path)
-        .map(|content| !content.chars().any(|c| c == '\0'))
+        .map(|content| !content.chars().any(|c| c == '\0') && !is_lfs_pointer(content.as_bytes()))
         .unwrap_or(false)
 }
 
@@ -1436,7 +1564,7 @@ fn is_text_file_in_head(repo: &Repository, path: &str) -> bool {
                     Ok(content) => content,
                     Err(_) => return false,
                 };
-                !blob_content.contains(&0)
+                !blob_content.contains(&0) && !is_lfs_pointer(&blob_content)
             } else {
                 false
             }
@@ -1,3 +1,4 @@
 pub mod agent_presets;
 pub mod agent_v1_preset;
 pub mod opencode_preset;
+pub mod structured_input;
@@ -0,0 +1,423 @@
+//! Structured checkpoint input for agent frameworks without a built-in preset.
+//!
+//! `git-ai checkpoint --stdin-json` reads one JSON payload from stdin:
+//!
+//! ```json
+//! {
+//!   "tool": "my-agent",
+//!   "model": "gpt-5",
+//!   "session_id": "run-42",
+//!   "repo_working_dir": "/path/to/repo",
+//!   "files": [
+//!     { "path": "src/lib.rs" },
+//!     { "path": "src/api.rs", "lines": [[10, 24], [40, 40]] },
+//!     { "path": "src/gen.rs", "content": "fn generated() {}\n" }
+//!   ],
+//!   "messages": [
+//!     { "type": "user", "text": "Add an API client" },
+//!     { "type": "assistant", "text": "Added ApiClient in src/api.rs" }
+//!   ],
+//!   "metadata": { "run_url": "https://ci.example.com/runs/42" }
+//! }
+//! ```
+//!
+//! - `tool` and `session_id` are required; lines from one session share a prompt record.
+//! - `repo_working_dir` defaults to the current directory.
+//! - A file with neither `lines` nor `content` is attributed like the editor presets do:
+//!   every line changed since the last checkpoint belongs to the agent.
+//! - `lines` lists 1-based inclusive ranges of the file's current content that the agent
+//!   wrote. Other changed lines are recorded as human.
+//! - `content` is the file as the agent wrote it, for agents that edit in memory.
+//! - `messages` use the transcript message format (`user`, `assistant`, `thinking`,
+//!   `plan`, `tool_use`).
+//!
+//! Rust agents linking the crate build the same payload with [`CheckpointBuilder`], which is
+//! also what `--stdin-json` records through.
+
+use crate::authorship::transcript::{AiTranscript, Message};
+use crate::authorship::working_log::{AgentId, CheckpointKind};
+use crate::commands::checkpoint::run as run_checkpoint;
+use crate::commands::checkpoint_agent::agent_presets::AgentRunResult;
+use crate::config::Config;
+use crate::error::GitAiError;
+use crate::git::repository::{Repository, find_repository_in_path};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::path::Path;
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CheckpointPayload {
+    pub tool: String,
+    #[serde(default = "default_model")]
+    pub model: String,
+    pub session_id: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repo_working_dir: Option<String>,
+    pub files: Vec<EditedFile>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub messages: Vec<Message>,
+    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
+    pub metadata: HashMap<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct EditedFile {
+    pub path: String,
+    /// 1-based inclusive line ranges the agent wrote
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub lines: Option<Vec<(u32, u32)>>,
+    /// Full content as written by the agent, used instead of the file on disk
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub content: Option<String>,
+}
+
+fn default_model() -> String {
+    "unknown".to_string()
+}
+
+impl CheckpointPayload {
+    fn validate(&self) -> Result<(), GitAiError> {
+        if self.tool.trim().is_empty() {
+            return Err(GitAiError::PresetError(
+                "\"tool\" must not be empty".to_string(),
+            ));
+        }
+        if self.session_id.trim().is_empty() {
+            return Err(GitAiError::PresetError(
+                "\"session_id\" must not be empty".to_string(),
+            ));
+        }
+        if self.files.is_empty() {
+            return Err(GitAiError::PresetError(
+                "\"files\" must list at least one file".to_string(),
+            ));
+        }
+        for file in &self.files {
+            for (start, end) in file.lines.iter().flatten() {
+                if *start == 0 || start > end {
+                    return Err(GitAiError::PresetError(format!(
+                        "Invalid line range [{}, {}] for {}",
+                        start, end, file.path
+                    )));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    /// Record the payload into the working log of the repository it points at. Returns the
+    /// number of files checkpointed.
+    pub fn record(&self) -> Result<usize, GitAiError> {
+        self.validate()?;
+        let working_dir = match &self.repo_working_dir {
+            Some(dir) => dir.clone(),
+            None => std::env::current_dir()?.to_string_lossy().to_string(),
+        };
+        let repo = find_repository_in_path(&working_dir)?;
+        if !Config::get().is_allowed_repository(&Some(repo.clone())) {
+            return Ok(0);
+        }
+        let author = match repo.config_get_str("user.name") {
+            Ok(Some(name)) if !name.trim().is_empty() => name,
+            _ => "unknown".to_string(),
+        };
+        let workdir = repo.workdir()?;
+
+        let mut paths = Vec::new();
+        let mut agent_contents = HashMap::new();
+        let mut human_contents = HashMap::new();
+        for file in &self.files {
+            let path = repo_relative_path(&workdir, &working_dir, &file.path);
+            let content = match (&file.content, &file.lines) {
+                (Some(content), _) => Some(content.clone()),
+                (None, Some(_)) => Some(read_file(&repo, &path)),
+                (None, None) => None,
+            };
+            if let (Some(content), Some(ranges)) = (&content, &file.lines) {
+                human_contents.insert(path.clone(), without_lines(content, ranges));
+            }
+            if let Some(content) = content {
+                agent_contents.insert(path.clone(), content);
+            }
+            paths.push(path);
+        }
+
+        // For line ranges, first record the file without the agent's lines as human-written,
+        // so the agent checkpoint below only claims the ranges it reported
+        if !human_contents.is_empty() {
+            let human_paths: Vec<String> = human_contents.keys().cloned().collect();
+            run_checkpoint(
+                &repo,
+                &author,
+                CheckpointKind::Human,
+                false,
+                false,
+                true,
+                Some(AgentRunResult {
+                    agent_id: AgentId {
+                        tool: "human".to_string(),
+                        id: "human".to_string(),
+                        model: "human".to_string(),
+                    },
+                    agent_metadata: None,
+                    checkpoint_kind: CheckpointKind::Human,
+                    transcript: None,
+                    repo_working_dir: Some(workdir.to_string_lossy().to_string()),
+                    edited_filepaths: None,
+                    will_edit_filepaths: Some(human_paths),
+                    dirty_files: Some(human_contents),
+                }),
+                false,
+            )?;
+        }
+
+        let (_, files_edited, _) = run_checkpoint(
+            &repo,
+            &author,
+            CheckpointKind::AiAgent,
+            false,
+            false,
+            true,
+            Some(self.agent_run_result(&workdir, paths, agent_contents)),
+            false,
+        )?;
+        Ok(files_edited)
+    }
+
+    fn agent_run_result(
+        &self,
+        workdir: &Path,
+        paths: Vec<String>,
+        contents: HashMap<String, String>,
+    ) -> AgentRunResult {
+        AgentRunResult {
+            agent_id: AgentId {
+                tool: self.tool.clone(),
+                id: self.session_id.clone(),
+                model: self.model.clone(),
+            },
+            agent_metadata: if self.metadata.is_empty() {
+                None
+            } else {
+                Some(self.metadata.clone())
+            },
+            checkpoint_kind: CheckpointKind::AiAgent,
+            transcript: Some(AiTranscript {
+                messages: self.messages.clone(),
+            }),
+            repo_working_dir: Some(workdir.to_string_lossy().to_string()),
+            edited_filepaths: Some(paths),
+            will_edit_filepaths: None,
+            dirty_files: if contents.is_empty() {
+                None
+            } else {
+                Some(contents)
+            },
+        }
+    }
+}
+
+/// Builds a [`CheckpointPayload`] for Rust agents that link git-ai instead of shelling out
+/// to `git-ai checkpoint --stdin-json`.
+///
+/// ```ignore
+/// use git_ai::commands::checkpoint_agent::structured_input::CheckpointBuilder;
+///
+/// CheckpointBuilder::new("my-agent", "run-42")
+///     .model("gpt-5")
+///     .edited_lines("src/api.rs", [(10, 24)])
+///     .record()
+///     .expect("checkpoint failed");
+/// ```
+#[derive(Debug, Clone)]
+pub struct CheckpointBuilder {
+    payload: CheckpointPayload,
+}
+
+#[allow(dead_code)]
+impl CheckpointBuilder {
+    pub fn new(tool: impl Into<String>, session_id: impl Into<String>) -> Self {
+        Self {
+            payload: CheckpointPayload {
+                tool: tool.into(),
+                model: default_model(),
+                session_id: session_id.into(),
+                repo_working_dir: None,
+                files: Vec::new(),
+                messages: Vec::new(),
+                metadata: HashMap::new(),
+            },
+        }
+    }
+
+    /// Start from a `--stdin-json` payload.
+    pub fn from_json(json: &str) -> Result<Self, GitAiError> {
+        let payload = serde_json::from_str(json)
+            .map_err(|e| GitAiError::PresetError(format!("Invalid checkpoint JSON: {}", e)))?;
+        Ok(Self { payload })
+    }
+
+    pub fn model(mut self, model: impl Into<String>) -> Self {
+        self.payload.model = model.into();
+        self
+    }
+
+    pub fn repo_working_dir(mut self, dir: impl Into<String>) -> Self {
+        self.payload.repo_working_dir = Some(dir.into());
+        self
+    }
+
+    /// Attribute every line of `path` changed since the last checkpoint to the agent.
+    pub fn edited_file(mut self, path: impl Into<String>) -> Self {
+        self.payload.files.push(EditedFile {
+            path: path.into(),
+            lines: None,
+            content: None,
+        });
+        self
+    }
+
+    /// Attribute only these 1-based inclusive line ranges of `path` to the agent.
+    pub fn edited_lines(
+        mut self,
+        path: impl Into<String>,
+        ranges: impl IntoIterator<Item = (u32, u32)>,
+    ) -> Self {
+        self.payload.files.push(EditedFile {
+            path: path.into(),
+            lines: Some(ranges.into_iter().collect()),
+            content: None,
+        });
+        self
+    }
+
+    /// Attribute `content`, as written by the agent, to it.
+    pub fn file_content(mut self, path: impl Into<String>, content: impl Into<String>) -> Self {
+        self.payload.files.push(EditedFile {
+            path: path.into(),
+            lines: None,
+            content: Some(content.into()),
+        });
+        self
+    }
+
+    pub fn message(mut self, message: Message) -> Self {
+        self.payload.messages.push(message);
+        self
+    }
+
+    pub fn metadata(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.payload.metadata.insert(key.into(), value.into());
+        self
+    }
+
+    /// The validated payload.
+    pub fn build(self) -> Result<CheckpointPayload, GitAiError> {
+        self.payload.validate()?;
+        Ok(self.payload)
+    }
+
+    /// Record the checkpoint. Returns the number of files checkpointed.
+    pub fn record(self) -> Result<usize, GitAiError> {
+        self.build()?.record()
+    }
+}
+
+fn repo_relative_path(workdir: &Path, working_dir: &str, path: &str) -> String {
+    let absolute = if Path::new(path).is_absolute() {
+        Path::new(path).to_path_buf()
+    } else {
+        Path::new(working_dir).join(path)
+    };
+    match absolute.strip_prefix(workdir) {
+        Ok(relative) => crate::utils::normalize_to_posix(&relative.to_string_lossy()),
+        Err(_) => path.to_string(),
+    }
+}
+
+fn read_file(repo: &Repository, path: &str) -> String {
+    repo.workdir()
+        .ok()
+        .and_then(|workdir| std::fs::read_to_string(workdir.join(path)).ok())
+        .unwrap_or_default()
+}
+
+/// `content` with the lines in `ranges` (1-based, inclusive) removed.
+fn without_lines(content: &str, ranges: &[(u32, u32)]) -> String {
+    content
+        .split_inclusive('\n')
+        .enumerate()
+        .filter(|(index, _)| {
+            let line = *index as u32 + 1;
+            !ranges
+                .iter()
+                .any(|(start, end)| line >= *start && line <= *end)
+        })
+        .map(|(_, line)| line)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_without_lines_removes_inclusive_ranges() {
+        let content = "a\nb\nc\nd\ne";
+        assert_eq!(without_lines(content, &[(2, 3)]), "a\nd\ne");
+        assert_eq!(without_lines(content, &[(1, 1), (5, 5)]), "b\nc\nd\n");
+        assert_eq!(without_lines(content, &[]), content);
+    }
+
+    #[test]
+    fn test_builder_round_trips_through_json() {
+        let payload = CheckpointBuilder::new("my-agent", "run-1")
+            .model("gpt-5")
+            .edited_file("src/lib.rs")
+            .edited_lines("src/api.rs", [(3, 4)])
+            .message(Message::user("Add an API".to_string(), None))
+            .metadata("run_url", "https://example.com")
+            .build()
+            .unwrap();
+
+        let json = serde_json::to_string(&payload).unwrap();
+        assert_eq!(
+            CheckpointBuilder::from_json(&json)
+                .unwrap()
+                .build()
+                .unwrap(),
+            payload
+        );
+    }
+
+    #[test]
+    fn test_from_json_rejects_invalid_payloads() {
+        let missing_files = r#"{"tool":"a","session_id":"s","files":[]}"#;
+        assert!(
+            CheckpointBuilder::from_json(missing_files)
+                .unwrap()
+                .build()
+                .is_err()
+        );
+
+        let bad_range = r#"{"tool":"a","session_id":"s","files":[{"path":"x","lines":[[5,2]]}]}"#;
+        assert!(
+            CheckpointBuilder::from_json(bad_range)
+                .unwrap()
+                .build()
+                .is_err()
+        );
+
+        assert!(CheckpointBuilder::new("a", "s").build().is_err());
+
+        let minimal = r#"{"tool":"a","session_id":"s","files":[{"path":"x"}]}"#;
+        assert_eq!(
+            CheckpointBuilder::from_json(minimal)
+                .unwrap()
+                .build()
+                .unwrap()
+                .model,
+            "unknown"
+        );
+    }
+}
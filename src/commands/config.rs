@@ -1,6 +1,9 @@
 use dirs;
 use serde_json::Value;
 
+use crate::authorship::identity::IdentityCategory;
+use crate::authorship::imara_diff_utils::DiffAlgorithm;
+use crate::authorship::line_shares::DominantAuthor;
 use crate::git::repository::find_repository_in_path;
 
 /// Determines the type of pattern value provided
@@ -103,13 +106,39 @@ fn print_config_help() {
     eprintln!("  telemetry_enterprise_dsn     Enterprise telemetry DSN");
     eprintln!("  disable_version_checks       Disable version checks (bool)");
     eprintln!("  disable_auto_updates         Disable auto updates (bool)");
+    eprintln!("  disable_notes_push           Don't push authorship notes on git push (bool)");
+    eprintln!(
+        "  stale_checkpoint_hours       Warn about checkpoints older than a file's edits (hours, 0 = off)"
+    );
+    eprintln!("  exclude_stale_checkpoints    Drop stale checkpoints at commit time (bool)");
     eprintln!("  update_channel               Update channel (latest/next)");
+    eprintln!(
+        "  diff_algorithm               Attribution diff algorithm (myers/histogram/patience)"
+    );
     eprintln!("  feature_flags                Feature flags (object)");
     eprintln!("  api_key                      API key for X-API-Key header");
     eprintln!("  prompt_storage               Prompt storage mode (default/notes/local)");
     eprintln!("  include_prompts_in_repositories  Repos to include for prompt storage (array)");
     eprintln!("  default_prompt_storage       Fallback storage mode for non-included repos");
     eprintln!("  quiet                        Suppress chart output after commits (bool)");
+    eprintln!(
+        "  blame_theme                  Colors for blame output (object, e.g. blame_theme.ai_author)"
+    );
+    eprintln!(
+        "  blame_dominant_author        Blame author of lines written by both human and AI (latest/majority/any-ai)"
+    );
+    eprintln!(
+        "  blame_overhead_budget        Max p95 blame overhead over git blame for `bench blame` (percent)"
+    );
+    eprintln!(
+        "  reassign_threshold           Reassign AI lines to the human at commit once they rewrote this much (percent)"
+    );
+    eprintln!(
+        "  model_aliases                Model name -> canonical name for stats (object, e.g. model_aliases.my-gpt)"
+    );
+    eprintln!(
+        "  identity_categories          Commit identity pattern -> bot/generator/ai/human for stats (object)"
+    );
     eprintln!();
     eprintln!("Repository Patterns:");
     eprintln!("  For exclude/allow/exclude_prompts_in_repositories, you can provide:");
@@ -267,6 +296,18 @@ fn show_all_config() -> Result<(), String> {
         "disable_auto_updates".to_string(),
         Value::Bool(runtime_config.auto_updates_disabled()),
     );
+    effective_config.insert(
+        "disable_notes_push".to_string(),
+        Value::Bool(runtime_config.notes_push_disabled()),
+    );
+    effective_config.insert(
+        "stale_checkpoint_hours".to_string(),
+        Value::from(runtime_config.stale_checkpoint_hours()),
+    );
+    effective_config.insert(
+        "exclude_stale_checkpoints".to_string(),
+        Value::Bool(runtime_config.exclude_stale_checkpoints()),
+    );
 
     // Optional strings
     if let Some(ref dsn) = file_config.telemetry_enterprise_dsn {
@@ -281,6 +322,11 @@ fn show_all_config() -> Result<(), String> {
         Value::String(runtime_config.update_channel().as_str().to_string()),
     );
 
+    effective_config.insert(
+        "diff_algorithm".to_string(),
+        Value::String(runtime_config.diff_algorithm().as_str().to_string()),
+    );
+
     effective_config.insert(
         "prompt_storage".to_string(),
         Value::String(runtime_config.prompt_storage().to_string()),
@@ -304,6 +350,38 @@ fn show_all_config() -> Result<(), String> {
 
     effective_config.insert("quiet".to_string(), Value::Bool(runtime_config.is_quiet()));
 
+    // Blame theme - show effective colors with defaults applied
+    let theme_value = serde_json::to_value(runtime_config.blame_theme())
+        .unwrap_or_else(|_| Value::Object(serde_json::Map::new()));
+    effective_config.insert("blame_theme".to_string(), theme_value);
+
+    effective_config.insert(
+        "blame_dominant_author".to_string(),
+        Value::String(runtime_config.blame_dominant_author().as_str().to_string()),
+    );
+
+    effective_config.insert(
+        "blame_overhead_budget".to_string(),
+        Value::from(runtime_config.blame_overhead_budget()),
+    );
+
+    effective_config.insert(
+        "reassign_threshold".to_string(),
+        Value::from(runtime_config.reassign_threshold()),
+    );
+
+    effective_config.insert(
+        "model_aliases".to_string(),
+        serde_json::to_value(runtime_config.model_aliases())
+            .unwrap_or_else(|_| Value::Object(serde_json::Map::new())),
+    );
+
+    effective_config.insert(
+        "identity_categories".to_string(),
+        serde_json::to_value(runtime_config.identity_categories())
+            .unwrap_or_else(|_| Value::Object(serde_json::Map::new())),
+    );
+
     // Feature flags - show effective flags with defaults applied
     let flags_value = serde_json::to_value(runtime_config.get_feature_flags())
         .unwrap_or_else(|_| Value::Object(serde_json::Map::new()));
@@ -363,7 +441,11 @@ fn get_config_value(key: &str) -> Result<(), String> {
             }
             "disable_version_checks" => Value::Bool(runtime_config.version_checks_disabled()),
             "disable_auto_updates" => Value::Bool(runtime_config.auto_updates_disabled()),
+            "disable_notes_push" => Value::Bool(runtime_config.notes_push_disabled()),
+            "stale_checkpoint_hours" => Value::from(runtime_config.stale_checkpoint_hours()),
+            "exclude_stale_checkpoints" => Value::Bool(runtime_config.exclude_stale_checkpoints()),
             "update_channel" => Value::String(runtime_config.update_channel().as_str().to_string()),
+            "diff_algorithm" => Value::String(runtime_config.diff_algorithm().as_str().to_string()),
             "feature_flags" => {
                 // Show effective flags with defaults applied
                 serde_json::to_value(runtime_config.get_feature_flags())
@@ -392,6 +474,17 @@ fn get_config_value(key: &str) -> Result<(), String> {
                 }
             }
             "quiet" => Value::Bool(runtime_config.is_quiet()),
+            "blame_theme" => serde_json::to_value(runtime_config.blame_theme())
+                .unwrap_or_else(|_| Value::Object(serde_json::Map::new())),
+            "blame_dominant_author" => {
+                Value::String(runtime_config.blame_dominant_author().as_str().to_string())
+            }
+            "blame_overhead_budget" => Value::from(runtime_config.blame_overhead_budget()),
+            "reassign_threshold" => Value::from(runtime_config.reassign_threshold()),
+            "model_aliases" => serde_json::to_value(runtime_config.model_aliases())
+                .unwrap_or_else(|_| Value::Object(serde_json::Map::new())),
+            "identity_categories" => serde_json::to_value(runtime_config.identity_categories())
+                .unwrap_or_else(|_| Value::Object(serde_json::Map::new())),
             _ => return Err(format!("Unknown config key: {}", key)),
         };
 
@@ -420,7 +513,42 @@ fn get_config_value(key: &str) -> Result<(), String> {
         return Ok(());
     }
 
-    Err("Nested keys are only supported for feature_flags".to_string())
+    if key_path[0] == "blame_theme" && key_path.len() == 2 {
+        let theme = serde_json::to_value(runtime_config.blame_theme())
+            .unwrap_or_else(|_| Value::Object(serde_json::Map::new()));
+        let value = theme
+            .get(&key_path[1])
+            .ok_or_else(|| format!("Config key not found: {}", key))?;
+        println!("{}", value);
+        return Ok(());
+    }
+
+    if key_path[0] == "model_aliases" {
+        // Model names may contain dots, so everything after the first segment is the name
+        let model = key_path[1..].join(".");
+        let alias = runtime_config
+            .model_aliases()
+            .get(&model)
+            .ok_or_else(|| format!("Config key not found: {}", key))?;
+        println!("{}", Value::String(alias.clone()));
+        return Ok(());
+    }
+
+    if key_path[0] == "identity_categories" {
+        // Patterns may contain dots (emails), so everything after the first segment is the pattern
+        let pattern = key_path[1..].join(".");
+        let category = runtime_config
+            .identity_categories()
+            .get(&pattern)
+            .ok_or_else(|| format!("Config key not found: {}", key))?;
+        println!("{}", Value::String(category.clone()));
+        return Ok(());
+    }
+
+    Err(
+        "Nested keys are only supported for feature_flags, blame_theme, model_aliases and identity_categories"
+            .to_string(),
+    )
 }
 
 fn set_config_value(key: &str, value: &str, add_mode: bool) -> Result<(), String> {
@@ -484,6 +612,27 @@ fn set_config_value(key: &str, value: &str, add_mode: bool) -> Result<(), String
                 crate::config::save_file_config(&file_config)?;
                 eprintln!("[disable_auto_updates]: {}", bool_value);
             }
+            "disable_notes_push" => {
+                let bool_value = parse_bool(value)?;
+                file_config.disable_notes_push = Some(bool_value);
+                crate::config::save_file_config(&file_config)?;
+                eprintln!("[disable_notes_push]: {}", bool_value);
+            }
+            "stale_checkpoint_hours" => {
+                let hours = value.trim().parse::<u64>().map_err(|_| {
+                    "Invalid stale_checkpoint_hours value. Expected a whole number of hours"
+                        .to_string()
+                })?;
+                file_config.stale_checkpoint_hours = Some(hours);
+                crate::config::save_file_config(&file_config)?;
+                eprintln!("[stale_checkpoint_hours]: {}", hours);
+            }
+            "exclude_stale_checkpoints" => {
+                let bool_value = parse_bool(value)?;
+                file_config.exclude_stale_checkpoints = Some(bool_value);
+                crate::config::save_file_config(&file_config)?;
+                eprintln!("[exclude_stale_checkpoints]: {}", bool_value);
+            }
             "update_channel" => {
                 // Validate update channel
                 if value != "latest" && value != "next" {
@@ -495,6 +644,15 @@ fn set_config_value(key: &str, value: &str, add_mode: bool) -> Result<(), String
                 crate::config::save_file_config(&file_config)?;
                 eprintln!("[update_channel]: {}", value);
             }
+            "diff_algorithm" => {
+                let algorithm = value.parse::<DiffAlgorithm>().map_err(|_| {
+                    "Invalid diff_algorithm value. Expected 'myers', 'histogram' or 'patience'"
+                        .to_string()
+                })?;
+                file_config.diff_algorithm = Some(algorithm.as_str().to_string());
+                crate::config::save_file_config(&file_config)?;
+                eprintln!("[diff_algorithm]: {}", algorithm.as_str());
+            }
             "feature_flags" => {
                 if add_mode {
                     return Err("Cannot use --add with feature_flags at top level. Use dot notation: feature_flags.key".to_string());
@@ -553,6 +711,95 @@ fn set_config_value(key: &str, value: &str, add_mode: bool) -> Result<(), String
                 crate::config::save_file_config(&file_config)?;
                 eprintln!("[quiet]: {}", bool_value);
             }
+            "blame_theme" => {
+                if add_mode {
+                    return Err("Cannot use --add with blame_theme at top level. Use dot notation: blame_theme.slot".to_string());
+                }
+                let overrides: std::collections::BTreeMap<String, String> =
+                    serde_json::from_str(value).map_err(|e| {
+                        format!(
+                            "Invalid JSON for blame_theme (expected object of strings): {}",
+                            e
+                        )
+                    })?;
+                let mut theme = crate::config::BlameTheme::default();
+                for (slot, spec) in &overrides {
+                    theme.set(slot, spec)?;
+                }
+                file_config.blame_theme = Some(overrides);
+                crate::config::save_file_config(&file_config)?;
+                eprintln!("[blame_theme]: {}", value);
+            }
+            "blame_dominant_author" => {
+                let rule = value.parse::<DominantAuthor>().map_err(|_| {
+                    "Invalid blame_dominant_author value. Expected 'latest', 'majority' or 'any-ai'"
+                        .to_string()
+                })?;
+                file_config.blame_dominant_author = Some(rule.as_str().to_string());
+                crate::config::save_file_config(&file_config)?;
+                eprintln!("[blame_dominant_author]: {}", rule.as_str());
+            }
+            "blame_overhead_budget" => {
+                let percent = value
+                    .trim()
+                    .trim_end_matches('%')
+                    .parse::<u32>()
+                    .map_err(|_| {
+                        "Invalid blame_overhead_budget value. Expected a whole number of percent"
+                            .to_string()
+                    })?;
+                file_config.blame_overhead_budget = Some(percent);
+                crate::config::save_file_config(&file_config)?;
+                eprintln!("[blame_overhead_budget]: {}", percent);
+            }
+            "reassign_threshold" => {
+                let percent = value
+                    .trim()
+                    .trim_end_matches('%')
+                    .parse::<u32>()
+                    .ok()
+                    .filter(|percent| *percent <= 100)
+                    .ok_or_else(|| {
+                        "Invalid reassign_threshold value. Expected a percent from 0 to 100"
+                            .to_string()
+                    })?;
+                file_config.reassign_threshold = Some(percent);
+                crate::config::save_file_config(&file_config)?;
+                eprintln!("[reassign_threshold]: {}", percent);
+            }
+            "model_aliases" => {
+                if add_mode {
+                    return Err("Cannot use --add with model_aliases at top level. Use dot notation: model_aliases.<model>".to_string());
+                }
+                let aliases: std::collections::BTreeMap<String, String> =
+                    serde_json::from_str(value).map_err(|e| {
+                        format!(
+                            "Invalid JSON for model_aliases (expected object of strings): {}",
+                            e
+                        )
+                    })?;
+                file_config.model_aliases = Some(aliases);
+                crate::config::save_file_config(&file_config)?;
+                eprintln!("[model_aliases]: {}", value);
+            }
+            "identity_categories" => {
+                if add_mode {
+                    return Err("Cannot use --add with identity_categories at top level. Use dot notation: identity_categories.<pattern>".to_string());
+                }
+                let categories: std::collections::BTreeMap<String, String> =
+                    serde_json::from_str(value).map_err(|e| {
+                        format!(
+                            "Invalid JSON for identity_categories (expected object of strings): {}",
+                            e
+                        )
+                    })?;
+                for category in categories.values() {
+                    parse_identity_category(category)?;
+                }
+                file_config.identity_categories = Some(categories);
+                crate::config::save_file_config(&file_config)?;
+                eprintln!("[identity_categories]: {}", value);
+            }
             _ => return Err(format!("Unknown config key: {}", key)),
         }
 
@@ -609,7 +856,42 @@ fn set_config_value(key: &str, value: &str, add_mode: bool) -> Result<(), String
         return Ok(());
     }
 
-    Err("Nested keys are only supported for feature_flags".to_string())
+    if key_path[0] == "blame_theme" && key_path.len() == 2 {
+        crate::config::BlameTheme::default().set(&key_path[1], value)?;
+        file_config
+            .blame_theme
+            .get_or_insert_with(Default::default)
+            .insert(key_path[1].clone(), value.to_string());
+        crate::config::save_file_config(&file_config)?;
+        eprintln!("+ [{}]: {}", key, value);
+        return Ok(());
+    }
+
+    if key_path[0] == "model_aliases" {
+        file_config
+            .model_aliases
+            .get_or_insert_with(Default::default)
+            .insert(key_path[1..].join("."), value.to_string());
+        crate::config::save_file_config(&file_config)?;
+        eprintln!("+ [{}]: {}", key, value);
+        return Ok(());
+    }
+
+    if key_path[0] == "identity_categories" {
+        let category = parse_identity_category(value)?;
+        file_config
+            .identity_categories
+            .get_or_insert_with(Default::default)
+            .insert(key_path[1..].join("."), category.as_str().to_string());
+        crate::config::save_file_config(&file_config)?;
+        eprintln!("+ [{}]: {}", key, category.as_str());
+        return Ok(());
+    }
+
+    Err(
+        "Nested keys are only supported for feature_flags, blame_theme, model_aliases and identity_categories"
+            .to_string(),
+    )
 }
 
 fn unset_config_value(key: &str) -> Result<(), String> {
@@ -675,6 +957,27 @@ fn unset_config_value(key: &str) -> Result<(), String> {
                     eprintln!("- [disable_auto_updates]: {}", v);
                 }
             }
+            "disable_notes_push" => {
+                let old_value = file_config.disable_notes_push.take();
+                crate::config::save_file_config(&file_config)?;
+                if let Some(v) = old_value {
+                    eprintln!("- [disable_notes_push]: {}", v);
+                }
+            }
+            "stale_checkpoint_hours" => {
+                let old_value = file_config.stale_checkpoint_hours.take();
+                crate::config::save_file_config(&file_config)?;
+                if let Some(v) = old_value {
+                    eprintln!("- [stale_checkpoint_hours]: {}", v);
+                }
+            }
+            "exclude_stale_checkpoints" => {
+                let old_value = file_config.exclude_stale_checkpoints.take();
+                crate::config::save_file_config(&file_config)?;
+                if let Some(v) = old_value {
+                    eprintln!("- [exclude_stale_checkpoints]: {}", v);
+                }
+            }
             "update_channel" => {
                 let old_value = file_config.update_channel.take();
                 crate::config::save_file_config(&file_config)?;
@@ -682,6 +985,13 @@ fn unset_config_value(key: &str) -> Result<(), String> {
                     eprintln!("- [update_channel]: {}", v);
                 }
             }
+            "diff_algorithm" => {
+                let old_value = file_config.diff_algorithm.take();
+                crate::config::save_file_config(&file_config)?;
+                if let Some(v) = old_value {
+                    eprintln!("- [diff_algorithm]: {}", v);
+                }
+            }
             "feature_flags" => {
                 let old_value = file_config.feature_flags.take();
                 crate::config::save_file_config(&file_config)?;
@@ -724,6 +1034,48 @@ fn unset_config_value(key: &str) -> Result<(), String> {
                     eprintln!("- [quiet]: {}", v);
                 }
             }
+            "blame_theme" => {
+                let old_value = file_config.blame_theme.take();
+                crate::config::save_file_config(&file_config)?;
+                if let Some(v) = old_value {
+                    eprintln!("- [blame_theme]: {:?}", v);
+                }
+            }
+            "blame_dominant_author" => {
+                let old_value = file_config.blame_dominant_author.take();
+                crate::config::save_file_config(&file_config)?;
+                if let Some(v) = old_value {
+                    eprintln!("- [blame_dominant_author]: {}", v);
+                }
+            }
+            "blame_overhead_budget" => {
+                let old_value = file_config.blame_overhead_budget.take();
+                crate::config::save_file_config(&file_config)?;
+                if let Some(v) = old_value {
+                    eprintln!("- [blame_overhead_budget]: {}", v);
+                }
+            }
+            "reassign_threshold" => {
+                let old_value = file_config.reassign_threshold.take();
+                crate::config::save_file_config(&file_config)?;
+                if let Some(v) = old_value {
+                    eprintln!("- [reassign_threshold]: {}", v);
+                }
+            }
+            "model_aliases" => {
+                let old_value = file_config.model_aliases.take();
+                crate::config::save_file_config(&file_config)?;
+                if let Some(v) = old_value {
+                    eprintln!("- [model_aliases]: {:?}", v);
+                }
+            }
+            "identity_categories" => {
+                let old_value = file_config.identity_categories.take();
+                crate::config::save_file_config(&file_config)?;
+                if let Some(v) = old_value {
+                    eprintln!("- [identity_categories]: {:?}", v);
+                }
+            }
             _ => return Err(format!("Unknown config key: {}", key)),
         }
 
@@ -784,7 +1136,66 @@ fn unset_config_value(key: &str) -> Result<(), String> {
         return Ok(());
     }
 
-    Err("Nested keys are only supported for feature_flags".to_string())
+    if key_path[0] == "blame_theme" && key_path.len() == 2 {
+        let old_value = file_config
+            .blame_theme
+            .as_mut()
+            .and_then(|theme| theme.remove(&key_path[1]))
+            .ok_or_else(|| format!("Config key not found: {}", key))?;
+        if file_config
+            .blame_theme
+            .as_ref()
+            .is_some_and(|theme| theme.is_empty())
+        {
+            file_config.blame_theme = None;
+        }
+        crate::config::save_file_config(&file_config)?;
+        eprintln!("- [{}]: {}", key, old_value);
+        return Ok(());
+    }
+
+    if key_path[0] == "model_aliases" {
+        let model = key_path[1..].join(".");
+        let old_value = file_config
+            .model_aliases
+            .as_mut()
+            .and_then(|aliases| aliases.remove(&model))
+            .ok_or_else(|| format!("Config key not found: {}", key))?;
+        if file_config
+            .model_aliases
+            .as_ref()
+            .is_some_and(|aliases| aliases.is_empty())
+        {
+            file_config.model_aliases = None;
+        }
+        crate::config::save_file_config(&file_config)?;
+        eprintln!("- [{}]: {}", key, old_value);
+        return Ok(());
+    }
+
+    if key_path[0] == "identity_categories" {
+        let pattern = key_path[1..].join(".");
+        let old_value = file_config
+            .identity_categories
+            .as_mut()
+            .and_then(|categories| categories.remove(&pattern))
+            .ok_or_else(|| format!("Config key not found: {}", key))?;
+        if file_config
+            .identity_categories
+            .as_ref()
+            .is_some_and(|categories| categories.is_empty())
+        {
+            file_config.identity_categories = None;
+        }
+        crate::config::save_file_config(&file_config)?;
+        eprintln!("- [{}]: {}", key, old_value);
+        return Ok(());
+    }
+
+    Err(
+        "Nested keys are only supported for feature_flags, blame_theme, model_aliases and identity_categories"
+            .to_string(),
+    )
 }
 
 fn parse_key_path(key: &str) -> Vec<String> {
@@ -892,6 +1303,15 @@ fn parse_bool(value: &str) -> Result<bool, String> {
     }
 }
 
+fn parse_identity_category(value: &str) -> Result<IdentityCategory, String> {
+    value.parse::<IdentityCategory>().map_err(|_| {
+        format!(
+            "Invalid identity category: '{}'. Expected bot, generator, ai or human",
+            value
+        )
+    })
+}
+
 fn parse_value(value: &str) -> Result<Value, String> {
     // Try to parse as JSON first
     if let Ok(json_value) = serde_json::from_str::<Value>(value) {
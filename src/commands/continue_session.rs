@@ -6,6 +6,7 @@
 use crate::authorship::authorship_log::PromptRecord;
 use crate::authorship::secrets::redact_secrets_from_prompts;
 use crate::authorship::transcript::Message;
+#[cfg(feature = "tui")]
 use crate::commands::prompt_picker;
 use crate::commands::search::{
     SearchResult, search_by_commit, search_by_commit_range, search_by_file, search_by_pattern,
@@ -312,6 +313,7 @@ fn gather_session_context(
 
 /// Agent output choice for TUI mode
 #[derive(Debug, Clone, PartialEq)]
+#[cfg_attr(not(feature = "tui"), allow(dead_code))]
 enum AgentChoice {
     /// Launch the specified agent CLI
     Launch(String),
@@ -322,6 +324,7 @@ enum AgentChoice {
 }
 
 /// Parse agent choice input string
+#[cfg_attr(not(feature = "tui"), allow(dead_code))]
 fn parse_agent_choice_input(input: &str) -> Result<AgentChoice, GitAiError> {
     match input.trim() {
         "" | "1" => Ok(AgentChoice::Launch("claude".to_string())),
@@ -332,6 +335,7 @@ fn parse_agent_choice_input(input: &str) -> Result<AgentChoice, GitAiError> {
 }
 
 /// Prompt user to select an output mode
+#[cfg_attr(not(feature = "tui"), allow(dead_code))]
 fn prompt_agent_choice(prompt_snippet: &str) -> Result<AgentChoice, GitAiError> {
     eprintln!("\nSelected prompt: {}", prompt_snippet);
     eprintln!("\nLaunch with which agent?");
@@ -352,7 +356,16 @@ fn prompt_agent_choice(prompt_snippet: &str) -> Result<AgentChoice, GitAiError>
     parse_agent_choice_input(&input)
 }
 
+/// Builds without the `tui` feature need the prompt picked by flag
+#[cfg(not(feature = "tui"))]
+fn handle_continue_tui(_repo: &Repository, _options: &ContinueOptions) {
+    eprintln!("This build of git-ai has no TUI.");
+    eprintln!("Use --commit, --file, or --prompt-id flags instead.");
+    std::process::exit(1);
+}
+
 /// Handle interactive TUI mode for continue command
+#[cfg(feature = "tui")]
 fn handle_continue_tui(repo: &Repository, options: &ContinueOptions) {
     // Check if terminal is interactive
     if !std::io::stdout().is_terminal() {
@@ -414,7 +427,7 @@ fn handle_continue_tui(repo: &Repository, options: &ContinueOptions) {
         AgentChoice::Stdout => {
             println!("{}", context);
         }
-        AgentChoice::Clipboard => match copy_to_clipboard(&context) {
+        AgentChoice::Clipboard => match crate::utils::copy_to_clipboard(&context) {
             Ok(()) => {
                 eprintln!("Context copied to clipboard ({} characters)", context.len());
             }
@@ -535,7 +548,7 @@ pub fn handle_continue(args: &[String]) {
     // Default behavior: launch agent if stdout is a terminal, otherwise print to stdout.
     // The --launch flag is accepted but is the default for interactive terminals.
     if parsed.options.clipboard {
-        match copy_to_clipboard(&output) {
+        match crate::utils::copy_to_clipboard(&output) {
             Ok(()) => {
                 eprintln!("Context copied to clipboard ({} characters)", output.len());
             }
@@ -640,143 +653,6 @@ fn launch_agent(agent: &str, context: &str, summary: bool) -> Result<(), GitAiEr
     }
 }
 
-/// Copy text to the system clipboard
-fn copy_to_clipboard(text: &str) -> Result<(), GitAiError> {
-    let result = copy_to_clipboard_platform(text);
-
-    if result.is_err() {
-        // Fallback: try common clipboard tools
-        if let Ok(()) = try_clipboard_fallback(text) {
-            return Ok(());
-        }
-    }
-
-    result
-}
-
-#[cfg(target_os = "macos")]
-fn copy_to_clipboard_platform(text: &str) -> Result<(), GitAiError> {
-    let mut child = Command::new("pbcopy")
-        .stdin(Stdio::piped())
-        .spawn()
-        .map_err(|e| GitAiError::Generic(format!("Failed to spawn pbcopy: {}", e)))?;
-
-    if let Some(mut stdin) = child.stdin.take() {
-        stdin
-            .write_all(text.as_bytes())
-            .map_err(|e| GitAiError::Generic(format!("Failed to write to pbcopy: {}", e)))?;
-    }
-
-    let status = child
-        .wait()
-        .map_err(|e| GitAiError::Generic(format!("Failed to wait for pbcopy: {}", e)))?;
-
-    if status.success() {
-        Ok(())
-    } else {
-        Err(GitAiError::Generic("pbcopy failed".to_string()))
-    }
-}
-
-#[cfg(target_os = "linux")]
-fn copy_to_clipboard_platform(text: &str) -> Result<(), GitAiError> {
-    // Try xclip first, then xsel
-    let mut child = Command::new("xclip")
-        .args(["-selection", "clipboard"])
-        .stdin(Stdio::piped())
-        .spawn()
-        .or_else(|_| {
-            Command::new("xsel")
-                .args(["--clipboard", "--input"])
-                .stdin(Stdio::piped())
-                .spawn()
-        })
-        .map_err(|e| {
-            GitAiError::Generic(format!(
-                "No clipboard tool available (xclip or xsel required): {}",
-                e
-            ))
-        })?;
-
-    if let Some(mut stdin) = child.stdin.take() {
-        stdin
-            .write_all(text.as_bytes())
-            .map_err(|e| GitAiError::Generic(format!("Failed to write to clipboard: {}", e)))?;
-    }
-
-    let status = child
-        .wait()
-        .map_err(|e| GitAiError::Generic(format!("Failed to wait for clipboard command: {}", e)))?;
-
-    if status.success() {
-        Ok(())
-    } else {
-        Err(GitAiError::Generic("Clipboard command failed".to_string()))
-    }
-}
-
-#[cfg(target_os = "windows")]
-fn copy_to_clipboard_platform(text: &str) -> Result<(), GitAiError> {
-    let mut child = Command::new("clip")
-        .stdin(Stdio::piped())
-        .spawn()
-        .map_err(|e| GitAiError::Generic(format!("Failed to spawn clip: {}", e)))?;
-
-    if let Some(mut stdin) = child.stdin.take() {
-        stdin
-            .write_all(text.as_bytes())
-            .map_err(|e| GitAiError::Generic(format!("Failed to write to clip: {}", e)))?;
-    }
-
-    let status = child
-        .wait()
-        .map_err(|e| GitAiError::Generic(format!("Failed to wait for clip: {}", e)))?;
-
-    if status.success() {
-        Ok(())
-    } else {
-        Err(GitAiError::Generic("clip failed".to_string()))
-    }
-}
-
-#[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
-fn copy_to_clipboard_platform(_text: &str) -> Result<(), GitAiError> {
-    Err(GitAiError::Generic(
-        "Clipboard not supported on this platform".to_string(),
-    ))
-}
-
-/// Fallback clipboard method for when platform-specific method fails
-fn try_clipboard_fallback(text: &str) -> Result<(), GitAiError> {
-    // Try common clipboard tools in order
-    let tools = [
-        ("pbcopy", vec![]),
-        ("xclip", vec!["-selection", "clipboard"]),
-        ("xsel", vec!["--clipboard", "--input"]),
-        ("clip", vec![]),
-    ];
-
-    for (tool, args) in tools {
-        if let Ok(mut child) = Command::new(tool)
-            .args(&args)
-            .stdin(Stdio::piped())
-            .stdout(Stdio::null())
-            .stderr(Stdio::null())
-            .spawn()
-            && let Some(mut stdin) = child.stdin.take()
-            && stdin.write_all(text.as_bytes()).is_ok()
-            && let Ok(status) = child.wait()
-            && status.success()
-        {
-            return Ok(());
-        }
-    }
-
-    Err(GitAiError::Generic(
-        "No clipboard tool available".to_string(),
-    ))
-}
-
 /// Parsed continue arguments
 #[derive(Debug)]
 struct ParsedContinueArgs {
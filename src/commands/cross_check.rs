@@ -0,0 +1,358 @@
+//! `git-ai cross-check <branchA> <branchB> [--repair] [-- <path>...]`: compare the
+//! attribution of files that are byte-identical on two branches.
+//!
+//! Identical blobs should blame to the same prompts on both branches, whatever route the
+//! lines took there (merge, cherry-pick, rebase). A line that is AI on one branch and human
+//! on the other points at notes that were never synced or a rewrite that lost them.
+//! `--repair` copies the AI attribution into the notes of the commits that introduced the
+//! line on the branch missing it.
+
+use crate::authorship::authorship_log::{LineRange, PromptRecord};
+use crate::authorship::authorship_log_serialization::{AttestationEntry, AuthorshipLog};
+use crate::commands::blame::GitAiBlameOptions;
+use crate::commands::diff::resolve_commit;
+use crate::error::GitAiError;
+use crate::git::find_repository;
+use crate::git::refs::{get_reference_as_authorship_log_v3, notes_add, show_authorship_note};
+use crate::git::repository::{Repository, exec_git};
+use std::collections::{BTreeMap, HashMap};
+
+/// Attribution of one line on one branch; `None` means human (or unattributed).
+pub type LineAttribution = Option<String>;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Divergence {
+    pub file: String,
+    pub line: u32,
+    /// Prompt hash on the first branch
+    pub a: LineAttribution,
+    /// Prompt hash on the second branch
+    pub b: LineAttribution,
+}
+
+#[derive(Debug, Default)]
+pub struct CrossCheckReport {
+    pub files_compared: usize,
+    pub divergences: Vec<Divergence>,
+    /// Prompts referenced by either branch, keyed by hash
+    pub prompts: HashMap<String, PromptRecord>,
+}
+
+pub fn handle_cross_check(args: &[String]) {
+    let mut repair = false;
+    let mut branches: Vec<String> = Vec::new();
+    let mut paths: Vec<String> = Vec::new();
+    let mut after_double_dash = false;
+    for arg in args {
+        match arg.as_str() {
+            _ if after_double_dash => paths.push(arg.clone()),
+            "--" => after_double_dash = true,
+            "--repair" => repair = true,
+            flag if flag.starts_with('-') => {
+                eprintln!("Error: unknown cross-check option: {}", flag);
+                std::process::exit(2);
+            }
+            branch => branches.push(branch.to_string()),
+        }
+    }
+    let [a, b] = branches.as_slice() else {
+        eprintln!("Usage: git-ai cross-check <branchA> <branchB> [--repair] [-- <path>...]");
+        std::process::exit(2);
+    };
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Failed to find repository: {}", e);
+            std::process::exit(2);
+        }
+    };
+
+    let (a_sha, b_sha) = match (resolve_commit(&repo, a), resolve_commit(&repo, b)) {
+        (Ok(a_sha), Ok(b_sha)) => (a_sha, b_sha),
+        (Err(e), _) | (_, Err(e)) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(2);
+        }
+    };
+
+    let report = match cross_check(&repo, &a_sha, &b_sha, &paths) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("Failed to cross-check attribution: {}", e);
+            std::process::exit(2);
+        }
+    };
+
+    println!(
+        "Compared attribution of {} file(s) identical on {} and {}",
+        report.files_compared, a, b
+    );
+    for divergence in &report.divergences {
+        println!(
+            "{}:{}  {}: {}  {}: {}",
+            divergence.file,
+            divergence.line,
+            a,
+            describe(&divergence.a, &report.prompts),
+            b,
+            describe(&divergence.b, &report.prompts)
+        );
+    }
+    if report.divergences.is_empty() {
+        println!("No divergent attribution");
+        return;
+    }
+    let files = report
+        .divergences
+        .iter()
+        .map(|divergence| divergence.file.as_str())
+        .collect::<std::collections::HashSet<_>>()
+        .len();
+    println!(
+        "{} divergent line(s) in {} file(s)",
+        report.divergences.len(),
+        files
+    );
+
+    if !repair {
+        std::process::exit(1);
+    }
+
+    let unrepaired = report
+        .divergences
+        .iter()
+        .filter(|divergence| divergence.a.is_some() && divergence.b.is_some())
+        .count();
+    for (branch, sha, missing_on_a) in [(a, &a_sha, true), (b, &b_sha, false)] {
+        match repair_branch(&repo, sha, &report, missing_on_a) {
+            Ok((0, _)) => {}
+            Ok((lines, notes)) => println!(
+                "Repaired {} line(s) on {} ({} commit note(s) updated)",
+                lines, branch, notes
+            ),
+            Err(e) => {
+                eprintln!("Failed to repair {}: {}", branch, e);
+                std::process::exit(1);
+            }
+        }
+    }
+    if unrepaired > 0 {
+        println!(
+            "{} line(s) attributed to different prompts on each branch were left as they are",
+            unrepaired
+        );
+        std::process::exit(1);
+    }
+}
+
+fn describe(attribution: &LineAttribution, prompts: &HashMap<String, PromptRecord>) -> String {
+    match attribution {
+        Some(hash) => match prompts.get(hash) {
+            Some(prompt) => format!("{} ({})", prompt.agent_id.tool, hash),
+            None => hash.clone(),
+        },
+        None => "human".to_string(),
+    }
+}
+
+/// Compare per-line attribution of the files whose blobs are identical at `a_sha` and `b_sha`,
+/// optionally restricted to `paths` (files or directories).
+pub fn cross_check(
+    repo: &Repository,
+    a_sha: &str,
+    b_sha: &str,
+    paths: &[String],
+) -> Result<CrossCheckReport, GitAiError> {
+    let a_blobs = list_blobs(repo, a_sha, paths)?;
+    let b_blobs = list_blobs(repo, b_sha, paths)?;
+
+    let mut report = CrossCheckReport::default();
+    for (file, blob) in &a_blobs {
+        if b_blobs.get(file) != Some(blob) {
+            continue;
+        }
+        report.files_compared += 1;
+        let a_lines = blame_attribution(repo, a_sha, file, &mut report.prompts)?;
+        let b_lines = blame_attribution(repo, b_sha, file, &mut report.prompts)?;
+        let last_line = a_lines.keys().chain(b_lines.keys()).max().copied();
+        for line in 1..=last_line.unwrap_or(0) {
+            let a = a_lines.get(&line).cloned().flatten();
+            let b = b_lines.get(&line).cloned().flatten();
+            if a != b {
+                report.divergences.push(Divergence {
+                    file: file.clone(),
+                    line,
+                    a,
+                    b,
+                });
+            }
+        }
+    }
+    Ok(report)
+}
+
+/// Path -> blob OID for every file in `commit_sha`'s tree.
+fn list_blobs(
+    repo: &Repository,
+    commit_sha: &str,
+    paths: &[String],
+) -> Result<BTreeMap<String, String>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.push("ls-tree".to_string());
+    args.push("-r".to_string());
+    args.push("-z".to_string());
+    args.push(commit_sha.to_string());
+    args.push("--".to_string());
+    args.extend(paths.iter().cloned());
+    let output = exec_git(&args)?;
+
+    let mut blobs = BTreeMap::new();
+    for record in output.stdout.split(|b| *b == 0) {
+        // "<mode> <type> <object>\t<path>"
+        let record = String::from_utf8_lossy(record);
+        let Some((meta, path)) = record.split_once('\t') else {
+            continue;
+        };
+        let mut meta = meta.split_whitespace();
+        if let (Some(_mode), Some("blob"), Some(oid)) = (meta.next(), meta.next(), meta.next()) {
+            blobs.insert(path.to_string(), oid.to_string());
+        }
+    }
+    Ok(blobs)
+}
+
+/// Line -> prompt hash (or `None` for human lines) for `file` as blamed from `commit_sha`.
+fn blame_attribution(
+    repo: &Repository,
+    commit_sha: &str,
+    file: &str,
+    prompts: &mut HashMap<String, PromptRecord>,
+) -> Result<HashMap<u32, LineAttribution>, GitAiError> {
+    let options = GitAiBlameOptions {
+        newest_commit: Some(commit_sha.to_string()),
+        no_output: true,
+        use_prompt_hashes_as_names: true,
+        ..Default::default()
+    };
+    let (line_authors, blame_prompts) = repo.blame(file, &options)?;
+    let lines = line_authors
+        .into_iter()
+        .map(|(line, author)| {
+            let hash = blame_prompts.contains_key(&author).then_some(author);
+            (line, hash)
+        })
+        .collect();
+    prompts.extend(blame_prompts);
+    Ok(lines)
+}
+
+/// Attribute the lines that are AI only on the other branch in the notes of the commits that
+/// introduced them on this one. Returns (lines repaired, notes written).
+fn repair_branch(
+    repo: &Repository,
+    branch_sha: &str,
+    report: &CrossCheckReport,
+    missing_on_a: bool,
+) -> Result<(usize, usize), GitAiError> {
+    // file -> line -> prompt hash to copy
+    let mut missing: BTreeMap<&str, BTreeMap<u32, &str>> = BTreeMap::new();
+    for divergence in &report.divergences {
+        let (here, there) = if missing_on_a {
+            (&divergence.a, &divergence.b)
+        } else {
+            (&divergence.b, &divergence.a)
+        };
+        if let (None, Some(hash)) = (here, there) {
+            missing
+                .entry(divergence.file.as_str())
+                .or_default()
+                .insert(divergence.line, hash.as_str());
+        }
+    }
+
+    // commit -> (path in that commit, prompt hash) -> lines in that commit
+    let mut additions: BTreeMap<String, BTreeMap<(String, String), Vec<u32>>> = BTreeMap::new();
+    let mut repaired = 0;
+    for (file, lines) in &missing {
+        let first = *lines.keys().next().unwrap_or(&1);
+        let last = *lines.keys().next_back().unwrap_or(&first);
+        let options = GitAiBlameOptions {
+            newest_commit: Some(branch_sha.to_string()),
+            ..Default::default()
+        };
+        for hunk in repo.blame_hunks(file, first, last, &options)? {
+            for offset in 0..=(hunk.range.1 - hunk.range.0) {
+                let Some(hash) = lines.get(&(hunk.range.0 + offset)) else {
+                    continue;
+                };
+                let path = hunk
+                    .orig_file_path
+                    .clone()
+                    .unwrap_or_else(|| file.to_string());
+                additions
+                    .entry(hunk.commit_sha.clone())
+                    .or_default()
+                    .entry((path, hash.to_string()))
+                    .or_default()
+                    .push(hunk.orig_range.0 + offset);
+                repaired += 1;
+            }
+        }
+    }
+
+    for (commit_sha, entries) in &additions {
+        let mut log = match get_reference_as_authorship_log_v3(repo, commit_sha) {
+            Ok(log) => log,
+            Err(e) if show_authorship_note(repo, commit_sha).is_some() => {
+                // Never overwrite a note we cannot read
+                return Err(e);
+            }
+            Err(_) => {
+                let mut log = AuthorshipLog::new();
+                log.metadata.base_commit_sha = commit_sha.clone();
+                log
+            }
+        };
+        for ((path, hash), lines) in entries {
+            let attestation = log.get_or_create_file(path);
+            match attestation
+                .entries
+                .iter_mut()
+                .find(|entry| &entry.hash == hash)
+            {
+                Some(entry) => {
+                    let mut all: Vec<u32> = entry
+                        .line_ranges
+                        .iter()
+                        .flat_map(LineRange::expand)
+                        .chain(lines.iter().copied())
+                        .collect();
+                    all.sort_unstable();
+                    all.dedup();
+                    entry.line_ranges = LineRange::compress_lines(&all);
+                }
+                None => {
+                    let mut lines = lines.clone();
+                    lines.sort_unstable();
+                    attestation.add_entry(AttestationEntry::new(
+                        hash.clone(),
+                        LineRange::compress_lines(&lines),
+                    ));
+                }
+            }
+            if let Some(prompt) = report.prompts.get(hash) {
+                log.metadata
+                    .prompts
+                    .entry(hash.clone())
+                    .or_insert_with(|| prompt.clone());
+            }
+        }
+        let serialized = log
+            .serialize_to_string()
+            .map_err(|_| GitAiError::Generic("Failed to serialize authorship log".to_string()))?;
+        notes_add(repo, commit_sha, &serialized)?;
+    }
+
+    Ok((repaired, additions.len()))
+}
@@ -0,0 +1,120 @@
+use crate::authorship::deletions::commit_deletions;
+use crate::git::find_repository;
+use crate::git::repository::exec_git;
+
+/// `git-ai deletions [--json] [--ai-only] [<revision range>] [[--] <path>...]`
+///
+/// Lists the lines each commit in the range deleted and who deleted them: the AI tool (and
+/// model) for deletions made by an agent, otherwise the commit author.
+pub fn handle_deletions(args: &[String]) {
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Failed to find repository: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let (rev_args, pathspecs) = match args.iter().position(|arg| arg == "--") {
+        Some(separator) => (&args[..separator], args[separator + 1..].to_vec()),
+        None => (args, Vec::new()),
+    };
+    let json = rev_args.iter().any(|arg| arg == "--json");
+    let ai_only = rev_args.iter().any(|arg| arg == "--ai-only");
+
+    let mut log_args = repo.global_args_for_exec();
+    log_args.push("log".to_string());
+    log_args.push("--format=%H".to_string());
+    log_args.extend(
+        rev_args
+            .iter()
+            .filter(|arg| !matches!(arg.as_str(), "--json" | "--ai-only"))
+            .cloned(),
+    );
+    if !pathspecs.is_empty() {
+        log_args.push("--".to_string());
+        log_args.extend(pathspecs.iter().cloned());
+    }
+    let commits = match exec_git(&log_args) {
+        Ok(output) => String::from_utf8_lossy(&output.stdout).to_string(),
+        Err(e) => {
+            eprintln!("Failed to list commits: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let pathspec_set = (!pathspecs.is_empty()).then(|| pathspecs.iter().cloned().collect());
+    let mut json_lines = Vec::new();
+    let mut printed_any = false;
+    for sha in commits.lines().filter(|l| !l.is_empty()) {
+        let deletions = match commit_deletions(&repo, sha, pathspec_set.as_ref()) {
+            Ok(deletions) => deletions,
+            Err(e) => {
+                eprintln!("Failed to read deletions for {}: {}", sha, e);
+                std::process::exit(1);
+            }
+        };
+        let lines: Vec<_> = deletions
+            .lines
+            .iter()
+            .filter(|line| !ai_only || line.prompt_hash.is_some())
+            .collect();
+        if lines.is_empty() {
+            continue;
+        }
+
+        if json {
+            for line in lines {
+                let prompt = line
+                    .prompt_hash
+                    .as_ref()
+                    .and_then(|hash| deletions.prompts.get(hash));
+                json_lines.push(serde_json::json!({
+                    "commit": deletions.commit_sha,
+                    "file": line.file,
+                    "line": line.line,
+                    "content": line.content,
+                    "deleted_by": deletions.deleted_by(line),
+                    "ai": prompt.is_some(),
+                    "prompt_id": line.prompt_hash,
+                    "tool": prompt.map(|p| p.agent_id.tool.clone()),
+                    "model": prompt.map(|p| p.agent_id.model.clone()),
+                }));
+            }
+            continue;
+        }
+
+        if printed_any {
+            println!();
+        }
+        printed_any = true;
+        let summary = repo
+            .find_commit(sha.to_string())
+            .and_then(|commit| commit.summary())
+            .unwrap_or_default();
+        println!("{} {}", &sha[..sha.len().min(8)], summary);
+        let mut current_file: Option<&str> = None;
+        for line in lines {
+            if current_file != Some(line.file.as_str()) {
+                println!("  {}", line.file);
+                current_file = Some(line.file.as_str());
+            }
+            println!(
+                "    {:>5} [{}] - {}",
+                line.line,
+                deletions.deleted_by(line),
+                line.content
+            );
+        }
+    }
+
+    if json {
+        match serde_json::to_string_pretty(&json_lines) {
+            Ok(output) => println!("{}", output),
+            Err(e) => {
+                eprintln!("Failed to serialize deletions: {}", e);
+                std::process::exit(1);
+            }
+        }
+    }
+}
@@ -0,0 +1,276 @@
+use crate::authorship::post_commit::post_commit;
+use crate::authorship::pre_commit::pre_commit;
+use crate::authorship::transcript::AiTranscript;
+use crate::authorship::working_log::{AgentId, CheckpointKind};
+use crate::commands::checkpoint;
+use crate::commands::checkpoint_agent::agent_presets::AgentRunResult;
+use crate::error::GitAiError;
+use crate::git::repository::{
+    Repository, exec_git, exec_git_stdin_with_env, find_repository_in_path,
+};
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+const ALICE: (&str, &str) = ("Alice Example", "alice@example.com");
+const BOB: (&str, &str) = ("Bob Example", "bob@example.com");
+
+/// Who produced an edit in the demo history.
+#[derive(Clone, Copy)]
+enum Editor {
+    Human,
+    /// (tool, model, session id)
+    Ai(&'static str, &'static str, &'static str),
+}
+
+struct DemoEdit {
+    path: &'static str,
+    contents: &'static str,
+    editor: Editor,
+}
+
+struct DemoCommit {
+    author: (&'static str, &'static str),
+    days_ago: u64,
+    message: &'static str,
+    edits: Vec<DemoEdit>,
+}
+
+pub fn handle_demo(args: &[String]) {
+    match args.first().map(String::as_str) {
+        Some("create") => {
+            let Some(dir) = args.get(1) else {
+                eprintln!("Usage: git-ai demo create <dir>");
+                std::process::exit(1);
+            };
+            if let Err(e) = create_demo_repo(Path::new(dir)) {
+                eprintln!("Failed to create demo repository: {}", e);
+                std::process::exit(1);
+            }
+        }
+        _ => {
+            eprintln!("Usage: git-ai demo create <dir>");
+            eprintln!(
+                "  Creates a sandbox repository with mixed human/AI history to try blame, stats and hooks"
+            );
+            std::process::exit(1);
+        }
+    }
+}
+
+pub fn create_demo_repo(dir: &Path) -> Result<(), GitAiError> {
+    if dir.exists() && fs::read_dir(dir)?.next().is_some() {
+        return Err(GitAiError::Generic(format!(
+            "{} already exists and is not empty",
+            dir.display()
+        )));
+    }
+    fs::create_dir_all(dir)?;
+    let dir = dir.canonicalize()?;
+    let dir_str = dir.to_string_lossy().to_string();
+
+    exec_git(&["init", "--quiet", &dir_str].map(String::from))?;
+    // Name the default branch consistently regardless of init.defaultBranch
+    exec_git(&["-C", &dir_str, "symbolic-ref", "HEAD", "refs/heads/main"].map(String::from))?;
+    for (key, value) in [("user.name", ALICE.0), ("user.email", ALICE.1)] {
+        exec_git(&["-C", &dir_str, "config", key, value].map(String::from))?;
+    }
+
+    let repo = find_repository_in_path(&dir_str)?;
+    let history = demo_history();
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0);
+
+    for commit in &history {
+        for edit in &commit.edits {
+            let path = dir.join(edit.path);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, edit.contents)?;
+            checkpoint_edit(&repo, commit.author.0, edit)?;
+        }
+        let timestamp = now.saturating_sub(commit.days_ago * 24 * 60 * 60);
+        commit_demo_step(&repo, commit, timestamp)?;
+    }
+
+    println!(
+        "Created demo repository at {} with {} commits.",
+        dir.display(),
+        history.len()
+    );
+    println!();
+    println!("Try:");
+    println!("  cd {}", dir.display());
+    println!("  git-ai blame src/inventory.py");
+    println!("  git-ai stats --by-model");
+    println!("  git-ai stats --by-author --since \"2 weeks ago\"");
+    println!("  git-ai show HEAD");
+    Ok(())
+}
+
+fn checkpoint_edit(repo: &Repository, human: &str, edit: &DemoEdit) -> Result<(), GitAiError> {
+    match edit.editor {
+        Editor::Human => {
+            checkpoint::run(
+                repo,
+                human,
+                CheckpointKind::Human,
+                false,
+                false,
+                true,
+                None,
+                false,
+            )?;
+        }
+        Editor::Ai(tool, model, session) => {
+            let agent_run_result = AgentRunResult {
+                agent_id: AgentId {
+                    tool: tool.to_string(),
+                    id: session.to_string(),
+                    model: model.to_string(),
+                },
+                agent_metadata: None,
+                checkpoint_kind: CheckpointKind::AiAgent,
+                transcript: Some(AiTranscript { messages: vec![] }),
+                repo_working_dir: None,
+                edited_filepaths: Some(vec![edit.path.to_string()]),
+                will_edit_filepaths: None,
+                dirty_files: None,
+            };
+            checkpoint::run(
+                repo,
+                tool,
+                CheckpointKind::AiAgent,
+                false,
+                false,
+                true,
+                Some(agent_run_result),
+                false,
+            )?;
+        }
+    }
+    Ok(())
+}
+
+/// Commit with the real git binary (no wrapper hooks) and write the authorship note directly.
+fn commit_demo_step(
+    repo: &Repository,
+    commit: &DemoCommit,
+    timestamp: u64,
+) -> Result<(), GitAiError> {
+    let (name, email) = commit.author;
+    let author = format!("{} <{}>", name, email);
+    pre_commit(repo, author.clone())?;
+
+    let parent = repo.head().ok().and_then(|head| head.target().ok());
+
+    let mut args = repo.global_args_for_exec();
+    args.push("add".to_string());
+    args.push("-A".to_string());
+    exec_git(&args)?;
+
+    let mut args = repo.global_args_for_exec();
+    args.extend(
+        [
+            "-c",
+            "core.hooksPath=/dev/null",
+            "commit",
+            "--quiet",
+            "-m",
+            commit.message,
+        ]
+        .map(String::from),
+    );
+    let date = format!("{} +0000", timestamp);
+    let env = [
+        ("GIT_AUTHOR_NAME", name),
+        ("GIT_AUTHOR_EMAIL", email),
+        ("GIT_AUTHOR_DATE", date.as_str()),
+        ("GIT_COMMITTER_NAME", name),
+        ("GIT_COMMITTER_EMAIL", email),
+        ("GIT_COMMITTER_DATE", date.as_str()),
+    ]
+    .map(|(k, v)| (k.to_string(), v.to_string()));
+    exec_git_stdin_with_env(&args, &env, &[])?;
+
+    let commit_sha = repo.head()?.target()?;
+    post_commit(repo, parent, commit_sha, author, true)?;
+    Ok(())
+}
+
+/// A small inventory service built up over a few weeks by two people and three agents.
+fn demo_history() -> Vec<DemoCommit> {
+    const CURSOR: Editor = Editor::Ai("cursor", "claude-sonnet-4", "demo-cursor-session");
+    const CLAUDE: Editor = Editor::Ai("claude", "claude-opus-4", "demo-claude-session");
+    const CODEX: Editor = Editor::Ai("codex", "gpt-5-codex", "demo-codex-session");
+
+    vec![
+        DemoCommit {
+            author: ALICE,
+            days_ago: 21,
+            message: "Initial project skeleton",
+            edits: vec![
+                DemoEdit {
+                    path: "README.md",
+                    contents: "# Inventory\n\nTiny inventory service used to demo git-ai.\n",
+                    editor: Editor::Human,
+                },
+                DemoEdit {
+                    path: "src/inventory.py",
+                    contents: "class Inventory:\n    def __init__(self):\n        self.items = {}\n",
+                    editor: Editor::Human,
+                },
+            ],
+        },
+        DemoCommit {
+            author: ALICE,
+            days_ago: 18,
+            message: "Add stock management",
+            edits: vec![DemoEdit {
+                path: "src/inventory.py",
+                contents: "class Inventory:\n    def __init__(self):\n        self.items = {}\n\n    def add(self, sku, quantity):\n        if quantity <= 0:\n            raise ValueError(\"quantity must be positive\")\n        self.items[sku] = self.items.get(sku, 0) + quantity\n\n    def remove(self, sku, quantity):\n        available = self.items.get(sku, 0)\n        if quantity > available:\n            raise ValueError(f\"only {available} of {sku} in stock\")\n        self.items[sku] = available - quantity\n",
+                editor: CURSOR,
+            }],
+        },
+        DemoCommit {
+            author: BOB,
+            days_ago: 12,
+            message: "Add tests and tighten error messages",
+            edits: vec![
+                DemoEdit {
+                    path: "tests/test_inventory.py",
+                    contents: "import pytest\n\nfrom src.inventory import Inventory\n\n\ndef test_add_accumulates():\n    inv = Inventory()\n    inv.add(\"apple\", 2)\n    inv.add(\"apple\", 3)\n    assert inv.items[\"apple\"] == 5\n\n\ndef test_remove_rejects_overdraw():\n    inv = Inventory()\n    inv.add(\"pear\", 1)\n    with pytest.raises(ValueError):\n        inv.remove(\"pear\", 2)\n",
+                    editor: CLAUDE,
+                },
+                DemoEdit {
+                    path: "src/inventory.py",
+                    contents: "class Inventory:\n    def __init__(self):\n        self.items = {}\n\n    def add(self, sku, quantity):\n        if quantity <= 0:\n            raise ValueError(f\"cannot add {quantity} of {sku}\")\n        self.items[sku] = self.items.get(sku, 0) + quantity\n\n    def remove(self, sku, quantity):\n        available = self.items.get(sku, 0)\n        if quantity > available:\n            raise ValueError(f\"only {available} of {sku} in stock\")\n        self.items[sku] = available - quantity\n",
+                    editor: Editor::Human,
+                },
+            ],
+        },
+        DemoCommit {
+            author: ALICE,
+            days_ago: 6,
+            message: "Report low stock",
+            edits: vec![DemoEdit {
+                path: "src/inventory.py",
+                contents: "LOW_STOCK_THRESHOLD = 3\n\n\nclass Inventory:\n    def __init__(self):\n        self.items = {}\n\n    def add(self, sku, quantity):\n        if quantity <= 0:\n            raise ValueError(f\"cannot add {quantity} of {sku}\")\n        self.items[sku] = self.items.get(sku, 0) + quantity\n\n    def remove(self, sku, quantity):\n        available = self.items.get(sku, 0)\n        if quantity > available:\n            raise ValueError(f\"only {available} of {sku} in stock\")\n        self.items[sku] = available - quantity\n\n    def low_stock(self):\n        return sorted(\n            sku for sku, count in self.items.items() if count < LOW_STOCK_THRESHOLD\n        )\n",
+                editor: CODEX,
+            }],
+        },
+        DemoCommit {
+            author: BOB,
+            days_ago: 2,
+            message: "Document usage",
+            edits: vec![DemoEdit {
+                path: "README.md",
+                contents: "# Inventory\n\nTiny inventory service used to demo git-ai.\n\n## Usage\n\n```python\ninv = Inventory()\ninv.add(\"apple\", 5)\ninv.low_stock()\n```\n",
+                editor: Editor::Human,
+            }],
+        },
+    ]
+}
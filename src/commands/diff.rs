@@ -176,7 +176,7 @@ pub fn execute_diff(
 // Commit Resolution
 // ============================================================================
 
-fn resolve_commit(repo: &Repository, rev: &str) -> Result<String, GitAiError> {
+pub(crate) fn resolve_commit(repo: &Repository, rev: &str) -> Result<String, GitAiError> {
     let mut args = repo.global_args_for_exec();
     args.push("rev-parse".to_string());
     args.push(rev.to_string());
@@ -597,7 +597,7 @@ fn get_diff_split_by_file(
 
 /// Collect annotations for a specific file, returning (annotations_map, prompt_records_map)
 #[allow(clippy::type_complexity)]
-fn collect_file_annotations(
+pub(crate) fn collect_file_annotations(
     repo: &Repository,
     from_commit: &str,
     to_commit: &str,
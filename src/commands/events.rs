@@ -0,0 +1,173 @@
+use crate::error::GitAiError;
+use crate::git::attribution_events::{AttributionEvent, parse_journal};
+use crate::git::find_repository;
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::time::Duration;
+
+const FOLLOW_POLL_INTERVAL: Duration = Duration::from_millis(250);
+const DEFAULT_FOLLOW_BACKLOG: usize = 10;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum EventsFormat {
+    Json,
+    Text,
+}
+
+pub fn handle_events(args: &[String]) {
+    let mut follow = false;
+    let mut format = EventsFormat::Text;
+    let mut tail: Option<usize> = None;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--follow" | "-f" => follow = true,
+            "--format" => {
+                i += 1;
+                format = parse_format(args.get(i).map(String::as_str));
+            }
+            "--json" => format = EventsFormat::Json,
+            "-n" | "--tail" => {
+                i += 1;
+                tail = Some(parse_tail(args.get(i).map(String::as_str)));
+            }
+            arg if arg.starts_with("--format=") => {
+                format = parse_format(arg.strip_prefix("--format="));
+            }
+            arg if arg.starts_with("--tail=") => {
+                tail = Some(parse_tail(arg.strip_prefix("--tail=")));
+            }
+            arg => {
+                eprintln!("Error: unknown events option: {}", arg);
+                std::process::exit(1);
+            }
+        }
+        i += 1;
+    }
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Failed to find repository: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let journal = repo.storage.events_journal.clone();
+    let backlog = tail.or(follow.then_some(DEFAULT_FOLLOW_BACKLOG));
+
+    if let Err(e) = run_events(&journal, format, backlog, follow) {
+        eprintln!("Failed to read events: {}", e);
+        std::process::exit(1);
+    }
+}
+
+fn parse_format(value: Option<&str>) -> EventsFormat {
+    match value {
+        Some("json") | Some("jsonl") => EventsFormat::Json,
+        Some("text") => EventsFormat::Text,
+        other => {
+            eprintln!(
+                "Error: --format expects json or text, got {}",
+                other.unwrap_or("nothing")
+            );
+            std::process::exit(1);
+        }
+    }
+}
+
+fn parse_tail(value: Option<&str>) -> usize {
+    match value.and_then(|v| v.parse::<usize>().ok()) {
+        Some(n) => n,
+        None => {
+            eprintln!("Error: --tail expects a non-negative number");
+            std::process::exit(1);
+        }
+    }
+}
+
+fn run_events(
+    journal: &Path,
+    format: EventsFormat,
+    backlog: Option<usize>,
+    follow: bool,
+) -> Result<(), GitAiError> {
+    let content = fs::read(journal).unwrap_or_default();
+    // Only replay complete lines; a partially written trailing line is picked up by --follow.
+    let complete_len = content
+        .iter()
+        .rposition(|b| *b == b'\n')
+        .map(|p| p + 1)
+        .unwrap_or(0);
+    let events = parse_journal(&String::from_utf8_lossy(&content[..complete_len]));
+    let skip = backlog.map_or(0, |n| events.len().saturating_sub(n));
+
+    let stdout = std::io::stdout();
+    let mut out = stdout.lock();
+    for event in &events[skip..] {
+        if !write_event(&mut out, event, format) {
+            return Ok(());
+        }
+    }
+    drop(out);
+
+    if follow {
+        follow_journal(journal, complete_len as u64, format)?;
+    }
+    Ok(())
+}
+
+/// Poll the journal for appended lines, restarting from the top when it is rotated or truncated.
+fn follow_journal(journal: &Path, mut offset: u64, format: EventsFormat) -> Result<(), GitAiError> {
+    let mut pending = String::new();
+    loop {
+        let len = fs::metadata(journal).map(|m| m.len()).unwrap_or(0);
+        if len < offset {
+            offset = 0;
+            pending.clear();
+        }
+
+        if len > offset {
+            let mut file = File::open(journal)?;
+            file.seek(SeekFrom::Start(offset))?;
+            let mut buf = Vec::new();
+            file.read_to_end(&mut buf)?;
+            offset += buf.len() as u64;
+            pending.push_str(&String::from_utf8_lossy(&buf));
+
+            if let Some(end) = pending.rfind('\n') {
+                let complete: String = pending.drain(..=end).collect();
+                let stdout = std::io::stdout();
+                let mut out = stdout.lock();
+                for event in parse_journal(&complete) {
+                    if !write_event(&mut out, &event, format) {
+                        return Ok(());
+                    }
+                }
+            }
+        }
+
+        std::thread::sleep(FOLLOW_POLL_INTERVAL);
+    }
+}
+
+/// Write one event and flush so consumers see it immediately.
+/// Returns false once stdout is closed (e.g. the reader of a pipe exited).
+fn write_event(out: &mut impl Write, event: &AttributionEvent, format: EventsFormat) -> bool {
+    let line = match format {
+        EventsFormat::Json => serde_json::to_string(event).unwrap_or_default(),
+        EventsFormat::Text => {
+            let when = chrono::DateTime::from_timestamp_millis(event.timestamp_millis() as i64)
+                .map(|dt| {
+                    dt.with_timezone(&chrono::Local)
+                        .format("%Y-%m-%d %H:%M:%S")
+                        .to_string()
+                })
+                .unwrap_or_default();
+            format!("{}  {}", when, event.summary())
+        }
+    };
+    writeln!(out, "{}", line).is_ok() && out.flush().is_ok()
+}
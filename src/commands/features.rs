@@ -0,0 +1,63 @@
+use crate::config::Config;
+use crate::feature_flags::{FeatureState, compiled_features};
+
+/// `git-ai features list [--json]`: show the cargo features this binary was built with and the
+/// runtime flags the current config and environment enable.
+pub fn handle_features(args: &[String]) {
+    let json = match args {
+        [command] if command == "list" => false,
+        [command, flag] if command == "list" && flag == "--json" => true,
+        _ => {
+            eprintln!("Usage: git-ai features list [--json]");
+            std::process::exit(1);
+        }
+    };
+
+    let compiled = compiled_features();
+    let runtime = Config::get().get_feature_flags().states();
+
+    if json {
+        let output = serde_json::json!({
+            "version": env!("CARGO_PKG_VERSION"),
+            "compiled": compiled,
+            "runtime": runtime,
+        });
+        match serde_json::to_string_pretty(&output) {
+            Ok(output) => println!("{}", output),
+            Err(e) => {
+                eprintln!("Failed to serialize features: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    println!("Compiled features:");
+    print_states(&compiled);
+    println!();
+    println!("Runtime flags (feature_flags in config, GIT_AI_<NAME> environment variables):");
+    print_states(&runtime);
+}
+
+fn print_states(states: &[FeatureState]) {
+    let width = states
+        .iter()
+        .map(|state| state.name.len())
+        .max()
+        .unwrap_or(0);
+    let on_off = |enabled: bool| if enabled { "on" } else { "off" };
+    for state in states {
+        let overridden = if state.enabled == state.default {
+            String::new()
+        } else {
+            format!(" [default: {}]", on_off(state.default))
+        };
+        println!(
+            "  {:<width$}  {:<3}  {}{}",
+            state.name,
+            on_off(state.enabled),
+            state.about,
+            overridden,
+        );
+    }
+}
@@ -1,7 +1,11 @@
 use crate::authorship::ignore::effective_ignore_patterns;
 use crate::authorship::internal_db::InternalDatabase;
+use crate::authorship::line_filter::LineFilter;
 use crate::authorship::range_authorship;
 use crate::authorship::stats::stats_command;
+use crate::authorship::stats_report::{
+    StatsGroupBy, StatsReportOptions, build_stats_report, print_stats_report, stats_report_to_csv,
+};
 use crate::authorship::working_log::{AgentId, CheckpointKind};
 use crate::commands;
 use crate::commands::checkpoint_agent::agent_presets::{
@@ -10,6 +14,7 @@ use crate::commands::checkpoint_agent::agent_presets::{
 };
 use crate::commands::checkpoint_agent::agent_v1_preset::AgentV1Preset;
 use crate::commands::checkpoint_agent::opencode_preset::OpenCodePreset;
+use crate::commands::checkpoint_agent::structured_input::CheckpointBuilder;
 use crate::config;
 use crate::git::find_repository;
 use crate::git::find_repository_in_path;
@@ -30,8 +35,8 @@ pub fn handle_git_ai(args: &[String]) {
 
     // Start DB warmup early for commands that need database access
     match args[0].as_str() {
-        "checkpoint" | "show-prompt" | "share" | "sync-prompts" | "flush-cas" | "search"
-        | "continue" => {
+        "checkpoint" | "with" | "show-prompt" | "prompt" | "share" | "sync-prompts"
+        | "flush-cas" | "search" | "continue" => {
             InternalDatabase::warmup();
         }
         _ => {}
@@ -67,9 +72,48 @@ pub fn handle_git_ai(args: &[String]) {
         "show" => {
             commands::show::handle_show(&args[1..]);
         }
+        "log" => {
+            commands::log::handle_log(&args[1..]);
+        }
+        "deletions" => {
+            commands::deletions::handle_deletions(&args[1..]);
+        }
+        "events" => {
+            commands::events::handle_events(&args[1..]);
+        }
+        "demo" => {
+            commands::demo::handle_demo(&args[1..]);
+        }
+        "serve" => {
+            commands::serve::handle_serve(&args[1..]);
+        }
+        "policy" => {
+            commands::policy::handle_policy(&args[1..]);
+        }
+        "plumbing" => {
+            commands::plumbing::handle_plumbing(&args[1..]);
+        }
+        "review" => {
+            commands::review::handle_review(&args[1..]);
+        }
+        "assert-attributed" => {
+            commands::assert_attributed::handle_assert_attributed(&args[1..]);
+        }
+        "sync" => {
+            commands::sync::handle_sync(&args[1..]);
+        }
+        "notes" => {
+            commands::notes::handle_notes(&args[1..]);
+        }
+        "cross-check" => {
+            commands::cross_check::handle_cross_check(&args[1..]);
+        }
         "checkpoint" => {
             handle_checkpoint(&args[1..]);
         }
+        "with" => {
+            commands::with::handle_with(&args[1..]);
+        }
         "blame" => {
             handle_ai_blame(&args[1..]);
             if is_interactive_terminal() {
@@ -109,6 +153,27 @@ pub fn handle_git_ai(args: &[String]) {
                 std::process::exit(1);
             }
         },
+        "backfill" => {
+            commands::backfill::handle_backfill(&args[1..]);
+        }
+        "bench" => {
+            commands::bench::handle_bench(&args[1..]);
+        }
+        "features" => {
+            commands::features::handle_features(&args[1..]);
+        }
+        "graft-attribution" => {
+            commands::graft_attribution::handle_graft_attribution(&args[1..]);
+        }
+        "provenance" => {
+            commands::provenance::handle_provenance(&args[1..]);
+        }
+        "reassign" => {
+            commands::reassign::handle_reassign(&args[1..]);
+        }
+        "sandbox" => {
+            commands::sandbox::handle_sandbox(&args[1..]);
+        }
         "squash-authorship" => {
             commands::squash_authorship::handle_squash_authorship(&args[1..]);
         }
@@ -142,6 +207,9 @@ pub fn handle_git_ai(args: &[String]) {
         "show-prompt" => {
             commands::show_prompt::handle_show_prompt(&args[1..]);
         }
+        "prompt" => {
+            commands::show_prompt::handle_prompt(&args[1..]);
+        }
         "share" => {
             commands::share::handle_share(&args[1..]);
         }
@@ -181,18 +249,112 @@ fn print_help() {
     eprintln!(
         "    --hook-input <json|stdin>   JSON payload required by presets, or 'stdin' to read from stdin"
     );
+    eprintln!(
+        "    --stdin-json                Record a JSON payload (tool, model, session_id, files) from stdin"
+    );
     eprintln!("    --show-working-log          Display current working log");
     eprintln!("    --reset                     Reset working log");
+    eprintln!(
+        "    --whole-file                Attribute edited binary files (images, models) as whole-file AI assets"
+    );
     eprintln!("    mock_ai [pathspecs...]      Test preset accepting optional file pathspecs");
+    eprintln!(
+        "  with --tool <tool> [--model <model>] -- <command>  Run a command and attribute its file changes to an AI tool"
+    );
     eprintln!("  blame <file>       Git blame with AI authorship overlay");
+    eprintln!(
+        "    --format <json|jsonl>  Per-line output with commit, human author, AI tool/model and prompt id"
+    );
+    eprintln!(
+        "    --color=<always|never|auto>  Colorize output (auto: only on a terminal; theme via blame_theme config)"
+    );
+    eprintln!(
+        "    --copy-provenance <file>:<line>  Copy the line's provenance string and permalink to the clipboard"
+    );
+    eprintln!(
+        "    --compat strict  Print exactly what git blame prints; write AI data to a JSON sidecar"
+    );
+    eprintln!(
+        "    --show-prompt-id  Show the full prompt id of AI lines (for `git-ai prompt show`/`show-prompt`)"
+    );
+    eprintln!(
+        "    --sidecar <path>  Sidecar location for --compat strict (default: .git/ai/blame/<file>.json)"
+    );
+    eprintln!(
+        "    --show-deleted    After the blame, list lines deleted from the file and who deleted them"
+    );
+    eprintln!(
+        "    --only-ai, --only-human  Only show AI-written or human-written lines (composes with -L and all formats)"
+    );
+    eprintln!("    --tool <name>, --model <name>  Only show AI lines from this tool or model");
+    eprintln!(
+        "    --dim-filtered    Dim the lines a filter excludes instead of folding them into `...`"
+    );
+    eprintln!(
+        "    --dominant-author <latest|majority|any-ai>  Author of lines written by both human and AI"
+    );
     eprintln!("  diff <commit|range>  Show diff with AI authorship annotations");
     eprintln!("    <commit>              Diff from commit's parent to commit");
     eprintln!("    <commit1>..<commit2>  Diff between two commits");
     eprintln!("  stats [commit]     Show AI authorship statistics for a commit");
     eprintln!("    --json                 Output in JSON format");
+    eprintln!("    --exclude-blank        Don't count blank lines");
+    eprintln!("    --exclude-comments     Don't count comment-only lines (by file type)");
+    eprintln!("    --min-line-length <n>  Don't count lines shorter than n characters (trimmed)");
+    eprintln!(
+        "  stats [--since <date>] [--author <name>] [--by-file|--by-author|--by-model] [<rev-range>]"
+    );
+    eprintln!(
+        "                     Aggregate AI vs human lines across commits using authorship notes"
+    );
+    eprintln!("    --csv                  Output the aggregate report as CSV");
+    eprintln!(
+        "    --raw-models           Group by model names as recorded (see model_aliases config)"
+    );
     eprintln!("  status             Show uncommitted AI authorship status (debug)");
     eprintln!("    --json                 Output in JSON format");
     eprintln!("  show <rev|range>   Display authorship logs for a revision or range");
+    eprintln!("    -p, --patch           Show each commit's diff with AI/human markers instead");
+    eprintln!("  log [<options>]    Like `git log -p`, with AI/human markers on added lines");
+    eprintln!(
+        "    --graph               Draw the commit graph, nodes marked A (mostly AI), M (mixed) or H (mostly human)"
+    );
+    eprintln!("    --format <text|json>  Output format (json emits per-commit AI/human totals)");
+    eprintln!(
+        "  deletions [<range>] [-- <path>...]  Show deleted lines and the human or AI tool that deleted them"
+    );
+    eprintln!("    --ai-only             Only show lines deleted by AI tools");
+    eprintln!("    --json                Output in JSON format");
+    eprintln!("  events             Show attribution events recorded in this repository");
+    eprintln!("    --follow, -f          Keep running and stream new events as they happen");
+    eprintln!("    --format <json|text>  Output format (json emits one event per line)");
+    eprintln!("    --tail, -n <n>        Only show the last n recorded events");
+    eprintln!("  serve              Answer blame/attribution queries as JSON-RPC for editors");
+    eprintln!("    --stdio               Newline-delimited JSON-RPC over stdin/stdout (default)");
+    eprintln!("    --socket <path>       Listen on a Unix socket instead");
+    eprintln!(
+        "  review <base>..<head>  Markdown report of AI-authored hunks, tools and AI share of a change"
+    );
+    eprintln!("    --json                Output in JSON format");
+    eprintln!(
+        "    --fail-on <metric>=<limit>  Exit 1 when ai-percent, ai-lines or missing-notes exceeds the limit"
+    );
+    eprintln!("  plumbing working-log dump  Print raw working log state as JSON for scripting");
+    eprintln!("    --base <rev>          Working log for this base commit (default: HEAD)");
+    eprintln!("    --format json         Output format (json only)");
+    eprintln!(
+        "  assert-attributed [<rev>...]  Exit non-zero unless each commit (default HEAD) has a valid authorship log"
+    );
+    eprintln!("    -q, --quiet           Only report failures");
+    eprintln!(
+        "  cross-check <branchA> <branchB>  Report lines of identical files attributed differently on each branch"
+    );
+    eprintln!("    --repair              Copy AI attribution to the branch missing it");
+    eprintln!("    -- <path>...          Only compare these paths");
+    eprintln!("  policy check [<rev>|<range>]  Check commits against .git-ai/policy.toml");
+    eprintln!("    --json                Output violations as JSON");
+    eprintln!("  prompt show [<rev>:]<file>:<line>  Show the prompt, tool and model behind a line");
+    eprintln!("    --json                Output in JSON format");
     eprintln!("  show-prompt <id>   Display a prompt record by its ID");
     eprintln!("    --commit <rev>        Look in a specific commit only");
     eprintln!(
@@ -200,6 +362,22 @@ fn print_help() {
     );
     eprintln!("  share <id>         Share a prompt by creating a bundle");
     eprintln!("    --title <title>       Custom title for the bundle (default: auto-generated)");
+    eprintln!("  sync [fetch|push] [<remote>...]  Fetch and/or push authorship notes now");
+    eprintln!(
+        "  sync reconcile [<remote>...]  After working offline: merge notes both sides attached to the same commits, push in one batch, report conflicts"
+    );
+    eprintln!(
+        "    git config git-ai.notesRef <ref>     Notes ref on remotes (default: refs/notes/ai)"
+    );
+    eprintln!(
+        "    git config git-ai.syncRemotes <list> Only sync automatically with these remotes"
+    );
+    eprintln!(
+        "    git config git-ai.sync off|fetch|push|both  Automatic sync around fetch/pull/clone/push"
+    );
+    eprintln!(
+        "  notes replicate --from <remote> --to <remote>  Copy notes of commits both remotes have (e.g. upstream to a fork)"
+    );
     eprintln!("  sync-prompts       Update prompts in database to latest versions");
     eprintln!("    --since <time>        Only sync prompts updated after this time");
     eprintln!(
@@ -212,6 +390,9 @@ fn print_help() {
     eprintln!("    set <key> <value>     Set a config value (arrays: single value = [value])");
     eprintln!("    --add <key> <value>   Add to array or upsert into object");
     eprintln!("    unset <key>           Remove config value (reverts to default)");
+    eprintln!(
+        "    git config ai.readOnly true  Never write attribution here (no checkpoints, notes or note pushes)"
+    );
     eprintln!("  install-hooks      Install git hooks for AI authorship tracking");
     eprintln!("  uninstall-hooks    Remove git-ai hooks from all detected tools");
     eprintln!("  ci                 Continuous integration utilities");
@@ -221,9 +402,50 @@ fn print_help() {
         "    <base_branch> <new_sha> <old_sha>  Required: base branch, new commit SHA, old commit SHA"
     );
     eprintln!("    --dry-run             Show what would be done without making changes");
+    eprintln!(
+        "  backfill --from <branch|range>  Recover authorship for a squash merge made outside git-ai"
+    );
+    eprintln!("    --onto <rev>          Branch the squash commit landed on (default: HEAD)");
+    eprintln!("    --dry-run             Show the matched squash commit without writing a note");
+    eprintln!(
+        "  graft-attribution --from <branch>  Copy attribution from an old history onto recreated commits"
+    );
+    eprintln!("    --onto <rev>          Branch with the recreated history (default: HEAD)");
+    eprintln!("    --dry-run             Show the matches without writing notes");
+    eprintln!(
+        "  reassign <file> -L <start>,<end> --to human  Hand committed AI lines over to the human"
+    );
+    eprintln!("    -L <start>,<end>      Lines to reassign (repeatable)");
+    eprintln!(
+        "  provenance <file>:<line>  Print a stable one-line provenance string (tag, commit, author or tool, model, checkpoint)"
+    );
+    eprintln!("  bench blame        Time git-ai blame on a sample of tracked files");
+    eprintln!("    --against-git         Also time git blame and report git-ai's overhead");
+    eprintln!("    --files <n>           Files to sample (default: 20)");
+    eprintln!("    --runs <n>            Runs per file, the median counts (default: 3)");
+    eprintln!(
+        "    --budget <percent>    Exit 1 when p95 overhead exceeds this (default: blame_overhead_budget)"
+    );
+    eprintln!("    --json                Output in JSON format");
+    eprintln!("  sandbox            Run an agent in its own worktree");
+    eprintln!("    create <branch>       Add a worktree that attributes its commits to an agent");
+    eprintln!("      --path <dir>          Worktree location (default: next to this one)");
+    eprintln!("      --from <rev>          Start a new branch from <rev> (default: HEAD)");
+    eprintln!("      --agent <tool>        Tool to attribute changes to (default: agent)");
+    eprintln!("      --model <model>       Model to record for those changes");
+    eprintln!("    merge <branch>        Check policy, merge the sandbox and remove its worktree");
+    eprintln!("      --squash              Squash into the working tree instead of merging");
+    eprintln!("      --keep                Keep the worktree after merging");
     eprintln!("  git-path           Print the path to the underlying git executable");
+    eprintln!("  demo create <dir>  Create a sandbox repo with mixed human/AI history to explore");
+    eprintln!("  features list      Show compiled features and runtime flags of this build");
+    eprintln!("    --json                Print them as JSON");
     eprintln!("  upgrade            Check for updates and install if available");
     eprintln!("    --force               Reinstall latest version even if already up to date");
+    eprintln!("    --check               Show the cached result of the last check (no network)");
+    eprintln!("    --json                With --check, print the result as JSON");
+    eprintln!("    --migrate             Update the installed git shim for this version");
+    eprintln!("    --dry-run             With --migrate, list the files without changing them");
     eprintln!("  prompts            Create local SQLite database for prompt analysis");
     eprintln!("    --since <time>        Only include prompts after this time (default: 30d)");
     eprintln!("    --author <name>       Filter by human author (default: current git user)");
@@ -264,7 +486,29 @@ fn print_help() {
     std::process::exit(0);
 }
 
+/// `git-ai checkpoint --stdin-json`: record a structured payload from an agent framework
+/// (format documented in `checkpoint_agent::structured_input`).
+fn handle_checkpoint_stdin_json() {
+    let mut buffer = String::new();
+    if let Err(e) = std::io::stdin().read_to_string(&mut buffer) {
+        eprintln!("Failed to read checkpoint JSON from stdin: {}", e);
+        std::process::exit(1);
+    }
+    match CheckpointBuilder::from_json(&buffer).and_then(CheckpointBuilder::record) {
+        Ok(files_edited) => eprintln!("Checkpoint recorded ({} files)", files_edited),
+        Err(e) => {
+            eprintln!("Checkpoint failed: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
 fn handle_checkpoint(args: &[String]) {
+    if args.iter().any(|arg| arg == "--stdin-json") {
+        handle_checkpoint_stdin_json();
+        return;
+    }
+
     let mut repository_working_dir = std::env::current_dir()
         .unwrap()
         .to_string_lossy()
@@ -273,6 +517,7 @@ fn handle_checkpoint(args: &[String]) {
     // Parse checkpoint-specific arguments
     let mut show_working_log = false;
     let mut reset = false;
+    let mut whole_file = false;
     let mut hook_input = None;
 
     let mut i = 0;
@@ -286,6 +531,10 @@ fn handle_checkpoint(args: &[String]) {
                 reset = true;
                 i += 1;
             }
+            "--whole-file" => {
+                whole_file = true;
+                i += 1;
+            }
             "--hook-input" => {
                 if i + 1 < args.len() {
                     hook_input = Some(args[i + 1].clone());
@@ -664,6 +913,11 @@ fn handle_checkpoint(args: &[String]) {
                     modified
                 });
 
+                let whole_file_run = if whole_file {
+                    repo_agent_result.clone()
+                } else {
+                    None
+                };
                 let checkpoint_result = commands::checkpoint::run(
                     &repo,
                     &default_user_name,
@@ -677,6 +931,19 @@ fn handle_checkpoint(args: &[String]) {
 
                 match checkpoint_result {
                     Ok((_, files_edited, _)) => {
+                        if let Some(agent_run) = whole_file_run.as_ref()
+                            && let Err(e) = commands::checkpoint::record_whole_file_assets(
+                                &repo,
+                                &default_user_name,
+                                agent_run,
+                            )
+                        {
+                            eprintln!(
+                                "  Failed to record whole-file assets for {}: {}",
+                                repo_workdir.display(),
+                                e
+                            );
+                        }
                         total_files_edited += files_edited;
                         eprintln!(
                             "  Checkpoint for {} completed ({} files)",
@@ -777,6 +1044,11 @@ fn handle_checkpoint(args: &[String]) {
 
     let checkpoint_start = std::time::Instant::now();
     let agent_tool = agent_run_result.as_ref().map(|r| r.agent_id.tool.clone());
+    let whole_file_run = if whole_file {
+        agent_run_result.clone()
+    } else {
+        None
+    };
     let checkpoint_result = commands::checkpoint::run(
         &repo,
         &default_user_name,
@@ -789,6 +1061,18 @@ fn handle_checkpoint(args: &[String]) {
     );
     match checkpoint_result {
         Ok((_, files_edited, _)) => {
+            if let Some(agent_run) = whole_file_run.as_ref() {
+                match commands::checkpoint::record_whole_file_assets(
+                    &repo,
+                    &default_user_name,
+                    agent_run,
+                ) {
+                    Ok(0) => {}
+                    Ok(count) => eprintln!("Attributed {} whole-file asset(s)", count),
+                    Err(e) => eprintln!("Failed to record whole-file assets: {}", e),
+                }
+            }
+
             let elapsed = checkpoint_start.elapsed();
             log_performance_for_checkpoint(files_edited, elapsed, checkpoint_kind);
             eprintln!("Checkpoint completed in {:?}", elapsed);
@@ -895,12 +1179,50 @@ fn handle_ai_blame(args: &[String]) {
         file_path
     };
 
+    if options.compat_strict {
+        // stdout must match git blame byte for byte, so git writes it directly
+        let status = std::process::Command::new(config::Config::get().git_cmd())
+            .arg("blame")
+            .args(strip_compat_args(args))
+            .status();
+        match status {
+            Ok(status) if status.success() => {}
+            Ok(status) => std::process::exit(status.code().unwrap_or(1)),
+            Err(e) => {
+                eprintln!("Failed to run git blame: {}", e);
+                std::process::exit(1);
+            }
+        }
+        // The sidecar is best-effort: blame output has already been delivered
+        if let Err(e) = repo.blame(&file_path, &options) {
+            eprintln!("warning: failed to write blame sidecar: {}", e);
+        }
+        return;
+    }
+
     if let Err(e) = repo.blame(&file_path, &options) {
         eprintln!("Blame failed: {}", e);
         std::process::exit(1);
     }
 }
 
+/// Drop git-ai's `--compat`/`--sidecar` flags so the rest can be handed to git blame.
+fn strip_compat_args(args: &[String]) -> Vec<String> {
+    let mut passthrough = Vec::with_capacity(args.len());
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--compat" | "--sidecar" => i += 2,
+            arg if arg.starts_with("--compat=") || arg.starts_with("--sidecar=") => i += 1,
+            _ => {
+                passthrough.push(args[i].clone());
+                i += 1;
+            }
+        }
+    }
+    passthrough
+}
+
 fn handle_ai_diff(args: &[String]) {
     let current_dir = env::current_dir()
         .unwrap_or_else(|_| std::path::PathBuf::from("."))
@@ -934,6 +1256,10 @@ fn handle_stats(args: &[String]) {
     let mut commit_sha = None;
     let mut commit_range: Option<CommitRange> = None;
     let mut ignore_patterns: Vec<String> = Vec::new();
+    let mut report_options = StatsReportOptions::default();
+    let mut report_mode = false;
+    let mut csv_output = false;
+    let mut line_filter = LineFilter::default();
 
     let mut i = 0;
     while i < args.len() {
@@ -942,6 +1268,69 @@ fn handle_stats(args: &[String]) {
                 json_output = true;
                 i += 1;
             }
+            "--csv" => {
+                csv_output = true;
+                report_mode = true;
+                i += 1;
+            }
+            "--since" | "--author" => {
+                let Some(value) = args.get(i + 1) else {
+                    eprintln!("{} requires a value", args[i]);
+                    std::process::exit(1);
+                };
+                if args[i] == "--since" {
+                    report_options.since = Some(value.clone());
+                } else {
+                    report_options.author = Some(value.clone());
+                }
+                report_mode = true;
+                i += 2;
+            }
+            arg if arg.starts_with("--since=") || arg.starts_with("--author=") => {
+                let (flag, value) = arg.split_once('=').unwrap();
+                if flag == "--since" {
+                    report_options.since = Some(value.to_string());
+                } else {
+                    report_options.author = Some(value.to_string());
+                }
+                report_mode = true;
+                i += 1;
+            }
+            "--by-file" | "--by-author" | "--by-model" => {
+                let group_by = match args[i].as_str() {
+                    "--by-file" => StatsGroupBy::File,
+                    "--by-author" => StatsGroupBy::Author,
+                    _ => StatsGroupBy::Model,
+                };
+                if report_options.group_by.is_some_and(|g| g != group_by) {
+                    eprintln!("--by-file, --by-author and --by-model are mutually exclusive");
+                    std::process::exit(1);
+                }
+                report_options.group_by = Some(group_by);
+                report_mode = true;
+                i += 1;
+            }
+            "--raw-models" => {
+                report_options.raw_models = true;
+                i += 1;
+            }
+            "--exclude-blank" => {
+                line_filter.exclude_blank = true;
+                i += 1;
+            }
+            "--exclude-comments" => {
+                line_filter.exclude_comments = true;
+                i += 1;
+            }
+            "--min-line-length" => {
+                line_filter.min_length = parse_min_line_length(args.get(i + 1));
+                i += 2;
+            }
+            arg if arg.starts_with("--min-line-length=") => {
+                line_filter.min_length =
+                    parse_min_line_length(arg.strip_prefix("--min-line-length="));
+                i += 1;
+            }
             "--ignore" => {
                 // Collect all arguments after --ignore until we hit another flag or commit SHA
                 // This supports shell glob expansion: `--ignore *.lock` expands to `--ignore Cargo.lock package.lock`
@@ -955,7 +1344,7 @@ fn handle_stats(args: &[String]) {
                     }
                     // Stop if this looks like a commit SHA or range (contains ..)
                     if arg.contains("..")
-                        || (commit_sha.is_none() && !found_pattern && arg.len() >= 7)
+                        || (report_options.rev_range.is_none() && !found_pattern && arg.len() >= 7)
                     {
                         // Could be a commit SHA, stop collecting patterns
                         break;
@@ -971,34 +1360,8 @@ fn handle_stats(args: &[String]) {
             }
             _ => {
                 // First non-flag argument is treated as commit SHA or range
-                if commit_sha.is_none() {
-                    let arg = &args[i];
-                    // Check if this is a commit range (contains "..")
-                    if arg.contains("..") {
-                        let parts: Vec<&str> = arg.split("..").collect();
-                        if parts.len() == 2 {
-                            match CommitRange::new_infer_refname(
-                                &repo,
-                                parts[0].to_string(),
-                                parts[1].to_string(),
-                                // @todo this is probably fine, but we might want to give users an option to override from this command.
-                                None,
-                            ) {
-                                Ok(range) => {
-                                    commit_range = Some(range);
-                                }
-                                Err(e) => {
-                                    eprintln!("Failed to create commit range: {}", e);
-                                    std::process::exit(1);
-                                }
-                            }
-                        } else {
-                            eprintln!("Invalid commit range format. Expected: <commit>..<commit>");
-                            std::process::exit(1);
-                        }
-                    } else {
-                        commit_sha = Some(arg.clone());
-                    }
+                if report_options.rev_range.is_none() {
+                    report_options.rev_range = Some(args[i].clone());
                     i += 1;
                 } else {
                     eprintln!("Unknown stats argument: {}", args[i]);
@@ -1010,9 +1373,65 @@ fn handle_stats(args: &[String]) {
 
     let effective_patterns = effective_ignore_patterns(&repo, &ignore_patterns, &[]);
 
+    // Aggregate report mode: --since/--author/--by-*/--csv across a revision range
+    if report_mode {
+        report_options.line_filter = line_filter;
+        match build_stats_report(&repo, &report_options, &effective_patterns) {
+            Ok(report) => {
+                if json_output {
+                    println!("{}", serde_json::to_string(&report).unwrap());
+                } else if csv_output {
+                    print!("{}", stats_report_to_csv(&report));
+                } else {
+                    print_stats_report(&report);
+                }
+            }
+            Err(e) => {
+                eprintln!("Stats failed: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    if let Some(arg) = &report_options.rev_range {
+        // Check if this is a commit range (contains "..")
+        if arg.contains("..") {
+            let parts: Vec<&str> = arg.split("..").collect();
+            if parts.len() == 2 {
+                match CommitRange::new_infer_refname(
+                    &repo,
+                    parts[0].to_string(),
+                    parts[1].to_string(),
+                    // @todo this is probably fine, but we might want to give users an option to override from this command.
+                    None,
+                ) {
+                    Ok(range) => {
+                        commit_range = Some(range);
+                    }
+                    Err(e) => {
+                        eprintln!("Failed to create commit range: {}", e);
+                        std::process::exit(1);
+                    }
+                }
+            } else {
+                eprintln!("Invalid commit range format. Expected: <commit>..<commit>");
+                std::process::exit(1);
+            }
+        } else {
+            commit_sha = Some(arg.clone());
+        }
+    }
+
     // Handle commit range if detected
     if let Some(range) = commit_range {
-        match range_authorship::range_authorship(range, false, &effective_patterns) {
+        match range_authorship::range_authorship(
+            range,
+            false,
+            &effective_patterns,
+            &line_filter,
+            report_options.raw_models,
+        ) {
             Ok(stats) => {
                 if json_output {
                     let json_str = serde_json::to_string(&stats).unwrap();
@@ -1034,6 +1453,8 @@ fn handle_stats(args: &[String]) {
         commit_sha.as_deref(),
         json_output,
         &effective_patterns,
+        &line_filter,
+        report_options.raw_models,
     ) {
         match e {
             crate::error::GitAiError::Generic(msg) if msg.starts_with("No commit found:") => {
@@ -1047,6 +1468,16 @@ fn handle_stats(args: &[String]) {
     }
 }
 
+fn parse_min_line_length<S: AsRef<str>>(value: Option<S>) -> usize {
+    match value.and_then(|v| v.as_ref().parse::<usize>().ok()) {
+        Some(n) => n,
+        None => {
+            eprintln!("--min-line-length requires a non-negative number");
+            std::process::exit(1);
+        }
+    }
+}
+
 fn get_all_files_for_mock_ai(working_dir: &str) -> Vec<String> {
     // Find the git repository
     let repo = match find_repository_in_path(working_dir) {
@@ -92,7 +92,7 @@ pub struct CommandHooksContext {
     pub rebase_onto: Option<String>,
     pub fetch_authorship_handle: Option<std::thread::JoinHandle<()>>,
     pub stash_sha: Option<String>,
-    pub push_authorship_handle: Option<std::thread::JoinHandle<()>>,
+    pub push_notes_plan: Option<push_hooks::PushNotesPlan>,
     /// VirtualAttributions captured before a pull --rebase --autostash operation.
     /// Used to preserve uncommitted AI attributions that git's internal stash would lose.
     pub stashed_va: Option<VirtualAttributions>,
@@ -115,9 +115,18 @@ pub fn handle_git(args: &[String]) {
 
     let config = config::Config::get();
 
-    let skip_hooks = !config.is_allowed_repository(&repository_option);
-
-    if skip_hooks {
+    let hooks_opted_out = parsed_args.no_ai_hooks || hooks_disabled_by_env();
+    let read_only = repository_option
+        .as_ref()
+        .is_some_and(|repository| repository.is_read_only());
+    let skip_hooks =
+        hooks_opted_out || read_only || !config.is_allowed_repository(&repository_option);
+
+    if hooks_opted_out {
+        debug_log("Skipping git-ai hooks because of --no-ai-hooks or GIT_AI_NO_HOOKS");
+    } else if read_only {
+        debug_log("Skipping git-ai hooks because the repository is read-only (ai.readOnly)");
+    } else if skip_hooks {
         debug_log(
             "Skipping git-ai hooks because repository is excluded or not in allow_repositories list",
         );
@@ -143,7 +152,7 @@ pub fn handle_git(args: &[String]) {
             rebase_onto: None,
             fetch_authorship_handle: None,
             stash_sha: None,
-            push_authorship_handle: None,
+            push_notes_plan: None,
             stashed_va: None,
         };
 
@@ -188,6 +197,14 @@ pub fn handle_git(args: &[String]) {
     exit_with_status(exit_status);
 }
 
+/// `GIT_AI_NO_HOOKS=1` runs git without any git-ai hooks, like `--no-ai-hooks`.
+fn hooks_disabled_by_env() -> bool {
+    matches!(
+        std::env::var("GIT_AI_NO_HOOKS").as_deref(),
+        Ok("1") | Ok("true")
+    )
+}
+
 /// Handle alias invocations
 #[cfg(feature = "test-support")]
 pub fn resolve_alias_invocation(
@@ -332,6 +349,9 @@ fn run_pre_command_hooks(
             Some("reset") => {
                 reset_hooks::pre_reset_hook(parsed_args, repository);
             }
+            Some("merge") => {
+                merge_hooks::pre_merge_hook(parsed_args, repository);
+            }
             Some("cherry-pick") => {
                 cherry_pick_hooks::pre_cherry_pick_hook(
                     parsed_args,
@@ -340,7 +360,7 @@ fn run_pre_command_hooks(
                 );
             }
             Some("push") => {
-                command_hooks_context.push_authorship_handle =
+                command_hooks_context.push_notes_plan =
                     push_hooks::push_pre_command_hook(parsed_args, repository);
             }
             Some("fetch") => {
@@ -0,0 +1,90 @@
+use crate::authorship::graft::graft_attribution;
+use crate::git::find_repository;
+
+/// `git-ai graft-attribution --from <old-branch> [--onto <rev>] [--dry-run]`: copy AI
+/// attribution from the old history onto commits of `--onto` (default HEAD) that recreated
+/// it, matching files by blob hash.
+pub fn handle_graft_attribution(args: &[String]) {
+    let mut from: Option<String> = None;
+    let mut onto = "HEAD".to_string();
+    let mut dry_run = false;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--from" | "--onto" => {
+                let Some(value) = args.get(i + 1) else {
+                    eprintln!("Error: {} requires a value", args[i]);
+                    std::process::exit(1);
+                };
+                if args[i] == "--from" {
+                    from = Some(value.clone());
+                } else {
+                    onto = value.clone();
+                }
+                i += 2;
+            }
+            "--dry-run" => {
+                dry_run = true;
+                i += 1;
+            }
+            arg => {
+                eprintln!("Unknown graft-attribution argument: {}", arg);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let Some(from) = from else {
+        eprintln!("Usage: git-ai graft-attribution --from <old-branch> [--onto <rev>] [--dry-run]");
+        std::process::exit(1);
+    };
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Failed to find repository: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let grafted = match graft_attribution(&repo, &from, &onto, dry_run) {
+        Ok(grafted) => grafted,
+        Err(e) => {
+            eprintln!("Graft failed: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    if grafted.is_empty() {
+        println!(
+            "No commit on {} without an authorship note shares AI-attributed files with {}",
+            onto, from
+        );
+        return;
+    }
+
+    let verb = if dry_run { "Would graft" } else { "Grafted" };
+    for commit in &grafted {
+        println!(
+            "{} {} AI lines onto {}",
+            verb,
+            commit.ai_lines(),
+            &commit.commit[..commit.commit.len().min(8)]
+        );
+        for file in &commit.files {
+            let source = if file.old_path == file.path {
+                String::new()
+            } else {
+                format!(" (was {})", file.old_path)
+            };
+            println!(
+                "  {}{}: {} lines from {}",
+                file.path,
+                source,
+                file.ai_lines,
+                &file.old_commit[..file.old_commit.len().min(8)]
+            );
+        }
+    }
+}
@@ -1,3 +1,4 @@
+use crate::authorship::policy::{self, ChangeAuthorship, CommitScope, PolicyStage};
 use crate::authorship::pre_commit;
 use crate::commands::git_handlers::CommandHooksContext;
 use crate::git::cli_parser::{ParsedGitInvocation, is_dry_run};
@@ -31,6 +32,20 @@ pub fn commit_pre_command_hook(
         eprintln!("Pre-commit failed: {}", e);
         std::process::exit(1);
     }
+
+    if !policy::enforce(
+        repository,
+        PolicyStage::Commit,
+        &parsed_args.command_args,
+        || {
+            ChangeAuthorship::from_commit_scope(
+                repository,
+                &commit_scope(&parsed_args.command_args),
+            )
+        },
+    ) {
+        std::process::exit(1);
+    }
     true
 }
 
@@ -176,6 +191,124 @@ pub fn get_commit_default_author(repo: &Repository, args: &[String]) -> String {
     }
 }
 
+/// Where the commit `args` describe takes its content from. `-a` and pathspecs pull files
+/// from the working tree after the pre-command hook has run, so the index alone doesn't say
+/// what gets committed.
+pub fn commit_scope(args: &[String]) -> CommitScope {
+    const SHORT_WITH_VALUE: &[char] = &['m', 'F', 'C', 'c', 't'];
+    const LONG_WITH_VALUE: &[&str] = &[
+        "--message",
+        "--file",
+        "--reuse-message",
+        "--reedit-message",
+        "--template",
+        "--author",
+        "--date",
+        "--cleanup",
+        "--squash",
+        "--trailer",
+    ];
+
+    let mut all = false;
+    let mut include = false;
+    let mut only = false;
+    let mut pathspecs = Vec::new();
+    let mut pathspec_file: Option<String> = None;
+    let mut pathspec_file_nul = false;
+    let mut i = 0;
+    while i < args.len() {
+        let arg = args[i].as_str();
+        if arg == "--" {
+            pathspecs.extend(args[i + 1..].iter().cloned());
+            break;
+        } else if let Some(long) = arg.strip_prefix("--") {
+            let (name, inline_value) = match long.split_once('=') {
+                Some((name, value)) => (name, Some(value.to_string())),
+                None => (long, None),
+            };
+            match name {
+                "all" => all = true,
+                "include" => include = true,
+                "only" => only = true,
+                "pathspec-file-nul" => pathspec_file_nul = true,
+                "pathspec-from-file" | "fixup" => {
+                    let value = inline_value.or_else(|| {
+                        i += 1;
+                        args.get(i).cloned()
+                    });
+                    if name == "fixup" {
+                        // `reword:` only rewrites the message, as if `--only` were given
+                        only |= value.is_some_and(|value| value.starts_with("reword:"));
+                    } else {
+                        pathspec_file = value;
+                    }
+                }
+                _ if inline_value.is_none() && LONG_WITH_VALUE.contains(&arg) => i += 1,
+                _ => {}
+            }
+        } else if let Some(short) = arg.strip_prefix('-').filter(|short| !short.is_empty()) {
+            // Clustered short flags (`-am msg`); a flag that takes a value consumes the rest
+            // of the cluster or, at its end, the next argument
+            for (offset, flag) in short.char_indices() {
+                match flag {
+                    'a' => all = true,
+                    'i' => include = true,
+                    'o' => only = true,
+                    _ if SHORT_WITH_VALUE.contains(&flag) => {
+                        if offset + flag.len_utf8() == short.len() {
+                            i += 1;
+                        }
+                        break;
+                    }
+                    _ => {}
+                }
+            }
+        } else {
+            pathspecs.push(arg.to_string());
+        }
+        i += 1;
+    }
+
+    if let Some(file) = pathspec_file {
+        match read_pathspec_file(&file, pathspec_file_nul) {
+            Some(from_file) => pathspecs.extend(from_file),
+            // Pathspecs on stdin are consumed by git; any tracked file may be committed
+            None => return CommitScope::AllTracked,
+        }
+    }
+
+    if !pathspecs.is_empty() {
+        CommitScope::Paths {
+            pathspecs,
+            include_index: include,
+        }
+    } else if all {
+        CommitScope::AllTracked
+    } else if only {
+        CommitScope::Unchanged
+    } else {
+        CommitScope::Index
+    }
+}
+
+/// Pathspecs listed in the `--pathspec-from-file` file, one per line or NUL-separated with
+/// `--pathspec-file-nul`. `None` when they come from stdin or the file can't be read.
+fn read_pathspec_file(file: &str, nul_separated: bool) -> Option<Vec<String>> {
+    if file == "-" {
+        return None;
+    }
+    let content = std::fs::read_to_string(file).ok()?;
+    let separator = if nul_separated { '\0' } else { '\n' };
+    Some(
+        content
+            .split(separator)
+            .map(|line| line.trim_end_matches('\r'))
+            .filter(|line| !line.is_empty())
+            .map(str::to_string)
+            .collect(),
+    )
+}
+
 fn extract_author_from_args(args: &[String]) -> Option<String> {
     let mut i = 0;
     while i < args.len() {
@@ -1,8 +1,10 @@
+use crate::authorship::squash_backfill::backfill_squash_merges_into;
 use crate::authorship::virtual_attribution::{VirtualAttributions, restore_stashed_va};
 use crate::commands::git_handlers::CommandHooksContext;
 use crate::commands::hooks::commit_hooks::get_commit_default_author;
 use crate::commands::hooks::rebase_hooks::build_rebase_commit_mappings;
 use crate::commands::upgrade;
+use crate::config::Config;
 use crate::git::cli_parser::{ParsedGitInvocation, is_dry_run};
 use crate::git::repository::{Repository, exec_git, find_repository};
 use crate::git::rewrite_log::RewriteLogEvent;
@@ -116,9 +118,9 @@ pub fn pull_pre_command_hook(
 }
 
 pub fn fetch_pull_post_command_hook(
-    _repository: &Repository,
-    _parsed_args: &ParsedGitInvocation,
-    _exit_status: std::process::ExitStatus,
+    repository: &Repository,
+    parsed_args: &ParsedGitInvocation,
+    exit_status: std::process::ExitStatus,
     command_hooks_context: &mut CommandHooksContext,
 ) {
     // Always wait for the authorship fetch thread to complete if it was started,
@@ -127,6 +129,35 @@ pub fn fetch_pull_post_command_hook(
     if let Some(handle) = command_hooks_context.fetch_authorship_handle.take() {
         let _ = handle.join();
     }
+
+    if exit_status.success() && !is_dry_run(&parsed_args.command_args) {
+        backfill_squash_merges_after_fetch(repository, parsed_args);
+    }
+}
+
+/// Give squash commits that landed on the remote's default branch outside git-ai (e.g. a
+/// GitHub "Squash and merge") the authorship of the local branch they squashed.
+///
+/// The scan diffs and patch-ids every candidate, so it only runs with the
+/// `fetch_squash_backfill` flag; `git-ai backfill` does the same for a single branch.
+fn backfill_squash_merges_after_fetch(repository: &Repository, parsed_args: &ParsedGitInvocation) {
+    if !Config::get().get_feature_flags().squash_backfill_on_fetch {
+        return;
+    }
+    let Ok(remote) = fetch_remote_from_args(repository, parsed_args) else {
+        return;
+    };
+    match backfill_squash_merges_into(repository, &format!("refs/remotes/{}/HEAD", remote)) {
+        Ok(backfilled) => {
+            for found in backfilled {
+                debug_log(&format!(
+                    "Backfilled authorship for squash commit {} from {}",
+                    found.squash_commit, found.source_head
+                ));
+            }
+        }
+        Err(e) => debug_log(&format!("squash merge backfill skipped: {}", e)),
+    }
 }
 
 /// Post-command hook for git pull.
@@ -149,6 +180,8 @@ pub fn pull_post_command_hook(
         return;
     }
 
+    backfill_squash_merges_after_fetch(repository, parsed_args);
+
     // Get old HEAD from pre-command capture
     let old_head = match &repository.pre_command_base_commit {
         Some(sha) => sha.clone(),
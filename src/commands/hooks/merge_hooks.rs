@@ -1,12 +1,31 @@
 use crate::{
+    authorship::pre_commit,
     commands::hooks::commit_hooks::get_commit_default_author,
     git::{
         cli_parser::{ParsedGitInvocation, is_dry_run},
         repository::Repository,
         rewrite_log::{MergeSquashEvent, RewriteLogEvent},
     },
+    utils::debug_log,
 };
 
+pub fn pre_merge_hook(parsed_args: &ParsedGitInvocation, repository: &mut Repository) {
+    if is_dry_run(&parsed_args.command_args) {
+        return;
+    }
+
+    // store HEAD context for post-command hook
+    repository.require_pre_command_head();
+
+    // `merge --continue` commits the resolution, so checkpoint it like `git commit` would
+    if parsed_args.has_command_flag("--continue") {
+        let default_author = get_commit_default_author(repository, &parsed_args.command_args);
+        if let Err(e) = pre_commit::pre_commit(repository, default_author) {
+            debug_log(&format!("Pre-commit before merge --continue failed: {}", e));
+        }
+    }
+}
+
 pub fn post_merge_hook(
     parsed_args: &ParsedGitInvocation,
     exit_status: std::process::ExitStatus,
@@ -45,5 +64,40 @@ pub fn post_merge_hook(
             false,
             true,
         );
+        return;
+    }
+
+    // The merge commit landed, either without conflicts or after `merge --continue`. Its
+    // authorship log is built from the working log like any other commit, cut down to the
+    // lines written while resolving.
+    if exit_status.success()
+        && !is_dry_run(&parsed_args.command_args)
+        && !parsed_args.has_command_flag("--abort")
+        && !parsed_args.has_command_flag("--quit")
+        && !parsed_args.has_command_flag("--no-commit")
+    {
+        let original_commit = repository.pre_command_base_commit.clone();
+        let Some(new_sha) = repository.head().ok().and_then(|h| h.target().ok()) else {
+            return;
+        };
+        if original_commit.as_deref() == Some(new_sha.as_str()) {
+            return;
+        }
+        // Fast-forwards move HEAD onto commits that already have their notes
+        let is_merge_commit = repository
+            .find_commit(new_sha.clone())
+            .and_then(|commit| commit.parent_count())
+            .is_ok_and(|count| count > 1);
+        if !is_merge_commit {
+            return;
+        }
+
+        let commit_author = get_commit_default_author(repository, &parsed_args.command_args);
+        repository.handle_rewrite_log_event(
+            RewriteLogEvent::commit(original_commit, new_sha),
+            commit_author,
+            true,
+            true,
+        );
     }
 }
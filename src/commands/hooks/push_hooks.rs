@@ -1,14 +1,23 @@
+use crate::authorship::policy::{self, ChangeAuthorship, PolicyStage};
 use crate::commands::git_handlers::CommandHooksContext;
 use crate::commands::upgrade;
+use crate::error::GitAiError;
 use crate::git::cli_parser::{ParsedGitInvocation, is_dry_run};
-use crate::git::repository::{Repository, find_repository};
-use crate::git::sync_authorship::push_authorship_notes;
+use crate::git::repository::{Repository, exec_git};
+use crate::git::sync_authorship::{SyncSettings, push_authorship_notes_for_commits};
 use crate::utils::debug_log;
 
+/// Remote and commits captured before `git push` runs, used to push their notes afterwards.
+#[derive(Debug, Clone)]
+pub struct PushNotesPlan {
+    pub remote: String,
+    pub commits: Vec<String>,
+}
+
 pub fn push_pre_command_hook(
     parsed_args: &ParsedGitInvocation,
     repository: &Repository,
-) -> Option<std::thread::JoinHandle<()>> {
+) -> Option<PushNotesPlan> {
     upgrade::maybe_schedule_background_update_check();
 
     // Early returns for cases where we shouldn't push authorship notes
@@ -47,53 +56,245 @@ pub fn push_pre_command_hook(
         .or_else(|| repository.upstream_remote().ok().flatten())
         .or_else(|| repository.get_default_remote().ok().flatten());
 
-    if let Some(remote) = remote {
-        debug_log(&format!(
-            "started pushing authorship notes to remote: {}",
-            remote
-        ));
-        // Clone what we need for the background thread
-        let global_args = repository.global_args_for_exec();
+    let Some(remote) = remote else {
+        // No remotes configured; skip silently
+        debug_log("no remotes found for authorship push; skipping");
+        return None;
+    };
 
-        crate::observability::spawn_background_flush();
+    // Work out which commits this push will send before the remote-tracking refs move;
+    // the policy checks them and the post-command hook only publishes notes for them.
+    let sources = extract_push_sources(&parsed_args.command_args);
+    let commits = commits_to_be_pushed(repository, &remote, &sources);
+    if !policy::enforce(
+        repository,
+        PolicyStage::Push,
+        &parsed_args.command_args,
+        || match &commits {
+            Ok(commits) => ChangeAuthorship::from_commits(repository, commits),
+            Err(e) => Err(GitAiError::Generic(format!(
+                "failed to resolve commits being pushed: {}",
+                e
+            ))),
+        },
+    ) {
+        std::process::exit(1);
+    }
 
-        // Spawn CAS flush if prompt_storage is "default" (CAS upload mode)
-        if crate::config::Config::get().prompt_storage() == "default" {
-            crate::commands::flush_cas::spawn_background_cas_flush();
-        }
+    crate::observability::spawn_background_flush();
 
-        // Spawn background thread to push authorship notes in parallel with main push
-        Some(std::thread::spawn(move || {
-            // Recreate repository in the background thread
-            if let Ok(repo) = find_repository(&global_args) {
-                if let Err(e) = push_authorship_notes(&repo, &remote) {
-                    debug_log(&format!("authorship push failed: {}", e));
-                }
-            } else {
-                debug_log("failed to open repository for authorship push");
-            }
-        }))
-    } else {
-        // No remotes configured; skip silently
-        debug_log("no remotes found for authorship push; skipping");
-        None
+    // Spawn CAS flush if prompt_storage is "default" (CAS upload mode)
+    if crate::config::Config::get().prompt_storage() == "default" {
+        crate::commands::flush_cas::spawn_background_cas_flush();
+    }
+
+    if crate::config::Config::get().notes_push_disabled() {
+        debug_log("authorship notes push disabled by config; skipping");
+        return None;
     }
+
+    let commits = commits.unwrap_or_else(|e| {
+        debug_log(&format!("failed to resolve commits being pushed: {}", e));
+        Vec::new()
+    });
+    debug_log(&format!(
+        "push to {} will send {} commit(s); notes will follow after the push",
+        remote,
+        commits.len()
+    ));
+    Some(PushNotesPlan { remote, commits })
 }
 
 pub fn push_post_command_hook(
-    _repository: &Repository,
+    repository: &Repository,
     _parsed_args: &ParsedGitInvocation,
-    _exit_status: std::process::ExitStatus,
+    exit_status: std::process::ExitStatus,
     command_hooks_context: &mut CommandHooksContext,
 ) {
-    // Always wait for the authorship push thread to complete if it was started,
-    // regardless of whether the main push succeeded or failed.
-    // This ensures proper cleanup of the background thread.
-    if let Some(handle) = command_hooks_context.push_authorship_handle.take() {
-        let _ = handle.join();
+    let Some(plan) = command_hooks_context.push_notes_plan.take() else {
+        return;
+    };
+    if !exit_status.success() || repository.is_read_only() {
+        return;
+    }
+
+    // Notes that failed to reach this remote on an earlier push go out with this one
+    let mut commits = repository.storage.read_pending_notes_push(&plan.remote);
+    for commit in plan.commits {
+        if !commits.contains(&commit) {
+            commits.push(commit);
+        }
+    }
+    if commits.is_empty() {
+        return;
+    }
+
+    let pending = match push_authorship_notes_for_commits(repository, &plan.remote, &commits) {
+        Ok(pushed) => {
+            if pushed {
+                debug_log(&format!("pushed authorship notes to {}", plan.remote));
+            }
+            Vec::new()
+        }
+        Err(e) => {
+            // The user's push already succeeded; never fail it because the notes didn't follow
+            debug_log(&format!("authorship push failed: {}", e));
+            eprintln!(
+                "Warning: {}",
+                describe_notes_push_failure(repository, &plan.remote, &e)
+            );
+            commits
+        }
+    };
+    if let Err(e) = repository
+        .storage
+        .write_pending_notes_push(&plan.remote, &pending)
+    {
+        debug_log(&format!("failed to record pending notes push: {}", e));
     }
 }
 
+/// Why the notes of a successful push didn't reach `remote`, and what happens next.
+fn describe_notes_push_failure(
+    repository: &Repository,
+    remote: &str,
+    error: &GitAiError,
+) -> String {
+    let notes_ref = SyncSettings::from_repository(repository).notes_ref;
+    let GitAiError::GitCliError { stderr, .. } = error else {
+        return format!(
+            "AI authorship notes were not pushed to {} ({}); they will be retried on the next push",
+            remote, error
+        );
+    };
+
+    let stderr = stderr.to_lowercase();
+    if stderr.contains("non-fast-forward") || stderr.contains("fetch first") {
+        format!(
+            "{} on {} has diverged from the local notes; AI authorship notes will be retried on the next push (or run `git-ai sync reconcile {}`)",
+            notes_ref, remote, remote
+        )
+    } else if stderr.contains("remote rejected")
+        || stderr.contains("denied")
+        || stderr.contains("declined")
+        || stderr.contains("protected")
+    {
+        format!(
+            "{} rejected {}; AI authorship notes were not pushed and will be retried on the next push (set git-ai.notesRef, git-ai.syncRemotes or git-ai.sync to change this)",
+            remote, notes_ref
+        )
+    } else if stderr.contains("could not read from remote")
+        || stderr.contains("unable to access")
+        || stderr.contains("authentication failed")
+        || stderr.contains("could not resolve host")
+    {
+        format!(
+            "could not reach {} to push {}; AI authorship notes will be retried on the next push",
+            remote, notes_ref
+        )
+    } else {
+        format!(
+            "pushing {} to {} failed; AI authorship notes will be retried on the next push",
+            notes_ref, remote
+        )
+    }
+}
+
+/// Commits reachable from the pushed refs that the remote does not have yet.
+fn commits_to_be_pushed(
+    repository: &Repository,
+    remote: &str,
+    sources: &PushSources,
+) -> Result<Vec<String>, GitAiError> {
+    let mut args = repository.global_args_for_exec();
+    args.push("rev-list".to_string());
+    if sources.all_branches {
+        args.push("--branches".to_string());
+    }
+    if sources.tags {
+        args.push("--tags".to_string());
+    }
+    args.extend(sources.refs.iter().cloned());
+    if !sources.all_branches && !sources.tags && sources.refs.is_empty() {
+        args.push("HEAD".to_string());
+    }
+    args.push("--not".to_string());
+    args.push(format!("--remotes={}", remote));
+    args.push("--".to_string());
+
+    let output = exec_git(&args)?;
+    Ok(String::from_utf8(output.stdout)?
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect())
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct PushSources {
+    refs: Vec<String>,
+    all_branches: bool,
+    tags: bool,
+}
+
+/// Source side of the refspecs given to `git push` (deletions are skipped).
+fn extract_push_sources(args: &[String]) -> PushSources {
+    let mut sources = PushSources::default();
+    let mut positionals: Vec<String> = Vec::new();
+    let mut remote_from_flag = false;
+
+    let mut i = 0;
+    while i < args.len() {
+        let arg = &args[i];
+        if arg == "--" {
+            positionals.extend(args[i + 1..].iter().cloned());
+            break;
+        }
+        if arg.starts_with('-') {
+            match arg.as_str() {
+                "--all" | "--branches" => sources.all_branches = true,
+                "--tags" | "--follow-tags" => sources.tags = true,
+                _ => {}
+            }
+            if let Some((flag, _)) = is_push_option_with_inline_value(arg) {
+                remote_from_flag |= flag == "--repo";
+                i += 1;
+                continue;
+            }
+            if option_consumes_separate_value(arg.as_str()) {
+                remote_from_flag |= arg == "--repo";
+                i += 2;
+                continue;
+            }
+            i += 1;
+            continue;
+        }
+        positionals.push(arg.clone());
+        i += 1;
+    }
+
+    let refspecs = if remote_from_flag {
+        &positionals[..]
+    } else {
+        positionals.get(1..).unwrap_or_default()
+    };
+    let mut iter = refspecs.iter();
+    while let Some(refspec) = iter.next() {
+        if refspec == "tag" {
+            if let Some(tag) = iter.next() {
+                sources.refs.push(format!("refs/tags/{}", tag));
+            }
+            continue;
+        }
+        let refspec = refspec.strip_prefix('+').unwrap_or(refspec);
+        let src = refspec.split(':').next().unwrap_or_default();
+        if !src.is_empty() {
+            sources.refs.push(src.to_string());
+        }
+    }
+    sources
+}
+
 fn extract_remote_from_push_args(args: &[String], known_remotes: &[String]) -> Option<String> {
     let mut i = 0;
     while i < args.len() {
@@ -149,3 +350,28 @@ fn option_consumes_separate_value(arg: &str) -> bool {
         "--repo" | "--receive-pack" | "--exec" | "-o" | "--push-option" | "-c" | "-C"
     )
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|s| s.to_string()).collect()
+    }
+
+    #[test]
+    fn test_extract_push_sources() {
+        let sources = extract_push_sources(&args(&["-u", "origin", "+main", "feature:release"]));
+        assert_eq!(sources.refs, vec!["main", "feature"]);
+
+        let sources = extract_push_sources(&args(&["origin", ":stale", "tag", "v1.0"]));
+        assert_eq!(sources.refs, vec!["refs/tags/v1.0"]);
+
+        let sources = extract_push_sources(&args(&["--repo", "origin", "HEAD"]));
+        assert_eq!(sources.refs, vec!["HEAD"]);
+
+        let sources = extract_push_sources(&args(&["--all", "origin"]));
+        assert!(sources.all_branches);
+        assert!(sources.refs.is_empty());
+    }
+}
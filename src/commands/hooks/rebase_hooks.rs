@@ -3,9 +3,10 @@ use crate::commands::git_handlers::CommandHooksContext;
 use crate::commands::hooks::commit_hooks::get_commit_default_author;
 use crate::git::cli_parser::ParsedGitInvocation;
 use crate::git::cli_parser::is_dry_run;
-use crate::git::repository::Repository;
-use crate::git::rewrite_log::RewriteLogEvent;
+use crate::git::repository::{Repository, exec_git, exec_git_stdin};
+use crate::git::rewrite_log::{RebaseProgressEvent, RewriteLogEvent};
 use crate::utils::debug_log;
+use std::collections::{HashMap, HashSet};
 
 pub fn pre_rebase_hook(
     parsed_args: &ParsedGitInvocation,
@@ -74,6 +75,7 @@ pub fn pre_rebase_hook(
         }
     } else {
         debug_log("Continuing existing rebase (will read original head from log in post-hook)");
+        snapshot_rebase_rewritten_list(repository);
     }
 }
 
@@ -98,8 +100,10 @@ pub fn handle_rebase_post_command(
     ));
 
     if is_in_progress {
-        // Rebase still in progress (conflict or not finished)
+        // Rebase still in progress (conflict or not finished). Git removes its rewritten-list
+        // when the rebase finishes, so record what has been rewritten so far.
         debug_log("⏸ Rebase still in progress, waiting for completion (conflict or multi-step)");
+        snapshot_rebase_rewritten_list(repository);
         return;
     }
 
@@ -290,14 +294,20 @@ fn process_completed_rebase(
         }
     ));
 
-    let rebase_event =
-        RewriteLogEvent::rebase_complete(crate::git::rewrite_log::RebaseCompleteEvent::new(
+    let rewritten =
+        resolve_rebase_rewrites(repository, original_head, &original_commits, &new_commits);
+    debug_log(&format!("Resolved rewrites: {:?}", rewritten));
+
+    let rebase_event = RewriteLogEvent::rebase_complete(
+        crate::git::rewrite_log::RebaseCompleteEvent::new(
             original_head.to_string(),
             new_head.clone(),
             is_interactive,
             original_commits.clone(),
             new_commits.clone(),
-        ));
+        )
+        .with_rewritten(rewritten),
+    );
 
     debug_log("Creating RebaseComplete event and rewriting authorship...");
     let commit_author = get_commit_default_author(repository, &parsed_args.command_args);
@@ -364,6 +374,244 @@ pub(crate) fn build_rebase_commit_mappings(
     Ok((original_commits, new_commits))
 }
 
+/// Read git's own record of rewritten commits (`<old> <new>` per line) from the
+/// in-progress rebase state directory.
+pub(crate) fn read_rebase_rewritten_list(repository: &Repository) -> Vec<(String, String)> {
+    let candidates = [
+        repository
+            .path()
+            .join("rebase-merge")
+            .join("rewritten-list"),
+        repository.path().join("rebase-apply").join("rewritten"),
+    ];
+
+    let mut rewritten = Vec::new();
+    for path in candidates {
+        let Ok(content) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        for line in content.lines() {
+            let mut parts = line.split_whitespace();
+            if let (Some(old), Some(new)) = (parts.next(), parts.next()) {
+                rewritten.push((old.to_string(), new.to_string()));
+            }
+        }
+    }
+    rewritten
+}
+
+/// Record the current rewritten-list in the rewrite log so it survives the rebase finishing.
+fn snapshot_rebase_rewritten_list(repository: &Repository) {
+    let rewritten = read_rebase_rewritten_list(repository);
+    if rewritten.is_empty() {
+        return;
+    }
+    let Some(start_event) = find_rebase_start_event(repository) else {
+        debug_log("Rebase rewritten-list found but no RebaseStart event to attach it to");
+        return;
+    };
+
+    let progress_event = RewriteLogEvent::rebase_progress(RebaseProgressEvent::new(
+        start_event.original_head,
+        rewritten,
+    ));
+    match repository.storage.append_rewrite_event(progress_event) {
+        Ok(_) => debug_log("✓ Logged RebaseProgress event"),
+        Err(e) => debug_log(&format!("✗ Failed to log RebaseProgress event: {}", e)),
+    }
+}
+
+/// Rewritten-list snapshots recorded for the rebase of `original_head`, oldest first.
+fn recorded_rebase_rewrites(repository: &Repository, original_head: &str) -> Vec<(String, String)> {
+    let Ok(events) = repository.storage.read_rewrite_events() else {
+        return Vec::new();
+    };
+
+    // Events are newest-first; stop at the Start event for this rebase
+    let mut snapshots = Vec::new();
+    for event in events {
+        match event {
+            RewriteLogEvent::RebaseProgress { rebase_progress }
+                if rebase_progress.original_head == original_head =>
+            {
+                snapshots.push(rebase_progress.rewritten);
+            }
+            RewriteLogEvent::RebaseStart { .. } => break,
+            _ => continue,
+        }
+    }
+    snapshots.into_iter().rev().flatten().collect()
+}
+
+/// Work out which original commit became which rebased commit, oldest first.
+///
+/// Sources, in order of trust:
+/// 1. git's rewritten-list, snapshotted whenever the rebase stopped
+/// 2. matching patch-ids (picks, rewords and reorders keep their patch-id)
+/// 3. file overlap between the remaining originals and the remaining new commits, which
+///    folds squashed/fixed-up commits into the commit they were combined with
+///
+/// Originals that can't be placed (dropped, or already upstream) are left out.
+pub(crate) fn resolve_rebase_rewrites(
+    repository: &Repository,
+    original_head: &str,
+    original_commits: &[String],
+    new_commits: &[String],
+) -> Vec<(String, String)> {
+    let original_set: HashSet<&str> = original_commits.iter().map(String::as_str).collect();
+    let new_set: HashSet<&str> = new_commits.iter().map(String::as_str).collect();
+
+    // Later snapshots win: an `edit` stop followed by `commit --amend` re-records the commit.
+    let mut mapping: HashMap<String, String> = HashMap::new();
+    let mut recorded = recorded_rebase_rewrites(repository, original_head);
+    recorded.extend(read_rebase_rewritten_list(repository));
+    for (old, new) in recorded {
+        if original_set.contains(old.as_str()) && new_set.contains(new.as_str()) {
+            mapping.insert(old, new);
+        }
+    }
+
+    let unmatched_originals = |mapping: &HashMap<String, String>| -> Vec<String> {
+        original_commits
+            .iter()
+            .filter(|commit| !mapping.contains_key(*commit))
+            .cloned()
+            .collect()
+    };
+    let unmatched_new = |mapping: &HashMap<String, String>| -> Vec<String> {
+        let targets: HashSet<&String> = mapping.values().collect();
+        new_commits
+            .iter()
+            .filter(|commit| !targets.contains(commit))
+            .cloned()
+            .collect()
+    };
+
+    let remaining_originals = unmatched_originals(&mapping);
+    let remaining_new = unmatched_new(&mapping);
+    if !remaining_originals.is_empty() && !remaining_new.is_empty() {
+        let mut commits = remaining_originals.clone();
+        commits.extend(remaining_new.iter().cloned());
+        let patch_ids = patch_ids_for_commits(repository, &commits);
+
+        let mut new_by_patch_id: HashMap<&str, &String> = HashMap::new();
+        for new_commit in &remaining_new {
+            if let Some(patch_id) = patch_ids.get(new_commit) {
+                new_by_patch_id
+                    .entry(patch_id.as_str())
+                    .or_insert(new_commit);
+            }
+        }
+        for original in &remaining_originals {
+            if let Some(patch_id) = patch_ids.get(original)
+                && let Some(new_commit) = new_by_patch_id.remove(patch_id.as_str())
+            {
+                mapping.insert(original.clone(), new_commit.clone());
+            }
+        }
+    }
+
+    let remaining_originals = unmatched_originals(&mapping);
+    let remaining_new = unmatched_new(&mapping);
+    if !remaining_originals.is_empty() && !remaining_new.is_empty() {
+        let mut commits = remaining_originals.clone();
+        commits.extend(remaining_new.iter().cloned());
+        let files_by_commit = changed_files_for_commits(repository, &commits);
+        let empty = HashSet::new();
+
+        for original in &remaining_originals {
+            let original_files = files_by_commit.get(original).unwrap_or(&empty);
+            let best = remaining_new
+                .iter()
+                .map(|new_commit| {
+                    let new_files = files_by_commit.get(new_commit).unwrap_or(&empty);
+                    (original_files.intersection(new_files).count(), new_commit)
+                })
+                .filter(|(overlap, _)| *overlap > 0)
+                // max_by_key keeps the last maximum; reverse so ties go to the earliest commit
+                .rev()
+                .max_by_key(|(overlap, _)| *overlap);
+            if let Some((_, new_commit)) = best {
+                mapping.insert(original.clone(), new_commit.clone());
+            }
+        }
+    }
+
+    original_commits
+        .iter()
+        .filter_map(|original| {
+            mapping
+                .get(original)
+                .map(|new_commit| (original.clone(), new_commit.clone()))
+        })
+        .collect()
+}
+
+/// Stable patch-id for each non-merge commit, keyed by commit SHA.
+fn patch_ids_for_commits(repository: &Repository, commits: &[String]) -> HashMap<String, String> {
+    let mut log_args = repository.global_args_for_exec();
+    log_args.push("log".to_string());
+    log_args.push("--no-walk=unsorted".to_string());
+    log_args.push("-p".to_string());
+    log_args.push("--no-color".to_string());
+    log_args.push("--no-ext-diff".to_string());
+    log_args.push("--format=commit %H".to_string());
+    log_args.extend(commits.iter().cloned());
+    let Ok(log_output) = exec_git(&log_args) else {
+        return HashMap::new();
+    };
+
+    let mut patch_id_args = repository.global_args_for_exec();
+    patch_id_args.push("patch-id".to_string());
+    patch_id_args.push("--stable".to_string());
+    let Ok(output) = exec_git_stdin(&patch_id_args, &log_output.stdout) else {
+        return HashMap::new();
+    };
+
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter_map(|line| {
+            let mut parts = line.split_whitespace();
+            let patch_id = parts.next()?;
+            let commit = parts.next()?;
+            Some((commit.to_string(), patch_id.to_string()))
+        })
+        .collect()
+}
+
+/// Paths touched by each commit (relative to its first parent), keyed by commit SHA.
+fn changed_files_for_commits(
+    repository: &Repository,
+    commits: &[String],
+) -> HashMap<String, HashSet<String>> {
+    let mut args = repository.global_args_for_exec();
+    args.push("log".to_string());
+    args.push("--no-walk=unsorted".to_string());
+    args.push("--name-only".to_string());
+    args.push("--format=%x00%H".to_string());
+    args.extend(commits.iter().cloned());
+    let Ok(output) = exec_git(&args) else {
+        return HashMap::new();
+    };
+
+    let mut files_by_commit: HashMap<String, HashSet<String>> = HashMap::new();
+    let mut current: Option<String> = None;
+    for line in String::from_utf8_lossy(&output.stdout).lines() {
+        if let Some(commit) = line.strip_prefix('\0') {
+            current = Some(commit.to_string());
+            files_by_commit.entry(commit.to_string()).or_default();
+        } else if !line.is_empty()
+            && let Some(commit) = &current
+        {
+            files_by_commit
+                .entry(commit.clone())
+                .or_default()
+                .insert(line.to_string());
+        }
+    }
+    files_by_commit
+}
+
 fn resolve_rebase_original_head(
     parsed_args: &ParsedGitInvocation,
     repository: &Repository,
@@ -421,7 +669,7 @@ fn is_ancestor(repository: &Repository, ancestor: &str, descendant: &str) -> boo
     args.push("--is-ancestor".to_string());
     args.push(ancestor.to_string());
     args.push(descendant.to_string());
-    crate::git::repository::exec_git(&args).is_ok()
+    exec_git(&args).is_ok()
 }
 
 struct RebaseArgsSummary {
@@ -531,6 +779,7 @@ mod tests {
             command_args: args.iter().map(|s| s.to_string()).collect(),
             saw_end_of_opts: false,
             is_help: false,
+            no_ai_hooks: false,
         }
     }
 
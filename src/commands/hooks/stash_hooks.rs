@@ -1,25 +1,28 @@
-use crate::authorship::virtual_attribution::VirtualAttributions;
+use crate::authorship::attribution_tracker::line_attributions_to_attributions;
+use crate::authorship::virtual_attribution::{VirtualAttributions, restore_stashed_va};
 use crate::authorship::working_log::CheckpointKind;
 use crate::commands::git_handlers::CommandHooksContext;
 use crate::commands::hooks::commit_hooks::get_commit_default_author;
 use crate::error::GitAiError;
 use crate::git::cli_parser::ParsedGitInvocation;
+use crate::git::repo_storage::StashAttributions;
 use crate::git::repository::{Repository, exec_git, exec_git_stdin};
+use crate::git::status::EntryKind;
 use crate::utils::debug_log;
+use std::time::{SystemTime, UNIX_EPOCH};
 
 pub fn pre_stash_hook(
     parsed_args: &ParsedGitInvocation,
     repository: &mut Repository,
     command_hooks_context: &mut CommandHooksContext,
 ) {
-    // Check if this is a pop or apply command - we need to capture the stash SHA before Git deletes it
-    let subcommand = match parsed_args.pos_command(0) {
-        Some(cmd) => cmd,
-        None => return, // Implicit push, nothing to capture
-    };
+    // No subcommand means implicit "push"
+    let subcommand = parsed_args
+        .pos_command(0)
+        .unwrap_or_else(|| "push".to_string());
 
-    if subcommand == "pop" || subcommand == "apply" {
-        // Capture the stash SHA BEFORE git runs (pop will delete it)
+    if subcommand == "pop" || subcommand == "apply" || subcommand == "drop" {
+        // Capture the stash SHA BEFORE git runs (pop and drop will delete it)
         let stash_ref = parsed_args
             .pos_command(1)
             .unwrap_or_else(|| "stash@{0}".to_string());
@@ -54,11 +57,6 @@ pub fn post_stash_hook(
     repository: &mut Repository,
     exit_status: std::process::ExitStatus,
 ) {
-    if !exit_status.success() {
-        debug_log("Stash failed, skipping post-stash hook");
-        return;
-    }
-
     // Check what subcommand was used
     let subcommand = match parsed_args.pos_command(0) {
         Some(cmd) => cmd,
@@ -68,6 +66,18 @@ pub fn post_stash_hook(
         }
     };
 
+    if !exit_status.success() {
+        // A pop/apply that stops on conflicts still leaves the stashed changes in the
+        // working tree (and keeps the stash entry), so their attributions are restored too
+        let applied_with_conflicts =
+            (subcommand == "pop" || subcommand == "apply") && has_unmerged_paths(repository);
+        if !applied_with_conflicts {
+            debug_log("Stash failed, skipping post-stash hook");
+            return;
+        }
+        debug_log("Stash applied with conflicts, restoring attributions");
+    }
+
     debug_log(&format!("Post-stash: processing stash {}", subcommand));
 
     // Handle different subcommands
@@ -95,14 +105,41 @@ pub fn post_stash_hook(
             stash_sha
         ));
 
-        let human_author = get_commit_default_author(repository, &parsed_args.command_args);
+        // Prefer the full snapshot, which can be remapped onto whatever HEAD and working
+        // tree the stash is applied to; stashes created by older versions only have a note
+        if let Some(stash_attributions) = repository.storage.read_stash_attributions(&stash_sha) {
+            if let Err(e) = restore_stash_snapshot(repository, stash_attributions) {
+                debug_log(&format!("Failed to restore stash snapshot: {}", e));
+            }
+        } else {
+            let human_author = get_commit_default_author(repository, &parsed_args.command_args);
+
+            if let Err(e) = restore_stash_attributions(repository, &stash_sha, &human_author) {
+                debug_log(&format!("Failed to restore stash attributions: {}", e));
+            }
+        }
 
-        if let Err(e) = restore_stash_attributions(repository, &stash_sha, &human_author) {
-            debug_log(&format!("Failed to restore stash attributions: {}", e));
+        // git keeps the stash entry when pop hits conflicts
+        if subcommand == "pop" && exit_status.success() {
+            repository.storage.delete_stash_attributions(&stash_sha);
         }
+    } else if subcommand == "drop" {
+        if let Some(stash_sha) = &command_hooks_context.stash_sha {
+            repository.storage.delete_stash_attributions(stash_sha);
+        }
+    } else if subcommand == "clear"
+        && let Err(e) = repository.storage.clear_stash_attributions()
+    {
+        debug_log(&format!("Failed to clear stash attributions: {}", e));
     }
 }
 
+fn has_unmerged_paths(repo: &Repository) -> bool {
+    repo.status(None, true)
+        .map(|entries| entries.iter().any(|e| e.kind == EntryKind::Unmerged))
+        .unwrap_or(false)
+}
+
 /// Save the current working log as an authorship log in git notes (refs/notes/ai-stash)
 fn save_stash_authorship_log(repo: &Repository, pathspecs: &[String]) -> Result<(), GitAiError> {
     let head_sha = repo.head()?.target()?.to_string();
@@ -151,6 +188,29 @@ fn save_stash_authorship_log(repo: &Repository, pathspecs: &[String]) -> Result<
         .attestations
         .retain(|a| filtered_files.contains(&a.file_path));
 
+    // Snapshot the attributions together with the stashed content so pop/apply can remap
+    // them onto a different HEAD or a conflicted working tree
+    let stash_attributions = StashAttributions {
+        base_commit: head_sha.clone(),
+        files: filtered_files
+            .iter()
+            .filter_map(|file| {
+                working_log_va
+                    .get_line_attributions(file)
+                    .map(|attrs| (file.clone(), attrs.clone()))
+            })
+            .collect(),
+        file_contents: filtered_files
+            .iter()
+            .filter_map(|file| {
+                stashed_file_content(repo, &stash_sha, file).map(|content| (file.clone(), content))
+            })
+            .collect(),
+        prompts: working_log_va.prompts().clone(),
+    };
+    repo.storage
+        .write_stash_attributions(&stash_sha, &stash_attributions)?;
+
     // Save as git note at refs/notes/ai-stash
     let json = authorship_log
         .serialize_to_string()
@@ -172,6 +232,59 @@ fn save_stash_authorship_log(repo: &Repository, pathspecs: &[String]) -> Result<
     Ok(())
 }
 
+/// Content of `file` as it was stashed. The working tree has already been reset by the time
+/// the post-hook runs, so it is read from the stash commit (or, for untracked files stashed
+/// with `-u`, its third parent).
+fn stashed_file_content(repo: &Repository, stash_sha: &str, file: &str) -> Option<String> {
+    for rev in [stash_sha.to_string(), format!("{}^3", stash_sha)] {
+        let mut args = repo.global_args_for_exec();
+        args.push("show".to_string());
+        args.push(format!("{}:{}", rev, file));
+        if let Ok(output) = exec_git(&args) {
+            return String::from_utf8(output.stdout).ok();
+        }
+    }
+    None
+}
+
+/// Re-merge a stash snapshot into the working log of the current HEAD
+fn restore_stash_snapshot(
+    repo: &mut Repository,
+    stash_attributions: StashAttributions,
+) -> Result<(), GitAiError> {
+    let head_sha = repo.head()?.target()?.to_string();
+    let ts = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+
+    let StashAttributions {
+        base_commit,
+        files,
+        file_contents,
+        prompts,
+    } = stash_attributions;
+    let attributions = files
+        .into_iter()
+        .filter_map(|(file, line_attrs)| {
+            let content = file_contents.get(&file)?;
+            let char_attrs = line_attributions_to_attributions(&line_attrs, content, ts);
+            Some((file, (char_attrs, line_attrs)))
+        })
+        .collect();
+
+    let stashed_va = VirtualAttributions::new_with_prompts(
+        repo.clone(),
+        base_commit.clone(),
+        attributions,
+        file_contents,
+        prompts,
+        ts,
+    );
+    restore_stashed_va(repo, &base_commit, &head_sha, stashed_va);
+    Ok(())
+}
+
 /// Restore attributions from a stash by reading the git note and converting to INITIAL attributions
 fn restore_stash_attributions(
     repo: &Repository,
@@ -243,8 +356,12 @@ fn restore_stash_attributions(
 
     // Write INITIAL attributions to working log
     if !initial_files.is_empty() || !initial_prompts.is_empty() {
+        // Merge into the existing INITIAL so attributions of files the stash didn't touch survive
         let working_log = repo.storage.working_log_for_base_commit(&head_sha);
-        working_log.write_initial_attributions(initial_files.clone(), initial_prompts.clone())?;
+        let mut initial = working_log.read_initial_attributions();
+        initial.files.extend(initial_files);
+        initial.prompts.extend(initial_prompts);
+        working_log.write_initial_attributions(initial.files, initial.prompts)?;
 
         debug_log(&format!(
             "✓ Wrote INITIAL attributions to working log for {}",
@@ -430,8 +547,16 @@ fn delete_working_log_for_files(
     // Write back the modified attributions
     working_log.write_initial_attributions(initial_attrs.files, initial_attrs.prompts)?;
 
-    // Note: We're not modifying checkpoints here as they're historical records
-    // The files were stashed, so we just remove them from the initial attributions
+    // The stash snapshot now owns these files' attributions; left in the checkpoints they
+    // would be applied to whatever is committed at the same lines before the stash is popped
+    let mut checkpoints = working_log.read_all_checkpoints()?;
+    for checkpoint in &mut checkpoints {
+        checkpoint
+            .entries
+            .retain(|entry| !files.contains(&entry.file));
+    }
+    checkpoints.retain(|checkpoint| !checkpoint.entries.is_empty());
+    working_log.write_all_checkpoints(&checkpoints)?;
 
     Ok(())
 }
@@ -1,4 +1,5 @@
 use crate::commands::flush_metrics_db::spawn_background_metrics_db_flush;
+use crate::commands::upgrade::maybe_migrate_after_upgrade;
 use crate::error::GitAiError;
 use crate::mdm::agents::get_all_installers;
 use crate::mdm::git_client_installer::GitClientInstallerParams;
@@ -122,6 +123,11 @@ pub fn run(args: &[String]) -> Result<HashMap<String, String>, GitAiError> {
     // Run async operations with smol and convert result
     let statuses = smol::block_on(async_run_install(&params, dry_run, verbose))?;
 
+    // Installers run this after placing a new binary, so migrate the shims it left behind
+    if !dry_run {
+        maybe_migrate_after_upgrade();
+    }
+
     // Spawn background processes to flush metrics
     crate::observability::spawn_background_flush();
     spawn_background_metrics_db_flush();
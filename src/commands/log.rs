@@ -0,0 +1,272 @@
+use crate::authorship::diff_annotation::{
+    DiffAnnotationSummary, annotated_commit, commit_annotation_summary,
+};
+use crate::git::find_repository;
+use crate::git::repository::{Repository, exec_git};
+use std::io::IsTerminal;
+
+/// Separates the graph drawing from the commit fields, and the fields from each other
+const RECORD_START: char = '\x1e';
+const FIELD_SEP: char = '\x1f';
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LogFormat {
+    Patch,
+    Json,
+}
+
+/// `git-ai log [--graph] [--format <text|json>] [<git log options>] [<revision range>] [[--] <path>...]`
+///
+/// Like `git log -p`, but every added line is marked with its AI or human author and each
+/// commit gets a summary of its AI share. `--graph` draws the commit graph instead, with each
+/// node marked by its AI share; `--format json` prints per-commit totals.
+pub fn handle_log(args: &[String]) {
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Failed to find repository: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let (rev_args, pathspecs) = match args.iter().position(|arg| arg == "--") {
+        Some(separator) => (&args[..separator], args[separator + 1..].to_vec()),
+        None => (args, Vec::new()),
+    };
+
+    let mut graph = false;
+    let mut format = LogFormat::Patch;
+    let mut git_args = Vec::new();
+    let mut i = 0;
+    while i < rev_args.len() {
+        match rev_args[i].as_str() {
+            "--graph" => graph = true,
+            "--format" => {
+                i += 1;
+                format = parse_format(rev_args.get(i).map(String::as_str));
+            }
+            arg if arg.starts_with("--format=") => {
+                format = parse_format(arg.strip_prefix("--format="));
+            }
+            _ => git_args.push(rev_args[i].clone()),
+        }
+        i += 1;
+    }
+
+    if format == LogFormat::Json {
+        print_json(&repo, &git_args, &pathspecs);
+    } else if graph {
+        print_graph(&repo, &git_args, &pathspecs);
+    } else {
+        print_patches(&repo, &git_args, &pathspecs);
+    }
+}
+
+fn parse_format(value: Option<&str>) -> LogFormat {
+    match value {
+        Some("json") => LogFormat::Json,
+        Some("text") => LogFormat::Patch,
+        Some(other) => {
+            eprintln!(
+                "Error: unsupported log format: {} (use text or json)",
+                other
+            );
+            std::process::exit(1);
+        }
+        None => {
+            eprintln!("Error: --format requires a value (text or json)");
+            std::process::exit(1);
+        }
+    }
+}
+
+fn print_patches(repo: &Repository, rev_args: &[String], pathspecs: &[String]) {
+    // The patch is always shown, and the commit list only needs hashes
+    let mut log_args = repo.global_args_for_exec();
+    log_args.push("log".to_string());
+    log_args.push("--format=%H".to_string());
+    log_args.extend(
+        rev_args
+            .iter()
+            .filter(|arg| !matches!(arg.as_str(), "-p" | "-u" | "--patch"))
+            .cloned(),
+    );
+    let commits = run_log(log_args, pathspecs);
+
+    let use_color = std::io::stdout().is_terminal();
+    for (index, sha) in commits.lines().filter(|l| !l.is_empty()).enumerate() {
+        if index > 0 {
+            println!();
+        }
+        match annotated_commit(repo, sha, pathspecs, use_color) {
+            Ok(output) => print!("{}", output),
+            Err(e) => {
+                eprintln!("Failed to annotate commit {}: {}", sha, e);
+                std::process::exit(1);
+            }
+        }
+    }
+}
+
+/// One line per commit (`--graph` adds the graph drawing before it and graph-only lines
+/// between commits).
+fn list_commits(
+    repo: &Repository,
+    rev_args: &[String],
+    pathspecs: &[String],
+    graph: bool,
+) -> String {
+    let mut log_args = repo.global_args_for_exec();
+    log_args.push("log".to_string());
+    log_args.push("--format=%x1e%H%x1f%h%x1f%P%x1f%an%x1f%s".to_string());
+    if graph {
+        log_args.push("--graph".to_string());
+    }
+    log_args.extend(
+        rev_args
+            .iter()
+            .filter(|arg| {
+                !matches!(arg.as_str(), "-p" | "-u" | "--patch" | "--oneline")
+                    && !arg.starts_with("--pretty")
+            })
+            .cloned(),
+    );
+    run_log(log_args, pathspecs)
+}
+
+fn run_log(mut log_args: Vec<String>, pathspecs: &[String]) -> String {
+    if !pathspecs.is_empty() {
+        log_args.push("--".to_string());
+        log_args.extend(pathspecs.iter().cloned());
+    }
+    match exec_git(&log_args) {
+        Ok(output) => String::from_utf8_lossy(&output.stdout).to_string(),
+        Err(e) => {
+            eprintln!("Failed to list commits: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+struct LogEntry<'a> {
+    sha: &'a str,
+    short_sha: &'a str,
+    parents: Vec<&'a str>,
+    author: &'a str,
+    subject: &'a str,
+}
+
+fn parse_entry(record: &str) -> Option<LogEntry<'_>> {
+    let mut fields = record.splitn(5, FIELD_SEP);
+    Some(LogEntry {
+        sha: fields.next()?,
+        short_sha: fields.next()?,
+        parents: fields.next()?.split_whitespace().collect(),
+        author: fields.next()?,
+        subject: fields.next().unwrap_or_default(),
+    })
+}
+
+fn summary_for(repo: &Repository, sha: &str, pathspecs: &[String]) -> DiffAnnotationSummary {
+    match commit_annotation_summary(repo, sha, pathspecs) {
+        Ok(summary) => summary,
+        Err(e) => {
+            eprintln!("Failed to annotate commit {}: {}", sha, e);
+            std::process::exit(1);
+        }
+    }
+}
+
+/// Graph node for a commit: `A` mostly AI (75% or more of its added lines), `M` mixed
+/// (25% or more), `H` mostly human, and git's `*` when the commit has no authorship log.
+fn graph_node(summary: &DiffAnnotationSummary) -> (char, &'static str) {
+    if !summary.has_log {
+        return ('*', "");
+    }
+    match summary.ai_percent() {
+        75.. => ('A', "\x1b[1;35m"),
+        25.. => ('M', "\x1b[1;33m"),
+        _ => ('H', "\x1b[1;32m"),
+    }
+}
+
+fn print_graph(repo: &Repository, rev_args: &[String], pathspecs: &[String]) {
+    let output = list_commits(repo, rev_args, pathspecs, true);
+    let use_color = std::io::stdout().is_terminal();
+    for line in output.lines() {
+        let Some((drawing, record)) = line.split_once(RECORD_START) else {
+            println!("{}", line);
+            continue;
+        };
+        let Some(entry) = parse_entry(record) else {
+            println!("{}", line);
+            continue;
+        };
+
+        let summary = summary_for(repo, entry.sha, pathspecs);
+        let (node, color) = graph_node(&summary);
+        let node = if use_color && !color.is_empty() {
+            format!("{}{}\x1b[0m", color, node)
+        } else {
+            node.to_string()
+        };
+        let drawing = match drawing.rfind('*') {
+            Some(index) => format!("{}{}{}", &drawing[..index], node, &drawing[index + 1..]),
+            None => drawing.to_string(),
+        };
+        let share = summary.summary_line();
+        if use_color {
+            println!(
+                "{}\x1b[33m{}\x1b[0m {} ({}) \x1b[2m[{}]\x1b[0m",
+                drawing, entry.short_sha, entry.subject, entry.author, share
+            );
+        } else {
+            println!(
+                "{}{} {} ({}) [{}]",
+                drawing, entry.short_sha, entry.subject, entry.author, share
+            );
+        }
+    }
+}
+
+fn print_json(repo: &Repository, rev_args: &[String], pathspecs: &[String]) {
+    let output = list_commits(repo, rev_args, pathspecs, false);
+    let mut commits = Vec::new();
+    for line in output.lines() {
+        let Some(entry) = line.strip_prefix(RECORD_START).and_then(parse_entry) else {
+            continue;
+        };
+        let summary = summary_for(repo, entry.sha, pathspecs);
+        let agents: Vec<_> = summary
+            .ai_lines_by_agent
+            .iter()
+            .map(|((tool, model), lines)| {
+                serde_json::json!({
+                    "tool": tool,
+                    "model": model,
+                    "lines": lines,
+                })
+            })
+            .collect();
+        commits.push(serde_json::json!({
+            "commit": entry.sha,
+            "parents": entry.parents,
+            "author": entry.author,
+            "subject": entry.subject,
+            "has_authorship_log": summary.has_log,
+            "added_lines": summary.added_lines,
+            "ai_lines": summary.ai_lines,
+            "human_lines": summary.added_lines - summary.ai_lines,
+            "ai_percent": summary.ai_percent(),
+            "agents": agents,
+        }));
+    }
+
+    match serde_json::to_string_pretty(&commits) {
+        Ok(output) => println!("{}", output),
+        Err(e) => {
+            eprintln!("Failed to serialize log: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
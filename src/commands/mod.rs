@@ -1,29 +1,51 @@
+pub mod assert_attributed;
+pub mod backfill;
+pub mod bench;
 pub mod blame;
 pub mod checkpoint;
 pub mod checkpoint_agent;
 pub mod ci_handlers;
 pub mod config;
 pub mod continue_session;
+pub mod cross_check;
+pub mod deletions;
+pub mod demo;
 pub mod diff;
+pub mod events;
 pub mod exchange_nonce;
+pub mod features;
 pub mod flush_cas;
 pub mod flush_logs;
 pub mod flush_metrics_db;
 pub mod git_ai_handlers;
 pub mod git_handlers;
+pub mod graft_attribution;
 pub mod hooks;
 pub mod install_hooks;
+pub mod log;
 pub mod login;
 pub mod logout;
+pub mod notes;
 pub mod personal_dashboard;
+pub mod plumbing;
+pub mod policy;
+#[cfg(feature = "tui")]
 pub mod prompt_picker;
 pub mod prompts_db;
+pub mod provenance;
+pub mod reassign;
+pub mod review;
+pub mod sandbox;
 pub mod search;
+pub mod serve;
 pub mod share;
+#[cfg(feature = "tui")]
 pub mod share_tui;
 pub mod show;
 pub mod show_prompt;
 pub mod squash_authorship;
 pub mod status;
+pub mod sync;
 pub mod sync_prompts;
 pub mod upgrade;
+pub mod with;
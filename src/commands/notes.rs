@@ -0,0 +1,70 @@
+use crate::git::find_repository;
+use crate::git::sync_authorship::replicate_notes;
+
+/// `git-ai notes <subcommand>`
+pub fn handle_notes(args: &[String]) {
+    match args.first().map(String::as_str) {
+        Some("replicate") => handle_replicate(&args[1..]),
+        _ => {
+            eprintln!("Usage: git-ai notes replicate --from <remote> --to <remote>");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// `git-ai notes replicate --from <remote> --to <remote>`: copy the authorship notes of
+/// commits both remotes share from one to the other.
+fn handle_replicate(args: &[String]) {
+    let mut from: Option<String> = None;
+    let mut to: Option<String> = None;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--from" | "--to" => {
+                let Some(value) = args.get(i + 1) else {
+                    eprintln!("Error: {} requires a remote", args[i]);
+                    std::process::exit(1);
+                };
+                if args[i] == "--from" {
+                    from = Some(value.clone());
+                } else {
+                    to = Some(value.clone());
+                }
+                i += 2;
+            }
+            arg => {
+                eprintln!("Unknown notes replicate argument: {}", arg);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let (Some(from), Some(to)) = (from, to) else {
+        eprintln!("Usage: git-ai notes replicate --from <remote> --to <remote>");
+        std::process::exit(1);
+    };
+    if from == to {
+        eprintln!("Error: --from and --to name the same remote");
+        std::process::exit(1);
+    }
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Failed to find repository: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    match replicate_notes(&repo, &from, &to) {
+        Ok(replication) => println!(
+            "Replicated {} note(s) from {} to {} ({} shared commit(s) with notes)",
+            replication.pushed, from, to, replication.shared_commits
+        ),
+        Err(e) => {
+            eprintln!("Failed to replicate notes from {} to {}: {}", from, to, e);
+            std::process::exit(1);
+        }
+    }
+}
@@ -0,0 +1,329 @@
+//! `git-ai plumbing`: low-level, scriptable views of git-ai's internal state.
+//!
+//! Output schemas here are stable: fields are only ever added, never renamed or removed,
+//! so scripts don't have to read files under `.git/ai` directly.
+
+use crate::authorship::attribution_tracker::LineAttribution;
+use crate::authorship::working_log::Checkpoint;
+use crate::error::GitAiError;
+use crate::git::find_repository;
+use crate::git::repo_storage::InitialAttributions;
+use crate::git::repository::Repository;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Bumped only for incompatible changes to the dump schema.
+const WORKING_LOG_DUMP_VERSION: u32 = 1;
+
+pub fn handle_plumbing(args: &[String]) {
+    match args.first().map(String::as_str) {
+        Some("working-log") => handle_working_log(&args[1..]),
+        Some(other) => {
+            eprintln!("Error: unknown plumbing command: {}", other);
+            print_plumbing_help();
+            std::process::exit(1);
+        }
+        None => {
+            print_plumbing_help();
+            std::process::exit(1);
+        }
+    }
+}
+
+fn print_plumbing_help() {
+    eprintln!("Usage: git-ai plumbing <command>");
+    eprintln!();
+    eprintln!("Commands:");
+    eprintln!("  working-log dump [--base <rev>] [--format json]");
+    eprintln!(
+        "                     Print working log checkpoints, INITIAL attributions and metadata"
+    );
+}
+
+fn handle_working_log(args: &[String]) {
+    if args.first().map(String::as_str) != Some("dump") {
+        print_plumbing_help();
+        std::process::exit(1);
+    }
+
+    let mut base: Option<String> = None;
+    let mut i = 1;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--base" => {
+                i += 1;
+                match args.get(i) {
+                    Some(rev) => base = Some(rev.clone()),
+                    None => {
+                        eprintln!("Error: --base requires a commit");
+                        std::process::exit(1);
+                    }
+                }
+            }
+            arg if arg.starts_with("--base=") => {
+                base = arg.strip_prefix("--base=").map(str::to_string);
+            }
+            "--format" => {
+                i += 1;
+                require_json_format(args.get(i).map(String::as_str));
+            }
+            arg if arg.starts_with("--format=") => {
+                require_json_format(arg.strip_prefix("--format="));
+            }
+            "--json" => {}
+            arg => {
+                eprintln!("Error: unknown working-log dump option: {}", arg);
+                std::process::exit(1);
+            }
+        }
+        i += 1;
+    }
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Failed to find repository: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let result = dump_working_log(&repo, base.as_deref())
+        .and_then(|dump| serde_json::to_string_pretty(&dump).map_err(GitAiError::from));
+    match result {
+        Ok(json) => println!("{}", json),
+        Err(e) => {
+            eprintln!("Failed to dump working log: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn require_json_format(format: Option<&str>) {
+    match format {
+        Some("json") => {}
+        Some(other) => {
+            eprintln!(
+                "Error: unsupported format '{}' (only json is supported)",
+                other
+            );
+            std::process::exit(1);
+        }
+        None => {
+            eprintln!("Error: --format requires a value");
+            std::process::exit(1);
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct WorkingLogDump {
+    pub version: u32,
+    pub base_commit: String,
+    /// Whether any working log has been recorded for this base commit.
+    pub exists: bool,
+    pub path: String,
+    pub checkpoints: Vec<CheckpointDump>,
+    pub initial: InitialDump,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CheckpointDump {
+    pub index: usize,
+    /// "human", "ai_agent" or "ai_tab"
+    pub kind: String,
+    pub author: String,
+    pub timestamp: u64,
+    pub agent: Option<AgentDump>,
+    pub agent_metadata: BTreeMap<String, String>,
+    pub line_stats: LineStatsDump,
+    pub transcript_message_count: usize,
+    pub whole_file_assets: Vec<String>,
+    pub api_version: String,
+    pub git_ai_version: Option<String>,
+    pub files: Vec<FileEntryDump>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AgentDump {
+    pub tool: String,
+    pub session_id: String,
+    pub model: String,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LineStatsDump {
+    pub additions: u32,
+    pub deletions: u32,
+    pub additions_sloc: u32,
+    pub deletions_sloc: u32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FileEntryDump {
+    pub path: String,
+    /// SHA-256 of the file content at this checkpoint.
+    pub content_sha256: String,
+    pub char_attributions: Vec<CharRangeDump>,
+    pub line_attributions: Vec<LineRangeDump>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct CharRangeDump {
+    pub start: usize,
+    pub end: usize,
+    pub author_id: String,
+    pub timestamp_ms: u128,
+}
+
+#[derive(Debug, Serialize)]
+pub struct LineRangeDump {
+    pub start_line: u32,
+    pub end_line: u32,
+    pub author_id: String,
+    pub overrode: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InitialDump {
+    pub files: BTreeMap<String, Vec<LineRangeDump>>,
+    pub prompts: BTreeMap<String, InitialPromptDump>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct InitialPromptDump {
+    pub tool: String,
+    pub session_id: String,
+    pub model: String,
+    pub human_author: Option<String>,
+}
+
+/// Collect the working log for `base` (a revision, or HEAD when omitted) into its stable
+/// dump representation.
+pub fn dump_working_log(
+    repo: &Repository,
+    base: Option<&str>,
+) -> Result<WorkingLogDump, GitAiError> {
+    let base_commit = match base {
+        // Working logs for an unborn branch live under "initial", which isn't a revision
+        Some(rev) => repo
+            .revparse_single(rev)
+            .map(|obj| obj.id())
+            .unwrap_or_else(|_| rev.to_string()),
+        None => repo
+            .head()
+            .and_then(|head| head.target())
+            .unwrap_or_else(|_| "initial".to_string()),
+    };
+
+    // Opening a working log creates its directory, so only open one that already exists
+    let exists = repo.storage.has_working_log(&base_commit);
+    let path = repo.storage.working_logs.join(&base_commit);
+    let (checkpoints, initial) = if exists {
+        let working_log = repo.storage.working_log_for_base_commit(&base_commit);
+        (
+            working_log.read_all_checkpoints()?,
+            working_log.read_initial_attributions(),
+        )
+    } else {
+        (Vec::new(), InitialAttributions::default())
+    };
+
+    Ok(WorkingLogDump {
+        version: WORKING_LOG_DUMP_VERSION,
+        base_commit,
+        exists,
+        path: path.to_string_lossy().to_string(),
+        checkpoints: checkpoints
+            .iter()
+            .enumerate()
+            .map(|(index, checkpoint)| checkpoint_dump(index, checkpoint))
+            .collect(),
+        initial: InitialDump {
+            files: initial
+                .files
+                .into_iter()
+                .map(|(path, attrs)| (path, line_ranges_dump(&attrs)))
+                .collect(),
+            prompts: initial
+                .prompts
+                .into_iter()
+                .map(|(id, record)| {
+                    (
+                        id,
+                        InitialPromptDump {
+                            tool: record.agent_id.tool,
+                            session_id: record.agent_id.id,
+                            model: record.agent_id.model,
+                            human_author: record.human_author,
+                        },
+                    )
+                })
+                .collect(),
+        },
+    })
+}
+
+fn checkpoint_dump(index: usize, checkpoint: &Checkpoint) -> CheckpointDump {
+    CheckpointDump {
+        index,
+        kind: checkpoint.kind.to_str(),
+        author: checkpoint.author.clone(),
+        timestamp: checkpoint.timestamp,
+        agent: checkpoint.agent_id.as_ref().map(|agent| AgentDump {
+            tool: agent.tool.clone(),
+            session_id: agent.id.clone(),
+            model: agent.model.clone(),
+        }),
+        agent_metadata: checkpoint
+            .agent_metadata
+            .clone()
+            .unwrap_or_default()
+            .into_iter()
+            .collect(),
+        line_stats: LineStatsDump {
+            additions: checkpoint.line_stats.additions,
+            deletions: checkpoint.line_stats.deletions,
+            additions_sloc: checkpoint.line_stats.additions_sloc,
+            deletions_sloc: checkpoint.line_stats.deletions_sloc,
+        },
+        transcript_message_count: checkpoint
+            .transcript
+            .as_ref()
+            .map(|transcript| transcript.messages().len())
+            .unwrap_or(0),
+        whole_file_assets: checkpoint.whole_file_assets.clone(),
+        api_version: checkpoint.api_version.clone(),
+        git_ai_version: checkpoint.git_ai_version.clone(),
+        files: checkpoint
+            .entries
+            .iter()
+            .map(|entry| FileEntryDump {
+                path: entry.file.clone(),
+                content_sha256: entry.blob_sha.clone(),
+                char_attributions: entry
+                    .attributions
+                    .iter()
+                    .map(|attr| CharRangeDump {
+                        start: attr.start,
+                        end: attr.end,
+                        author_id: attr.author_id.clone(),
+                        timestamp_ms: attr.ts,
+                    })
+                    .collect(),
+                line_attributions: line_ranges_dump(&entry.line_attributions),
+            })
+            .collect(),
+    }
+}
+
+fn line_ranges_dump(attrs: &[LineAttribution]) -> Vec<LineRangeDump> {
+    attrs
+        .iter()
+        .map(|attr| LineRangeDump {
+            start_line: attr.start_line,
+            end_line: attr.end_line,
+            author_id: attr.author_id.clone(),
+            overrode: attr.overrode.clone(),
+        })
+        .collect()
+}
@@ -0,0 +1,96 @@
+use crate::authorship::policy::{
+    ChangeAuthorship, POLICY_FILE, Policy, has_blocking, print_violations,
+};
+use crate::error::GitAiError;
+use crate::git::find_repository;
+use crate::git::repository::{Repository, exec_git};
+
+pub fn handle_policy(args: &[String]) {
+    match args.first().map(String::as_str) {
+        Some("check") => handle_check(&args[1..]),
+        _ => {
+            eprintln!("Usage: git-ai policy check [<rev>|<range>] [--json]");
+            std::process::exit(1);
+        }
+    }
+}
+
+/// `git-ai policy check [<rev>|<range>] [--json]`: evaluate every rule against the lines
+/// added by the commits in the range, exiting non-zero on blocking violations (for CI).
+fn handle_check(args: &[String]) {
+    let json = args.iter().any(|arg| arg == "--json");
+    let spec = args
+        .iter()
+        .find(|arg| !arg.starts_with("--"))
+        .cloned()
+        .unwrap_or_else(|| "HEAD".to_string());
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Failed to find repository: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let policy = match Policy::load(&repo) {
+        Ok(Some(policy)) => policy,
+        Ok(None) => {
+            eprintln!("No policy configured ({} not found)", POLICY_FILE);
+            if json {
+                println!(
+                    "{}",
+                    serde_json::json!({ "violations": [], "blocked": false })
+                );
+            }
+            return;
+        }
+        Err(e) => {
+            eprintln!("{}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let change = match resolve_commits(&repo, &spec)
+        .and_then(|commits| ChangeAuthorship::from_commits(&repo, &commits))
+    {
+        Ok(change) => change,
+        Err(e) => {
+            eprintln!("Failed to check policy: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let violations = policy.evaluate(None, &change);
+    let blocked = has_blocking(&violations);
+    if json {
+        println!(
+            "{}",
+            serde_json::json!({ "violations": violations, "blocked": blocked })
+        );
+    } else if violations.is_empty() {
+        println!("Policy check passed ({} rules)", policy.rules.len());
+    } else {
+        print_violations(&violations);
+    }
+
+    if blocked {
+        std::process::exit(1);
+    }
+}
+
+fn resolve_commits(repo: &Repository, spec: &str) -> Result<Vec<String>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.push("rev-list".to_string());
+    if !spec.contains("..") {
+        args.push("--max-count=1".to_string());
+    }
+    args.push(spec.to_string());
+    args.push("--".to_string());
+    let output = exec_git(&args)?;
+    Ok(String::from_utf8(output.stdout)?
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect())
+}
@@ -0,0 +1,43 @@
+use crate::authorship::provenance::line_provenance;
+use crate::git::find_repository;
+
+/// `git-ai provenance <file>:<line>`: print a single-line, stable provenance string for the
+/// line (nearest tag, commit, author or tool, model and checkpoint id) for build systems to
+/// embed in generated headers or SBOM fields.
+pub fn handle_provenance(args: &[String]) {
+    let [target] = args else {
+        print_usage_and_exit();
+    };
+    let Some((file, line)) = target
+        .rsplit_once(':')
+        .and_then(|(file, line)| Some((file, line.parse::<u32>().ok()?)))
+        .filter(|(file, line)| !file.is_empty() && *line >= 1)
+    else {
+        print_usage_and_exit();
+    };
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Failed to find repository: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    // Like blame, take the path relative to the current directory
+    let path = std::env::current_dir()
+        .map(|dir| dir.join(file))
+        .unwrap_or_else(|_| file.into());
+    match line_provenance(&repo, &path.to_string_lossy(), line) {
+        Ok(provenance) => println!("{}", provenance.to_line()),
+        Err(e) => {
+            eprintln!("Failed to get provenance for {}:{}: {}", file, line, e);
+            std::process::exit(1);
+        }
+    }
+}
+
+fn print_usage_and_exit() -> ! {
+    eprintln!("Usage: git-ai provenance <file>:<line>");
+    std::process::exit(1);
+}
@@ -0,0 +1,113 @@
+use crate::authorship::reassign::reassign_lines;
+use crate::git::find_repository;
+
+/// `git-ai reassign <file> -L <start>,<end> [-L ...] --to human`: hand the committed AI lines
+/// in the ranges over to the human, recording the transfer in the notes of the commits that
+/// introduced them.
+pub fn handle_reassign(args: &[String]) {
+    let mut file: Option<String> = None;
+    let mut ranges: Vec<(u32, u32)> = Vec::new();
+    let mut to: Option<String> = None;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "-L" | "--to" => {
+                let Some(value) = args.get(i + 1) else {
+                    eprintln!("Error: {} requires a value", args[i]);
+                    std::process::exit(1);
+                };
+                if args[i] == "--to" {
+                    to = Some(value.clone());
+                } else {
+                    match parse_range(value) {
+                        Some(range) => ranges.push(range),
+                        None => {
+                            eprintln!(
+                                "Error: invalid line range '{}'; expected <start>,<end>",
+                                value
+                            );
+                            std::process::exit(1);
+                        }
+                    }
+                }
+                i += 2;
+            }
+            arg if arg.starts_with('-') => {
+                eprintln!("Unknown reassign argument: {}", arg);
+                std::process::exit(1);
+            }
+            arg => {
+                if file.is_some() {
+                    eprintln!("Error: reassign takes a single file");
+                    std::process::exit(1);
+                }
+                file = Some(arg.to_string());
+                i += 1;
+            }
+        }
+    }
+
+    let (Some(file), Some(to)) = (file, to) else {
+        print_usage_and_exit();
+    };
+    if ranges.is_empty() {
+        print_usage_and_exit();
+    }
+    if to != "human" {
+        eprintln!("Error: lines can only be reassigned to human");
+        std::process::exit(1);
+    }
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Failed to find repository: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let by = match repo.config_get_str("user.name") {
+        Ok(Some(name)) if !name.trim().is_empty() => name,
+        _ => "unknown".to_string(),
+    };
+
+    let reassigned = match reassign_lines(&repo, &file, &ranges, &by) {
+        Ok(reassigned) => reassigned,
+        Err(e) => {
+            eprintln!("Reassign failed: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    if reassigned.is_empty() {
+        println!("No committed AI lines in the given ranges of {}", file);
+        return;
+    }
+    let total: u32 = reassigned.iter().map(|commit| commit.lines).sum();
+    println!("Reassigned {} AI lines of {} to human", total, file);
+    for commit in &reassigned {
+        println!(
+            "  {} {}: {} lines",
+            &commit.commit[..commit.commit.len().min(8)],
+            commit.path,
+            commit.lines
+        );
+    }
+}
+
+fn print_usage_and_exit() -> ! {
+    eprintln!("Usage: git-ai reassign <file> -L <start>,<end> [-L <start>,<end>...] --to human");
+    std::process::exit(1);
+}
+
+/// `<start>,<end>` or a single line number.
+fn parse_range(value: &str) -> Option<(u32, u32)> {
+    let (start, end) = match value.split_once(',') {
+        Some((start, end)) => (start.trim().parse().ok()?, end.trim().parse().ok()?),
+        None => {
+            let line = value.trim().parse().ok()?;
+            (line, line)
+        }
+    };
+    (start >= 1 && start <= end).then_some((start, end))
+}
@@ -0,0 +1,435 @@
+//! `git-ai review <base>..<head>`: a Markdown or JSON summary of the AI-authored lines in a
+//! change, for posting as a pull request comment from CI.
+//!
+//! Lines are attributed the way `git-ai diff` does it: every line the change adds (relative
+//! to the merge base) is blamed through the authorship notes of the commits in the range.
+
+use crate::authorship::authorship_log::{LineRange, PromptRecord};
+use crate::authorship::diff_annotation::agent_label;
+use crate::commands::diff::{collect_file_annotations, get_diff_with_line_numbers, resolve_commit};
+use crate::error::GitAiError;
+use crate::git::find_repository;
+use crate::git::refs::show_authorship_note;
+use crate::git::repository::{Repository, exec_git};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+
+#[derive(Debug, Serialize)]
+pub struct ReviewReport {
+    pub base: String,
+    pub head: String,
+    pub added_lines: u32,
+    pub ai_lines: u32,
+    pub ai_percent: f64,
+    pub commits: usize,
+    /// Commits in the range without an authorship note; their lines count as human
+    pub commits_without_notes: Vec<String>,
+    pub agents: Vec<AgentLines>,
+    pub files: Vec<FileReview>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct AgentLines {
+    pub tool: String,
+    pub model: String,
+    pub lines: u32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct FileReview {
+    pub path: String,
+    pub added_lines: u32,
+    pub ai_lines: u32,
+    pub ai_percent: f64,
+    pub ai_hunks: Vec<AiHunk>,
+}
+
+/// A run of consecutive added lines written by one prompt.
+#[derive(Debug, Serialize)]
+pub struct AiHunk {
+    pub start_line: u32,
+    pub end_line: u32,
+    pub tool: String,
+    pub model: String,
+    pub prompt_id: String,
+}
+
+/// A `--fail-on <metric>=<limit>` gate: the review fails when the metric exceeds the limit.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FailOn {
+    pub metric: FailOnMetric,
+    pub limit: f64,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FailOnMetric {
+    AiPercent,
+    AiLines,
+    MissingNotes,
+}
+
+impl FailOnMetric {
+    fn as_str(&self) -> &'static str {
+        match self {
+            FailOnMetric::AiPercent => "ai-percent",
+            FailOnMetric::AiLines => "ai-lines",
+            FailOnMetric::MissingNotes => "missing-notes",
+        }
+    }
+}
+
+impl FailOn {
+    pub fn parse(spec: &str) -> Result<FailOn, String> {
+        let (metric, limit) = spec
+            .split_once('=')
+            .ok_or_else(|| format!("Invalid --fail-on '{}': expected <metric>=<limit>", spec))?;
+        let metric = match metric.trim() {
+            "ai-percent" => FailOnMetric::AiPercent,
+            "ai-lines" => FailOnMetric::AiLines,
+            "missing-notes" => FailOnMetric::MissingNotes,
+            other => {
+                return Err(format!(
+                    "Unknown --fail-on metric '{}' (expected ai-percent, ai-lines or missing-notes)",
+                    other
+                ));
+            }
+        };
+        let limit = limit
+            .trim()
+            .trim_end_matches('%')
+            .parse::<f64>()
+            .map_err(|_| format!("Invalid --fail-on limit in '{}'", spec))?;
+        Ok(FailOn { metric, limit })
+    }
+
+    /// The metric's value when it exceeds the limit.
+    pub fn check(&self, report: &ReviewReport) -> Option<f64> {
+        let value = match self.metric {
+            FailOnMetric::AiPercent => report.ai_percent,
+            FailOnMetric::AiLines => report.ai_lines as f64,
+            FailOnMetric::MissingNotes => report.commits_without_notes.len() as f64,
+        };
+        (value > self.limit).then_some(value)
+    }
+}
+
+pub fn handle_review(args: &[String]) {
+    let mut json = false;
+    let mut range: Option<String> = None;
+    let mut gates: Vec<FailOn> = Vec::new();
+    let mut i = 0;
+    while i < args.len() {
+        let parsed = match args[i].as_str() {
+            "--json" => {
+                json = true;
+                Ok(())
+            }
+            "--fail-on" => {
+                i += 1;
+                match args.get(i) {
+                    Some(spec) => FailOn::parse(spec).map(|gate| gates.push(gate)),
+                    None => Err("--fail-on requires <metric>=<limit>".to_string()),
+                }
+            }
+            arg if arg.starts_with("--fail-on=") => {
+                FailOn::parse(&arg["--fail-on=".len()..]).map(|gate| gates.push(gate))
+            }
+            arg if arg.starts_with('-') => Err(format!("unknown review option: {}", arg)),
+            arg if range.is_none() => {
+                range = Some(arg.to_string());
+                Ok(())
+            }
+            arg => Err(format!("unexpected argument: {}", arg)),
+        };
+        if let Err(e) = parsed {
+            eprintln!("Error: {}", e);
+            eprintln!("Usage: git-ai review <base>..<head> [--json] [--fail-on <metric>=<limit>]");
+            std::process::exit(2);
+        }
+        i += 1;
+    }
+
+    let range = range.unwrap_or_else(|| "HEAD~1..HEAD".to_string());
+    let Some((base, head)) = range.split_once("..") else {
+        eprintln!("Error: review requires a <base>..<head> range");
+        std::process::exit(2);
+    };
+    let head = if head.is_empty() { "HEAD" } else { head };
+    let head = head.strip_prefix('.').unwrap_or(head);
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Failed to find repository: {}", e);
+            std::process::exit(2);
+        }
+    };
+
+    let report = match build_review(&repo, base, head) {
+        Ok(report) => report,
+        Err(e) => {
+            eprintln!("Failed to build review: {}", e);
+            std::process::exit(2);
+        }
+    };
+
+    let failures: Vec<String> = gates
+        .iter()
+        .filter_map(|gate| {
+            gate.check(&report).map(|value| {
+                format!(
+                    "{} is {} (limit {})",
+                    gate.metric.as_str(),
+                    format_number(value),
+                    format_number(gate.limit)
+                )
+            })
+        })
+        .collect();
+
+    if json {
+        let mut value = serde_json::to_value(&report).unwrap_or_default();
+        value["failures"] = serde_json::json!(failures);
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&value).unwrap_or_default()
+        );
+    } else {
+        print!("{}", render_markdown(&report, &failures));
+    }
+
+    if !failures.is_empty() {
+        for failure in &failures {
+            eprintln!("review failed: {}", failure);
+        }
+        std::process::exit(1);
+    }
+}
+
+/// Attribute every line added between the merge base of `base` and `head`, and `head`.
+pub fn build_review(repo: &Repository, base: &str, head: &str) -> Result<ReviewReport, GitAiError> {
+    let base_sha = resolve_commit(repo, base)?;
+    let head_sha = resolve_commit(repo, head)?;
+    let from = repo
+        .merge_base(base_sha.clone(), head_sha.clone())
+        .unwrap_or_else(|_| base_sha.clone());
+
+    let mut args = repo.global_args_for_exec();
+    args.push("rev-list".to_string());
+    args.push(format!("{}..{}", from, head_sha));
+    args.push("--".to_string());
+    let commits: Vec<String> = String::from_utf8(exec_git(&args)?.stdout)?
+        .lines()
+        .map(|line| line.trim().to_string())
+        .filter(|line| !line.is_empty())
+        .collect();
+    let commits_without_notes: Vec<String> = commits
+        .iter()
+        .filter(|sha| show_authorship_note(repo, sha).is_none())
+        .cloned()
+        .collect();
+
+    let hunks = get_diff_with_line_numbers(repo, &from, &head_sha)?;
+    let mut added_by_file: BTreeMap<String, u32> = BTreeMap::new();
+    for hunk in &hunks {
+        *added_by_file.entry(hunk.file_path.clone()).or_default() += hunk.added_lines.len() as u32;
+    }
+
+    let mut files = Vec::new();
+    let mut agent_lines: BTreeMap<(String, String), u32> = BTreeMap::new();
+    for (path, added_lines) in added_by_file {
+        if added_lines == 0 {
+            continue;
+        }
+        let (annotations, prompts) =
+            collect_file_annotations(repo, &from, &head_sha, &path, &hunks)?;
+        let ai_hunks = ai_hunks(&annotations, &prompts);
+        let ai_lines: u32 = ai_hunks
+            .iter()
+            .map(|hunk| hunk.end_line - hunk.start_line + 1)
+            .sum();
+        for hunk in &ai_hunks {
+            *agent_lines
+                .entry((hunk.tool.clone(), hunk.model.clone()))
+                .or_default() += hunk.end_line - hunk.start_line + 1;
+        }
+        files.push(FileReview {
+            path,
+            added_lines,
+            ai_lines,
+            ai_percent: percent(ai_lines, added_lines),
+            ai_hunks,
+        });
+    }
+
+    let added_lines = files.iter().map(|file| file.added_lines).sum();
+    let ai_lines = files.iter().map(|file| file.ai_lines).sum();
+    let mut agents: Vec<AgentLines> = agent_lines
+        .into_iter()
+        .map(|((tool, model), lines)| AgentLines { tool, model, lines })
+        .collect();
+    agents.sort_by_key(|agent| std::cmp::Reverse(agent.lines));
+
+    Ok(ReviewReport {
+        base: base_sha,
+        head: head_sha,
+        added_lines,
+        ai_lines,
+        ai_percent: percent(ai_lines, added_lines),
+        commits: commits.len(),
+        commits_without_notes,
+        agents,
+        files,
+    })
+}
+
+fn ai_hunks(
+    annotations: &BTreeMap<String, Vec<LineRange>>,
+    prompts: &HashMap<String, PromptRecord>,
+) -> Vec<AiHunk> {
+    let mut hunks = Vec::new();
+    for (hash, ranges) in annotations {
+        let Some(prompt) = prompts.get(hash) else {
+            continue;
+        };
+        for range in ranges {
+            let (start_line, end_line) = match range {
+                LineRange::Single(line) => (*line, *line),
+                LineRange::Range(start, end) => (*start, *end),
+            };
+            hunks.push(AiHunk {
+                start_line,
+                end_line,
+                tool: prompt.agent_id.tool.clone(),
+                model: prompt.agent_id.model.clone(),
+                prompt_id: hash.clone(),
+            });
+        }
+    }
+    hunks.sort_by_key(|hunk| hunk.start_line);
+    hunks
+}
+
+fn percent(part: u32, total: u32) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+    (part as f64 / total as f64 * 1000.0).round() / 10.0
+}
+
+fn format_number(value: f64) -> String {
+    if value.fract() == 0.0 {
+        format!("{}", value as i64)
+    } else {
+        format!("{:.1}", value)
+    }
+}
+
+pub fn render_markdown(report: &ReviewReport, failures: &[String]) -> String {
+    let mut out = String::from("## AI authorship review\n\n");
+    out.push_str(&format!(
+        "**{}% AI**: {} of {} added lines across {} files, {} commits (`{}..{}`)\n",
+        format_number(report.ai_percent),
+        report.ai_lines,
+        report.added_lines,
+        report.files.len(),
+        report.commits,
+        &report.base[..report.base.len().min(8)],
+        &report.head[..report.head.len().min(8)],
+    ));
+    if !report.commits_without_notes.is_empty() {
+        out.push_str(&format!(
+            "\n> {} commits have no authorship notes; their lines count as human.\n",
+            report.commits_without_notes.len()
+        ));
+    }
+    if !failures.is_empty() {
+        out.push_str("\n**Failed checks:**\n\n");
+        for failure in failures {
+            out.push_str(&format!("- {}\n", failure));
+        }
+    }
+
+    if !report.agents.is_empty() {
+        out.push_str("\n| Tool | Model | AI lines |\n|---|---|---:|\n");
+        for agent in &report.agents {
+            out.push_str(&format!(
+                "| {} | {} | {} |\n",
+                agent.tool, agent.model, agent.lines
+            ));
+        }
+    }
+
+    if !report.files.is_empty() {
+        out.push_str("\n| File | Added | AI | AI % |\n|---|---:|---:|---:|\n");
+        for file in &report.files {
+            out.push_str(&format!(
+                "| `{}` | {} | {} | {}% |\n",
+                file.path,
+                file.added_lines,
+                file.ai_lines,
+                format_number(file.ai_percent)
+            ));
+        }
+    }
+
+    let with_hunks: Vec<&FileReview> = report
+        .files
+        .iter()
+        .filter(|file| !file.ai_hunks.is_empty())
+        .collect();
+    if !with_hunks.is_empty() {
+        out.push_str("\n<details><summary>AI-authored hunks</summary>\n\n");
+        for file in with_hunks {
+            out.push_str(&format!("**`{}`**\n", file.path));
+            for hunk in &file.ai_hunks {
+                let lines = if hunk.start_line == hunk.end_line {
+                    format!("L{}", hunk.start_line)
+                } else {
+                    format!("L{}-{}", hunk.start_line, hunk.end_line)
+                };
+                out.push_str(&format!(
+                    "- {}: {}\n",
+                    lines,
+                    agent_label(&hunk.tool, &hunk.model)
+                ));
+            }
+            out.push('\n');
+        }
+        out.push_str("</details>\n");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_fail_on() {
+        assert_eq!(
+            FailOn::parse("ai-percent=50").unwrap(),
+            FailOn {
+                metric: FailOnMetric::AiPercent,
+                limit: 50.0
+            }
+        );
+        assert_eq!(
+            FailOn::parse("ai-lines = 10").unwrap().metric,
+            FailOnMetric::AiLines
+        );
+        assert_eq!(FailOn::parse("ai-percent=75%").unwrap().limit, 75.0);
+        assert!(FailOn::parse("ai-percent").is_err());
+        assert!(FailOn::parse("human-percent=5").is_err());
+        assert!(FailOn::parse("ai-lines=many").is_err());
+    }
+
+    #[test]
+    fn test_percent_rounds_to_one_decimal() {
+        assert_eq!(percent(0, 0), 0.0);
+        assert_eq!(percent(1, 3), 33.3);
+        assert_eq!(percent(2, 3), 66.7);
+        assert_eq!(format_number(50.0), "50");
+        assert_eq!(format_number(66.7), "66.7");
+    }
+}
@@ -0,0 +1,335 @@
+//! `git-ai sandbox`: give an agent its own worktree and fold the accepted work back in.
+//!
+//! `sandbox create <branch>` adds a linked worktree on `<branch>`. Linked worktrees have
+//! their own git dir, so the sandbox's working log is separate from the main worktree's. A
+//! marker in that git dir turns on auto-checkpointing: each commit made in the sandbox
+//! attributes its uncheckpointed changes to the sandbox's agent, so agents without git-ai
+//! hooks are still tracked. Commits in the sandbox run the repository policy as usual.
+//!
+//! `sandbox merge <branch>`, run from the main worktree, re-checks the sandbox's commits
+//! against the policy, merges them into the current branch (carrying their attribution) and
+//! removes the worktree.
+
+use crate::authorship::policy::{ChangeAuthorship, PolicyStage, enforce};
+use crate::authorship::working_log::{AgentId, CheckpointKind};
+use crate::commands::checkpoint_agent::agent_presets::AgentRunResult;
+use crate::commands::hooks::commit_hooks::get_commit_default_author;
+use crate::error::GitAiError;
+use crate::git::find_repository;
+use crate::git::repository::{Repository, exec_git, find_repository_in_path};
+use crate::git::rewrite_log::{MergeSquashEvent, RewriteLogEvent};
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Marker file in a sandbox worktree's git dir (under `ai/`).
+pub const SANDBOX_MARKER: &str = "sandbox.json";
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Sandbox {
+    pub branch: String,
+    /// Commit the sandbox started from
+    pub base_commit: String,
+    /// Worktree the sandbox was created from
+    pub origin_workdir: String,
+    /// Tool that uncheckpointed changes are attributed to on commit
+    pub agent: String,
+    pub model: String,
+    pub created_at: u64,
+}
+
+impl Sandbox {
+    pub fn marker_path(repo: &Repository) -> PathBuf {
+        repo.path().join("ai").join(SANDBOX_MARKER)
+    }
+
+    /// The sandbox `repo` is a worktree of, if any.
+    pub fn load(repo: &Repository) -> Option<Sandbox> {
+        let content = std::fs::read_to_string(Self::marker_path(repo)).ok()?;
+        serde_json::from_str(&content).ok()
+    }
+
+    fn save(&self, repo: &Repository) -> Result<(), GitAiError> {
+        let path = Self::marker_path(repo);
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        std::fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+
+    /// Checkpoint input that attributes every uncheckpointed change to the sandbox's agent.
+    pub fn agent_run_result(&self) -> AgentRunResult {
+        AgentRunResult {
+            agent_id: AgentId {
+                tool: self.agent.clone(),
+                id: format!("sandbox-{}-{}", self.branch, self.created_at),
+                model: self.model.clone(),
+            },
+            agent_metadata: None,
+            checkpoint_kind: CheckpointKind::AiAgent,
+            transcript: None,
+            repo_working_dir: None,
+            edited_filepaths: None,
+            will_edit_filepaths: None,
+            dirty_files: None,
+        }
+    }
+}
+
+pub fn handle_sandbox(args: &[String]) {
+    let result = match args.first().map(String::as_str) {
+        Some("create") => handle_create(&args[1..]),
+        Some("merge") => handle_merge(&args[1..]),
+        _ => {
+            eprintln!(
+                "Usage: git-ai sandbox create <branch> [--path <dir>] [--from <rev>] [--agent <tool>] [--model <model>]"
+            );
+            eprintln!("       git-ai sandbox merge <branch> [--squash] [--keep] [--no-verify]");
+            std::process::exit(1);
+        }
+    };
+    if let Err(e) = result {
+        eprintln!("Sandbox failed: {}", e);
+        std::process::exit(1);
+    }
+}
+
+/// `sandbox create <branch> [--path <dir>] [--from <rev>] [--agent <tool>] [--model <model>]`
+fn handle_create(args: &[String]) -> Result<(), GitAiError> {
+    let mut branch: Option<String> = None;
+    let mut path: Option<String> = None;
+    let mut from = "HEAD".to_string();
+    let mut agent = "agent".to_string();
+    let mut model = "unknown".to_string();
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            flag @ ("--path" | "--from" | "--agent" | "--model") => {
+                let Some(value) = args.get(i + 1) else {
+                    return Err(GitAiError::Generic(format!("{} requires a value", flag)));
+                };
+                match flag {
+                    "--path" => path = Some(value.clone()),
+                    "--from" => from = value.clone(),
+                    "--agent" => agent = value.clone(),
+                    _ => model = value.clone(),
+                }
+                i += 2;
+            }
+            arg if arg.starts_with('-') => {
+                return Err(GitAiError::Generic(format!(
+                    "Unknown sandbox argument: {}",
+                    arg
+                )));
+            }
+            arg => {
+                branch = Some(arg.to_string());
+                i += 1;
+            }
+        }
+    }
+    let Some(branch) = branch else {
+        return Err(GitAiError::Generic(
+            "sandbox create requires a branch name".to_string(),
+        ));
+    };
+
+    let repo = find_repository(&Vec::<String>::new())?;
+    let workdir = repo.workdir()?;
+    let path = path
+        .map(PathBuf::from)
+        .unwrap_or_else(|| default_sandbox_path(&workdir, &branch));
+    let base_commit = repo.revparse_single(&from)?.id();
+
+    let mut worktree_args = repo.global_args_for_exec();
+    worktree_args.extend(["worktree".to_string(), "add".to_string()]);
+    if repo
+        .revparse_single(&format!("refs/heads/{}", branch))
+        .is_ok()
+    {
+        worktree_args.push(path.to_string_lossy().to_string());
+        worktree_args.push(branch.clone());
+    } else {
+        worktree_args.push("-b".to_string());
+        worktree_args.push(branch.clone());
+        worktree_args.push(path.to_string_lossy().to_string());
+        worktree_args.push(base_commit.clone());
+    }
+    exec_git(&worktree_args)?;
+
+    let sandbox_repo = find_repository_in_path(&path.to_string_lossy())?;
+    let sandbox = Sandbox {
+        branch: branch.clone(),
+        base_commit,
+        origin_workdir: workdir.to_string_lossy().to_string(),
+        agent,
+        model,
+        created_at: SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs(),
+    };
+    sandbox.save(&sandbox_repo)?;
+
+    println!("Created sandbox {} at {}", branch, path.display());
+    Ok(())
+}
+
+/// `sandbox merge <branch> [--squash] [--keep] [--no-verify]`
+fn handle_merge(args: &[String]) -> Result<(), GitAiError> {
+    let squash = args.iter().any(|arg| arg == "--squash");
+    let keep = args.iter().any(|arg| arg == "--keep");
+    let Some(branch) = args.iter().find(|arg| !arg.starts_with('-')) else {
+        return Err(GitAiError::Generic(
+            "sandbox merge requires a branch name".to_string(),
+        ));
+    };
+
+    let mut repo = find_repository(&Vec::<String>::new())?;
+    let Some(sandbox_path) = worktree_for_branch(&repo, branch)? else {
+        return Err(GitAiError::Generic(format!(
+            "No worktree has {} checked out",
+            branch
+        )));
+    };
+    let sandbox_repo = find_repository_in_path(&sandbox_path.to_string_lossy())?;
+    if Sandbox::load(&sandbox_repo).is_none() {
+        return Err(GitAiError::Generic(format!(
+            "{} is not a git-ai sandbox",
+            sandbox_path.display()
+        )));
+    }
+    if sandbox_repo.workdir()? == repo.workdir()? {
+        return Err(GitAiError::Generic(
+            "Run sandbox merge from the worktree the sandbox should merge into".to_string(),
+        ));
+    }
+
+    let mut status_args = sandbox_repo.global_args_for_exec();
+    status_args.extend(["status".to_string(), "--porcelain".to_string()]);
+    if !exec_git(&status_args)?.stdout.is_empty() {
+        return Err(GitAiError::Generic(format!(
+            "Sandbox {} has uncommitted changes; commit or discard them first",
+            branch
+        )));
+    }
+
+    let commits = rev_list(&repo, &format!("HEAD..{}", branch))?;
+    if commits.is_empty() {
+        println!("Sandbox {} has no new commits", branch);
+    } else {
+        if !enforce(&repo, PolicyStage::Commit, args, || {
+            ChangeAuthorship::from_commits(&repo, &commits)
+        }) {
+            std::process::exit(1);
+        }
+        merge_sandbox_branch(&mut repo, branch, squash, args)?;
+    }
+
+    if !keep {
+        let mut remove_args = repo.global_args_for_exec();
+        remove_args.extend([
+            "worktree".to_string(),
+            "remove".to_string(),
+            sandbox_path.to_string_lossy().to_string(),
+        ]);
+        exec_git(&remove_args)?;
+    }
+
+    if squash && !commits.is_empty() {
+        println!(
+            "Squashed sandbox {} into the working tree; commit to keep its attribution",
+            branch
+        );
+    } else if !commits.is_empty() {
+        println!("Merged sandbox {}", branch);
+    }
+    Ok(())
+}
+
+fn merge_sandbox_branch(
+    repo: &mut Repository,
+    branch: &str,
+    squash: bool,
+    args: &[String],
+) -> Result<(), GitAiError> {
+    let mut merge_args = repo.global_args_for_exec();
+    merge_args.push("merge".to_string());
+    if squash {
+        merge_args.push("--squash".to_string());
+    } else {
+        merge_args.extend(["--no-ff".to_string(), "--no-edit".to_string()]);
+    }
+    merge_args.push(branch.to_string());
+
+    let base_head = repo.head()?.target()?;
+    exec_git(&merge_args)?;
+
+    // A merge commit keeps the sandbox commits (and their notes) in history; a squash
+    // needs their attribution carried into this worktree's working log, as after
+    // `git merge --squash`
+    if squash {
+        let source_head = repo.revparse_single(branch)?.id();
+        let base_branch = repo.head()?.name().unwrap_or("HEAD").to_string();
+        let author = get_commit_default_author(repo, args);
+        repo.handle_rewrite_log_event(
+            RewriteLogEvent::merge_squash(MergeSquashEvent::new(
+                branch.to_string(),
+                source_head,
+                base_branch,
+                base_head,
+            )),
+            author,
+            false,
+            true,
+        );
+    }
+    Ok(())
+}
+
+fn default_sandbox_path(workdir: &Path, branch: &str) -> PathBuf {
+    let name = workdir
+        .file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_else(|| "repo".to_string());
+    let sandbox_name = format!("{}-{}", name, branch.replace('/', "-"));
+    match workdir.parent() {
+        Some(parent) => parent.join(sandbox_name),
+        None => PathBuf::from(sandbox_name),
+    }
+}
+
+/// Path of the worktree that has `refs/heads/<branch>` checked out.
+fn worktree_for_branch(repo: &Repository, branch: &str) -> Result<Option<PathBuf>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.extend([
+        "worktree".to_string(),
+        "list".to_string(),
+        "--porcelain".to_string(),
+    ]);
+    let output = String::from_utf8(exec_git(&args)?.stdout)?;
+    let wanted = format!("refs/heads/{}", branch);
+
+    let mut current: Option<&str> = None;
+    for line in output.lines() {
+        if let Some(path) = line.strip_prefix("worktree ") {
+            current = Some(path);
+        } else if let Some(head_ref) = line.strip_prefix("branch ")
+            && head_ref == wanted
+        {
+            return Ok(current.map(PathBuf::from));
+        }
+    }
+    Ok(None)
+}
+
+fn rev_list(repo: &Repository, range: &str) -> Result<Vec<String>, GitAiError> {
+    let mut args = repo.global_args_for_exec();
+    args.extend(["rev-list".to_string(), range.to_string()]);
+    Ok(String::from_utf8(exec_git(&args)?.stdout)?
+        .lines()
+        .map(str::to_string)
+        .collect())
+}
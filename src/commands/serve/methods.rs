@@ -0,0 +1,332 @@
+use crate::authorship::authorship_log::PromptRecord;
+use crate::authorship::working_log::Checkpoint;
+use crate::commands::blame::{GitAiBlameOptions, working_log_line_attributions};
+use crate::commands::serve::protocol::{
+    INTERNAL_ERROR, INVALID_REQUEST, METHOD_NOT_FOUND, PARSE_ERROR, RpcError, RpcRequest,
+    RpcResponse,
+};
+use crate::git::repository::Repository;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::collections::{BTreeMap, HashMap};
+use std::path::Path;
+
+#[derive(Debug, Deserialize)]
+struct BlameFileParams {
+    path: String,
+    /// Blame as of this commit instead of the working tree.
+    #[serde(default)]
+    commit: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BlameRangeParams {
+    path: String,
+    start_line: u32,
+    end_line: u32,
+    #[serde(default)]
+    commit: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct BufferParams {
+    path: String,
+    /// Unsaved editor contents, blamed in place of the file on disk.
+    contents: String,
+    #[serde(default)]
+    start_line: Option<u32>,
+    #[serde(default)]
+    end_line: Option<u32>,
+}
+
+#[derive(Debug, Deserialize)]
+struct AppendCheckpointParams {
+    /// HEAD the checkpoint was taken on; selects the working log.
+    base_commit: String,
+    checkpoint: Checkpoint,
+}
+
+#[derive(Debug, Serialize)]
+struct LineAttributionResult {
+    line: u32,
+    /// "ai" or "human"
+    kind: &'static str,
+    /// Tool for AI lines, the git author otherwise.
+    author: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    prompt_id: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct PromptSummary {
+    tool: String,
+    model: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    human_author: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct AttributionResult {
+    path: String,
+    lines: Vec<LineAttributionResult>,
+    prompts: BTreeMap<String, PromptSummary>,
+}
+
+/// One client's view of a repository. The repository handle (and with it the storage
+/// layer and attribution cache) stays open across requests.
+pub struct ServeSession {
+    repo: Repository,
+    shutdown_requested: bool,
+}
+
+impl ServeSession {
+    pub fn new(repo: Repository) -> Self {
+        ServeSession {
+            repo,
+            shutdown_requested: false,
+        }
+    }
+
+    pub fn shutdown_requested(&self) -> bool {
+        self.shutdown_requested
+    }
+
+    /// Handle one framed message and return the serialized response, if any.
+    pub fn handle_message(&mut self, message: &str) -> Option<String> {
+        let request: RpcRequest = match serde_json::from_str::<Value>(message) {
+            Ok(value) => match serde_json::from_value(value) {
+                Ok(request) => request,
+                Err(e) => {
+                    return Some(respond(RpcResponse::error(
+                        Value::Null,
+                        RpcError::new(INVALID_REQUEST, e.to_string()),
+                    )));
+                }
+            },
+            Err(e) => {
+                return Some(respond(RpcResponse::error(
+                    Value::Null,
+                    RpcError::new(PARSE_ERROR, e.to_string()),
+                )));
+            }
+        };
+
+        let outcome = self.dispatch(&request.method, request.params);
+        let id = request.id?;
+        Some(respond(match outcome {
+            Ok(result) => RpcResponse::result(id, result),
+            Err(error) => RpcResponse::error(id, error),
+        }))
+    }
+
+    fn dispatch(&mut self, method: &str, params: Value) -> Result<Value, RpcError> {
+        match method {
+            "ping" => Ok(Value::String("pong".to_string())),
+            "shutdown" => {
+                self.shutdown_requested = true;
+                Ok(Value::Null)
+            }
+            "blame_file" => {
+                let params: BlameFileParams = parse_params(params)?;
+                self.attribute(&params.path, params.commit, None, None)
+            }
+            "blame_range" => {
+                let params: BlameRangeParams = parse_params(params)?;
+                self.attribute(
+                    &params.path,
+                    params.commit,
+                    Some((params.start_line, params.end_line)),
+                    None,
+                )
+            }
+            "attribution_for_buffer" => {
+                let params: BufferParams = parse_params(params)?;
+                let range = match (params.start_line, params.end_line) {
+                    (Some(start), Some(end)) => Some((start, end)),
+                    (None, None) => None,
+                    _ => {
+                        return Err(RpcError::invalid_params(
+                            "start_line and end_line must be given together",
+                        ));
+                    }
+                };
+                self.attribute(&params.path, None, range, Some(params.contents))
+            }
+            "append_checkpoint" => {
+                let params: AppendCheckpointParams = parse_params(params)?;
+                self.repo
+                    .storage
+                    .working_log_for_base_commit(&params.base_commit)
+                    .append_checkpoint(&params.checkpoint)
+                    .map_err(|e| RpcError::new(INTERNAL_ERROR, e.to_string()))?;
+                Ok(Value::Null)
+            }
+            _ => Err(RpcError::new(
+                METHOD_NOT_FOUND,
+                format!("Unknown method: {}", method),
+            )),
+        }
+    }
+
+    fn attribute(
+        &self,
+        path: &str,
+        commit: Option<String>,
+        range: Option<(u32, u32)>,
+        contents: Option<String>,
+    ) -> Result<Value, RpcError> {
+        let relative_path = self.relative_path(path);
+        let uncommitted = commit.is_none();
+
+        let mut options = GitAiBlameOptions {
+            no_output: true,
+            use_prompt_hashes_as_names: true,
+            newest_commit: commit,
+            contents_data: contents.as_ref().map(|c| c.as_bytes().to_vec()),
+            ..Default::default()
+        };
+        if let Some(range) = range {
+            options.line_ranges = vec![range];
+        }
+
+        let (line_authors, mut prompt_records) = self
+            .repo
+            .blame(&relative_path, &options)
+            .map_err(|e| RpcError::new(INTERNAL_ERROR, e.to_string()))?;
+        let mut line_authors: BTreeMap<u32, String> = line_authors.into_iter().collect();
+
+        // Lines that are not committed yet can still be attributed from the working log,
+        // as long as the checkpointed content matches what is being blamed.
+        if uncommitted {
+            let content =
+                match &contents {
+                    Some(contents) => Some(contents.clone()),
+                    None => self.repo.workdir().ok().and_then(|workdir| {
+                        std::fs::read_to_string(workdir.join(&relative_path)).ok()
+                    }),
+                };
+            if let Some(content) = content
+                && let Some((pending_lines, pending_prompts)) =
+                    working_log_line_attributions(&self.repo, &relative_path, &content)
+            {
+                for (line, prompt_id) in pending_lines {
+                    if let Some(author) = line_authors.get_mut(&line)
+                        && !prompt_records.contains_key(author.as_str())
+                    {
+                        *author = prompt_id;
+                    }
+                }
+                for (prompt_id, record) in pending_prompts {
+                    prompt_records.entry(prompt_id).or_insert(record);
+                }
+            }
+        }
+
+        let result = attribution_result(relative_path, line_authors, &prompt_records);
+        serde_json::to_value(result).map_err(|e| RpcError::new(INTERNAL_ERROR, e.to_string()))
+    }
+
+    /// Editors usually send absolute paths; blame and the working log are keyed by
+    /// repo-relative ones.
+    fn relative_path(&self, path: &str) -> String {
+        let path_buf = Path::new(path);
+        if !path_buf.is_absolute() {
+            return path.to_string();
+        }
+        let canonical = path_buf
+            .canonicalize()
+            .unwrap_or_else(|_| path_buf.to_path_buf());
+        let workdir = self.repo.workdir().unwrap_or_default();
+        canonical
+            .strip_prefix(self.repo.canonical_workdir())
+            .or_else(|_| path_buf.strip_prefix(&workdir))
+            .map(|relative| relative.to_string_lossy().replace('\\', "/"))
+            .unwrap_or_else(|_| path.to_string())
+    }
+}
+
+fn parse_params<T: for<'de> Deserialize<'de>>(params: Value) -> Result<T, RpcError> {
+    serde_json::from_value(params).map_err(|e| RpcError::invalid_params(e.to_string()))
+}
+
+fn respond(response: RpcResponse) -> String {
+    serde_json::to_string(&response).unwrap_or_default()
+}
+
+fn attribution_result(
+    path: String,
+    line_authors: BTreeMap<u32, String>,
+    prompt_records: &HashMap<String, PromptRecord>,
+) -> AttributionResult {
+    let mut prompts = BTreeMap::new();
+    let lines = line_authors
+        .into_iter()
+        .map(|(line, author)| match prompt_records.get(&author) {
+            Some(record) => {
+                prompts
+                    .entry(author.clone())
+                    .or_insert_with(|| PromptSummary {
+                        tool: record.agent_id.tool.clone(),
+                        model: record.agent_id.model.clone(),
+                        human_author: record.human_author.clone(),
+                    });
+                LineAttributionResult {
+                    line,
+                    kind: "ai",
+                    author: record.agent_id.tool.clone(),
+                    prompt_id: Some(author),
+                }
+            }
+            None => LineAttributionResult {
+                line,
+                kind: "human",
+                author,
+                prompt_id: None,
+            },
+        })
+        .collect();
+
+    AttributionResult {
+        path,
+        lines,
+        prompts,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::git::test_utils::TmpRepo;
+
+    #[test]
+    fn test_session_reports_protocol_errors() {
+        let tmp_repo = TmpRepo::new().unwrap();
+        let mut session = ServeSession::new(tmp_repo.gitai_repo().clone());
+
+        let response = session.handle_message("{not json").unwrap();
+        assert!(response.contains(&PARSE_ERROR.to_string()));
+
+        let response = session
+            .handle_message(r#"{"jsonrpc":"2.0","id":1,"method":"nope"}"#)
+            .unwrap();
+        assert!(response.contains(&METHOD_NOT_FOUND.to_string()));
+
+        let response = session
+            .handle_message(r#"{"jsonrpc":"2.0","id":2,"method":"blame_file","params":{}}"#)
+            .unwrap();
+        assert!(response.contains("-32602"));
+
+        // Notifications never get a response.
+        assert!(
+            session
+                .handle_message(r#"{"jsonrpc":"2.0","method":"ping"}"#)
+                .is_none()
+        );
+
+        let response = session
+            .handle_message(r#"{"jsonrpc":"2.0","id":3,"method":"shutdown"}"#)
+            .unwrap();
+        assert_eq!(response, r#"{"jsonrpc":"2.0","id":3,"result":null}"#);
+        assert!(session.shutdown_requested());
+    }
+}
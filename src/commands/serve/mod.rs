@@ -0,0 +1,151 @@
+//! `git-ai serve`: a long-running attribution server for editor integrations.
+//!
+//! Speaks newline-delimited JSON-RPC 2.0 over stdio (default) or a Unix socket, so
+//! editors can ask for blame on every keystroke without spawning a process per request.
+//!
+//! Methods:
+//! - `blame_file { path, commit? }`
+//! - `blame_range { path, start_line, end_line, commit? }`
+//! - `attribution_for_buffer { path, contents, start_line?, end_line? }`
+//! - `append_checkpoint { base_commit, checkpoint }`
+//! - `ping`, `shutdown`
+//!
+//! On a socket the server is also the repository's storage writer of record: CLI invocations
+//! send their checkpoints to it instead of writing the working log themselves (see
+//! `git::storage_coordination`).
+
+pub mod methods;
+pub mod protocol;
+
+use crate::error::GitAiError;
+use crate::git::find_repository;
+use crate::git::repository::Repository;
+use methods::ServeSession;
+use std::io::{BufRead, Write};
+
+pub fn handle_serve(args: &[String]) {
+    let mut socket_path: Option<String> = None;
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--stdio" => socket_path = None,
+            "--socket" => {
+                i += 1;
+                match args.get(i) {
+                    Some(path) => socket_path = Some(path.clone()),
+                    None => {
+                        eprintln!("Error: --socket requires a path");
+                        std::process::exit(1);
+                    }
+                }
+            }
+            arg if arg.starts_with("--socket=") => {
+                socket_path = arg.strip_prefix("--socket=").map(str::to_string);
+            }
+            arg => {
+                eprintln!("Error: unknown serve option: {}", arg);
+                std::process::exit(1);
+            }
+        }
+        i += 1;
+    }
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Failed to find repository: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let result = match socket_path {
+        Some(path) => serve_socket(repo, &path),
+        None => {
+            let stdin = std::io::stdin();
+            let stdout = std::io::stdout();
+            serve_connection(repo, stdin.lock(), stdout.lock()).map(|_| ())
+        }
+    };
+
+    if let Err(e) = result {
+        eprintln!("git-ai serve failed: {}", e);
+        std::process::exit(1);
+    }
+}
+
+/// Answer requests until the client disconnects or asks to shut down.
+/// Returns true if the client requested a shutdown.
+pub fn serve_connection(
+    repo: Repository,
+    reader: impl BufRead,
+    mut writer: impl Write,
+) -> Result<bool, GitAiError> {
+    let mut session = ServeSession::new(repo);
+    for line in reader.lines() {
+        let line = line?;
+        if line.trim().is_empty() {
+            continue;
+        }
+        if let Some(response) = session.handle_message(&line) {
+            writeln!(writer, "{}", response)?;
+            writer.flush()?;
+        }
+        if session.shutdown_requested() {
+            return Ok(true);
+        }
+    }
+    Ok(false)
+}
+
+#[cfg(unix)]
+fn serve_socket(repo: Repository, path: &str) -> Result<(), GitAiError> {
+    use crate::git::storage_coordination::{REGISTRATION_FILE, register_writer};
+    use std::io::BufReader;
+    use std::os::unix::net::UnixListener;
+    use std::path::Path;
+
+    // A socket file left behind by a previous server would make bind fail.
+    let _ = std::fs::remove_file(path);
+    let listener = UnixListener::bind(path)?;
+    let path = path.to_string();
+
+    let ai_dir = repo.storage.repo_path.join("ai");
+    let _writer = match register_writer(&ai_dir, Path::new(&path)) {
+        Ok(writer) => Some(writer),
+        Err(e) => {
+            eprintln!(
+                "Warning: {}; this server is not the writer of record, CLI invocations write storage directly",
+                e
+            );
+            None
+        }
+    };
+
+    for stream in listener.incoming() {
+        let stream = stream?;
+        let repo = repo.clone();
+        let socket_path = path.clone();
+        let registration_file = ai_dir.join(REGISTRATION_FILE);
+        std::thread::spawn(move || {
+            let reader = match stream.try_clone() {
+                Ok(reader) => BufReader::new(reader),
+                Err(_) => return,
+            };
+            if let Ok(true) = serve_connection(repo, reader, stream) {
+                let _ = std::fs::remove_file(&socket_path);
+                // Exiting skips the writer guard's cleanup
+                let _ = std::fs::remove_file(&registration_file);
+                std::process::exit(0);
+            }
+        });
+    }
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn serve_socket(_repo: Repository, _path: &str) -> Result<(), GitAiError> {
+    Err(GitAiError::Generic(
+        "--socket is only supported on Unix; use --stdio".to_string(),
+    ))
+}
@@ -19,10 +19,16 @@ pub fn handle_share(args: &[String]) {
         }
         Err(e) if e.contains("requires a prompt ID") => {
             // No prompt_id - launch TUI
+            #[cfg(feature = "tui")]
             if let Err(tui_err) = crate::commands::share_tui::run_tui() {
                 eprintln!("TUI error: {}", tui_err);
                 std::process::exit(1);
             }
+            #[cfg(not(feature = "tui"))]
+            {
+                eprintln!("Error: {} (this build of git-ai has no TUI)", e);
+                std::process::exit(1);
+            }
         }
         Err(e) => {
             // Other parsing error
@@ -1,11 +1,20 @@
+use crate::authorship::diff_annotation::annotated_commit;
 use crate::error::GitAiError;
 use crate::git::find_repository;
 use crate::git::refs::{CommitAuthorship, get_commits_with_notes_from_list};
 use crate::git::repository::{CommitRange, Repository};
+use std::io::IsTerminal;
 
 const NO_AUTHORSHIP_DATA_MESSAGE: &str = "No authorship data found for this revision";
 
 pub fn handle_show(args: &[String]) {
+    let patch = args.iter().any(|arg| arg == "-p" || arg == "--patch");
+    let args: Vec<String> = args
+        .iter()
+        .filter(|arg| *arg != "-p" && *arg != "--patch")
+        .cloned()
+        .collect();
+
     if args.is_empty() {
         eprintln!("Error: show requires a revision or range");
         std::process::exit(1);
@@ -24,7 +33,12 @@ pub fn handle_show(args: &[String]) {
         }
     };
 
-    if let Err(e) = show_authorship(&repo, &args[0]) {
+    let result = if patch {
+        show_annotated_patches(&repo, &args[0])
+    } else {
+        show_authorship(&repo, &args[0])
+    };
+    if let Err(e) = result {
         eprintln!("Failed to show authorship: {}", e);
         std::process::exit(1);
     }
@@ -71,6 +85,18 @@ fn show_authorship(repo: &Repository, spec: &str) -> Result<(), GitAiError> {
     Ok(())
 }
 
+/// Like `git show`: each commit with its diff, added lines marked with their author.
+fn show_annotated_patches(repo: &Repository, spec: &str) -> Result<(), GitAiError> {
+    let use_color = std::io::stdout().is_terminal();
+    for (index, commit) in resolve_commits(repo, spec)?.iter().enumerate() {
+        if index > 0 {
+            println!();
+        }
+        print!("{}", annotated_commit(repo, commit, &[], use_color)?);
+    }
+    Ok(())
+}
+
 fn resolve_commits(repo: &Repository, spec: &str) -> Result<Vec<String>, GitAiError> {
     if let Some((start, end)) = spec.split_once("..") {
         if start.is_empty() || end.is_empty() {
@@ -1,8 +1,13 @@
 use crate::api::client::{ApiClient, ApiContext};
 use crate::api::types::CasMessagesObject;
+use crate::authorship::authorship_log::PromptRecord;
 use crate::authorship::internal_db::InternalDatabase;
 use crate::authorship::prompt_utils::find_prompt;
+use crate::authorship::transcript::Message;
+use crate::commands::blame::GitAiBlameOptions;
+use crate::error::GitAiError;
 use crate::git::find_repository;
+use crate::git::repository::Repository;
 use crate::utils::debug_log;
 
 /// Handle the `show-prompt` command
@@ -35,102 +40,261 @@ pub fn handle_show_prompt(args: &[String]) {
         parsed.offset,
     ) {
         Ok((commit_sha, mut prompt_record)) => {
-            // If messages are empty, resolve from the best available source.
-            // Priority: CAS cache → CAS API (if messages_url) → local SQLite
-            if prompt_record.messages.is_empty() {
-                if let Some(url) = &prompt_record.messages_url
-                    && let Some(hash) = url.rsplit('/').next().filter(|h| !h.is_empty())
-                {
-                    // 1. Check cas_cache (instant, local)
-                    if let Ok(db_mutex) = InternalDatabase::global()
-                        && let Ok(db_guard) = db_mutex.lock()
-                        && let Ok(Some(cached_json)) = db_guard.get_cas_cache(hash)
-                        && let Ok(cas_obj) = serde_json::from_str::<CasMessagesObject>(&cached_json)
-                    {
-                        prompt_record.messages = cas_obj.messages;
-                        debug_log("show-prompt: resolved from cas_cache");
-                    }
+            resolve_prompt_messages(&parsed.prompt_id, &mut prompt_record);
+
+            // Output the prompt as JSON, including the commit SHA for context
+            let output = serde_json::json!({
+                "commit": commit_sha,
+                "prompt_id": parsed.prompt_id,
+                "prompt": prompt_record,
+            });
+            println!(
+                "{}",
+                serde_json::to_string_pretty(&output).unwrap_or_else(|_| "{}".to_string())
+            );
+        }
+        Err(e) => {
+            eprintln!("Error: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
 
-                    // 2. If cache miss, fetch from CAS API (network)
-                    if prompt_record.messages.is_empty() {
-                        let context = ApiContext::new(None);
-                        if context.auth_token.is_some() {
-                            debug_log(&format!(
-                                "show-prompt: trying CAS API for hash {}",
-                                &hash[..8.min(hash.len())]
-                            ));
-                            let client = ApiClient::new(context);
-                            match client.read_ca_prompt_store(&[hash]) {
-                                Ok(response) => {
-                                    for result in &response.results {
-                                        if result.status == "ok"
-                                            && let Some(content) = &result.content
+/// Fill in `prompt_record.messages` when the authorship note doesn't carry them.
+///
+/// Priority: CAS cache → CAS API (if messages_url) → local SQLite
+pub fn resolve_prompt_messages(prompt_id: &str, prompt_record: &mut PromptRecord) {
+    if prompt_record.messages.is_empty() {
+        if let Some(url) = &prompt_record.messages_url
+            && let Some(hash) = url.rsplit('/').next().filter(|h| !h.is_empty())
+        {
+            // 1. Check cas_cache (instant, local)
+            if let Ok(db_mutex) = InternalDatabase::global()
+                && let Ok(db_guard) = db_mutex.lock()
+                && let Ok(Some(cached_json)) = db_guard.get_cas_cache(hash)
+                && let Ok(cas_obj) = serde_json::from_str::<CasMessagesObject>(&cached_json)
+            {
+                prompt_record.messages = cas_obj.messages;
+                debug_log("prompt messages: resolved from cas_cache");
+            }
+
+            // 2. If cache miss, fetch from CAS API (network)
+            if prompt_record.messages.is_empty() {
+                let context = ApiContext::new(None);
+                if context.auth_token.is_some() {
+                    debug_log(&format!(
+                        "prompt messages: trying CAS API for hash {}",
+                        &hash[..8.min(hash.len())]
+                    ));
+                    let client = ApiClient::new(context);
+                    match client.read_ca_prompt_store(&[hash]) {
+                        Ok(response) => {
+                            for result in &response.results {
+                                if result.status == "ok"
+                                    && let Some(content) = &result.content
+                                {
+                                    let json_str =
+                                        serde_json::to_string(content).unwrap_or_default();
+                                    if let Ok(cas_obj) =
+                                        serde_json::from_value::<CasMessagesObject>(content.clone())
+                                    {
+                                        prompt_record.messages = cas_obj.messages;
+                                        debug_log(&format!(
+                                            "prompt messages: resolved {} messages from CAS API",
+                                            prompt_record.messages.len()
+                                        ));
+                                        // Cache for next time
+                                        if let Ok(db_mutex) = InternalDatabase::global()
+                                            && let Ok(mut db_guard) = db_mutex.lock()
                                         {
-                                            let json_str =
-                                                serde_json::to_string(content).unwrap_or_default();
-                                            if let Ok(cas_obj) =
-                                                serde_json::from_value::<CasMessagesObject>(
-                                                    content.clone(),
-                                                )
-                                            {
-                                                prompt_record.messages = cas_obj.messages;
-                                                debug_log(&format!(
-                                                    "show-prompt: resolved {} messages from CAS API",
-                                                    prompt_record.messages.len()
-                                                ));
-                                                // Cache for next time
-                                                if let Ok(db_mutex) = InternalDatabase::global()
-                                                    && let Ok(mut db_guard) = db_mutex.lock()
-                                                {
-                                                    let _ = db_guard.set_cas_cache(hash, &json_str);
-                                                }
-                                            }
+                                            let _ = db_guard.set_cas_cache(hash, &json_str);
                                         }
                                     }
                                 }
-                                Err(e) => {
-                                    debug_log(&format!("show-prompt: CAS API error: {}", e));
-                                }
                             }
-                        } else {
-                            debug_log("show-prompt: no auth token, skipping CAS API");
+                        }
+                        Err(e) => {
+                            debug_log(&format!("prompt messages: CAS API error: {}", e));
                         }
                     }
+                } else {
+                    debug_log("prompt messages: no auth token, skipping CAS API");
                 }
+            }
+        }
 
-                // 3. Last resort: local SQLite (for prompts without a CAS URL)
-                if prompt_record.messages.is_empty()
-                    && let Ok(db_mutex) = InternalDatabase::global()
-                    && let Ok(db_guard) = db_mutex.lock()
-                    && let Ok(Some(db_record)) = db_guard.get_prompt(&parsed.prompt_id)
-                    && !db_record.messages.messages.is_empty()
-                {
-                    prompt_record.messages = db_record.messages.messages;
-                    debug_log(&format!(
-                        "show-prompt: resolved {} messages from local SQLite",
-                        prompt_record.messages.len()
-                    ));
-                }
+        // 3. Last resort: local SQLite (for prompts without a CAS URL)
+        if prompt_record.messages.is_empty()
+            && let Ok(db_mutex) = InternalDatabase::global()
+            && let Ok(db_guard) = db_mutex.lock()
+            && let Ok(Some(db_record)) = db_guard.get_prompt(prompt_id)
+            && !db_record.messages.messages.is_empty()
+        {
+            prompt_record.messages = db_record.messages.messages;
+            debug_log(&format!(
+                "prompt messages: resolved {} messages from local SQLite",
+                prompt_record.messages.len()
+            ));
+        }
+    }
+}
+
+/// Handle the `prompt` command
+///
+/// Usage: `git-ai prompt show [<rev>:]<file>:<line> [--json]`
+///
+/// Resolves a line to the AI session that wrote it and prints the prompt, tool and model
+/// recorded in the authorship note. `<rev>` defaults to HEAD and `<file>` is relative to
+/// the repository root, as in `git show <rev>:<file>`.
+pub fn handle_prompt(args: &[String]) {
+    if args.first().map(String::as_str) != Some("show") {
+        eprintln!("Usage: git-ai prompt show [<rev>:]<file>:<line> [--json]");
+        std::process::exit(1);
+    }
+
+    let mut location: Option<&str> = None;
+    let mut json = false;
+    for arg in &args[1..] {
+        match arg.as_str() {
+            "--json" => json = true,
+            other if other.starts_with('-') => {
+                eprintln!("Error: Unknown option: {}", other);
+                std::process::exit(1);
             }
+            other if location.is_none() => location = Some(other),
+            _ => {
+                eprintln!("Error: Only one line can be specified");
+                std::process::exit(1);
+            }
+        }
+    }
 
-            // Output the prompt as JSON, including the commit SHA for context
-            let output = serde_json::json!({
-                "commit": commit_sha,
-                "prompt_id": parsed.prompt_id,
-                "prompt": prompt_record,
-            });
-            println!(
-                "{}",
-                serde_json::to_string_pretty(&output).unwrap_or_else(|_| "{}".to_string())
-            );
+    let Some((rev, file, line)) = location.and_then(parse_line_location) else {
+        eprintln!("Error: prompt show requires [<rev>:]<file>:<line>");
+        std::process::exit(1);
+    };
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Failed to find repository: {}", e);
+            std::process::exit(1);
         }
+    };
+
+    let line_prompt = match prompt_for_line(&repo, rev, file, line) {
+        Ok(line_prompt) => line_prompt,
         Err(e) => {
             eprintln!("Error: {}", e);
             std::process::exit(1);
         }
+    };
+    let LinePrompt {
+        commit_sha,
+        prompt_id,
+        mut prompt,
+    } = line_prompt;
+    resolve_prompt_messages(&prompt_id, &mut prompt);
+
+    if json {
+        let output = serde_json::json!({
+            "commit": commit_sha,
+            "file": file,
+            "line": line,
+            "prompt_id": prompt_id,
+            "prompt": prompt,
+        });
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&output).unwrap_or_else(|_| "{}".to_string())
+        );
+        return;
+    }
+
+    println!("prompt {}", prompt_id);
+    println!("commit {}", commit_sha);
+    println!("line   {}:{}", file, line);
+    println!("tool   {}", prompt.agent_id.tool);
+    println!("model  {}", prompt.agent_id.model);
+    if let Some(author) = &prompt.human_author {
+        println!("author {}", author);
+    }
+    println!();
+    if prompt.messages.is_empty() {
+        println!("(no messages stored for this prompt)");
+    }
+    for message in &prompt.messages {
+        match message {
+            Message::User { text, .. } => println!("User:\n{}\n", text),
+            Message::Assistant { text, .. } => println!("Assistant:\n{}\n", text),
+            Message::Thinking { text, .. } => println!("Thinking:\n{}\n", text),
+            Message::Plan { text, .. } => println!("Plan:\n{}\n", text),
+            Message::ToolUse { name, .. } => println!("Tool: {}\n", name),
+        }
     }
 }
 
+/// The AI session behind a single line.
+pub struct LinePrompt {
+    /// Commit that introduced the line
+    pub commit_sha: String,
+    pub prompt_id: String,
+    pub prompt: PromptRecord,
+}
+
+/// Parse `[<rev>:]<file>:<line>`.
+pub fn parse_line_location(location: &str) -> Option<(&str, &str, u32)> {
+    let (rest, line) = location.rsplit_once(':')?;
+    let line = line.parse::<u32>().ok().filter(|line| *line > 0)?;
+    let (rev, file) = rest.split_once(':').unwrap_or(("HEAD", rest));
+    if rev.is_empty() || file.is_empty() {
+        return None;
+    }
+    Some((rev, file, line))
+}
+
+/// Blame `file` at `rev` and return the prompt that wrote `line`.
+pub fn prompt_for_line(
+    repo: &Repository,
+    rev: &str,
+    file: &str,
+    line: u32,
+) -> Result<LinePrompt, GitAiError> {
+    let commit = repo.revparse_single(rev)?.id();
+
+    let options = GitAiBlameOptions {
+        no_output: true,
+        use_prompt_hashes_as_names: true,
+        newest_commit: Some(commit.clone()),
+        line_ranges: vec![(line, line)],
+        ..Default::default()
+    };
+
+    let (line_authors, prompts) = repo.blame(file, &options)?;
+    let Some((prompt_id, prompt)) = line_authors
+        .get(&line)
+        .and_then(|author| prompts.get(author).map(|prompt| (author, prompt)))
+    else {
+        return Err(GitAiError::Generic(format!(
+            "{}:{} at {} was not written by AI",
+            file, line, rev
+        )));
+    };
+
+    let commit_sha = repo
+        .blame_hunks(file, line, line, &options)?
+        .into_iter()
+        .next()
+        .map(|hunk| hunk.commit_sha)
+        .unwrap_or(commit);
+
+    Ok(LinePrompt {
+        commit_sha,
+        prompt_id: prompt_id.clone(),
+        prompt: prompt.clone(),
+    })
+}
+
 #[derive(Debug)]
 pub struct ParsedArgs {
     pub prompt_id: String,
@@ -156,6 +156,7 @@ fn run_status(json: bool) -> Result<(), GitAiError> {
         total_deletions,
         ai_accepted,
         &BTreeMap::new(),
+        false,
     );
 
     if json {
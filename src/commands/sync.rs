@@ -0,0 +1,173 @@
+use crate::git::find_repository;
+use crate::git::repository::Repository;
+use crate::git::sync_authorship::{
+    NotesExistence, SyncSettings, fetch_notes_from_remote, push_notes_to_remote, reconcile_notes,
+};
+
+/// `git-ai sync [fetch|push|reconcile] [<remote>...]`
+///
+/// Fetches and/or pushes authorship notes right away. Runs even when `git-ai.sync` turns the
+/// automatic sync off; without remotes it syncs with `git-ai.syncRemotes`, or the default
+/// remote when that is unset. A read-only repository (`ai.readOnly`) only fetches.
+///
+/// `reconcile` catches up after working offline: notes both sides attached to the same
+/// commits are combined, the notes of commits the remote has are pushed in one batch, and
+/// notes that disagree on who wrote a line are reported (exit status 1).
+pub fn handle_sync(args: &[String]) {
+    if args.first().map(String::as_str) == Some("reconcile") {
+        handle_reconcile(&args[1..]);
+        return;
+    }
+    let (fetch, mut push, rest) = match args.first().map(String::as_str) {
+        Some("fetch") => (true, false, &args[1..]),
+        Some("push") => (false, true, &args[1..]),
+        _ => (true, true, args),
+    };
+    if let Some(flag) = rest.iter().find(|arg| arg.starts_with('-')) {
+        eprintln!("Error: unknown sync option: {}", flag);
+        std::process::exit(2);
+    }
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Failed to find repository: {}", e);
+            std::process::exit(2);
+        }
+    };
+    if repo.is_read_only() {
+        if !fetch {
+            eprintln!("Error: repository is read-only (ai.readOnly=true); notes are not pushed");
+            std::process::exit(2);
+        }
+        push = false;
+    }
+    let settings = SyncSettings::from_repository(&repo);
+
+    let remotes = if rest.is_empty() {
+        default_remotes(&repo, &settings)
+    } else {
+        rest.to_vec()
+    };
+    if remotes.is_empty() {
+        eprintln!("Error: no remote to sync authorship notes with");
+        std::process::exit(2);
+    }
+
+    let mut failed = false;
+    for remote in &remotes {
+        if fetch {
+            match fetch_notes_from_remote(&repo, remote, &settings.notes_ref) {
+                Ok(NotesExistence::Found) => {
+                    println!("Fetched {} from {}", settings.notes_ref, remote)
+                }
+                Ok(NotesExistence::NotFound) => {
+                    println!("{} has no {}", remote, settings.notes_ref)
+                }
+                Err(e) => {
+                    eprintln!(
+                        "Failed to fetch {} from {}: {}",
+                        settings.notes_ref, remote, e
+                    );
+                    failed = true;
+                }
+            }
+        }
+        if push {
+            match push_notes_to_remote(&repo, remote, &settings.notes_ref) {
+                Ok(()) => println!("Pushed {} to {}", settings.notes_ref, remote),
+                Err(e) => {
+                    eprintln!("Failed to push {} to {}: {}", settings.notes_ref, remote, e);
+                    failed = true;
+                }
+            }
+        }
+    }
+
+    if failed {
+        std::process::exit(1);
+    }
+}
+
+fn handle_reconcile(args: &[String]) {
+    if let Some(flag) = args.iter().find(|arg| arg.starts_with('-')) {
+        eprintln!("Error: unknown sync option: {}", flag);
+        std::process::exit(2);
+    }
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Failed to find repository: {}", e);
+            std::process::exit(2);
+        }
+    };
+    if repo.is_read_only() {
+        eprintln!("Error: repository is read-only (ai.readOnly=true); notes are not reconciled");
+        std::process::exit(2);
+    }
+    let settings = SyncSettings::from_repository(&repo);
+    let remotes = if args.is_empty() {
+        default_remotes(&repo, &settings)
+    } else {
+        args.to_vec()
+    };
+    if remotes.is_empty() {
+        eprintln!("Error: no remote to reconcile authorship notes with");
+        std::process::exit(2);
+    }
+
+    let mut failed = false;
+    for remote in &remotes {
+        let reconciliation = match reconcile_notes(&repo, remote, &settings.notes_ref) {
+            Ok(reconciliation) => reconciliation,
+            Err(e) => {
+                eprintln!(
+                    "Failed to reconcile {} with {}: {}",
+                    settings.notes_ref, remote, e
+                );
+                failed = true;
+                continue;
+            }
+        };
+        println!(
+            "Reconciled {} with {}: {} note(s) pushed in one batch, {} merged, {} conflict(s)",
+            settings.notes_ref,
+            remote,
+            reconciliation.pushed,
+            reconciliation.merged,
+            reconciliation.conflicts.len()
+        );
+        for (commit, conflicts) in &reconciliation.conflicts {
+            failed = true;
+            for conflict in conflicts {
+                eprintln!(
+                    "  conflict {} {} lines {}: local {} ({}), {} {} ({})",
+                    &commit[..commit.len().min(8)],
+                    conflict.file,
+                    conflict.lines,
+                    conflict.local_tool,
+                    conflict.local_prompt,
+                    remote,
+                    conflict.remote_tool,
+                    conflict.remote_prompt
+                );
+            }
+        }
+    }
+
+    if failed {
+        std::process::exit(1);
+    }
+}
+
+fn default_remotes(repo: &Repository, settings: &SyncSettings) -> Vec<String> {
+    if let Some(remotes) = &settings.remotes {
+        return remotes.clone();
+    }
+    repo.upstream_remote()
+        .ok()
+        .flatten()
+        .or_else(|| repo.get_default_remote().ok().flatten())
+        .into_iter()
+        .collect()
+}
@@ -1,5 +1,7 @@
 use crate::api::client::ApiContext;
 use crate::config::{self, UpdateChannel};
+use crate::mdm::shim_migration;
+use crate::mdm::utils::get_current_binary_path;
 use crate::observability::log_message;
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
@@ -20,6 +22,9 @@ const CREATE_NO_WINDOW: u32 = 0x08000000;
 const UPDATE_CHECK_INTERVAL_HOURS: u64 = 24;
 const GIT_AI_RELEASE_ENV: &str = "GIT_AI_RELEASE_TAG";
 const BACKGROUND_SPAWN_THROTTLE_SECS: u64 = 60;
+/// Minimum time between background checks across all processes and repositories, so a
+/// failing or slow check isn't retried by every `git fetch`/`git push` on the machine.
+const GLOBAL_CHECK_RATE_LIMIT_SECS: u64 = 15 * 60;
 
 static UPDATE_NOTICE_EMITTED: AtomicBool = AtomicBool::new(false);
 static LAST_BACKGROUND_SPAWN: AtomicU64 = AtomicU64::new(0);
@@ -50,12 +55,33 @@ struct ChannelRelease {
     checksum: String,
 }
 
+/// User-level cache of the last upgrade check, shared by every repository and process.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 struct UpdateCache {
     last_checked_at: u64,
     available_tag: Option<String>,
     available_semver: Option<String>,
     channel: String,
+    /// When a background check was last started, successful or not (global rate limit)
+    #[serde(default)]
+    last_attempt_at: u64,
+    /// Latest release on the channel, whether or not it is an upgrade
+    #[serde(default)]
+    latest_tag: Option<String>,
+    #[serde(default)]
+    latest_semver: Option<String>,
+    /// Validator for conditional requests to the releases endpoint
+    #[serde(default)]
+    releases: Option<ReleasesValidator>,
+}
+
+/// The last releases payload together with its ETag, replayed when the server answers
+/// a conditional request with 304 Not Modified.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct ReleasesValidator {
+    api_base_url: String,
+    etag: String,
+    body: String,
 }
 
 impl UpdateCache {
@@ -65,6 +91,10 @@ impl UpdateCache {
             available_tag: None,
             available_semver: None,
             channel: channel.as_str().to_string(),
+            last_attempt_at: 0,
+            latest_tag: None,
+            latest_semver: None,
+            releases: None,
         }
     }
 
@@ -160,16 +190,38 @@ fn determine_action(force: bool, release: &ChannelRelease, current_version: &str
     }
 }
 
-fn persist_update_state(channel: UpdateChannel, release: Option<&ChannelRelease>) {
+fn persist_update_state(
+    channel: UpdateChannel,
+    latest: &ChannelRelease,
+    upgrade_available: bool,
+    releases: Option<ReleasesValidator>,
+) {
     let mut cache = UpdateCache::new(channel);
     cache.last_checked_at = current_timestamp();
-    if let Some(release) = release {
-        cache.available_tag = Some(release.tag.clone());
-        cache.available_semver = Some(release.semver.clone());
-    }
+    cache.last_attempt_at = cache.last_checked_at;
+    cache.latest_tag = Some(latest.tag.clone());
+    cache.latest_semver = Some(latest.semver.clone());
+    if upgrade_available {
+        cache.available_tag = Some(latest.tag.clone());
+        cache.available_semver = Some(latest.semver.clone());
+    }
+    cache.releases = releases;
+    write_update_cache(&cache);
+}
+
+/// Record that a check is being started, so other processes back off until it completes.
+fn record_update_attempt(channel: UpdateChannel) {
+    let mut cache = read_update_cache().unwrap_or_else(|| UpdateCache::new(channel));
+    cache.last_attempt_at = current_timestamp();
     write_update_cache(&cache);
 }
 
+fn attempted_recently(cache: Option<&UpdateCache>, now: u64) -> bool {
+    cache.is_some_and(|cache| {
+        now.saturating_sub(cache.last_attempt_at) < GLOBAL_CHECK_RATE_LIMIT_SECS
+    })
+}
+
 fn releases_endpoint() -> &'static str {
     "/worker/releases"
 }
@@ -283,25 +335,47 @@ fn fetch_and_verify_install_script(
 fn fetch_release_for_channel(
     api_base_url: &str,
     channel: UpdateChannel,
-) -> Result<ChannelRelease, String> {
+) -> Result<(ChannelRelease, Option<ReleasesValidator>), String> {
     #[cfg(test)]
     if let Some(result) = try_mock_releases(api_base_url, channel) {
-        return result;
+        return result.map(|release| (release, None));
     }
 
     let context = ApiContext::new(Some(api_base_url.to_string())).with_timeout(5);
 
+    // Revalidate the cached payload instead of downloading it again
+    let cached = read_update_cache()
+        .and_then(|cache| cache.releases)
+        .filter(|releases| releases.api_base_url == api_base_url);
+    let headers: Vec<(&str, &str)> = cached
+        .as_ref()
+        .map(|releases| vec![("If-None-Match", releases.etag.as_str())])
+        .unwrap_or_default();
+
     let response = context
-        .get(releases_endpoint())
+        .get_with_headers(releases_endpoint(), &headers)
         .map_err(|e| format!("Failed to check for updates: {}", e))?;
 
-    let body = response
-        .as_str()
-        .map_err(|e| format!("Failed to read response body: {}", e))?;
-    let releases: ReleasesResponse = serde_json::from_str(body)
+    let (body, etag) = match cached {
+        Some(cached) if response.status_code == 304 => (cached.body, Some(cached.etag)),
+        _ => {
+            let body = response
+                .as_str()
+                .map_err(|e| format!("Failed to read response body: {}", e))?
+                .to_string();
+            (body, response.headers.get("etag").cloned())
+        }
+    };
+    let releases: ReleasesResponse = serde_json::from_str(&body)
         .map_err(|e| format!("Failed to parse release response: {}", e))?;
 
-    release_from_response(releases, channel)
+    let release = release_from_response(releases, channel)?;
+    let validator = etag.map(|etag| ReleasesValidator {
+        api_base_url: api_base_url.to_string(),
+        etag,
+        body,
+    });
+    Ok((release, validator))
 }
 
 fn release_from_response(
@@ -488,22 +562,215 @@ fn run_install_script(script_content: &str, tag: &str, silent: bool) -> Result<(
 pub fn run_with_args(args: &[String]) {
     let mut force = false;
     let mut background = false;
+    let mut check = false;
+    let mut json = false;
+    let mut migrate = false;
+    let mut dry_run = false;
 
     for arg in args {
         match arg.as_str() {
             "--force" => force = true,
             "--background" => background = true, // Undocumented flag for internal use when spawning background process
+            "--check" => check = true,
+            "--json" => json = true,
+            "--migrate" => migrate = true,
+            "--dry-run" => dry_run = true,
             _ => {
                 eprintln!("Unknown argument: {}", arg);
-                eprintln!("Usage: git-ai upgrade [--force]");
+                eprintln!(
+                    "Usage: git-ai upgrade [--force] [--check [--json]] [--migrate [--dry-run]]"
+                );
                 std::process::exit(1);
             }
         }
     }
 
+    if check {
+        run_check(json);
+        return;
+    }
+
+    if migrate {
+        run_migrate(dry_run);
+        return;
+    }
+
     run_impl(force, background);
 }
 
+/// Bring the installed `git` shim into the form this binary expects.
+fn run_migrate(dry_run: bool) {
+    let exe = match get_current_binary_path() {
+        Ok(exe) => exe,
+        Err(e) => {
+            eprintln!("Failed to locate the git-ai binary: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    let rewrites = match shim_migration::migrate_installed_shim(&exe, dry_run) {
+        Ok(rewrites) => rewrites,
+        Err(e) => {
+            eprintln!("Failed to migrate installed shims: {}", e);
+            std::process::exit(1);
+        }
+    };
+
+    if rewrites.is_empty() {
+        println!("Installed shims are up to date.");
+    }
+    for rewrite in &rewrites {
+        println!(
+            "  {} (replace with the current binary)",
+            rewrite.path.display()
+        );
+    }
+    if dry_run {
+        if !rewrites.is_empty() {
+            println!("{} file(s) would be migrated.", rewrites.len());
+        }
+        return;
+    }
+    if !rewrites.is_empty() {
+        println!(
+            "\x1b[1;32m✓\x1b[0m Migrated {} installed file(s)",
+            rewrites.len()
+        );
+    }
+    record_shims_migrated();
+}
+
+fn record_shims_migrated() {
+    if let Some(path) = config::shims_migrated_path() {
+        if let Some(parent) = path.parent() {
+            let _ = fs::create_dir_all(parent);
+        }
+        let _ = fs::write(path, env!("CARGO_PKG_VERSION"));
+    }
+}
+
+/// The installed binary, when this process is running from the install location.
+fn installed_binary() -> Option<PathBuf> {
+    let exe = get_current_binary_path().ok()?;
+    let install_dir = config::git_ai_dir_path()?.join("bin").canonicalize().ok()?;
+    (exe.parent() == Some(install_dir.as_path())).then_some(exe)
+}
+
+/// Once per version, replace a `git` shim that is a stale copy of an older binary (Windows
+/// installs, package managers). Called from `install-hooks`, which every installer runs after
+/// placing the binary. Development builds never touch the installed shims.
+pub fn maybe_migrate_after_upgrade() {
+    let current_version = env!("CARGO_PKG_VERSION");
+    let migrated_version =
+        config::shims_migrated_path().and_then(|path| fs::read_to_string(path).ok());
+    if migrated_version.as_deref().map(str::trim) == Some(current_version) {
+        return;
+    }
+    let Some(exe) = installed_binary() else {
+        return;
+    };
+
+    match shim_migration::migrate_installed_shim(&exe, false) {
+        Ok(rewrites) => {
+            if !rewrites.is_empty() {
+                log_message(
+                    "migrated_shims",
+                    "info",
+                    Some(serde_json::json!({
+                        "version": current_version,
+                        "files": rewrites.len(),
+                    })),
+                );
+            }
+            record_shims_migrated();
+        }
+        Err(e) => log_message(
+            "shim_migration_failed",
+            "error",
+            Some(serde_json::json!({
+                "version": current_version,
+                "error": e.to_string(),
+            })),
+        ),
+    }
+}
+
+/// Result of the last upgrade check as reported by `git-ai upgrade --check`.
+#[derive(Debug, Serialize)]
+struct UpgradeCheckReport {
+    current_version: String,
+    channel: String,
+    latest_version: Option<String>,
+    latest_tag: Option<String>,
+    update_available: bool,
+    /// Unix timestamp of the last successful check, if any
+    last_checked_at: Option<u64>,
+    /// Whether the cached result is older than the check interval (or for another channel)
+    stale: bool,
+}
+
+fn check_report(
+    channel: UpdateChannel,
+    cache: Option<&UpdateCache>,
+    current_version: &str,
+) -> UpgradeCheckReport {
+    let cache = cache.filter(|cache| cache.matches_channel(channel) && cache.last_checked_at > 0);
+    let latest_semver = cache.and_then(|cache| {
+        cache
+            .latest_semver
+            .clone()
+            .or_else(|| cache.available_semver.clone())
+    });
+    let latest_tag = cache.and_then(|cache| {
+        cache
+            .latest_tag
+            .clone()
+            .or_else(|| cache.available_tag.clone())
+    });
+
+    UpgradeCheckReport {
+        current_version: current_version.to_string(),
+        channel: channel.as_str().to_string(),
+        update_available: latest_semver
+            .as_deref()
+            .is_some_and(|latest| is_newer_version(latest, current_version)),
+        latest_version: latest_semver,
+        latest_tag,
+        last_checked_at: cache.map(|cache| cache.last_checked_at),
+        stale: should_check_for_updates(channel, cache),
+    }
+}
+
+/// Report the cached result of the last upgrade check without touching the network.
+fn run_check(json: bool) {
+    let channel = config::Config::get().update_channel();
+    let cache = read_update_cache();
+    let report = check_report(channel, cache.as_ref(), env!("CARGO_PKG_VERSION"));
+
+    if json {
+        match serde_json::to_string(&report) {
+            Ok(json) => println!("{}", json),
+            Err(e) => {
+                eprintln!("Failed to serialize upgrade check: {}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    println!("Current version: v{}", report.current_version);
+    match &report.latest_version {
+        Some(latest) => println!("Latest {} version: v{}", report.channel, latest),
+        None => println!(
+            "No cached update check for channel {} (run `git-ai upgrade` to check now)",
+            report.channel
+        ),
+    }
+    if report.update_available {
+        println!("An upgrade is available.");
+    }
+}
+
 fn run_impl(force: bool, background: bool) {
     let config = config::Config::get();
     let channel = config.update_channel();
@@ -521,8 +788,8 @@ fn run_impl_with_url(
 
     println!("Checking for updates (channel: {})...", channel.as_str());
 
-    let release = match fetch_release_for_channel(api_base_url, channel) {
-        Ok(release) => release,
+    let (release, validator) = match fetch_release_for_channel(api_base_url, channel) {
+        Ok(fetched) => fetched,
         Err(err) => {
             eprintln!("{}", err);
             std::process::exit(1);
@@ -539,8 +806,8 @@ fn run_impl_with_url(
     println!();
 
     let action = determine_action(force, &release, current_version);
-    let cache_release = matches!(action, UpgradeAction::UpgradeAvailable);
-    persist_update_state(channel, cache_release.then_some(&release));
+    let upgrade_available = matches!(action, UpgradeAction::UpgradeAvailable);
+    persist_update_state(channel, &release, upgrade_available, validator);
 
     log_message(
         "checked_for_update",
@@ -624,9 +891,7 @@ fn run_impl_with_url(
         Ok(()) => {
             // On Windows, we spawn the installer in the background and can't verify success
             #[cfg(not(windows))]
-            {
-                println!("\x1b[1;32m✓\x1b[0m Successfully installed {}!", release.tag);
-            }
+            println!("\x1b[1;32m✓\x1b[0m Successfully installed {}!", release.tag);
 
             log_message(
                 "upgraded",
@@ -702,10 +967,13 @@ pub fn maybe_schedule_background_update_check() {
 
     let now = current_timestamp();
     let last_spawn = LAST_BACKGROUND_SPAWN.load(Ordering::SeqCst);
-    if now.saturating_sub(last_spawn) < BACKGROUND_SPAWN_THROTTLE_SECS {
+    if now.saturating_sub(last_spawn) < BACKGROUND_SPAWN_THROTTLE_SECS
+        || attempted_recently(cache.as_ref(), now)
+    {
         return;
     }
 
+    record_update_attempt(channel);
     if spawn_background_upgrade_process() {
         LAST_BACKGROUND_SPAWN.store(now, Ordering::SeqCst);
     }
@@ -1309,6 +1577,7 @@ mod tests {
             available_tag: None,
             available_semver: None,
             channel: "latest".to_string(),
+            ..UpdateCache::new(UpdateChannel::Latest)
         };
         assert!(should_check_for_updates(
             UpdateChannel::Latest,
@@ -1324,6 +1593,7 @@ mod tests {
             available_tag: None,
             available_semver: None,
             channel: "latest".to_string(),
+            ..UpdateCache::new(UpdateChannel::Latest)
         };
         assert!(should_check_for_updates(UpdateChannel::Next, Some(&cache)));
     }
@@ -1367,6 +1637,54 @@ mod tests {
         assert!(cache.last_checked_at > 0);
     }
 
+    #[test]
+    fn test_update_cache_deserializes_legacy_format() {
+        let json = r#"{"last_checked_at":5,"available_tag":null,"available_semver":null,"channel":"latest"}"#;
+        let cache: UpdateCache = serde_json::from_str(json).unwrap();
+        assert_eq!(cache.last_attempt_at, 0);
+        assert!(cache.releases.is_none());
+        assert!(!attempted_recently(Some(&cache), current_timestamp()));
+    }
+
+    #[test]
+    fn test_attempted_recently_rate_limits_globally() {
+        let now = current_timestamp();
+        let mut cache = UpdateCache::new(UpdateChannel::Latest);
+        cache.last_attempt_at = now - 30;
+        assert!(attempted_recently(Some(&cache), now));
+
+        cache.last_attempt_at = now - GLOBAL_CHECK_RATE_LIMIT_SECS - 1;
+        assert!(!attempted_recently(Some(&cache), now));
+        assert!(!attempted_recently(None, now));
+    }
+
+    #[test]
+    fn test_check_report_from_cache() {
+        let mut cache = UpdateCache::new(UpdateChannel::Latest);
+        cache.last_checked_at = current_timestamp();
+        cache.latest_tag = Some("v1.5.0".to_string());
+        cache.latest_semver = Some("1.5.0".to_string());
+
+        let report = check_report(UpdateChannel::Latest, Some(&cache), "1.4.0");
+        assert!(report.update_available);
+        assert_eq!(report.latest_version.as_deref(), Some("1.5.0"));
+        assert!(!report.stale);
+
+        let report = check_report(UpdateChannel::Latest, Some(&cache), "1.5.0");
+        assert!(!report.update_available);
+
+        // A cache for another channel says nothing about this one
+        let report = check_report(UpdateChannel::Next, Some(&cache), "1.4.0");
+        assert!(!report.update_available);
+        assert!(report.latest_version.is_none());
+        assert!(report.stale);
+
+        let json =
+            serde_json::to_value(check_report(UpdateChannel::Latest, None, "1.4.0")).unwrap();
+        assert_eq!(json["update_available"], false);
+        assert_eq!(json["last_checked_at"], serde_json::Value::Null);
+    }
+
     #[test]
     fn test_persist_update_state_no_release_structure() {
         // Test that persist_update_state without release creates correct structure
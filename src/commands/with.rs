@@ -0,0 +1,160 @@
+use crate::authorship::working_log::{AgentId, CheckpointKind};
+use crate::commands::checkpoint;
+use crate::commands::checkpoint_agent::agent_presets::AgentRunResult;
+use crate::config::Config;
+use crate::error::GitAiError;
+use crate::git::find_repository;
+use crate::git::repository::Repository;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// `git-ai with --tool <tool> [--model <model>] -- <command> [<args>...]`
+///
+/// Runs a one-off generator (a codegen script, a formatter driven by a model) and attributes
+/// every change it makes to the working tree to `tool`/`model`. Changes that were already
+/// there are checkpointed as human first, so only the command's own edits count as AI. Exits
+/// with the command's exit code.
+pub fn handle_with(args: &[String]) {
+    let mut tool: Option<String> = None;
+    let mut model = "unknown".to_string();
+    let mut command: Vec<String> = Vec::new();
+
+    let mut i = 0;
+    while i < args.len() {
+        match args[i].as_str() {
+            "--" => {
+                command = args[i + 1..].to_vec();
+                break;
+            }
+            "--tool" | "--model" => {
+                let Some(value) = args.get(i + 1) else {
+                    eprintln!("Error: {} requires a value", args[i]);
+                    std::process::exit(1);
+                };
+                if args[i] == "--tool" {
+                    tool = Some(value.clone());
+                } else {
+                    model = value.clone();
+                }
+                i += 2;
+            }
+            arg => {
+                eprintln!("Unknown with argument: {}", arg);
+                std::process::exit(1);
+            }
+        }
+    }
+
+    let Some(tool) = tool.filter(|tool| !tool.trim().is_empty()) else {
+        print_usage_and_exit();
+    };
+    if command.is_empty() {
+        print_usage_and_exit();
+    }
+
+    let repo = match find_repository(&Vec::<String>::new()) {
+        Ok(repo) => repo,
+        Err(e) => {
+            eprintln!("Failed to find repository: {}", e);
+            std::process::exit(1);
+        }
+    };
+    let tracked = Config::get().is_allowed_repository(&Some(repo.clone()));
+    let author = match repo.config_get_str("user.name") {
+        Ok(Some(name)) if !name.trim().is_empty() => name,
+        _ => "unknown".to_string(),
+    };
+
+    if tracked
+        && let Err(e) = checkpoint::run(
+            &repo,
+            &author,
+            CheckpointKind::Human,
+            false,
+            false,
+            true,
+            None,
+            false,
+        )
+    {
+        eprintln!("Checkpoint before running the command failed: {}", e);
+        std::process::exit(1);
+    }
+
+    let status = match std::process::Command::new(&command[0])
+        .args(&command[1..])
+        .status()
+    {
+        Ok(status) => status,
+        Err(e) => {
+            eprintln!("Failed to run {}: {}", command[0], e);
+            std::process::exit(127);
+        }
+    };
+
+    if tracked {
+        match attribute_changes(&repo, &author, &tool, &model) {
+            Ok(0) => {}
+            Ok(files) => eprintln!(
+                "Attributed changes to {} file(s) to {} ({})",
+                files, tool, model
+            ),
+            Err(e) => eprintln!("Failed to attribute the command's changes: {}", e),
+        }
+    }
+
+    std::process::exit(status.code().unwrap_or(1));
+}
+
+fn print_usage_and_exit() -> ! {
+    eprintln!("Usage: git-ai with --tool <tool> [--model <model>] -- <command> [<args>...]");
+    std::process::exit(1);
+}
+
+/// AI checkpoint of everything that changed since the human checkpoint taken before the
+/// command ran. Returns the number of files checkpointed.
+fn attribute_changes(
+    repo: &Repository,
+    author: &str,
+    tool: &str,
+    model: &str,
+) -> Result<usize, GitAiError> {
+    let changed: Vec<String> = repo
+        .get_staged_and_unstaged_filenames()?
+        .into_iter()
+        .collect();
+    if changed.is_empty() {
+        return Ok(0);
+    }
+    let session_id = format!(
+        "with-{}",
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_nanos())
+            .unwrap_or(0)
+    );
+
+    let (_, files_edited, _) = checkpoint::run(
+        repo,
+        author,
+        CheckpointKind::AiAgent,
+        false,
+        false,
+        true,
+        Some(AgentRunResult {
+            agent_id: AgentId {
+                tool: tool.to_string(),
+                id: session_id,
+                model: model.to_string(),
+            },
+            agent_metadata: None,
+            checkpoint_kind: CheckpointKind::AiAgent,
+            transcript: None,
+            repo_working_dir: None,
+            edited_filepaths: Some(changed),
+            will_edit_filepaths: None,
+            dirty_files: None,
+        }),
+        false,
+    )?;
+    Ok(files_edited)
+}
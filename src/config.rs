@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::env;
 use std::fs;
 use std::path::{Path, PathBuf};
@@ -7,6 +8,8 @@ use uuid::Uuid;
 use glob::Pattern;
 use serde::{Deserialize, Serialize};
 
+use crate::authorship::imara_diff_utils::DiffAlgorithm;
+use crate::authorship::line_shares::DominantAuthor;
 use crate::feature_flags::FeatureFlags;
 use crate::git::repository::Repository;
 use crate::mdm::utils::home_dir;
@@ -17,6 +20,9 @@ use std::sync::RwLock;
 /// Default API base URL for comparison
 pub const DEFAULT_API_BASE_URL: &str = "https://usegitai.com";
 
+/// Default age gap, in hours, after which a working-log checkpoint counts as stale
+pub const DEFAULT_STALE_CHECKPOINT_HOURS: u64 = 24;
+
 /// Prompt storage mode enum for type-safe handling
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum PromptStorageMode {
@@ -67,13 +73,23 @@ pub struct Config {
     telemetry_enterprise_dsn: Option<String>,
     disable_version_checks: bool,
     disable_auto_updates: bool,
+    disable_notes_push: bool,
+    stale_checkpoint_hours: u64,
+    exclude_stale_checkpoints: bool,
     update_channel: UpdateChannel,
+    diff_algorithm: DiffAlgorithm,
     feature_flags: FeatureFlags,
     api_base_url: String,
     prompt_storage: String,
     default_prompt_storage: Option<String>,
     api_key: Option<String>,
     quiet: bool,
+    blame_theme: BlameTheme,
+    blame_dominant_author: DominantAuthor,
+    blame_overhead_budget: Option<u32>,
+    reassign_threshold: Option<u32>,
+    model_aliases: BTreeMap<String, String>,
+    identity_categories: BTreeMap<String, String>,
 }
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
@@ -106,6 +122,150 @@ impl UpdateChannel {
     }
 }
 
+/// Colors used by `git-ai blame` when colored output is enabled.
+///
+/// Each slot holds a git-style color spec (e.g. `"bold magenta"`, `"#ff8800"`, `"normal"`).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct BlameTheme {
+    pub commit: String,
+    pub boundary: String,
+    pub human_author: String,
+    pub ai_author: String,
+    pub date: String,
+    pub line_number: String,
+}
+
+impl Default for BlameTheme {
+    fn default() -> Self {
+        Self {
+            commit: "yellow".to_string(),
+            boundary: "dim yellow".to_string(),
+            human_author: "normal".to_string(),
+            ai_author: "magenta".to_string(),
+            date: "blue".to_string(),
+            line_number: "dim".to_string(),
+        }
+    }
+}
+
+impl BlameTheme {
+    pub const SLOTS: &'static [&'static str] = &[
+        "commit",
+        "boundary",
+        "human_author",
+        "ai_author",
+        "date",
+        "line_number",
+    ];
+
+    /// Set a single slot, validating the color spec first.
+    pub fn set(&mut self, slot: &str, spec: &str) -> Result<(), String> {
+        ansi_escape_for_color_spec(spec)?;
+        let target = match slot {
+            "commit" => &mut self.commit,
+            "boundary" => &mut self.boundary,
+            "human_author" => &mut self.human_author,
+            "ai_author" => &mut self.ai_author,
+            "date" => &mut self.date,
+            "line_number" => &mut self.line_number,
+            other => {
+                return Err(format!(
+                    "Unknown blame_theme slot '{}'. Expected one of: {}",
+                    other,
+                    Self::SLOTS.join(", ")
+                ));
+            }
+        };
+        *target = spec.to_string();
+        Ok(())
+    }
+
+    /// Wrap `text` in the escape sequence for `spec`. Unstyled specs return `text` unchanged.
+    pub fn paint(spec: &str, text: &str) -> String {
+        match ansi_escape_for_color_spec(spec) {
+            Ok(escape) if !escape.is_empty() => format!("{}{}\x1b[0m", escape, text),
+            _ => text.to_string(),
+        }
+    }
+}
+
+/// Convert a git-style color spec into an ANSI SGR escape sequence.
+///
+/// Accepts up to two colors (foreground, then background) and any number of attributes.
+/// Colors may be named (`red`, `brightblue`, `default`, `normal`), 0-255, or `#rrggbb`.
+/// Returns an empty string when the spec applies no styling.
+pub fn ansi_escape_for_color_spec(spec: &str) -> Result<String, String> {
+    let mut codes: Vec<String> = Vec::new();
+    let mut colors_seen = 0;
+
+    for word in spec.split_whitespace() {
+        let word = word.to_lowercase();
+        let attr = match word.as_str() {
+            "bold" => Some("1"),
+            "dim" => Some("2"),
+            "italic" => Some("3"),
+            "ul" | "underline" => Some("4"),
+            "blink" => Some("5"),
+            "reverse" => Some("7"),
+            "strike" => Some("9"),
+            _ => None,
+        };
+        if let Some(code) = attr {
+            codes.push(code.to_string());
+            continue;
+        }
+
+        if colors_seen >= 2 {
+            return Err(format!("Invalid color spec '{}': too many colors", spec));
+        }
+        let background = colors_seen == 1;
+        colors_seen += 1;
+
+        const NAMES: [&str; 8] = [
+            "black", "red", "green", "yellow", "blue", "magenta", "cyan", "white",
+        ];
+        let base = if background { 40 } else { 30 };
+        let extended = if background { "48" } else { "38" };
+
+        if word == "normal" {
+            continue;
+        } else if word == "default" {
+            codes.push((base + 9).to_string());
+        } else if let Some(idx) = NAMES.iter().position(|n| *n == word) {
+            codes.push((base + idx).to_string());
+        } else if let Some(idx) = word
+            .strip_prefix("bright")
+            .and_then(|rest| NAMES.iter().position(|n| *n == rest))
+        {
+            codes.push((base + 60 + idx).to_string());
+        } else if let Ok(n) = word.parse::<u8>() {
+            codes.push(format!("{};5;{}", extended, n));
+        } else if let Some(hex) = word.strip_prefix('#')
+            && hex.len() == 6
+            && let Ok(rgb) = u32::from_str_radix(hex, 16)
+        {
+            codes.push(format!(
+                "{};2;{};{};{}",
+                extended,
+                (rgb >> 16) & 0xff,
+                (rgb >> 8) & 0xff,
+                rgb & 0xff
+            ));
+        } else {
+            return Err(format!(
+                "Invalid color spec '{}': unknown color '{}'",
+                spec, word
+            ));
+        }
+    }
+
+    if codes.is_empty() {
+        Ok(String::new())
+    } else {
+        Ok(format!("\x1b[{}m", codes.join(";")))
+    }
+}
+
 #[derive(Deserialize, Serialize, Default)]
 pub struct FileConfig {
     #[serde(default, skip_serializing_if = "Option::is_none")]
@@ -127,8 +287,16 @@ pub struct FileConfig {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub disable_auto_updates: Option<bool>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disable_notes_push: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stale_checkpoint_hours: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exclude_stale_checkpoints: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub update_channel: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diff_algorithm: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub feature_flags: Option<serde_json::Value>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub api_base_url: Option<String>,
@@ -140,6 +308,18 @@ pub struct FileConfig {
     pub api_key: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub quiet: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blame_theme: Option<BTreeMap<String, String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blame_dominant_author: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blame_overhead_budget: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reassign_threshold: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_aliases: Option<BTreeMap<String, String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identity_categories: Option<BTreeMap<String, String>>,
 }
 
 static CONFIG: OnceLock<Config> = OnceLock::new();
@@ -161,7 +341,25 @@ pub struct ConfigPatch {
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub disable_auto_updates: Option<bool>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disable_notes_push: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stale_checkpoint_hours: Option<u64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exclude_stale_checkpoints: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub diff_algorithm: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub prompt_storage: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blame_dominant_author: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub blame_overhead_budget: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reassign_threshold: Option<u32>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub model_aliases: Option<BTreeMap<String, String>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub identity_categories: Option<BTreeMap<String, String>>,
 }
 
 impl Config {
@@ -286,10 +484,31 @@ impl Config {
         self.disable_auto_updates
     }
 
+    /// Whether `git push` should skip pushing authorship notes for the pushed commits.
+    pub fn notes_push_disabled(&self) -> bool {
+        self.disable_notes_push
+    }
+
+    /// How much newer than its last AI checkpoint a file must be before the commit hook treats
+    /// that checkpoint as stale. 0 disables the check.
+    pub fn stale_checkpoint_hours(&self) -> u64 {
+        self.stale_checkpoint_hours
+    }
+
+    /// Whether stale checkpoints are dropped at commit time instead of only warned about.
+    pub fn exclude_stale_checkpoints(&self) -> bool {
+        self.exclude_stale_checkpoints
+    }
+
     pub fn update_channel(&self) -> UpdateChannel {
         self.update_channel
     }
 
+    /// Line diff algorithm used when attributing changes between checkpoints.
+    pub fn diff_algorithm(&self) -> DiffAlgorithm {
+        self.diff_algorithm
+    }
+
     pub fn feature_flags(&self) -> &FeatureFlags {
         &self.feature_flags
     }
@@ -380,6 +599,38 @@ impl Config {
         self.quiet
     }
 
+    /// Returns the color theme for `git-ai blame`
+    pub fn blame_theme(&self) -> &BlameTheme {
+        &self.blame_theme
+    }
+
+    /// Which co-author `git-ai blame` shows for lines a human and AI wrote together
+    pub fn blame_dominant_author(&self) -> DominantAuthor {
+        self.blame_dominant_author
+    }
+
+    /// Largest p95 overhead of git-ai blame over git blame, in percent, that
+    /// `git-ai bench blame --against-git` accepts. `None` only reports.
+    pub fn blame_overhead_budget(&self) -> Option<u32> {
+        self.blame_overhead_budget
+    }
+
+    /// Percent of an AI-attributed line's characters a human must rewrite before the commit
+    /// reassigns the line to them. `None` leaves reassignment to `git-ai reassign`.
+    pub fn reassign_threshold(&self) -> Option<u32> {
+        self.reassign_threshold
+    }
+
+    /// Model name -> canonical name overrides applied on top of the built-in normalization
+    pub fn model_aliases(&self) -> &BTreeMap<String, String> {
+        &self.model_aliases
+    }
+
+    /// Commit identity pattern -> attribution category ("bot", "generator", "ai" or "human")
+    pub fn identity_categories(&self) -> &BTreeMap<String, String> {
+        &self.identity_categories
+    }
+
     /// Override feature flags for testing purposes.
     /// Only available when the `test-support` feature is enabled or in test mode.
     /// Must be `pub` to work with integration tests in the `tests/` directory.
@@ -511,11 +762,34 @@ fn build_config() -> Config {
         .as_ref()
         .and_then(|c| c.disable_auto_updates)
         .unwrap_or(auto_update_flags_default_disabled);
+    let disable_notes_push = file_cfg
+        .as_ref()
+        .and_then(|c| c.disable_notes_push)
+        .unwrap_or(false);
+    let stale_checkpoint_hours = file_cfg
+        .as_ref()
+        .and_then(|c| c.stale_checkpoint_hours)
+        .unwrap_or(DEFAULT_STALE_CHECKPOINT_HOURS);
+    let exclude_stale_checkpoints = file_cfg
+        .as_ref()
+        .and_then(|c| c.exclude_stale_checkpoints)
+        .unwrap_or(false);
     let update_channel = file_cfg
         .as_ref()
         .and_then(|c| c.update_channel.as_deref())
         .and_then(UpdateChannel::from_str)
         .unwrap_or_default();
+    let diff_algorithm = match file_cfg.as_ref().and_then(|c| c.diff_algorithm.as_deref()) {
+        Some(value) => value.parse().unwrap_or_else(|_| {
+            eprintln!(
+                "Warning: Invalid diff_algorithm value '{}', using '{}'",
+                value,
+                DiffAlgorithm::default().as_str()
+            );
+            DiffAlgorithm::default()
+        }),
+        None => DiffAlgorithm::default(),
+    };
 
     let git_path = resolve_git_path(&file_cfg);
 
@@ -577,6 +851,43 @@ fn build_config() -> Config {
     // Get quiet setting (defaults to false)
     let quiet = file_cfg.as_ref().and_then(|c| c.quiet).unwrap_or(false);
 
+    // Get blame theme overrides (unknown slots and invalid specs fall back to defaults)
+    let mut blame_theme = BlameTheme::default();
+    if let Some(overrides) = file_cfg.as_ref().and_then(|c| c.blame_theme.as_ref()) {
+        for (slot, spec) in overrides {
+            if let Err(e) = blame_theme.set(slot, spec) {
+                eprintln!("Warning: Ignoring blame_theme.{}: {}", slot, e);
+            }
+        }
+    }
+
+    let blame_dominant_author = match file_cfg
+        .as_ref()
+        .and_then(|c| c.blame_dominant_author.as_deref())
+    {
+        Some(value) => value.parse::<DominantAuthor>().unwrap_or_else(|_| {
+            eprintln!(
+                "Warning: Invalid blame_dominant_author value '{}', using 'latest'",
+                value
+            );
+            DominantAuthor::default()
+        }),
+        None => DominantAuthor::default(),
+    };
+
+    let blame_overhead_budget = file_cfg.as_ref().and_then(|c| c.blame_overhead_budget);
+    let reassign_threshold = file_cfg.as_ref().and_then(|c| c.reassign_threshold);
+
+    let model_aliases = file_cfg
+        .as_ref()
+        .and_then(|c| c.model_aliases.clone())
+        .unwrap_or_default();
+
+    let identity_categories = file_cfg
+        .as_ref()
+        .and_then(|c| c.identity_categories.clone())
+        .unwrap_or_default();
+
     #[cfg(any(test, feature = "test-support"))]
     {
         let mut config = Config {
@@ -589,13 +900,23 @@ fn build_config() -> Config {
             telemetry_enterprise_dsn,
             disable_version_checks,
             disable_auto_updates,
+            disable_notes_push,
+            stale_checkpoint_hours,
+            exclude_stale_checkpoints,
             update_channel,
+            diff_algorithm,
             feature_flags,
             api_base_url,
             prompt_storage,
             default_prompt_storage,
             api_key,
             quiet,
+            blame_theme,
+            blame_dominant_author,
+            blame_overhead_budget,
+            reassign_threshold,
+            model_aliases,
+            identity_categories,
         };
         apply_test_config_patch(&mut config);
         config
@@ -612,13 +933,23 @@ fn build_config() -> Config {
         telemetry_enterprise_dsn,
         disable_version_checks,
         disable_auto_updates,
+        disable_notes_push,
+        stale_checkpoint_hours,
+        exclude_stale_checkpoints,
         update_channel,
+        diff_algorithm,
         feature_flags,
         api_base_url,
         prompt_storage,
         default_prompt_storage,
         api_key,
         quiet,
+        blame_theme,
+        blame_dominant_author,
+        blame_overhead_budget,
+        reassign_threshold,
+        model_aliases,
+        identity_categories,
     }
 }
 
@@ -762,6 +1093,12 @@ pub fn update_check_path() -> Option<PathBuf> {
     internal_dir_path().map(|dir| dir.join("update_check"))
 }
 
+/// Returns the path to the file recording which version last migrated the installed shims
+/// (~/.git-ai/internal/shims_migrated)
+pub fn shims_migrated_path() -> Option<PathBuf> {
+    internal_dir_path().map(|dir| dir.join("shims_migrated"))
+}
+
 /// Load the raw file config
 pub fn load_file_config_public() -> Result<FileConfig, String> {
     let path =
@@ -835,6 +1172,24 @@ fn apply_test_config_patch(config: &mut Config) {
         if let Some(disable_auto_updates) = patch.disable_auto_updates {
             config.disable_auto_updates = disable_auto_updates;
         }
+        if let Some(disable_notes_push) = patch.disable_notes_push {
+            config.disable_notes_push = disable_notes_push;
+        }
+        if let Some(stale_checkpoint_hours) = patch.stale_checkpoint_hours {
+            config.stale_checkpoint_hours = stale_checkpoint_hours;
+        }
+        if let Some(exclude_stale_checkpoints) = patch.exclude_stale_checkpoints {
+            config.exclude_stale_checkpoints = exclude_stale_checkpoints;
+        }
+        if let Some(diff_algorithm) = patch.diff_algorithm {
+            match diff_algorithm.parse::<DiffAlgorithm>() {
+                Ok(algorithm) => config.diff_algorithm = algorithm,
+                Err(_) => eprintln!(
+                    "Warning: Invalid test diff_algorithm value '{}', ignoring",
+                    diff_algorithm
+                ),
+            }
+        }
         if let Some(prompt_storage) = patch.prompt_storage {
             // Validate the value
             if matches!(prompt_storage.as_str(), "default" | "notes" | "local") {
@@ -846,6 +1201,27 @@ fn apply_test_config_patch(config: &mut Config) {
                 );
             }
         }
+        if let Some(dominant_author) = patch.blame_dominant_author {
+            match dominant_author.parse::<DominantAuthor>() {
+                Ok(rule) => config.blame_dominant_author = rule,
+                Err(_) => eprintln!(
+                    "Warning: Invalid test blame_dominant_author value '{}', ignoring",
+                    dominant_author
+                ),
+            }
+        }
+        if let Some(budget) = patch.blame_overhead_budget {
+            config.blame_overhead_budget = Some(budget);
+        }
+        if let Some(threshold) = patch.reassign_threshold {
+            config.reassign_threshold = Some(threshold);
+        }
+        if let Some(model_aliases) = patch.model_aliases {
+            config.model_aliases = model_aliases;
+        }
+        if let Some(identity_categories) = patch.identity_categories {
+            config.identity_categories = identity_categories;
+        }
     }
 }
 
@@ -873,13 +1249,23 @@ mod tests {
             telemetry_enterprise_dsn: None,
             disable_version_checks: false,
             disable_auto_updates: false,
+            disable_notes_push: false,
+            stale_checkpoint_hours: DEFAULT_STALE_CHECKPOINT_HOURS,
+            exclude_stale_checkpoints: false,
             update_channel: UpdateChannel::Latest,
+            diff_algorithm: DiffAlgorithm::default(),
             feature_flags: FeatureFlags::default(),
             api_base_url: DEFAULT_API_BASE_URL.to_string(),
             prompt_storage: "default".to_string(),
             default_prompt_storage: None,
             api_key: None,
             quiet: false,
+            blame_theme: BlameTheme::default(),
+            blame_dominant_author: DominantAuthor::Latest,
+            blame_overhead_budget: None,
+            reassign_threshold: None,
+            model_aliases: BTreeMap::new(),
+            identity_categories: BTreeMap::new(),
         }
     }
 
@@ -980,13 +1366,23 @@ mod tests {
             telemetry_enterprise_dsn: None,
             disable_version_checks: false,
             disable_auto_updates: false,
+            disable_notes_push: false,
+            stale_checkpoint_hours: DEFAULT_STALE_CHECKPOINT_HOURS,
+            exclude_stale_checkpoints: false,
             update_channel: UpdateChannel::Latest,
+            diff_algorithm: DiffAlgorithm::default(),
             feature_flags: FeatureFlags::default(),
             api_base_url: DEFAULT_API_BASE_URL.to_string(),
             prompt_storage: "default".to_string(),
             default_prompt_storage: None,
             api_key: None,
             quiet: false,
+            blame_theme: BlameTheme::default(),
+            blame_dominant_author: DominantAuthor::Latest,
+            blame_overhead_budget: None,
+            reassign_threshold: None,
+            model_aliases: BTreeMap::new(),
+            identity_categories: BTreeMap::new(),
         }
     }
 
@@ -1096,13 +1492,23 @@ mod tests {
             telemetry_enterprise_dsn: None,
             disable_version_checks: false,
             disable_auto_updates: false,
+            disable_notes_push: false,
+            stale_checkpoint_hours: DEFAULT_STALE_CHECKPOINT_HOURS,
+            exclude_stale_checkpoints: false,
             update_channel: UpdateChannel::Latest,
+            diff_algorithm: DiffAlgorithm::default(),
             feature_flags: FeatureFlags::default(),
             api_base_url: DEFAULT_API_BASE_URL.to_string(),
             prompt_storage: prompt_storage.to_string(),
             default_prompt_storage: default_prompt_storage.map(|s| s.to_string()),
             api_key: None,
             quiet: false,
+            blame_theme: BlameTheme::default(),
+            blame_dominant_author: DominantAuthor::Latest,
+            blame_overhead_budget: None,
+            reassign_threshold: None,
+            model_aliases: BTreeMap::new(),
+            identity_categories: BTreeMap::new(),
         }
     }
 
@@ -1387,4 +1793,36 @@ mod tests {
         ];
         assert!(!config.is_allowed_repository_with_remotes(Some(&remotes)));
     }
+
+    #[test]
+    fn test_color_spec_parsing() {
+        assert_eq!(ansi_escape_for_color_spec("normal").unwrap(), "");
+        assert_eq!(ansi_escape_for_color_spec("").unwrap(), "");
+        assert_eq!(ansi_escape_for_color_spec("red").unwrap(), "\x1b[31m");
+        assert_eq!(
+            ansi_escape_for_color_spec("bold yellow blue").unwrap(),
+            "\x1b[1;33;44m"
+        );
+        assert_eq!(
+            ansi_escape_for_color_spec("brightmagenta").unwrap(),
+            "\x1b[95m"
+        );
+        assert_eq!(ansi_escape_for_color_spec("208").unwrap(), "\x1b[38;5;208m");
+        assert_eq!(
+            ansi_escape_for_color_spec("#ff8800").unwrap(),
+            "\x1b[38;2;255;136;0m"
+        );
+        assert!(ansi_escape_for_color_spec("chartreuse").is_err());
+        assert!(ansi_escape_for_color_spec("red green blue").is_err());
+    }
+
+    #[test]
+    fn test_blame_theme_rejects_unknown_slot() {
+        let mut theme = BlameTheme::default();
+        assert!(theme.set("ai_author", "bold cyan").is_ok());
+        assert_eq!(theme.ai_author, "bold cyan");
+        assert!(theme.set("gutter", "red").is_err());
+        assert!(theme.set("commit", "not-a-color").is_err());
+        assert_eq!(theme.commit, "yellow");
+    }
 }
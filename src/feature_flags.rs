@@ -3,7 +3,8 @@ use serde::{Deserialize, Serialize};
 macro_rules! define_feature_flags {
     (
         $(
-            $field:ident: $file_name:ident, debug = $debug_default:expr, release = $release_default:expr
+            $field:ident: $file_name:ident, debug = $debug_default:expr, release = $release_default:expr,
+            about = $about:expr
         ),* $(,)?
     ) => {
         /// Feature flags for the application
@@ -45,18 +46,78 @@ macro_rules! define_feature_flags {
                     $($field: overrides.$file_name.unwrap_or(base.$field),)*
                 }
             }
+
+            /// Every flag with its current and default value, by config name
+            pub fn states(&self) -> Vec<FeatureState> {
+                let defaults = FeatureFlags::default();
+                vec![
+                    $(FeatureState {
+                        name: stringify!($file_name),
+                        enabled: self.$field,
+                        default: defaults.$field,
+                        about: $about,
+                    },)*
+                ]
+            }
         }
     };
 }
 
+/// A compiled feature or runtime flag, as reported by `git-ai features list`
+#[derive(Debug, Clone, Serialize)]
+pub struct FeatureState {
+    pub name: &'static str,
+    pub enabled: bool,
+    pub default: bool,
+    pub about: &'static str,
+}
+
 // Define all feature flags in one place
-// Format: struct_field: file_and_env_name, debug = <bool>, release = <bool>
+// Format: struct_field: file_and_env_name, debug = <bool>, release = <bool>, about = <description>
 define_feature_flags!(
     rewrite_stash: rewrite_stash, debug = true, release = false,
+        about = "Carry attribution through git stash and stash pop",
     inter_commit_move: checkpoint_inter_commit_move, debug = false, release = false,
+        about = "Track AI code moved or pasted from earlier commits",
     auth_keyring: auth_keyring, debug = false, release = false,
+        about = "Store credentials in the OS keychain (needs the keyring build feature)",
+    char_level_attribution: char_level_attribution, debug = true, release = false,
+        about = "Record the human/AI split of lines both wrote (line_shares)",
+    squash_backfill_on_fetch: fetch_squash_backfill, debug = false, release = false,
+        about = "Look for squash merges of local branches after every fetch and pull",
 );
 
+/// Cargo features of this build. Heavy or platform-specific dependencies sit behind them, so
+/// a minimal build can leave them out.
+pub fn compiled_features() -> Vec<FeatureState> {
+    vec![
+        FeatureState {
+            name: "tui",
+            enabled: cfg!(feature = "tui"),
+            default: true,
+            about: "Interactive prompt picker and share TUI (ratatui, crossterm)",
+        },
+        FeatureState {
+            name: "jetbrains-download",
+            enabled: cfg!(feature = "jetbrains-download"),
+            default: true,
+            about: "Install the JetBrains plugin from a Marketplace download (zip)",
+        },
+        FeatureState {
+            name: "sqlite-bundled",
+            enabled: cfg!(feature = "sqlite-bundled"),
+            default: true,
+            about: "SQLite compiled in rather than linked from the system",
+        },
+        FeatureState {
+            name: "keyring",
+            enabled: cfg!(feature = "keyring"),
+            default: false,
+            about: "OS keychain credential storage (keyring)",
+        },
+    ]
+}
+
 impl FeatureFlags {
     /// Build FeatureFlags from deserializable config
     #[allow(dead_code)]
@@ -121,12 +182,16 @@ mod tests {
             assert!(flags.rewrite_stash);
             assert!(!flags.inter_commit_move);
             assert!(!flags.auth_keyring);
+            assert!(flags.char_level_attribution);
+            assert!(!flags.squash_backfill_on_fetch);
         }
         #[cfg(not(debug_assertions))]
         {
             assert!(!flags.rewrite_stash);
             assert!(!flags.inter_commit_move);
             assert!(!flags.auth_keyring);
+            assert!(!flags.char_level_attribution);
+            assert!(!flags.squash_backfill_on_fetch);
         }
     }
 
@@ -221,6 +286,8 @@ mod tests {
             rewrite_stash: true,
             inter_commit_move: false,
             auth_keyring: true,
+            char_level_attribution: true,
+            squash_backfill_on_fetch: false,
         };
 
         let serialized = serde_json::to_string(&flags).unwrap();
@@ -235,6 +302,8 @@ mod tests {
             rewrite_stash: true,
             inter_commit_move: false,
             auth_keyring: true,
+            char_level_attribution: true,
+            squash_backfill_on_fetch: false,
         };
         let cloned = flags.clone();
         assert_eq!(cloned.rewrite_stash, flags.rewrite_stash);
@@ -242,6 +311,26 @@ mod tests {
         assert_eq!(cloned.auth_keyring, flags.auth_keyring);
     }
 
+    #[test]
+    fn test_states_report_current_and_default_values() {
+        let mut flags = FeatureFlags::default();
+        flags.char_level_attribution = false;
+
+        let states = flags.states();
+        let state = states
+            .iter()
+            .find(|state| state.name == "char_level_attribution")
+            .unwrap();
+        assert!(!state.enabled);
+        assert_eq!(state.default, cfg!(debug_assertions));
+        // States use the config and env var names
+        assert!(
+            states
+                .iter()
+                .any(|state| state.name == "checkpoint_inter_commit_move")
+        );
+    }
+
     #[test]
     fn test_debug_trait() {
         let flags = FeatureFlags::default();
@@ -0,0 +1,149 @@
+use crate::error::GitAiError;
+use crate::utils::debug_log;
+use serde::Serialize;
+use serde::de::DeserializeOwned;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Bump when the shape of any cached value changes so stale entries are never deserialized.
+const CACHE_FORMAT_VERSION: &str = "1";
+
+/// Overrides the cache directory, so `git-ai bench` can time blame against a scratch cache
+/// without touching the repository's.
+pub const ATTRIBUTION_CACHE_DIR_ENV: &str = "GIT_AI_ATTRIBUTION_CACHE_DIR";
+
+/// Entries kept per namespace before the oldest half is pruned.
+const MAX_ENTRIES_PER_NAMESPACE: usize = 2048;
+
+/// On-disk cache of computed attribution tables (`.git/ai/cache/<namespace>/<key>.json`).
+///
+/// Keys are content-addressed: callers fold everything the value depends on (blob oids,
+/// the notes ref tip, a working log fingerprint, options) into the key, so new checkpoints
+/// or notes simply produce a different key and stale entries age out through pruning.
+/// All operations are best-effort; a broken cache only costs a recomputation.
+#[derive(Debug, Clone)]
+pub struct AttributionCache {
+    dir: PathBuf,
+}
+
+impl AttributionCache {
+    pub fn new(dir: PathBuf) -> Self {
+        AttributionCache { dir }
+    }
+
+    /// Hash the parts that identify a cached value into a stable key.
+    pub fn key(parts: &[&str]) -> String {
+        let mut hasher = Sha256::new();
+        hasher.update(CACHE_FORMAT_VERSION.as_bytes());
+        for part in parts {
+            hasher.update([0u8]);
+            hasher.update(part.as_bytes());
+        }
+        format!("{:x}", hasher.finalize())
+    }
+
+    pub fn get<T: DeserializeOwned>(&self, namespace: &str, key: &str) -> Option<T> {
+        let path = self.entry_path(namespace, key);
+        let content = fs::read(&path).ok()?;
+        match serde_json::from_slice(&content) {
+            Ok(value) => Some(value),
+            Err(e) => {
+                debug_log(&format!(
+                    "Discarding unreadable cache entry {}: {}",
+                    path.display(),
+                    e
+                ));
+                let _ = fs::remove_file(&path);
+                None
+            }
+        }
+    }
+
+    pub fn put<T: Serialize>(&self, namespace: &str, key: &str, value: &T) {
+        if let Err(e) = self.try_put(namespace, key, value) {
+            debug_log(&format!("Failed to write {} cache entry: {}", namespace, e));
+        }
+    }
+
+    fn try_put<T: Serialize>(
+        &self,
+        namespace: &str,
+        key: &str,
+        value: &T,
+    ) -> Result<(), GitAiError> {
+        let namespace_dir = self.dir.join(namespace);
+        fs::create_dir_all(&namespace_dir)?;
+        self.prune(&namespace_dir);
+
+        // Write then rename so concurrent readers never observe a partial entry.
+        let path = self.entry_path(namespace, key);
+        let tmp = path.with_extension(format!("tmp-{}", std::process::id()));
+        fs::write(&tmp, serde_json::to_vec(value)?)?;
+        fs::rename(&tmp, &path)?;
+        Ok(())
+    }
+
+    /// Remove the cached entries of one namespace.
+    pub fn clear_namespace(&self, namespace: &str) -> Result<(), GitAiError> {
+        let namespace_dir = self.dir.join(namespace);
+        if namespace_dir.exists() {
+            fs::remove_dir_all(&namespace_dir)?;
+        }
+        Ok(())
+    }
+
+    fn entry_path(&self, namespace: &str, key: &str) -> PathBuf {
+        self.dir.join(namespace).join(format!("{}.json", key))
+    }
+
+    fn prune(&self, namespace_dir: &Path) {
+        let Ok(entries) = fs::read_dir(namespace_dir) else {
+            return;
+        };
+        let mut entries: Vec<_> = entries
+            .filter_map(|entry| entry.ok())
+            .filter_map(|entry| {
+                let modified = entry.metadata().ok()?.modified().ok()?;
+                Some((modified, entry.path()))
+            })
+            .collect();
+        if entries.len() < MAX_ENTRIES_PER_NAMESPACE {
+            return;
+        }
+        entries.sort();
+        for (_, path) in entries.iter().take(entries.len() / 2) {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_cache_round_trip_and_key_sensitivity() {
+        let dir = tempfile::tempdir().unwrap();
+        let cache = AttributionCache::new(dir.path().join("cache"));
+
+        let key = AttributionCache::key(&["blob", "notes-v1"]);
+        assert_ne!(key, AttributionCache::key(&["blob", "notes-v2"]));
+        assert_ne!(key, AttributionCache::key(&["blobnotes-v1"]));
+
+        assert_eq!(cache.get::<Vec<u32>>("blame", &key), None);
+        cache.put("blame", &key, &vec![1u32, 2, 3]);
+        assert_eq!(cache.get::<Vec<u32>>("blame", &key), Some(vec![1, 2, 3]));
+
+        // Corrupt entries are dropped rather than returned.
+        fs::write(
+            dir.path().join("cache/blame").join(format!("{}.json", key)),
+            "{",
+        )
+        .unwrap();
+        assert_eq!(cache.get::<Vec<u32>>("blame", &key), None);
+
+        cache.clear_namespace("blame").unwrap();
+        assert!(!dir.path().join("cache/blame").exists());
+    }
+}
@@ -0,0 +1,193 @@
+use crate::error::GitAiError;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Journal files larger than this are rotated to `events.jsonl.old` before the next append.
+pub const MAX_JOURNAL_BYTES: u64 = 10 * 1024 * 1024;
+
+/// An attribution event appended to the per-repo journal (`.git/ai/events.jsonl`).
+///
+/// Consumers such as `git-ai events --follow` tail the journal to react to
+/// attribution changes as they happen.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+#[serde(tag = "event", rename_all = "snake_case")]
+pub enum AttributionEvent {
+    CheckpointRecorded {
+        ts: u64,
+        base_commit: String,
+        kind: String,
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        agent: Option<String>,
+        files: Vec<String>,
+    },
+    CommitAttributed {
+        ts: u64,
+        commit_sha: String,
+        files: Vec<String>,
+        prompts: usize,
+    },
+    NotesFetched {
+        ts: u64,
+        remote: String,
+    },
+    NotesPushed {
+        ts: u64,
+        remote: String,
+    },
+}
+
+impl AttributionEvent {
+    pub fn checkpoint_recorded(
+        base_commit: &str,
+        kind: &str,
+        agent: Option<String>,
+        files: Vec<String>,
+    ) -> Self {
+        AttributionEvent::CheckpointRecorded {
+            ts: now_millis(),
+            base_commit: base_commit.to_string(),
+            kind: kind.to_string(),
+            agent,
+            files,
+        }
+    }
+
+    pub fn commit_attributed(commit_sha: &str, files: Vec<String>, prompts: usize) -> Self {
+        AttributionEvent::CommitAttributed {
+            ts: now_millis(),
+            commit_sha: commit_sha.to_string(),
+            files,
+            prompts,
+        }
+    }
+
+    pub fn notes_fetched(remote: &str) -> Self {
+        AttributionEvent::NotesFetched {
+            ts: now_millis(),
+            remote: remote.to_string(),
+        }
+    }
+
+    pub fn notes_pushed(remote: &str) -> Self {
+        AttributionEvent::NotesPushed {
+            ts: now_millis(),
+            remote: remote.to_string(),
+        }
+    }
+
+    pub fn timestamp_millis(&self) -> u64 {
+        match self {
+            AttributionEvent::CheckpointRecorded { ts, .. }
+            | AttributionEvent::CommitAttributed { ts, .. }
+            | AttributionEvent::NotesFetched { ts, .. }
+            | AttributionEvent::NotesPushed { ts, .. } => *ts,
+        }
+    }
+
+    /// One-line human readable summary used by `git-ai events --format text`.
+    pub fn summary(&self) -> String {
+        match self {
+            AttributionEvent::CheckpointRecorded {
+                base_commit,
+                kind,
+                agent,
+                files,
+                ..
+            } => format!(
+                "checkpoint_recorded {} {} ({} file{}) on {}",
+                kind,
+                agent.as_deref().unwrap_or("-"),
+                files.len(),
+                if files.len() == 1 { "" } else { "s" },
+                short_sha(base_commit)
+            ),
+            AttributionEvent::CommitAttributed {
+                commit_sha,
+                files,
+                prompts,
+                ..
+            } => format!(
+                "commit_attributed {} ({} file{}, {} prompt{})",
+                short_sha(commit_sha),
+                files.len(),
+                if files.len() == 1 { "" } else { "s" },
+                prompts,
+                if *prompts == 1 { "" } else { "s" }
+            ),
+            AttributionEvent::NotesFetched { remote, .. } => {
+                format!("notes_fetched from {}", remote)
+            }
+            AttributionEvent::NotesPushed { remote, .. } => format!("notes_pushed to {}", remote),
+        }
+    }
+}
+
+fn short_sha(sha: &str) -> &str {
+    &sha[..sha.len().min(7)]
+}
+
+fn now_millis() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+/// Append an event to the journal, rotating it first if it has grown past `MAX_JOURNAL_BYTES`.
+pub fn append_event_to_journal(path: &Path, event: &AttributionEvent) -> Result<(), GitAiError> {
+    if let Ok(meta) = fs::metadata(path)
+        && meta.len() > MAX_JOURNAL_BYTES
+    {
+        let rotated = path.with_extension("jsonl.old");
+        let _ = fs::remove_file(&rotated);
+        fs::rename(path, &rotated)?;
+    }
+
+    let mut line = serde_json::to_string(event)?;
+    line.push('\n');
+
+    // Single write_all on an O_APPEND handle keeps concurrent writers from interleaving lines.
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    file.write_all(line.as_bytes())?;
+    Ok(())
+}
+
+/// Parse journal content, skipping blank, partial or unrecognised lines.
+pub fn parse_journal(content: &str) -> Vec<AttributionEvent> {
+    content
+        .lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_events_round_trip_through_journal() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("events.jsonl");
+
+        let checkpoint = AttributionEvent::checkpoint_recorded(
+            "abc1234def",
+            "ai_agent",
+            Some("cursor".to_string()),
+            vec!["src/main.rs".to_string()],
+        );
+        let pushed = AttributionEvent::notes_pushed("origin");
+        append_event_to_journal(&path, &checkpoint).unwrap();
+        append_event_to_journal(&path, &pushed).unwrap();
+
+        let mut content = fs::read_to_string(&path).unwrap();
+        assert!(content.contains("\"event\":\"checkpoint_recorded\""));
+
+        // A torn trailing line from a concurrent writer must not break parsing.
+        content.push_str("{\"event\":\"commit_attr");
+        assert_eq!(parse_journal(&content), vec![checkpoint, pushed]);
+    }
+}
@@ -10,6 +10,8 @@
 ///   `--version`, `--help`, `--exec-path[=path]`, `--html-path`, `--man-path`, `--info-path`
 ///   are treated as `command_args` (never as `global_args`).
 /// - Supports `--long=VAL`, `--long VAL`, `-Cpath`, `-C path`, `-cname=value`, and `-c name=value`.
+/// - `--no-ai-hooks` among the global options is git-ai's own: it sets `no_ai_hooks` and is
+///   dropped from the arguments.
 ///
 /// This does *not* attempt to validate combinations or emulate Git's error paths.
 /// It is intentionally permissive and order-preserving.
@@ -22,6 +24,8 @@ pub struct ParsedGitInvocation {
     pub saw_end_of_opts: bool,
     /// True if this invocation requests help: presence of -h/--help or `help` command.
     pub is_help: bool,
+    /// True if git-ai's own `--no-ai-hooks` global flag was given. The flag is not passed on to git.
+    pub no_ai_hooks: bool,
 }
 
 impl ParsedGitInvocation {
@@ -276,6 +280,7 @@ pub fn parse_git_cli_args(args: &[String]) -> ParsedGitInvocation {
     // - an unknown dash-option (treat as "no command", remaining go to command_args)
     let mut i = 0usize;
     let mut saw_end_of_opts = false;
+    let mut no_ai_hooks = false;
 
     while i < args.len() {
         let tok = &args[i];
@@ -286,6 +291,12 @@ pub fn parse_git_cli_args(args: &[String]) -> ParsedGitInvocation {
             break;
         }
 
+        if tok == "--no-ai-hooks" {
+            no_ai_hooks = true;
+            i += 1;
+            continue;
+        }
+
         match classify(tok) {
             GlobalNoValue => {
                 global_args.push(tok.clone());
@@ -474,6 +485,7 @@ pub fn parse_git_cli_args(args: &[String]) -> ParsedGitInvocation {
         command_args,
         saw_end_of_opts,
         is_help,
+        no_ai_hooks,
     }
 }
 
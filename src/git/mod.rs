@@ -1,3 +1,5 @@
+pub mod attribution_cache;
+pub mod attribution_events;
 pub mod cli_parser;
 pub mod diff_tree_to_tree;
 pub mod refs;
@@ -13,6 +15,7 @@ pub use repository::{
 pub mod repo_storage;
 pub mod rewrite_log;
 pub mod status;
+pub mod storage_coordination;
 pub mod sync_authorship;
 
 #[cfg(feature = "test-support")]
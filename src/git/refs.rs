@@ -8,13 +8,14 @@ use std::collections::{HashMap, HashSet};
 
 // Modern refspecs without force to enable proper merging
 pub const AI_AUTHORSHIP_REFNAME: &str = "ai";
-pub const AI_AUTHORSHIP_PUSH_REFSPEC: &str = "refs/notes/ai:refs/notes/ai";
+pub const AI_AUTHORSHIP_NOTES_REF: &str = "refs/notes/ai";
 
 pub fn notes_add(
     repo: &Repository,
     commit_sha: &str,
     note_content: &str,
 ) -> Result<(), GitAiError> {
+    repo.ensure_writable()?;
     let mut args = repo.global_args_for_exec();
     args.push("notes".to_string());
     args.push("--ref=ai".to_string());
@@ -37,12 +38,12 @@ fn notes_path_for_object(oid: &str) -> String {
     }
 }
 
-fn flat_note_pathspec_for_commit(commit_sha: &str) -> String {
-    format!("refs/notes/ai:{}", commit_sha)
+fn flat_note_pathspec_for_commit(notes_ref: &str, commit_sha: &str) -> String {
+    format!("{}:{}", notes_ref, commit_sha)
 }
 
-fn fanout_note_pathspec_for_commit(commit_sha: &str) -> String {
-    format!("refs/notes/ai:{}", notes_path_for_object(commit_sha))
+fn fanout_note_pathspec_for_commit(notes_ref: &str, commit_sha: &str) -> String {
+    format!("{}:{}", notes_ref, notes_path_for_object(commit_sha))
 }
 
 fn parse_batch_check_blob_oid(line: &str) -> Option<String> {
@@ -66,6 +67,16 @@ fn parse_batch_check_blob_oid(line: &str) -> Option<String> {
 pub fn note_blob_oids_for_commits(
     repo: &Repository,
     commit_shas: &[String],
+) -> Result<HashMap<String, String>, GitAiError> {
+    note_blob_oids_for_commits_on_ref(repo, AI_AUTHORSHIP_NOTES_REF, commit_shas)
+}
+
+/// Same as `note_blob_oids_for_commits`, but reads notes from an arbitrary notes ref
+/// (e.g. a remote tracking ref under refs/notes/ai-remote/*).
+pub fn note_blob_oids_for_commits_on_ref(
+    repo: &Repository,
+    notes_ref: &str,
+    commit_shas: &[String],
 ) -> Result<HashMap<String, String>, GitAiError> {
     if commit_shas.is_empty() {
         return Ok(HashMap::new());
@@ -79,9 +90,9 @@ pub fn note_blob_oids_for_commits(
     for commit_sha in commit_shas {
         // Notes can be stored with either flat paths (<sha>) or fanout paths (<aa>/<bb...>).
         // Query both forms so this works regardless of repository note fanout state.
-        stdin_data.push_str(&flat_note_pathspec_for_commit(commit_sha));
+        stdin_data.push_str(&flat_note_pathspec_for_commit(notes_ref, commit_sha));
         stdin_data.push('\n');
-        stdin_data.push_str(&fanout_note_pathspec_for_commit(commit_sha));
+        stdin_data.push_str(&fanout_note_pathspec_for_commit(notes_ref, commit_sha));
         stdin_data.push('\n');
     }
 
@@ -110,11 +121,12 @@ pub fn notes_add_batch(repo: &Repository, entries: &[(String, String)]) -> Resul
     if entries.is_empty() {
         return Ok(());
     }
+    repo.ensure_writable()?;
 
     let mut args = repo.global_args_for_exec();
     args.push("rev-parse".to_string());
     args.push("--verify".to_string());
-    args.push("refs/notes/ai".to_string());
+    args.push(AI_AUTHORSHIP_NOTES_REF.to_string());
     let existing_notes_tip = match exec_git(&args) {
         Ok(output) => Some(String::from_utf8(output.stdout)?.trim().to_string()),
         Err(GitAiError::GitCliError {
@@ -148,7 +160,7 @@ pub fn notes_add_batch(repo: &Repository, entries: &[(String, String)]) -> Resul
         script.extend_from_slice(b"\n");
     }
 
-    script.extend_from_slice(b"commit refs/notes/ai\n");
+    script.extend_from_slice(format!("commit {}\n", AI_AUTHORSHIP_NOTES_REF).as_bytes());
     script.extend_from_slice(format!("committer git-ai <git-ai@local> {} +0000\n", now).as_bytes());
     script.extend_from_slice(b"data 0\n");
     if let Some(existing_tip) = existing_notes_tip {
@@ -185,11 +197,12 @@ pub fn notes_add_blob_batch(
     if entries.is_empty() {
         return Ok(());
     }
+    repo.ensure_writable()?;
 
     let mut args = repo.global_args_for_exec();
     args.push("rev-parse".to_string());
     args.push("--verify".to_string());
-    args.push("refs/notes/ai".to_string());
+    args.push(AI_AUTHORSHIP_NOTES_REF.to_string());
     let existing_notes_tip = match exec_git(&args) {
         Ok(output) => Some(String::from_utf8(output.stdout)?.trim().to_string()),
         Err(GitAiError::GitCliError {
@@ -208,20 +221,36 @@ pub fn notes_add_blob_batch(
     }
     deduped_entries.reverse();
 
+    write_note_blobs_commit(
+        repo,
+        AI_AUTHORSHIP_NOTES_REF,
+        existing_notes_tip.as_deref(),
+        &deduped_entries,
+    )
+}
+
+/// Write a notes commit on `target_ref` (parented on `parent`, if any) that attaches the
+/// given existing note blobs to their commits. Entries are (commit_sha, note_blob_oid).
+pub fn write_note_blobs_commit(
+    repo: &Repository,
+    target_ref: &str,
+    parent: Option<&str>,
+    entries: &[(String, String)],
+) -> Result<(), GitAiError> {
     let now = std::time::SystemTime::now()
         .duration_since(std::time::UNIX_EPOCH)
         .map_err(|e| GitAiError::Generic(format!("System clock before epoch: {}", e)))?
         .as_secs();
 
     let mut script = Vec::<u8>::new();
-    script.extend_from_slice(b"commit refs/notes/ai\n");
+    script.extend_from_slice(format!("commit {}\n", target_ref).as_bytes());
     script.extend_from_slice(format!("committer git-ai <git-ai@local> {} +0000\n", now).as_bytes());
     script.extend_from_slice(b"data 0\n");
-    if let Some(existing_tip) = existing_notes_tip {
-        script.extend_from_slice(format!("from {}\n", existing_tip).as_bytes());
+    if let Some(parent) = parent {
+        script.extend_from_slice(format!("from {}\n", parent).as_bytes());
     }
 
-    for (commit_sha, blob_oid) in &deduped_entries {
+    for (commit_sha, blob_oid) in entries {
         let fanout_path = notes_path_for_object(commit_sha);
         let flat_path = commit_sha.clone();
         if flat_path != fanout_path {
@@ -253,7 +282,7 @@ pub enum CommitAuthorship {
     Log {
         sha: String,
         git_author: String,
-        authorship_log: AuthorshipLog,
+        authorship_log: Box<AuthorshipLog>,
     },
 }
 pub fn get_commits_with_notes_from_list(
@@ -314,7 +343,7 @@ pub fn get_commits_with_notes_from_list(
             result.push(CommitAuthorship::Log {
                 sha: sha.clone(),
                 git_author,
-                authorship_log,
+                authorship_log: Box::new(authorship_log),
             });
         } else {
             result.push(CommitAuthorship::NoLog {
@@ -488,7 +517,7 @@ pub fn grep_ai_notes(repo: &Repository, pattern: &str) -> Result<Vec<String>, Gi
     args.push("grep".to_string());
     args.push("-nI".to_string());
     args.push(pattern.to_string());
-    args.push("refs/notes/ai".to_string());
+    args.push(AI_AUTHORSHIP_NOTES_REF.to_string());
 
     let output = exec_git(&args)?;
     let stdout = String::from_utf8(output.stdout)
@@ -498,7 +527,9 @@ pub fn grep_ai_notes(repo: &Repository, pattern: &str) -> Result<Vec<String>, Gi
     // Extract the commit SHA from the path
     let mut shas = HashSet::new();
     for line in stdout.lines() {
-        if let Some(path_and_rest) = line.strip_prefix("refs/notes/ai:")
+        if let Some(path_and_rest) = line
+            .strip_prefix(AI_AUTHORSHIP_NOTES_REF)
+            .and_then(|rest| rest.strip_prefix(':'))
             && let Some(path_end) = path_and_rest.find(':')
         {
             let path = &path_and_rest[..path_end];
@@ -1002,7 +1033,7 @@ mod tests {
     #[test]
     fn test_flat_note_pathspec_for_commit() {
         let sha = "abcdef1234567890abcdef1234567890abcdef12";
-        let pathspec = flat_note_pathspec_for_commit(sha);
+        let pathspec = flat_note_pathspec_for_commit("refs/notes/ai", sha);
         assert_eq!(
             pathspec,
             "refs/notes/ai:abcdef1234567890abcdef1234567890abcdef12"
@@ -1012,7 +1043,7 @@ mod tests {
     #[test]
     fn test_fanout_note_pathspec_for_commit() {
         let sha = "abcdef1234567890abcdef1234567890abcdef12";
-        let pathspec = fanout_note_pathspec_for_commit(sha);
+        let pathspec = fanout_note_pathspec_for_commit("refs/notes/ai", sha);
         assert_eq!(
             pathspec,
             "refs/notes/ai:ab/cdef1234567890abcdef1234567890abcdef12"
@@ -1,13 +1,20 @@
 use crate::authorship::attribution_tracker::LineAttribution;
 use crate::authorship::authorship_log::PromptRecord;
 use crate::authorship::authorship_log_serialization::generate_short_hash;
-use crate::authorship::working_log::{CHECKPOINT_API_VERSION, Checkpoint, CheckpointKind};
+use crate::authorship::working_log::{
+    CHECKPOINT_API_VERSION, Checkpoint, CheckpointKind, next_checkpoint_sequence, sort_checkpoints,
+};
 use crate::error::GitAiError;
+use crate::git::attribution_cache::{ATTRIBUTION_CACHE_DIR_ENV, AttributionCache};
+use crate::git::attribution_events::{AttributionEvent, append_event_to_journal};
 use crate::git::rewrite_log::{RewriteLogEvent, append_event_to_file};
+use crate::git::storage_coordination::{
+    forward_append_checkpoint, lock_working_log, running_writer,
+};
 use crate::utils::{debug_log, normalize_to_posix};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha256};
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs;
 use std::path::{Path, PathBuf};
 
@@ -20,28 +27,57 @@ pub struct InitialAttributions {
     pub prompts: HashMap<String, PromptRecord>,
 }
 
+/// Uncommitted attributions parked with a `git stash` entry, so they can be re-merged into
+/// the working log whenever (and on whatever HEAD) the stash is applied.
+#[derive(Debug, Clone, Serialize, Deserialize, Default)]
+pub struct StashAttributions {
+    /// HEAD when the stash was created
+    pub base_commit: String,
+    /// Map of file path to line attributions, relative to the stashed content
+    pub files: HashMap<String, Vec<LineAttribution>>,
+    /// Map of file path to its content as stashed
+    pub file_contents: HashMap<String, String>,
+    /// Prompt records mapping prompt_id -> (commit_sha -> PromptRecord)
+    pub prompts: BTreeMap<String, BTreeMap<String, PromptRecord>>,
+}
+
 #[derive(Debug, Clone)]
 pub struct RepoStorage {
     pub repo_path: PathBuf,
     pub repo_workdir: PathBuf,
     pub working_logs: PathBuf,
     pub rewrite_log: PathBuf,
+    pub events_journal: PathBuf,
+    pub cache: PathBuf,
+    pub stashes: PathBuf,
+    pub pending_notes_push: PathBuf,
     pub logs: PathBuf,
 }
 
 impl RepoStorage {
     pub fn for_repo_path(repo_path: &Path, repo_workdir: &Path) -> RepoStorage {
-        let ai_dir = repo_path.join("ai");
-        let working_logs_dir = ai_dir.join("working_logs");
-        let rewrite_log_file = ai_dir.join("rewrite_log");
-        let logs_dir = ai_dir.join("logs");
+        Self::for_worktree(repo_path, repo_path, repo_workdir)
+    }
+
+    /// Storage for one worktree. State tied to the worktree's HEAD and index (working logs,
+    /// the rewrite log and the event journal) lives under its private git dir, so checkpoints
+    /// in one worktree never show up in another. State keyed by content or shared refs (the
+    /// attribution cache and stash attributions, since `refs/stash` is shared) and logs live
+    /// under the common dir.
+    pub fn for_worktree(git_dir: &Path, common_dir: &Path, repo_workdir: &Path) -> RepoStorage {
+        let ai_dir = git_dir.join("ai");
+        let shared_ai_dir = common_dir.join("ai");
 
         let config = RepoStorage {
-            repo_path: repo_path.to_path_buf(),
+            repo_path: git_dir.to_path_buf(),
             repo_workdir: repo_workdir.to_path_buf(),
-            working_logs: working_logs_dir,
-            rewrite_log: rewrite_log_file,
-            logs: logs_dir,
+            working_logs: ai_dir.join("working_logs"),
+            rewrite_log: ai_dir.join("rewrite_log"),
+            events_journal: ai_dir.join("events.jsonl"),
+            cache: shared_ai_dir.join("cache"),
+            stashes: shared_ai_dir.join("stashes"),
+            pending_notes_push: shared_ai_dir.join("pending_notes_push.json"),
+            logs: shared_ai_dir.join("logs"),
         };
 
         config.ensure_config_directory().unwrap();
@@ -147,12 +183,114 @@ impl RepoStorage {
         let content = fs::read_to_string(&self.rewrite_log)?;
         crate::git::rewrite_log::deserialize_events_from_jsonl(&content)
     }
+
+    /* Attribution Event Journal */
+
+    /// Record an attribution event for `git-ai events` consumers.
+    /// Best-effort: journal failures never fail the operation that emitted the event.
+    pub fn record_attribution_event(&self, event: AttributionEvent) {
+        if let Err(e) = append_event_to_journal(&self.events_journal, &event) {
+            debug_log(&format!("Failed to append attribution event: {}", e));
+        }
+    }
+
+    /* Attribution Cache */
+
+    /// Cache of computed attribution tables shared by blame and working log consumers.
+    pub fn attribution_cache(&self) -> AttributionCache {
+        let dir = std::env::var_os(ATTRIBUTION_CACHE_DIR_ENV)
+            .map(PathBuf::from)
+            .unwrap_or_else(|| self.cache.clone());
+        AttributionCache::new(dir)
+    }
+
+    /* Stash Attributions */
+
+    pub fn write_stash_attributions(
+        &self,
+        stash_sha: &str,
+        attributions: &StashAttributions,
+    ) -> Result<(), GitAiError> {
+        fs::create_dir_all(&self.stashes)?;
+        fs::write(
+            self.stashes.join(format!("{}.json", stash_sha)),
+            serde_json::to_string(attributions)?,
+        )?;
+        Ok(())
+    }
+
+    pub fn read_stash_attributions(&self, stash_sha: &str) -> Option<StashAttributions> {
+        let content = fs::read_to_string(self.stashes.join(format!("{}.json", stash_sha))).ok()?;
+        match serde_json::from_str(&content) {
+            Ok(attributions) => Some(attributions),
+            Err(e) => {
+                debug_log(&format!(
+                    "Failed to parse stash attributions for {}: {}",
+                    stash_sha, e
+                ));
+                None
+            }
+        }
+    }
+
+    pub fn delete_stash_attributions(&self, stash_sha: &str) {
+        let _ = fs::remove_file(self.stashes.join(format!("{}.json", stash_sha)));
+    }
+
+    /// Remove the attributions of every stash (after `git stash clear`).
+    pub fn clear_stash_attributions(&self) -> Result<(), GitAiError> {
+        if self.stashes.exists() {
+            fs::remove_dir_all(&self.stashes)?;
+        }
+        Ok(())
+    }
+
+    /* Pending Notes Pushes */
+
+    /// Pushed commits whose notes have not reached `remote` yet.
+    pub fn read_pending_notes_push(&self, remote: &str) -> Vec<String> {
+        self.read_all_pending_notes_pushes()
+            .remove(remote)
+            .unwrap_or_default()
+    }
+
+    /// Replace the commits whose notes still have to be pushed to `remote`.
+    pub fn write_pending_notes_push(
+        &self,
+        remote: &str,
+        commits: &[String],
+    ) -> Result<(), GitAiError> {
+        let mut pending = self.read_all_pending_notes_pushes();
+        if commits.is_empty() {
+            if pending.remove(remote).is_none() {
+                return Ok(());
+            }
+        } else {
+            pending.insert(remote.to_string(), commits.to_vec());
+        }
+
+        if pending.is_empty() {
+            let _ = fs::remove_file(&self.pending_notes_push);
+            return Ok(());
+        }
+        if let Some(parent) = self.pending_notes_push.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&self.pending_notes_push, serde_json::to_string(&pending)?)?;
+        Ok(())
+    }
+
+    fn read_all_pending_notes_pushes(&self) -> BTreeMap<String, Vec<String>> {
+        fs::read_to_string(&self.pending_notes_push)
+            .ok()
+            .and_then(|content| serde_json::from_str(&content).ok())
+            .unwrap_or_default()
+    }
 }
 
 #[derive(Clone)]
 pub struct PersistedWorkingLog {
     pub dir: PathBuf,
-    #[allow(dead_code)]
     pub base_commit: String,
     pub repo_workdir: PathBuf,
     /// Canonical (absolute, resolved) version of workdir for reliable path comparisons
@@ -196,7 +334,33 @@ impl PersistedWorkingLog {
         self.dirty_files = normalized_dirty_files;
     }
 
+    /// Identifies the current contents of this working log (checkpoints and INITIAL).
+    /// Changes whenever a checkpoint is appended or the log is rewritten or reset.
+    pub fn fingerprint(&self) -> String {
+        let stamp = |path: PathBuf| {
+            fs::metadata(path)
+                .map(|meta| {
+                    let modified = meta
+                        .modified()
+                        .ok()
+                        .and_then(|t| t.duration_since(std::time::UNIX_EPOCH).ok())
+                        .map(|d| d.as_nanos())
+                        .unwrap_or(0);
+                    format!("{}:{}", meta.len(), modified)
+                })
+                .unwrap_or_else(|_| "-".to_string())
+        };
+        format!(
+            "{}|{}|{}",
+            self.dir.display(),
+            stamp(self.dir.join("checkpoints.jsonl")),
+            stamp(self.initial_file.clone())
+        )
+    }
+
     pub fn reset_working_log(&self) -> Result<(), GitAiError> {
+        let _lock = lock_working_log(&self.dir)?;
+
         // Clear all blobs by removing the blobs directory
         let blobs_dir = self.dir.join("blobs");
         if blobs_dir.exists() {
@@ -319,8 +483,26 @@ impl PersistedWorkingLog {
         }
     }
 
+    /// The `ai` storage directory this working log lives in.
+    fn ai_dir(&self) -> Option<&Path> {
+        self.dir.parent()?.parent()
+    }
+
     /* append checkpoint */
+    /// Append a checkpoint, through the running `git-ai serve` writer of record if there is
+    /// one (see `storage_coordination`), otherwise directly.
     pub fn append_checkpoint(&self, checkpoint: &Checkpoint) -> Result<(), GitAiError> {
+        if let Some(writer) = self.ai_dir().and_then(running_writer) {
+            if forward_append_checkpoint(&writer, &self.base_commit, checkpoint)? {
+                return Ok(());
+            }
+            debug_log(&format!(
+                "git-ai server (pid {}) is unreachable, writing the checkpoint directly",
+                writer.pid
+            ));
+        }
+
+        let _lock = lock_working_log(&self.dir)?;
         // Read existing checkpoints
         let mut checkpoints = self.read_all_checkpoints().unwrap_or_default();
 
@@ -366,15 +548,20 @@ impl PersistedWorkingLog {
             storage_checkpoint.transcript = None;
         }
 
+        if storage_checkpoint.sequence == 0 {
+            storage_checkpoint.sequence = next_checkpoint_sequence(&checkpoints);
+        }
+
         // Add the new checkpoint
         checkpoints.push(storage_checkpoint);
+        sort_checkpoints(&mut checkpoints);
 
         // Prune char-level attributions from older checkpoints for the same files
         // Only the most recent checkpoint per file needs char-level precision
         self.prune_old_char_attributions(&mut checkpoints);
 
         // Write all checkpoints back
-        self.write_all_checkpoints(&checkpoints)
+        self.write_checkpoints_file(&checkpoints)
     }
 
     pub fn read_all_checkpoints(&self) -> Result<Vec<Checkpoint>, GitAiError> {
@@ -407,6 +594,9 @@ impl PersistedWorkingLog {
             checkpoints.push(checkpoint);
         }
 
+        // Checkpoints can be appended out of order from machines sharing a worktree
+        sort_checkpoints(&mut checkpoints);
+
         // Migrate 7-char prompt hashes to 16-char hashes
         // Step 1: Build mapping from old 7-char hash to new 16-char hash
         let mut old_to_new_hash: HashMap<String, String> = HashMap::new();
@@ -487,6 +677,12 @@ impl PersistedWorkingLog {
     /// by post-commit after transcripts have been refetched and need to be preserved
     /// for from_just_working_log() to read them.
     pub fn write_all_checkpoints(&self, checkpoints: &[Checkpoint]) -> Result<(), GitAiError> {
+        let _lock = lock_working_log(&self.dir)?;
+        self.write_checkpoints_file(checkpoints)
+    }
+
+    /// Callers hold the working log lock.
+    fn write_checkpoints_file(&self, checkpoints: &[Checkpoint]) -> Result<(), GitAiError> {
         let checkpoints_file = self.dir.join("checkpoints.jsonl");
 
         // Serialize all checkpoints to JSONL
@@ -496,13 +692,16 @@ impl PersistedWorkingLog {
             lines.push(json_line);
         }
 
-        // Write all lines to file
+        // Write all lines to a temporary file and swap it in, so readers that don't take the
+        // lock never see a half-written log
         let content = lines.join("\n");
+        let tmp_file = self.dir.join("checkpoints.jsonl.tmp");
         if !content.is_empty() {
-            fs::write(&checkpoints_file, format!("{}\n", content))?;
+            fs::write(&tmp_file, format!("{}\n", content))?;
         } else {
-            fs::write(&checkpoints_file, "")?;
+            fs::write(&tmp_file, "")?;
         }
+        fs::rename(&tmp_file, &checkpoints_file)?;
 
         Ok(())
     }
@@ -718,6 +917,41 @@ mod tests {
         assert_eq!(sha, sha2, "Same content should produce same SHA");
     }
 
+    #[test]
+    fn test_concurrent_appends_keep_every_checkpoint() {
+        use crate::authorship::working_log::CheckpointKind;
+
+        let tmp_repo = TmpRepo::new().expect("Failed to create tmp repo");
+        let repo_storage =
+            RepoStorage::for_repo_path(tmp_repo.repo().path(), tmp_repo.repo().workdir().unwrap());
+        let working_log = repo_storage.working_log_for_base_commit("test-commit-sha");
+
+        let writers: Vec<_> = (0..4)
+            .map(|writer| {
+                let working_log = working_log.clone();
+                std::thread::spawn(move || {
+                    for i in 0..10 {
+                        let checkpoint = Checkpoint::new(
+                            CheckpointKind::Human,
+                            format!("diff-{}-{}", writer, i),
+                            "test-author".to_string(),
+                            vec![],
+                        );
+                        working_log
+                            .append_checkpoint(&checkpoint)
+                            .expect("Failed to append checkpoint");
+                    }
+                })
+            })
+            .collect();
+        for writer in writers {
+            writer.join().unwrap();
+        }
+
+        let checkpoints = working_log.read_all_checkpoints().unwrap();
+        assert_eq!(checkpoints.len(), 40);
+    }
+
     #[test]
     fn test_persisted_working_log_checkpoint_storage() {
         use crate::authorship::working_log::CheckpointKind;
@@ -934,4 +1168,29 @@ mod tests {
             "Working log directory should be in correct location"
         );
     }
+
+    #[test]
+    fn test_for_worktree_keeps_working_logs_private() {
+        let tmp_repo = TmpRepo::new().expect("Failed to create tmp repo");
+        let common_dir = tmp_repo.repo().path().to_path_buf();
+        let worktree_git_dir = common_dir.join("worktrees").join("feature");
+        fs::create_dir_all(&worktree_git_dir).unwrap();
+
+        let repo_storage = RepoStorage::for_worktree(
+            &worktree_git_dir,
+            &common_dir,
+            tmp_repo.repo().workdir().unwrap(),
+        );
+
+        assert!(repo_storage.working_logs.starts_with(&worktree_git_dir));
+        assert!(repo_storage.rewrite_log.starts_with(&worktree_git_dir));
+        assert!(repo_storage.events_journal.starts_with(&worktree_git_dir));
+        assert_eq!(repo_storage.stashes, common_dir.join("ai").join("stashes"));
+        assert_eq!(repo_storage.cache, common_dir.join("ai").join("cache"));
+
+        let main_storage =
+            RepoStorage::for_repo_path(&common_dir, tmp_repo.repo().workdir().unwrap());
+        assert_ne!(main_storage.working_logs, repo_storage.working_logs);
+        assert_eq!(main_storage.stashes, repo_storage.stashes);
+    }
 }
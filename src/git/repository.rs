@@ -1066,6 +1066,28 @@ impl Repository {
         }
     }
 
+    /// Whether `ai.readOnly` is set for this repository. A read-only repository gets no
+    /// checkpoints, working logs or authorship notes and pushes no notes; blame, stats and the
+    /// other commands that only read attribution work as usual.
+    pub fn is_read_only(&self) -> bool {
+        self.get_git_config_file()
+            .ok()
+            .and_then(|config| config.boolean("ai.readOnly"))
+            .and_then(Result::ok)
+            .unwrap_or(false)
+    }
+
+    /// Fail a write to attribution state when the repository is read-only.
+    pub fn ensure_writable(&self) -> Result<(), GitAiError> {
+        if self.is_read_only() {
+            return Err(GitAiError::Generic(
+                "repository is read-only (ai.readOnly=true); attribution was not changed"
+                    .to_string(),
+            ));
+        }
+        Ok(())
+    }
+
     /// Get all config values matching a regex pattern.
     ///
     /// Regular expression matching is currently case-sensitive
@@ -1931,6 +1953,7 @@ pub fn find_repository(global_args: &[String]) -> Result<Repository, GitAiError>
     // string "absolute-git-dir" instead of the resolved path).
     rev_parse_args.push("--is-bare-repository".to_string());
     rev_parse_args.push("--git-dir".to_string());
+    rev_parse_args.push("--git-common-dir".to_string());
 
     let rev_parse_output = exec_git(&rev_parse_args)?;
     let rev_parse_stdout = String::from_utf8(rev_parse_output.stdout)?;
@@ -1972,6 +1995,19 @@ pub fn find_repository(global_args: &[String]) -> Result<Repository, GitAiError>
         )));
     }
 
+    // Git before 2.5 echoes the unknown flag back instead of a path
+    let git_common_dir = match lines.next() {
+        Some(dir) if dir != "--git-common-dir" => {
+            let dir = if Path::new(dir).is_relative() {
+                command_base_dir.join(dir)
+            } else {
+                PathBuf::from(dir)
+            };
+            if dir.is_dir() { dir } else { git_dir.clone() }
+        }
+        _ => git_dir.clone(),
+    };
+
     let workdir = if is_bare {
         git_dir.parent().map(Path::to_path_buf).ok_or_else(|| {
             GitAiError::Generic(format!(
@@ -2024,7 +2060,7 @@ pub fn find_repository(global_args: &[String]) -> Result<Repository, GitAiError>
 
     Ok(Repository {
         global_args: normalized_global_args,
-        storage: RepoStorage::for_repo_path(&git_dir, &workdir),
+        storage: RepoStorage::for_worktree(&git_dir, &git_common_dir, &workdir),
         git_dir,
         pre_command_base_commit: None,
         pre_command_refname: None,
@@ -14,6 +14,9 @@ pub enum RewriteLogEvent {
     RebaseStart {
         rebase_start: RebaseStartEvent,
     },
+    RebaseProgress {
+        rebase_progress: RebaseProgressEvent,
+    },
     RebaseComplete {
         rebase_complete: RebaseCompleteEvent,
     },
@@ -81,6 +84,12 @@ impl RewriteLogEvent {
         }
     }
 
+    pub fn rebase_progress(event: RebaseProgressEvent) -> Self {
+        Self::RebaseProgress {
+            rebase_progress: event,
+        }
+    }
+
     pub fn rebase_complete(event: RebaseCompleteEvent) -> Self {
         Self::RebaseComplete {
             rebase_complete: event,
@@ -232,6 +241,24 @@ impl RebaseStartEvent {
     }
 }
 
+/// Snapshot of git's `rewritten-list` taken while a rebase is paused. Git deletes its
+/// rebase state when the rebase finishes, so this is the only record of the exact
+/// old -> new commit mapping for steps before the last stop.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RebaseProgressEvent {
+    pub original_head: String,
+    pub rewritten: Vec<(String, String)>,
+}
+
+impl RebaseProgressEvent {
+    pub fn new(original_head: String, rewritten: Vec<(String, String)>) -> Self {
+        Self {
+            original_head,
+            rewritten,
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct RebaseCompleteEvent {
     pub original_head: String,
@@ -239,6 +266,10 @@ pub struct RebaseCompleteEvent {
     pub is_interactive: bool,
     pub original_commits: Vec<String>,
     pub new_commits: Vec<String>,
+    /// Original -> rewritten commit pairs, oldest first. Several originals map to the
+    /// same new commit for squash/fixup; dropped commits are absent. Empty when unknown.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub rewritten: Vec<(String, String)>,
 }
 
 impl RebaseCompleteEvent {
@@ -255,8 +286,14 @@ impl RebaseCompleteEvent {
             is_interactive,
             original_commits,
             new_commits,
+            rewritten: Vec::new(),
         }
     }
+
+    pub fn with_rewritten(mut self, rewritten: Vec<(String, String)>) -> Self {
+        self.rewritten = rewritten;
+        self
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -621,6 +658,56 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_rebase_rewritten_pairs_round_trip() {
+        let progress = RewriteLogEvent::rebase_progress(RebaseProgressEvent::new(
+            "orig".to_string(),
+            vec![("a1".to_string(), "b1".to_string())],
+        ));
+        let complete = RewriteLogEvent::rebase_complete(
+            RebaseCompleteEvent::new(
+                "orig".to_string(),
+                "b2".to_string(),
+                true,
+                vec!["a1".to_string(), "a2".to_string()],
+                vec!["b2".to_string()],
+            )
+            .with_rewritten(vec![
+                ("a1".to_string(), "b2".to_string()),
+                ("a2".to_string(), "b2".to_string()),
+            ]),
+        );
+
+        let jsonl = serialize_events_to_jsonl(&[progress, complete]).unwrap();
+        let deserialized = deserialize_events_from_jsonl(&jsonl).unwrap();
+
+        match &deserialized[0] {
+            RewriteLogEvent::RebaseProgress { rebase_progress } => {
+                assert_eq!(rebase_progress.original_head, "orig");
+                assert_eq!(
+                    rebase_progress.rewritten,
+                    vec![("a1".to_string(), "b1".to_string())]
+                );
+            }
+            _ => panic!("Expected RebaseProgress event"),
+        }
+        match &deserialized[1] {
+            RewriteLogEvent::RebaseComplete { rebase_complete } => {
+                assert_eq!(rebase_complete.rewritten.len(), 2);
+            }
+            _ => panic!("Expected RebaseComplete event"),
+        }
+
+        // Logs written before the mapping existed still parse
+        let legacy = r#"{"rebase_complete":{"original_head":"o","new_head":"n","is_interactive":false,"original_commits":["a"],"new_commits":["b"]}}"#;
+        match serde_json::from_str::<RewriteLogEvent>(legacy).unwrap() {
+            RewriteLogEvent::RebaseComplete { rebase_complete } => {
+                assert!(rebase_complete.rewritten.is_empty());
+            }
+            _ => panic!("Expected RebaseComplete event"),
+        }
+    }
+
     #[test]
     fn test_commit_amend_event_serialization() {
         let event =
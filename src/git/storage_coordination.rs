@@ -0,0 +1,225 @@
+//! Coordinates storage writes between a long-running `git-ai serve --socket` and CLI
+//! invocations, so both can be used on the same repository at once.
+//!
+//! The protocol:
+//! 1. A server listening on a socket registers as the storage writer of record: it holds an
+//!    exclusive lock on `ai/daemon.lock` for its lifetime and records its pid and socket in
+//!    `ai/daemon.json`.
+//! 2. A CLI appending a checkpoint first looks for a live registration (one whose lock is
+//!    held). If there is one, the checkpoint is sent to the server as an `append_checkpoint`
+//!    request and the server writes it.
+//! 3. Without a live server, or when its socket does not accept connections, the CLI writes
+//!    directly.
+//! 4. Every read-modify-write of a working log, by the server or a CLI, holds the working log's
+//!    `.lock`, so writes that race the registration (or come from hooks that never forward)
+//!    cannot drop each other's checkpoints.
+//!
+//! A registration whose lock is free belongs to a server that exited without cleaning up; it
+//! is ignored and removed.
+
+use crate::authorship::working_log::Checkpoint;
+use crate::error::GitAiError;
+use crate::utils::{LockFile, debug_log};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+pub const REGISTRATION_FILE: &str = "daemon.json";
+const REGISTRATION_LOCK_FILE: &str = "daemon.lock";
+const WORKING_LOG_LOCK_FILE: &str = ".lock";
+
+/// Long enough for a slow checkpoint rewrite by another process
+const WORKING_LOG_LOCK_TIMEOUT: Duration = Duration::from_secs(30);
+/// A CLI briefly holds the registration lock while checking for a stale registration
+const REGISTRATION_LOCK_TIMEOUT: Duration = Duration::from_millis(500);
+#[cfg(unix)]
+const IPC_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Set while this process is the writer of record, so its own writes are never forwarded.
+static IS_WRITER_OF_RECORD: AtomicBool = AtomicBool::new(false);
+
+/// A running server that accepts storage writes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct WriterRegistration {
+    pub pid: u32,
+    pub socket: String,
+}
+
+/// Held by the server for as long as it is the writer of record. Dropping it unregisters.
+pub struct WriterGuard {
+    _lock: LockFile,
+    registration_file: PathBuf,
+}
+
+impl Drop for WriterGuard {
+    fn drop(&mut self) {
+        IS_WRITER_OF_RECORD.store(false, Ordering::SeqCst);
+        let _ = fs::remove_file(&self.registration_file);
+    }
+}
+
+/// Register this process as the writer of record for the storage in `ai_dir`, reachable at
+/// `socket`. Fails if another live server is registered.
+pub fn register_writer(ai_dir: &Path, socket: &Path) -> Result<WriterGuard, GitAiError> {
+    let Some(lock) = LockFile::acquire(
+        &ai_dir.join(REGISTRATION_LOCK_FILE),
+        REGISTRATION_LOCK_TIMEOUT,
+    ) else {
+        let holder = read_registration(ai_dir)
+            .map(|registration| format!(" (pid {})", registration.pid))
+            .unwrap_or_default();
+        return Err(GitAiError::Generic(format!(
+            "another git-ai server{} is already the storage writer",
+            holder
+        )));
+    };
+
+    let socket = if socket.is_absolute() {
+        socket.to_path_buf()
+    } else {
+        std::env::current_dir()?.join(socket)
+    };
+    let registration = WriterRegistration {
+        pid: std::process::id(),
+        socket: socket.to_string_lossy().to_string(),
+    };
+    let registration_file = ai_dir.join(REGISTRATION_FILE);
+    fs::write(&registration_file, serde_json::to_string(&registration)?)?;
+    IS_WRITER_OF_RECORD.store(true, Ordering::SeqCst);
+
+    Ok(WriterGuard {
+        _lock: lock,
+        registration_file,
+    })
+}
+
+/// The live writer of record for the storage in `ai_dir`, if writes should go through one.
+/// Returns None inside the writer itself.
+pub fn running_writer(ai_dir: &Path) -> Option<WriterRegistration> {
+    if IS_WRITER_OF_RECORD.load(Ordering::SeqCst) {
+        return None;
+    }
+    let registration = read_registration(ai_dir)?;
+    match LockFile::try_acquire(&ai_dir.join(REGISTRATION_LOCK_FILE)) {
+        Some(_stale) => {
+            debug_log(&format!(
+                "Removing stale storage writer registration (pid {})",
+                registration.pid
+            ));
+            let _ = fs::remove_file(ai_dir.join(REGISTRATION_FILE));
+            None
+        }
+        None => Some(registration),
+    }
+}
+
+fn read_registration(ai_dir: &Path) -> Option<WriterRegistration> {
+    let content = fs::read_to_string(ai_dir.join(REGISTRATION_FILE)).ok()?;
+    serde_json::from_str(&content).ok()
+}
+
+/// Ask `writer` to append `checkpoint` to the working log for `base_commit`.
+///
+/// Returns `Ok(false)` when the writer cannot be reached, so the caller can write directly.
+/// Once the request is sent, a missing or failed response is an error: writing directly too
+/// could store the checkpoint twice.
+#[cfg(unix)]
+pub fn forward_append_checkpoint(
+    writer: &WriterRegistration,
+    base_commit: &str,
+    checkpoint: &Checkpoint,
+) -> Result<bool, GitAiError> {
+    use std::io::{BufRead, BufReader, Write};
+    use std::os::unix::net::UnixStream;
+
+    let Ok(mut stream) = UnixStream::connect(&writer.socket) else {
+        return Ok(false);
+    };
+    stream.set_read_timeout(Some(IPC_TIMEOUT))?;
+    stream.set_write_timeout(Some(IPC_TIMEOUT))?;
+
+    let request = serde_json::json!({
+        "jsonrpc": "2.0",
+        "id": 1,
+        "method": "append_checkpoint",
+        "params": {
+            "base_commit": base_commit,
+            "checkpoint": checkpoint,
+        },
+    });
+    writeln!(stream, "{}", request)?;
+    stream.flush()?;
+
+    let mut line = String::new();
+    BufReader::new(&stream).read_line(&mut line)?;
+    let response: serde_json::Value = serde_json::from_str(&line)?;
+    if let Some(error) = response.get("error") {
+        return Err(GitAiError::Generic(format!(
+            "git-ai server (pid {}) failed to append the checkpoint: {}",
+            writer.pid, error["message"]
+        )));
+    }
+    Ok(true)
+}
+
+#[cfg(not(unix))]
+pub fn forward_append_checkpoint(
+    _writer: &WriterRegistration,
+    _base_commit: &str,
+    _checkpoint: &Checkpoint,
+) -> Result<bool, GitAiError> {
+    Ok(false)
+}
+
+/// Hold the lock of the working log in `dir` for a read-modify-write.
+pub fn lock_working_log(dir: &Path) -> Result<LockFile, GitAiError> {
+    LockFile::acquire(&dir.join(WORKING_LOG_LOCK_FILE), WORKING_LOG_LOCK_TIMEOUT).ok_or_else(|| {
+        GitAiError::Generic(format!(
+            "Timed out waiting for the working log lock in {}",
+            dir.display()
+        ))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_stale_registration_is_ignored_and_removed() {
+        let dir = tempfile::tempdir().unwrap();
+        let registration = WriterRegistration {
+            pid: 1,
+            socket: "/tmp/gone.sock".to_string(),
+        };
+        fs::write(
+            dir.path().join(REGISTRATION_FILE),
+            serde_json::to_string(&registration).unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(running_writer(dir.path()), None);
+        assert!(!dir.path().join(REGISTRATION_FILE).exists());
+    }
+
+    #[test]
+    fn test_registration_is_live_while_its_lock_is_held() {
+        let dir = tempfile::tempdir().unwrap();
+        let registration = WriterRegistration {
+            pid: 1,
+            socket: "/tmp/live.sock".to_string(),
+        };
+        fs::write(
+            dir.path().join(REGISTRATION_FILE),
+            serde_json::to_string(&registration).unwrap(),
+        )
+        .unwrap();
+        let lock = LockFile::try_acquire(&dir.path().join(REGISTRATION_LOCK_FILE)).unwrap();
+
+        assert_eq!(running_writer(dir.path()), Some(registration));
+        drop(lock);
+        assert_eq!(running_writer(dir.path()), None);
+    }
+}
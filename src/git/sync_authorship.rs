@@ -1,11 +1,17 @@
+use crate::authorship::authorship_log_serialization::AuthorshipLog;
+use crate::authorship::note_merge::{LineConflict, merge_notes};
+use crate::git::attribution_events::AttributionEvent;
 use crate::git::refs::{
-    AI_AUTHORSHIP_PUSH_REFSPEC, copy_ref, merge_notes_from_ref, ref_exists, tracking_ref_for_remote,
+    AI_AUTHORSHIP_NOTES_REF, copy_ref, merge_notes_from_ref, note_blob_oids_for_commits,
+    note_blob_oids_for_commits_on_ref, notes_add_batch, ref_exists, tracking_ref_for_remote,
+    write_note_blobs_commit,
 };
 use crate::{
     error::GitAiError,
     git::{cli_parser::ParsedGitInvocation, repository::exec_git},
     utils::debug_log,
 };
+use std::collections::{HashMap, HashSet};
 
 use super::repository::Repository;
 
@@ -18,6 +24,117 @@ pub enum NotesExistence {
     NotFound,
 }
 
+/// Which directions of notes sync git-ai runs on its own around fetch, pull, clone and push
+/// (`git config git-ai.sync off|fetch|push|both`). `git-ai sync` ignores it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncMode {
+    Off,
+    Fetch,
+    Push,
+    Both,
+}
+
+impl SyncMode {
+    pub fn parse(value: &str) -> Option<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "off" => Some(SyncMode::Off),
+            "fetch" => Some(SyncMode::Fetch),
+            "push" => Some(SyncMode::Push),
+            "both" => Some(SyncMode::Both),
+            _ => None,
+        }
+    }
+
+    pub fn fetches(self) -> bool {
+        matches!(self, SyncMode::Fetch | SyncMode::Both)
+    }
+
+    pub fn pushes(self) -> bool {
+        matches!(self, SyncMode::Push | SyncMode::Both)
+    }
+}
+
+/// Notes sync settings read from git config.
+///
+/// - `git-ai.notesRef`: ref the notes are fetched from and pushed to on remotes (a bare name
+///   like `ai-authorship` means `refs/notes/ai-authorship`). Local notes stay in
+///   `refs/notes/ai`.
+/// - `git-ai.syncRemotes`: comma or space separated remotes to sync with automatically.
+/// - `git-ai.sync`: see [`SyncMode`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SyncSettings {
+    pub notes_ref: String,
+    pub remotes: Option<Vec<String>>,
+    pub mode: SyncMode,
+}
+
+impl Default for SyncSettings {
+    fn default() -> Self {
+        SyncSettings {
+            notes_ref: AI_AUTHORSHIP_NOTES_REF.to_string(),
+            remotes: None,
+            mode: SyncMode::Both,
+        }
+    }
+}
+
+impl SyncSettings {
+    pub fn from_repository(repository: &Repository) -> Self {
+        let get = |key: &str| repository.config_get_str(key).ok().flatten();
+        Self::from_values(
+            get("git-ai.notesRef").as_deref(),
+            get("git-ai.syncRemotes").as_deref(),
+            get("git-ai.sync").as_deref(),
+        )
+    }
+
+    pub fn from_values(notes_ref: Option<&str>, remotes: Option<&str>, mode: Option<&str>) -> Self {
+        let mut settings = SyncSettings::default();
+        if let Some(notes_ref) = notes_ref.map(str::trim).filter(|r| !r.is_empty()) {
+            settings.notes_ref = if notes_ref.starts_with("refs/") {
+                notes_ref.to_string()
+            } else {
+                format!("refs/notes/{}", notes_ref)
+            };
+        }
+        if let Some(remotes) = remotes {
+            settings.remotes = Some(
+                remotes
+                    .split(|c: char| c == ',' || c.is_whitespace())
+                    .filter(|remote| !remote.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+            );
+        }
+        if let Some(mode) = mode {
+            match SyncMode::parse(mode) {
+                Some(mode) => settings.mode = mode,
+                None => debug_log(&format!(
+                    "ignoring unknown git-ai.sync value '{}'; expected off, fetch, push or both",
+                    mode
+                )),
+            }
+        }
+        settings
+    }
+
+    fn syncs_with(&self, remote_name: &str) -> bool {
+        self.remotes
+            .as_ref()
+            .is_none_or(|remotes| remotes.iter().any(|remote| remote == remote_name))
+    }
+
+    /// Whether notes are fetched from `remote_name` automatically.
+    pub fn auto_fetch(&self, remote_name: &str) -> bool {
+        self.mode.fetches() && self.syncs_with(remote_name)
+    }
+
+    /// Whether notes are pushed to `remote_name` automatically.
+    pub fn auto_push(&self, remote_name: &str) -> bool {
+        self.mode.pushes() && self.syncs_with(remote_name)
+    }
+}
+
 pub fn fetch_remote_from_args(
     repository: &Repository,
     parsed_args: &ParsedGitInvocation,
@@ -64,6 +181,24 @@ pub fn fetch_remote_from_args(
 pub fn fetch_authorship_notes(
     repository: &Repository,
     remote_name: &str,
+) -> Result<NotesExistence, GitAiError> {
+    let settings = SyncSettings::from_repository(repository);
+    if !settings.auto_fetch(remote_name) {
+        debug_log(&format!(
+            "authorship fetch from '{}' disabled by git-ai.sync/git-ai.syncRemotes; skipping",
+            remote_name
+        ));
+        return Ok(NotesExistence::NotFound);
+    }
+    fetch_notes_from_remote(repository, remote_name, &settings.notes_ref)
+}
+
+/// Fetch `remote_notes_ref` from `remote_name` and merge it into the local notes, whatever
+/// the sync settings say.
+pub fn fetch_notes_from_remote(
+    repository: &Repository,
+    remote_name: &str,
+    remote_notes_ref: &str,
 ) -> Result<NotesExistence, GitAiError> {
     // Generate tracking ref for this remote
     let tracking_ref = tracking_ref_for_remote(remote_name);
@@ -73,12 +208,12 @@ pub fn fetch_authorship_notes(
         remote_name, tracking_ref
     ));
 
-    // First, check if the remote has refs/notes/ai using ls-remote
+    // First, check if the remote has the notes ref using ls-remote
     // This is important for bare repos where the refmap might not be configured
     let mut ls_remote_args = repository.global_args_for_exec();
     ls_remote_args.push("ls-remote".to_string());
     ls_remote_args.push(remote_name.to_string());
-    ls_remote_args.push("refs/notes/ai".to_string());
+    ls_remote_args.push(remote_notes_ref.to_string());
 
     debug_log(&format!("ls-remote command: {:?}", ls_remote_args));
 
@@ -114,7 +249,7 @@ pub fn fetch_authorship_notes(
     }
 
     // Now fetch the notes to the tracking ref with explicit refspec
-    let fetch_refspec = format!("+refs/notes/ai:{}", tracking_ref);
+    let fetch_refspec = format!("+{}:{}", remote_notes_ref, tracking_ref);
 
     // Build the internal authorship fetch with explicit flags and disabled hooks
     // IMPORTANT: use repository.global_args_for_exec() to ensure -C flag is present for bare repos
@@ -150,7 +285,7 @@ pub fn fetch_authorship_notes(
     }
 
     // After successful fetch, merge the tracking ref into refs/notes/ai
-    let local_notes_ref = "refs/notes/ai";
+    let local_notes_ref = AI_AUTHORSHIP_NOTES_REF;
 
     if crate::git::refs::ref_exists(repository, &tracking_ref) {
         if crate::git::refs::ref_exists(repository, local_notes_ref) {
@@ -181,14 +316,374 @@ pub fn fetch_authorship_notes(
         ));
     }
 
+    repository
+        .storage
+        .record_attribution_event(AttributionEvent::notes_fetched(remote_name));
+
     Ok(NotesExistence::Found)
 }
 // for use with post-push hook
 pub fn push_authorship_notes(repository: &Repository, remote_name: &str) -> Result<(), GitAiError> {
+    let settings = SyncSettings::from_repository(repository);
+    if !settings.auto_push(remote_name) {
+        debug_log(&format!(
+            "authorship push to '{}' disabled by git-ai.sync/git-ai.syncRemotes; skipping",
+            remote_name
+        ));
+        return Ok(());
+    }
+    push_notes_to_remote(repository, remote_name, &settings.notes_ref)
+}
+
+/// Push all local notes to `remote_notes_ref` on `remote_name`, whatever the sync settings say.
+pub fn push_notes_to_remote(
+    repository: &Repository,
+    remote_name: &str,
+    remote_notes_ref: &str,
+) -> Result<(), GitAiError> {
+    repository.ensure_writable()?;
+
     // STEP 1: Fetch remote notes into tracking ref and merge before pushing
     // This ensures we don't lose notes from other branches/clones
+    fetch_and_merge_notes_before_push(repository, remote_name, remote_notes_ref);
+
+    // STEP 2: Push notes without force (requires fast-forward)
+    let mut push_authorship: Vec<String> = repository.global_args_for_exec();
+    push_authorship.push("-c".to_string());
+    push_authorship.push("core.hooksPath=/dev/null".to_string());
+    push_authorship.push("push".to_string());
+    push_authorship.push("--quiet".to_string());
+    push_authorship.push("--no-recurse-submodules".to_string());
+    push_authorship.push("--no-verify".to_string());
+    push_authorship.push("--no-signed".to_string());
+    push_authorship.push(remote_name.to_string());
+    push_authorship.push(format!("{}:{}", AI_AUTHORSHIP_NOTES_REF, remote_notes_ref));
+
+    debug_log(&format!(
+        "pushing authorship refs (no force): {:?}",
+        &push_authorship
+    ));
+    if let Err(e) = exec_git(&push_authorship) {
+        // Best-effort; don't fail user operation due to authorship sync issues
+        debug_log(&format!("authorship push skipped due to error: {}", e));
+        return Err(e);
+    }
+
+    repository
+        .storage
+        .record_attribution_event(AttributionEvent::notes_pushed(remote_name));
+
+    Ok(())
+}
+
+// for use with post-push hook: push only the notes attached to `commits`.
+// The remote notes ref is extended with a new notes commit on top of its current tip,
+// so notes for local-only commits never leave the machine.
+// Returns Ok(false) when there was nothing to push.
+pub fn push_authorship_notes_for_commits(
+    repository: &Repository,
+    remote_name: &str,
+    commits: &[String],
+) -> Result<bool, GitAiError> {
+    if commits.is_empty() {
+        return Ok(false);
+    }
+    let settings = SyncSettings::from_repository(repository);
+    if !settings.auto_push(remote_name) {
+        debug_log(&format!(
+            "authorship push to '{}' disabled by git-ai.sync/git-ai.syncRemotes; skipping",
+            remote_name
+        ));
+        return Ok(false);
+    }
+    push_notes_for_commits_to_remote(repository, remote_name, &settings.notes_ref, commits)
+        .map(|pushed| pushed > 0)
+}
+
+/// Push the local notes attached to `commits` to `remote_notes_ref` on `remote_name`,
+/// whatever the sync settings say. Returns how many notes the remote didn't have yet.
+pub fn push_notes_for_commits_to_remote(
+    repository: &Repository,
+    remote_name: &str,
+    remote_notes_ref: &str,
+    commits: &[String],
+) -> Result<usize, GitAiError> {
+    if commits.is_empty() {
+        return Ok(0);
+    }
+    repository.ensure_writable()?;
+
+    // STEP 1: Bring the tracking ref up to date so the new notes commit fast-forwards it
+    fetch_and_merge_notes_before_push(repository, remote_name, remote_notes_ref);
+
+    let tracking_ref = tracking_ref_for_remote(remote_name);
+    let local_notes = note_blob_oids_for_commits(repository, commits)?;
+    let remote_tip = resolve_ref(repository, &tracking_ref);
+    let remote_notes = if remote_tip.is_some() {
+        note_blob_oids_for_commits_on_ref(repository, &tracking_ref, commits)?
+    } else {
+        HashMap::new()
+    };
+
+    // Keep commit order stable and skip notes the remote already has verbatim
+    let entries: Vec<(String, String)> = commits
+        .iter()
+        .filter_map(|sha| {
+            let blob = local_notes.get(sha)?;
+            (remote_notes.get(sha) != Some(blob)).then(|| (sha.clone(), blob.clone()))
+        })
+        .collect();
+    if entries.is_empty() {
+        debug_log("no authorship notes to push for pushed commits");
+        return Ok(0);
+    }
+
+    // STEP 2: Stage a notes commit on a scratch ref and push it without force
+    let staging_ref = tracking_ref.replacen("refs/notes/ai-remote/", "refs/notes/ai-push/", 1);
+    let _ = delete_ref(repository, &staging_ref);
+    write_note_blobs_commit(repository, &staging_ref, remote_tip.as_deref(), &entries)?;
+
+    let mut push_authorship: Vec<String> = repository.global_args_for_exec();
+    push_authorship.push("-c".to_string());
+    push_authorship.push("core.hooksPath=/dev/null".to_string());
+    push_authorship.push("push".to_string());
+    push_authorship.push("--quiet".to_string());
+    push_authorship.push("--no-recurse-submodules".to_string());
+    push_authorship.push("--no-verify".to_string());
+    push_authorship.push("--no-signed".to_string());
+    push_authorship.push(remote_name.to_string());
+    push_authorship.push(format!("{}:{}", staging_ref, remote_notes_ref));
+
+    debug_log(&format!(
+        "pushing notes for {} commit(s) (no force): {:?}",
+        entries.len(),
+        &push_authorship
+    ));
+    let result = exec_git(&push_authorship);
+    if result.is_ok() {
+        // The remote now points at the staged commit; remember that as its notes tip
+        let _ = copy_ref(repository, &staging_ref, &tracking_ref);
+    }
+    let _ = delete_ref(repository, &staging_ref);
+    result?;
+
+    repository
+        .storage
+        .record_attribution_event(AttributionEvent::notes_pushed(remote_name));
+
+    Ok(entries.len())
+}
+
+/// Notes `replicate_notes` copied from one remote to another.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NotesReplication {
+    /// Commits with a note on the source remote that the target remote also has
+    pub shared_commits: usize,
+    /// Notes of those commits the target remote didn't have yet
+    pub pushed: usize,
+}
+
+/// Copy the notes `from_remote` has for commits `to_remote` also has onto `to_remote`, e.g.
+/// upstream's attribution onto a fork, or a fork's onto upstream once its PR has merged.
+/// Notes of commits only `from_remote` has stay there. What `to_remote` has is read from
+/// its remote-tracking branches, so it should be fetched first.
+pub fn replicate_notes(
+    repository: &Repository,
+    from_remote: &str,
+    to_remote: &str,
+) -> Result<NotesReplication, GitAiError> {
+    repository.ensure_writable()?;
+    let settings = SyncSettings::from_repository(repository);
+
+    let mut args = repository.global_args_for_exec();
+    args.extend(
+        [
+            "for-each-ref",
+            "--count=1",
+            "--format=%(refname)",
+            &format!("refs/remotes/{}/", to_remote),
+        ]
+        .map(str::to_string),
+    );
+    if String::from_utf8(exec_git(&args)?.stdout)?
+        .trim()
+        .is_empty()
+    {
+        return Err(GitAiError::Generic(format!(
+            "no remote-tracking branches for '{}'; fetch it first",
+            to_remote
+        )));
+    }
+
+    if fetch_notes_from_remote(repository, from_remote, &settings.notes_ref)?
+        == NotesExistence::NotFound
+    {
+        return Ok(NotesReplication::default());
+    }
+
+    let reachable = commits_on_remote(repository, to_remote)?;
+    let mut shared: Vec<String> = list_notes(repository, &tracking_ref_for_remote(from_remote))?
+        .into_keys()
+        .filter(|commit| reachable.contains(commit))
+        .collect();
+    shared.sort();
+    let pushed =
+        push_notes_for_commits_to_remote(repository, to_remote, &settings.notes_ref, &shared)?;
+    Ok(NotesReplication {
+        shared_commits: shared.len(),
+        pushed,
+    })
+}
+
+/// Outcome of `reconcile_notes`.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NotesReconciliation {
+    /// Notes pushed to the remote, all in one notes commit
+    pub pushed: usize,
+    /// Commits both sides had noted differently whose notes were combined
+    pub merged: usize,
+    /// Commits whose notes attribute lines to different prompts. Each side keeps its own note
+    /// for them, and they are not pushed
+    pub conflicts: Vec<(String, Vec<LineConflict>)>,
+}
+
+/// Reconcile the local notes with `remote_name` after working offline: fetch its notes,
+/// combine the notes both sides attached to the same commits (see `note_merge`), then push
+/// the notes of every commit the remote has in a single batch. Notes of commits the remote
+/// doesn't have stay local, as with the post-push sync. What the remote has is read from its
+/// remote-tracking branches, so it should be fetched first.
+pub fn reconcile_notes(
+    repository: &Repository,
+    remote_name: &str,
+    remote_notes_ref: &str,
+) -> Result<NotesReconciliation, GitAiError> {
+    repository.ensure_writable()?;
+    let mut reconciliation = NotesReconciliation::default();
+
+    // Remote-only notes are added locally; notes both sides have keep the local version
+    // until they are combined below
+    let local_before = list_notes(repository, AI_AUTHORSHIP_NOTES_REF)?;
+    let tracking_ref = tracking_ref_for_remote(remote_name);
+    let remote_notes = match fetch_notes_from_remote(repository, remote_name, remote_notes_ref)? {
+        NotesExistence::Found => list_notes(repository, &tracking_ref)?,
+        NotesExistence::NotFound => HashMap::new(),
+    };
+
+    let mut diverged: Vec<(&String, &String, &String)> = local_before
+        .iter()
+        .filter_map(|(commit, local_blob)| {
+            let remote_blob = remote_notes.get(commit)?;
+            (remote_blob != local_blob).then_some((commit, local_blob, remote_blob))
+        })
+        .collect();
+    diverged.sort();
+
+    let mut merged_notes = Vec::new();
+    for (commit, local_blob, remote_blob) in diverged {
+        let (Some(local), Some(remote)) = (
+            read_note_blob(repository, local_blob),
+            read_note_blob(repository, remote_blob),
+        ) else {
+            debug_log(&format!(
+                "skipping notes of {} that don't parse as authorship logs",
+                commit
+            ));
+            continue;
+        };
+        match merge_notes(&local, &remote) {
+            Ok(mut merged) => {
+                merged.metadata.base_commit_sha = local.metadata.base_commit_sha.clone();
+                let note = merged.serialize_to_string().map_err(|_| {
+                    GitAiError::Generic("Failed to serialize authorship log".to_string())
+                })?;
+                merged_notes.push((commit.clone(), note));
+            }
+            Err(conflicts) => reconciliation.conflicts.push((commit.clone(), conflicts)),
+        }
+    }
+    reconciliation.merged = merged_notes.len();
+    notes_add_batch(repository, &merged_notes)?;
+
+    let reachable = commits_on_remote(repository, remote_name)?;
+    let mut to_push: Vec<String> = list_notes(repository, AI_AUTHORSHIP_NOTES_REF)?
+        .into_keys()
+        .filter(|commit| reachable.contains(commit))
+        .filter(|commit| {
+            !reconciliation
+                .conflicts
+                .iter()
+                .any(|(conflicted, _)| conflicted == commit)
+        })
+        .collect();
+    to_push.sort();
+    reconciliation.pushed =
+        push_notes_for_commits_to_remote(repository, remote_name, remote_notes_ref, &to_push)?;
+
+    Ok(reconciliation)
+}
+
+/// Commit -> note blob of every note on `notes_ref`.
+fn list_notes(
+    repository: &Repository,
+    notes_ref: &str,
+) -> Result<HashMap<String, String>, GitAiError> {
+    if !ref_exists(repository, notes_ref) {
+        return Ok(HashMap::new());
+    }
+    let mut args = repository.global_args_for_exec();
+    args.extend(["notes", &format!("--ref={}", notes_ref), "list"].map(str::to_string));
+    Ok(String::from_utf8(exec_git(&args)?.stdout)?
+        .lines()
+        .filter_map(|line| {
+            let (blob, commit) = line.split_once(' ')?;
+            Some((commit.trim().to_string(), blob.to_string()))
+        })
+        .collect())
+}
+
+fn read_note_blob(repository: &Repository, blob: &str) -> Option<AuthorshipLog> {
+    let mut args = repository.global_args_for_exec();
+    args.extend(["cat-file", "blob", blob].map(str::to_string));
+    let content = String::from_utf8(exec_git(&args).ok()?.stdout).ok()?;
+    AuthorshipLog::deserialize_from_string(&content).ok()
+}
+
+/// Commits reachable from `remote`'s remote-tracking branches.
+fn commits_on_remote(repository: &Repository, remote: &str) -> Result<HashSet<String>, GitAiError> {
+    let mut args = repository.global_args_for_exec();
+    args.extend(["rev-list", &format!("--remotes={}", remote)].map(str::to_string));
+    Ok(String::from_utf8(exec_git(&args)?.stdout)?
+        .lines()
+        .map(str::to_string)
+        .collect())
+}
+
+fn resolve_ref(repository: &Repository, ref_name: &str) -> Option<String> {
+    let mut args = repository.global_args_for_exec();
+    args.push("rev-parse".to_string());
+    args.push("--verify".to_string());
+    args.push("--quiet".to_string());
+    args.push(ref_name.to_string());
+    let output = exec_git(&args).ok()?;
+    let oid = String::from_utf8(output.stdout).ok()?.trim().to_string();
+    (!oid.is_empty()).then_some(oid)
+}
+
+fn delete_ref(repository: &Repository, ref_name: &str) -> Result<(), GitAiError> {
+    let mut args = repository.global_args_for_exec();
+    args.push("update-ref".to_string());
+    args.push("-d".to_string());
+    args.push(ref_name.to_string());
+    exec_git(&args).map(|_| ())
+}
+
+fn fetch_and_merge_notes_before_push(
+    repository: &Repository,
+    remote_name: &str,
+    remote_notes_ref: &str,
+) {
     let tracking_ref = tracking_ref_for_remote(remote_name);
-    let fetch_refspec = format!("+refs/notes/ai:{}", tracking_ref);
+    let fetch_refspec = format!("+{}:{}", remote_notes_ref, tracking_ref);
 
     let mut fetch_before_push: Vec<String> = repository.global_args_for_exec();
     fetch_before_push.push("-c".to_string());
@@ -210,7 +705,7 @@ pub fn push_authorship_notes(repository: &Repository, remote_name: &str) -> Resu
     // Fetch is best-effort; if it fails (e.g., no remote notes yet), continue
     if exec_git(&fetch_before_push).is_ok() {
         // Merge fetched notes into local refs/notes/ai
-        let local_notes_ref = "refs/notes/ai";
+        let local_notes_ref = AI_AUTHORSHIP_NOTES_REF;
 
         if ref_exists(repository, &tracking_ref) {
             if ref_exists(repository, local_notes_ref) {
@@ -234,30 +729,6 @@ pub fn push_authorship_notes(repository: &Repository, remote_name: &str) -> Resu
             }
         }
     }
-
-    // STEP 2: Push notes without force (requires fast-forward)
-    let mut push_authorship: Vec<String> = repository.global_args_for_exec();
-    push_authorship.push("-c".to_string());
-    push_authorship.push("core.hooksPath=/dev/null".to_string());
-    push_authorship.push("push".to_string());
-    push_authorship.push("--quiet".to_string());
-    push_authorship.push("--no-recurse-submodules".to_string());
-    push_authorship.push("--no-verify".to_string());
-    push_authorship.push("--no-signed".to_string());
-    push_authorship.push(remote_name.to_string());
-    push_authorship.push(AI_AUTHORSHIP_PUSH_REFSPEC.to_string());
-
-    debug_log(&format!(
-        "pushing authorship refs (no force): {:?}",
-        &push_authorship
-    ));
-    if let Err(e) = exec_git(&push_authorship) {
-        // Best-effort; don't fail user operation due to authorship sync issues
-        debug_log(&format!("authorship push skipped due to error: {}", e));
-        return Err(e);
-    }
-
-    Ok(())
 }
 
 fn extract_remote_from_fetch_args(args: &[String]) -> Option<String> {
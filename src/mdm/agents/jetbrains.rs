@@ -78,7 +78,9 @@ impl JetBrainsInstaller {
         }
 
         // Try direct download from Marketplace
-        if let Some(build_number) = &detected.build_number {
+        if cfg!(feature = "jetbrains-download")
+            && let Some(build_number) = &detected.build_number
+        {
             match download_plugin_from_marketplace(
                 PLUGIN_ID,
                 detected.ide.product_code,
@@ -1,6 +1,5 @@
 use crate::error::GitAiError;
 use crate::utils::debug_log;
-use std::io::{Cursor, Read};
 use std::path::Path;
 
 /// Download plugin from JetBrains Marketplace
@@ -43,7 +42,9 @@ pub fn download_plugin_from_marketplace(
 ///
 /// The ZIP file should contain a directory structure that will be extracted
 /// directly into the plugins directory
+#[cfg(feature = "jetbrains-download")]
 pub fn install_plugin_to_directory(zip_data: &[u8], plugin_dir: &Path) -> Result<(), GitAiError> {
+    use std::io::{Cursor, Read};
     use zip::ZipArchive;
 
     // Ensure the plugins directory exists
@@ -131,6 +132,14 @@ pub fn install_plugin_to_directory(zip_data: &[u8], plugin_dir: &Path) -> Result
     Ok(())
 }
 
+/// Builds without the `jetbrains-download` feature can't extract plugin archives
+#[cfg(not(feature = "jetbrains-download"))]
+pub fn install_plugin_to_directory(_zip_data: &[u8], _plugin_dir: &Path) -> Result<(), GitAiError> {
+    Err(GitAiError::Generic(
+        "This build of git-ai was compiled without the jetbrains-download feature".to_string(),
+    ))
+}
+
 /// Try to install plugin using IDE CLI
 ///
 /// Returns Ok(true) if installation succeeded, Ok(false) if CLI failed
@@ -4,6 +4,7 @@ pub mod git_client_installer;
 pub mod git_clients;
 pub mod hook_installer;
 pub mod jetbrains;
+pub mod shim_migration;
 pub mod skills_installer;
 pub mod spinner;
 pub mod utils;
@@ -0,0 +1,235 @@
+//! Post-upgrade migration of the installed `git` shim.
+//!
+//! The installers put a `git` shim next to the git-ai binary on PATH. `install.sh` makes it a
+//! symlink to git-ai, which keeps pointing at the new binary after a self-update. `install.ps1`
+//! copies the binary instead, since Windows has no symlinks without privileges, and that copy
+//! keeps running the old version after an upgrade. The migration replaces such a stale copy.
+//!
+//! Migration runs in two phases. Planning inspects the shim and records the expected form of
+//! every file that differs. Applying stages every new file next to its original first, then
+//! swaps them in one by one, keeping the originals as backups. If any step fails, or a file
+//! changed since it was planned, every swapped file is restored and the error is returned, so
+//! the files are either all migrated or all untouched.
+
+use crate::config;
+use crate::error::GitAiError;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+const STAGED_SUFFIX: &str = "git-ai-migrate";
+const BACKUP_SUFFIX: &str = "git-ai-backup";
+
+/// What a path holds, as far as the migration is concerned.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Snapshot {
+    Symlink(PathBuf),
+    File(Vec<u8>),
+}
+
+/// One file to replace with a copy of the current binary.
+#[derive(Debug, Clone)]
+pub struct Rewrite {
+    pub path: PathBuf,
+    original: Snapshot,
+    contents: Vec<u8>,
+}
+
+/// Find the `git` shim installed for the git-ai binary at `exe`. It is only git-ai's when `exe`
+/// runs from the install directory the installer created it in.
+pub fn detect_path_shim(exe: &Path) -> Option<PathBuf> {
+    let shim_name = if cfg!(windows) { "git.exe" } else { "git" };
+    let install_dir = config::git_ai_dir_path().and_then(|dir| dir.join("bin").canonicalize().ok());
+    exe.parent()
+        .filter(|dir| install_dir.as_deref() == dir.canonicalize().ok().as_deref())
+        .map(|dir| dir.join(shim_name))
+        .filter(|shim| shim.symlink_metadata().is_ok())
+}
+
+/// Rewrites that bring the shims in `shims` into the form expected by the binary at `exe`.
+pub fn plan_migration(shims: &[PathBuf], exe: &Path) -> Result<Vec<Rewrite>, GitAiError> {
+    let mut rewrites = Vec::new();
+    for shim in shims {
+        if let Some(rewrite) = plan_path_shim(shim, exe)? {
+            rewrites.push(rewrite);
+        }
+    }
+    Ok(rewrites)
+}
+
+/// Bring the shim installed for `exe` into its expected form. Returns the rewrites, which are
+/// only planned when `dry_run` is set.
+pub fn migrate_installed_shim(exe: &Path, dry_run: bool) -> Result<Vec<Rewrite>, GitAiError> {
+    let shims: Vec<PathBuf> = detect_path_shim(exe).into_iter().collect();
+    let rewrites = plan_migration(&shims, exe)?;
+    if !dry_run {
+        apply_migration(&rewrites)?;
+    }
+    Ok(rewrites)
+}
+
+/// A shim that is a copy of an older binary is replaced with a copy of `exe`. A symlinked shim
+/// follows the binary on its own, so it is left alone.
+fn plan_path_shim(shim: &Path, exe: &Path) -> Result<Option<Rewrite>, GitAiError> {
+    let original = snapshot(shim)?;
+    let Snapshot::File(current) = &original else {
+        return Ok(None);
+    };
+    let contents = fs::read(exe)?;
+    if *current == contents {
+        return Ok(None);
+    }
+
+    Ok(Some(Rewrite {
+        path: shim.to_path_buf(),
+        original,
+        contents,
+    }))
+}
+
+fn snapshot(path: &Path) -> Result<Snapshot, GitAiError> {
+    let metadata = path.symlink_metadata()?;
+    if metadata.file_type().is_symlink() {
+        return Ok(Snapshot::Symlink(fs::read_link(path)?));
+    }
+    Ok(Snapshot::File(fs::read(path)?))
+}
+
+fn sibling(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(".");
+    name.push(suffix);
+    path.with_file_name(name)
+}
+
+fn write_executable(path: &Path, contents: &[u8]) -> Result<(), GitAiError> {
+    fs::write(path, contents)?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o755))?;
+    }
+    Ok(())
+}
+
+/// Apply `rewrites` all together: on any failure, every file is restored and the error is
+/// returned.
+pub fn apply_migration(rewrites: &[Rewrite]) -> Result<(), GitAiError> {
+    let mut staged = Vec::new();
+    for rewrite in rewrites {
+        let staged_path = sibling(&rewrite.path, STAGED_SUFFIX);
+        let _ = fs::remove_file(&staged_path);
+        if let Err(e) = write_executable(&staged_path, &rewrite.contents) {
+            let _ = fs::remove_file(&staged_path);
+            discard(&staged);
+            return Err(e);
+        }
+        staged.push(staged_path);
+    }
+
+    let mut swapped: Vec<&Rewrite> = Vec::new();
+    for (rewrite, staged_path) in rewrites.iter().zip(&staged) {
+        if let Err(e) = swap_in(rewrite, staged_path) {
+            roll_back(&swapped);
+            discard(&staged);
+            return Err(GitAiError::Generic(format!(
+                "Failed to migrate {}: {}; all changes were rolled back",
+                rewrite.path.display(),
+                e
+            )));
+        }
+        swapped.push(rewrite);
+    }
+
+    for rewrite in rewrites {
+        let _ = fs::remove_file(sibling(&rewrite.path, BACKUP_SUFFIX));
+    }
+    Ok(())
+}
+
+fn swap_in(rewrite: &Rewrite, staged_path: &Path) -> Result<(), GitAiError> {
+    if snapshot(&rewrite.path)? != rewrite.original {
+        return Err(GitAiError::Generic(
+            "it changed during the migration".to_string(),
+        ));
+    }
+    let backup = sibling(&rewrite.path, BACKUP_SUFFIX);
+    let _ = fs::remove_file(&backup);
+    fs::rename(&rewrite.path, &backup)?;
+    if let Err(e) = fs::rename(staged_path, &rewrite.path) {
+        let _ = fs::rename(&backup, &rewrite.path);
+        return Err(e.into());
+    }
+    Ok(())
+}
+
+fn roll_back(swapped: &[&Rewrite]) {
+    for rewrite in swapped.iter().rev() {
+        let _ = fs::rename(sibling(&rewrite.path, BACKUP_SUFFIX), &rewrite.path);
+    }
+}
+
+fn discard(staged: &[PathBuf]) {
+    for path in staged {
+        let _ = fs::remove_file(path);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn write_file(dir: &Path, name: &str, contents: &str) -> PathBuf {
+        let path = dir.join(name);
+        write_executable(&path, contents.as_bytes()).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_plan_and_apply_replaces_stale_shim_copy() {
+        let dir = TempDir::new().unwrap();
+        let exe = write_file(dir.path(), "git-ai.exe", "new binary");
+        // install.ps1 copies the binary, so the shim is the previous version's copy
+        let shim = write_file(dir.path(), "git.exe", "old binary");
+
+        let rewrites = plan_migration(std::slice::from_ref(&shim), &exe).unwrap();
+        assert_eq!(rewrites.len(), 1);
+        apply_migration(&rewrites).unwrap();
+
+        assert_eq!(fs::read_to_string(&shim).unwrap(), "new binary");
+        assert!(plan_migration(&[shim], &exe).unwrap().is_empty());
+        // No staged files or backups are left behind
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 2);
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_plan_leaves_symlinked_shim_alone() {
+        let dir = TempDir::new().unwrap();
+        let exe = write_file(dir.path(), "git-ai", "new binary");
+        // install.sh symlinks the shim, which already runs whatever binary is installed
+        let shim = dir.path().join("git");
+        std::os::unix::fs::symlink(&exe, &shim).unwrap();
+
+        assert!(plan_migration(&[shim], &exe).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_apply_rolls_back_every_file_when_one_fails() {
+        let dir = TempDir::new().unwrap();
+        let exe = write_file(dir.path(), "git-ai.exe", "new binary");
+        let first = write_file(dir.path(), "git.exe", "old binary");
+        let second = write_file(dir.path(), "git-other.exe", "old binary");
+        let rewrites = plan_migration(&[first.clone(), second.clone()], &exe).unwrap();
+        assert_eq!(rewrites.len(), 2);
+
+        // Something replaces the second shim between planning and applying
+        write_file(dir.path(), "git-other.exe", "edited binary");
+        let err = apply_migration(&rewrites).unwrap_err();
+        assert!(err.to_string().contains("rolled back"), "{}", err);
+
+        assert_eq!(fs::read_to_string(&first).unwrap(), "old binary");
+        assert_eq!(fs::read_to_string(&second).unwrap(), "edited binary");
+        assert_eq!(fs::read_dir(dir.path()).unwrap().count(), 3);
+    }
+}
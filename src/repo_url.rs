@@ -78,9 +78,44 @@ fn normalize_ssh_url(host: &str, path: &str) -> Result<String, String> {
     Ok(canonical)
 }
 
+/// Link to a single line of a file at a commit on the hosting service of a canonical repo URL.
+/// GitLab and Bitbucket use their own URL shapes; every other host gets GitHub's, which
+/// Gitea, Forgejo and most GitHub-compatible hosts also understand.
+pub fn line_permalink(repo_url: &str, commit_sha: &str, path: &str, line: u32) -> String {
+    let repo_url = repo_url.trim_end_matches('/');
+    let host = Url::parse(repo_url)
+        .ok()
+        .and_then(|url| url.host_str().map(str::to_lowercase))
+        .unwrap_or_default();
+
+    if host.contains("gitlab") {
+        format!("{}/-/blob/{}/{}#L{}", repo_url, commit_sha, path, line)
+    } else if host == "bitbucket.org" {
+        format!("{}/src/{}/{}#lines-{}", repo_url, commit_sha, path, line)
+    } else {
+        format!("{}/blob/{}/{}#L{}", repo_url, commit_sha, path, line)
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::normalize_repo_url;
+    use super::{line_permalink, normalize_repo_url};
+
+    #[test]
+    fn test_line_permalink_by_host() {
+        assert_eq!(
+            line_permalink("https://github.com/user/repo", "abc123", "src/main.rs", 7),
+            "https://github.com/user/repo/blob/abc123/src/main.rs#L7"
+        );
+        assert_eq!(
+            line_permalink("https://gitlab.com/group/repo", "abc123", "a.py", 1),
+            "https://gitlab.com/group/repo/-/blob/abc123/a.py#L1"
+        );
+        assert_eq!(
+            line_permalink("https://bitbucket.org/team/repo", "abc123", "a.py", 3),
+            "https://bitbucket.org/team/repo/src/abc123/a.py#lines-3"
+        );
+    }
 
     #[test]
     fn test_normalize_repo_url_https() {
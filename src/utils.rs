@@ -1,7 +1,8 @@
 use crate::error::GitAiError;
 use crate::git::diff_tree_to_tree::Diff;
-use std::io::IsTerminal;
+use std::io::{IsTerminal, Write};
 use std::path::PathBuf;
+use std::process::{Command, Stdio};
 
 /// Check if debug logging is enabled via environment variable
 ///
@@ -147,6 +148,21 @@ impl LockFile {
         let file = try_lock_exclusive(path)?;
         Some(Self { _file: file })
     }
+
+    /// Wait up to `timeout` for an exclusive lock on the given path.
+    /// Returns `None` if another process still holds the lock when the timeout expires.
+    pub fn acquire(path: &std::path::Path, timeout: std::time::Duration) -> Option<Self> {
+        let deadline = std::time::Instant::now() + timeout;
+        loop {
+            if let Some(lock) = Self::try_acquire(path) {
+                return Some(lock);
+            }
+            if std::time::Instant::now() >= deadline {
+                return None;
+            }
+            std::thread::sleep(std::time::Duration::from_millis(5));
+        }
+    }
 }
 
 #[cfg(unix)]
@@ -177,6 +193,152 @@ fn try_lock_exclusive(path: &std::path::Path) -> Option<std::fs::File> {
         .ok()
 }
 
+/// Copy text to the system clipboard
+pub fn copy_to_clipboard(text: &str) -> Result<(), GitAiError> {
+    let result = copy_to_clipboard_platform(text);
+
+    if result.is_err() {
+        // Fallback: try common clipboard tools
+        if let Ok(()) = try_clipboard_fallback(text) {
+            return Ok(());
+        }
+    }
+
+    result
+}
+
+#[cfg(target_os = "macos")]
+fn copy_to_clipboard_platform(text: &str) -> Result<(), GitAiError> {
+    let mut child = Command::new("pbcopy")
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|e| GitAiError::Generic(format!("Failed to spawn pbcopy: {}", e)))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(text.as_bytes())
+            .map_err(|e| GitAiError::Generic(format!("Failed to write to pbcopy: {}", e)))?;
+    }
+
+    let status = child
+        .wait()
+        .map_err(|e| GitAiError::Generic(format!("Failed to wait for pbcopy: {}", e)))?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(GitAiError::Generic("pbcopy failed".to_string()))
+    }
+}
+
+#[cfg(target_os = "linux")]
+fn copy_to_clipboard_platform(text: &str) -> Result<(), GitAiError> {
+    // Prefer wl-copy under Wayland, then xclip, then xsel
+    let wayland = std::env::var_os("WAYLAND_DISPLAY").is_some();
+    let mut child = (if wayland {
+        Command::new("wl-copy").stdin(Stdio::piped()).spawn()
+    } else {
+        Err(std::io::Error::from(std::io::ErrorKind::NotFound))
+    })
+    .or_else(|_| {
+        Command::new("xclip")
+            .args(["-selection", "clipboard"])
+            .stdin(Stdio::piped())
+            .spawn()
+    })
+    .or_else(|_| {
+        Command::new("xsel")
+            .args(["--clipboard", "--input"])
+            .stdin(Stdio::piped())
+            .spawn()
+    })
+    .map_err(|e| {
+        GitAiError::Generic(format!(
+            "No clipboard tool available (wl-copy, xclip or xsel required): {}",
+            e
+        ))
+    })?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(text.as_bytes())
+            .map_err(|e| GitAiError::Generic(format!("Failed to write to clipboard: {}", e)))?;
+    }
+
+    let status = child
+        .wait()
+        .map_err(|e| GitAiError::Generic(format!("Failed to wait for clipboard command: {}", e)))?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(GitAiError::Generic("Clipboard command failed".to_string()))
+    }
+}
+
+#[cfg(target_os = "windows")]
+fn copy_to_clipboard_platform(text: &str) -> Result<(), GitAiError> {
+    let mut child = Command::new("clip")
+        .stdin(Stdio::piped())
+        .spawn()
+        .map_err(|e| GitAiError::Generic(format!("Failed to spawn clip: {}", e)))?;
+
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin
+            .write_all(text.as_bytes())
+            .map_err(|e| GitAiError::Generic(format!("Failed to write to clip: {}", e)))?;
+    }
+
+    let status = child
+        .wait()
+        .map_err(|e| GitAiError::Generic(format!("Failed to wait for clip: {}", e)))?;
+
+    if status.success() {
+        Ok(())
+    } else {
+        Err(GitAiError::Generic("clip failed".to_string()))
+    }
+}
+
+#[cfg(not(any(target_os = "macos", target_os = "linux", target_os = "windows")))]
+fn copy_to_clipboard_platform(_text: &str) -> Result<(), GitAiError> {
+    Err(GitAiError::Generic(
+        "Clipboard not supported on this platform".to_string(),
+    ))
+}
+
+/// Fallback clipboard method for when platform-specific method fails
+fn try_clipboard_fallback(text: &str) -> Result<(), GitAiError> {
+    // Try common clipboard tools in order
+    let tools = [
+        ("pbcopy", vec![]),
+        ("wl-copy", vec![]),
+        ("xclip", vec!["-selection", "clipboard"]),
+        ("xsel", vec!["--clipboard", "--input"]),
+        ("clip", vec![]),
+    ];
+
+    for (tool, args) in tools {
+        if let Ok(mut child) = Command::new(tool)
+            .args(&args)
+            .stdin(Stdio::piped())
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .spawn()
+            && let Some(mut stdin) = child.stdin.take()
+            && stdin.write_all(text.as_bytes()).is_ok()
+            && let Ok(status) = child.wait()
+            && status.success()
+        {
+            return Ok(());
+        }
+    }
+
+    Err(GitAiError::Generic(
+        "No clipboard tool available".to_string(),
+    ))
+}
+
 /// Windows-specific flag to prevent console window creation
 #[cfg(windows)]
 pub const CREATE_NO_WINDOW: u32 = 0x08000000;
@@ -0,0 +1,100 @@
+#[macro_use]
+mod repos;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::{TestRepo, get_binary_path};
+
+#[test]
+fn test_assert_attributed_passes_for_commits_with_authorship_logs() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("file.txt");
+    file.set_contents(lines!["Line 1", "Line 2"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    file.insert_at(1, lines!["AI line".ai()]);
+    repo.stage_all_and_commit("AI commit").unwrap();
+
+    let output = repo
+        .git_ai(&["assert-attributed", "HEAD", "HEAD~1"])
+        .unwrap();
+    assert!(output.contains("HEAD~1"), "{}", output);
+    assert!(output.contains("has a valid authorship log"), "{}", output);
+}
+
+#[test]
+fn test_assert_attributed_fails_without_authorship_log() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("file.txt");
+    file.set_contents(lines!["Line 1", "Line 2"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    // Bypass the git-ai wrapper so no note is written
+    repo.git_og(&["commit", "--allow-empty", "-m", "unattributed"])
+        .unwrap();
+
+    let err = repo.git_ai(&["assert-attributed"]).unwrap_err();
+    assert!(err.contains("No authorship note found"), "{}", err);
+    assert!(repo.git_ai(&["assert-attributed", "HEAD~1"]).is_ok());
+}
+
+/// `git rebase --exec` command that runs the test binary as `git-ai` on each rebased commit.
+fn assert_attributed_exec() -> String {
+    format!(
+        "GIT_AI=git-ai '{}' assert-attributed HEAD",
+        get_binary_path().display()
+    )
+}
+
+/// A feature branch with one AI commit, and a base commit on the default branch to rebase it
+/// onto. Returns the base commit.
+fn feature_branch_behind_base(repo: &TestRepo) -> String {
+    let mut file = repo.filename("file.txt");
+    file.set_contents(lines!["Line 1", "Line 2"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    let default_branch = repo.current_branch();
+
+    repo.git(&["checkout", "-b", "feature"]).unwrap();
+    file.insert_at(1, lines!["AI line".ai()]);
+    repo.stage_all_and_commit("AI commit").unwrap();
+
+    repo.git(&["checkout", &default_branch]).unwrap();
+    let mut main_file = repo.filename("main.txt");
+    main_file.set_contents(lines!["main"]);
+    repo.stage_all_and_commit("Main work").unwrap();
+    let base = repo.git(&["rev-parse", "HEAD"]).unwrap().trim().to_string();
+    repo.git(&["checkout", "feature"]).unwrap();
+    base
+}
+
+#[test]
+fn test_assert_attributed_in_rebase_exec_passes_for_attributed_commits() {
+    let repo = TestRepo::new();
+    let base = feature_branch_behind_base(&repo);
+
+    let output = repo
+        .git(&["rebase", "--exec", &assert_attributed_exec(), &base])
+        .expect("every rebased commit should pass");
+    // The rebased commit has no note until the rebase finishes, so it passes through the
+    // commit it was rewritten from
+    assert!(output.contains("is being rebased from"), "{}", output);
+    assert!(repo.git_ai(&["assert-attributed", "HEAD"]).is_ok());
+}
+
+#[test]
+fn test_assert_attributed_in_rebase_exec_stops_at_unattributed_commit() {
+    let repo = TestRepo::new();
+    let base = feature_branch_behind_base(&repo);
+    // Bypass the git-ai wrapper so no note is written
+    repo.git_og(&["commit", "--allow-empty", "-m", "unattributed"])
+        .unwrap();
+    repo.git_og(&["commit", "--allow-empty", "-m", "after"])
+        .unwrap();
+
+    let err = repo
+        .git(&["rebase", "--exec", &assert_attributed_exec(), &base])
+        .unwrap_err();
+    assert!(err.contains("which has no valid authorship log"), "{}", err);
+    // The attributed commit passed, so the rebase stopped at the unattributed one
+    let subject = repo.git(&["log", "-1", "--format=%s"]).unwrap();
+    assert_eq!(subject.trim(), "unattributed");
+
+    repo.git(&["rebase", "--abort"]).unwrap();
+}
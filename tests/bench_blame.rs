@@ -0,0 +1,71 @@
+#[macro_use]
+mod repos;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+
+fn repo_with_files() -> TestRepo {
+    let repo = TestRepo::new();
+    let mut first = repo.filename("first.txt");
+    first.set_contents(lines!["Line 1", "Line 2"]);
+    let mut second = repo.filename("second.txt");
+    second.set_contents(lines!["Line 1", "AI line".ai(), "Line 3"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    repo
+}
+
+#[test]
+fn test_bench_blame_reports_overhead_against_git() {
+    let repo = repo_with_files();
+
+    let output = repo
+        .git_ai(&[
+            "bench",
+            "blame",
+            "--against-git",
+            "--runs",
+            "1",
+            "--budget",
+            "100000",
+            "--json",
+        ])
+        .unwrap();
+    let report: serde_json::Value = serde_json::from_str(&output).unwrap();
+    assert_eq!(report["files"], 2);
+    assert!(report["git_ai_ms"]["p95"].as_f64().unwrap() > 0.0);
+    assert!(report["git_ms"]["p95"].as_f64().unwrap() > 0.0);
+    assert!(report["overhead_percent"]["p95"].is_number());
+    assert_eq!(report["within_budget"], true);
+
+    let output = repo
+        .git_ai(&["bench", "blame", "--files", "1", "--runs", "1"])
+        .unwrap();
+    assert!(output.contains("Blamed 1 files"), "{}", output);
+    assert!(!output.contains("overhead"), "{}", output);
+}
+
+#[test]
+fn test_bench_blame_budget_needs_baseline() {
+    let repo = repo_with_files();
+    let err = repo
+        .git_ai(&["bench", "blame", "--budget", "50"])
+        .unwrap_err();
+    assert!(err.contains("--against-git"), "{}", err);
+}
+
+#[test]
+fn test_bench_blame_leaves_the_blame_cache_alone() {
+    let repo = repo_with_files();
+    repo.git_ai(&["blame", "second.txt"]).unwrap();
+    let cache_dir = repo
+        .path()
+        .join(".git")
+        .join("ai")
+        .join("cache")
+        .join("blame");
+    let cached_entries = || std::fs::read_dir(&cache_dir).unwrap().count();
+    let before = cached_entries();
+    assert!(before > 0);
+
+    repo.git_ai(&["bench", "blame", "--runs", "1"]).unwrap();
+    assert_eq!(cached_entries(), before);
+}
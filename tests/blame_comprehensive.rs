@@ -898,7 +898,7 @@ fn test_blame_contents_modified_buffer() {
         .unwrap();
 
     assert!(output.contains("Modified line"));
-    assert!(output.contains("External file"));
+    assert!(output.starts_with("00000000 (Test User"));
 }
 
 // =============================================================================
@@ -976,9 +976,10 @@ fn test_blame_abbrev_custom_length() {
         .git_ai(&["blame", "--abbrev", "10", "test.txt"])
         .unwrap();
 
-    // First field should be 10-character hash
+    // Like git, the hash column is one wider than the abbreviation so boundary commits
+    // fit their '^' prefix
     let first_field = output.split_whitespace().next().unwrap();
-    assert_eq!(first_field.len(), 10);
+    assert_eq!(first_field.len(), 11);
 }
 
 #[test]
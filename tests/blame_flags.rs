@@ -688,14 +688,60 @@ fn test_blame_contents_from_stdin() {
     println!("\n[DEBUG] git-ai blame output:\n{}", git_ai_output);
     let lines = git_ai_output.lines().collect::<Vec<&str>>();
 
+    // Edited and new buffer lines are uncommitted edits by the current user
     assert!(
-        lines[0].starts_with("0000000 (External file (--contents)"),
-        "First line should be the  --contents"
+        lines[0].starts_with("00000000 (Test User"),
+        "First line should be an uncommitted edit by the current user: {}",
+        lines[0]
+    );
+    assert!(
+        lines[3].starts_with("00000000 (Test User"),
+        "Last line should be an uncommitted edit by the current user: {}",
+        lines[3]
     );
 
+    // Unchanged lines keep their committed attribution
     assert!(
-        lines[3].starts_with("0000000 (External file (--contents)"),
-        "Last line should be the --contents"
+        lines[1].contains("mock_ai"),
+        "Line 2 should still be attributed to mock_ai: {}",
+        lines[1]
+    );
+    assert!(
+        !git_ai_output.contains("External file"),
+        "No line should be reported as an external file: {}",
+        git_ai_output
+    );
+}
+
+#[test]
+fn test_blame_contents_keeps_working_log_attribution() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+
+    file.set_contents(lines!["Line 1", "Line 2"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    // AI edits the file on disk (checkpointed but not committed)...
+    file.set_contents(lines!["Line 1", "Line 2", "AI line".ai()]);
+
+    // ...and the user then types an unsaved line in the editor buffer
+    let buffer = "Line 1\nTyped line\nLine 2\nAI line";
+    let output = repo
+        .git_ai_with_stdin(&["blame", "--contents", "-", "test.txt"], buffer.as_bytes())
+        .unwrap();
+    let lines = output.lines().collect::<Vec<&str>>();
+
+    assert!(lines[0].contains("Test User"), "{}", lines[0]);
+    assert!(
+        lines[1].starts_with("00000000 (Test User"),
+        "Unsaved line should belong to the current user: {}",
+        lines[1]
+    );
+    assert!(lines[2].contains("Test User"), "{}", lines[2]);
+    assert!(
+        lines[3].starts_with("0000000") && lines[3].contains("mock_ai"),
+        "Checkpointed AI line should keep its working log attribution: {}",
+        lines[3]
     );
 }
 
@@ -1185,3 +1231,549 @@ fn test_blame_ai_human_author() {
         ]
     );
 }
+
+fn porcelain_commit_shas(porcelain: &str) -> Vec<String> {
+    porcelain
+        .lines()
+        .filter(|line| {
+            let first = line.split_whitespace().next().unwrap_or("");
+            first.len() == 40 && first.chars().all(|c| c.is_ascii_hexdigit())
+        })
+        .map(|line| line.split_whitespace().next().unwrap().to_string())
+        .collect()
+}
+
+#[test]
+fn test_blame_format_json_matches_git_lines() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+
+    file.set_contents(lines!["Line 1", "Line 2".ai(), "Line 3"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let git_output = repo
+        .git(&["blame", "--line-porcelain", "test.txt"])
+        .unwrap();
+    let git_ai_output = repo
+        .git_ai(&["blame", "--format=json", "test.txt"])
+        .unwrap();
+
+    let json = &git_ai_output[git_ai_output.find('[').unwrap()..=git_ai_output.rfind(']').unwrap()];
+    let records: Vec<serde_json::Value> = serde_json::from_str(json).unwrap();
+    let shas: Vec<String> = records
+        .iter()
+        .map(|r| r["commit_sha"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(shas, porcelain_commit_shas(&git_output));
+
+    let contents: Vec<&str> = records
+        .iter()
+        .map(|r| r["content"].as_str().unwrap())
+        .collect();
+    assert_eq!(contents, vec!["Line 1", "Line 2", "Line 3"]);
+
+    assert!(records[0]["ai_tool"].is_null());
+    assert!(records[0]["prompt_id"].is_null());
+    assert_eq!(records[0]["source"], "authorship_note");
+
+    assert_eq!(records[1]["ai_tool"], "mock_ai");
+    assert_eq!(records[1]["ai_model"], "unknown");
+    assert!(records[1]["prompt_id"].is_string());
+    assert!(records[1]["session_id"].is_string());
+    assert_eq!(records[1]["source"], "authorship_note");
+}
+
+#[test]
+fn test_blame_format_jsonl_reports_working_log_lines() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+
+    file.set_contents(lines!["Line 1", "Line 2"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    file.insert_at(2, lines!["AI line".ai()]);
+
+    let git_output = repo
+        .git(&["blame", "--line-porcelain", "test.txt"])
+        .unwrap();
+    let git_ai_output = repo
+        .git_ai(&["blame", "--format", "jsonl", "test.txt"])
+        .unwrap();
+
+    let records: Vec<serde_json::Value> = git_ai_output
+        .lines()
+        .filter(|line| line.starts_with('{'))
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    let shas: Vec<String> = records
+        .iter()
+        .map(|r| r["commit_sha"].as_str().unwrap().to_string())
+        .collect();
+    assert_eq!(shas, porcelain_commit_shas(&git_output));
+
+    assert_eq!(records[2]["line"], 3);
+    assert_eq!(records[2]["content"], "AI line");
+    assert_eq!(records[2]["ai_tool"], "mock_ai");
+    assert_eq!(records[2]["source"], "working_log");
+    assert_eq!(records[0]["source"], "authorship_note");
+}
+
+#[test]
+fn test_blame_format_rejects_unknown_value() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["Line 1"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    assert!(repo.git_ai(&["blame", "--format=xml", "test.txt"]).is_err());
+}
+
+#[test]
+fn test_blame_piped_output_is_byte_identical_to_git() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+
+    file.set_contents(lines!["Line 1", "Line 2", "Line 3"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    file.insert_at(3, lines!["Line 4", "Line 5", "Line 6", "Line 7"]);
+    file.insert_at(7, lines!["Line 8", "Line 9", "Line 10"]);
+    repo.stage_all_and_commit("Second commit").unwrap();
+
+    let flag_sets: Vec<Vec<&str>> = vec![
+        vec![],
+        vec!["-n"],
+        vec!["-f"],
+        vec!["-s"],
+        vec!["-e"],
+        vec!["-l"],
+        vec!["-t"],
+        vec!["-b"],
+        vec!["--root"],
+        vec!["--abbrev=10"],
+        vec!["-L", "2,4"],
+        vec!["-f", "-n", "-e"],
+        vec!["-t", "-e"],
+    ];
+
+    for flags in flag_sets {
+        let mut args = vec!["blame"];
+        args.extend(flags.iter().copied());
+        args.push("test.txt");
+
+        let git_output = repo.git(&args).unwrap();
+        let git_ai_output = repo.git_ai(&args).unwrap();
+        assert_eq!(
+            git_ai_output, git_output,
+            "git-ai blame {:?} should match git blame byte-for-byte",
+            flags
+        );
+    }
+}
+
+#[test]
+fn test_blame_color_always_only_adds_escape_codes() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+
+    file.set_contents(lines!["Line 1", "Line 2".ai(), "Line 3"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let plain = repo
+        .git_ai(&["blame", "--color=never", "test.txt"])
+        .unwrap();
+    let auto = repo.git_ai(&["blame", "--color=auto", "test.txt"]).unwrap();
+    let colored = repo
+        .git_ai(&["blame", "--color=always", "test.txt"])
+        .unwrap();
+
+    assert!(!plain.contains('\x1b'));
+    assert_eq!(auto, plain, "auto should not color piped output");
+    assert!(colored.contains("\x1b["));
+
+    let escape = regex::Regex::new(r"\x1b\[[0-9;]*m").unwrap();
+    assert_eq!(escape.replace_all(&colored, ""), plain);
+}
+
+#[test]
+fn test_blame_color_rejects_unknown_value() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["Line 1"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    assert!(
+        repo.git_ai(&["blame", "--color=sometimes", "test.txt"])
+            .is_err()
+    );
+}
+
+#[test]
+fn test_blame_cache_is_reused_and_invalidated_by_notes() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["Line 1", "Line 2".ai()]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let first = repo.git_ai(&["blame", "test.txt"]).unwrap();
+    let cache_dir = repo
+        .path()
+        .join(".git")
+        .join("ai")
+        .join("cache")
+        .join("blame");
+    assert!(
+        std::fs::read_dir(&cache_dir).unwrap().next().is_some(),
+        "blame should populate the attribution cache"
+    );
+    let second = repo.git_ai(&["blame", "test.txt"]).unwrap();
+    assert_eq!(first, second);
+
+    // Removing the note moves refs/notes/ai, so the cached attribution must not be served.
+    repo.git(&["notes", "--ref=ai", "remove", "HEAD"]).unwrap();
+    let after = repo.git_ai(&["blame", "test.txt"]).unwrap();
+    let git_output = repo.git(&["blame", "test.txt"]).unwrap();
+    assert_ne!(extract_authors(&first), extract_authors(&after));
+    assert_eq!(
+        normalize_for_snapshot(&git_output),
+        normalize_for_snapshot(&after)
+    );
+}
+
+#[test]
+fn test_blame_copy_provenance() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+
+    file.set_contents(lines!["Line 1", "Line 2".ai()]);
+    let commit = repo.stage_all_and_commit("Initial commit").unwrap();
+    repo.git(&["remote", "add", "origin", "git@github.com:acme/widgets.git"])
+        .unwrap();
+
+    let output = repo
+        .git_ai(&["blame", "--copy-provenance", "test.txt:2"])
+        .unwrap();
+
+    // The same provenance string `git-ai provenance` prints, plus the permalink
+    let provenance = repo.git_ai(&["provenance", "test.txt:2"]).unwrap();
+    assert!(output.contains(provenance.trim()), "{}", output);
+    assert!(
+        output.contains(&format!("commit={}", commit.commit_sha)),
+        "{}",
+        output
+    );
+    assert!(output.contains("by=ai:mock_ai"), "{}", output);
+    assert!(
+        output.contains(&format!(
+            "https://github.com/acme/widgets/blob/{}/test.txt#L2",
+            commit.commit_sha
+        )),
+        "{}",
+        output
+    );
+
+    let human = repo
+        .git_ai(&["blame", "--copy-provenance", "test.txt:1"])
+        .unwrap();
+    assert!(human.contains("file=test.txt:1"), "{}", human);
+    assert!(human.contains("by=human:"), "{}", human);
+
+    assert!(
+        repo.git_ai(&["blame", "--copy-provenance", "test.txt"])
+            .is_err()
+    );
+}
+
+#[test]
+fn test_blame_compat_strict_matches_git_and_writes_sidecar() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("strict.txt");
+    file.set_contents(lines!["Line 1", "AI line".ai()]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let sidecar = repo.path().join("strict-sidecar.json");
+    let sidecar_arg = sidecar.to_str().unwrap();
+    let strict = repo
+        .git_ai(&[
+            "blame",
+            "--compat",
+            "strict",
+            "--sidecar",
+            sidecar_arg,
+            "strict.txt",
+        ])
+        .unwrap();
+    let git_blame = repo.git_og(&["blame", "strict.txt"]).unwrap();
+    assert_eq!(strict, git_blame);
+
+    let json: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&sidecar).unwrap()).unwrap();
+    assert_eq!(json["version"], 1);
+    assert_eq!(json["file"], "strict.txt");
+    let lines = json["lines"].as_array().unwrap();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[1]["ai_tool"], "mock_ai");
+    assert!(lines[0]["ai_tool"].is_null());
+
+    let err = repo
+        .git_ai(&["blame", "--compat", "loose", "strict.txt"])
+        .unwrap_err();
+    assert!(err.contains("Invalid value for --compat"));
+}
+
+#[test]
+fn test_blame_compat_strict_rejects_git_ai_only_flags() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("strict.txt");
+    file.set_contents(lines!["Line 1", "AI line".ai()]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    for flags in [
+        &["--only-ai"][..],
+        &["--tool", "mock_ai"],
+        &["--format=json"],
+        &["--show-deleted"],
+        &["--dominant-author", "majority"],
+    ] {
+        let mut args = vec!["blame", "--compat", "strict"];
+        args.extend_from_slice(flags);
+        args.push("strict.txt");
+        let err = repo.git_ai(&args).unwrap_err();
+        assert!(
+            err.contains(&format!(
+                "{} is a git-ai option",
+                flags[0].split('=').next().unwrap()
+            )),
+            "{}",
+            err
+        );
+    }
+
+    // git blame's own flags still pass through
+    let strict = repo
+        .git_ai(&["blame", "--compat", "strict", "-s", "strict.txt"])
+        .unwrap();
+    assert_eq!(strict, repo.git_og(&["blame", "-s", "strict.txt"]).unwrap());
+}
+
+#[test]
+fn test_blame_follows_rename_with_ai_attribution() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("old_name.txt");
+    file.set_contents(lines!["Human line", "AI generated line".ai()]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    repo.git(&["mv", "old_name.txt", "new_name.txt"]).unwrap();
+    repo.stage_all_and_commit("Rename file").unwrap();
+
+    let git_output = repo.git(&["blame", "new_name.txt"]).unwrap();
+    let git_ai_output = repo.git_ai(&["blame", "new_name.txt"]).unwrap();
+    assert_eq!(
+        normalize_for_snapshot(&git_output),
+        normalize_for_snapshot(&git_ai_output),
+        "Renamed file blame should match git, including the filename column"
+    );
+    assert_eq!(
+        extract_authors(&git_ai_output),
+        vec!["Test".to_string(), "mock_ai".to_string()]
+    );
+}
+
+#[test]
+fn test_blame_move_detection_within_file() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("moves.rs");
+    file.set_contents(lines![
+        "fn compute_invoice_total_for_customer_account() -> u64 {".ai(),
+        "    calculate_line_items_and_apply_regional_taxes()".ai(),
+        "}".ai(),
+        "// helper functions written by a human follow below",
+        "fn human_written_helper_for_formatting_output() {}",
+    ]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    // Move the AI block below the human lines
+    std::fs::write(
+        repo.path().join("moves.rs"),
+        "// helper functions written by a human follow below\n\
+         fn human_written_helper_for_formatting_output() {}\n\
+         fn compute_invoice_total_for_customer_account() -> u64 {\n\
+         \x20   calculate_line_items_and_apply_regional_taxes()\n\
+         }",
+    )
+    .unwrap();
+    repo.stage_all_and_commit("Move block").unwrap();
+
+    let git_output = repo.git(&["blame", "-M", "moves.rs"]).unwrap();
+    let git_ai_output = repo.git_ai(&["blame", "-M", "moves.rs"]).unwrap();
+    assert_eq!(
+        normalize_for_snapshot(&git_output),
+        normalize_for_snapshot(&git_ai_output)
+    );
+
+    let authors = extract_authors(&git_ai_output);
+    assert_eq!(authors[2], "mock_ai");
+    assert_eq!(authors[3], "mock_ai");
+}
+
+#[test]
+fn test_blame_copy_detection_across_files() {
+    let repo = TestRepo::new();
+    let mut source = repo.filename("source.rs");
+    source.set_contents(lines![
+        "fn normalize_customer_identifier_before_lookup(id: &str) -> String {".ai(),
+        "    id.trim().to_ascii_lowercase().replace(' ', \"_\")".ai(),
+        "}".ai(),
+    ]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    // Copy the AI block into a new file; the source is left untouched
+    std::fs::write(
+        repo.path().join("copy.rs"),
+        "// copied helper\n\
+         fn normalize_customer_identifier_before_lookup(id: &str) -> String {\n\
+         \x20   id.trim().to_ascii_lowercase().replace(' ', \"_\")\n\
+         }",
+    )
+    .unwrap();
+    repo.stage_all_and_commit("Copy helper").unwrap();
+
+    let git_output = repo.git(&["blame", "-C", "-C", "copy.rs"]).unwrap();
+    let git_ai_output = repo.git_ai(&["blame", "-C", "-C", "copy.rs"]).unwrap();
+    assert_eq!(
+        normalize_for_snapshot(&git_output),
+        normalize_for_snapshot(&git_ai_output)
+    );
+    assert!(git_ai_output.contains("source.rs"));
+
+    let authors = extract_authors(&git_ai_output);
+    assert_eq!(authors[1], "mock_ai");
+    assert_eq!(authors[2], "mock_ai");
+
+    // Without copy detection the lines belong to the commit that created copy.rs
+    let plain = repo.git_ai(&["blame", "copy.rs"]).unwrap();
+    assert!(!plain.contains("source.rs"));
+}
+
+/// Lines 2, 5 and 6 are AI, the rest human.
+fn mixed_authorship_repo() -> TestRepo {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["Line 1", "Line 2", "Line 3", "Line 4"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    file.insert_at(1, lines!["AI 1".ai()]);
+    file.insert_at(4, lines!["AI 2".ai(), "AI 3".ai()]);
+    repo.stage_all_and_commit("AI commit").unwrap();
+    repo
+}
+
+#[test]
+fn test_blame_only_ai_folds_other_lines_into_gap_markers() {
+    let repo = mixed_authorship_repo();
+
+    let output = repo.git_ai(&["blame", "--only-ai", "test.txt"]).unwrap();
+    let shown: Vec<&str> = output
+        .lines()
+        .map(|line| {
+            if line == "..." {
+                "..."
+            } else {
+                line.rsplit(") ").next().unwrap()
+            }
+        })
+        .collect();
+    assert_eq!(shown, vec!["...", "AI 1", "...", "AI 2", "AI 3", "..."]);
+
+    let output = repo
+        .git_ai(&["blame", "--only-human", "-L", "2,5", "test.txt"])
+        .unwrap();
+    assert!(!output.contains("AI "), "{}", output);
+    assert!(output.contains("Line 2") && output.contains("Line 3"));
+    assert_eq!(output.lines().filter(|line| *line == "...").count(), 2);
+
+    let output = repo
+        .git_ai(&["blame", "--tool", "cursor", "test.txt"])
+        .unwrap();
+    assert!(output.trim().is_empty(), "{}", output);
+}
+
+#[test]
+fn test_blame_filters_compose_with_porcelain_and_json() {
+    let repo = mixed_authorship_repo();
+
+    let porcelain = repo
+        .git_ai(&["blame", "--tool=mock_ai", "--line-porcelain", "test.txt"])
+        .unwrap();
+    let content: Vec<&str> = porcelain
+        .lines()
+        .filter_map(|line| line.strip_prefix('\t'))
+        .collect();
+    assert_eq!(content, vec!["AI 1", "AI 2", "AI 3"]);
+
+    let jsonl = repo
+        .git_ai(&["blame", "--only-human", "--format", "jsonl", "test.txt"])
+        .unwrap();
+    let lines: Vec<u64> = jsonl
+        .lines()
+        .filter(|line| line.starts_with('{'))
+        .map(|line| {
+            serde_json::from_str::<serde_json::Value>(line).unwrap()["line"]
+                .as_u64()
+                .unwrap()
+        })
+        .collect();
+    assert_eq!(lines, vec![1, 3, 4, 7]);
+
+    let json = repo
+        .git_ai(&["blame", "--json", "--only-ai", "test.txt"])
+        .unwrap();
+    let parsed: serde_json::Value = serde_json::from_str(&json[json.find('{').unwrap()..]).unwrap();
+    let ranges: Vec<&String> = parsed["lines"].as_object().unwrap().keys().collect();
+    assert_eq!(ranges, vec!["2", "5-6"]);
+}
+
+#[test]
+fn test_blame_filter_conflicts_are_rejected() {
+    let repo = mixed_authorship_repo();
+    let err = repo
+        .git_ai(&["blame", "--only-human", "--tool", "mock_ai", "test.txt"])
+        .unwrap_err();
+    assert!(err.contains("--only-human cannot be combined"), "{}", err);
+}
+
+#[test]
+fn test_blame_cache_follows_configured_ignore_revs_file_and_mailmap() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("test.txt");
+    file.set_contents(lines!["Line 1", "Line 2"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    let initial_sha = repo.git(&["rev-parse", "HEAD"]).unwrap().trim().to_string();
+    file.set_contents(lines!["  Line 1", "  Line 2"]);
+    repo.stage_all_and_commit("Format: add indentation")
+        .unwrap();
+    let format_sha = repo.git(&["rev-parse", "HEAD"]).unwrap().trim().to_string();
+
+    let ignore_revs_path = repo.path().join("ignored-revs");
+    std::fs::write(&ignore_revs_path, "").unwrap();
+    repo.git_og(&["config", "blame.ignoreRevsFile", "ignored-revs"])
+        .unwrap();
+    let before = repo.git_ai(&["blame", "test.txt"]).unwrap();
+    assert!(before.contains(&format_sha[..7]), "{}", before);
+
+    // Editing the configured list changes what git blame returns, so the cached hunks must
+    // not be served
+    std::fs::write(&ignore_revs_path, format!("{}\n", format_sha)).unwrap();
+    let after = repo.git_ai(&["blame", "test.txt"]).unwrap();
+    assert!(after.contains(&initial_sha[..7]), "{}", after);
+    assert!(!after.contains(&format_sha[..7]), "{}", after);
+
+    // Same for a mailmap that renames the author
+    std::fs::write(
+        repo.path().join(".mailmap"),
+        "Mapped Author <test@example.com>\n",
+    )
+    .unwrap();
+    let mapped = repo.git_ai(&["blame", "test.txt"]).unwrap();
+    assert!(mapped.contains("Mapped Author"), "{}", mapped);
+}
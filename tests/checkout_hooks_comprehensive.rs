@@ -20,6 +20,7 @@ fn make_checkout_invocation(args: &[&str]) -> ParsedGitInvocation {
         command_args: args.iter().map(|s| s.to_string()).collect(),
         saw_end_of_opts: false,
         is_help: false,
+        no_ai_hooks: false,
     }
 }
 
@@ -46,7 +47,7 @@ fn test_pre_checkout_hook_normal() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     let parsed_args = make_checkout_invocation(&["main"]);
@@ -83,7 +84,7 @@ fn test_pre_checkout_hook_with_merge_flag() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     let parsed_args = make_checkout_invocation(&["--merge", "main"]);
@@ -113,7 +114,7 @@ fn test_pre_checkout_hook_merge_without_changes() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     let parsed_args = make_checkout_invocation(&["--merge", "main"]);
@@ -141,7 +142,7 @@ fn test_pre_checkout_hook_merge_short_flag() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     let parsed_args = make_checkout_invocation(&["-m", "main"]);
@@ -185,7 +186,7 @@ fn test_post_checkout_hook_success() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     let exit_status = std::process::Command::new("true").status().unwrap();
@@ -213,7 +214,7 @@ fn test_post_checkout_hook_failed() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     let exit_status = std::process::Command::new("false")
@@ -251,7 +252,7 @@ fn test_post_checkout_hook_head_unchanged() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     let exit_status = std::process::Command::new("true").status().unwrap();
@@ -293,7 +294,7 @@ fn test_post_checkout_hook_pathspec() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     let exit_status = std::process::Command::new("true").status().unwrap();
@@ -335,7 +336,7 @@ fn test_post_checkout_hook_multiple_pathspecs() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     let exit_status = std::process::Command::new("true").status().unwrap();
@@ -388,7 +389,7 @@ fn test_post_checkout_hook_force_checkout() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     let exit_status = std::process::Command::new("true").status().unwrap();
@@ -430,7 +431,7 @@ fn test_post_checkout_hook_force_short_flag() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     let exit_status = std::process::Command::new("true").status().unwrap();
@@ -471,7 +472,7 @@ fn test_post_checkout_hook_with_merge() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     // In real scenario, pre_checkout_hook would populate this
@@ -685,7 +686,7 @@ fn test_checkout_normal_flow() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     let parsed_args = make_checkout_invocation(&[&original_branch]);
@@ -736,7 +737,7 @@ fn test_checkout_force_flow() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     let parsed_args = make_checkout_invocation(&["--force", &original_branch]);
@@ -787,7 +788,7 @@ fn test_checkout_pathspec_flow() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
 
@@ -824,7 +825,7 @@ fn test_checkout_new_branch_creation() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     let parsed_args = make_checkout_invocation(&["-b", "new-branch"]);
@@ -863,7 +864,7 @@ fn test_checkout_detached_head() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     let parsed_args = make_checkout_invocation(&[&commit1.commit_sha]);
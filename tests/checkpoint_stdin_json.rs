@@ -0,0 +1,95 @@
+#[macro_use]
+mod repos;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+use std::fs;
+
+fn checkpoint_json(repo: &TestRepo, payload: serde_json::Value) -> Result<String, String> {
+    repo.git_ai_with_stdin(
+        &["checkpoint", "--stdin-json"],
+        serde_json::to_string(&payload).unwrap().as_bytes(),
+    )
+}
+
+#[test]
+fn test_stdin_json_attributes_edited_files() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("app.py");
+    file.set_contents(lines!["print('base')"]);
+    repo.stage_all_and_commit("Base").unwrap();
+
+    fs::write(
+        repo.path().join("app.py"),
+        "print('base')\nprint('agent')\n",
+    )
+    .unwrap();
+    let output = checkpoint_json(
+        &repo,
+        serde_json::json!({
+            "tool": "codex",
+            "model": "my-model",
+            "session_id": "run-1",
+            "files": [{ "path": "app.py" }],
+            "messages": [{ "type": "user", "text": "Print something" }],
+        }),
+    )
+    .expect("checkpoint should succeed");
+    assert!(output.contains("Checkpoint recorded"), "{}", output);
+
+    let commit = repo.stage_all_and_commit("Agent edit").unwrap();
+    let prompt = commit
+        .authorship_log
+        .metadata
+        .prompts
+        .values()
+        .next()
+        .expect("agent prompt should be recorded");
+    assert_eq!(prompt.agent_id.tool, "codex");
+    assert_eq!(prompt.agent_id.model, "my-model");
+
+    let mut file = repo.filename("app.py");
+    file.assert_lines_and_blame(lines!["print('base')".human(), "print('agent')".ai()]);
+}
+
+#[test]
+fn test_stdin_json_line_ranges_only_claim_reported_lines() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(lines!["fn base() {}"]);
+    repo.stage_all_and_commit("Base").unwrap();
+
+    fs::write(
+        repo.path().join("lib.rs"),
+        "fn base() {}\nfn agent_one() {}\nfn agent_two() {}\nfn typed_by_hand() {}\n",
+    )
+    .unwrap();
+    checkpoint_json(
+        &repo,
+        serde_json::json!({
+            "tool": "codex",
+            "session_id": "run-2",
+            "files": [{ "path": "lib.rs", "lines": [[2, 3]] }],
+        }),
+    )
+    .expect("checkpoint should succeed");
+
+    repo.stage_all_and_commit("Mixed edit").unwrap();
+    let mut file = repo.filename("lib.rs");
+    file.assert_lines_and_blame(lines![
+        "fn base() {}".human(),
+        "fn agent_one() {}".ai(),
+        "fn agent_two() {}".ai(),
+        "fn typed_by_hand() {}".human()
+    ]);
+}
+
+#[test]
+fn test_stdin_json_rejects_invalid_payload() {
+    let repo = TestRepo::new();
+    let err = checkpoint_json(
+        &repo,
+        serde_json::json!({ "tool": "codex", "session_id": "run-3", "files": [] }),
+    )
+    .expect_err("an empty file list should be rejected");
+    assert!(err.contains("\"files\""), "{}", err);
+}
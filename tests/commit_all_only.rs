@@ -0,0 +1,115 @@
+#[macro_use]
+mod repos;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+
+const CRYPTO_POLICY: &str = r#"[[rule]]
+name = "crypto"
+paths = ["src/crypto/**"]
+max_ai_percent = 50
+severity = "block"
+"#;
+
+fn write_policy(repo: &TestRepo, policy: &str) {
+    let dir = repo.path().join(".git-ai");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("policy.toml"), policy).unwrap();
+}
+
+#[test]
+fn test_commit_all_attributes_unstaged_ai_lines() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("file.txt");
+    file.set_contents(lines!["Line 1", "Line 2"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    file.insert_at(1, lines!["AI line".ai()]);
+    repo.git(&["commit", "-a", "-m", "AI change"]).unwrap();
+
+    file.assert_lines_and_blame(lines!["Line 1".human(), "AI line".ai(), "Line 2".human()]);
+    let status = repo.git(&["status", "--porcelain"]).unwrap();
+    assert!(status.trim().is_empty(), "{}", status);
+}
+
+#[test]
+fn test_commit_only_leaves_other_staged_files_for_the_next_commit() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("file.txt");
+    let mut other = repo.filename("other.txt");
+    file.set_contents(lines!["Line 1", "Line 2"]);
+    other.set_contents(lines!["Other 1", "Other 2"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    file.insert_at(1, lines!["AI line".ai()]);
+    other.insert_at(1, lines!["Other AI line".ai()]);
+    repo.git(&["add", "other.txt"]).unwrap();
+    repo.git(&["commit", "--only", "file.txt", "-m", "Only file"])
+        .unwrap();
+
+    file.assert_lines_and_blame(lines!["Line 1".human(), "AI line".ai(), "Line 2".human()]);
+    let log = repo.git_ai(&["show", "HEAD"]).unwrap();
+    assert!(!log.contains("other.txt"), "{}", log);
+
+    repo.git(&["commit", "-m", "Other"]).unwrap();
+    other.assert_lines_and_blame(lines![
+        "Other 1".human(),
+        "Other AI line".ai(),
+        "Other 2".human()
+    ]);
+}
+
+#[test]
+fn test_commit_pathspec_commits_only_those_files() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("file.txt");
+    let mut other = repo.filename("other.txt");
+    file.set_contents(lines!["Line 1", "Line 2"]);
+    other.set_contents(lines!["Other 1", "Other 2"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    file.insert_at(1, lines!["AI line".ai()]);
+    other.insert_at(1, lines!["Other AI line".ai()]);
+    repo.git(&["commit", "-m", "Only file", "--", "file.txt"])
+        .unwrap();
+
+    file.assert_lines_and_blame(lines!["Line 1".human(), "AI line".ai(), "Line 2".human()]);
+    repo.git(&["commit", "-am", "Other"]).unwrap();
+    other.assert_lines_and_blame(lines![
+        "Other 1".human(),
+        "Other AI line".ai(),
+        "Other 2".human()
+    ]);
+}
+
+#[test]
+fn test_policy_checks_what_commit_all_commits() {
+    let repo = TestRepo::new();
+    write_policy(&repo, CRYPTO_POLICY);
+    let mut aes = repo.filename("src/crypto/aes.rs");
+    aes.set_contents(lines!["// aes".human()]);
+    repo.stage_all_and_commit("Initial").unwrap();
+
+    // Nothing is staged, but -a commits the AI lines
+    aes.insert_at(1, lines!["fn encrypt() {}".ai(), "fn decrypt() {}".ai()]);
+    let err = repo
+        .git(&["commit", "-a", "-m", "AI crypto"])
+        .expect_err("policy should block the commit");
+    assert!(err.contains("[crypto]"), "error output: {}", err);
+}
+
+#[test]
+fn test_policy_ignores_staged_files_outside_the_pathspec() {
+    let repo = TestRepo::new();
+    write_policy(&repo, CRYPTO_POLICY);
+    let mut aes = repo.filename("src/crypto/aes.rs");
+    let mut readme = repo.filename("README.md");
+    aes.set_contents(lines!["// aes".human()]);
+    readme.set_contents(lines!["# crypto".human()]);
+    repo.stage_all_and_commit("Initial").unwrap();
+
+    aes.insert_at(1, lines!["fn encrypt() {}".ai(), "fn decrypt() {}".ai()]);
+    readme.insert_at(1, lines!["Docs".human()]);
+    repo.git(&["add", "-A"]).unwrap();
+    repo.git(&["commit", "-m", "Docs only", "README.md"])
+        .expect("the crypto file is staged but not committed");
+}
@@ -5,9 +5,10 @@ use git_ai::git::repository::Repository;
 mod test_utils;
 
 use crate::repos::test_repo::TestRepo;
+use git_ai::authorship::policy::CommitScope;
 use git_ai::commands::git_handlers::CommandHooksContext;
 use git_ai::commands::hooks::commit_hooks::{
-    commit_post_command_hook, commit_pre_command_hook, get_commit_default_author,
+    commit_post_command_hook, commit_pre_command_hook, commit_scope, get_commit_default_author,
 };
 use git_ai::git::cli_parser::ParsedGitInvocation;
 use git_ai::git::rewrite_log::RewriteLogEvent;
@@ -23,6 +24,7 @@ fn make_commit_invocation(args: &[&str]) -> ParsedGitInvocation {
         command_args: args.iter().map(|s| s.to_string()).collect(),
         saw_end_of_opts: false,
         is_help: false,
+        no_ai_hooks: false,
     }
 }
 
@@ -127,7 +129,7 @@ fn test_post_commit_hook_success() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     context.pre_commit_hook_result = Some(true);
@@ -174,7 +176,7 @@ fn test_post_commit_hook_amend() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     context.pre_commit_hook_result = Some(true);
@@ -209,7 +211,7 @@ fn test_post_commit_hook_dry_run() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
 
@@ -243,7 +245,7 @@ fn test_post_commit_hook_failed_status() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     context.pre_commit_hook_result = Some(true);
@@ -287,7 +289,7 @@ fn test_post_commit_hook_pre_hook_failed() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     context.pre_commit_hook_result = Some(false);
@@ -328,7 +330,7 @@ fn test_post_commit_hook_porcelain_suppresses_output() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     context.pre_commit_hook_result = Some(true);
@@ -361,7 +363,7 @@ fn test_post_commit_hook_quiet_suppresses_output() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     context.pre_commit_hook_result = Some(true);
@@ -637,6 +639,115 @@ fn test_extract_author_not_present() {
     assert_eq!(author, None);
 }
 
+// ==============================================================================
+// Commit Scope Tests
+// ==============================================================================
+
+fn scope(args: &[&str]) -> CommitScope {
+    commit_scope(&args.iter().map(|s| s.to_string()).collect::<Vec<_>>())
+}
+
+#[test]
+fn test_commit_scope_plain_commit_uses_index() {
+    assert_eq!(scope(&["-m", "message"]), CommitScope::Index);
+    assert_eq!(scope(&["--amend", "--no-edit"]), CommitScope::Index);
+    // Flag values are not pathspecs
+    assert_eq!(
+        scope(&["--author", "Test User <test@example.com>", "-m", "a.txt"]),
+        CommitScope::Index
+    );
+}
+
+#[test]
+fn test_commit_scope_all_flag() {
+    assert_eq!(scope(&["-a", "-m", "message"]), CommitScope::AllTracked);
+    assert_eq!(scope(&["--all", "-m", "message"]), CommitScope::AllTracked);
+    assert_eq!(scope(&["-am", "message"]), CommitScope::AllTracked);
+    // -m consumes the rest of the cluster, so the "a" here is part of the message
+    assert_eq!(scope(&["-mall"]), CommitScope::Index);
+}
+
+#[test]
+fn test_commit_scope_pathspecs() {
+    assert_eq!(
+        scope(&["-m", "message", "a.txt", "b.txt"]),
+        CommitScope::Paths {
+            pathspecs: vec!["a.txt".to_string(), "b.txt".to_string()],
+            include_index: false,
+        }
+    );
+    assert_eq!(
+        scope(&["--only", "-m", "message", "--", "-odd-name.txt"]),
+        CommitScope::Paths {
+            pathspecs: vec!["-odd-name.txt".to_string()],
+            include_index: false,
+        }
+    );
+    assert_eq!(
+        scope(&["-i", "-m", "message", "a.txt"]),
+        CommitScope::Paths {
+            pathspecs: vec!["a.txt".to_string()],
+            include_index: true,
+        }
+    );
+}
+
+#[test]
+fn test_commit_scope_pathspec_from_file() {
+    let dir = tempfile::tempdir().unwrap();
+    let list = dir.path().join("paths.txt");
+    std::fs::write(&list, "a.txt\nsrc/b.txt\n").unwrap();
+    let list = list.to_str().unwrap();
+    let expected = CommitScope::Paths {
+        pathspecs: vec!["a.txt".to_string(), "src/b.txt".to_string()],
+        include_index: false,
+    };
+    assert_eq!(
+        scope(&["-m", "message", "--pathspec-from-file", list]),
+        expected
+    );
+    assert_eq!(
+        scope(&["-m", "message", &format!("--pathspec-from-file={}", list)]),
+        expected
+    );
+
+    let nul_list = dir.path().join("paths-nul.txt");
+    std::fs::write(&nul_list, "a.txt\0src/b.txt\0").unwrap();
+    assert_eq!(
+        scope(&[
+            "--pathspec-file-nul",
+            "--pathspec-from-file",
+            nul_list.to_str().unwrap(),
+        ]),
+        expected
+    );
+
+    // Pathspecs on stdin can't be read ahead of git
+    assert_eq!(
+        scope(&["--pathspec-from-file=-", "-m", "message"]),
+        CommitScope::AllTracked
+    );
+}
+
+#[test]
+fn test_commit_scope_fixup_targets_are_not_pathspecs() {
+    assert_eq!(
+        scope(&["--fixup", "amend:HEAD~1", "--no-edit"]),
+        CommitScope::Index
+    );
+    assert_eq!(scope(&["--fixup=amend:HEAD~1"]), CommitScope::Index);
+    // A reword fixup commits no content, like `--only` without pathspecs
+    assert_eq!(scope(&["--fixup", "reword:HEAD~1"]), CommitScope::Unchanged);
+    assert_eq!(
+        scope(&["--fixup=amend:HEAD~1", "--only"]),
+        CommitScope::Unchanged
+    );
+    assert_eq!(
+        scope(&["--amend", "-o", "-m", "message"]),
+        CommitScope::Unchanged
+    );
+}
+
 // ==============================================================================
 // Integration Tests
 // ==============================================================================
@@ -668,7 +779,7 @@ fn test_commit_full_flow() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     context.pre_commit_hook_result = Some(true);
@@ -722,7 +833,7 @@ fn test_commit_amend_full_flow() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     context.pre_commit_hook_result = Some(true);
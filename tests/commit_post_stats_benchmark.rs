@@ -7,6 +7,7 @@
 //! `cargo test benchmark_stats_hunk_density_hotspot -- --ignored --nocapture`
 
 use git_ai::authorship::diff_ai_accepted::diff_ai_accepted_stats;
+use git_ai::authorship::line_filter::LineFilter;
 use git_ai::authorship::stats::{get_git_diff_stats, stats_for_commit_stats};
 use git_ai::git::find_repository_in_path;
 use std::fs;
@@ -171,8 +172,16 @@ fn benchmark_stats(repo_path: &Path) -> StatsBreakdown {
     let git_numstat = git_numstat_start.elapsed();
 
     let diff_ai_start = Instant::now();
-    let _diff_ai = diff_ai_accepted_stats(&repo, &parent_sha, &head_sha, Some(&parent_sha), &[])
-        .expect("diff_ai_accepted_stats failed");
+    let _diff_ai = diff_ai_accepted_stats(
+        &repo,
+        &parent_sha,
+        &head_sha,
+        Some(&parent_sha),
+        &[],
+        &LineFilter::default(),
+        false,
+    )
+    .expect("diff_ai_accepted_stats failed");
     let diff_ai_accepted = diff_ai_start.elapsed();
 
     let total_stats_start = Instant::now();
@@ -0,0 +1,64 @@
+#[macro_use]
+mod repos;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+
+/// main gets an AI commit; feature gets the same change through a cherry-pick git-ai never
+/// saw, so the file is identical on both branches but only main attributes the AI line.
+/// `-x` keeps the cherry-pick from reproducing main's commit byte for byte.
+fn diverged_branches() -> (TestRepo, String) {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("file.txt");
+    file.set_contents(lines!["Line 1", "Line 2", "Line 3"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    let main_branch = repo.current_branch();
+    repo.git(&["branch", "feature"]).unwrap();
+
+    file.insert_at(1, lines!["AI line".ai()]);
+    repo.stage_all_and_commit("AI change").unwrap();
+
+    repo.git(&["checkout", "feature"]).unwrap();
+    repo.git_og(&["cherry-pick", "-x", &main_branch]).unwrap();
+    (repo, main_branch)
+}
+
+#[test]
+fn test_cross_check_reports_divergent_attribution() {
+    let (repo, main_branch) = diverged_branches();
+
+    let result = repo.git_ai(&["cross-check", &main_branch, "feature"]);
+    assert!(result.is_err(), "divergence should fail the check");
+
+    let clean = repo
+        .git_ai(&["cross-check", &main_branch, &main_branch])
+        .unwrap();
+    assert!(clean.contains("No divergent attribution"), "{}", clean);
+}
+
+#[test]
+fn test_cross_check_repair_copies_attribution() {
+    let (repo, main_branch) = diverged_branches();
+
+    let output = repo
+        .git_ai(&["cross-check", &main_branch, "feature", "--repair"])
+        .unwrap();
+    assert!(output.contains("file.txt:2"), "{}", output);
+    assert!(
+        output.contains("Repaired 1 line(s) on feature (1 commit note(s) updated)"),
+        "{}",
+        output
+    );
+
+    let output = repo
+        .git_ai(&["cross-check", &main_branch, "feature"])
+        .unwrap();
+    assert!(output.contains("No divergent attribution"), "{}", output);
+
+    let mut file = repo.filename("file.txt");
+    file.assert_lines_and_blame(lines![
+        "Line 1".human(),
+        "AI line".ai(),
+        "Line 2".human(),
+        "Line 3".human(),
+    ]);
+}
@@ -0,0 +1,171 @@
+#[macro_use]
+mod repos;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+use std::fs;
+
+fn commit_validation_removal(repo: &TestRepo) {
+    let mut file = repo.filename("handler.py");
+    file.set_contents(lines![
+        "def handle(request):",
+        "    validate(request)",
+        "    process(request)",
+        "    log(request)"
+    ]);
+    repo.stage_all_and_commit("Add handler").unwrap();
+
+    // The agent removes the validation check...
+    let path = repo.path().join("handler.py");
+    fs::write(
+        &path,
+        "def handle(request):\n    process(request)\n    log(request)\n",
+    )
+    .unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai"]).unwrap();
+
+    // ...and the human drops the logging
+    fs::write(&path, "def handle(request):\n    process(request)\n").unwrap();
+    repo.stage_all_and_commit("Simplify handler").unwrap();
+}
+
+#[test]
+fn test_deletions_credit_ai_and_human() {
+    let repo = TestRepo::new();
+    commit_validation_removal(&repo);
+
+    let output = repo
+        .git_ai(&["deletions", "--json", "HEAD~1..HEAD"])
+        .unwrap();
+    let deleted: serde_json::Value = serde_json::from_str(&output).unwrap();
+    let deleted = deleted.as_array().unwrap();
+    assert_eq!(deleted.len(), 2, "{}", output);
+
+    assert_eq!(deleted[0]["line"], 2);
+    assert_eq!(deleted[0]["content"], "    validate(request)");
+    assert_eq!(deleted[0]["ai"], true);
+    assert_eq!(deleted[0]["tool"], "mock_ai");
+
+    assert_eq!(deleted[1]["line"], 4);
+    assert_eq!(deleted[1]["ai"], false);
+    assert_eq!(deleted[1]["tool"], serde_json::Value::Null);
+
+    let ai_only = repo
+        .git_ai(&["deletions", "--json", "--ai-only", "HEAD~1..HEAD"])
+        .unwrap();
+    let ai_only: serde_json::Value = serde_json::from_str(&ai_only).unwrap();
+    assert_eq!(ai_only.as_array().unwrap().len(), 1);
+}
+
+#[test]
+fn test_blame_show_deleted_lists_deleting_tool() {
+    let repo = TestRepo::new();
+    commit_validation_removal(&repo);
+
+    let output = repo
+        .git_ai(&["blame", "--show-deleted", "handler.py"])
+        .unwrap();
+    let deleted_section = output
+        .split("Deleted lines:")
+        .nth(1)
+        .expect("blame should list deleted lines");
+    let validate_line = deleted_section
+        .lines()
+        .find(|line| line.contains("validate(request)"))
+        .expect("the removed validation should be listed");
+    assert!(validate_line.contains("mock_ai"), "{}", validate_line);
+    let log_line = deleted_section
+        .lines()
+        .find(|line| line.contains("log(request)"))
+        .expect("the removed logging should be listed");
+    assert!(!log_line.contains("mock_ai"), "{}", log_line);
+
+    // Deletions don't change who wrote the remaining lines
+    let mut file = repo.filename("handler.py");
+    file.assert_lines_and_blame(lines![
+        "def handle(request):".human(),
+        "    process(request)".human()
+    ]);
+}
+
+const HANDLER: &str =
+    "import os\nimport sys\n\ndef handle(request):\n    validate(request)\n    process(request)\n";
+
+/// Commit `HANDLER`, then an AI edit that drops the validation (line 5) and adds an audit
+/// call on a `feature` branch. Returns the default branch name.
+fn commit_ai_validation_removal_on_feature(repo: &TestRepo) -> String {
+    let path = repo.path().join("handler.py");
+    fs::write(&path, HANDLER).unwrap();
+    repo.stage_all_and_commit("Add handler").unwrap();
+    let default_branch = repo.current_branch();
+
+    repo.git(&["checkout", "-b", "feature"]).unwrap();
+    fs::write(
+        &path,
+        "import os\nimport sys\n\ndef handle(request):\n    process(request)\n    audit(request)\n",
+    )
+    .unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai"]).unwrap();
+    repo.stage_all_and_commit("Drop validation").unwrap();
+    default_branch
+}
+
+fn ai_deleted_lines(repo: &TestRepo) -> Vec<(u64, String)> {
+    let output = repo
+        .git_ai(&["deletions", "--json", "--ai-only", "HEAD~1..HEAD"])
+        .unwrap();
+    let deleted: serde_json::Value = serde_json::from_str(&output).unwrap();
+    deleted
+        .as_array()
+        .unwrap()
+        .iter()
+        .map(|line| {
+            (
+                line["line"].as_u64().unwrap(),
+                line["content"].as_str().unwrap().to_string(),
+            )
+        })
+        .collect()
+}
+
+#[test]
+fn test_deletions_survive_amend() {
+    let repo = TestRepo::new();
+    commit_ai_validation_removal_on_feature(&repo);
+
+    repo.git(&["commit", "--amend", "-m", "Drop the validation step"])
+        .unwrap();
+    assert_eq!(
+        ai_deleted_lines(&repo),
+        vec![(5, "    validate(request)".to_string())]
+    );
+}
+
+#[test]
+fn test_deletions_follow_rebase_and_cherry_pick() {
+    let repo = TestRepo::new();
+    let default_branch = commit_ai_validation_removal_on_feature(&repo);
+    let feature_commit = repo.git(&["rev-parse", "HEAD"]).unwrap().trim().to_string();
+
+    // Upstream adds a header, shifting the deleted line down by two
+    repo.git(&["checkout", &default_branch]).unwrap();
+    fs::write(
+        repo.path().join("handler.py"),
+        format!("# Request handling\n\n{}", HANDLER),
+    )
+    .unwrap();
+    repo.stage_all_and_commit("Add header").unwrap();
+
+    repo.git(&["checkout", "-b", "picked"]).unwrap();
+    repo.git(&["cherry-pick", &feature_commit]).unwrap();
+    assert_eq!(
+        ai_deleted_lines(&repo),
+        vec![(7, "    validate(request)".to_string())]
+    );
+
+    repo.git(&["checkout", "feature"]).unwrap();
+    repo.git(&["rebase", &default_branch]).unwrap();
+    assert_eq!(
+        ai_deleted_lines(&repo),
+        vec![(7, "    validate(request)".to_string())]
+    );
+}
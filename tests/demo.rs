@@ -0,0 +1,54 @@
+mod repos;
+use repos::test_repo::TestRepo;
+use std::path::Path;
+use std::process::Command;
+
+fn git_stdout(dir: &Path, args: &[&str]) -> String {
+    let output = Command::new("git")
+        .arg("-C")
+        .arg(dir)
+        .args(args)
+        .output()
+        .expect("git command should run");
+    assert!(
+        output.status.success(),
+        "git {:?} failed: {}",
+        args,
+        String::from_utf8_lossy(&output.stderr)
+    );
+    String::from_utf8_lossy(&output.stdout).to_string()
+}
+
+#[test]
+fn test_demo_create_builds_attributed_history() {
+    let repo = TestRepo::new();
+    let sandbox = tempfile::tempdir().unwrap();
+    let demo_dir = sandbox.path().join("demo");
+
+    repo.git_ai(&["demo", "create", demo_dir.to_str().unwrap()])
+        .expect("demo create should succeed");
+
+    let commits = git_stdout(&demo_dir, &["log", "--format=%H %an"]);
+    assert_eq!(commits.lines().count(), 5);
+    assert!(commits.contains("Alice Example"));
+    assert!(commits.contains("Bob Example"));
+
+    // The cursor commit carries an authorship note attributing its lines to the agent
+    let note = git_stdout(&demo_dir, &["notes", "--ref=ai", "show", "HEAD~3"]);
+    assert!(note.contains("src/inventory.py"), "{}", note);
+    assert!(note.contains("\"tool\": \"cursor\""), "{}", note);
+
+    let note = git_stdout(&demo_dir, &["notes", "--ref=ai", "show", "HEAD~1"]);
+    assert!(note.contains("\"tool\": \"codex\""), "{}", note);
+}
+
+#[test]
+fn test_demo_create_refuses_non_empty_directory() {
+    let repo = TestRepo::new();
+    let sandbox = tempfile::tempdir().unwrap();
+    std::fs::write(sandbox.path().join("keep.txt"), "data").unwrap();
+
+    let result = repo.git_ai(&["demo", "create", sandbox.path().to_str().unwrap()]);
+    assert!(result.is_err());
+    assert!(sandbox.path().join("keep.txt").exists());
+}
@@ -0,0 +1,173 @@
+//! Benchmark comparing the line diff algorithms available for attribution.
+//!
+//! Measures both speed and churn (lines reported as deleted and re-inserted even though
+//! they did not change) on code with many near-identical blocks, which is where Myers
+//! tends to slide hunks across repeated lines and shift attribution onto the wrong author.
+//! The churn comparison always runs; the timings are printed by the ignored benchmark.
+//!
+//! Run with: cargo test test_diff_algorithm_benchmark --release -- --nocapture --ignored
+
+use git_ai::authorship::imara_diff_utils::{
+    DiffAlgorithm, LineChangeTag, compute_line_changes_with,
+};
+use std::time::{Duration, Instant};
+
+const ALGORITHMS: [DiffAlgorithm; 3] = [
+    DiffAlgorithm::Myers,
+    DiffAlgorithm::Histogram,
+    DiffAlgorithm::Patience,
+];
+
+/// Generate a file of `blocks` similar handler functions, the kind of repetitive code
+/// (match arms, test cases, route handlers) that confuses position-based diffs.
+fn generate_repeated_blocks(blocks: usize) -> String {
+    let mut text = String::new();
+    for i in 0..blocks {
+        text.push_str(&format!("fn handle_{}(req: &Request) -> Response {{\n", i));
+        text.push_str("    let user = req.user()?;\n");
+        text.push_str("    if !user.is_authorized() {\n");
+        text.push_str("        return Response::forbidden();\n");
+        text.push_str("    }\n");
+        text.push_str("    let body = req.body();\n");
+        text.push_str("    Response::ok(body)\n");
+        text.push_str("}\n\n");
+    }
+    text
+}
+
+/// Apply edits typical of an agent session: insert new handlers between existing ones
+/// and tweak a line inside some of the existing ones.
+fn edit_repeated_blocks(original: &str, every: usize) -> (String, usize) {
+    let mut edited = String::new();
+    let mut inserted_lines = 0;
+    for (i, block) in original.split_inclusive("}\n\n").enumerate() {
+        if i % every == 0 {
+            let new_block = format!(
+                "fn handle_new_{}(req: &Request) -> Response {{\n    let user = req.user()?;\n    if !user.is_authorized() {{\n        return Response::forbidden();\n    }}\n    Response::ok(req.body())\n}}\n\n",
+                i
+            );
+            inserted_lines += new_block.lines().count();
+            edited.push_str(&new_block);
+        }
+        edited.push_str(block);
+    }
+    (edited, inserted_lines)
+}
+
+struct AlgorithmResult {
+    algorithm: DiffAlgorithm,
+    average: Duration,
+    inserted: usize,
+    deleted: usize,
+}
+
+impl AlgorithmResult {
+    /// The edits are pure insertions: any deleted line, and any insert beyond the new
+    /// blocks, is churn.
+    fn churn(&self, expected_inserted: usize) -> usize {
+        self.deleted + self.inserted.saturating_sub(expected_inserted)
+    }
+}
+
+fn run_algorithm(
+    algorithm: DiffAlgorithm,
+    old: &str,
+    new: &str,
+    iterations: usize,
+) -> AlgorithmResult {
+    let mut total = Duration::ZERO;
+    let mut inserted = 0;
+    let mut deleted = 0;
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let changes = compute_line_changes_with(old, new, algorithm);
+        total += start.elapsed();
+        inserted = changes
+            .iter()
+            .filter(|c| *c.tag() == LineChangeTag::Insert)
+            .count();
+        deleted = changes
+            .iter()
+            .filter(|c| *c.tag() == LineChangeTag::Delete)
+            .count();
+    }
+    let result = AlgorithmResult {
+        algorithm,
+        average: total / iterations as u32,
+        inserted,
+        deleted,
+    };
+    assert_eq!(
+        result.inserted - result.deleted,
+        new.lines().count() - old.lines().count(),
+        "{} produced an inconsistent diff",
+        algorithm.as_str()
+    );
+    result
+}
+
+#[test]
+fn test_default_diff_algorithm_has_least_churn_on_repeated_blocks() {
+    // Large enough for Myers to slide hunks across the repeats
+    let old = generate_repeated_blocks(2000);
+    let (new, expected_inserted) = edit_repeated_blocks(&old, 7);
+
+    let churn: Vec<(DiffAlgorithm, usize)> = ALGORITHMS
+        .into_iter()
+        .map(|algorithm| {
+            let result = run_algorithm(algorithm, &old, &new, 1);
+            (algorithm, result.churn(expected_inserted))
+        })
+        .collect();
+    let default_churn = churn
+        .iter()
+        .find(|(algorithm, _)| *algorithm == DiffAlgorithm::default())
+        .map(|(_, churn)| *churn)
+        .unwrap();
+    let myers_churn = churn[0].1;
+    assert!(
+        default_churn < myers_churn,
+        "default {} churns {} lines, myers {}",
+        DiffAlgorithm::default().as_str(),
+        default_churn,
+        myers_churn
+    );
+    for (algorithm, algorithm_churn) in &churn {
+        assert!(
+            default_churn <= *algorithm_churn,
+            "default {} churns {} lines, {} only {}",
+            DiffAlgorithm::default().as_str(),
+            default_churn,
+            algorithm.as_str(),
+            algorithm_churn
+        );
+    }
+}
+
+#[test]
+#[ignore]
+fn test_diff_algorithm_benchmark() {
+    let iterations = 20;
+    for blocks in [50, 500, 2000] {
+        let old = generate_repeated_blocks(blocks);
+        let (new, expected_inserted) = edit_repeated_blocks(&old, 7);
+
+        println!(
+            "\n=== {} repeated blocks ({} lines, {} lines inserted) ===",
+            blocks,
+            old.lines().count(),
+            expected_inserted
+        );
+        for algorithm in ALGORITHMS {
+            let result = run_algorithm(algorithm, &old, &new, iterations);
+            println!(
+                "  {:<10} avg {:>8.3}ms  +{:<6} -{:<6} churn {}",
+                result.algorithm.as_str(),
+                result.average.as_secs_f64() * 1000.0,
+                result.inserted,
+                result.deleted,
+                result.churn(expected_inserted)
+            );
+        }
+    }
+}
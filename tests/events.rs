@@ -0,0 +1,64 @@
+#[macro_use]
+mod repos;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+use serde_json::Value;
+
+fn journal_events(repo: &TestRepo) -> Vec<Value> {
+    let output = repo.git_ai(&["events", "--format", "json"]).unwrap();
+    output
+        .lines()
+        .filter(|line| line.starts_with('{'))
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect()
+}
+
+#[test]
+fn test_events_journal_records_checkpoints_and_commits() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("app.py");
+    file.set_contents(lines!["def main():".human(), "    pass".ai()]);
+    let commit = repo.stage_all_and_commit("Add app").unwrap();
+
+    let events = journal_events(&repo);
+    let names: Vec<&str> = events
+        .iter()
+        .map(|e| e["event"].as_str().unwrap())
+        .collect();
+    assert!(names.contains(&"checkpoint_recorded"), "{:?}", names);
+
+    let attributed = events
+        .iter()
+        .rfind(|e| e["event"] == "commit_attributed")
+        .expect("commit_attributed event");
+    assert_eq!(attributed["commit_sha"], commit.commit_sha.as_str());
+    assert_eq!(attributed["files"], serde_json::json!(["app.py"]));
+
+    let ai_checkpoint = events
+        .iter()
+        .find(|e| e["event"] == "checkpoint_recorded" && e["kind"] == "ai_agent")
+        .expect("ai checkpoint event");
+    assert!(
+        ai_checkpoint["files"]
+            .as_array()
+            .unwrap()
+            .contains(&Value::from("app.py"))
+    );
+}
+
+#[test]
+fn test_events_tail_limits_backlog() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("notes.txt");
+    file.set_contents(lines!["one".ai()]);
+    repo.stage_all_and_commit("First").unwrap();
+    file.insert_at(1, lines!["two".ai()]);
+    repo.stage_all_and_commit("Second").unwrap();
+
+    let output = repo
+        .git_ai(&["events", "--format", "json", "--tail", "1"])
+        .unwrap();
+    let lines: Vec<&str> = output.lines().filter(|l| l.starts_with('{')).collect();
+    assert_eq!(lines.len(), 1);
+    assert!(lines[0].contains("\"event\":\"commit_attributed\""));
+}
@@ -0,0 +1,42 @@
+#[macro_use]
+mod repos;
+use repos::test_repo::TestRepo;
+
+fn feature_states(output: &str, section: &str) -> Vec<serde_json::Value> {
+    let json: serde_json::Value = serde_json::from_str(output).unwrap();
+    json[section].as_array().unwrap().clone()
+}
+
+fn state<'a>(states: &'a [serde_json::Value], name: &str) -> &'a serde_json::Value {
+    states
+        .iter()
+        .find(|state| state["name"] == name)
+        .unwrap_or_else(|| panic!("no feature {}", name))
+}
+
+#[test]
+fn test_features_list_reports_build_and_runtime_flags() {
+    let repo = TestRepo::new();
+    let output = repo
+        .git_ai_with_env(
+            &["features", "list", "--json"],
+            &[("GIT_AI_CHAR_LEVEL_ATTRIBUTION", "false")],
+        )
+        .unwrap();
+
+    let compiled = feature_states(&output, "compiled");
+    assert_eq!(state(&compiled, "tui")["enabled"], cfg!(feature = "tui"));
+    assert_eq!(state(&compiled, "keyring")["default"], false);
+
+    let runtime = feature_states(&output, "runtime");
+    let char_level = state(&runtime, "char_level_attribution");
+    assert_eq!(char_level["enabled"], false);
+    assert_eq!(char_level["default"], true);
+
+    let text = repo.git_ai(&["features", "list"]).unwrap();
+    assert!(text.contains("Compiled features:"), "{}", text);
+    assert!(text.contains("char_level_attribution"), "{}", text);
+
+    let err = repo.git_ai(&["features", "enable", "tui"]).unwrap_err();
+    assert!(err.contains("Usage: git-ai features list"), "{}", err);
+}
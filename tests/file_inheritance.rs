@@ -0,0 +1,144 @@
+#[macro_use]
+mod repos;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+
+#[test]
+fn test_file_split_inherits_ai_attribution() {
+    let repo = TestRepo::new();
+    let mut big = repo.filename("big.py");
+    big.set_contents(lines![
+        "# helpers",
+        "def alpha():".ai(),
+        "    return 'alpha'".ai(),
+        "",
+        "def beta():".ai(),
+        "    return 'beta'".ai(),
+    ]);
+    repo.stage_all_and_commit("Add helpers").unwrap();
+
+    // A human splits the file in an editor without any AI checkpoint
+    std::fs::write(
+        repo.path().join("alpha.py"),
+        "# alpha\ndef alpha():\n    return 'alpha'\n",
+    )
+    .unwrap();
+    std::fs::write(
+        repo.path().join("beta.py"),
+        "# beta\ndef beta():\n    return 'beta'\n",
+    )
+    .unwrap();
+    std::fs::remove_file(repo.path().join("big.py")).unwrap();
+    repo.stage_all_and_commit("Split helpers").unwrap();
+
+    let mut alpha = repo.filename("alpha.py");
+    alpha.assert_lines_and_blame(lines![
+        "# alpha".human(),
+        "def alpha():".ai(),
+        "    return 'alpha'".ai(),
+    ]);
+    let mut beta = repo.filename("beta.py");
+    beta.assert_lines_and_blame(lines![
+        "# beta".human(),
+        "def beta():".ai(),
+        "    return 'beta'".ai(),
+    ]);
+}
+
+#[test]
+fn test_extracted_block_inherits_ai_attribution() {
+    let repo = TestRepo::new();
+    let mut main = repo.filename("main.py");
+    main.set_contents(lines![
+        "import os".human(),
+        "def parse(value):".ai(),
+        "    return value.strip()".ai(),
+        "print(parse(os.getcwd()))".human(),
+    ]);
+    repo.stage_all_and_commit("Add main").unwrap();
+
+    // Extract parse() into its own module; main.py stays behind
+    std::fs::write(
+        repo.path().join("parsing.py"),
+        "def parse(value):\n    return value.strip()\n",
+    )
+    .unwrap();
+    std::fs::write(
+        repo.path().join("main.py"),
+        "import os\nfrom parsing import parse\nprint(parse(os.getcwd()))",
+    )
+    .unwrap();
+    repo.stage_all_and_commit("Extract parsing").unwrap();
+
+    let mut parsing = repo.filename("parsing.py");
+    parsing.assert_lines_and_blame(lines![
+        "def parse(value):".ai(),
+        "    return value.strip()".ai(),
+    ]);
+}
+
+#[test]
+fn test_file_merge_inherits_ai_and_human_attribution() {
+    let repo = TestRepo::new();
+    let mut first = repo.filename("first.py");
+    first.set_contents(lines!["def first():".ai(), "    return 'first'".ai(),]);
+    let mut second = repo.filename("second.py");
+    second.set_contents(lines![
+        "def second():".human(),
+        "    return 'second'".human(),
+    ]);
+    let mut third = repo.filename("third.py");
+    third.set_contents(lines!["def third():".ai(), "    return 'third'".ai(),]);
+    repo.stage_all_and_commit("Add modules").unwrap();
+
+    // A human concatenates the three modules into one
+    std::fs::write(
+        repo.path().join("combined.py"),
+        "def first():\n    return 'first'\ndef second():\n    return 'second'\ndef third():\n    return 'third'\n",
+    )
+    .unwrap();
+    for name in ["first.py", "second.py", "third.py"] {
+        std::fs::remove_file(repo.path().join(name)).unwrap();
+    }
+    repo.stage_all_and_commit("Merge modules").unwrap();
+
+    let mut combined = repo.filename("combined.py");
+    combined.assert_lines_and_blame(lines![
+        "def first():".ai(),
+        "    return 'first'".ai(),
+        "def second():".human(),
+        "    return 'second'".human(),
+        "def third():".ai(),
+        "    return 'third'".ai(),
+    ]);
+}
+
+#[test]
+fn test_file_folded_into_existing_file_inherits_ai_attribution() {
+    let repo = TestRepo::new();
+    let mut main = repo.filename("main.py");
+    main.set_contents(lines!["import sys".human(), "print(sys.argv)".human()]);
+    let mut util = repo.filename("util.py");
+    util.set_contents(lines![
+        "def shout(text):".ai(),
+        "    return text.upper()".ai(),
+    ]);
+    repo.stage_all_and_commit("Add main and util").unwrap();
+
+    // util.py is folded into main.py, which already existed
+    std::fs::write(
+        repo.path().join("main.py"),
+        "import sys\ndef shout(text):\n    return text.upper()\nprint(sys.argv)",
+    )
+    .unwrap();
+    std::fs::remove_file(repo.path().join("util.py")).unwrap();
+    repo.stage_all_and_commit("Fold util into main").unwrap();
+
+    let mut main = repo.filename("main.py");
+    main.assert_lines_and_blame(lines![
+        "import sys".human(),
+        "def shout(text):".ai(),
+        "    return text.upper()".ai(),
+        "print(sys.argv)".human(),
+    ]);
+}
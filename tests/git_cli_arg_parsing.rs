@@ -911,3 +911,25 @@ fn pull_verbose_flag_not_treated_as_version() {
     assert_eq!(got.command.as_deref(), Some("pull"));
     assert_eq!(got.command_args, s(&["-v"]));
 }
+
+#[test]
+fn no_ai_hooks_flag_is_consumed_and_not_passed_to_git() {
+    let args = s(&["--no-ai-hooks", "-C", "..", "commit", "-m", "foo"]);
+    let got = parse_git_cli_args(&args);
+    assert!(got.no_ai_hooks);
+    assert_eq!(got.global_args, s(&["-C", ".."]));
+    assert_eq!(got.command, Some("commit".into()));
+    assert_eq!(
+        got.to_invocation_vec(),
+        s(&["-C", "..", "commit", "-m", "foo"])
+    );
+}
+
+#[test]
+fn no_ai_hooks_after_command_is_a_command_arg() {
+    let got = parse_git_cli_args(&s(&["commit", "--no-ai-hooks"]));
+    assert!(!got.no_ai_hooks);
+    assert_eq!(got.command_args, s(&["--no-ai-hooks"]));
+
+    assert!(!parse_git_cli_args(&s(&["status"])).no_ai_hooks);
+}
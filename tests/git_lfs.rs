@@ -0,0 +1,86 @@
+#[macro_use]
+mod repos;
+use git_ai::authorship::stats::CommitStats;
+use repos::test_repo::TestRepo;
+use std::fs;
+
+const LFS_POINTER: &str = "version https://git-lfs.github.com/spec/v1\noid sha256:4d7a214614ab2935c943f9e0ff69d22eadbb8f32b1258daaa5e2ca24d17e2393\nsize 3221225472\n";
+
+fn extract_json_object(output: &str) -> String {
+    let start = output.find('{').unwrap_or(0);
+    let end = output.rfind('}').unwrap_or(output.len().saturating_sub(1));
+    output[start..=end].to_string()
+}
+
+fn head_stats(repo: &TestRepo) -> CommitStats {
+    let raw = repo.git_ai(&["stats", "HEAD", "--json"]).unwrap();
+    serde_json::from_str(&extract_json_object(&raw)).unwrap()
+}
+
+fn init_repo_with_lfs_attributes() -> TestRepo {
+    let repo = TestRepo::new();
+    fs::write(
+        repo.path().join(".gitattributes"),
+        "*.psd filter=lfs diff=lfs merge=lfs -text\n",
+    )
+    .unwrap();
+    repo.filename("README.md").set_contents(lines!["# Project"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    repo
+}
+
+#[test]
+fn test_lfs_tracked_pointer_is_not_attributed_as_code() {
+    let repo = init_repo_with_lfs_attributes();
+
+    fs::write(repo.path().join("cover.psd"), LFS_POINTER).unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai", "cover.psd"])
+        .unwrap();
+
+    let commit = repo.stage_all_and_commit("Add cover art").unwrap();
+    assert!(
+        commit.authorship_log.attestations.is_empty(),
+        "LFS pointer lines must not be attributed: {:?}",
+        commit.authorship_log.attestations
+    );
+
+    let stats = head_stats(&repo);
+    assert_eq!(stats.git_diff_added_lines, 0);
+    assert_eq!(stats.ai_additions, 0);
+}
+
+#[test]
+fn test_pointer_content_without_lfs_attributes_is_not_attributed() {
+    let repo = TestRepo::new();
+    repo.filename("README.md").set_contents(lines!["# Project"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    fs::write(repo.path().join("weights.bin"), LFS_POINTER).unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai", "weights.bin"])
+        .unwrap();
+
+    let commit = repo.stage_all_and_commit("Add weights").unwrap();
+    assert!(commit.authorship_log.attestations.is_empty());
+}
+
+#[test]
+fn test_lfs_asset_can_be_attributed_at_file_granularity() {
+    let repo = init_repo_with_lfs_attributes();
+
+    fs::write(repo.path().join("cover.psd"), LFS_POINTER).unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai", "--whole-file", "cover.psd"])
+        .unwrap();
+
+    let commit = repo.stage_all_and_commit("Add cover art").unwrap();
+    assert!(commit.authorship_log.attestations.is_empty());
+    assert!(
+        commit
+            .authorship_log
+            .metadata
+            .assets
+            .contains_key("cover.psd")
+    );
+
+    let stats = head_stats(&repo);
+    assert_eq!(stats.ai_generated_assets, vec!["cover.psd".to_string()]);
+}
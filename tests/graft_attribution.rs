@@ -0,0 +1,69 @@
+#[macro_use]
+mod repos;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+use std::fs;
+
+/// main writes app.txt with an AI line; `split` recreates it from a new root under lib/,
+/// committed without git-ai, so the file keeps its blob but loses its note.
+fn split_history() -> (TestRepo, String) {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("app.txt");
+    file.set_contents(lines!["Line 1", "AI line".ai(), "Line 3"]);
+    repo.stage_all_and_commit("Add app").unwrap();
+    let main_branch = repo.current_branch();
+
+    repo.git_og(&["checkout", "--orphan", "split"]).unwrap();
+    fs::create_dir_all(repo.path().join("lib")).unwrap();
+    fs::rename(repo.path().join("app.txt"), repo.path().join("lib/app.txt")).unwrap();
+    repo.git_og(&["add", "-A"]).unwrap();
+    repo.git_og(&["commit", "-m", "Split into lib"]).unwrap();
+    (repo, main_branch)
+}
+
+#[test]
+fn test_graft_attribution_matches_blobs_across_paths() {
+    let (repo, main_branch) = split_history();
+
+    let mut file = repo.filename("lib/app.txt");
+    file.assert_lines_and_blame(lines![
+        "Line 1".human(),
+        "AI line".human(),
+        "Line 3".human()
+    ]);
+
+    let output = repo
+        .git_ai(&["graft-attribution", "--from", &main_branch])
+        .unwrap();
+    assert!(output.contains("Grafted 1 AI lines onto"), "{}", output);
+    assert!(
+        output.contains("lib/app.txt (was app.txt): 1 lines"),
+        "{}",
+        output
+    );
+
+    file.assert_lines_and_blame(lines!["Line 1".human(), "AI line".ai(), "Line 3".human()]);
+
+    // The recreated commit now has a note, so a second run has nothing to do
+    let output = repo
+        .git_ai(&["graft-attribution", "--from", &main_branch])
+        .unwrap();
+    assert!(output.contains("No commit on HEAD"), "{}", output);
+}
+
+#[test]
+fn test_graft_attribution_dry_run_writes_nothing() {
+    let (repo, main_branch) = split_history();
+
+    let output = repo
+        .git_ai(&["graft-attribution", "--from", &main_branch, "--dry-run"])
+        .unwrap();
+    assert!(output.contains("Would graft 1 AI lines onto"), "{}", output);
+
+    let mut file = repo.filename("lib/app.txt");
+    file.assert_lines_and_blame(lines![
+        "Line 1".human(),
+        "AI line".human(),
+        "Line 3".human()
+    ]);
+}
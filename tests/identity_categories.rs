@@ -0,0 +1,154 @@
+#[macro_use]
+mod repos;
+use git_ai::authorship::stats::CommitStats;
+use repos::test_repo::TestRepo;
+use std::collections::BTreeMap;
+
+fn repo_with_categories(categories: &[(&str, &str)]) -> TestRepo {
+    let mut repo = TestRepo::new();
+    let categories: BTreeMap<String, String> = categories
+        .iter()
+        .map(|(pattern, category)| (pattern.to_string(), category.to_string()))
+        .collect();
+    repo.patch_git_ai_config(|patch| {
+        patch.identity_categories = Some(categories);
+    });
+    let mut file = repo.filename("package.json");
+    file.set_contents(lines!["{", "}"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    repo
+}
+
+/// Commit the working tree as `name <email>`, the way a CI bot would.
+fn commit_as(repo: &TestRepo, name: &str, email: &str, message: &str) {
+    repo.git(&["add", "-A"]).unwrap();
+    let author = format!("{} <{}>", name, email);
+    repo.git(&["commit", "--author", &author, "-m", message])
+        .unwrap();
+}
+
+fn head_stats(repo: &TestRepo) -> CommitStats {
+    let output = repo.git_ai(&["stats", "HEAD", "--json"]).unwrap();
+    let start = output.find('{').unwrap();
+    let end = output.rfind('}').unwrap();
+    serde_json::from_str(&output[start..=end]).unwrap()
+}
+
+#[test]
+fn test_bot_commits_are_not_counted_as_human() {
+    let repo = repo_with_categories(&[("dependabot*", "bot")]);
+    let mut file = repo.filename("package.json");
+    file.insert_at(1, lines!["  \"left-pad\": \"1.3.0\","]);
+    commit_as(
+        &repo,
+        "dependabot[bot]",
+        "49699333+dependabot[bot]@users.noreply.github.com",
+        "Bump left-pad",
+    );
+
+    let stats = head_stats(&repo);
+    assert_eq!(stats.identity_category.as_deref(), Some("bot"));
+    assert_eq!(stats.bot_additions, 1);
+    assert_eq!(stats.human_additions, 0);
+    assert_eq!(stats.ai_additions, 0);
+
+    let output = repo.git_ai(&["stats", "HEAD"]).unwrap();
+    assert!(
+        output.contains("1 lines committed by a bot identity"),
+        "{}",
+        output
+    );
+}
+
+#[test]
+fn test_ai_identity_commits_count_as_that_tool() {
+    let repo = repo_with_categories(&[("*@agents.example.com", "ai")]);
+    let mut file = repo.filename("package.json");
+    file.insert_at(1, lines!["  \"name\": \"demo\","]);
+    commit_as(
+        &repo,
+        "Fix Agent",
+        "fixer@agents.example.com",
+        "Agent change",
+    );
+
+    let stats = head_stats(&repo);
+    assert_eq!(stats.identity_category.as_deref(), Some("ai"));
+    assert_eq!(stats.ai_accepted, 1);
+    assert_eq!(stats.human_additions, 0);
+    assert!(
+        stats
+            .tool_model_breakdown
+            .keys()
+            .any(|key| key.contains("Fix Agent")),
+        "{:?}",
+        stats.tool_model_breakdown
+    );
+}
+
+#[test]
+fn test_unmatched_identities_keep_their_stats() {
+    let repo = repo_with_categories(&[("dependabot*", "bot")]);
+    let mut file = repo.filename("package.json");
+    file.insert_at(1, lines!["  \"private\": true,"]);
+    repo.stage_all_and_commit("Human change").unwrap();
+
+    let stats = head_stats(&repo);
+    assert_eq!(stats.identity_category, None);
+    assert_eq!(stats.human_additions, 1);
+    assert_eq!(stats.bot_additions, 0);
+}
+
+#[test]
+fn test_range_stats_classify_each_commit() {
+    let repo = repo_with_categories(&[("dependabot*", "bot")]);
+    let mut file = repo.filename("package.json");
+    file.insert_at(1, lines!["  \"private\": true,"]);
+    repo.stage_all_and_commit("Human change").unwrap();
+    file.insert_at(
+        2,
+        lines!["  \"left-pad\": \"1.3.0\",", "  \"is-odd\": \"3.0.1\","],
+    );
+    commit_as(
+        &repo,
+        "dependabot[bot]",
+        "49699333+dependabot[bot]@users.noreply.github.com",
+        "Bump dependencies",
+    );
+
+    let output = repo.git_ai(&["stats", "HEAD~2..HEAD", "--json"]).unwrap();
+    let start = output.find('{').unwrap();
+    let end = output.rfind('}').unwrap();
+    let stats: serde_json::Value = serde_json::from_str(&output[start..=end]).unwrap();
+    let range_stats: CommitStats = serde_json::from_value(stats["range_stats"].clone()).unwrap();
+    assert_eq!(range_stats.git_diff_added_lines, 3);
+    assert_eq!(range_stats.bot_additions, 2);
+    assert_eq!(range_stats.human_additions, 1);
+    assert_eq!(range_stats.ai_additions, 0);
+}
+
+#[test]
+fn test_human_commit_committed_by_bot_stays_human() {
+    let repo = repo_with_categories(&[("github-actions*", "bot"), ("*web-flow*", "bot")]);
+    let mut file = repo.filename("package.json");
+    file.insert_at(1, lines!["  \"private\": true,"]);
+    repo.git(&["add", "-A"]).unwrap();
+    // A bot rebasing or merging a human's change becomes the committer
+    repo.git_with_env(
+        &["commit", "-m", "Human change"],
+        &[
+            ("GIT_COMMITTER_NAME", "github-actions[bot]"),
+            (
+                "GIT_COMMITTER_EMAIL",
+                "41898282+github-actions[bot]@users.noreply.github.com",
+            ),
+        ],
+        None,
+    )
+    .unwrap();
+
+    let stats = head_stats(&repo);
+    assert_eq!(stats.identity_category, None);
+    assert_eq!(stats.human_additions, 1);
+    assert_eq!(stats.bot_additions, 0);
+}
@@ -1,3 +1,4 @@
+#![cfg(feature = "jetbrains-download")]
 /// Tests for JetBrains plugin download and installation functionality
 use git_ai::mdm::jetbrains::download::{
     download_plugin_from_marketplace, install_plugin_to_directory, install_plugin_via_cli,
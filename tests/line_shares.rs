@@ -0,0 +1,123 @@
+#[macro_use]
+mod repos;
+use git_ai::authorship::authorship_log_serialization::AuthorshipLog;
+use repos::test_repo::TestRepo;
+use std::fs;
+
+/// The human writes a skeleton, the agent fills in the body, and the human renames the call
+/// the agent wrote, leaving the body line split between them.
+fn commit_pair_programmed_line(repo: &TestRepo) -> AuthorshipLog {
+    commit_pair_programmed_line_with_env(repo, &[])
+}
+
+fn commit_pair_programmed_line_with_env(repo: &TestRepo, envs: &[(&str, &str)]) -> AuthorshipLog {
+    let path = repo.path().join("calc.py");
+    fs::write(&path, "def total(items):\n    pass\n").unwrap();
+    repo.git_ai(&["checkpoint"]).unwrap();
+
+    fs::write(
+        &path,
+        "def total(items):\n    return compute_sum(items, start=0)\n",
+    )
+    .unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai"]).unwrap();
+
+    fs::write(&path, "def total(items):\n    return sum(items, start=0)\n").unwrap();
+    repo.git_ai(&["checkpoint"]).unwrap();
+
+    repo.git(&["add", "-A"]).unwrap();
+    repo.commit_with_env("Add total", envs, None)
+        .unwrap()
+        .authorship_log
+}
+
+fn blame_records(repo: &TestRepo, extra: &[&str]) -> Vec<serde_json::Value> {
+    let mut args = vec!["blame", "--format", "json"];
+    args.extend_from_slice(extra);
+    args.push("calc.py");
+    let output = repo.git_ai(&args).unwrap();
+    serde_json::from_str::<serde_json::Value>(&output)
+        .unwrap()
+        .as_array()
+        .unwrap()
+        .clone()
+}
+
+#[test]
+fn test_shared_line_records_ai_percentage() {
+    let repo = TestRepo::new();
+    let log = commit_pair_programmed_line(&repo);
+    let shares = log
+        .metadata
+        .line_shares
+        .get("calc.py")
+        .expect("line shares");
+    let line_two = shares.get(&2).expect("the body line is shared");
+    assert_eq!(line_two.len(), 1);
+    let ai_share = *line_two.values().next().unwrap();
+    assert!(ai_share > 50 && ai_share < 100, "ai share {}", ai_share);
+    assert!(!shares.contains_key(&1), "the skeleton is human only");
+
+    let records = blame_records(&repo, &[]);
+    assert_eq!(records[0]["ai_share"], 0);
+    assert_eq!(records[1]["ai_share"], ai_share);
+}
+
+#[test]
+fn test_dominant_author_rule_picks_shared_line_owner() {
+    let repo = TestRepo::new();
+    commit_pair_programmed_line(&repo);
+
+    // The human edited the line last...
+    let latest = blame_records(&repo, &[]);
+    assert_eq!(latest[1]["ai_tool"], serde_json::Value::Null);
+
+    // ...but the agent wrote most of it
+    let majority = blame_records(&repo, &["--dominant-author", "majority"]);
+    assert_eq!(majority[1]["ai_tool"], "mock_ai");
+    assert_eq!(majority[0]["ai_tool"], serde_json::Value::Null);
+
+    let err = repo
+        .git_ai(&["blame", "--dominant-author=loudest", "calc.py"])
+        .unwrap_err();
+    assert!(err.contains("--dominant-author"), "{}", err);
+}
+
+#[test]
+fn test_char_level_attribution_flag_disables_line_shares() {
+    let repo = TestRepo::new();
+    let log =
+        commit_pair_programmed_line_with_env(&repo, &[("GIT_AI_CHAR_LEVEL_ATTRIBUTION", "false")]);
+    assert!(log.metadata.line_shares.is_empty());
+
+    // Without the split, the line belongs to whoever edited it last
+    let records = blame_records(&repo, &[]);
+    assert_eq!(records[1]["ai_share"], 0);
+}
+
+#[test]
+fn test_shared_lines_survive_amend_and_rebase() {
+    let repo = TestRepo::new();
+    fs::write(repo.path().join("README.md"), "# Calc\n").unwrap();
+    repo.stage_all_and_commit("Initial").unwrap();
+    let default_branch = repo.current_branch();
+
+    repo.git(&["checkout", "-b", "feature"]).unwrap();
+    let log = commit_pair_programmed_line(&repo);
+    let ai_share = *log.metadata.line_shares["calc.py"][&2]
+        .values()
+        .next()
+        .unwrap();
+
+    repo.git(&["commit", "--amend", "-m", "Add the total helper"])
+        .unwrap();
+    assert_eq!(blame_records(&repo, &[])[1]["ai_share"], ai_share);
+
+    repo.git(&["checkout", &default_branch]).unwrap();
+    fs::write(repo.path().join("README.md"), "# Calc\n\nSums things.\n").unwrap();
+    repo.stage_all_and_commit("Describe the project").unwrap();
+
+    repo.git(&["checkout", "feature"]).unwrap();
+    repo.git(&["rebase", &default_branch]).unwrap();
+    assert_eq!(blame_records(&repo, &[])[1]["ai_share"], ai_share);
+}
@@ -0,0 +1,119 @@
+#[macro_use]
+mod repos;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+
+#[test]
+fn test_log_and_show_patch_annotate_added_lines() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("app.py");
+    file.set_contents(lines!["def main():".human()]);
+    repo.stage_all_and_commit("Initial").unwrap();
+
+    file.set_contents(lines![
+        "def main():".human(),
+        "    print('ai')".ai(),
+        "    return 1".ai(),
+        "# done".human(),
+    ]);
+    repo.stage_all_and_commit("Add body").unwrap();
+
+    let log = repo.git_ai(&["log", "-n", "1"]).unwrap();
+    assert!(log.contains("Add body"), "log output: {}", log);
+    assert!(!log.contains("    Initial\n"), "log output: {}", log);
+    // The first line is re-added with a trailing newline, so 2 of 4 added lines are AI
+    assert!(log.contains("50% AI (mock_ai"), "log output: {}", log);
+    assert!(
+        log.contains("+    print('ai')  🤖mock_ai"),
+        "log output: {}",
+        log
+    );
+    assert!(log.contains("+# done  👤Test User"), "log output: {}", log);
+
+    let show = repo.git_ai(&["show", "--patch", "HEAD"]).unwrap();
+    assert!(show.contains("50% AI (mock_ai"), "show output: {}", show);
+    assert!(
+        show.contains("+    return 1  🤖mock_ai"),
+        "show output: {}",
+        show
+    );
+
+    // Without --patch, show keeps printing the raw authorship log
+    let raw = repo.git_ai(&["show", "HEAD"]).unwrap();
+    assert!(raw.contains("app.py"), "show output: {}", raw);
+    assert!(!raw.contains("🤖"), "show output: {}", raw);
+
+    // Path filtering limits both the commit list and the diffs
+    let filtered = repo.git_ai(&["log", "--", "missing.txt"]).unwrap();
+    assert!(!filtered.contains("Add body"), "log output: {}", filtered);
+}
+
+#[test]
+fn test_log_graph_marks_nodes_by_ai_share() {
+    let repo = TestRepo::new();
+    let mut readme = repo.filename("README.md");
+    readme.set_contents(lines!["# demo".human()]);
+    repo.stage_all_and_commit("Initial").unwrap();
+
+    repo.git(&["checkout", "-b", "agent-work"]).unwrap();
+    let mut agent = repo.filename("agent.py");
+    agent.set_contents(lines!["print('one')".ai(), "print('two')".ai()]);
+    repo.stage_all_and_commit("Agent work").unwrap();
+
+    repo.git(&["checkout", "-"]).unwrap();
+    readme.insert_at(1, lines!["Docs".human()]);
+    repo.stage_all_and_commit("Human docs").unwrap();
+    repo.git(&["merge", "--no-ff", "agent-work", "-m", "Merge agent work"])
+        .unwrap();
+
+    let graph = repo.git_ai(&["log", "--graph"]).unwrap();
+    let line_for = |subject: &str| {
+        graph
+            .lines()
+            .find(|line| line.contains(subject))
+            .unwrap_or_else(|| panic!("no {} in graph:\n{}", subject, graph))
+            .to_string()
+    };
+    let agent_line = line_for("Agent work");
+    assert!(agent_line.contains("A "), "graph:\n{}", graph);
+    assert!(
+        agent_line.contains("[100% AI (mock_ai"),
+        "graph:\n{}",
+        graph
+    );
+    assert!(line_for("Human docs").contains("H "), "graph:\n{}", graph);
+    // The branch lines of the graph are kept
+    assert!(graph.contains("|\\"), "graph:\n{}", graph);
+    assert!(!graph.contains("🤖"), "graph:\n{}", graph);
+}
+
+#[test]
+fn test_log_format_json_reports_per_commit_totals() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("app.py");
+    file.set_contents(lines!["def main():".human()]);
+    repo.stage_all_and_commit("Initial").unwrap();
+    file.insert_at(1, lines!["    return 1".ai(), "# done".human()]);
+    repo.stage_all_and_commit("Add body").unwrap();
+
+    let output = repo
+        .git_ai(&["log", "--format", "json", "-n", "1"])
+        .unwrap();
+    let commits: serde_json::Value = serde_json::from_str(output.trim()).unwrap();
+    let commits = commits.as_array().unwrap();
+    assert_eq!(commits.len(), 1);
+    let commit = &commits[0];
+    assert_eq!(commit["subject"], "Add body");
+    assert_eq!(commit["parents"].as_array().unwrap().len(), 1);
+    assert_eq!(commit["has_authorship_log"], true);
+    assert_eq!(
+        commit["ai_lines"].as_u64().unwrap() + commit["human_lines"].as_u64().unwrap(),
+        commit["added_lines"].as_u64().unwrap()
+    );
+    assert_eq!(commit["ai_lines"], 1);
+    assert_eq!(commit["agents"][0]["tool"], "mock_ai");
+    assert_eq!(commit["agents"][0]["lines"], 1);
+
+    let err = repo.git_ai(&["log", "--format", "yaml"]).unwrap_err();
+    assert!(err.contains("unsupported log format"), "{}", err);
+}
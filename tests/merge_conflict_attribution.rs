@@ -0,0 +1,96 @@
+#[macro_use]
+mod repos;
+use git_ai::git::find_repository_in_path;
+use git_ai::git::refs::get_reference_as_authorship_log_v3;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+use std::fs;
+
+/// main and feature both rewrite line 2, so merging feature into main conflicts.
+fn conflicting_branches() -> TestRepo {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("file.txt");
+    file.set_contents(lines!["Line 1", "Line 2", "Line 3"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    let main_branch = repo.current_branch();
+
+    repo.git(&["checkout", "-b", "feature"]).unwrap();
+    file.replace_at(1, "FEATURE_AI".ai());
+    repo.stage_all_and_commit("AI feature").unwrap();
+
+    repo.git(&["checkout", &main_branch]).unwrap();
+    file.replace_at(1, "MAIN_VERSION".human());
+    repo.stage_all_and_commit("Human change").unwrap();
+
+    assert!(
+        repo.git(&["merge", "feature"]).is_err(),
+        "merge should conflict"
+    );
+    repo
+}
+
+#[test]
+fn test_human_conflict_resolution_keeps_both_sides_attribution() {
+    let repo = conflicting_branches();
+
+    fs::write(
+        repo.path().join("file.txt"),
+        "Line 1\nMAIN_VERSION\nFEATURE_AI\nRESOLVED_BY_HAND\nLine 3",
+    )
+    .unwrap();
+    repo.git(&["add", "file.txt"]).unwrap();
+    repo.git(&["commit", "--no-edit"]).unwrap();
+
+    let mut file = repo.filename("file.txt");
+    file.assert_lines_and_blame(lines![
+        "Line 1".human(),
+        "MAIN_VERSION".human(),
+        "FEATURE_AI".ai(),
+        "RESOLVED_BY_HAND".human(),
+        "Line 3".human(),
+    ]);
+}
+
+#[test]
+fn test_ai_conflict_resolution_only_claims_resolved_lines() {
+    let repo = conflicting_branches();
+
+    fs::write(
+        repo.path().join("file.txt"),
+        "Line 1\nMAIN_VERSION\nFEATURE_AI\nRESOLVED_BY_AI\nLine 3",
+    )
+    .unwrap();
+    repo.git(&["add", "file.txt"]).unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai", "file.txt"]).unwrap();
+    repo.git(&["merge", "--continue"]).unwrap();
+
+    let head = repo.git(&["rev-parse", "HEAD"]).unwrap().trim().to_string();
+    let parents = repo
+        .git(&["rev-list", "--parents", "-n", "1", "HEAD"])
+        .unwrap();
+    assert_eq!(parents.split_whitespace().count(), 3, "HEAD is a merge");
+
+    // The checkpoint saw FEATURE_AI as new relative to main, but it came from the feature
+    // branch, so the merge commit only claims the line written during the resolution
+    let log = get_reference_as_authorship_log_v3(
+        &find_repository_in_path(repo.path().to_str().unwrap()).unwrap(),
+        &head,
+    )
+    .unwrap();
+    assert_eq!(log.attestations.len(), 1);
+    let lines: Vec<u32> = log.attestations[0]
+        .entries
+        .iter()
+        .flat_map(|entry| entry.line_ranges.iter().flat_map(|range| range.expand()))
+        .collect();
+    assert_eq!(lines, vec![4]);
+
+    let mut file = repo.filename("file.txt");
+    file.assert_lines_and_blame(lines![
+        "Line 1".human(),
+        "MAIN_VERSION".human(),
+        "FEATURE_AI".ai(),
+        "RESOLVED_BY_AI".ai(),
+        "Line 3".human(),
+    ]);
+}
@@ -20,6 +20,7 @@ fn make_merge_invocation(args: &[&str]) -> ParsedGitInvocation {
         command_args: args.iter().map(|s| s.to_string()).collect(),
         saw_end_of_opts: false,
         is_help: false,
+        no_ai_hooks: false,
     }
 }
 
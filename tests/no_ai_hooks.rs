@@ -0,0 +1,48 @@
+#[macro_use]
+mod repos;
+use git_ai::git::find_repository_in_path;
+use git_ai::git::refs::show_authorship_note;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+
+fn head_note(repo: &TestRepo) -> Option<String> {
+    let head = repo.git(&["rev-parse", "HEAD"]).unwrap().trim().to_string();
+    let gitai_repo = find_repository_in_path(repo.path().to_str().unwrap()).unwrap();
+    show_authorship_note(&gitai_repo, &head)
+}
+
+#[test]
+fn test_no_ai_hooks_flag_commits_without_authorship_note() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("file.txt");
+    file.set_contents(lines!["Line 1", "Line 2"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    assert!(head_note(&repo).is_some());
+
+    file.insert_at(1, lines!["AI line".ai()]);
+    repo.git(&["add", "-A"]).unwrap();
+    repo.git(&["--no-ai-hooks", "commit", "-m", "emergency fix"])
+        .unwrap();
+
+    let subject = repo.git(&["log", "-1", "--format=%s"]).unwrap();
+    assert_eq!(subject.trim(), "emergency fix");
+    assert!(head_note(&repo).is_none());
+}
+
+#[test]
+fn test_git_ai_no_hooks_env_commits_without_authorship_note() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("file.txt");
+    file.set_contents(lines!["Line 1", "Line 2"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    file.insert_at(1, lines!["AI line".ai()]);
+    repo.git(&["add", "-A"]).unwrap();
+    repo.git_with_env(
+        &["commit", "-m", "scripted commit"],
+        &[("GIT_AI_NO_HOOKS", "1")],
+        None,
+    )
+    .unwrap();
+    assert!(head_note(&repo).is_none());
+}
@@ -0,0 +1,145 @@
+#[macro_use]
+mod repos;
+
+use git_ai::authorship::authorship_log::LineRange;
+use git_ai::authorship::authorship_log_serialization::{AttestationEntry, AuthorshipLog};
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+
+fn note_on(repo: &TestRepo, commit: &str) -> AuthorshipLog {
+    let note = repo
+        .git_og(&["notes", "--ref=ai", "show", commit])
+        .expect("note should exist");
+    AuthorshipLog::deserialize_from_string(&note).unwrap()
+}
+
+/// Replace the note of `commit` on the bare `upstream`, as another clone's push would.
+fn write_upstream_note(upstream: &TestRepo, commit: &str, log: &AuthorshipLog) {
+    let path = upstream.path().join(format!("note-{}", commit));
+    std::fs::write(&path, log.serialize_to_string().unwrap()).unwrap();
+    upstream
+        .git_og(&[
+            "-c",
+            "user.name=Other Clone",
+            "-c",
+            "user.email=other@example.com",
+            "notes",
+            "--ref=ai",
+            "add",
+            "-f",
+            "-F",
+            path.to_str().unwrap(),
+            commit,
+        ])
+        .unwrap();
+}
+
+/// Attribute `line` of `file` to a new prompt of another tool in `log`.
+fn attribute_to_other_tool(log: &mut AuthorshipLog, file: &str, line: u32, prompt: &str) {
+    let mut record = log.metadata.prompts.values().next().unwrap().clone();
+    record.agent_id.tool = "other_tool".to_string();
+    log.metadata.prompts.insert(prompt.to_string(), record);
+    for attestation in &mut log.attestations {
+        for entry in &mut attestation.entries {
+            if attestation.file_path == file {
+                entry.remove_line_ranges(&[LineRange::Single(line)]);
+            }
+        }
+        attestation
+            .entries
+            .retain(|entry| !entry.line_ranges.is_empty());
+    }
+    log.get_or_create_file(file)
+        .add_entry(AttestationEntry::new(
+            prompt.to_string(),
+            vec![LineRange::Single(line)],
+        ));
+}
+
+#[test]
+fn test_reconcile_merges_pushes_and_reports_conflicts_after_offline_work() {
+    let (local, upstream) = TestRepo::new_with_remote();
+
+    let mut app = local.filename("app.rs");
+    let mut lib = local.filename("lib.rs");
+    app.set_contents(lines!["fn a() {}".ai(), "fn b() {}".ai(), "// app".human()]);
+    lib.set_contents(lines!["fn lib() {}".human(), "// lib".human()]);
+    let shared = local.stage_all_and_commit("Shared").unwrap().commit_sha;
+    let mut other = local.filename("other.rs");
+    other.set_contents(lines!["fn other() {}".ai(), "// other".human()]);
+    let disputed = local.stage_all_and_commit("Disputed").unwrap().commit_sha;
+    local.git(&["push", "origin", "HEAD:main"]).unwrap();
+
+    // Offline: no notes leave the machine
+    local.git_og(&["config", "git-ai.sync", "off"]).unwrap();
+    local
+        .git_ai(&["reassign", "app.rs", "-L", "2,2", "--to", "human"])
+        .unwrap();
+    let mut offline = local.filename("offline.rs");
+    offline.set_contents(lines!["fn offline() {}".ai(), "// offline".human()]);
+    let offline_commit = local.stage_all_and_commit("Offline").unwrap().commit_sha;
+
+    // Meanwhile another clone attributes more lines of the same commits
+    let mut remote_shared = note_on(&upstream, &shared);
+    attribute_to_other_tool(&mut remote_shared, "lib.rs", 1, "remote01");
+    write_upstream_note(&upstream, &shared, &remote_shared);
+    let mut remote_disputed = note_on(&upstream, &disputed);
+    attribute_to_other_tool(&mut remote_disputed, "other.rs", 1, "remote02");
+    write_upstream_note(&upstream, &disputed, &remote_disputed);
+
+    // Back online, the commits are pushed without their notes
+    local.git(&["push", "origin", "HEAD:main"]).unwrap();
+    local.git_og(&["fetch", "origin"]).unwrap();
+
+    let err = local
+        .git_ai(&["sync", "reconcile", "origin"])
+        .expect_err("the disputed note is a conflict");
+    assert!(
+        err.contains("other.rs lines 1: local mock_ai") && err.contains("other_tool (remote02)"),
+        "{}",
+        err
+    );
+
+    // Both sides' changes to the shared commit survive, locally and upstream
+    for repo in [&local, &upstream] {
+        let merged = note_on(repo, &shared);
+        assert!(merged.metadata.prompts.contains_key("remote01"));
+        assert_eq!(merged.metadata.reassignments["app.rs"].len(), 1);
+        let app_lines: Vec<u32> = merged
+            .attestations
+            .iter()
+            .find(|attestation| attestation.file_path == "app.rs")
+            .unwrap()
+            .entries
+            .iter()
+            .flat_map(|entry| entry.line_ranges.iter().flat_map(LineRange::expand))
+            .collect();
+        assert_eq!(app_lines, vec![1]);
+    }
+    // The offline commit's note went out; each side keeps its version of the disputed one
+    assert!(
+        upstream
+            .git_og(&["notes", "--ref=ai", "show", &offline_commit])
+            .is_ok()
+    );
+    assert!(
+        note_on(&upstream, &disputed)
+            .metadata
+            .prompts
+            .contains_key("remote02")
+    );
+    assert!(
+        !note_on(&local, &disputed)
+            .metadata
+            .prompts
+            .contains_key("remote02")
+    );
+
+    // Blame picks up the line attributed by the other clone
+    let blame = local.git_ai(&["blame", "lib.rs"]).unwrap();
+    assert!(
+        blame.lines().next().unwrap().contains("other_tool"),
+        "{}",
+        blame
+    );
+}
@@ -0,0 +1,69 @@
+#[macro_use]
+mod repos;
+
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+
+fn has_note(repo: &TestRepo, commit_sha: &str) -> bool {
+    repo.git_og(&["notes", "--ref=ai", "show", commit_sha])
+        .is_ok_and(|note| !note.trim().is_empty())
+}
+
+fn ai_commit(repo: &TestRepo, name: &str) -> String {
+    let mut file = repo.filename(name);
+    file.set_contents(vec!["fn generated() {}".ai()]);
+    repo.stage_all_and_commit(&format!("add {}", name))
+        .expect("commit should succeed")
+        .commit_sha
+}
+
+#[test]
+fn test_notes_replicate_copies_notes_of_shared_commits() {
+    let (local, upstream) = TestRepo::new_with_remote();
+    let fork = TestRepo::new_bare();
+
+    let shared = ai_commit(&local, "shared.rs");
+    local.git(&["push", "origin", "HEAD:main"]).unwrap();
+    assert!(has_note(&upstream, &shared));
+
+    // The fork gets the commit without its note, as a plain `git push` to it would
+    local
+        .git_og(&["remote", "add", "fork", fork.path().to_str().unwrap()])
+        .unwrap();
+    local.git_og(&["push", "fork", "HEAD:main"]).unwrap();
+    local.git_og(&["fetch", "fork"]).unwrap();
+    assert!(!has_note(&fork, &shared));
+
+    let upstream_only = ai_commit(&local, "upstream_only.rs");
+    local.git(&["push", "origin", "HEAD:main"]).unwrap();
+
+    let output = local
+        .git_ai(&["notes", "replicate", "--from", "origin", "--to", "fork"])
+        .unwrap();
+    assert!(
+        output.contains("Replicated 1 note(s) from origin to fork"),
+        "{}",
+        output
+    );
+    assert!(has_note(&fork, &shared));
+    assert!(!has_note(&fork, &upstream_only));
+
+    let output = local
+        .git_ai(&["notes", "replicate", "--from", "origin", "--to", "fork"])
+        .unwrap();
+    assert!(output.contains("Replicated 0 note(s)"), "{}", output);
+}
+
+#[test]
+fn test_notes_replicate_needs_fetched_target() {
+    let (local, _upstream) = TestRepo::new_with_remote();
+    ai_commit(&local, "file.rs");
+    local
+        .git_og(&["remote", "add", "fork", "/nonexistent/fork.git"])
+        .unwrap();
+
+    let err = local
+        .git_ai(&["notes", "replicate", "--from", "origin", "--to", "fork"])
+        .unwrap_err();
+    assert!(err.contains("fetch it first"), "{}", err);
+}
@@ -0,0 +1,121 @@
+#[macro_use]
+mod repos;
+
+use git_ai::git::sync_authorship::{SyncMode, SyncSettings};
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+
+fn has_note(repo: &TestRepo, notes_ref: &str, commit_sha: &str) -> bool {
+    repo.git_og(&["notes", &format!("--ref={}", notes_ref), "show", commit_sha])
+        .is_ok_and(|note| !note.trim().is_empty())
+}
+
+fn ai_commit(repo: &TestRepo) -> String {
+    let mut file = repo.filename("feature.rs");
+    file.set_contents(vec!["fn feature() {}".ai()]);
+    repo.stage_all_and_commit("add feature")
+        .expect("commit should succeed")
+        .commit_sha
+}
+
+#[test]
+fn test_sync_settings_from_values() {
+    let settings = SyncSettings::from_values(None, None, None);
+    assert_eq!(settings.notes_ref, "refs/notes/ai");
+    assert!(settings.auto_fetch("origin") && settings.auto_push("mirror"));
+
+    let settings =
+        SyncSettings::from_values(Some("ai-authorship"), Some("origin, backup"), Some("fetch"));
+    assert_eq!(settings.notes_ref, "refs/notes/ai-authorship");
+    assert_eq!(settings.mode, SyncMode::Fetch);
+    assert!(settings.auto_fetch("backup"));
+    assert!(!settings.auto_fetch("gerrit"));
+    assert!(!settings.auto_push("origin"));
+
+    let settings = SyncSettings::from_values(Some("refs/notes/custom"), None, Some("OFF"));
+    assert_eq!(settings.notes_ref, "refs/notes/custom");
+    assert!(!settings.auto_fetch("origin") && !settings.auto_push("origin"));
+}
+
+#[test]
+fn test_sync_remotes_excludes_remote_until_explicit_sync() {
+    let (local, upstream) = TestRepo::new_with_remote();
+    local
+        .git_og(&["config", "git-ai.syncRemotes", "backup"])
+        .unwrap();
+    let commit = ai_commit(&local);
+
+    local.git(&["push", "origin", "HEAD"]).unwrap();
+    assert!(
+        !has_note(&upstream, "ai", &commit),
+        "origin is not in git-ai.syncRemotes, so push must not send notes"
+    );
+
+    let output = local.git_ai(&["sync", "push", "origin"]).unwrap();
+    assert!(
+        output.contains("Pushed refs/notes/ai to origin"),
+        "{}",
+        output
+    );
+    assert!(has_note(&upstream, "ai", &commit));
+}
+
+#[test]
+fn test_custom_notes_ref_and_sync_off() {
+    let (local, upstream) = TestRepo::new_with_remote();
+    local
+        .git_og(&["config", "git-ai.notesRef", "ai-authorship"])
+        .unwrap();
+    let commit = ai_commit(&local);
+
+    local.git(&["push", "origin", "HEAD"]).unwrap();
+    assert!(has_note(&upstream, "ai-authorship", &commit));
+    assert!(
+        !has_note(&upstream, "ai", &commit),
+        "the default notes ref must not be pushed when git-ai.notesRef is set"
+    );
+
+    // With sync off, fetching does not bring the notes back; `git-ai sync fetch` does
+    local.git_og(&["config", "git-ai.sync", "off"]).unwrap();
+    local
+        .git_og(&["update-ref", "-d", "refs/notes/ai"])
+        .unwrap();
+    local
+        .git_og(&["update-ref", "-d", "refs/notes/ai-remote/origin"])
+        .unwrap();
+    local.git(&["fetch", "origin"]).unwrap();
+    assert!(!has_note(&local, "ai", &commit));
+
+    let output = local.git_ai(&["sync", "fetch"]).unwrap();
+    assert!(
+        output.contains("Fetched refs/notes/ai-authorship from origin"),
+        "{}",
+        output
+    );
+    assert!(has_note(&local, "ai", &commit));
+}
+
+#[test]
+fn test_blame_cache_follows_notes_fetched_from_custom_ref() {
+    let (local, _upstream) = TestRepo::new_with_remote();
+    local
+        .git_og(&["config", "git-ai.notesRef", "ai-authorship"])
+        .unwrap();
+    ai_commit(&local);
+    local.git(&["push", "origin", "HEAD"]).unwrap();
+
+    local
+        .git_og(&["update-ref", "-d", "refs/notes/ai"])
+        .unwrap();
+    local
+        .git_og(&["update-ref", "-d", "refs/notes/ai-remote/origin"])
+        .unwrap();
+    let without_notes = local.git_ai(&["blame", "feature.rs"]).unwrap();
+    assert!(!without_notes.contains("mock_ai"), "{}", without_notes);
+
+    // The fetch brings the notes back from refs/notes/ai-authorship, so the blame cached
+    // without them must not be served
+    local.git(&["fetch", "origin"]).unwrap();
+    let with_notes = local.git_ai(&["blame", "feature.rs"]).unwrap();
+    assert!(with_notes.contains("mock_ai"), "{}", with_notes);
+}
@@ -0,0 +1,52 @@
+#[macro_use]
+mod repos;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+use serde_json::Value;
+
+fn working_log_dump(repo: &TestRepo, extra_args: &[&str]) -> Value {
+    let mut args = vec!["plumbing", "working-log", "dump", "--format", "json"];
+    args.extend_from_slice(extra_args);
+    let output = repo.git_ai(&args).unwrap();
+    let start = output.find('{').expect("JSON output");
+    serde_json::from_str(&output[start..]).unwrap()
+}
+
+#[test]
+fn test_plumbing_working_log_dump() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("app.py");
+    file.set_contents(lines!["def main():".human()]);
+    let commit = repo.stage_all_and_commit("Initial").unwrap();
+
+    file.set_contents(lines!["def main():".human(), "    return 1".ai()]);
+
+    let dump = working_log_dump(&repo, &[]);
+    assert_eq!(dump["version"], 1);
+    assert_eq!(dump["base_commit"], commit.commit_sha.as_str());
+    assert_eq!(dump["exists"], true);
+
+    let checkpoints = dump["checkpoints"].as_array().unwrap();
+    let ai_checkpoint = checkpoints
+        .iter()
+        .find(|c| c["kind"] == "ai_agent")
+        .expect("ai checkpoint");
+    assert_eq!(ai_checkpoint["agent"]["tool"], "mock_ai");
+    let entry = &ai_checkpoint["files"][0];
+    assert_eq!(entry["path"], "app.py");
+    assert_eq!(entry["content_sha256"].as_str().unwrap().len(), 64);
+    assert!(!entry["line_attributions"].as_array().unwrap().is_empty());
+    assert!(dump["initial"]["files"].is_object());
+
+    let explicit = working_log_dump(&repo, &["--base", "HEAD"]);
+    assert_eq!(explicit["base_commit"], commit.commit_sha.as_str());
+    assert_eq!(explicit["checkpoints"], dump["checkpoints"]);
+
+    // A base without a working log is reported as empty rather than created
+    let missing = "0000000000000000000000000000000000000000";
+    let empty = working_log_dump(&repo, &["--base", missing]);
+    assert_eq!(empty["exists"], false);
+    assert_eq!(empty["checkpoints"], serde_json::json!([]));
+    let again = working_log_dump(&repo, &["--base", missing]);
+    assert_eq!(again["exists"], false);
+}
@@ -0,0 +1,146 @@
+#[macro_use]
+mod repos;
+use git_ai::authorship::authorship_log_serialization::AuthorshipLog;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+
+const CRYPTO_POLICY: &str = r#"[[rule]]
+name = "crypto"
+paths = ["src/crypto/**"]
+max_ai_percent = 50
+severity = "block"
+"#;
+
+const MIGRATIONS_POLICY: &str = r#"[[rule]]
+name = "migrations"
+paths = ["migrations/**"]
+require_prompt_records = true
+severity = "block"
+"#;
+
+fn write_policy(repo: &TestRepo, policy: &str) {
+    let dir = repo.path().join(".git-ai");
+    std::fs::create_dir_all(&dir).unwrap();
+    std::fs::write(dir.join("policy.toml"), policy).unwrap();
+}
+
+#[test]
+fn test_policy_blocks_ai_heavy_commit_unless_skipped() {
+    let repo = TestRepo::new();
+    write_policy(&repo, CRYPTO_POLICY);
+    let mut readme = repo.filename("README.md");
+    readme.set_contents(lines!["# crypto".human()]);
+    repo.stage_all_and_commit("Initial").unwrap();
+
+    let mut aes = repo.filename("src/crypto/aes.rs");
+    aes.set_contents(lines![
+        "fn encrypt() {}".ai(),
+        "fn decrypt() {}".ai(),
+        "// reviewed".human(),
+    ]);
+    repo.git(&["add", "-A"]).unwrap();
+
+    let blocked = repo.commit("AI crypto");
+    let err = blocked.expect_err("policy should block the commit");
+    assert!(err.contains("[crypto]"), "error output: {}", err);
+    assert!(err.contains("67%"), "error output: {}", err);
+
+    let skipped = repo
+        .git(&["commit", "--no-verify", "-m", "AI crypto"])
+        .expect("--no-verify should bypass the policy");
+    assert!(!skipped.contains("[crypto]"));
+
+    // CI sees the same violation on the committed range
+    let check = repo.git_ai(&["policy", "check", "HEAD"]);
+    let output = check.expect_err("blocking violations should fail the check");
+    assert!(output.contains("[crypto]"), "check output: {}", output);
+
+    let clean = repo
+        .git_ai(&["policy", "check", "HEAD~1", "--json"])
+        .unwrap();
+    assert!(
+        clean.contains("\"blocked\":false"),
+        "check output: {}",
+        clean
+    );
+}
+
+#[test]
+fn test_policy_requires_prompt_records_for_ai_lines() {
+    let repo = TestRepo::new();
+    write_policy(&repo, MIGRATIONS_POLICY);
+    let mut readme = repo.filename("README.md");
+    readme.set_contents(lines!["# migrations".human()]);
+    repo.stage_all_and_commit("Initial").unwrap();
+
+    // The working log has the prompt behind every AI line, so the commit goes through
+    let mut migration = repo.filename("migrations/001.sql");
+    migration.set_contents(lines!["CREATE TABLE users (id INT);".ai()]);
+    repo.stage_all_and_commit("Add users table")
+        .expect("AI lines with prompt records should pass");
+    repo.git_ai(&["policy", "check", "HEAD"])
+        .expect("the committed lines have prompt records");
+
+    // Drop the prompt records from the note, as a tool that doesn't write them would
+    let note = repo.git_og(&["notes", "--ref=ai", "show", "HEAD"]).unwrap();
+    let mut log = AuthorshipLog::deserialize_from_string(&note).unwrap();
+    log.metadata.prompts.clear();
+    let path = repo.path().join(".git").join("stripped-note");
+    std::fs::write(&path, log.serialize_to_string().unwrap()).unwrap();
+    repo.git_og(&[
+        "notes",
+        "--ref=ai",
+        "add",
+        "-f",
+        "-F",
+        path.to_str().unwrap(),
+        "HEAD",
+    ])
+    .unwrap();
+
+    let check = repo.git_ai(&["policy", "check", "HEAD"]);
+    let output = check.expect_err("AI lines without prompt records should fail the check");
+    assert!(output.contains("[migrations]"), "check output: {}", output);
+    assert!(
+        output.contains("no prompt record"),
+        "check output: {}",
+        output
+    );
+}
+
+#[test]
+fn test_unparseable_policy_blocks_commit_and_fails_check() {
+    let repo = TestRepo::new();
+    let mut readme = repo.filename("README.md");
+    readme.set_contents(lines!["# policy".human()]);
+    repo.stage_all_and_commit("Initial").unwrap();
+    // Typo in the rule table
+    write_policy(&repo, "[[rule]\nname = \"crypto\"\n");
+
+    let mut aes = repo.filename("src/crypto/aes.rs");
+    aes.set_contents(lines!["fn encrypt() {}".ai()]);
+    repo.git(&["add", "-A"]).unwrap();
+
+    let err = repo
+        .commit("AI crypto")
+        .expect_err("a policy that doesn't parse should block the commit");
+    assert!(
+        err.contains("Invalid .git-ai/policy.toml"),
+        "error output: {}",
+        err
+    );
+    repo.git_with_env(
+        &["commit", "-m", "AI crypto"],
+        &[("GIT_AI_SKIP_POLICY", "1")],
+        None,
+    )
+    .expect("GIT_AI_SKIP_POLICY should bypass the policy");
+
+    let check = repo.git_ai(&["policy", "check", "HEAD"]);
+    let output = check.expect_err("a policy that doesn't parse should fail the check");
+    assert!(
+        output.contains("Invalid .git-ai/policy.toml"),
+        "check output: {}",
+        output
+    );
+}
@@ -0,0 +1,104 @@
+#[macro_use]
+mod repos;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+
+fn head_sha(repo: &TestRepo) -> String {
+    repo.git(&["rev-parse", "HEAD"]).unwrap().trim().to_string()
+}
+
+#[test]
+fn test_provenance_of_committed_ai_and_human_lines() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("app.py");
+    file.set_contents(lines!["import os".human(), "main()".human()]);
+    repo.stage_all_and_commit("Initial").unwrap();
+    let initial = head_sha(&repo);
+    repo.git(&["tag", "v1.0.0"]).unwrap();
+
+    file.insert_at(1, lines!["print('hi')".ai()]);
+    repo.stage_all_and_commit("AI change").unwrap();
+    let ai_commit = head_sha(&repo);
+
+    let output = repo.git_ai(&["provenance", "app.py:2"]).unwrap();
+    let line = output.trim();
+    let prefix = format!(
+        "git-ai-provenance/1 file=app.py:2 tag=v1.0.0+1 commit={} by=ai:mock_ai model=",
+        ai_commit
+    );
+    assert!(line.starts_with(&prefix), "{}", line);
+    assert!(!line.contains("checkpoint=none"), "{}", line);
+    assert_eq!(line.lines().count(), 1, "{}", line);
+    // Stable across runs
+    assert_eq!(
+        repo.git_ai(&["provenance", "app.py:2"]).unwrap().trim(),
+        line
+    );
+
+    let output = repo.git_ai(&["provenance", "app.py:3"]).unwrap();
+    assert_eq!(
+        output.trim(),
+        format!(
+            "git-ai-provenance/1 file=app.py:3 tag=v1.0.0+0 commit={} by=human:test@example.com model=none checkpoint=none",
+            initial
+        )
+    );
+}
+
+#[test]
+fn test_provenance_of_uncommitted_ai_line() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("app.py");
+    file.set_contents(lines!["import os".human(), "main()".human()]);
+    repo.stage_all_and_commit("Initial").unwrap();
+
+    file.insert_at(1, lines!["print('hi')".ai()]);
+    let output = repo.git_ai(&["provenance", "app.py:2"]).unwrap();
+    assert!(
+        output.contains("tag=none commit=uncommitted by=ai:mock_ai"),
+        "{}",
+        output
+    );
+}
+
+#[test]
+fn test_provenance_resolves_paths_from_a_subdirectory() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("src/app.py");
+    file.set_contents(lines!["import os".human(), "main()".human()]);
+    repo.stage_all_and_commit("Initial").unwrap();
+    file.insert_at(1, lines!["print('hi')".ai()]);
+    let subdir = repo.path().join("src");
+
+    let output = repo
+        .git_ai_from_working_dir(&subdir, &["provenance", "app.py:2"])
+        .unwrap();
+    assert!(
+        output.contains("file=src/app.py:2 tag=none commit=uncommitted by=ai:mock_ai"),
+        "{}",
+        output
+    );
+
+    repo.stage_all_and_commit("AI change").unwrap();
+    let output = repo
+        .git_ai_from_working_dir(&subdir, &["provenance", "app.py:2"])
+        .unwrap();
+    let prefix = format!(
+        "git-ai-provenance/1 file=src/app.py:2 tag=none commit={} by=ai:mock_ai",
+        head_sha(&repo)
+    );
+    assert!(output.trim().starts_with(&prefix), "{}", output);
+}
+
+#[test]
+fn test_provenance_rejects_bad_targets() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("app.py");
+    file.set_contents(lines!["import os".human()]);
+    repo.stage_all_and_commit("Initial").unwrap();
+
+    let err = repo.git_ai(&["provenance", "app.py"]).unwrap_err();
+    assert!(err.contains("Usage: git-ai provenance"), "{}", err);
+    let err = repo.git_ai(&["provenance", "app.py:9"]).unwrap_err();
+    assert!(err.contains("Failed to get provenance"), "{}", err);
+}
@@ -83,3 +83,96 @@ fn push_after_branch_set_upstream_pushes_authorship_notes() {
         "expected authorship notes to be pushed after setting upstream with git branch -u"
     );
 }
+
+#[test]
+fn push_only_sends_notes_for_pushed_commits() {
+    let (local, upstream) = TestRepo::new_with_remote();
+
+    let mut file = local.filename("shared.rs");
+    file.set_contents(vec!["fn shared() {}".ai()]);
+    let shared = local
+        .stage_all_and_commit("shared commit")
+        .expect("commit should succeed");
+    let main_branch = local.current_branch();
+
+    local
+        .git(&["checkout", "-b", "local-only"])
+        .expect("branch should be created");
+    let mut wip = local.filename("wip.rs");
+    wip.set_contents(vec!["fn wip() {}".ai()]);
+    let unpushed = local
+        .stage_all_and_commit("local experiment")
+        .expect("commit should succeed");
+
+    local
+        .git(&["push", "origin", &main_branch])
+        .expect("push should succeed");
+
+    assert!(
+        read_remote_authorship_note(&upstream, &shared.commit_sha).is_some(),
+        "notes for pushed commits should reach the remote"
+    );
+    assert!(
+        read_remote_authorship_note(&upstream, &unpushed.commit_sha).is_none(),
+        "notes for commits that were not pushed must stay local"
+    );
+}
+
+#[test]
+fn push_does_not_send_notes_when_disabled() {
+    let (mut local, upstream) = TestRepo::new_with_remote();
+    local.patch_git_ai_config(|patch| {
+        patch.disable_notes_push = Some(true);
+    });
+
+    let mut file = local.filename("quiet.rs");
+    file.set_contents(vec!["fn quiet() {}".ai()]);
+    let commit = local
+        .stage_all_and_commit("quiet commit")
+        .expect("commit should succeed");
+
+    local
+        .git(&["push", "origin", "HEAD"])
+        .expect("push should succeed");
+
+    assert!(read_remote_authorship_note(&upstream, &commit.commit_sha).is_none());
+}
+
+#[test]
+fn push_retries_notes_the_remote_rejected_earlier() {
+    let (local, upstream) = TestRepo::new_with_remote();
+
+    // The remote refuses notes refs until the hook is removed
+    let hook = upstream.path().join("hooks").join("pre-receive");
+    std::fs::create_dir_all(hook.parent().unwrap()).unwrap();
+    std::fs::write(
+        &hook,
+        "#!/bin/sh\nwhile read old new ref; do\n  case \"$ref\" in refs/notes/*) exit 1;; esac\ndone\n",
+    )
+    .unwrap();
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        std::fs::set_permissions(&hook, std::fs::Permissions::from_mode(0o755)).unwrap();
+    }
+
+    let mut file = local.filename("retry.rs");
+    file.set_contents(vec!["fn retry() {}".ai()]);
+    let commit = local
+        .stage_all_and_commit("retry commit")
+        .expect("commit should succeed");
+
+    local
+        .git(&["push", "origin", "HEAD"])
+        .expect("push should succeed even though the notes were rejected");
+    assert!(read_remote_authorship_note(&upstream, &commit.commit_sha).is_none());
+
+    std::fs::remove_file(&hook).unwrap();
+    local
+        .git(&["push", "origin", "HEAD"])
+        .expect("push should succeed");
+    assert!(
+        read_remote_authorship_note(&upstream, &commit.commit_sha).is_some(),
+        "notes rejected on the first push should be sent with the next one"
+    );
+}
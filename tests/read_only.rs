@@ -0,0 +1,53 @@
+#[macro_use]
+mod repos;
+use git_ai::git::find_repository_in_path;
+use git_ai::git::refs::show_authorship_note;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+
+fn head_note(repo: &TestRepo) -> Option<String> {
+    let head = repo.git(&["rev-parse", "HEAD"]).unwrap().trim().to_string();
+    let gitai_repo = find_repository_in_path(repo.path().to_str().unwrap()).unwrap();
+    show_authorship_note(&gitai_repo, &head)
+}
+
+#[test]
+fn test_read_only_records_nothing_but_still_reads_attribution() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("file.txt");
+    file.set_contents(lines!["Line 1", "Line 2"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    file.insert_at(1, lines!["AI line".ai()]);
+    repo.stage_all_and_commit("AI change").unwrap();
+
+    repo.git(&["config", "ai.readOnly", "true"]).unwrap();
+
+    file.insert_at(2, lines!["Second AI line".ai()]);
+    let checkpoint = repo.git_ai(&["checkpoint", "--show-working-log"]).unwrap();
+    assert!(!checkpoint.contains("Second AI line"), "{}", checkpoint);
+    repo.git(&["add", "-A"]).unwrap();
+    repo.git(&["commit", "-m", "Audited change"]).unwrap();
+    assert!(head_note(&repo).is_none());
+
+    // Existing attribution still reads as before
+    file.assert_lines_and_blame(lines![
+        "Line 1".human(),
+        "AI line".ai(),
+        "Second AI line".human(),
+        "Line 2".human()
+    ]);
+    let stats = repo.git_ai(&["stats", "HEAD~1", "--json"]).unwrap();
+    assert!(stats.contains("\"ai_additions\":1"), "{}", stats);
+}
+
+#[test]
+fn test_read_only_refuses_note_pushes() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("file.txt");
+    file.set_contents(lines!["Line 1"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    repo.git(&["config", "ai.readOnly", "true"]).unwrap();
+
+    let err = repo.git_ai(&["sync", "push", "origin"]).unwrap_err();
+    assert!(err.contains("read-only"), "{}", err);
+}
@@ -0,0 +1,163 @@
+#[macro_use]
+mod repos;
+use git_ai::authorship::authorship_log_serialization::AuthorshipLog;
+use git_ai::git::find_repository_in_path;
+use git_ai::git::refs::show_authorship_note;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+use std::fs;
+
+fn note_for(repo: &TestRepo, rev: &str) -> AuthorshipLog {
+    let sha = repo.git(&["rev-parse", rev]).unwrap().trim().to_string();
+    let gitai_repo = find_repository_in_path(repo.path().to_str().unwrap()).unwrap();
+    let note = show_authorship_note(&gitai_repo, &sha).expect("authorship note");
+    AuthorshipLog::deserialize_from_string(&note).unwrap()
+}
+
+#[test]
+fn test_reassign_hands_ai_lines_to_human_and_keeps_provenance() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("file.txt");
+    file.set_contents(lines!["Line 1", "Line 2"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    file.insert_at(
+        1,
+        lines!["AI line 1".ai(), "AI line 2".ai(), "AI line 3".ai()],
+    );
+    repo.stage_all_and_commit("AI change").unwrap();
+
+    let output = repo
+        .git_ai(&["reassign", "file.txt", "-L", "2,3", "--to", "human"])
+        .unwrap();
+    assert!(
+        output.contains("Reassigned 2 AI lines of file.txt to human"),
+        "{}",
+        output
+    );
+
+    file.assert_lines_and_blame(lines![
+        "Line 1".human(),
+        "AI line 1".human(),
+        "AI line 2".human(),
+        "AI line 3".ai(),
+        "Line 2".human()
+    ]);
+
+    let log = note_for(&repo, "HEAD");
+    let records = &log.metadata.reassignments["file.txt"];
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].lines, "2-3");
+    assert_eq!(records[0].to, "human");
+    assert_eq!(records[0].reason, "manual");
+    assert!(log.metadata.prompts.contains_key(&records[0].prompt));
+
+    // Nothing AI is left in those lines
+    let output = repo
+        .git_ai(&["reassign", "file.txt", "-L", "2,3", "--to", "human"])
+        .unwrap();
+    assert!(output.contains("No committed AI lines"), "{}", output);
+}
+
+#[test]
+fn test_reassign_rejects_other_targets() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("file.txt");
+    file.set_contents(lines!["Line 1"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let err = repo
+        .git_ai(&["reassign", "file.txt", "-L", "1,1", "--to", "mock_ai"])
+        .unwrap_err();
+    assert!(err.contains("only be reassigned to human"), "{}", err);
+}
+
+#[test]
+fn test_commit_reassigns_lines_rewritten_past_threshold() {
+    let mut repo = TestRepo::new();
+    repo.patch_git_ai_config(|patch| {
+        patch.reassign_threshold = Some(50);
+    });
+
+    // The human writes the line, the agent renames one call in it last
+    let path = repo.path().join("calc.py");
+    fs::write(
+        &path,
+        "def total(items):\n    return compute_sum(items, start=0)\n",
+    )
+    .unwrap();
+    repo.git_ai(&["checkpoint"]).unwrap();
+    fs::write(
+        &path,
+        "def total(items):\n    return fsum(items, start=0)\n",
+    )
+    .unwrap();
+    repo.git_ai(&["checkpoint", "mock_ai"]).unwrap();
+
+    let log = repo
+        .stage_all_and_commit("Add total")
+        .unwrap()
+        .authorship_log;
+    let records = &log.metadata.reassignments["calc.py"];
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].lines, "2");
+    assert_eq!(records[0].reason, "rewrite");
+    assert!(log.attestations.is_empty());
+    assert!(log.metadata.prompts.contains_key(&records[0].prompt));
+}
+
+#[test]
+fn test_reassignments_survive_amend_and_rebase() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("file.txt");
+    file.set_contents(lines!["Line 1", "Line 2", "Line 3", "Line 4"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    let default_branch = repo.current_branch();
+
+    repo.git(&["checkout", "-b", "feature"]).unwrap();
+    file.insert_at(
+        3,
+        lines!["AI line 1".ai(), "AI line 2".ai(), "AI line 3".ai()],
+    );
+    repo.stage_all_and_commit("AI change").unwrap();
+    repo.git_ai(&["reassign", "file.txt", "-L", "4,5", "--to", "human"])
+        .unwrap();
+
+    repo.git(&["commit", "--amend", "-m", "AI change, reviewed"])
+        .unwrap();
+    let log = note_for(&repo, "HEAD");
+    let records = &log.metadata.reassignments["file.txt"];
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].lines, "4-5");
+    assert!(log.metadata.prompts.contains_key(&records[0].prompt));
+
+    // Upstream adds a header, shifting the reassigned lines down by two
+    repo.git(&["checkout", &default_branch]).unwrap();
+    fs::write(
+        repo.path().join("file.txt"),
+        "Header\n\nLine 1\nLine 2\nLine 3\nLine 4",
+    )
+    .unwrap();
+    repo.stage_all_and_commit("Add header").unwrap();
+
+    repo.git(&["checkout", "feature"]).unwrap();
+    repo.git(&["rebase", &default_branch]).unwrap();
+    let log = note_for(&repo, "HEAD");
+    let records = &log.metadata.reassignments["file.txt"];
+    assert_eq!(records.len(), 1);
+    assert_eq!(records[0].lines, "6-7");
+    assert_eq!(records[0].reason, "manual");
+    assert!(log.metadata.prompts.contains_key(&records[0].prompt));
+
+    let mut file = repo.filename("file.txt");
+    file.assert_lines_and_blame(lines![
+        "Header".human(),
+        "".human(),
+        "Line 1".human(),
+        "Line 2".human(),
+        "Line 3".human(),
+        "AI line 1".human(),
+        "AI line 2".human(),
+        "AI line 3".ai(),
+        "Line 4".human()
+    ]);
+}
@@ -1449,3 +1449,90 @@ cat {} > "$1"
         "function feature3() {}".ai()
     ]);
 }
+
+/// Reorder + fixup across a `break` stop: the new commits must carry the authorship of the
+/// originals they were rewritten from, not of whichever original shared their position.
+#[test]
+#[cfg(not(target_os = "windows"))]
+fn test_rebase_reorder_and_fixup_across_pause_maps_authorship_by_rewrite() {
+    let repo = TestRepo::new();
+
+    let mut base_file = repo.filename("base.txt");
+    base_file.set_contents(lines!["base content"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    let base_commit = repo.git(&["rev-parse", "HEAD"]).unwrap().trim().to_string();
+
+    repo.git(&["checkout", "-b", "feature"]).unwrap();
+
+    let mut file_a = repo.filename("a.txt");
+    file_a.set_contents(lines!["// AI a".ai(), "a line".ai()]);
+    let commit_a = repo.stage_all_and_commit("Add a").unwrap().commit_sha;
+
+    let mut file_b = repo.filename("b.txt");
+    file_b.set_contents(lines!["// AI b".ai(), "b line".ai()]);
+    let commit_b = repo.stage_all_and_commit("Add b").unwrap().commit_sha;
+
+    file_a.insert_at(1, lines!["a fixup line".ai()]);
+    let commit_c = repo
+        .stage_all_and_commit("fixup! Add a")
+        .unwrap()
+        .commit_sha;
+
+    use std::io::Write;
+    let script_content = format!(
+        "#!/bin/sh\ncat > \"$1\" <<TODO\npick {}\nbreak\npick {}\nfixup {}\nTODO\n",
+        commit_b, commit_a, commit_c
+    );
+    let script_path = repo.path().join("reorder_script.sh");
+    let mut script_file = std::fs::File::create(&script_path).unwrap();
+    script_file.write_all(script_content.as_bytes()).unwrap();
+    drop(script_file);
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = std::fs::metadata(&script_path).unwrap().permissions();
+        perms.set_mode(0o755);
+        std::fs::set_permissions(&script_path, perms).unwrap();
+    }
+
+    repo.git_with_env(
+        &["rebase", "-i", &base_commit],
+        &[
+            ("GIT_SEQUENCE_EDITOR", script_path.to_str().unwrap()),
+            ("GIT_EDITOR", "true"),
+        ],
+        None,
+    )
+    .expect("rebase should stop at break");
+    repo.git_with_env(&["rebase", "--continue"], &[("GIT_EDITOR", "true")], None)
+        .expect("rebase --continue should finish");
+
+    let rewrite_log =
+        std::fs::read_to_string(repo.path().join(".git").join("ai").join("rewrite_log")).unwrap();
+    assert!(
+        rewrite_log.contains("rebase_progress"),
+        "the paused rebase should have snapshotted git's rewritten-list"
+    );
+
+    let new_b = repo
+        .git(&["rev-parse", "HEAD~1"])
+        .unwrap()
+        .trim()
+        .to_string();
+    let new_a = repo.git(&["rev-parse", "HEAD"]).unwrap().trim().to_string();
+
+    let note_b = read_authorship_note(&repo, &new_b).expect("rebased b commit should have a note");
+    let log_b = AuthorshipLog::deserialize_from_string(&note_b).unwrap();
+    let files_b: Vec<&str> = log_b
+        .attestations
+        .iter()
+        .map(|a| a.file_path.as_str())
+        .collect();
+    assert_eq!(files_b, vec!["b.txt"]);
+
+    let note_a = read_authorship_note(&repo, &new_a).expect("squashed a commit should have a note");
+    let log_a = AuthorshipLog::deserialize_from_string(&note_a).unwrap();
+    assert!(log_a.attestations.iter().any(|a| a.file_path == "a.txt"));
+
+    file_a.assert_lines_and_blame(lines!["// AI a".ai(), "a fixup line".ai(), "a line".ai()]);
+    file_b.assert_lines_and_blame(lines!["// AI b".ai(), "b line".ai()]);
+}
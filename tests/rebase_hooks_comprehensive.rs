@@ -21,6 +21,7 @@ fn make_rebase_invocation(args: &[&str]) -> ParsedGitInvocation {
         command_args: args.iter().map(|s| s.to_string()).collect(),
         saw_end_of_opts: false,
         is_help: false,
+        no_ai_hooks: false,
     }
 }
 
@@ -52,7 +53,7 @@ fn test_pre_rebase_hook_starts_new_rebase() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     let parsed_args = make_rebase_invocation(&["main"]);
@@ -95,7 +96,7 @@ fn test_pre_rebase_hook_continuing_rebase() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     let parsed_args = make_rebase_invocation(&["--continue"]);
@@ -131,7 +132,7 @@ fn test_pre_rebase_hook_interactive_mode() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     let parsed_args = make_rebase_invocation(&["-i", "main"]);
@@ -178,7 +179,7 @@ fn test_pre_rebase_hook_with_onto() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     let parsed_args = make_rebase_invocation(&["--onto", &onto_commit.commit_sha, "main"]);
@@ -212,7 +213,7 @@ fn test_post_rebase_hook_still_in_progress() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     let parsed_args = make_rebase_invocation(&["main"]);
@@ -266,7 +267,7 @@ fn test_post_rebase_hook_aborted() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     context.rebase_original_head = Some(original_commit.commit_sha.clone());
@@ -308,7 +309,7 @@ fn test_post_rebase_hook_dry_run() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     let parsed_args = make_rebase_invocation(&["--dry-run", "main"]);
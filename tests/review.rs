@@ -0,0 +1,77 @@
+#[macro_use]
+mod repos;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+
+/// main has one file; feature adds one AI line and one human line to it, plus a human file.
+fn feature_branch() -> (TestRepo, String) {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("app.rs");
+    file.set_contents(lines!["fn main() {", "}"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    let main_branch = repo.current_branch();
+
+    repo.git(&["checkout", "-b", "feature"]).unwrap();
+    file.insert_at(1, lines!["    ai_call();".ai()]);
+    repo.stage_all_and_commit("AI change").unwrap();
+    file.insert_at(1, lines!["    human_call();".human()]);
+    let mut notes = repo.filename("NOTES.md");
+    notes.set_contents(lines!["notes".human()]);
+    repo.stage_all_and_commit("Human change").unwrap();
+    (repo, main_branch)
+}
+
+#[test]
+fn test_review_json_reports_ai_hunks_and_share() {
+    let (repo, main_branch) = feature_branch();
+
+    let output = repo
+        .git_ai(&["review", &format!("{}..feature", main_branch), "--json"])
+        .unwrap();
+    let json_start = output.find('{').unwrap();
+    let report: serde_json::Value = serde_json::from_str(&output[json_start..]).unwrap();
+
+    assert_eq!(report["added_lines"], 3);
+    assert_eq!(report["ai_lines"], 1);
+    assert_eq!(report["ai_percent"], 33.3);
+    assert_eq!(report["commits"], 2);
+    assert_eq!(report["agents"][0]["tool"], "mock_ai");
+
+    let files = report["files"].as_array().unwrap();
+    assert_eq!(files.len(), 2);
+    let app = files.iter().find(|f| f["path"] == "app.rs").unwrap();
+    assert_eq!(app["added_lines"], 2);
+    assert_eq!(app["ai_hunks"][0]["start_line"], 3);
+    assert_eq!(app["ai_hunks"][0]["end_line"], 3);
+    let notes = files.iter().find(|f| f["path"] == "NOTES.md").unwrap();
+    assert_eq!(notes["ai_lines"], 0);
+}
+
+#[test]
+fn test_review_markdown_and_fail_on_gate() {
+    let (repo, main_branch) = feature_branch();
+    let range = format!("{}..feature", main_branch);
+
+    let markdown = repo.git_ai(&["review", &range]).unwrap();
+    assert!(markdown.contains("## AI authorship review"), "{}", markdown);
+    assert!(
+        markdown.contains("**33.3% AI**: 1 of 3 added lines"),
+        "{}",
+        markdown
+    );
+    assert!(
+        markdown.contains("| `app.rs` | 2 | 1 | 50% |"),
+        "{}",
+        markdown
+    );
+    assert!(markdown.contains("- L3: mock_ai"), "{}", markdown);
+
+    assert!(
+        repo.git_ai(&["review", &range, "--fail-on", "ai-percent=50"])
+            .is_ok()
+    );
+    let err = repo
+        .git_ai(&["review", &range, "--fail-on", "ai-percent=25"])
+        .unwrap_err();
+    assert!(err.contains("ai-percent is 33.3 (limit 25)"), "{}", err);
+}
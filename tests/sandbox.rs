@@ -0,0 +1,125 @@
+#[macro_use]
+mod repos;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+use std::fs;
+
+fn sandbox_dir(repo: &TestRepo, name: &str) -> std::path::PathBuf {
+    let dir = repo.path().parent().unwrap().join(format!(
+        "{}-{}",
+        repo.path().file_name().unwrap().to_string_lossy(),
+        name
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    dir
+}
+
+#[test]
+fn test_sandbox_commits_are_attributed_to_agent_and_merged_back() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("main.py");
+    file.set_contents(lines!["print('base')"]);
+    repo.stage_all_and_commit("Base").unwrap();
+
+    let dir = sandbox_dir(&repo, "agent-task");
+    let output = repo
+        .git_ai(&[
+            "sandbox",
+            "create",
+            "agent-task",
+            "--path",
+            dir.to_str().unwrap(),
+            "--agent",
+            "mock_ai",
+        ])
+        .expect("sandbox create should succeed");
+    assert!(output.contains("Created sandbox agent-task"), "{}", output);
+
+    // The agent edits without any checkpoint hooks; the sandbox attributes it on commit
+    fs::write(dir.join("main.py"), "print('base')\nprint('from agent')\n").unwrap();
+    repo.git_from_working_dir(&dir, &["add", "-A"]).unwrap();
+    repo.git_from_working_dir(&dir, &["commit", "-m", "Agent work"])
+        .expect("commit in sandbox should succeed");
+
+    // The main worktree's working log is untouched by the sandbox
+    assert!(
+        repo.current_working_logs()
+            .read_all_checkpoints()
+            .unwrap()
+            .is_empty()
+    );
+
+    let output = repo
+        .git_ai(&["sandbox", "merge", "agent-task"])
+        .expect("sandbox merge should succeed");
+    assert!(output.contains("Merged sandbox agent-task"), "{}", output);
+    assert!(!dir.exists(), "sandbox worktree should be removed");
+
+    let mut file = repo.filename("main.py");
+    file.assert_lines_and_blame(lines!["print('base')".human(), "print('from agent')".ai()]);
+}
+
+#[test]
+fn test_sandbox_squash_merge_carries_attribution_into_working_tree() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(lines!["fn base() {}"]);
+    repo.stage_all_and_commit("Base").unwrap();
+
+    let dir = sandbox_dir(&repo, "agent-squash");
+    repo.git_ai(&[
+        "sandbox",
+        "create",
+        "agent-squash",
+        "--path",
+        dir.to_str().unwrap(),
+        "--agent",
+        "mock_ai",
+    ])
+    .expect("sandbox create should succeed");
+
+    fs::write(dir.join("lib.rs"), "fn base() {}\nfn agent() {}\n").unwrap();
+    repo.git_from_working_dir(&dir, &["add", "-A"]).unwrap();
+    repo.git_from_working_dir(&dir, &["commit", "-m", "Agent work"])
+        .unwrap();
+
+    let output = repo
+        .git_ai(&["sandbox", "merge", "agent-squash", "--squash"])
+        .expect("sandbox squash merge should succeed");
+    assert!(
+        output.contains("Squashed sandbox agent-squash"),
+        "{}",
+        output
+    );
+
+    repo.commit("Accept agent work").unwrap();
+    let mut file = repo.filename("lib.rs");
+    file.assert_lines_and_blame(lines!["fn base() {}".human(), "fn agent() {}".ai()]);
+}
+
+#[test]
+fn test_sandbox_merge_refuses_uncommitted_changes() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("a.txt");
+    file.set_contents(lines!["a"]);
+    repo.stage_all_and_commit("Base").unwrap();
+
+    let dir = sandbox_dir(&repo, "agent-dirty");
+    repo.git_ai(&[
+        "sandbox",
+        "create",
+        "agent-dirty",
+        "--path",
+        dir.to_str().unwrap(),
+    ])
+    .unwrap();
+    fs::write(dir.join("a.txt"), "a\nb\n").unwrap();
+
+    let err = repo
+        .git_ai(&["sandbox", "merge", "agent-dirty"])
+        .expect_err("merge should refuse a dirty sandbox");
+    assert!(err.contains("uncommitted changes"), "{}", err);
+    assert!(dir.exists());
+
+    let _ = fs::remove_dir_all(&dir);
+}
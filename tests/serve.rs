@@ -0,0 +1,116 @@
+#[macro_use]
+mod repos;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+
+fn responses(output: &str) -> Vec<serde_json::Value> {
+    output
+        .lines()
+        .filter_map(|line| serde_json::from_str(line).ok())
+        .collect()
+}
+
+#[test]
+fn test_serve_answers_blame_requests_over_stdio() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("app.py");
+    file.set_contents(lines!["import os".human(), "print('hi')".ai()]);
+    repo.stage_all_and_commit("Initial").unwrap();
+
+    let requests = [
+        r#"{"jsonrpc":"2.0","id":1,"method":"blame_file","params":{"path":"app.py"}}"#,
+        r#"{"jsonrpc":"2.0","id":2,"method":"blame_range","params":{"path":"app.py","start_line":2,"end_line":2}}"#,
+        r##"{"jsonrpc":"2.0","id":3,"method":"attribution_for_buffer","params":{"path":"app.py","contents":"# new\nimport os\nprint('hi')"}}"##,
+        r#"{"jsonrpc":"2.0","id":4,"method":"shutdown"}"#,
+        r#"{"jsonrpc":"2.0","id":5,"method":"ping"}"#,
+    ]
+    .join("\n");
+    let output = repo
+        .git_ai_with_stdin(&["serve"], requests.as_bytes())
+        .unwrap();
+    let responses = responses(&output);
+    assert_eq!(
+        responses.len(),
+        4,
+        "server stops after shutdown: {}",
+        output
+    );
+
+    let lines = responses[0]["result"]["lines"].as_array().unwrap();
+    assert_eq!(lines.len(), 2);
+    assert_eq!(lines[0]["kind"], "human");
+    assert_eq!(lines[1]["kind"], "ai");
+    let prompt_id = lines[1]["prompt_id"].as_str().unwrap();
+    assert!(responses[0]["result"]["prompts"][prompt_id]["tool"].is_string());
+
+    let lines = responses[1]["result"]["lines"].as_array().unwrap();
+    assert_eq!(lines.len(), 1);
+    assert_eq!(lines[0]["line"], 2);
+    assert_eq!(lines[0]["kind"], "ai");
+
+    // Unsaved buffer contents shift the AI line down by one.
+    let lines = responses[2]["result"]["lines"].as_array().unwrap();
+    assert_eq!(lines.len(), 3);
+    assert_eq!(lines[0]["kind"], "human");
+    assert_eq!(lines[2]["kind"], "ai");
+
+    assert_eq!(responses[3]["id"], 4);
+    assert!(responses[3]["result"].is_null());
+}
+
+#[cfg(unix)]
+#[test]
+fn test_checkpoints_go_through_a_running_socket_server() {
+    use std::process::{Command, Stdio};
+    use std::time::{Duration, Instant};
+
+    let repo = TestRepo::new();
+    let mut file = repo.filename("app.py");
+    file.set_contents(lines!["import os".human(), "main()".human()]);
+    repo.stage_all_and_commit("Initial").unwrap();
+
+    let ai_dir = repo.path().join(".git").join("ai");
+    let registration = ai_dir.join("daemon.json");
+    let mut server = Command::new(repos::test_repo::get_binary_path())
+        .args([
+            "serve",
+            "--socket",
+            ai_dir.join("serve.sock").to_str().unwrap(),
+        ])
+        .current_dir(repo.path())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .unwrap();
+    let deadline = Instant::now() + Duration::from_secs(30);
+    while !registration.exists() {
+        assert!(Instant::now() < deadline, "server never registered");
+        std::thread::sleep(Duration::from_millis(20));
+    }
+    let registered: serde_json::Value =
+        serde_json::from_str(&std::fs::read_to_string(&registration).unwrap()).unwrap();
+    assert_eq!(registered["pid"], server.id());
+
+    file.insert_at(1, lines!["print('hi')".ai()]);
+    repo.stage_all_and_commit("AI change").unwrap();
+    file.assert_lines_and_blame(lines![
+        "import os".human(),
+        "print('hi')".ai(),
+        "main()".human()
+    ]);
+
+    // A killed server leaves its registration behind; the next write notices and writes
+    // directly
+    server.kill().unwrap();
+    server.wait().unwrap();
+    assert!(registration.exists());
+    file.insert_at(2, lines!["print('bye')".ai()]);
+    repo.stage_all_and_commit("More AI").unwrap();
+    file.assert_lines_and_blame(lines![
+        "import os".human(),
+        "print('hi')".ai(),
+        "print('bye')".ai(),
+        "main()".human()
+    ]);
+    assert!(!registration.exists());
+}
@@ -167,3 +167,59 @@ fn show_prompt_with_offset_skips_occurrences() {
         err
     );
 }
+
+#[test]
+fn prompt_show_resolves_line_to_prompt() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("app.py");
+    file.set_contents(lines!["print('hi')".human(), "print('from ai')".ai()]);
+    let commit = repo.stage_all_and_commit("Add app").unwrap();
+    let (prompt_id, _) = commit
+        .authorship_log
+        .metadata
+        .prompts
+        .iter()
+        .next()
+        .expect("expected a prompt");
+
+    let output = repo
+        .git_ai(&["prompt", "show", "HEAD:app.py:2", "--json"])
+        .expect("prompt show should succeed");
+    let json: serde_json::Value = serde_json::from_str(output.trim()).unwrap();
+    assert_eq!(json["prompt_id"].as_str(), Some(prompt_id.as_str()));
+    assert_eq!(json["commit"].as_str(), Some(commit.commit_sha.as_str()));
+    assert_eq!(json["line"], 2);
+    assert_eq!(json["prompt"]["agent_id"]["tool"], "mock_ai");
+
+    // Text output, with the revision defaulting to HEAD
+    let text = repo.git_ai(&["prompt", "show", "app.py:2"]).unwrap();
+    assert!(text.contains(&format!("prompt {}", prompt_id)));
+    assert!(text.contains("tool   mock_ai"));
+
+    // Blame surfaces the same id
+    let blame = repo
+        .git_ai(&["blame", "--show-prompt-id", "app.py"])
+        .unwrap();
+    assert!(blame.contains(&format!("mock_ai [{}]", prompt_id)));
+
+    let err = repo
+        .git_ai(&["prompt", "show", "HEAD:app.py:1"])
+        .unwrap_err();
+    assert!(err.contains("was not written by AI"), "{}", err);
+}
+
+#[test]
+fn parse_line_location_defaults_to_head() {
+    use git_ai::commands::show_prompt::parse_line_location;
+
+    assert_eq!(
+        parse_line_location("HEAD~1:src/app.py:12"),
+        Some(("HEAD~1", "src/app.py", 12))
+    );
+    assert_eq!(
+        parse_line_location("src/app.py:3"),
+        Some(("HEAD", "src/app.py", 3))
+    );
+    assert_eq!(parse_line_location("src/app.py"), None);
+    assert_eq!(parse_line_location("src/app.py:0"), None);
+}
@@ -0,0 +1,166 @@
+#[macro_use]
+mod repos;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+
+/// Squash-merge `feature` into the current branch with plain git, the way a forge does it.
+fn squash_merge_without_git_ai(repo: &TestRepo, branch: &str) {
+    repo.git_og(&["merge", "--squash", branch])
+        .expect("squash merge should succeed");
+    repo.git_og(&["commit", "-m", "Squash feature"])
+        .expect("squash commit should succeed");
+}
+
+#[test]
+fn test_backfill_squash_merge_from_branch() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(lines!["fn base() {}"]);
+    repo.stage_all_and_commit("Base").unwrap();
+    let main_branch = repo.current_branch();
+
+    repo.git(&["checkout", "-b", "feature"]).unwrap();
+    file.set_contents(lines!["fn base() {}", "fn ai_one() {}".ai()]);
+    repo.stage_all_and_commit("AI one").unwrap();
+    file.set_contents(lines![
+        "fn base() {}",
+        "fn ai_one() {}".ai(),
+        "fn human() {}".human(),
+        "fn ai_two() {}".ai()
+    ]);
+    repo.stage_all_and_commit("AI two").unwrap();
+
+    repo.git(&["checkout", &main_branch]).unwrap();
+    squash_merge_without_git_ai(&repo, "feature");
+
+    let dry_run = repo
+        .git_ai(&["backfill", "--from", "feature", "--dry-run"])
+        .expect("dry run should find the squash commit");
+    assert!(
+        dry_run.contains("Would backfill"),
+        "unexpected output: {}",
+        dry_run
+    );
+
+    let output = repo
+        .git_ai(&["backfill", "--from", "feature"])
+        .expect("backfill should succeed");
+    assert!(
+        output.contains("Backfilled authorship"),
+        "unexpected output: {}",
+        output
+    );
+
+    let mut file = repo.filename("lib.rs");
+    file.assert_lines_and_blame(lines![
+        "fn base() {}".human(),
+        "fn ai_one() {}".ai(),
+        "fn human() {}".human(),
+        "fn ai_two() {}".ai()
+    ]);
+
+    // The squash commit now has a note, so there is nothing left to backfill
+    assert!(repo.git_ai(&["backfill", "--from", "feature"]).is_err());
+}
+
+#[test]
+fn test_backfill_squash_merge_from_range() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("app.py");
+    file.set_contents(lines!["print('base')"]);
+    let base = repo.stage_all_and_commit("Base").unwrap().commit_sha;
+    let main_branch = repo.current_branch();
+
+    repo.git(&["checkout", "-b", "feature"]).unwrap();
+    file.set_contents(lines!["print('base')", "print('ai')".ai()]);
+    let head = repo.stage_all_and_commit("AI").unwrap().commit_sha;
+
+    repo.git(&["checkout", &main_branch]).unwrap();
+    squash_merge_without_git_ai(&repo, "feature");
+    // Deleting the branch is what a forge usually does after merging
+    repo.git_og(&["branch", "-D", "feature"]).unwrap();
+
+    repo.git_ai(&["backfill", "--from", &format!("{}..{}", base, head)])
+        .expect("backfill from a range should succeed");
+
+    let mut file = repo.filename("app.py");
+    file.assert_lines_and_blame(lines!["print('base')".human(), "print('ai')".ai()]);
+}
+
+#[test]
+fn test_backfill_without_matching_squash_commit_fails() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("a.txt");
+    file.set_contents(lines!["base"]);
+    repo.stage_all_and_commit("Base").unwrap();
+    let main_branch = repo.current_branch();
+
+    repo.git(&["checkout", "-b", "feature"]).unwrap();
+    file.set_contents(lines!["base", "ai".ai()]);
+    repo.stage_all_and_commit("AI").unwrap();
+    repo.git(&["checkout", &main_branch]).unwrap();
+
+    let err = repo
+        .git_ai(&["backfill", "--from", "feature"])
+        .expect_err("no squash commit exists yet");
+    assert!(
+        err.contains("matches the squashed changes"),
+        "unexpected error: {}",
+        err
+    );
+}
+
+#[test]
+fn test_fetch_backfills_squash_merges_only_with_flag() {
+    let (local, _upstream) = TestRepo::new_with_remote();
+    let mut file = local.filename("lib.rs");
+    file.set_contents(lines!["fn base() {}"]);
+    local.stage_all_and_commit("Base").unwrap();
+    local.git(&["push", "-u", "origin", "main"]).unwrap();
+    local
+        .git_og(&["remote", "set-head", "origin", "main"])
+        .unwrap();
+
+    local.git(&["checkout", "-b", "feature"]).unwrap();
+    file.set_contents(lines!["fn base() {}", "fn ai() {}".ai()]);
+    local.stage_all_and_commit("AI").unwrap();
+
+    // Squash-merge on the "forge": land the squash commit on origin/main without git-ai
+    local.git_og(&["checkout", "-b", "forge", "main"]).unwrap();
+    squash_merge_without_git_ai(&local, "feature");
+    let squash_sha = local
+        .git_og(&["rev-parse", "HEAD"])
+        .unwrap()
+        .trim()
+        .to_string();
+    local.git_og(&["push", "origin", "forge:main"]).unwrap();
+    local
+        .git_og(&["update-ref", "-d", "refs/remotes/origin/main"])
+        .unwrap();
+    local.git_og(&["checkout", "feature"]).unwrap();
+    local.git_og(&["branch", "-D", "forge"]).unwrap();
+
+    let has_note = || {
+        local
+            .git_og(&["notes", "--ref=ai", "show", &squash_sha])
+            .is_ok()
+    };
+
+    local.git(&["fetch", "origin"]).unwrap();
+    assert!(
+        !has_note(),
+        "fetch should not scan for squash merges by default"
+    );
+
+    local
+        .git_with_env(
+            &["fetch", "origin"],
+            &[("GIT_AI_FETCH_SQUASH_BACKFILL", "true")],
+            None,
+        )
+        .unwrap();
+    assert!(
+        has_note(),
+        "fetch with the flag should backfill the squash commit"
+    );
+}
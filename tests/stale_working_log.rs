@@ -0,0 +1,110 @@
+#[macro_use]
+mod repos;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+use std::time::{Duration, SystemTime};
+
+fn set_modified_days_ahead(repo: &TestRepo, file: &str, days: u64) {
+    std::fs::File::options()
+        .write(true)
+        .open(repo.path().join(file))
+        .unwrap()
+        .set_modified(SystemTime::now() + Duration::from_secs(days * 24 * 3600))
+        .unwrap();
+}
+
+/// The AI adds a line, then three days later the file is edited again, keeping it.
+fn edit_long_after_ai_checkpoint(repo: &TestRepo) {
+    let mut file = repo.filename("file.txt");
+    file.set_contents(lines!["Line 1", "Line 2", "Line 3"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    file.insert_at(1, lines!["AI line".ai()]);
+    file.replace_at(3, "Line 3 edited".human());
+    set_modified_days_ahead(repo, "file.txt", 3);
+}
+
+/// The AI adds a line, and three days later that session is reverted by hand.
+fn revert_long_after_ai_checkpoint(repo: &TestRepo) {
+    let mut file = repo.filename("file.txt");
+    file.set_contents(lines!["Line 1", "Line 2", "Line 3"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    file.insert_at(1, lines!["AI line".ai()]);
+    std::fs::write(
+        repo.path().join("file.txt"),
+        "Line 1\nLine 2\nLine 3 edited\n",
+    )
+    .unwrap();
+    set_modified_days_ahead(repo, "file.txt", 3);
+}
+
+#[test]
+fn test_stale_checkpoint_warns_by_default() {
+    let repo = TestRepo::new();
+    revert_long_after_ai_checkpoint(&repo);
+
+    let commit = repo.stage_all_and_commit("Later edit").unwrap();
+    assert!(
+        commit.stdout.contains("may be stale"),
+        "expected stale warning, got: {}",
+        commit.stdout
+    );
+    assert!(!commit.stdout.contains("excluding them"));
+
+    let mut file = repo.filename("file.txt");
+    file.assert_lines_and_blame(lines![
+        "Line 1".human(),
+        "Line 2".human(),
+        "Line 3 edited".human(),
+    ]);
+}
+
+#[test]
+fn test_stale_checkpoint_excluded_when_configured() {
+    let mut repo = TestRepo::new();
+    repo.patch_git_ai_config(|patch| {
+        patch.exclude_stale_checkpoints = Some(true);
+    });
+    revert_long_after_ai_checkpoint(&repo);
+
+    let commit = repo.stage_all_and_commit("Later edit").unwrap();
+    assert!(commit.stdout.contains("excluding them from attribution"));
+    assert!(commit.authorship_log.attestations.is_empty());
+}
+
+#[test]
+fn test_old_checkpoint_whose_ai_lines_remain_is_not_stale() {
+    let mut repo = TestRepo::new();
+    repo.patch_git_ai_config(|patch| {
+        patch.exclude_stale_checkpoints = Some(true);
+    });
+    edit_long_after_ai_checkpoint(&repo);
+
+    let commit = repo.stage_all_and_commit("Later edit").unwrap();
+    assert!(
+        !commit.stdout.contains("may be stale"),
+        "unexpected stale warning: {}",
+        commit.stdout
+    );
+
+    let mut file = repo.filename("file.txt");
+    file.assert_lines_and_blame(lines![
+        "Line 1".human(),
+        "AI line".ai(),
+        "Line 2".human(),
+        "Line 3 edited".human(),
+    ]);
+}
+
+#[test]
+fn test_recent_checkpoint_is_not_stale() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("file.txt");
+    file.set_contents(lines!["Line 1", "Line 2"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    file.insert_at(1, lines!["AI line".ai()]);
+    let commit = repo.stage_all_and_commit("AI edit").unwrap();
+    assert!(!commit.stdout.contains("may be stale"));
+}
@@ -947,3 +947,56 @@ fn test_stash_apply_reset_apply_again() {
         "Expected AI prompts in authorship log after multiple apply/reset cycles"
     );
 }
+
+#[test]
+fn test_stash_pop_after_head_shifts_lines() {
+    // Stashed attributions are remapped onto the content the stash is popped into,
+    // not replayed at the line numbers they had when the stash was created
+    let repo = TestRepo::new();
+
+    let mut example = repo.filename("example.txt");
+    example.set_contents(vec!["fn a() {}".human(), "fn b() {}".human()]);
+    repo.stage_all_and_commit("initial commit")
+        .expect("commit should succeed");
+
+    example.set_contents(vec![
+        "fn a() {}".human(),
+        "fn b() {}".human(),
+        "fn ai_one() {}".ai(),
+        "fn ai_two() {}".ai(),
+    ]);
+
+    repo.git(&["stash"]).expect("stash should succeed");
+
+    // Move HEAD with a non-conflicting change that shifts every stashed line down
+    example.set_contents(vec![
+        "// header".human(),
+        "// more header".human(),
+        "".human(),
+        "fn a() {}".human(),
+        "fn b() {}".human(),
+    ]);
+    repo.stage_all_and_commit("add header")
+        .expect("commit should succeed");
+
+    repo.git(&["stash", "pop"])
+        .expect("stash pop should succeed");
+
+    let commit = repo
+        .stage_all_and_commit("apply stashed changes")
+        .expect("commit should succeed");
+
+    example.assert_lines_and_blame(vec![
+        "// header".human(),
+        "// more header".human(),
+        "".human(),
+        "fn a() {}".human(),
+        "fn b() {}".human(),
+        "fn ai_one() {}".ai(),
+        "fn ai_two() {}".ai(),
+    ]);
+    assert!(
+        !commit.authorship_log.metadata.prompts.is_empty(),
+        "Expected AI prompts in authorship log"
+    );
+}
@@ -243,6 +243,9 @@ fn test_markdown_stats_deletion_only() {
         git_diff_deleted_lines: 5,
         git_diff_added_lines: 0,
         tool_model_breakdown: BTreeMap::new(),
+        ai_generated_assets: Vec::new(),
+        identity_category: None,
+        bot_additions: 0,
     };
 
     let markdown = write_stats_to_markdown(&stats);
@@ -266,6 +269,9 @@ fn test_markdown_stats_all_human() {
         git_diff_deleted_lines: 0,
         git_diff_added_lines: 10,
         tool_model_breakdown: BTreeMap::new(),
+        ai_generated_assets: Vec::new(),
+        identity_category: None,
+        bot_additions: 0,
     };
 
     let markdown = write_stats_to_markdown(&stats);
@@ -289,6 +295,9 @@ fn test_markdown_stats_all_ai() {
         git_diff_deleted_lines: 0,
         git_diff_added_lines: 15,
         tool_model_breakdown: BTreeMap::new(),
+        ai_generated_assets: Vec::new(),
+        identity_category: None,
+        bot_additions: 0,
     };
 
     let markdown = write_stats_to_markdown(&stats);
@@ -312,6 +321,9 @@ fn test_markdown_stats_mixed() {
         git_diff_deleted_lines: 5,
         git_diff_added_lines: 30,
         tool_model_breakdown: BTreeMap::new(),
+        ai_generated_assets: Vec::new(),
+        identity_category: None,
+        bot_additions: 0,
     };
 
     let markdown = write_stats_to_markdown(&stats);
@@ -335,6 +347,9 @@ fn test_markdown_stats_no_mixed() {
         git_diff_deleted_lines: 0,
         git_diff_added_lines: 20,
         tool_model_breakdown: BTreeMap::new(),
+        ai_generated_assets: Vec::new(),
+        identity_category: None,
+        bot_additions: 0,
     };
 
     let markdown = write_stats_to_markdown(&stats);
@@ -359,6 +374,9 @@ fn test_markdown_stats_minimal_human() {
         git_diff_deleted_lines: 0,
         git_diff_added_lines: 100,
         tool_model_breakdown: BTreeMap::new(),
+        ai_generated_assets: Vec::new(),
+        identity_category: None,
+        bot_additions: 0,
     };
 
     let markdown = write_stats_to_markdown(&stats);
@@ -394,6 +412,9 @@ fn test_markdown_stats_formatting() {
         time_waiting_for_ai: 25,
         git_diff_deleted_lines: 2,
         git_diff_added_lines: 13,
+        ai_generated_assets: Vec::new(),
+        identity_category: None,
+        bot_additions: 0,
         tool_model_breakdown,
     };
 
@@ -620,3 +641,296 @@ fn test_post_commit_large_ignored_files_do_not_trigger_skip_warning() {
     assert_eq!(stats.ai_additions, 0);
     assert_eq!(stats.human_additions, 0);
 }
+
+#[test]
+fn test_whole_file_binary_asset_is_attributed_to_ai() {
+    let repo = TestRepo::new();
+    repo.filename("README.md").set_contents(lines!["# Repo"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    std::fs::write(
+        repo.path().join("logo.png"),
+        [0x89, b'P', b'N', b'G', 0x00, 0x01, 0x02, 0x00],
+    )
+    .unwrap();
+    std::fs::write(repo.path().join("texture.bin"), [0x00, 0xFF, 0x00]).unwrap();
+    repo.git_ai(&[
+        "checkpoint",
+        "mock_ai",
+        "--whole-file",
+        "logo.png",
+        "texture.bin",
+    ])
+    .unwrap();
+
+    // Only commit one of the two assets; the other must carry over to the next commit.
+    repo.git(&["add", "logo.png"]).unwrap();
+    let commit = repo.commit("Add logo").unwrap();
+    let assets = &commit.authorship_log.metadata.assets;
+    assert_eq!(assets.len(), 1);
+    let prompt_hash = assets.get("logo.png").expect("logo.png should be an asset");
+    assert!(
+        commit
+            .authorship_log
+            .metadata
+            .prompts
+            .contains_key(prompt_hash)
+    );
+    assert!(commit.authorship_log.attestations.is_empty());
+
+    let stats = stats_from_args(&repo, &["stats", "HEAD", "--json"]);
+    assert_eq!(stats.ai_generated_assets, vec!["logo.png".to_string()]);
+
+    repo.git(&["add", "texture.bin"]).unwrap();
+    let commit = repo.commit("Add texture").unwrap();
+    assert_eq!(
+        commit
+            .authorship_log
+            .metadata
+            .assets
+            .keys()
+            .collect::<Vec<_>>(),
+        vec!["texture.bin"]
+    );
+}
+
+#[test]
+fn test_stats_report_aggregates_across_range() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("app.py");
+    file.set_contents(lines!["import os".human(), "print('hi')".human()]);
+    repo.stage_all_and_commit("Initial").unwrap();
+
+    file.insert_at(
+        1,
+        lines!["def a():".ai(), "    return 1".ai(), "# note".human()],
+    );
+    repo.stage_all_and_commit("Add function").unwrap();
+
+    let mut other = repo.filename("lib.py");
+    other.set_contents(lines!["x = 1".ai()]);
+    repo.stage_all_and_commit("Add lib").unwrap();
+
+    let raw = repo
+        .git_ai(&["stats", "--by-file", "--json", "HEAD"])
+        .unwrap();
+    let report: serde_json::Value = serde_json::from_str(&extract_json_object(&raw)).unwrap();
+    assert_eq!(report["total_commits"], 3);
+    assert_eq!(report["totals"]["ai_lines"], 3);
+    assert_eq!(report["totals"]["human_lines"], 3);
+    assert_eq!(report["totals"]["total_lines"], 6);
+
+    let rows = report["rows"].as_array().unwrap();
+    let app = rows.iter().find(|r| r["key"] == "app.py").unwrap();
+    assert_eq!(app["commits"], 2);
+    assert_eq!(app["ai_lines"], 2);
+    let lib = rows.iter().find(|r| r["key"] == "lib.py").unwrap();
+    assert_eq!(lib["ai_lines"], 1);
+    assert_eq!(lib["human_lines"], 0);
+
+    // Excluding the initial commit only counts the later two.
+    let raw = repo
+        .git_ai(&["stats", "--by-model", "--json", "HEAD~2..HEAD"])
+        .unwrap();
+    let report: serde_json::Value = serde_json::from_str(&extract_json_object(&raw)).unwrap();
+    assert_eq!(report["total_commits"], 2);
+    let rows = report["rows"].as_array().unwrap();
+    let human = rows.iter().find(|r| r["key"] == "human").unwrap();
+    assert_eq!(human["human_lines"], 1);
+    let ai_total: u64 = rows
+        .iter()
+        .filter(|r| r["key"] != "human")
+        .map(|r| r["ai_lines"].as_u64().unwrap())
+        .sum();
+    assert_eq!(ai_total, 3);
+}
+
+#[test]
+fn test_stats_report_csv_and_author_filter() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("main.rs");
+    file.set_contents(lines!["fn main() {}".ai()]);
+    repo.stage_all_and_commit("Initial").unwrap();
+
+    let csv = repo.git_ai(&["stats", "--by-author", "--csv"]).unwrap();
+    let lines: Vec<&str> = csv.lines().filter(|l| l.contains(',')).collect();
+    assert_eq!(
+        lines[0],
+        "group,commits,ai_lines,human_lines,total_lines,ai_percent"
+    );
+    assert_eq!(lines.last().unwrap(), &"total,1,1,0,1,100.0");
+
+    let raw = repo
+        .git_ai(&["stats", "--author", "nobody-matches-this", "--json"])
+        .unwrap();
+    let report: serde_json::Value = serde_json::from_str(&extract_json_object(&raw)).unwrap();
+    assert_eq!(report["total_commits"], 0);
+    assert_eq!(report["totals"]["total_lines"], 0);
+}
+
+#[test]
+fn test_stats_line_filters_exclude_blank_comment_and_short_lines() {
+    let repo = TestRepo::new();
+    let mut readme = repo.filename("README.md");
+    readme.set_contents(lines!["# Project"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let mut file = repo.filename("lib.rs");
+    file.set_contents(lines![
+        "// Helpers".ai(),
+        "fn add(a: i32, b: i32) -> i32 {".ai(),
+        "    a + b".ai(),
+        "}".ai(),
+        "".ai(),
+        "fn sub(a: i32, b: i32) -> i32 { a - b }".human(),
+    ]);
+    repo.stage_all_and_commit("Add helpers").unwrap();
+
+    let raw = stats_from_args(&repo, &["stats", "--json"]);
+    assert_eq!(raw.git_diff_added_lines, 6);
+
+    let filtered = stats_from_args(
+        &repo,
+        &[
+            "stats",
+            "--exclude-blank",
+            "--exclude-comments",
+            "--min-line-length=2",
+            "--json",
+        ],
+    );
+    assert_eq!(filtered.git_diff_added_lines, 3);
+    assert_eq!(filtered.ai_additions, 2);
+    assert_eq!(filtered.human_additions, 1);
+
+    let filter_args = [
+        "--exclude-blank",
+        "--exclude-comments",
+        "--min-line-length=2",
+        "--json",
+    ];
+    let mut range_args = vec!["stats"];
+    range_args.extend_from_slice(&filter_args);
+    range_args.push("HEAD~1..HEAD");
+    let raw = repo.git_ai(&range_args).unwrap();
+    let range: serde_json::Value = serde_json::from_str(&extract_json_object(&raw)).unwrap();
+    assert_eq!(range["range_stats"]["git_diff_added_lines"], 3);
+    assert_eq!(range["range_stats"]["ai_additions"], 2);
+    assert_eq!(range["range_stats"]["human_additions"], 1);
+
+    let mut report_args = vec!["stats", "--by-file"];
+    report_args.extend_from_slice(&filter_args);
+    report_args.push("HEAD~1..HEAD");
+    let raw = repo.git_ai(&report_args).unwrap();
+    let report: serde_json::Value = serde_json::from_str(&extract_json_object(&raw)).unwrap();
+    assert_eq!(report["totals"]["ai_lines"], 2);
+    assert_eq!(report["totals"]["human_lines"], 1);
+    assert_eq!(report["totals"]["total_lines"], 3);
+}
+
+#[test]
+fn test_stats_line_filters_reject_merge_commits() {
+    let repo = TestRepo::new();
+    let mut readme = repo.filename("README.md");
+    readme.set_contents(lines!["# Project"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+    let main_branch = repo.current_branch();
+
+    repo.git(&["checkout", "-b", "feature"]).unwrap();
+    let mut feature = repo.filename("feature.txt");
+    feature.set_contents(lines!["feature".ai()]);
+    repo.stage_all_and_commit("Add feature").unwrap();
+
+    repo.git(&["checkout", &main_branch]).unwrap();
+    let mut other = repo.filename("other.txt");
+    other.set_contents(lines!["other"]);
+    repo.stage_all_and_commit("Add other").unwrap();
+    repo.git(&["merge", "--no-ff", "feature", "-m", "Merge feature"])
+        .unwrap();
+
+    let err = repo
+        .git_ai(&["stats", "--exclude-blank", "--json"])
+        .unwrap_err();
+    assert!(err.contains("merge commit"), "{}", err);
+}
+
+fn commit_with_model(repo: &TestRepo, file: &str, model: &str, conversation_id: &str) {
+    std::fs::write(repo.path().join(file), format!("written by {}\n", model)).unwrap();
+    let hook_input = serde_json::json!({
+        "type": "ai_agent",
+        "repo_working_dir": repo.path().to_str().unwrap(),
+        "edited_filepaths": [file],
+        "transcript": { "messages": [] },
+        "agent_name": "test-agent",
+        "model": model,
+        "conversation_id": conversation_id,
+    });
+    repo.git_ai(&[
+        "checkpoint",
+        "agent-v1",
+        "--hook-input",
+        &hook_input.to_string(),
+    ])
+    .unwrap();
+    repo.stage_all_and_commit(&format!("Add {}", file)).unwrap();
+}
+
+#[test]
+fn test_stats_by_model_groups_model_versions() {
+    let repo = TestRepo::new();
+    let mut readme = repo.filename("README.md");
+    readme.set_contents(lines!["# Project".human()]);
+    repo.stage_all_and_commit("Initial").unwrap();
+
+    commit_with_model(&repo, "a.txt", "gpt-4o-2024-08-06", "conversation-a");
+    commit_with_model(&repo, "b.txt", "GPT-4o", "conversation-b");
+
+    let model_rows = |extra: &[&str]| -> Vec<(String, u64)> {
+        let mut args = vec!["stats", "--by-model", "--json"];
+        args.extend_from_slice(extra);
+        args.push("HEAD~2..HEAD");
+        let raw = repo.git_ai(&args).unwrap();
+        let report: serde_json::Value = serde_json::from_str(&extract_json_object(&raw)).unwrap();
+        let mut rows: Vec<(String, u64)> = report["rows"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .filter(|r| r["key"] != "human")
+            .map(|r| {
+                (
+                    r["key"].as_str().unwrap().to_string(),
+                    r["ai_lines"].as_u64().unwrap(),
+                )
+            })
+            .collect();
+        rows.sort();
+        rows
+    };
+
+    assert_eq!(model_rows(&[]), vec![("test-agent::gpt-4o".to_string(), 2)]);
+    assert_eq!(
+        model_rows(&["--raw-models"]),
+        vec![
+            ("test-agent::GPT-4o".to_string(), 1),
+            ("test-agent::gpt-4o-2024-08-06".to_string(), 1),
+        ]
+    );
+
+    let breakdown_keys = |extra: &[&str]| -> Vec<String> {
+        let mut args = vec!["stats", "--json"];
+        args.extend_from_slice(extra);
+        let raw = repo.git_ai(&args).unwrap();
+        let stats: serde_json::Value = serde_json::from_str(&extract_json_object(&raw)).unwrap();
+        stats["tool_model_breakdown"]
+            .as_object()
+            .unwrap()
+            .keys()
+            .cloned()
+            .collect()
+    };
+    assert_eq!(breakdown_keys(&[]), vec!["test-agent::gpt-4o".to_string()]);
+    assert_eq!(
+        breakdown_keys(&["--raw-models"]),
+        vec!["test-agent::GPT-4o".to_string()]
+    );
+}
@@ -20,6 +20,7 @@ fn make_switch_invocation(args: &[&str]) -> ParsedGitInvocation {
         command_args: args.iter().map(|s| s.to_string()).collect(),
         saw_end_of_opts: false,
         is_help: false,
+        no_ai_hooks: false,
     }
 }
 
@@ -46,7 +47,7 @@ fn test_pre_switch_hook_normal() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     let parsed_args = make_switch_invocation(&["main"]);
@@ -83,7 +84,7 @@ fn test_pre_switch_hook_with_merge_flag() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     let parsed_args = make_switch_invocation(&["--merge", "main"]);
@@ -114,7 +115,7 @@ fn test_pre_switch_hook_merge_without_changes() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     let parsed_args = make_switch_invocation(&["--merge", "main"]);
@@ -149,7 +150,7 @@ fn test_pre_switch_hook_merge_short_flag() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     let parsed_args = make_switch_invocation(&["-m", "main"]);
@@ -191,7 +192,7 @@ fn test_post_switch_hook_success() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     let exit_status = std::process::Command::new("true").status().unwrap();
@@ -221,7 +222,7 @@ fn test_post_switch_hook_failed() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     let exit_status = std::process::Command::new("false")
@@ -259,7 +260,7 @@ fn test_post_switch_hook_head_unchanged() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     let exit_status = std::process::Command::new("true").status().unwrap();
@@ -308,7 +309,7 @@ fn test_post_switch_hook_force_switch() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     let exit_status = std::process::Command::new("true").status().unwrap();
@@ -347,7 +348,7 @@ fn test_post_switch_hook_force_short_flag() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     let exit_status = std::process::Command::new("true").status().unwrap();
@@ -386,7 +387,7 @@ fn test_post_switch_hook_discard_changes_flag() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     let exit_status = std::process::Command::new("true").status().unwrap();
@@ -429,7 +430,7 @@ fn test_post_switch_hook_with_merge() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     // In real scenario, pre_switch_hook would populate this
@@ -589,7 +590,7 @@ fn test_switch_normal_flow() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     let parsed_args = make_switch_invocation(&["main"]);
@@ -637,7 +638,7 @@ fn test_switch_force_flow() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     let parsed_args = make_switch_invocation(&["--force", "main"]);
@@ -674,7 +675,7 @@ fn test_switch_new_branch_creation() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     let parsed_args = make_switch_invocation(&["-c", "new-branch"]);
@@ -721,7 +722,7 @@ fn test_switch_between_multiple_branches() {
         rebase_onto: None,
         fetch_authorship_handle: None,
         stash_sha: None,
-        push_authorship_handle: None,
+        push_notes_plan: None,
         stashed_va: None,
     };
     let parsed_args = make_switch_invocation(&["branch1"]);
@@ -0,0 +1,65 @@
+#[macro_use]
+mod repos;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+use std::fs;
+
+#[test]
+fn test_with_attributes_command_changes_to_tool() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("notes.txt");
+    file.set_contents(lines!["Line 1", "Line 2"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    // A human edit made before the command stays human
+    fs::write(
+        repo.path().join("notes.txt"),
+        "Line 1\nHuman line\nLine 2\n",
+    )
+    .unwrap();
+
+    repo.git_ai(&[
+        "with",
+        "--tool",
+        "codegen",
+        "--model",
+        "gen-1",
+        "--",
+        "sh",
+        "-c",
+        "printf 'generated 1\\ngenerated 2\\n' > gen.txt",
+    ])
+    .unwrap();
+    repo.stage_all_and_commit("Generate").unwrap();
+
+    file.assert_lines_and_blame(lines![
+        "Line 1".human(),
+        "Human line".human(),
+        "Line 2".human()
+    ]);
+
+    let output = repo
+        .git_ai(&["blame", "--format", "json", "gen.txt"])
+        .unwrap();
+    let records: serde_json::Value = serde_json::from_str(&output).unwrap();
+    for record in records.as_array().unwrap() {
+        assert_eq!(record["ai_tool"], "codegen");
+        assert_eq!(record["ai_model"], "gen-1");
+    }
+}
+
+#[test]
+fn test_with_exits_with_command_status() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("file.txt");
+    file.set_contents(lines!["Line 1"]);
+    repo.stage_all_and_commit("Initial commit").unwrap();
+
+    let err = repo
+        .git_ai(&["with", "--tool", "codegen", "--", "sh", "-c", "exit 3"])
+        .unwrap_err();
+    assert!(!err.contains("Usage"), "{}", err);
+
+    let err = repo.git_ai(&["with", "--", "true"]).unwrap_err();
+    assert!(err.contains("Usage"), "{}", err);
+}
@@ -0,0 +1,64 @@
+#[macro_use]
+mod repos;
+use repos::test_file::ExpectedLineExt;
+use repos::test_repo::TestRepo;
+use std::fs;
+
+fn add_worktree(repo: &TestRepo, branch: &str) -> std::path::PathBuf {
+    let dir = repo.path().parent().unwrap().join(format!(
+        "{}-{}",
+        repo.path().file_name().unwrap().to_string_lossy(),
+        branch
+    ));
+    let _ = fs::remove_dir_all(&dir);
+    repo.git_og(&["worktree", "add", "-b", branch, dir.to_str().unwrap()])
+        .expect("worktree add should succeed");
+    dir
+}
+
+#[test]
+fn test_worktree_checkpoints_do_not_bleed_into_main_worktree() {
+    let repo = TestRepo::new();
+    let mut file = repo.filename("shared.py");
+    file.set_contents(lines!["print('base')"]);
+    repo.stage_all_and_commit("Base").unwrap();
+
+    // Both worktrees sit on the same commit, so a shared working log would collide
+    let dir = add_worktree(&repo, "feature");
+    fs::write(dir.join("shared.py"), "print('base')\nprint('ai')\n").unwrap();
+    repo.git_ai_from_working_dir(&dir, &["checkpoint", "mock_ai", "shared.py"])
+        .expect("checkpoint in worktree should succeed");
+
+    assert!(
+        repo.current_working_logs()
+            .read_all_checkpoints()
+            .unwrap()
+            .is_empty(),
+        "main worktree should not see the linked worktree's checkpoints"
+    );
+
+    // The same edit made by a human in the main worktree stays human
+    let mut file = repo.filename("shared.py");
+    file.set_contents(lines!["print('base')", "print('ai')"]);
+    repo.stage_all_and_commit("Human edit").unwrap();
+    file.assert_lines_and_blame(lines!["print('base')".human(), "print('ai')".human()]);
+
+    // And the worktree commit keeps its AI attribution
+    repo.git_from_working_dir(&dir, &["add", "-A"]).unwrap();
+    repo.git_from_working_dir(&dir, &["commit", "-m", "AI edit"])
+        .unwrap();
+    let blame = repo
+        .git_ai_from_working_dir(&dir, &["blame", "shared.py"])
+        .unwrap();
+    let ai_line = blame
+        .lines()
+        .find(|line| line.contains("print('ai')"))
+        .unwrap();
+    assert!(
+        ai_line.contains("mock_ai"),
+        "unexpected blame line: {}",
+        ai_line
+    );
+
+    let _ = repo.git_og(&["worktree", "remove", "--force", dir.to_str().unwrap()]);
+}